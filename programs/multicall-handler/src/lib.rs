@@ -13,8 +13,15 @@ pub mod multicall_handler {
     use super::*;
 
     // Handler to receive AcrossV3 message formatted as serialized message compiled instructions. When deserialized,
-    // these are matched with the passed accounts and executed as CPIs.
-    pub fn handle_v3_across_message(ctx: Context<HandleV3AcrossMessage>, message: Vec<u8>) -> Result<()> {
+    // these are matched with the passed accounts and executed as CPIs. The sent token, amount and relayer are not used
+    // as the compiled instructions reference the required accounts and amounts directly.
+    pub fn handle_v3_across_message(
+        ctx: Context<HandleV3AcrossMessage>,
+        _token: Pubkey,
+        _amount: u64,
+        _relayer: Pubkey,
+        message: Vec<u8>,
+    ) -> Result<()> {
         // Some instructions might require being signed by handler PDA.
         let (handler_signer, bump) = Pubkey::find_program_address(&[b"handler_signer"], &crate::ID);
         let mut use_handler_signer = false;
@@ -0,0 +1,615 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, system_program},
+    InstructionData,
+};
+use anchor_spl::{associated_token, token, token_2022};
+
+use crate::{
+    accounts,
+    constants::{MEMO_PROGRAM_ID, NATIVE_MINT},
+    error::SvmError,
+    instruction,
+    utils::{
+        derive_event_authority, get_fill_index_bucket, get_fill_index_position, get_v3_relay_hash, ExtraArgsV1,
+        ExtraArgsV2, ExtraArgsV5, ExtraArgsV6, ARGS_VERSION_V0, ARGS_VERSION_V1, ARGS_VERSION_V2, ARGS_VERSION_V5,
+        ARGS_VERSION_V6,
+    },
+};
+
+pub use crate::{
+    common::V3RelayData,
+    instructions::{HandleReceiveMessageParams, RelayerRefundLeaf, RouteUpdate, V3SlowFill},
+    state::{
+        ClaimLiability, DeliveryMode, ExecuteRelayerRefundLeafParams, ExecutionApprovalThreshold, FillStatus,
+        FillStatusAccount, RootBundle, Route, SettlementMode, SlowFillLiability, State, VaultLiability,
+    },
+};
+
+// Instruction builders for off-chain Rust clients, so that this crate is the single source of truth for the account
+// ordering and PDA derivation of the core instructions. Optional accounts that depend on the state or recipient
+// configuration cannot be derived from the instruction arguments alone and are selected through the options.
+
+// State level configuration shared by all builders.
+#[derive(Clone, Default)]
+pub struct SpokeConfig {
+    pub seed: u64,                           // State seed, 0 on mainnet.
+    pub chain_id: u64,                       // Chain ID of the state, used to derive relay hashes.
+    pub compliance: bool,                    // State has a compliance authority.
+    pub event_commitment_epoch: Option<u32>, // Current epoch when the state enables event commitments.
+    pub fill_index: bool,                    // State enables the fill index.
+    pub vault_outflow_rate_limits: bool,     // State enables vault outflow rate limits.
+    pub event_forwarder: Option<Pubkey>,     // Event forwarder program of the state.
+    pub permissioned_mode: bool,             // State enables permissioned mode.
+    pub strict_chain_aliases: bool,          // State requires registered chain aliases.
+}
+
+#[derive(Clone, Default)]
+pub struct FillOptions {
+    pub spoke: SpokeConfig,
+    pub mint_owner: Pubkey,                      // Owner of the output mint account, selects the token program.
+    pub repayment_chain_id: u64,                 // Chain where the relayer wants to be refunded.
+    pub repayment_address: Pubkey,               // Address of the relayer on the repayment chain.
+    pub relayer_token_account: Option<Pubkey>,   // Defaults to the relayer ATA.
+    pub recipient_token_account: Option<Pubkey>, // Defaults to the recipient ATA, used unless delivered elsewhere.
+    pub delivery_mode: Option<DeliveryMode>,     // Delivery preference set by the recipient.
+    pub memo: Option<Vec<u8>>,                   // Payout memo, encoded as version 1 extra args.
+    pub remaining_accounts: Vec<AccountMeta>,    // Accounts of the Across+ message handler.
+    pub route: bool,                             // Route of the output token has a decimal shift or fee floor.
+    pub create_repayment_token_account: bool,    // Create the repayment address ATA, encoded as version 2 extra args.
+    pub create_recipient_token_account: bool,    // Create the recipient ATA, encoded as version 6 extra args.
+    pub relayer_stats: bool,                     // Track the stats of the exclusive relayer.
+    pub payer: Option<Pubkey>,                   // Sponsor paying the rent, defaults to the relayer.
+}
+
+#[derive(Clone, Default)]
+pub struct RequestSlowFillOptions {
+    pub spoke: SpokeConfig,
+    pub requester_activity: bool, // State enables slow fill rate limits.
+    pub relayer_stats: bool,      // Track the stats of the exclusive relayer.
+    pub payer: Option<Pubkey>,    // Sponsor paying the rent, defaults to the signer.
+}
+
+#[derive(Clone, Default)]
+pub struct ExecuteSlowRelayLeafOptions {
+    pub spoke: SpokeConfig,
+    pub mint_owner: Pubkey,                      // Owner of the output mint account, selects the token program.
+    pub settlement_mode: Option<SettlementMode>, // Settlement mode of the route, None when there is no route.
+    pub recipient_token_account: Option<Pubkey>, // Defaults to the recipient ATA, used unless delivered elsewhere.
+    pub delivery_mode: Option<DeliveryMode>,     // Delivery preference set by the recipient.
+    pub fee_vault: bool,                         // State charges a protocol fee.
+    pub liquidity_pool: bool,                    // State enables liquidity pools with a fee.
+    pub memo: Option<Vec<u8>>,                   // Payout memo, encoded as version 1 extra args.
+    pub remaining_accounts: Vec<AccountMeta>,    // Accounts of the Across+ message handler.
+    pub execution_approval: bool,                // Leaf exceeds the execution approval threshold of its mint.
+    pub vault_liability: bool,                   // Vault liability of the mint was created by a deposit.
+}
+
+#[derive(Clone)]
+pub struct DepositArgs {
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub input_token: Pubkey,
+    pub output_token: Pubkey,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub destination_chain_id: u64,
+    pub exclusive_relayer: Pubkey,
+    pub quote_timestamp: u32,
+    pub fill_deadline: u32,
+    pub exclusivity_parameter: u32,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Default)]
+pub struct DepositOptions {
+    pub spoke: SpokeConfig,
+    pub mint_owner: Pubkey,              // Owner of the input mint account, selects the token program.
+    pub revoke_after: bool,              // Revoke any remaining state delegation of the depositor token account.
+    pub canonical_chain_id: Option<u64>, // Canonical chain ID when the destination chain ID is an alias.
+    pub depositor_nonce: Option<u64>,    // Next nonce of depositors that initialized a depositor nonce PDA.
+}
+
+pub fn build_fill_ix(relay_data: &V3RelayData, relayer: &Pubkey, options: &FillOptions) -> Result<Instruction> {
+    let spoke = &options.spoke;
+    let state = get_state_address(spoke.seed);
+    let mint = relay_data.output_token;
+    let token_program = get_token_program(&options.mint_owner)?;
+    let relay_hash = get_v3_relay_hash(relay_data, spoke.chain_id);
+    let (recipient_token_account, delivery_preference, recipient_escrow) =
+        get_delivery_accounts(spoke.seed, &relay_data.recipient, &mint, &token_program, &options.delivery_mode);
+    let (native_unwrap_account, native_recipient) =
+        get_native_unwrap_accounts(spoke.seed, relayer, &relay_data.recipient, &mint, &options.delivery_mode);
+    let (args_version, extra) = match (options.create_recipient_token_account, options.create_repayment_token_account) {
+        (true, _) => encode_fill_extra_args_v6(&options.memo, options.create_repayment_token_account)?,
+        (false, true) => encode_fill_extra_args_v2(&options.memo)?,
+        (false, false) => encode_payout_memo_args(&options.memo)?,
+    };
+    let (repayment_address_account, repayment_token_account) = match options.create_repayment_token_account {
+        true => (
+            Some(options.repayment_address),
+            Some(get_associated_token_address(&options.repayment_address, &mint, &token_program)),
+        ),
+        false => (None, None),
+    };
+    // The created ATA replaces the recipient token account, which is only passed when the payout goes to the ATA.
+    let (recipient_token_account, recipient_address_account, recipient_ata) =
+        match (options.create_recipient_token_account, recipient_token_account) {
+            (true, Some(ata)) => (None, Some(relay_data.recipient), Some(ata)),
+            (_, recipient_token_account) => (recipient_token_account, None, None),
+        };
+
+    let mut account_metas = accounts::FillV3Relay {
+        signer: *relayer,
+        instruction_params: None,
+        state,
+        vault_registry: get_vault_registry_address(spoke.seed, &mint),
+        mint,
+        relayer_token_account: options
+            .relayer_token_account
+            .unwrap_or_else(|| get_associated_token_address(relayer, &mint, &token_program)),
+        recipient_token_account: recipient_token_account.map(|ata| options.recipient_token_account.unwrap_or(ata)),
+        delivery_preference,
+        recipient_escrow,
+        native_unwrap_account,
+        native_recipient,
+        fill_status: get_fill_status_address(&relay_hash),
+        slow_fill_liability: Some(get_slow_fill_liability_address(spoke.seed, &mint)),
+        fill_volume: get_fill_volume_address(spoke.seed, &mint),
+        message_schema: get_message_schema_address(&relay_data.recipient),
+        depositor_compliance: get_compliance_address(spoke, &relay_data.depositor),
+        recipient_compliance: get_compliance_address(spoke, &relay_data.recipient),
+        allowlist_entry: get_allowlist_entry_address(spoke, relayer),
+        route: options.route.then(|| get_route_address(spoke.seed, &mint, relay_data.origin_chain_id)),
+        repayment_address_account,
+        repayment_token_account,
+        event_commitment: get_event_commitment_address(spoke),
+        fill_index_shard: get_fill_index_shard_address(spoke, relay_data),
+        origin_chain_alias: get_origin_chain_alias_address(spoke, relay_data),
+        relayer_stats: get_relayer_stats_address(spoke, relay_data, options.relayer_stats),
+        memo_program: options.memo.as_ref().map(|_| MEMO_PROGRAM_ID),
+        event_forwarder: spoke.event_forwarder,
+        token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        payer: options.payer.unwrap_or(*relayer),
+        recipient_address_account,
+        recipient_ata,
+        event_authority: derive_event_authority(),
+        program: crate::ID,
+    }
+    .to_account_metas(None);
+    account_metas.extend_from_slice(&options.remaining_accounts);
+
+    let data = instruction::FillV3Relay {
+        relay_hash,
+        relay_data: Some(relay_data.clone()),
+        repayment_chain_id: Some(options.repayment_chain_id),
+        repayment_address: Some(options.repayment_address),
+        args_version,
+        extra,
+    }
+    .data();
+
+    Ok(Instruction { program_id: crate::ID, accounts: account_metas, data })
+}
+
+pub fn build_request_slow_fill_ix(
+    relay_data: &V3RelayData,
+    signer: &Pubkey,
+    options: &RequestSlowFillOptions,
+) -> Instruction {
+    let spoke = &options.spoke;
+    let relay_hash = get_v3_relay_hash(relay_data, spoke.chain_id);
+
+    let account_metas = accounts::RequestV3SlowFill {
+        signer: *signer,
+        instruction_params: None,
+        state: get_state_address(spoke.seed),
+        fill_status: get_fill_status_address(&relay_hash),
+        slow_fill_liability: get_slow_fill_liability_address(spoke.seed, &relay_data.output_token),
+        message_schema: get_message_schema_address(&relay_data.recipient),
+        depositor_compliance: get_compliance_address(spoke, &relay_data.depositor),
+        recipient_compliance: get_compliance_address(spoke, &relay_data.recipient),
+        allowlist_entry: get_allowlist_entry_address(spoke, signer),
+        requester_activity: options.requester_activity.then(|| {
+            find_address(&[b"requester_activity", spoke.seed.to_le_bytes().as_ref(), signer.as_ref()])
+        }),
+        event_commitment: get_event_commitment_address(spoke),
+        fill_index_shard: get_fill_index_shard_address(spoke, relay_data),
+        origin_chain_alias: get_origin_chain_alias_address(spoke, relay_data),
+        relayer_stats: get_relayer_stats_address(spoke, relay_data, options.relayer_stats),
+        system_program: system_program::ID,
+        payer: options.payer.unwrap_or(*signer),
+        event_authority: derive_event_authority(),
+        program: crate::ID,
+    }
+    .to_account_metas(None);
+
+    let data = instruction::RequestV3SlowFill { relay_hash, relay_data: Some(relay_data.clone()) }.data();
+
+    Instruction { program_id: crate::ID, accounts: account_metas, data }
+}
+
+pub fn build_execute_slow_relay_leaf_ix(
+    slow_fill_leaf: &V3SlowFill,
+    root_bundle_id: u32,
+    proof: Vec<[u8; 32]>,
+    signer: &Pubkey,
+    options: &ExecuteSlowRelayLeafOptions,
+) -> Result<Instruction> {
+    let spoke = &options.spoke;
+    let relay_data = &slow_fill_leaf.relay_data;
+    let seed_bytes = spoke.seed.to_le_bytes();
+    let state = get_state_address(spoke.seed);
+    let mint = relay_data.output_token;
+    let token_program = get_token_program(&options.mint_owner)?;
+    // The program overrides the leaf chain ID with the state chain ID when verifying the relay hash.
+    let relay_hash = get_v3_relay_hash(relay_data, spoke.chain_id);
+    let (args_version, extra) = encode_payout_memo_args(&options.memo)?;
+    let execution_approval = match options.execution_approval {
+        true => {
+            let leaf = V3SlowFill { chain_id: spoke.chain_id, ..slow_fill_leaf.clone() };
+            let leaf_hash = leaf.to_keccak_hash()?;
+            Some(find_address(&[b"execution_approval", seed_bytes.as_ref(), leaf_hash.as_ref()]))
+        }
+        false => None,
+    };
+
+    // Burn settlement does not pay out to the recipient, so none of its delivery accounts are needed.
+    let (recipient_token_account, delivery_preference, recipient_escrow) = match options.settlement_mode {
+        Some(SettlementMode::BurnFromVault) => (None, None, None),
+        _ => get_delivery_accounts(spoke.seed, &relay_data.recipient, &mint, &token_program, &options.delivery_mode),
+    };
+    let (native_unwrap_account, native_recipient) = match options.settlement_mode {
+        Some(SettlementMode::BurnFromVault) => (None, None),
+        _ => get_native_unwrap_accounts(spoke.seed, signer, &relay_data.recipient, &mint, &options.delivery_mode),
+    };
+
+    let mut account_metas = accounts::ExecuteV3SlowRelayLeaf {
+        signer: *signer,
+        instruction_params: None,
+        state,
+        root_bundle: find_address(&[b"root_bundle", seed_bytes.as_ref(), root_bundle_id.to_le_bytes().as_ref()]),
+        fill_status: get_fill_status_address(&relay_hash),
+        mint,
+        route: options
+            .settlement_mode
+            .as_ref()
+            .map(|_| get_route_address(spoke.seed, &mint, relay_data.origin_chain_id)),
+        recipient_token_account: recipient_token_account.map(|ata| options.recipient_token_account.unwrap_or(ata)),
+        delivery_preference,
+        recipient_escrow,
+        native_unwrap_account,
+        native_recipient,
+        depositor_compliance: get_compliance_address(spoke, &relay_data.depositor),
+        recipient_compliance: get_compliance_address(spoke, &relay_data.recipient),
+        allowlist_entry: get_allowlist_entry_address(spoke, signer),
+        vault: get_vault_address(&state, &mint, &token_program),
+        vault_authority: get_vault_authority_address(&state),
+        fee_vault: options.fee_vault.then(|| find_address(&[b"fee_vault", seed_bytes.as_ref(), mint.as_ref()])),
+        liquidity_pool: options
+            .liquidity_pool
+            .then(|| find_address(&[b"liquidity_pool", seed_bytes.as_ref(), mint.as_ref()])),
+        event_commitment: get_event_commitment_address(spoke),
+        memo_program: options.memo.as_ref().map(|_| MEMO_PROGRAM_ID),
+        rate_limit: spoke
+            .vault_outflow_rate_limits
+            .then(|| find_address(&[b"rate_limit", seed_bytes.as_ref(), mint.as_ref()])),
+        vault_liability: options
+            .vault_liability
+            .then(|| find_address(&[b"vault_liability", seed_bytes.as_ref(), mint.as_ref()])),
+        slow_fill_liability: get_slow_fill_liability_address(spoke.seed, &mint),
+        execution_approval,
+        token_program,
+        system_program: system_program::ID,
+        event_authority: derive_event_authority(),
+        program: crate::ID,
+    }
+    .to_account_metas(None);
+    account_metas.extend_from_slice(&options.remaining_accounts);
+
+    let data = instruction::ExecuteV3SlowRelayLeaf {
+        relay_hash,
+        slow_fill_leaf: Some(slow_fill_leaf.clone()),
+        _root_bundle_id: Some(root_bundle_id),
+        proof: Some(proof),
+        args_version,
+        extra,
+    }
+    .data();
+
+    Ok(Instruction { program_id: crate::ID, accounts: account_metas, data })
+}
+
+pub fn build_deposit_ix(deposit: &DepositArgs, signer: &Pubkey, options: &DepositOptions) -> Result<Instruction> {
+    let spoke = &options.spoke;
+    let seed_bytes = spoke.seed.to_le_bytes();
+    let state = get_state_address(spoke.seed);
+    let mint = deposit.input_token;
+    let token_program = get_token_program(&options.mint_owner)?;
+    let route_chain_id = options.canonical_chain_id.unwrap_or(deposit.destination_chain_id);
+
+    let account_metas = accounts::DepositV3 {
+        signer: *signer,
+        state,
+        destination_chain_alias: get_destination_chain_alias_address(deposit, options),
+        route: find_address(&[
+            b"route",
+            mint.as_ref(),
+            seed_bytes.as_ref(),
+            route_chain_id.to_le_bytes().as_ref(),
+        ]),
+        deposit_limit: get_deposit_limit_address(spoke.seed, &mint, route_chain_id),
+        depositor_nonce: find_address(&[b"depositor_nonce", seed_bytes.as_ref(), deposit.depositor.as_ref()]),
+        vault_registry: get_vault_registry_address(spoke.seed, &mint),
+        depositor_token_account: get_associated_token_address(&deposit.depositor, &mint, &token_program),
+        vault: get_vault_address(&state, &mint, &token_program),
+        mint,
+        vault_liability: find_address(&[b"vault_liability", seed_bytes.as_ref(), mint.as_ref()]),
+        event_commitment: get_event_commitment_address(spoke),
+        token_program,
+        system_program: system_program::ID,
+        event_authority: derive_event_authority(),
+        program: crate::ID,
+    }
+    .to_account_metas(None);
+    let (args_version, extra) = encode_deposit_extra_args(options.depositor_nonce)?;
+
+    let data = instruction::DepositV3 {
+        depositor: deposit.depositor,
+        recipient: deposit.recipient,
+        input_token: deposit.input_token,
+        output_token: deposit.output_token,
+        input_amount: deposit.input_amount,
+        output_amount: deposit.output_amount,
+        destination_chain_id: deposit.destination_chain_id,
+        exclusive_relayer: deposit.exclusive_relayer,
+        quote_timestamp: deposit.quote_timestamp,
+        fill_deadline: deposit.fill_deadline,
+        exclusivity_parameter: deposit.exclusivity_parameter,
+        message: deposit.message.clone(),
+        revoke_after: options.revoke_after,
+        args_version,
+        extra,
+    }
+    .data();
+
+    Ok(Instruction { program_id: crate::ID, accounts: account_metas, data })
+}
+
+// Deposits lamports of the depositor, who must sign as there is no delegation of native SOL. The input token and
+// mint owner of the deposit are ignored as native deposits always use the wSOL mint of the SPL Token program.
+pub fn build_deposit_native_ix(deposit: &DepositArgs, options: &DepositOptions) -> Instruction {
+    let spoke = &options.spoke;
+    let seed_bytes = spoke.seed.to_le_bytes();
+    let state = get_state_address(spoke.seed);
+    let route_chain_id = options.canonical_chain_id.unwrap_or(deposit.destination_chain_id);
+
+    let account_metas = accounts::DepositV3Native {
+        signer: deposit.depositor,
+        state,
+        destination_chain_alias: get_destination_chain_alias_address(deposit, options),
+        route: find_address(&[
+            b"route",
+            NATIVE_MINT.as_ref(),
+            seed_bytes.as_ref(),
+            route_chain_id.to_le_bytes().as_ref(),
+        ]),
+        deposit_limit: get_deposit_limit_address(spoke.seed, &NATIVE_MINT, route_chain_id),
+        vault: get_vault_address(&state, &NATIVE_MINT, &token::ID),
+        mint: NATIVE_MINT,
+        vault_liability: find_address(&[b"vault_liability", seed_bytes.as_ref(), NATIVE_MINT.as_ref()]),
+        event_commitment: get_event_commitment_address(spoke),
+        token_program: token::ID,
+        system_program: system_program::ID,
+        native_wrap_account: find_address(&[b"native_wrap", seed_bytes.as_ref(), deposit.depositor.as_ref()]),
+        event_authority: derive_event_authority(),
+        program: crate::ID,
+    }
+    .to_account_metas(None);
+
+    let data = instruction::DepositV3Native {
+        recipient: deposit.recipient,
+        output_token: deposit.output_token,
+        input_amount: deposit.input_amount,
+        output_amount: deposit.output_amount,
+        destination_chain_id: deposit.destination_chain_id,
+        exclusive_relayer: deposit.exclusive_relayer,
+        quote_timestamp: deposit.quote_timestamp,
+        fill_deadline: deposit.fill_deadline,
+        exclusivity_parameter: deposit.exclusivity_parameter,
+        message: deposit.message.clone(),
+    }
+    .data();
+
+    Instruction { program_id: crate::ID, accounts: account_metas, data }
+}
+
+// Selects the token program from the owner of the mint account, rejecting mints owned by any other program.
+pub fn get_token_program(mint_owner: &Pubkey) -> Result<Pubkey> {
+    match *mint_owner {
+        token::ID | token_2022::ID => Ok(*mint_owner),
+        _ => err!(SvmError::InvalidMint),
+    }
+}
+
+pub fn get_state_address(seed: u64) -> Pubkey {
+    find_address(&[b"state", seed.to_le_bytes().as_ref()])
+}
+
+pub fn get_fill_status_address(relay_hash: &[u8; 32]) -> Pubkey {
+    find_address(&[b"fills", relay_hash.as_ref()])
+}
+
+// Vaults are the ATAs of the vault authority.
+pub fn get_vault_address(state: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address(&get_vault_authority_address(state), mint, token_program)
+}
+
+fn get_vault_authority_address(state: &Pubkey) -> Pubkey {
+    find_address(&[b"vault_authority", state.as_ref()])
+}
+
+fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    associated_token::get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+fn get_vault_registry_address(seed: u64, mint: &Pubkey) -> Pubkey {
+    find_address(&[b"vault", seed.to_le_bytes().as_ref(), mint.as_ref()])
+}
+
+fn get_slow_fill_liability_address(seed: u64, mint: &Pubkey) -> Pubkey {
+    find_address(&[b"slow_fill_liability", seed.to_le_bytes().as_ref(), mint.as_ref()])
+}
+
+fn get_fill_volume_address(seed: u64, mint: &Pubkey) -> Pubkey {
+    find_address(&[b"fill_volume", seed.to_le_bytes().as_ref(), mint.as_ref()])
+}
+
+fn get_message_schema_address(recipient: &Pubkey) -> Pubkey {
+    find_address(&[b"message_schema", recipient.as_ref()])
+}
+
+fn get_compliance_address(spoke: &SpokeConfig, entry: &Pubkey) -> Option<Pubkey> {
+    spoke.compliance.then(|| find_address(&[b"compliance", spoke.seed.to_le_bytes().as_ref(), entry.as_ref()]))
+}
+
+fn get_allowlist_entry_address(spoke: &SpokeConfig, signer: &Pubkey) -> Option<Pubkey> {
+    spoke.permissioned_mode.then(|| find_address(&[b"allowlist", spoke.seed.to_le_bytes().as_ref(), signer.as_ref()]))
+}
+
+fn get_route_address(seed: u64, mint: &Pubkey, origin_chain_id: u64) -> Pubkey {
+    find_address(&[b"route", mint.as_ref(), seed.to_le_bytes().as_ref(), origin_chain_id.to_le_bytes().as_ref()])
+}
+
+// Deposit limits are passed even when not created, as routes without a deposit limit are unlimited.
+fn get_deposit_limit_address(seed: u64, mint: &Pubkey, destination_chain_id: u64) -> Pubkey {
+    find_address(&[
+        b"deposit_limit",
+        seed.to_le_bytes().as_ref(),
+        mint.as_ref(),
+        destination_chain_id.to_le_bytes().as_ref(),
+    ])
+}
+
+fn get_chain_alias_address(seed: u64, chain_id: u64) -> Pubkey {
+    find_address(&[b"chain_alias", seed.to_le_bytes().as_ref(), chain_id.to_le_bytes().as_ref()])
+}
+
+// Aliased destination chain IDs are translated by their chain alias, which strict chain alias mode always requires.
+fn get_destination_chain_alias_address(deposit: &DepositArgs, options: &DepositOptions) -> Option<Pubkey> {
+    (options.spoke.strict_chain_aliases || options.canonical_chain_id.is_some())
+        .then(|| get_chain_alias_address(options.spoke.seed, deposit.destination_chain_id))
+}
+
+// Relays from canonical origin chain IDs only need their chain alias in strict chain alias mode.
+fn get_origin_chain_alias_address(spoke: &SpokeConfig, relay_data: &V3RelayData) -> Option<Pubkey> {
+    spoke.strict_chain_aliases.then(|| get_chain_alias_address(spoke.seed, relay_data.origin_chain_id))
+}
+
+fn get_event_commitment_address(spoke: &SpokeConfig) -> Option<Pubkey> {
+    spoke.event_commitment_epoch.map(|epoch| {
+        find_address(&[b"event_commitment", spoke.seed.to_le_bytes().as_ref(), epoch.to_le_bytes().as_ref()])
+    })
+}
+
+// Deposit ids that do not fit into u64 are not indexed, so their fills do not take the shard account.
+fn get_fill_index_shard_address(spoke: &SpokeConfig, relay_data: &V3RelayData) -> Option<Pubkey> {
+    if !spoke.fill_index || get_fill_index_position(&relay_data.deposit_id).is_none() {
+        return None;
+    }
+
+    Some(find_address(&[
+        b"fill_index",
+        spoke.seed.to_le_bytes().as_ref(),
+        relay_data.origin_chain_id.to_le_bytes().as_ref(),
+        get_fill_index_bucket(&relay_data.deposit_id).to_le_bytes().as_ref(),
+    ]))
+}
+
+// Relays without an exclusive relayer are not attributed, so they do not take the stats account.
+fn get_relayer_stats_address(spoke: &SpokeConfig, relay_data: &V3RelayData, relayer_stats: bool) -> Option<Pubkey> {
+    if !relayer_stats || relay_data.exclusive_relayer == Pubkey::default() {
+        return None;
+    }
+
+    Some(find_address(&[b"relayer_stats", spoke.seed.to_le_bytes().as_ref(), relay_data.exclusive_relayer.as_ref()]))
+}
+
+// Returns the recipient token account, delivery preference and recipient escrow accounts for the delivery mode.
+fn get_delivery_accounts(
+    seed: u64,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    delivery_mode: &Option<DeliveryMode>,
+) -> (Option<Pubkey>, Option<Pubkey>, Option<Pubkey>) {
+    let seed_bytes = seed.to_le_bytes();
+    let recipient_token_account = get_associated_token_address(recipient, mint, token_program);
+    let delivery_preference = find_address(&[b"delivery_preference", seed_bytes.as_ref(), recipient.as_ref()]);
+
+    match delivery_mode {
+        None => (Some(recipient_token_account), None, None),
+        Some(DeliveryMode::Push) => (Some(recipient_token_account), Some(delivery_preference), None),
+        Some(DeliveryMode::Native) if *mint == NATIVE_MINT => (None, Some(delivery_preference), None),
+        Some(DeliveryMode::Native) => (Some(recipient_token_account), Some(delivery_preference), None),
+        Some(DeliveryMode::Escrow) => {
+            let escrow = find_address(&[b"escrow", seed_bytes.as_ref(), recipient.as_ref(), mint.as_ref()]);
+            (None, Some(delivery_preference), Some(escrow))
+        }
+    }
+}
+
+// Returns the native unwrap and native recipient accounts, only used for wSOL payouts with the Native delivery mode.
+fn get_native_unwrap_accounts(
+    seed: u64,
+    signer: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    delivery_mode: &Option<DeliveryMode>,
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    match delivery_mode {
+        Some(DeliveryMode::Native) if *mint == NATIVE_MINT => {
+            let native_unwrap_account = find_address(&[b"native_unwrap", seed.to_le_bytes().as_ref(), signer.as_ref()]);
+            (Some(native_unwrap_account), Some(*recipient))
+        }
+        _ => (None, None),
+    }
+}
+
+fn encode_payout_memo_args(memo: &Option<Vec<u8>>) -> Result<(u8, Vec<u8>)> {
+    match memo {
+        None => Ok((ARGS_VERSION_V0, Vec::new())),
+        Some(memo) => Ok((ARGS_VERSION_V1, ExtraArgsV1 { memo: Some(memo.clone()) }.try_to_vec()?)),
+    }
+}
+
+fn encode_fill_extra_args_v2(memo: &Option<Vec<u8>>) -> Result<(u8, Vec<u8>)> {
+    let extra_args = ExtraArgsV2 { memo: memo.clone(), create_repayment_token_account: true };
+
+    Ok((ARGS_VERSION_V2, extra_args.try_to_vec()?))
+}
+
+fn encode_fill_extra_args_v6(memo: &Option<Vec<u8>>, create_repayment_token_account: bool) -> Result<(u8, Vec<u8>)> {
+    let extra_args = ExtraArgsV6 {
+        memo: memo.clone(),
+        create_repayment_token_account,
+        origin_tx_ref: None,
+        accept_low_fee: false,
+        create_recipient_token_account: true,
+    };
+
+    Ok((ARGS_VERSION_V6, extra_args.try_to_vec()?))
+}
+
+fn encode_deposit_extra_args(depositor_nonce: Option<u64>) -> Result<(u8, Vec<u8>)> {
+    match depositor_nonce {
+        None => Ok((ARGS_VERSION_V0, Vec::new())),
+        Some(depositor_nonce) => Ok((ARGS_VERSION_V5, ExtraArgsV5 { depositor_nonce }.try_to_vec()?)),
+    }
+}
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
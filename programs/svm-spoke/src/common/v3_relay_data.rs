@@ -1,18 +1,29 @@
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct V3RelayData {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub depositor: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub recipient: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub exclusive_relayer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub input_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub output_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub input_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub output_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub origin_chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub deposit_id: [u8; 32],
     pub fill_deadline: u32,
     pub exclusivity_deadline: u32,
     #[max_len(0)]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes"))]
     pub message: Vec<u8>,
 }
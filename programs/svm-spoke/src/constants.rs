@@ -4,8 +4,205 @@ pub const DISCRIMINATOR_SIZE: usize = 8;
 
 pub const MESSAGE_TRANSMITTER_PROGRAM_ID: Pubkey = pubkey!("CCTPmbSD7gX1bxKPAmg77w8oFzNFpaQiQUWD43TKaecd");
 
-// One year in seconds. If exclusivityParameter is set to a value less than this, then the emitted exclusivityDeadline
-// in a deposit event will be set to the current time plus this value.
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// One year in seconds. If exclusivityParameter is set to a value up to and including this, then the emitted
+// exclusivityDeadline in a deposit event will be set to the current time plus exclusivityParameter.
 pub const MAX_EXCLUSIVITY_PERIOD_SECONDS: u32 = 31_536_000;
 
+// Max deposit quote time and fill deadline buffers in seconds, so that deposits can't quote stale prices or lock funds
+// until far future fill deadlines.
+pub const MAX_DEPOSIT_BUFFER: u32 = 30 * 86_400;
+
+// Min delay in seconds between the start of a wind down and the vault sweeps, so that relays of deposits made right
+// before the wind down can still be filled or slow filled and refunded until their fill deadlines.
+pub const MIN_WIND_DOWN_DELAY: u32 = MAX_DEPOSIT_BUFFER;
+
 pub const ZERO_DEPOSIT_ID: [u8; 32] = [0u8; 32];
+
+// Denominator of fees expressed in basis points.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Failsafe cap on the combined protocol and liquidity pool fees skimmed from a slow fill payout.
+pub const MAX_TOTAL_FEE_BPS: u64 = 200;
+
+// Versioned action list envelope of Across+ messages: the tag and version byte prefix the serialized action list.
+// Messages without this prefix are treated as a single handler message.
+pub const ACTION_LIST_MESSAGE_TAG: [u8; 4] = *b"ACXA";
+pub const ACTION_LIST_MESSAGE_VERSION: u8 = 1;
+pub const ACTION_LIST_MESSAGE_HEADER_SIZE: usize = 5;
+
+// Maximum number of handler actions invoked from a single action list message.
+pub const MAX_MESSAGE_ACTIONS: usize = 4;
+
+// Length in seconds of each epoch whose events are chained into a separate EventCommitment PDA.
+pub const EVENT_COMMITMENT_EPOCH_DURATION: u32 = 86_400;
+
+// Number of consecutive deposit ids tracked in the bitmap of each FillIndexShard PDA.
+pub const FILL_INDEX_SHARD_SIZE: u64 = 1024;
+
+// Network tags stored in the state to catch relay data built against chain IDs of another network.
+pub const NETWORK_MAINNET: u8 = 0;
+pub const NETWORK_DEVNET: u8 = 1;
+pub const NETWORK_LOCALNET: u8 = 2;
+
+// Across definition of chainId for Solana networks.
+pub const SOLANA_MAINNET_CHAIN_ID: u64 = 34268394551451;
+pub const SOLANA_DEVNET_CHAIN_ID: u64 = 133268194659241;
+
+// Packed layout of each get_fill_statuses_packed return data entry: status (u8), relayer (Pubkey) and fill_deadline
+// (u32, little-endian). Entries follow the order of the passed fill status accounts.
+pub const FILL_STATUSES_PACKED_STATUS_OFFSET: usize = 0;
+pub const FILL_STATUSES_PACKED_RELAYER_OFFSET: usize = 1;
+pub const FILL_STATUSES_PACKED_FILL_DEADLINE_OFFSET: usize = 33;
+pub const FILL_STATUSES_PACKED_ENTRY_SIZE: usize = 37;
+
+// Keeps the packed entries within the 1024 byte return data limit.
+pub const FILL_STATUSES_PACKED_MAX_ACCOUNTS: usize = 25;
+
+// Status byte packed for accounts that do not deserialize as a fill status, e.g. closed or foreign accounts.
+pub const FILL_STATUSES_PACKED_INVALID_STATUS: u8 = u8::MAX;
+
+// Sizes of fill status PDAs created before the fill deadline (status and relayer only) and before the requester and
+// payer were recorded. Instructions writing to them fail to deserialize them until migrate_fill_status_account.
+pub const LEGACY_FILL_STATUS_ACCOUNT_SIZES: [usize; 2] = [DISCRIMINATOR_SIZE + 33, DISCRIMINATOR_SIZE + 37];
+
+// Size of route PDAs created when routes only tracked whether they were enabled. Instructions reading them fail to
+// deserialize them until migrate_route.
+pub const LEGACY_ROUTE_SIZE: usize = DISCRIMINATOR_SIZE + 1;
+
+// Maximum size in bytes of the optional memo logged via the SPL Memo program before fill payouts.
+pub const MAX_PAYOUT_MEMO_SIZE: usize = 64;
+
+// Slow fill leaves are only executable until the fill deadline of their relay, same as executeV3SlowRelayLeaf in the
+// EVM SpokePool that goes through the fill deadline check of _fillRelayV3. Deposits expiring before their slow fill
+// is executed are refunded on the origin chain by the dataworker, so a later execution would pay them out twice.
+pub const SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE: bool = false;
+
+// Fill status PDAs of pending slow fill requests can only be closed this long after their fill deadline, which covers
+// the execution window of root bundles relayed with their slow fill leaf.
+pub const REQUESTED_SLOW_FILL_CLOSE_DELAY: u32 = 86_400;
+
+// Divergence in seconds of the test current time override from the Clock sysvar logged with the debug-logs feature.
+pub const CURRENT_TIME_MAX_SKEW: u32 = 3_600;
+
+// Version byte prefixing compact relay data of batched instructions, see decode_compact_relay_data.
+pub const COMPACT_RELAY_DATA_VERSION: u8 = 1;
+
+// Max deviation in bps of reconciled counters from their current values, unless the reconciliation is forced.
+pub const RECONCILE_COUNTERS_MAX_DEVIATION_BPS: u64 = 1000;
+
+// Max size of the discriminator prefixed event data passed to the event forwarder program.
+pub const MAX_FORWARDED_EVENT_SIZE: usize = 1024;
+
+// Self-CPI data of events emitted by emit_cpi! is kept within the transaction packet size, as larger inner instructions
+// failed on public clusters. Larger refund events are split into ExecutedRelayerRefundRootChunk events.
+pub const MAX_EVENT_CPI_DATA_SIZE: usize = 1_232;
+
+// Max size of relay messages accepted by deposits, fills and slow fill requests. The EVM SpokePool and HubPool do not
+// bound messages, so this follows the 10KiB CPI instruction data limit of the message handler invocation, leaving room
+// for its discriminator and token, amount and relayer arguments. Relays from other chains with larger messages can't be
+// filled here and are refunded on the origin chain once expired.
+pub const MAX_MESSAGE_SIZE: usize = 8_192;
+
+// Deposit events carry messages up to this size inline. Larger messages are emitted as their keccak hash with an empty
+// message, so that V3FundsDeposited stays well within MAX_EVENT_CPI_DATA_SIZE. Their full bytes are still in the
+// deposit instruction data, and fill events only ever carry the message hash.
+pub const MAX_INLINE_EVENT_MESSAGE_SIZE: usize = 256;
+
+// Role bits of AllowlistEntry PDAs checked in permissioned mode.
+pub const ALLOWLIST_ROLE_RELAYER: u8 = 1 << 0; // Can fill relays, and request slow fills when strictly checked.
+pub const ALLOWLIST_ROLE_REQUESTER: u8 = 1 << 1; // Can request slow fills.
+pub const ALLOWLIST_ROLE_EXECUTOR: u8 = 1 << 2; // Can execute slow relay leaves.
+pub const ALLOWLIST_ROLES_MASK: u8 = ALLOWLIST_ROLE_RELAYER | ALLOWLIST_ROLE_REQUESTER | ALLOWLIST_ROLE_EXECUTOR;
+
+// Max number of fill events the owner can replay within each EVENT_REPLAY_RATE_WINDOW seconds.
+pub const EVENT_REPLAY_RATE_LIMIT: u32 = 10;
+pub const EVENT_REPLAY_RATE_WINDOW: u32 = 3600;
+
+// Max absolute decimal shift of a route, keeping the scaling factor within u64.
+pub const MAX_DECIMAL_SHIFT: u8 = 18;
+
+// Max route updates of set_enable_routes_batch, keeping the batch within the transaction account and compute limits.
+pub const MAX_ROUTE_UPDATES_PER_BATCH: usize = 10;
+
+// Max slow relay leaves of execute_v3_slow_relay_leaves, keeping their 4 accounts each within the 64 account locks of a
+// transaction. Each leaf consumes roughly 40k to 60k compute units depending on its proof length and fees.
+pub const MAX_SLOW_RELAY_LEAVES_PER_BATCH: usize = 12;
+
+// Max slow fill requests of request_v3_slow_fills_batch, keeping the batch within the 1.4M compute unit limit of a
+// transaction. Each request consumes roughly 50k compute units, mostly creating its fill status PDA and emitting
+// events.
+pub const MAX_SLOW_FILL_REQUESTS_PER_BATCH: usize = 20;
+
+// Staleness after which gc_account can close each account kind, and the share of the rent paid to its caller when the
+// original payer is recorded. Without a recorded payer the caller receives all of the rent.
+pub const GC_ROUTE_DISABLED_PERIOD: u32 = 30 * 86_400;
+pub const GC_REQUESTER_ACTIVITY_RETENTION: u32 = 7 * 86_400;
+pub const GC_EVENT_COMMITMENT_RETENTION: u32 = 90 * 86_400;
+pub const GC_CALLER_INCENTIVE_BPS: u16 = 1000;
+
+// Keeps the per mint outflows of simulate_bundle_summary within the 1024 byte return data limit.
+pub const BUNDLE_SUMMARY_MAX_MINTS: usize = 20;
+
+// Keeps the leaf IDs returned by get_unclaimed_leaves within the 1024 byte return data limit, including the length
+// prefix of the returned vec.
+pub const UNCLAIMED_LEAVES_MAX_IDS: usize = 255;
+
+// Maximum number of mints that can have an execution approval threshold for their slow fill leaves in the state.
+pub const MAX_EXECUTION_APPROVAL_THRESHOLDS: usize = 8;
+
+// Bits of the health_check return data and HealthCheckFailed event. A set bit marks a failed check, so 0 is healthy.
+pub const HEALTH_CHECK_STATE_LAYOUT: u32 = 1 << 0; // State is owned by the program and deserializes at its layout.
+pub const HEALTH_CHECK_STATE_BUMP: u32 = 1 << 1; // State address re-derives as the canonical PDA of its seed.
+pub const HEALTH_CHECK_PAUSE_FLAGS: u32 = 1 << 2; // Pause flags and the fills pause expiry are consistent.
+pub const HEALTH_CHECK_WSOL_VAULT: u32 = 1 << 3; // Passed wSOL vault is the vault ATA and its balance is synced.
+pub const HEALTH_CHECK_COUNTERS: u32 = 1 << 4; // Times, fees and thresholds of the state are within their bounds.
+pub const HEALTH_CHECK_ALL: u32 = HEALTH_CHECK_STATE_LAYOUT
+    | HEALTH_CHECK_STATE_BUMP
+    | HEALTH_CHECK_PAUSE_FLAGS
+    | HEALTH_CHECK_WSOL_VAULT
+    | HEALTH_CHECK_COUNTERS;
+
+// Bits of the blockers reported by preview_vault_migration. A set bit marks a check failing migrate_vault_authority
+// with VaultMigrationBlocked, so 0 is a go.
+pub const VAULT_MIGRATION_STATE: u32 = 1 << 0; // State is the expected new state at the current layout.
+pub const VAULT_MIGRATION_FROZEN: u32 = 1 << 1; // Neither the state vault nor the vault authority ATA is frozen.
+pub const VAULT_MIGRATION_SLOW_FILLS: u32 = 1 << 2; // No requested slow fills of the mint are pending execution.
+pub const VAULT_MIGRATION_CLAIMS: u32 = 1 << 3; // No relayer refunds of the mint are pending in claim accounts.
+pub const VAULT_MIGRATION_POOL_LIQUIDITY: u32 = 1 << 4; // No liquidity is owed to the pool of the mint.
+
+// Layout of the export_* return data pages, that the matching import_* instructions accept as is: layout version (u8),
+// record count (u8) and continuation token (u32, little-endian) followed by the packed records. The continuation token
+// is the page to pass for the next records, or 0 when this is the last page.
+pub const EXPORT_LAYOUT_VERSION: u8 = 1;
+pub const EXPORT_HEADER_SIZE: usize = 6;
+// Fill status record: relay_hash, status (u8), relayer, fill_deadline (u32, little-endian), requester and payer.
+pub const EXPORT_FILL_STATUS_RECORD_SIZE: usize = 133;
+// Claim account record: mint, refund_address, amount (u64, little-endian) and initializer.
+pub const EXPORT_CLAIM_ACCOUNT_RECORD_SIZE: usize = 104;
+// Liability record: mint and pending_to_hub_pool (u64, little-endian) of its transfer liability.
+pub const EXPORT_LIABILITY_RECORD_SIZE: usize = 40;
+
+// Keeps export pages within the 1024 byte return data limit, including the length prefix of the returned bytes.
+pub const MAX_EXPORT_PAGE_SIZE: usize = 1020;
+
+// Layout of the get_instruction_metadata return data: layout version (u8) and account count (u8) followed by each
+// account in instruction order as its METADATA_* flags (u8), role and writable reason. Both texts are UTF-8 prefixed
+// with their length (u8), with an empty writable reason for read-only accounts.
+pub const METADATA_LAYOUT_VERSION: u8 = 1;
+pub const METADATA_WRITABLE: u8 = 1 << 0;
+pub const METADATA_SIGNER: u8 = 1 << 1;
+pub const METADATA_OPTIONAL: u8 = 1 << 2; // Pass the program ID to represent None.
+
+// Roughly a day of slots, well beyond the retry window of automation resubmitting admin instructions.
+pub const ADMIN_NONCE_RETENTION_SLOTS: u64 = 216_000;
+
+// Merkle trees of up to 2^32 leaves, far beyond the size of any relayer refund or slow relay tree.
+pub const MAX_MERKLE_PROOF_DEPTH: usize = 32;
+
+// Layout version of the State account, bumped whenever fields are appended to it. States of older versions, including
+// version 0 of states created before the version was recorded, must be migrated with migrate_state.
+pub const CURRENT_STATE_VERSION: u8 = 3;
@@ -1,8 +1,14 @@
 use anchor_lang::prelude::*;
 
 use crate::{
+    constants::{
+        MAX_MESSAGE_SIZE, NETWORK_DEVNET, NETWORK_LOCALNET, NETWORK_MAINNET, SOLANA_DEVNET_CHAIN_ID,
+        SOLANA_MAINNET_CHAIN_ID,
+    },
+    error::SvmError,
+    relay_admission::is_fill_pause_active,
     state::State,
-    utils::{get_self_authority_pda, get_v3_relay_hash},
+    utils::{encode_hex, get_current_time, get_self_authority_pda, get_v3_relay_hash},
     V3RelayData,
 };
 
@@ -10,6 +16,95 @@ pub fn is_local_or_remote_owner(signer: &Signer, state: &Account<State>) -> bool
     signer.key() == state.owner || signer.key() == get_self_authority_pda()
 }
 
+pub fn are_fills_paused(state: &Account<State>) -> Result<bool> {
+    Ok(is_fill_pause_active(state, get_current_time(state)?))
+}
+
+// Announced upgrades block starting new multi-transaction flows from their effective slot until cleared.
+pub fn is_upgrade_pending(state: &Account<State>) -> Result<bool> {
+    match state.upgrade_effective_after_slot {
+        Some(effective_after_slot) => Ok(Clock::get()?.slot >= effective_after_slot),
+        None => Ok(false),
+    }
+}
+
+// Relay hashes commit to the destination chain ID as in the EVM getV3RelayHash, so that relays targeting another
+// deployment of the program can never match the relay hash checked against this state. V3RelayData carries no
+// destination chain of its own, so fills and slow fill requests for another chain can't be told apart from any other
+// relay data or hash mismatch and fail with InvalidRelayHash. Mismatches log both hashes, so that clients can tell
+// which relay data or chain ID they hashed differently.
 pub fn is_relay_hash_valid(relay_hash: &[u8; 32], relay_data: &V3RelayData, state: &Account<State>) -> bool {
-    relay_hash == &get_v3_relay_hash(relay_data, state.chain_id)
+    let computed_relay_hash = get_v3_relay_hash(relay_data, state.chain_id);
+    if relay_hash != &computed_relay_hash {
+        msg!(
+            "Relay hash {} does not match {} computed for chain ID {}",
+            encode_hex(relay_hash),
+            encode_hex(&computed_relay_hash),
+            state.chain_id
+        );
+        return false;
+    }
+    true
+}
+
+// Rejects relay data that no deposit can produce before it creates a fill status PDA. Zero output amounts are only
+// accepted for message-only relays, as a relay with neither tokens nor a message would not do anything. Fill deadlines
+// older than a full fill deadline buffer can't belong to any open deposit, and are rejected as stale rather than as
+// expired. Messages above MAX_MESSAGE_SIZE could not be passed to the message handler.
+pub fn validate_relay_data(relay_data: &V3RelayData, state: &State, current_time: u32) -> Result<()> {
+    if relay_data.recipient == Pubkey::default() {
+        return err!(SvmError::InvalidRelayRecipient);
+    }
+    if relay_data.output_token == Pubkey::default() {
+        return err!(SvmError::InvalidRelayOutputToken);
+    }
+    if is_empty_relay(relay_data.output_amount, &relay_data.message) {
+        return err!(SvmError::EmptyRelay);
+    }
+    if is_message_too_large(&relay_data.message) {
+        return err!(SvmError::MessageTooLarge);
+    }
+    if relay_data.fill_deadline.saturating_add(state.fill_deadline_buffer) < current_time {
+        return err!(SvmError::StaleFillDeadline);
+    }
+    Ok(())
+}
+
+// Slow fill leaves are the only relay inputs that name their destination chain, so leaves of slow relay roots for
+// another chain are rejected explicitly before their proof is checked.
+pub fn check_destination_chain_id(state: &State, chain_id: u64) -> Result<()> {
+    if chain_id != state.chain_id {
+        msg!("Destination chain ID {} does not match chain ID {}", chain_id, state.chain_id);
+        return err!(SvmError::WrongDestinationChain);
+    }
+    Ok(())
+}
+
+// Message-only relays with a zero output amount are executed without any token transfer, but still need a message.
+pub fn is_empty_relay(output_amount: u64, message: &[u8]) -> bool {
+    output_amount == 0 && message.is_empty()
+}
+
+pub fn is_message_too_large(message: &[u8]) -> bool {
+    message.len() > MAX_MESSAGE_SIZE
+}
+
+// Mainnet and devnet must use their Across chain IDs, while localnet can use any other chain ID.
+pub fn is_network_chain_id_valid(network: u8, chain_id: u64) -> bool {
+    match network {
+        NETWORK_MAINNET => chain_id == SOLANA_MAINNET_CHAIN_ID,
+        NETWORK_DEVNET => chain_id == SOLANA_DEVNET_CHAIN_ID,
+        NETWORK_LOCALNET => chain_id != SOLANA_MAINNET_CHAIN_ID && chain_id != SOLANA_DEVNET_CHAIN_ID,
+        _ => false,
+    }
+}
+
+// Network of the Across chain ID, so that states migrated from before the network was recorded get the one their chain
+// ID is valid for.
+pub fn get_network_for_chain_id(chain_id: u64) -> u8 {
+    match chain_id {
+        SOLANA_MAINNET_CHAIN_ID => NETWORK_MAINNET,
+        SOLANA_DEVNET_CHAIN_ID => NETWORK_DEVNET,
+        _ => NETWORK_LOCALNET,
+    }
 }
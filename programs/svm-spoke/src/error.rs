@@ -15,6 +15,8 @@ pub enum CommonError {
     NoSlowFillsInExclusivityWindow,
     #[msg("The relay has already been filled!")]
     RelayFilled,
+    // No longer returned since slow fill requests report SvmError::SlowFillAlreadyRequested, RelayAlreadyFilled or
+    // RelayExpired instead. Kept so that the codes of the following variants don't change.
     #[msg("Slow fill requires status of Unfilled!")]
     InvalidSlowFillRequest,
     #[msg("The fill deadline has passed!")]
@@ -62,6 +64,8 @@ pub enum SvmError {
     InvalidMint,
     #[msg("Exceeded pending bridge amount to HubPool!")]
     ExceededPendingBridgeAmount,
+    #[msg("Exceeded CCTP burn limit per message!")]
+    ExceededCctpBurnLimit,
     #[msg("Overflow writing to parameters account!")]
     ParamsWriteOverflow,
     #[msg("Invalid refund address!")]
@@ -76,6 +80,302 @@ pub enum SvmError {
     InvalidProductionSeed,
     #[msg("Invalid remaining accounts for ATA creation!")]
     InvalidATACreationAccounts,
+    #[msg("Root bundle ID does not match the provided root bundle account!")]
+    RootBundleIdMismatch,
+    #[msg("The spoke pool is winding down!")]
+    WindingDown,
+    #[msg("The spoke pool is not winding down!")]
+    NotWindingDown,
+    #[msg("The wind down delay has not passed!")]
+    WindDownDelayNotPassed,
+    #[msg("Missing owner token account to sweep non-CCTP vault!")]
+    MissingSweepRecipient,
+    #[msg("Invalid Ed25519 signature verification instruction!")]
+    InvalidEd25519Instruction,
+    #[msg("Invalid requester signature!")]
+    InvalidRequesterSignature,
+    #[msg("Requester nonce was already used!")]
+    InvalidRequesterNonce,
+    #[msg("Mint decimals do not match the route expected decimals!")]
+    UnexpectedMintDecimals,
+    #[msg("Chain ID does not belong to the network!")]
+    InvalidNetworkChainId,
+    #[msg("Root bundle execution window has closed!")]
+    BundleExecutionWindowClosed,
+    #[msg("Lamport destination must be a system owned account!")]
+    InvalidLamportDestination,
+    #[msg("Recipient token account is required for vault transfer settlement!")]
+    MissingRecipientTokenAccount,
+    #[msg("Only the compliance authority can call this function!")]
+    NotComplianceAuthority,
+    #[msg("Compliance attestation accounts are required!")]
+    MissingComplianceAttestation,
+    #[msg("Relay depositor or recipient is blocked!")]
+    ComplianceBlocked,
+    #[msg("Bundle audit account is required when bundle audits are enabled!")]
+    MissingBundleAudit,
+    #[msg("Too many fill status accounts requested!")]
+    TooManyFillStatusAccounts,
+    #[msg("Only the restricted executor can execute leaves of this root bundle!")]
+    NotRestrictedExecutor,
+    #[msg("Unsupported instruction args version!")]
+    UnsupportedArgsVersion,
+    #[msg("Extra instruction args do not match their version!")]
+    InvalidExtraArgs,
+    #[msg("Too many slow fill requests in the current rate limit window!")]
+    RateLimited,
+    #[msg("Requester activity account is required when slow fill rate limits are enabled!")]
+    MissingRequesterActivity,
+    #[msg("Requester activity can only be closed after its rate limit window has passed!")]
+    RequesterActivityStillActive,
+    #[msg("Fill confirmation oracle is not set!")]
+    FillConfirmationOracleNotSet,
+    #[msg("Invalid fill confirmation attestation!")]
+    InvalidFillAttestation,
+    #[msg("Recipient escrow is required when the recipient opted into escrow delivery!")]
+    MissingRecipientEscrow,
+    #[msg("Unknown config key!")]
+    UnknownConfigKey,
+    #[msg("Closing accounts is frozen during a bundle dispute!")]
+    ClosesFrozenDuringDispute,
+    #[msg("Protocol and liquidity pool fees cannot exceed the fee cap!")]
+    InvalidProtocolFee,
+    #[msg("Only the protocol fee recipient can call this function!")]
+    NotProtocolFeeRecipient,
+    #[msg("Fee vault is required when a protocol fee is charged!")]
+    MissingFeeVault,
+    #[msg("Withdrawal exceeds accrued protocol fees!")]
+    InsufficientProtocolFees,
+    #[msg("Event commitment is required when event commitments are enabled!")]
+    MissingEventCommitment,
+    #[msg("Event commitment does not match the state or the current epoch!")]
+    InvalidEventCommitment,
+    #[msg("Liquidity pools are disabled!")]
+    LiquidityPoolsDisabled,
+    #[msg("Liquidity pool and protocol fees cannot exceed the fee cap!")]
+    InvalidLiquidityPoolFee,
+    #[msg("Liquidity pool is required when a liquidity pool fee is charged!")]
+    MissingLiquidityPool,
+    #[msg("Liquidity amount must be worth at least one share!")]
+    InvalidLiquidityAmount,
+    #[msg("Not enough liquidity pool shares!")]
+    InsufficientLiquidityShares,
+    #[msg("Pause expiry must be in the future!")]
+    InvalidPauseExpiry,
+    #[msg("Fill index shard account is required when the fill index is enabled!")]
+    MissingFillIndexShard,
+    #[msg("Payout memo must be between 1 and 64 bytes!")]
+    InvalidPayoutMemo,
+    #[msg("Memo program is required when a payout memo is provided!")]
+    MissingMemoProgram,
+    #[msg("Payout token account requires a memo for incoming transfers!")]
+    MissingRequiredMemo,
+    #[msg("Invalid compact relay data encoding!")]
+    InvalidCompactRelayData,
+    #[msg("Arithmetic overflow!")]
+    ArithmeticOverflow,
+    #[msg("Vault outflow would exceed the rate limit of the mint!")]
+    VaultOutflowRateLimited,
+    #[msg("Rate limit account is required when vault outflow rate limits are enabled!")]
+    MissingVaultOutflowRateLimit,
+    #[msg("Rate limit window must be positive!")]
+    InvalidRateLimitWindow,
+    #[msg("Reconciled counters deviate too much from their current values without force!")]
+    ReconciliationOutOfBand,
+    #[msg("Event forwarder account is required when the state has an event forwarder!")]
+    MissingEventForwarder,
+    #[msg("Event forwarder account does not match the executable forwarder program of the state!")]
+    InvalidEventForwarder,
+    #[msg("Event is too large to be forwarded!")]
+    ForwardedEventTooLarge,
+    #[msg("Signer does not hold the allowlist role required in permissioned mode!")]
+    NotAllowlisted,
+    #[msg("Slow fill requester must be an allowlisted relayer other than the recipient or depositor!")]
+    SlowFillRequesterNotAllowed,
+    #[msg("Invalid allowlist roles!")]
+    InvalidAllowlistRoles,
+    #[msg("Only filled relays can have their fill event replayed!")]
+    FillNotTerminal,
+    #[msg("Too many event replays in the current rate limit window!")]
+    EventReplayRateLimited,
+    #[msg("Invalid decimal shift!")]
+    InvalidDecimalShift,
+    #[msg("Invalid account for the garbage collected account kind!")]
+    InvalidGcAccount,
+    #[msg("Account is not eligible for garbage collection yet!")]
+    AccountNotCollectable,
+    #[msg("Original payer of the garbage collected account is missing or invalid!")]
+    InvalidOriginalPayer,
+    #[msg("Repayment token account creation requires the repayment address and token accounts!")]
+    MissingRepaymentTokenAccount,
+    #[msg("Invalid repayment address or token account!")]
+    InvalidRepaymentTokenAccount,
+    #[msg("Invalid vault or fill status account for the simulated bundle leaf!")]
+    InvalidBundleSummaryAccount,
+    #[msg("Too many mints in the simulated bundle!")]
+    TooManyBundleSummaryMints,
+    #[msg("Program upgrade is pending!")]
+    UpgradePending,
+    #[msg("Deposit amount received after the transfer fee is zero!")]
+    ZeroDepositAfterTransferFee,
+    #[msg("Invalid depositor signature!")]
+    InvalidDepositorSignature,
+    #[msg("Only the execution approver can manage execution approvals!")]
+    NotExecutionApprover,
+    #[msg("Too many execution approval thresholds!")]
+    TooManyExecutionApprovalThresholds,
+    #[msg("Slow fill leaf above the execution approval threshold requires an execution approval!")]
+    MissingExecutionApproval,
+    #[msg("Execution approval does not match the slow fill leaf or the execution approver!")]
+    InvalidExecutionApproval,
+    #[msg("Execution approval has expired!")]
+    ExpiredExecutionApproval,
+    #[msg("Requested slow fill can only be closed after its root bundle window has passed!")]
+    CanOnlyCloseRequestedSlowFillAfterBundleWindow,
+    #[msg("Native delivery requires the native unwrap account and the recipient account!")]
+    MissingNativeUnwrapAccount,
+    #[msg("Native delivery recipient account does not match the relay recipient!")]
+    InvalidNativeRecipient,
+    #[msg("Refund to a token account that cannot receive it requires the claim account of the relayer!")]
+    MissingRefundClaimAccount,
+    #[msg("Signature has already been used!")]
+    SignatureAlreadyUsed,
+    #[msg("Used signature PDA can only be closed after it has expired!")]
+    CanOnlyCloseUsedSignatureAfterExpiry,
+    #[msg("Only the pending owner can accept ownership!")]
+    NotPendingOwner,
+    #[msg("Config snapshot was emitted within the snapshot interval!")]
+    ConfigSnapshotTooSoon,
+    #[msg("Instruction params can only be closed by others after the params buffer TTL!")]
+    CanOnlyCloseParamsBufferAfterTtl,
+    #[msg("Invalid number of route updates!")]
+    InvalidRouteUpdateCount,
+    #[msg("Invalid route update accounts!")]
+    InvalidRouteUpdateAccounts,
+    #[msg("Mint extensions making vault balances unreliable are not supported!")]
+    UnsupportedMintExtension,
+    #[msg("Invalid transfer fee amount!")]
+    InvalidTransferFeeAmount,
+    #[msg("Invalid number of slow relay leaves!")]
+    InvalidSlowRelayLeafCount,
+    #[msg("Invalid slow relay leaf accounts!")]
+    InvalidSlowRelayLeafAccounts,
+    #[msg("Slow relay leaf must be executed individually!")]
+    UnsupportedBatchedSlowRelayLeaf,
+    #[msg("Emergency executions require an execution approver other than the emergency executor!")]
+    MissingEmergencyApprover,
+    #[msg("Fill status account already has the current layout!")]
+    NotLegacyFillStatusAccount,
+    #[msg("Invalid export page!")]
+    InvalidExportPage,
+    #[msg("State imports are disabled after the first deposit or root bundle!")]
+    ImportsDisabled,
+    #[msg("Root bundle has no slow relay root!")]
+    BundleHasNoSlowRelayRoot,
+    #[msg("Root bundle has no relayer refund root!")]
+    BundleHasNoRelayerRefundRoot,
+    #[msg("Sweep amount exceeds the vault balance over its liabilities!")]
+    SweepExceedsSurplus,
+    #[msg("Idempotency key requires the admin nonce PDA!")]
+    MissingAdminNonce,
+    #[msg("Admin nonce PDA can only be closed after it has expired!")]
+    CanOnlyCloseAdminNonceAfterExpiry,
+    #[msg("Merkle proof exceeds the maximum depth!")]
+    InvalidProofLength,
+    #[msg("Token program does not own the mint!")]
+    TokenProgramMismatch,
+    #[msg("Relayer lapse threshold exceeds 10000 bps!")]
+    InvalidRelayerLapseThreshold,
+    #[msg("Deposit would exceed the deposit limit of the route!")]
+    DepositLimitExceeded,
+    #[msg("Vault of the mint is missing!")]
+    VaultMissingForMint,
+    #[msg("No metadata for the instruction!")]
+    MissingInstructionMetadata,
+    #[msg("Can only mark relays expired after their fill deadline!")]
+    CanOnlyMarkExpiredAfterFillDeadline,
+    #[msg("Requested slow fill can only be marked expired after the bundle window!")]
+    CanOnlyMarkRequestedSlowFillExpiredAfterBundleWindow,
+    #[msg("Relay was marked expired!")]
+    RelayExpired,
+    #[msg("Invalid repayment address for the repayment chain!")]
+    InvalidRepaymentAddress,
+    #[msg("Implied relayer fee is below the floor of the route!")]
+    RelayerFeeBelowFloor,
+    #[msg("Relayer fee floor exceeds 10000 bps!")]
+    InvalidRelayerFeeFloor,
+    #[msg("Relay recipient can't be the default pubkey!")]
+    InvalidRelayRecipient,
+    #[msg("Relay output token can't be the default pubkey!")]
+    InvalidRelayOutputToken,
+    #[msg("Relay output amount can't be zero!")]
+    ZeroRelayOutputAmount,
+    #[msg("Relay fill deadline is older than the fill deadline buffer!")]
+    StaleFillDeadline,
+    #[msg("Slow fill liability is required to release a requested slow fill!")]
+    MissingSlowFillLiability,
+    #[msg("Vault balance can't drop below the pending slow fill liability!")]
+    SlowFillLiabilityExceeded,
+    #[msg("Deposit buffers must be non-zero and at most MAX_DEPOSIT_BUFFER!")]
+    InvalidDepositBuffer,
+    #[msg("Vault of the mint is frozen!")]
+    VaultFrozen,
+    #[msg("Vault of the mint is not frozen!")]
+    VaultNotFrozen,
+    #[msg("Swap program is not an enabled swap adapter!")]
+    DisabledSwapAdapter,
+    #[msg("Swap received less than the minimum input amount!")]
+    SwapSlippageExceeded,
+    #[msg("Chain ID has no registered chain alias!")]
+    UnregisteredChainAlias,
+    #[msg("Chain ID is an alias of another canonical chain ID!")]
+    NonCanonicalChainId,
+    #[msg("Relay has neither an output amount nor a message!")]
+    EmptyRelay,
+    #[msg("Invalid number of slow fill requests!")]
+    InvalidSlowFillRequestCount,
+    #[msg("Invalid slow fill request accounts!")]
+    InvalidSlowFillRequestAccounts,
+    #[msg("Deposit nonce does not match the next nonce of the depositor!")]
+    NonceMismatch,
+    #[msg("Invalid vault!")]
+    InvalidVault,
+    #[msg("Message exceeds the max message size!")]
+    MessageTooLarge,
+    #[msg("Bundle refunds exceed the fill volume of the mint!")]
+    RefundsExceedFillVolume,
+    #[msg("State must be migrated to the current version!")]
+    StateNotMigrated,
+    #[msg("State already has the current version!")]
+    StateAlreadyMigrated,
+    #[msg("Lookup table must be the canonical lookup table of the spoke!")]
+    InvalidLookupTable,
+    #[msg("Lookup table can't hold more addresses!")]
+    LookupTableFull,
+    #[msg("Slow fill was not requested for the relay!")]
+    SlowFillNotRequested,
+    #[msg("Relay was already filled, so its slow fill can't be requested!")]
+    RelayAlreadyFilled,
+    #[msg("Slow fill was already requested for the relay!")]
+    SlowFillAlreadyRequested,
+    #[msg("Instruction is only available in test builds!")]
+    TestOnlyInstruction,
+    #[msg("Root bundle already has the current layout!")]
+    NotLegacyRootBundle,
+    #[msg("Route already has the current layout!")]
+    NotLegacyRoute,
+    #[msg("State vault was migrated to the vault authority!")]
+    StateVaultMigrated,
+    #[msg("Wind down delay is below the minimum!")]
+    WindDownDelayTooShort,
+    #[msg("Recipient token account creation requires the recipient address and token accounts!")]
+    MissingRecipientAta,
+    #[msg("Invalid recipient address for the recipient token account creation!")]
+    InvalidRecipientAta,
+    #[msg("Vault migration is blocked by failed checks!")]
+    VaultMigrationBlocked,
+    #[msg("Slow fill leaf targets another destination chain!")]
+    WrongDestinationChain,
 }
 
 // CCTP specific errors.
@@ -95,8 +395,10 @@ pub enum CallDataError {
     InvalidUint64,
     #[msg("Invalid solidity uint128 argument")]
     InvalidUint128,
-    #[msg("Unsupported solidity selector")]
-    UnsupportedSelector,
+    #[msg("Invalid solidity int8 argument")]
+    InvalidInt8,
+    #[msg("Unknown admin action")]
+    UnknownAdminAction,
 }
 
 // Across+ specific errors.
@@ -118,4 +420,20 @@ pub enum AcrossPlusError {
     NotWritableMessageAccountKey,
     #[msg("Missing value recipient key")]
     MissingValueRecipientKey,
+    #[msg("Message does not match recipient schema")]
+    MessageSchemaMismatch,
+    #[msg("Invalid message schema size bounds")]
+    InvalidMessageSchema,
+    #[msg("Unsupported action list message version")]
+    UnsupportedMessageVersion,
+    #[msg("Invalid message action count")]
+    InvalidMessageActionCount,
+    #[msg("Message action out of bounds")]
+    MessageActionOutOfBounds,
+    #[msg("Invalid message action program")]
+    InvalidMessageActionProgram,
+    #[msg("Message handler is not an executable program")]
+    MessageHandlerNotExecutable,
+    #[msg("Spoke owned accounts cannot be passed as writable to message handlers")]
+    ForbiddenHandlerAccount,
 }
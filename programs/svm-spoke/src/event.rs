@@ -1,4 +1,6 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::state::{DeliveryMode, ExecutionApprovalThreshold, GcAccountKind, SettlementMode};
 
 // Admin events
 #[event]
@@ -6,6 +8,23 @@ pub struct SetXDomainAdmin {
     pub new_admin: Pubkey,
 }
 
+#[event]
+pub struct SetAdminMessenger {
+    pub admin_messenger: Option<Pubkey>,
+}
+
+#[event]
+pub struct OwnershipTransferStarted {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferred {
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
 #[event]
 pub struct PausedDeposits {
     pub is_paused: bool,
@@ -16,11 +35,58 @@ pub struct PausedFills {
     pub is_paused: bool,
 }
 
+#[event]
+pub struct PausedFillsWithExpiry {
+    pub is_paused: bool,
+    pub expires_at: u32,
+}
+
+#[event]
+pub struct PauseExpired {
+    pub expires_at: u32,
+}
+
 #[event]
 pub struct EnabledDepositRoute {
     pub origin_token: Pubkey,
     pub destination_chain_id: u64,
     pub enabled: bool,
+    pub disable_at: u32,
+    pub decimal_shift: i8,
+}
+
+#[event]
+pub struct SetRouteSettlementMode {
+    pub origin_token: Pubkey,
+    pub destination_chain_id: u64,
+    pub settlement_mode: SettlementMode,
+}
+
+#[event]
+pub struct SetRouteMinRelayerFee {
+    pub origin_token: Pubkey,
+    pub destination_chain_id: u64,
+    pub min_relayer_fee_bps: u16,
+}
+
+#[event]
+pub struct SetComplianceAuthority {
+    pub compliance_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct AddedComplianceEntry {
+    pub subject: Pubkey,
+}
+
+#[event]
+pub struct RemovedComplianceEntry {
+    pub subject: Pubkey,
+}
+
+#[event]
+pub struct SetFillConfirmationOracle {
+    pub fill_confirmation_oracle: Option<Pubkey>,
 }
 
 #[event]
@@ -28,6 +94,241 @@ pub struct RelayedRootBundle {
     pub root_bundle_id: u32,
     pub relayer_refund_root: [u8; 32],
     pub slow_relay_root: [u8; 32],
+    pub execution_deadline: u32,
+    pub restricted_executor: Option<Pubkey>,
+    pub restricted_until: u32,
+}
+
+#[event]
+pub struct SetBundleAuditConfig {
+    pub enabled: bool,
+    pub max_mints: u8,
+}
+
+#[event]
+pub struct SetSlowFillRateLimit {
+    pub enabled: bool,
+    pub limit: u32,
+    pub window: u32,
+}
+
+#[event]
+pub struct SetDepositQuoteTimeBuffer {
+    pub deposit_quote_time_buffer: u32,
+}
+
+#[event]
+pub struct SetFillDeadlineBuffer {
+    pub fill_deadline_buffer: u32,
+}
+
+#[event]
+pub struct SetConfig {
+    pub key: [u8; 32],
+    pub value: [u8; 32],
+}
+
+// Full configuration and counters of the state, so that indexers can bootstrap without replaying all admin events.
+#[event]
+pub struct ConfigSnapshot {
+    pub snapshot_time: u32,
+    pub seed: u64,
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+    pub cross_domain_admin: Pubkey,
+    pub chain_id: u64,
+    pub remote_domain: u32,
+    pub network: u8,
+    pub paused_deposits: bool,
+    pub paused_fills: bool,
+    pub fills_pause_expires_at: u32,
+    pub number_of_deposits: u32,
+    pub root_bundle_id: u32,
+    pub deposit_quote_time_buffer: u32,
+    pub fill_deadline_buffer: u32,
+    pub winding_down: bool,
+    pub wind_down_time: u32,
+    pub wind_down_delay: u32,
+    pub compliance_authority: Option<Pubkey>,
+    pub bundle_audits_enabled: bool,
+    pub bundle_audit_max_mints: u8,
+    pub slow_fill_rate_limit_enabled: bool,
+    pub slow_fill_rate_limit: u32,
+    pub slow_fill_rate_window: u32,
+    pub slow_fill_grace_seconds: u32,
+    pub fill_confirmation_oracle: Option<Pubkey>,
+    pub dispute_freeze_until: u32,
+    pub protocol_fee_bps: u16,
+    pub protocol_fee_recipient: Option<Pubkey>,
+    pub event_commitments_enabled: bool,
+    pub liquidity_pools_enabled: bool,
+    pub liquidity_pool_fee_bps: u16,
+    pub fill_index_enabled: bool,
+    pub vault_outflow_rate_limits_enabled: bool,
+    pub event_forwarder: Option<Pubkey>,
+    pub event_forwarder_best_effort: bool,
+    pub permissioned_mode: bool,
+    pub strict_slow_fill_requester: bool,
+    pub upgrade_effective_after_slot: Option<u64>,
+    pub execution_approver: Option<Pubkey>,
+    pub execution_approval_validity: u32,
+    pub execution_approval_thresholds: Vec<ExecutionApprovalThreshold>,
+    pub snapshot_min_interval: u32,
+    pub lookup_table: Option<Pubkey>, // Canonical lookup table of the spoke, None until create_lookup_table.
+    pub exclusive_slow_fills_disabled: bool,
+    pub canonical: bool,
+}
+
+// Failed checks of a health_check run as HEALTH_CHECK_* bits. Not emitted when all checks pass.
+#[event]
+pub struct HealthCheckFailed {
+    pub state: Pubkey,
+    pub failed_checks: u32,
+}
+
+// Vault of a mint reported missing through report_missing_vault, e.g. when slow fills of the mint fail to execute.
+#[event]
+pub struct VaultMissing {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
+
+// Vault of a mint reported frozen by the freeze authority of the mint through report_frozen_vault.
+#[event]
+pub struct VaultFrozen {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
+
+// Relay marked unfilled past its fill deadline through mark_expired, so its deposit is refunded on the origin chain.
+#[event]
+pub struct FillStatusExpired {
+    pub relay_hash: [u8; 32],
+    pub origin_chain_id: u64,
+    pub deposit_id: [u8; 32],
+    pub fill_deadline: u32,
+}
+
+#[event]
+pub struct SetDisputeFreeze {
+    pub dispute_freeze_until: u32,
+}
+
+#[event]
+pub struct AnnouncedUpgrade {
+    pub effective_after_slot: u64,
+}
+
+#[event]
+pub struct ClearedUpgradeAnnouncement {
+    pub effective_after_slot: Option<u64>, // Cleared announcement, None when there was nothing to clear.
+}
+
+#[event]
+pub struct SetExecutionApprover {
+    pub execution_approver: Option<Pubkey>,
+    pub execution_approval_validity: u32,
+}
+
+#[event]
+pub struct SetExecutionApprovalThreshold {
+    pub mint: Pubkey,
+    pub threshold: Option<u64>, // None when the threshold of the mint was removed.
+}
+
+#[event]
+pub struct SetProtocolFee {
+    pub protocol_fee_bps: u16,
+    pub protocol_fee_recipient: Option<Pubkey>,
+}
+
+#[event]
+pub struct SetEventCommitments {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SetFillIndex {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SetEventForwarder {
+    pub event_forwarder: Option<Pubkey>,
+    pub best_effort: bool,
+}
+
+#[event]
+pub struct SetPermissionedMode {
+    pub enabled: bool,
+    pub strict_slow_fill_requester: bool,
+}
+
+#[event]
+pub struct SetAllowlistRoles {
+    pub account: Pubkey,
+    pub roles: u8,
+}
+
+#[event]
+pub struct SetSwapAdapter {
+    pub swap_program: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SetChainAlias {
+    pub alias: u64,
+    pub canonical_chain_id: u64,
+}
+
+#[event]
+pub struct RemovedChainAlias {
+    pub alias: u64,
+}
+
+#[event]
+pub struct ResetDepositorNonce {
+    pub depositor: Pubkey,
+    pub next_nonce: u64,
+}
+
+#[event]
+pub struct SetVaultOutflowRateLimits {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SetVaultOutflowRateLimit {
+    pub mint: Pubkey,
+    pub limit: u64,
+    pub window: u32,
+}
+
+#[event]
+pub struct SetFillVolumeTolerance {
+    pub mint: Pubkey,
+    pub tolerance: u64,
+}
+
+#[event]
+pub struct ReconciledFillVolume {
+    pub mint: Pubkey,
+    pub previous_fill_volume: u64,
+    pub fill_volume: u64,
+}
+
+#[event]
+pub struct SetDepositLimit {
+    pub mint: Pubkey,
+    pub destination_chain_id: u64,
+    pub max_outstanding: u64,
+}
+
+#[event]
+pub struct SetLiquidityPoolConfig {
+    pub enabled: bool,
+    pub fee_bps: u16,
 }
 
 #[event]
@@ -35,22 +336,138 @@ pub struct EmergencyDeletedRootBundle {
     pub root_bundle_id: u32,
 }
 
+#[event]
+pub struct StartedWindDown {
+    pub wind_down_time: u32,
+    pub wind_down_delay: u32,
+}
+
+#[event]
+pub struct SweptVault {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to_hub_pool: bool, // True if credited to the hub pool liability for CCTP bridging, false if sent to owner.
+}
+
+#[event]
+pub struct TokensSwept {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey, // Owner of the token account receiving the swept tokens.
+}
+
+// Previous and new output token amount reserved in the vault for requested slow fills of the mint.
+#[event]
+pub struct SlowFillLiabilityChanged {
+    pub mint: Pubkey,
+    pub previous_amount: u64,
+    pub pending_slow_fill_amount: u64,
+}
+
+#[event]
+pub struct MigratedVaultAuthority {
+    pub mint: Pubkey,
+    pub vault: Pubkey, // Vault ATA of the vault authority PDA.
+    pub amount: u64,   // Balance moved from the state vault.
+}
+
+#[event]
+pub struct VaultCreated {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub authority: Pubkey, // State or vault authority PDA owning the vault.
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct MigratedFrozenVault {
+    pub mint: Pubkey,
+    pub frozen_vault: Pubkey, // Frozen vault ATA of the state PDA, left in place until thawed.
+    pub vault: Pubkey,        // Vault ATA of the vault authority PDA, receiving all later vault flows.
+}
+
+#[event]
+pub struct MigratedState {
+    pub previous_version: u8,
+    pub version: u8,
+}
+
+#[event]
+pub struct ReconciledCounters {
+    pub mint: Pubkey,
+    pub old_pending_to_hub_pool: u64,
+    pub new_pending_to_hub_pool: u64,
+    pub forced: bool,
+}
+
+// Message schema events
+#[event]
+pub struct RegisteredMessageSchema {
+    pub recipient: Pubkey,
+    pub min_message_size: u32,
+    pub max_message_size: u32,
+    pub schema_tag: [u8; 4],
+}
+
+#[event]
+pub struct DeregisteredMessageSchema {
+    pub recipient: Pubkey,
+}
+
 // Deposit events
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct V3FundsDeposited {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub input_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub output_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub input_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub output_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub destination_chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub deposit_id: [u8; 32],
     pub quote_timestamp: u32,
     pub fill_deadline: u32,
     pub exclusivity_deadline: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub depositor: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub recipient: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub exclusive_relayer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes"))]
     pub message: Vec<u8>,
+    pub token_decimals: u8, // Decimals of the input token mint, so that consumers can render the input amount.
+    pub unsafe_deposit_id: bool, // Deposit ID was derived from the deposit nonce instead of the deposit counter.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::option_amount"))]
+    pub depositor_nonce: Option<u64>, // Only set for depositors that opted into ordered deposits with a nonce PDA.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::option_bytes32"))]
+    pub message_hash: Option<[u8; 32]>, // Only set for messages above MAX_INLINE_EVENT_MESSAGE_SIZE, emitted empty.
+    pub exclusivity_parameter: u32, // Raw deposit parameter that exclusivity_deadline was resolved from.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::option_amount"))]
+    pub dust_refunded: Option<u64>, // Only set for native deposits returning lamports left over in the wrap account.
+}
+
+// Emitted along with V3FundsDeposited by deposit_v3_with_swap, whose input_amount is the measured swap output.
+#[event]
+pub struct SwappedForDeposit {
+    pub deposit_id: [u8; 32],
+    pub swap_program: Pubkey,
+    pub swap_in_token: Pubkey,
+    pub swap_in_amount: u64, // Balance decrease of the depositor swap in token account over the swap.
+    pub input_token: Pubkey,
+    pub input_amount: u64,
+}
+
+#[event]
+pub struct DepositFilled {
+    pub deposit_id: [u8; 32],
+    pub destination_fill_event_hash: [u8; 32],
+    pub oracle: Pubkey,
 }
 
 // Fill events
@@ -62,62 +479,311 @@ pub enum FillType {
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct V3RelayExecutionEventInfo {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub updated_recipient: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub updated_message_hash: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub updated_output_amount: u64,
     pub fill_type: FillType,
+    pub root_bundle_id: Option<u32>, // Only set for slow fills executed from a relayed root bundle.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::option_pubkey"))]
+    pub escrow: Option<Pubkey>, // Only set when the payout was delivered to the recipient escrow.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
+    pub memo_hash: [u8; 32], // Zeroed when no payout memo was provided.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub normalized_output_amount: u64, // Updated output amount scaled by the decimal shift of the route.
+    pub repayment_token_account_created: bool, // Set when the fill created the repayment address ATA.
+    pub emergency_execution: bool, // Set when the emergency executor executed the slow fill leaf during a fills pause.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::option_bytes32"))]
+    pub origin_tx_ref: Option<[u8; 32]>, // Origin deposit transaction reference, only set when provided by the relayer.
+    pub message_only: bool, // Set when the zero updated output amount was not transferred and only the message ran.
 }
 
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct FilledV3Relay {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub input_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub output_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub input_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub output_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub repayment_chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub origin_chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub deposit_id: [u8; 32],
     pub fill_deadline: u32,
     pub exclusivity_deadline: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub exclusive_relayer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub relayer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub depositor: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub recipient: Pubkey,
     // TODO: update EVM implementation to use message_hash in all fill related events.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub message_hash: [u8; 32],
     pub relay_execution_info: V3RelayExecutionEventInfo,
+    pub token_decimals: u8, // Decimals of the output token mint, so that consumers can render the output amounts.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
+    pub relay_hash: [u8; 32], // Seed of the fill status PDA, so that relayers need not re-hash the relay data.
+}
+
+// Replay of a fill event from its stored fill status PDA. Only carries the stored fields and the replay flag lets
+// consumers dedupe it against the original FilledV3Relay event of the relay hash.
+#[event]
+pub struct FilledV3RelayReplay {
+    pub relay_hash: [u8; 32],
+    pub relayer: Pubkey,
+    pub fill_deadline: u32,
+    pub requester: Pubkey,
+    pub replay: bool,
 }
 
 // Slow fill events
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct RequestedV3SlowFill {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub input_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub output_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub input_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub output_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub origin_chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub deposit_id: [u8; 32],
     pub fill_deadline: u32,
     pub exclusivity_deadline: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub exclusive_relayer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub depositor: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub recipient: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
     pub message_hash: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
+    pub relay_hash: [u8; 32], // Seed of the fill status PDA, so that relayers need not re-hash the relay data.
+    pub current_time: u32, // Time of the request on this chain, so that batching does not rely on local clocks.
+    pub seconds_until_fill_deadline: u32, // Time left to fill the relay at the request, 0 at the fill deadline.
+}
+
+// Version 2 of RequestedV3SlowFill, emitted right after it, that also carries the destination chain, the requester and
+// the relay hash so that indexers shared by multiple deployments can dedupe requests without re-hashing the relay.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub struct RequestedV3SlowFill2 {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub input_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub output_token: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub input_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub output_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub origin_chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
+    pub deposit_id: [u8; 32],
+    pub fill_deadline: u32,
+    pub exclusivity_deadline: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub exclusive_relayer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub depositor: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub recipient: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
+    pub message_hash: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub destination_chain_id: u64, // Chain ID of the state that the slow fill was requested on.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub requester: Pubkey, // Requester recorded in the fill status PDA, i.e. the recipient of signed requests.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::bytes32"))]
+    pub relay_hash: [u8; 32],
+    pub message_only: bool, // Set when the relay has a zero output amount, so that the slow fill only runs the message.
+}
+
+// Summary of request_v3_slow_fills_batch, so that keepers can verify their batch without decoding every request event.
+#[event]
+pub struct RequestedV3SlowFillsBatch {
+    pub requester: Pubkey,
+    pub requested: u32, // Relays that had their slow fill requested, each with its own RequestedV3SlowFill event.
+    pub skipped: u32,   // Relays that were skipped as already filled, requested or expired.
+}
+
+#[event]
+pub struct CreatedExecutionApproval {
+    pub leaf_hash: [u8; 32],
+    pub approver: Pubkey,
+    pub approved_at: u32,
+}
+
+#[event]
+pub struct RevokedExecutionApproval {
+    pub leaf_hash: [u8; 32],
+    pub approver: Pubkey,
+}
+
+// Delivery events
+#[event]
+pub struct SetDeliveryPreference {
+    pub recipient: Pubkey,
+    pub mode: DeliveryMode,
+}
+
+#[event]
+pub struct ClearedDeliveryPreference {
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct WithdrewEscrow {
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AccruedProtocolFees {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WithdrewProtocolFees {
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProvidedLiquidity {
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct RemovedLiquidity {
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct AccruedLiquidityPoolFees {
+    pub mint: Pubkey,
+    pub amount: u64,
 }
 
 // Bundle refund events
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct ExecutedRelayerRefundRoot {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub amount_to_return: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount_vec"))]
     pub refund_amounts: Vec<u64>,
     pub root_bundle_id: u32,
     pub leaf_id: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub l2_token_address: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey_vec"))]
     pub refund_addresses: Vec<Pubkey>,
     pub deferred_refunds: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub caller: Pubkey,
+    pub token_decimals: u8, // Decimals of the l2 token mint, so that consumers can render the refund amounts.
+}
+
+// Emitted instead of ExecutedRelayerRefundRoot when it would exceed MAX_EVENT_CPI_DATA_SIZE. Each chunk carries a slice
+// of the refunds in leaf order, reassembled by (root_bundle_id, leaf_id, chunk_index).
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub struct ExecutedRelayerRefundRootChunk {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub amount_to_return: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
+    pub chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount_vec"))]
+    pub refund_amounts: Vec<u64>,
+    pub root_bundle_id: u32,
+    pub leaf_id: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub l2_token_address: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey_vec"))]
+    pub refund_addresses: Vec<Pubkey>,
+    pub deferred_refunds: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
+    pub caller: Pubkey,
+    pub token_decimals: u8,
+    pub chunk_index: u32,
+    pub total_chunks: u32,
+}
+
+// Emitted for refunds paid into the ATA of a refund address created by the leaf execution, e.g. for depositors refunded
+// for expired deposits. Refunds paid into existing ATAs are only part of ExecutedRelayerRefundRoot.
+#[event]
+pub struct RefundPaid {
+    pub root_bundle_id: u32,
+    pub leaf_id: u32,
+    pub mint: Pubkey,
+    pub refund_address: Pubkey,
+    pub token_account: Pubkey, // ATA of the refund address created at the expense of the executor.
+    pub amount: u64,
+}
+
+// Emitted for refunds accrued to the claim account of a refund address when executing leaves without deferred refunds,
+// as its ATA could not receive them. These have to be claimed with claim_relayer_refund or claim_refund_for.
+#[event]
+pub struct RefundDeferred {
+    pub root_bundle_id: u32,
+    pub leaf_id: u32,
+    pub mint: Pubkey,
+    pub refund_address: Pubkey,
+    pub claim_account: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted for refund leaves executed with an execution approval although the refunds of their bundle exceed the fill
+// volume of the mint observed in the bundle window plus its tolerance.
+#[event]
+pub struct ApprovedRefundsAboveFillVolume {
+    pub root_bundle_id: u32,
+    pub leaf_id: u32,
+    pub mint: Pubkey,
+    pub bundle_refunds: u64,
+    pub bundle_fill_volume: u64,
+    pub tolerance: u64,
+    pub approver: Pubkey,
+}
+
+#[event]
+pub struct BundleAuditSnapshot {
+    pub root_bundle_id: u32,
+    pub mint: Pubkey,
+    pub balance_before: u64,
+    pub balance_after: u64,
 }
 
 #[event]
@@ -131,6 +797,7 @@ pub struct ClaimedRelayerRefund {
 pub struct BridgedToHubPool {
     pub amount: u64,
     pub mint: Pubkey,
+    pub nonce: u64, // CCTP message nonce of the deposit_for_burn.
 }
 
 #[event]
@@ -141,3 +808,54 @@ pub struct TokensBridged {
     pub l2_token_address: Pubkey,
     pub caller: Pubkey,
 }
+
+// Garbage collection events
+#[event]
+pub struct GarbageCollectedAccount {
+    pub kind: GcAccountKind,
+    pub account: Pubkey,
+    pub caller: Pubkey,
+    pub caller_rent: u64,
+    pub original_payer: Option<Pubkey>,
+    pub original_payer_rent: u64,
+}
+
+#[event]
+pub struct ClosedStaleParamsBuffer {
+    pub payer: Pubkey,
+    pub closer: Pubkey,
+    pub created_at: u32,
+}
+
+// Relayer stats events
+#[event]
+pub struct RelayerStatsThresholdCrossed {
+    pub relayer: Pubkey,
+    pub fills_in_window: u64,
+    pub lapses: u64,
+    pub lapse_ratio_bps: u64,
+    pub threshold_bps: u16,
+    pub above_threshold: bool, // Lapse ratio is now above the threshold, false when it fell back to it or below.
+}
+
+// Lookup table events
+#[event]
+pub struct CreatedLookupTable {
+    pub lookup_table: Pubkey,
+    pub recent_slot: u64,
+}
+
+#[event]
+pub struct ExtendedLookupTable {
+    pub lookup_table: Pubkey,
+    pub addresses: Vec<Pubkey>, // Addresses appended to the table, skipping those it already held.
+}
+
+// Discriminators prefixing the CPI event data of the events decoded by indexers, so that external crates can match on
+// them without the Anchor Discriminator trait. Pinned in test/svm/fixtures/schema.json.
+pub const V3_FUNDS_DEPOSITED_DISCRIMINATOR: [u8; 8] = V3FundsDeposited::DISCRIMINATOR;
+pub const FILLED_V3_RELAY_DISCRIMINATOR: [u8; 8] = FilledV3Relay::DISCRIMINATOR;
+pub const REQUESTED_V3_SLOW_FILL_DISCRIMINATOR: [u8; 8] = RequestedV3SlowFill::DISCRIMINATOR;
+pub const REQUESTED_V3_SLOW_FILL_2_DISCRIMINATOR: [u8; 8] = RequestedV3SlowFill2::DISCRIMINATOR;
+pub const EXECUTED_RELAYER_REFUND_ROOT_DISCRIMINATOR: [u8; 8] = ExecutedRelayerRefundRoot::DISCRIMINATOR;
+pub const EXECUTED_RELAYER_REFUND_ROOT_CHUNK_DISCRIMINATOR: [u8; 8] = ExecutedRelayerRefundRootChunk::DISCRIMINATOR;
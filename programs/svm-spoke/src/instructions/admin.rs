@@ -1,27 +1,53 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::address_lookup_table,
+    system_program::{allocate, assign, transfer, Allocate, Assign, Transfer},
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    associated_token::{self, AssociatedToken},
+    token_2022::spl_token_2022::instruction::AuthorityType,
+    token_interface::{set_authority, Mint, SetAuthority, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    constants::DISCRIMINATOR_SIZE,
-    constraints::is_local_or_remote_owner,
+    constants::{
+        BPS_DENOMINATOR, CURRENT_STATE_VERSION, DISCRIMINATOR_SIZE, LEGACY_ROUTE_SIZE, MAX_DECIMAL_SHIFT,
+        MAX_DEPOSIT_BUFFER, MAX_ROUTE_UPDATES_PER_BATCH, MIN_WIND_DOWN_DELAY, RECONCILE_COUNTERS_MAX_DEVIATION_BPS,
+        VAULT_MIGRATION_CLAIMS, VAULT_MIGRATION_FROZEN, VAULT_MIGRATION_POOL_LIQUIDITY, VAULT_MIGRATION_SLOW_FILLS,
+        VAULT_MIGRATION_STATE,
+    },
+    constraints::{get_network_for_chain_id, is_local_or_remote_owner, is_network_chain_id_valid},
     error::SvmError,
     event::{
-        EmergencyDeletedRootBundle, EnabledDepositRoute, PausedDeposits, PausedFills, RelayedRootBundle,
-        SetXDomainAdmin,
+        AnnouncedUpgrade, ClearedUpgradeAnnouncement, EmergencyDeletedRootBundle, EnabledDepositRoute,
+        ExtendedLookupTable, MigratedFrozenVault, MigratedState, MigratedVaultAuthority, OwnershipTransferStarted,
+        OwnershipTransferred, PausedDeposits, PausedFills, PausedFillsWithExpiry, ReconciledCounters,
+        RelayedRootBundle, SetAdminMessenger, SetBundleAuditConfig, SetConfig, SetDepositQuoteTimeBuffer,
+        SetDisputeFreeze, SetFillDeadlineBuffer, SetRouteMinRelayerFee, SetRouteSettlementMode, SetSlowFillRateLimit,
+        SetXDomainAdmin, StartedWindDown, SweptVault, TokensSwept,
+    },
+    state::{
+        LookupTableConfig, RateLimit, RootBundle, Route, SettlementMode, State, TransferLiability, VaultLiability,
+    },
+    token_messenger_minter::{self, accounts::LocalToken},
+    utils::{
+        assert_closes_not_frozen, consume_idempotency_key, decode_solidity_bool, decode_solidity_uint32,
+        extend_state_lookup_table, get_canonical_ata, get_corridor_lookup_table_addresses, get_current_time,
+        get_pending_claim_amount, get_pending_slow_fill_amount, get_pool_liquidity, has_unsupported_extension,
+        initialize_current_time, is_canonical_token_program, is_migrated_state_vault, is_vault, is_vault_address,
+        is_vault_frozen, pay_from_vault, set_seed, transfer_from, OrArithmeticOverflow, VaultSigner,
     },
-    state::{RootBundle, Route, State},
-    utils::{initialize_current_time, set_seed},
 };
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
 pub struct Initialize<'info> {
+    /// Deployer signing the initialization. Becomes the state owner and pays the state PDA rent.
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Spoke state PDA, seeds ["state", seed]. Created here, so it must not exist yet.
     #[account(
         init, // Use init, not init_if_needed to prevent re-initialization.
         payer = signer,
@@ -31,6 +57,7 @@ pub struct Initialize<'info> {
     )]
     pub state: Account<'info, State>,
 
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
@@ -43,7 +70,12 @@ pub fn initialize(
     cross_domain_admin: Pubkey,      // HubPool on Mainnet Ethereum.
     deposit_quote_time_buffer: u32,  // Deposit quote times can't be set more than this amount into the past/future.
     fill_deadline_buffer: u32,       // Fill deadlines can't be set more than this amount into the future.
+    network: u8,                     // Network tag (mainnet/devnet/localnet) that chain_id must belong to.
 ) -> Result<()> {
+    if !is_network_chain_id_valid(network, chain_id) {
+        return err!(SvmError::InvalidNetworkChainId);
+    }
+
     let state = &mut ctx.accounts.state;
     state.owner = *ctx.accounts.signer.key;
     state.number_of_deposits = initial_number_of_deposits;
@@ -52,21 +84,93 @@ pub fn initialize(
     state.cross_domain_admin = cross_domain_admin;
     state.deposit_quote_time_buffer = deposit_quote_time_buffer;
     state.fill_deadline_buffer = fill_deadline_buffer;
+    state.network = network;
+    state.genesis = true;
+    state.version = CURRENT_STATE_VERSION;
 
     // Set seed and initialize current time. Both enable testing functionality and are no-ops in production.
     set_seed(state, seed)?;
     initialize_current_time(state)?;
 
+    // Single-state builds only initialize the state of seed 0, which is the canonical one.
+    state.canonical = cfg!(feature = "single-state");
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MigrateState<'info> {
+    /// Owner migrating the state, checked against the state deserialized in the handler. Writable signer paying the
+    /// rent for its extended size.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Writable state PDA of a previous version, seeds ["state", seed]. Deserialized in the handler, as its size
+    /// predates the current layout.
+    #[account(mut, owner = crate::ID, seeds = [b"state", seed.to_le_bytes().as_ref()], bump)]
+    pub state: UncheckedAccount<'info>,
+
+    /// System program, used to transfer the rent of the extended size.
+    pub system_program: Program<'info, System>,
+}
+
+// Fields appended since the previous version are read from the zero padding of its data, so that they default to zero
+// unless they are set below from the fields the previous version already had.
+pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let space = DISCRIMINATOR_SIZE + State::INIT_SPACE;
+    let mut data = state_info.try_borrow_data()?.to_vec();
+    data.resize(std::cmp::max(space, data.len()), 0);
+    let mut state = State::try_deserialize(&mut &data[..])?;
+    if state.owner != ctx.accounts.signer.key() {
+        return err!(SvmError::NotOwner);
+    }
+    if state.seed != state.pda_seed() {
+        return err!(SvmError::InvalidProductionSeed);
+    }
+    if state.version >= CURRENT_STATE_VERSION {
+        return err!(SvmError::StateAlreadyMigrated);
+    }
+    let previous_version = state.version;
+    state.version = CURRENT_STATE_VERSION;
+    // Unversioned states may predate the network, whose zero padding would read as mainnet whatever their chain ID.
+    if previous_version == 0 {
+        state.network = get_network_for_chain_id(state.chain_id);
+    }
+    if previous_version < 3 {
+        state.canonical = cfg!(feature = "single-state");
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(state_info.lamports());
+    if required_lamports > 0 {
+        let transfer_accounts = Transfer { from: ctx.accounts.signer.to_account_info(), to: state_info.clone() };
+        let cpi_context = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts);
+        transfer(cpi_context, required_lamports)?;
+    }
+    state_info.realloc(space, false)?;
+    state.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
+
+    emit_cpi!(MigratedState { previous_version, version: CURRENT_STATE_VERSION });
+
     Ok(())
 }
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct PauseDeposits<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
     #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
-    #[account(mut, seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 }
 
@@ -82,34 +186,98 @@ pub fn pause_deposits(ctx: Context<PauseDeposits>, pause: bool) -> Result<()> {
 #[event_cpi]
 #[derive(Accounts)]
 pub struct PauseFills<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
     #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
-    #[account(mut, seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 }
 
 pub fn pause_fills(ctx: Context<PauseFills>, pause: bool) -> Result<()> {
     let state = &mut ctx.accounts.state;
     state.paused_fills = pause;
+    state.fills_pause_expires_at = 0;
 
     emit_cpi!(PausedFills { is_paused: pause });
 
     Ok(())
 }
 
+pub fn pause_fills_with_expiry(ctx: Context<PauseFills>, paused: bool, expires_at: u32) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+
+    // Zero expiry keeps the pause indefinite, while unpausing always clears the expiry.
+    let expires_at = if paused { expires_at } else { 0 };
+    if expires_at != 0 && expires_at <= get_current_time(state)? {
+        return err!(SvmError::InvalidPauseExpiry);
+    }
+
+    state.paused_fills = paused;
+    state.fills_pause_expires_at = expires_at;
+
+    emit_cpi!(PausedFillsWithExpiry { is_paused: paused, expires_at });
+
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct TransferOwnership<'info> {
+    /// Current owner of the state. Cannot be relayed remotely.
     #[account(address = state.owner @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
-    #[account(mut, seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 }
 
+// Only proposes the new owner, so that ownership is not lost to a mistyped key. New proposals replace the pending one.
 pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pubkey) -> Result<()> {
     let state = &mut ctx.accounts.state;
-    state.owner = new_owner;
+    state.pending_owner = Some(new_owner);
+
+    emit_cpi!(OwnershipTransferStarted { owner: state.owner, pending_owner: new_owner });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    /// Pending owner proposed by the current owner through transfer_ownership.
+    #[account(constraint = state.pending_owner == Some(signer.key()) @ SvmError::NotPendingOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let previous_owner = state.owner;
+    state.owner = ctx.accounts.signer.key();
+    state.pending_owner = None;
+
+    emit_cpi!(OwnershipTransferred { previous_owner, new_owner: state.owner });
 
     Ok(())
 }
@@ -117,10 +285,17 @@ pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pubkey) ->
 #[event_cpi]
 #[derive(Accounts)]
 pub struct SetCrossDomainAdmin<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
     #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
-    #[account(mut, seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 }
 
@@ -133,19 +308,54 @@ pub fn set_cross_domain_admin(ctx: Context<SetCrossDomainAdmin>, cross_domain_ad
     Ok(())
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAdminMessengerState<'info> {
+    /// Current owner of the state. Cannot be relayed remotely, so that a messenger cannot replace itself.
+    #[account(address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_admin_messenger(ctx: Context<SetAdminMessengerState>, admin_messenger: Option<Pubkey>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.admin_messenger = admin_messenger;
+
+    emit_cpi!(SetAdminMessenger { admin_messenger });
+
+    Ok(())
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(origin_token: Pubkey, destination_chain_id: u64)]
 pub struct SetEnableRoute<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
     #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
+    /// Pays the rent for the route PDA and vault ATA if they are created. Writable signer.
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
+    /// PDA to store route information for this particular token & chainId pair. Seeds ["route", origin_token, seed,
+    /// destination_chain_id], created if missing.
     #[account(
         init_if_needed,
         payer = payer,
@@ -158,17 +368,19 @@ pub struct SetEnableRoute<'info> {
         ],
         bump
     )]
-    pub route: Account<'info, Route>, // PDA to store route information for this particular token & chainId pair.
+    pub route: Account<'info, Route>,
 
+    /// ATA, owned by the vault authority PDA, to store the origin token for spoke. Created if missing.
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = origin_token_mint,
-        associated_token::authority = state,
+        associated_token::authority = vault_authority,
         associated_token::token_program = token_program
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>, // ATA, owned by the state, to store the origin token for spoke.
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Mint of the origin token. Must match the origin_token parameter.
     #[account(
         mint::token_program = token_program,
         // IDL build fails when requiring address = origin_token for mint, thus using a custom constraint.
@@ -176,9 +388,31 @@ pub struct SetEnableRoute<'info> {
     )]
     pub origin_token_mint: InterfaceAccount<'info, Mint>,
 
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
+    /// Associated token program, used to create associated token accounts.
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
+
+    /// Lookup table config PDA, seeds ["lookup_table_config", seed]. When provided, enabled routes extend the
+    /// canonical lookup table with the corridor addresses. Pass this program ID to represent None.
+    #[account(seeds = [b"lookup_table_config", state.seed.to_le_bytes().as_ref()], bump)]
+    pub lookup_table_config: Option<Account<'info, LookupTableConfig>>,
+
+    /// CHECK: Canonical lookup table of the spoke, checked against the lookup table config. Writable to be extended.
+    /// Pass this program ID to represent None.
+    #[account(mut)]
+    pub lookup_table: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Address Lookup Table program. Pass this program ID to represent None.
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault. Declared last so that existing
+    /// clients only insert it ahead of the event accounts.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
 }
 
 pub fn set_enable_route(
@@ -186,79 +420,1421 @@ pub fn set_enable_route(
     origin_token: Pubkey,
     destination_chain_id: u64,
     enabled: bool,
+    disable_at: u32,
+    decimal_shift: i8,
+) -> Result<()> {
+    let current_time = get_current_time(&ctx.accounts.state)?;
+    let route = &mut ctx.accounts.route;
+    route.bump = ctx.bumps.route;
+    route.destination_chain_id = destination_chain_id;
+    update_route(route, current_time, &ctx.accounts.origin_token_mint, enabled, disable_at, decimal_shift)?;
+
+    emit_cpi!(EnabledDepositRoute { origin_token, destination_chain_id, enabled, disable_at, decimal_shift });
+
+    // Disabled routes are kept in the lookup table, as their corridor addresses are still used by pending fills.
+    if !enabled {
+        return Ok(());
+    }
+    if let Some(lookup_table_config) = &ctx.accounts.lookup_table_config {
+        let lookup_table = match &ctx.accounts.lookup_table {
+            Some(lookup_table) if lookup_table.key() == lookup_table_config.lookup_table => lookup_table,
+            _ => return err!(SvmError::InvalidLookupTable),
+        };
+        let address_lookup_table_program =
+            ctx.accounts.address_lookup_table_program.as_ref().ok_or(SvmError::InvalidLookupTable)?;
+
+        let corridor_addresses = get_corridor_lookup_table_addresses(
+            &ctx.accounts.state,
+            &origin_token,
+            &ctx.accounts.vault.key(),
+            &ctx.accounts.route.key(),
+        );
+        let addresses = extend_state_lookup_table(
+            &lookup_table.to_account_info(),
+            &ctx.accounts.state,
+            ctx.bumps.state,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &address_lookup_table_program.to_account_info(),
+            &corridor_addresses,
+        )?;
+        if !addresses.is_empty() {
+            emit_cpi!(ExtendedLookupTable { lookup_table: lookup_table.key(), addresses });
+        }
+    }
+
+    Ok(())
+}
+
+// Shared by set_enable_route and set_enable_routes_batch, so that both validate and apply route updates the same way.
+fn update_route(
+    route: &mut Route,
+    current_time: u32,
+    origin_token_mint: &InterfaceAccount<Mint>,
+    enabled: bool,
+    disable_at: u32,
+    decimal_shift: i8,
 ) -> Result<()> {
-    ctx.accounts.route.enabled = enabled;
+    if decimal_shift.unsigned_abs() > MAX_DECIMAL_SHIFT {
+        return err!(SvmError::InvalidDecimalShift);
+    }
+    // Routes of such mints can still be disabled.
+    if enabled && has_unsupported_extension(origin_token_mint)? {
+        return err!(SvmError::UnsupportedMintExtension);
+    }
+
+    // Repeated disables keep the original disable time, so that garbage collection eligibility is not postponed.
+    route.disabled_at = match enabled {
+        true => 0,
+        false if route.enabled || route.disabled_at == 0 => current_time,
+        false => route.disabled_at,
+    };
+    route.enabled = enabled;
+    route.disable_at = disable_at; // Each call replaces any previously scheduled disable time.
 
-    emit_cpi!(EnabledDepositRoute { origin_token, destination_chain_id, enabled });
+    // Deposits quote amounts assuming the decimals at the time the route was enabled, and fills of the corridor scale
+    // their output amounts by the shift configured at the same time.
+    if enabled {
+        route.expected_decimals = origin_token_mint.decimals;
+        route.decimal_shift = decimal_shift;
+    }
 
     Ok(())
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RouteUpdate {
+    pub origin_token: Pubkey,
+    pub destination_chain_id: u64,
+    pub enabled: bool,
+    pub disable_at: u32,
+    pub decimal_shift: i8,
+}
+
 #[event_cpi]
 #[derive(Accounts)]
-pub struct RelayRootBundle<'info> {
+pub struct SetEnableRoutesBatch<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
     #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
+    /// Pays the rent for the route PDAs and vault ATAs that are created. Writable signer.
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(mut, seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
+    /// SPL Token or Token-2022 program owning the mints of all route updates.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Associated token program, used to create associated token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vaults. Declared last so that
+    /// existing clients only insert it ahead of the event accounts.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+// Remaining accounts hold the writable route PDA, the writable vault ATA and the origin token mint of each route update
+// in order. Route PDAs and vaults are created if missing as in set_enable_route, and any invalid update reverts the
+// whole batch.
+pub fn set_enable_routes_batch<'c, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SetEnableRoutesBatch<'info>>,
+    route_updates: Vec<RouteUpdate>,
+) -> Result<()>
+where
+    'c: 'info,
+{
+    if route_updates.is_empty() || route_updates.len() > MAX_ROUTE_UPDATES_PER_BATCH {
+        return err!(SvmError::InvalidRouteUpdateCount);
+    }
+    if ctx.remaining_accounts.len() != route_updates.len() * 3 {
+        return err!(SvmError::InvalidRouteUpdateAccounts);
+    }
+
+    let current_time = get_current_time(&ctx.accounts.state)?;
+    let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
+
+    for (i, (route_update, accounts)) in route_updates.into_iter().zip(ctx.remaining_accounts.chunks(3)).enumerate() {
+        let RouteUpdate { origin_token, destination_chain_id, enabled, disable_at, decimal_shift } = route_update;
+        let (route_info, vault_info, mint_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+        let mint_account_name = format!("remaining_accounts[{}]", i * 3 + 2);
+        if mint_info.key() != origin_token {
+            return Err(Error::from(SvmError::InvalidMint).with_account_name(&mint_account_name));
+        }
+        if mint_info.owner != ctx.accounts.token_program.key {
+            return Err(Error::from(ErrorCode::ConstraintMintTokenProgram).with_account_name(&mint_account_name));
+        }
+        let origin_token_mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+
+        let destination_chain_id_bytes = destination_chain_id.to_le_bytes();
+        let route_seeds =
+            [b"route".as_ref(), origin_token.as_ref(), state_seed_bytes.as_ref(), destination_chain_id_bytes.as_ref()];
+        let (route_key, bump) = Pubkey::find_program_address(&route_seeds, &crate::ID);
+        if route_info.key() != route_key {
+            return Err(Error::from(ErrorCode::ConstraintSeeds)
+                .with_account_name(format!("remaining_accounts[{}]", i * 3))
+                .with_pubkeys((route_info.key(), route_key)));
+        }
+
+        let mut route = match route_info.owner == &crate::ID {
+            true => Account::<Route>::try_from(route_info)?,
+            false => {
+                let bump_seed = [bump];
+                let signer_seeds = [route_seeds[0], route_seeds[1], route_seeds[2], route_seeds[3], &bump_seed];
+                create_route_account(&ctx.accounts.payer, route_info, &ctx.accounts.system_program, &signer_seeds)?;
+                // Created accounts hold zeroed data without the discriminator, same as init_if_needed accounts.
+                Account::<Route>::try_from_unchecked(route_info)?
+            }
+        };
+        route.bump = bump;
+        route.destination_chain_id = destination_chain_id;
+        update_route(&mut route, current_time, &origin_token_mint, enabled, disable_at, decimal_shift)?;
+        // Persist the updated route (Anchor handles this only for static accounts).
+        route.exit(&crate::ID)?;
+
+        // The ATA creation CPI checks that the vault is the vault authority ATA of the mint.
+        let cpi_accounts = associated_token::Create {
+            payer: ctx.accounts.payer.to_account_info(),
+            associated_token: vault_info.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+            mint: mint_info.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.associated_token_program.to_account_info(), cpi_accounts);
+        associated_token::create_idempotent(cpi_ctx)?;
+
+        emit_cpi!(EnabledDepositRoute { origin_token, destination_chain_id, enabled, disable_at, decimal_shift });
+    }
+
+    Ok(())
+}
+
+// Funds, allocates and assigns the route PDA as Anchor init_if_needed does, which also succeeds when it was prefunded.
+fn create_route_account<'info>(
+    payer: &Signer<'info>,
+    route_info: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let space = DISCRIMINATOR_SIZE + Route::INIT_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(route_info.lamports());
+    if required_lamports > 0 {
+        let transfer_accounts = Transfer { from: payer.to_account_info(), to: route_info.clone() };
+        transfer(CpiContext::new(system_program.to_account_info(), transfer_accounts), required_lamports)?;
+    }
+
+    let allocate_accounts = Allocate { account_to_allocate: route_info.clone() };
+    allocate(
+        CpiContext::new_with_signer(system_program.to_account_info(), allocate_accounts, &[signer_seeds]),
+        space as u64,
+    )?;
+
+    let assign_accounts = Assign { account_to_assign: route_info.clone() };
+    assign(CpiContext::new_with_signer(system_program.to_account_info(), assign_accounts, &[signer_seeds]), &crate::ID)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(origin_token: Pubkey, destination_chain_id: u64)]
+pub struct SetRouteSettlement<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
     #[account(
-        init, // Init to create root bundle account. Prevents re-initialization for a given root..
-        payer = payer,
-        space = DISCRIMINATOR_SIZE + RootBundle::INIT_SPACE,
-        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), state.root_bundle_id.to_le_bytes().as_ref()],
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Writable route PDA, seeds ["route", origin_token, seed, destination_chain_id]. Route must have been created with
+    /// set_enable_route.
+    #[account(
+        mut,
+        seeds = [
+            b"route",
+            origin_token.as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            destination_chain_id.to_le_bytes().as_ref(),
+        ],
+        bump = route.bump
+    )]
+    pub route: Account<'info, Route>,
+}
+
+pub fn set_route_settlement_mode(
+    ctx: Context<SetRouteSettlement>,
+    origin_token: Pubkey,
+    destination_chain_id: u64,
+    settlement_mode: SettlementMode,
+) -> Result<()> {
+    ctx.accounts.route.settlement_mode = settlement_mode.clone();
+
+    emit_cpi!(SetRouteSettlementMode { origin_token, destination_chain_id, settlement_mode });
+
+    Ok(())
+}
+
+// Reuses the SetRouteSettlement accounts, as both only update an existing route.
+pub fn set_route_min_relayer_fee(
+    ctx: Context<SetRouteSettlement>,
+    origin_token: Pubkey,
+    destination_chain_id: u64,
+    min_relayer_fee_bps: u16,
+) -> Result<()> {
+    if min_relayer_fee_bps as u64 > BPS_DENOMINATOR {
+        return err!(SvmError::InvalidRelayerFeeFloor);
+    }
+    ctx.accounts.route.min_relayer_fee_bps = min_relayer_fee_bps;
+
+    emit_cpi!(SetRouteMinRelayerFee { origin_token, destination_chain_id, min_relayer_fee_bps });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(origin_token: Pubkey, destination_chain_id: u64)]
+pub struct MigrateRoute<'info> {
+    /// Anyone can migrate legacy route PDAs. Writable signer paying the rent for their extended size.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Writable route PDA of the legacy layout, seeds ["route", origin_token, seed, destination_chain_id].
+    /// Deserialized in the handler, as its size predates the current layout.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"route",
+            origin_token.as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            destination_chain_id.to_le_bytes().as_ref(),
+        ],
         bump
     )]
-    pub root_bundle: Account<'info, RootBundle>,
+    pub route: UncheckedAccount<'info>,
+
+    /// Mint of the origin token, whose decimals are snapshotted. Must match the origin_token parameter.
+    #[account(constraint = origin_token_mint.key() == origin_token @ SvmError::InvalidMint)]
+    pub origin_token_mint: InterfaceAccount<'info, Mint>,
 
+    /// System program, used to transfer the rent of the extended size.
     pub system_program: Program<'info, System>,
 }
 
-pub fn relay_root_bundle(
-    ctx: Context<RelayRootBundle>,
-    relayer_refund_root: [u8; 32],
-    slow_relay_root: [u8; 32],
-) -> Result<()> {
-    let state = &mut ctx.accounts.state;
-    let root_bundle = &mut ctx.accounts.root_bundle;
-    root_bundle.relayer_refund_root = relayer_refund_root;
-    root_bundle.slow_relay_root = slow_relay_root;
+pub fn migrate_route(ctx: Context<MigrateRoute>, destination_chain_id: u64) -> Result<()> {
+    let route_info = ctx.accounts.route.to_account_info();
+    if route_info.data_len() != LEGACY_ROUTE_SIZE {
+        return err!(SvmError::NotLegacyRoute);
+    }
+
+    let mut route = Route::try_deserialize_any_layout(&route_info.try_borrow_data()?)?;
+    route.migrate_legacy_layout(ctx.bumps.route, destination_chain_id, ctx.accounts.origin_token_mint.decimals);
+
+    let space = DISCRIMINATOR_SIZE + Route::INIT_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(route_info.lamports());
+    if required_lamports > 0 {
+        let transfer_accounts = Transfer { from: ctx.accounts.signer.to_account_info(), to: route_info.clone() };
+        let cpi_context = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts);
+        transfer(cpi_context, required_lamports)?;
+    }
+    route_info.realloc(space, false)?;
+
+    let mut data = route_info.try_borrow_mut_data()?;
+    route.try_serialize(&mut &mut data[..])
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetBundleAuditConfigState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
 
-    emit_cpi!(RelayedRootBundle { root_bundle_id: state.root_bundle_id, relayer_refund_root, slow_relay_root });
+pub fn set_bundle_audit_config(ctx: Context<SetBundleAuditConfigState>, enabled: bool, max_mints: u8) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.bundle_audits_enabled = enabled;
+    state.bundle_audit_max_mints = max_mints;
 
-    state.root_bundle_id += 1;
+    emit_cpi!(SetBundleAuditConfig { enabled, max_mints });
 
     Ok(())
 }
 
 #[event_cpi]
 #[derive(Accounts)]
-#[instruction(root_bundle_id: u32)]
-pub struct EmergencyDeleteRootBundleState<'info> {
+pub struct SetSlowFillRateLimitState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
     #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
     pub signer: Signer<'info>,
 
-    #[account(mut)]
-    // We do not restrict who can receive lamports from closing root_bundle account as that would require storing the
-    // original payer when root bundle was relayed and unnecessarily make it more expensive to relay in the happy path.
-    pub closer: SystemAccount<'info>,
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_slow_fill_rate_limit(
+    ctx: Context<SetSlowFillRateLimitState>,
+    enabled: bool,
+    limit: u32,
+    window: u32,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.slow_fill_rate_limit_enabled = enabled;
+    state.slow_fill_rate_limit = limit;
+    state.slow_fill_rate_window = window;
+
+    emit_cpi!(SetSlowFillRateLimit { enabled, limit, window });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDepositBufferState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
+}
 
-    #[account(mut,
-        seeds =[b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
-        close = closer,
-        bump)]
-    pub root_bundle: Account<'info, RootBundle>,
+pub fn set_deposit_quote_time_buffer(
+    ctx: Context<SetDepositBufferState>,
+    deposit_quote_time_buffer: u32,
+) -> Result<()> {
+    ctx.accounts.state.deposit_quote_time_buffer = validate_deposit_buffer(deposit_quote_time_buffer)?;
+
+    emit_cpi!(SetDepositQuoteTimeBuffer { deposit_quote_time_buffer });
+
+    Ok(())
 }
 
-pub fn emergency_delete_root_bundle(ctx: Context<EmergencyDeleteRootBundleState>, root_bundle_id: u32) -> Result<()> {
-    emit_cpi!(EmergencyDeletedRootBundle { root_bundle_id });
+pub fn set_fill_deadline_buffer(ctx: Context<SetDepositBufferState>, fill_deadline_buffer: u32) -> Result<()> {
+    ctx.accounts.state.fill_deadline_buffer = validate_deposit_buffer(fill_deadline_buffer)?;
+
+    emit_cpi!(SetFillDeadlineBuffer { fill_deadline_buffer });
 
     Ok(())
 }
+
+// A zero buffer would reject all deposits not quoted or expiring at the current time.
+fn validate_deposit_buffer(buffer: u32) -> Result<u32> {
+    if buffer == 0 || buffer > MAX_DEPOSIT_BUFFER {
+        return err!(SvmError::InvalidDepositBuffer);
+    }
+
+    Ok(buffer)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetConfigState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Writable admin nonce PDA recording the idempotency key, seeds ["admin_nonce", seed, idempotency_key].
+    /// Only required when passing an idempotency key, in which case the signer must be writable to pay its rent.
+    /// Pass this program ID to represent None.
+    #[account(mut)]
+    pub admin_nonce: Option<UncheckedAccount<'info>>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_config(
+    ctx: Context<SetConfigState>,
+    key: [u8; 32],
+    value: [u8; 32],
+    idempotency_key: Option<[u8; 16]>,
+) -> Result<bool> {
+    if !consume_idempotency_key(
+        idempotency_key,
+        &ctx.accounts.state,
+        ctx.accounts.admin_nonce.as_ref(),
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )? {
+        return Ok(false);
+    }
+
+    let state = &mut ctx.accounts.state;
+
+    // Keys are State field names right padded with zero bytes, same as bytes32 string literals in Solidity. Values are
+    // ABI encoded words.
+    let key_len = key.iter().rposition(|&byte| byte != 0).map_or(0, |index| index + 1);
+    match &key[..key_len] {
+        b"deposit_quote_time_buffer" => {
+            state.deposit_quote_time_buffer = validate_deposit_buffer(decode_solidity_uint32(&value)?)?
+        }
+        b"fill_deadline_buffer" => {
+            state.fill_deadline_buffer = validate_deposit_buffer(decode_solidity_uint32(&value)?)?
+        }
+        b"slow_fill_rate_limit" => state.slow_fill_rate_limit = decode_solidity_uint32(&value)?,
+        b"slow_fill_rate_window" => state.slow_fill_rate_window = decode_solidity_uint32(&value)?,
+        b"slow_fill_grace_seconds" => state.slow_fill_grace_seconds = decode_solidity_uint32(&value)?,
+        b"snapshot_min_interval" => state.snapshot_min_interval = decode_solidity_uint32(&value)?,
+        b"params_buffer_ttl" => state.params_buffer_ttl = decode_solidity_uint32(&value)?,
+        b"relayer_lapse_threshold_bps" => {
+            let threshold_bps = decode_solidity_uint32(&value)?;
+            if threshold_bps as u64 > BPS_DENOMINATOR {
+                return err!(SvmError::InvalidRelayerLapseThreshold);
+            }
+            state.relayer_lapse_threshold_bps = threshold_bps as u16;
+        }
+        b"strict_chain_aliases" => state.strict_chain_aliases = decode_solidity_bool(&value)?,
+        b"exclusive_slow_fills_disabled" => state.exclusive_slow_fills_disabled = decode_solidity_bool(&value)?,
+        // Zero address clears the emergency executor.
+        b"emergency_executor" => {
+            let emergency_executor = Pubkey::new_from_array(value);
+            state.emergency_executor = (emergency_executor != Pubkey::default()).then_some(emergency_executor);
+        }
+        _ => return err!(SvmError::UnknownConfigKey),
+    }
+
+    emit_cpi!(SetConfig { key, value });
+
+    Ok(true)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDisputeFreezeState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_dispute_freeze(ctx: Context<SetDisputeFreezeState>, dispute_freeze_until: u32) -> Result<()> {
+    ctx.accounts.state.dispute_freeze_until = dispute_freeze_until;
+
+    emit_cpi!(SetDisputeFreeze { dispute_freeze_until });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpgradeAnnouncementState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn announce_upgrade(ctx: Context<UpgradeAnnouncementState>, effective_after_slot: u64) -> Result<()> {
+    ctx.accounts.state.upgrade_effective_after_slot = Some(effective_after_slot);
+
+    emit_cpi!(AnnouncedUpgrade { effective_after_slot });
+
+    Ok(())
+}
+
+pub fn clear_upgrade_announcement(ctx: Context<UpgradeAnnouncementState>) -> Result<()> {
+    let effective_after_slot = ctx.accounts.state.upgrade_effective_after_slot.take();
+
+    emit_cpi!(ClearedUpgradeAnnouncement { effective_after_slot });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RelayRootBundle<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Pays the rent for the root bundle PDA. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to increment root_bundle_id.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Root bundle PDA created for the next bundle, seeds ["root_bundle", seed, state.root_bundle_id].
+    #[account(
+        init, // Init to create root bundle account. Prevents re-initialization for a given root..
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + RootBundle::INIT_SPACE,
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), state.root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub root_bundle: Account<'info, RootBundle>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Writable admin nonce PDA recording the idempotency key, seeds ["admin_nonce", seed, idempotency_key].
+    /// Only required when passing an idempotency key. Pass this program ID to represent None.
+    #[account(mut)]
+    pub admin_nonce: Option<UncheckedAccount<'info>>,
+}
+
+pub fn relay_root_bundle(
+    ctx: Context<RelayRootBundle>,
+    relayer_refund_root: [u8; 32],
+    slow_relay_root: [u8; 32],
+    execution_deadline: u32,
+    restricted_executor: Option<Pubkey>,
+    executor_grace_period: u32,
+    idempotency_key: Option<[u8; 16]>,
+) -> Result<bool> {
+    if !consume_idempotency_key(
+        idempotency_key,
+        &ctx.accounts.state,
+        ctx.accounts.admin_nonce.as_ref(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )? {
+        // Resubmissions derive the root bundle PDA from the incremented root_bundle_id, so the empty root bundle that
+        // was just created is closed again to not block relaying the next bundle.
+        return ctx.accounts.root_bundle.close(ctx.accounts.payer.to_account_info()).map(|_| false);
+    }
+
+    let state = &mut ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+
+    // The restriction window starts when the bundle is relayed and has no effect without a restricted executor.
+    let restricted_until = match restricted_executor {
+        Some(_) => current_time.saturating_add(executor_grace_period),
+        None => 0,
+    };
+
+    let root_bundle = &mut ctx.accounts.root_bundle;
+    root_bundle.relayer_refund_root = relayer_refund_root;
+    root_bundle.slow_relay_root = slow_relay_root;
+    root_bundle.id = state.root_bundle_id;
+    root_bundle.execution_deadline = execution_deadline;
+    root_bundle.restricted_executor = restricted_executor;
+    root_bundle.restricted_until = restricted_until;
+
+    emit_cpi!(RelayedRootBundle {
+        root_bundle_id: state.root_bundle_id,
+        relayer_refund_root,
+        slow_relay_root,
+        execution_deadline,
+        restricted_executor,
+        restricted_until
+    });
+
+    state.root_bundle_id = state.root_bundle_id.checked_add(1).or_overflow("root bundle id")?;
+    state.genesis = false;
+
+    Ok(true)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct EmergencyDeleteRootBundleState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Writable system account receiving the root bundle rent, provided by the admin. We do not restrict who can
+    /// receive lamports from closing root_bundle account as that would require storing the original payer when root
+    /// bundle was relayed and unnecessarily make it more expensive to relay in the happy path.
+    #[account(mut)]
+    pub closer: SystemAccount<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Root bundle PDA being closed, seeds ["root_bundle", seed, root_bundle_id]. Writable.
+    #[account(mut,
+        seeds =[b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        close = closer,
+        bump)]
+    pub root_bundle: Account<'info, RootBundle>,
+}
+
+pub fn emergency_delete_root_bundle(ctx: Context<EmergencyDeleteRootBundleState>, root_bundle_id: u32) -> Result<()> {
+    assert_closes_not_frozen(&ctx.accounts.state)?;
+
+    emit_cpi!(EmergencyDeletedRootBundle { root_bundle_id });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WindDown<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable and must not be winding down yet.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        // Wind down is irreversible, so it cannot be restarted to reset its delay.
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn wind_down(ctx: Context<WindDown>, wind_down_delay: u32) -> Result<()> {
+    if wind_down_delay < MIN_WIND_DOWN_DELAY {
+        return err!(SvmError::WindDownDelayTooShort);
+    }
+
+    let state = &mut ctx.accounts.state;
+    let wind_down_time = get_current_time(state)?;
+    state.winding_down = true;
+    state.wind_down_time = wind_down_time;
+    state.wind_down_delay = wind_down_delay;
+
+    emit_cpi!(StartedWindDown { wind_down_time, wind_down_delay });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepVaultToHub<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Pays the rent for the transfer liability PDA if it is created. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Must be winding down.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.winding_down @ SvmError::NotWindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the vault being swept.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint.
+    #[account(
+        mut,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Transfer liability PDA for the mint, seeds ["transfer_liability", mint]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + TransferLiability::INIT_SPACE,
+        seeds = [b"transfer_liability", mint.key().as_ref()],
+        bump
+    )]
+    pub transfer_liability: Account<'info, TransferLiability>,
+
+    /// CCTP local token for the mint. If passed, the vault is swept via CCTP, else it goes to the owner token account.
+    /// Pass this program ID to represent None.
+    #[account(
+        seeds = [b"local_token", mint.key().as_ref()],
+        bump,
+        seeds::program = token_messenger_minter::ID
+    )]
+    pub local_token: Option<Account<'info, LocalToken>>,
+
+    /// Writable ATA of the state owner for the mint. Required only if local_token is None. Pass this program ID to
+    /// represent None.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = state.owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required for sweeps to the owner
+    /// token account when the state enables vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint], whose pending slow fill
+    /// amount is kept in the vault. Read-only, treated as nothing pending when no slow fill request of the mint created
+    /// it.
+    #[account(seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Claim liability PDA of the mint, seeds ["claim_liability", seed, mint], whose refunds owed to claim
+    /// accounts are kept in the vault. Read-only, treated as nothing owed when no refund of the mint was accrued.
+    #[account(seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub claim_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint], whose owed liquidity is kept in the
+    /// vault. Read-only, treated as nothing owed when the pool of the mint was never initialized.
+    #[account(seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: UncheckedAccount<'info>,
+}
+
+pub fn sweep_vault_to_hub(ctx: Context<SweepVaultToHub>) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+    if current_time < state.wind_down_time.saturating_add(state.wind_down_delay) {
+        return err!(SvmError::WindDownDelayNotPassed);
+    }
+
+    // Requested slow fills, refunds accrued to claim accounts and liquidity owed to the pool are still paid out of the
+    // vault after the wind down, so only the vault balance exceeding them is swept.
+    let reserved_amount = get_pending_slow_fill_amount(&ctx.accounts.slow_fill_liability)?
+        .saturating_add(get_pending_claim_amount(&ctx.accounts.claim_liability)?)
+        .saturating_add(get_pool_liquidity(&ctx.accounts.liquidity_pool)?);
+    let sweepable_amount = ctx.accounts.vault.amount.saturating_sub(reserved_amount);
+    let to_hub_pool = ctx.accounts.local_token.is_some();
+
+    let amount = if to_hub_pool {
+        // Credit the sweepable balance on top of the amounts already pending to the hub pool, which are still held by
+        // the vault, so it can be bridged through the permissionless bridge_tokens_to_hub_pool in chunks respecting the
+        // CCTP burn limit.
+        let transfer_liability = &mut ctx.accounts.transfer_liability;
+        let amount = sweepable_amount.saturating_sub(transfer_liability.pending_to_hub_pool);
+        transfer_liability.pending_to_hub_pool =
+            transfer_liability.pending_to_hub_pool.checked_add(amount).or_overflow("pending to hub pool")?;
+        amount
+    } else {
+        let owner_token_account = ctx.accounts.owner_token_account.as_ref().ok_or(SvmError::MissingSweepRecipient)?;
+        let vault_signer = VaultSigner::new(state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+        pay_from_vault(
+            &ctx.accounts.vault,
+            &owner_token_account.to_account_info(),
+            sweepable_amount,
+            state,
+            &vault_signer,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &mut ctx.accounts.rate_limit,
+        )?;
+        // Amounts pending to the hub pool were swept to the owner as well, so nothing is left in the vault to bridge.
+        ctx.accounts.transfer_liability.pending_to_hub_pool = 0;
+        sweepable_amount
+    };
+
+    emit_cpi!(SweptVault { mint: ctx.accounts.mint.key(), amount, to_hub_pool });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(amount: u64, recipient: Pubkey)]
+pub struct SweepTokens<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Pays the rent for the vault liability PDA if it is created. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the swept tokens.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the swept tokens.
+    #[account(
+        mut,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Vault liability PDA of the mint, seeds ["vault_liability", seed, mint]. Created if missing, as vaults of mints
+    /// that were never deposited have no liabilities.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + VaultLiability::INIT_SPACE,
+        seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault_liability: Account<'info, VaultLiability>,
+
+    /// CHECK: Slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint], whose pending slow fill
+    /// amount can't be swept. Read-only, treated as nothing pending when no slow fill request of the mint created it.
+    #[account(seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint], whose owed liquidity can't be swept.
+    /// Read-only, treated as nothing owed when the pool of the mint was never initialized.
+    #[account(seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: UncheckedAccount<'info>,
+
+    /// Writable token account of the recipient for the mint, receiving the swept tokens.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required when the state enables
+    /// vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Writable admin nonce PDA recording the idempotency key, seeds ["admin_nonce", seed, idempotency_key].
+    /// Only required when passing an idempotency key. Pass this program ID to represent None.
+    #[account(mut)]
+    pub admin_nonce: Option<UncheckedAccount<'info>>,
+}
+
+pub fn sweep_tokens(
+    ctx: Context<SweepTokens>,
+    amount: u64,
+    recipient: Pubkey,
+    idempotency_key: Option<[u8; 16]>,
+) -> Result<bool> {
+    if !consume_idempotency_key(
+        idempotency_key,
+        &ctx.accounts.state,
+        ctx.accounts.admin_nonce.as_ref(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )? {
+        return Ok(false);
+    }
+
+    // Only the vault balance exceeding the outstanding obligations, including the output amount reserved for requested
+    // slow fills and the liquidity owed to the pool, can be swept, so that a fully backed vault has nothing to sweep.
+    // Pool liquidity is owed even while liquidity pools are disabled, as providers can still remove it.
+    let pending_slow_fill_amount = get_pending_slow_fill_amount(&ctx.accounts.slow_fill_liability)?;
+    let pool_liquidity = get_pool_liquidity(&ctx.accounts.liquidity_pool)?;
+    let obligations = ctx
+        .accounts
+        .vault_liability
+        .liabilities
+        .saturating_add(pending_slow_fill_amount)
+        .saturating_add(pool_liquidity);
+    let surplus = ctx.accounts.vault.amount.saturating_sub(obligations);
+    if surplus == 0 || amount > surplus {
+        return err!(SvmError::SweepExceedsSurplus);
+    }
+
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+    pay_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        amount,
+        &ctx.accounts.state,
+        &vault_signer,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.rate_limit,
+    )?;
+
+    emit_cpi!(TokensSwept { mint: ctx.accounts.mint.key(), amount, recipient });
+
+    Ok(true)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateVaultAuthority<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Pays the rent for the new vault if it is created. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the state vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the migrated vault.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Writable vault ATA of the state PDA for the mint, marked as migrated by the migration. Only its address
+    /// is checked here, as it may not exist and is rejected as a vault once migrated.
+    #[account(
+        mut,
+        address = get_canonical_ata(&state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated
+    )]
+    pub state_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the new vault.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, receiving the state vault balance. Created if
+    /// missing.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Associated token program, used to create associated token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint], whose pending slow fills
+    /// block the migration. Read-only, treated as nothing pending when no slow fill request of the mint created it. The
+    /// liability accounts follow the system program, so that existing clients keep their account order.
+    #[account(seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Claim liability PDA of the mint, seeds ["claim_liability", seed, mint], whose refunds owed to claim
+    /// accounts block the migration. Read-only, treated as nothing owed when no refund of the mint was accrued.
+    #[account(seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub claim_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint], whose owed liquidity blocks the
+    /// migration. Read-only, treated as nothing owed when the pool of the mint was never initialized.
+    #[account(seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: UncheckedAccount<'info>,
+}
+
+pub fn migrate_vault_authority(ctx: Context<MigrateVaultAuthority>) -> Result<bool> {
+    let state_vault = &ctx.accounts.state_vault;
+    let preview = preview_migration(
+        &ctx.accounts.state,
+        &ctx.accounts.state.key(),
+        state_vault,
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.slow_fill_liability,
+        &ctx.accounts.claim_liability,
+        &ctx.accounts.liquidity_pool,
+    )?;
+    if preview.blockers != 0 {
+        msg!("Vault migration blocked by checks {}", preview.blockers);
+        return err!(SvmError::VaultMigrationBlocked);
+    }
+
+    if !preview.state_vault_exists {
+        return Ok(false);
+    }
+    let migrated = preview.state_vault_migrated;
+    let amount = preview.state_vault_balance;
+
+    // Repeated migrations only move tokens sent to the migrated state vault afterwards.
+    if migrated && amount == 0 {
+        return Ok(false);
+    }
+    if amount > 0 {
+        transfer_from(
+            state_vault,
+            &ctx.accounts.vault,
+            amount,
+            &ctx.accounts.state,
+            ctx.bumps.state,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // The state vault is kept open instead of being closed, so that it can't be recreated as a vault of the mint and
+    // Token-2022 vaults with withheld transfer fees, that can't be closed, migrate too. Its close authority marks it as
+    // migrated, which rejects any later instruction still passing it as the vault of the mint.
+    if !migrated {
+        let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
+        let seeds = &[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]];
+        let signer_seeds = &[&seeds[..]];
+        let set_authority_accounts = SetAuthority {
+            current_authority: ctx.accounts.state.to_account_info(),
+            account_or_mint: state_vault.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            set_authority_accounts,
+            signer_seeds,
+        );
+        set_authority(cpi_context, AuthorityType::CloseAccount, Some(ctx.accounts.vault_authority.key()))?;
+    }
+
+    emit_cpi!(MigratedVaultAuthority { mint: ctx.accounts.mint.key(), vault: ctx.accounts.vault.key(), amount });
+
+    Ok(true)
+}
+
+#[derive(Accounts)]
+pub struct PreviewVaultMigration<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only. Its layout version is checked by
+    /// the preview, so that a state still pending migrate_state is reported instead of failing the preview.
+    #[account(seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()], bump)]
+    pub state: Account<'info, State>,
+
+    /// Mint of the previewed vault.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Vault ATA of the state PDA for the mint, derived with the token program owning the mint. Only its
+    /// address is checked here, as it may not exist.
+    #[account(
+        address = get_canonical_ata(&state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated
+    )]
+    pub state_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault ATA of the vault authority PDA for the mint, derived with the token program owning the mint.
+    /// Existence and freezing are checked by the preview itself.
+    #[account(
+        constraint = is_vault_address(vault.key, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAddress
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint]. Read-only, treated as
+    /// nothing pending when no slow fill request of the mint created it.
+    #[account(seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Claim liability PDA of the mint, seeds ["claim_liability", seed, mint]. Read-only, treated as nothing
+    /// owed when no refund of the mint was accrued.
+    #[account(seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub claim_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint]. Read-only, treated as nothing owed
+    /// when the pool of the mint was never initialized.
+    #[account(seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VaultMigrationPreview {
+    pub blockers: u32,                 // VAULT_MIGRATION_* bits of the failed checks, 0 when the migration can run.
+    pub state_vault_exists: bool,      // Migrations of a missing state vault only return false.
+    pub state_vault_migrated: bool,    // Already marked as migrated, so only tokens sent to it since are moved.
+    pub state_vault_balance: u64,      // Balance moved to the vault authority ATA by the migration.
+    pub vault_exists: bool,            // Vault authority ATA exists, else the migration creates it.
+    pub pending_slow_fill_amount: u64, // Output amount of requested slow fills pending execution.
+    pub pending_claim_amount: u64,     // Relayer refunds accrued to claim accounts and not claimed yet.
+    pub pool_liquidity: u64,           // Liquidity owed to the pool providers of the mint.
+}
+
+pub fn preview_vault_migration(
+    ctx: Context<PreviewVaultMigration>,
+    mint: Pubkey,
+    new_state: Pubkey,
+) -> Result<VaultMigrationPreview> {
+    require_keys_eq!(ctx.accounts.mint.key(), mint, ErrorCode::ConstraintAddress);
+
+    preview_migration(
+        &ctx.accounts.state,
+        &new_state,
+        &ctx.accounts.state_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.slow_fill_liability,
+        &ctx.accounts.claim_liability,
+        &ctx.accounts.liquidity_pool,
+    )
+}
+
+// Runs the checks of migrate_vault_authority, which fails whenever the preview reports blockers, so that the preview
+// can't report a go for a migration that would be rejected.
+fn preview_migration(
+    state: &Account<State>,
+    new_state: &Pubkey,
+    state_vault: &AccountInfo,
+    vault: &AccountInfo,
+    slow_fill_liability: &AccountInfo,
+    claim_liability: &AccountInfo,
+    liquidity_pool: &AccountInfo,
+) -> Result<VaultMigrationPreview> {
+    let mut blockers = 0;
+
+    if state.key() != *new_state || !state.is_current_version() {
+        blockers |= VAULT_MIGRATION_STATE;
+    }
+
+    let state_vault_account = if state_vault.data_is_empty() {
+        None
+    } else {
+        Some(TokenAccount::try_deserialize(&mut &state_vault.try_borrow_data()?[..])?)
+    };
+    let vault_exists = !vault.data_is_empty();
+
+    // Frozen token accounts can neither be transferred from or to, nor have their close authority set.
+    let state_vault_frozen = state_vault_account.as_ref().is_some_and(|account| account.is_frozen());
+    if state_vault_frozen || (vault_exists && is_vault_frozen(vault)?) {
+        blockers |= VAULT_MIGRATION_FROZEN;
+    }
+
+    // Obligations pending against the mint are settled before its state vault balance moves, so that the migration
+    // never runs while requested slow fills, claims or pool providers could still be owed out of that balance.
+    let pending_slow_fill_amount = get_pending_slow_fill_amount(slow_fill_liability)?;
+    if pending_slow_fill_amount > 0 {
+        blockers |= VAULT_MIGRATION_SLOW_FILLS;
+    }
+    let pending_claim_amount = get_pending_claim_amount(claim_liability)?;
+    if pending_claim_amount > 0 {
+        blockers |= VAULT_MIGRATION_CLAIMS;
+    }
+    let pool_liquidity = get_pool_liquidity(liquidity_pool)?;
+    if pool_liquidity > 0 {
+        blockers |= VAULT_MIGRATION_POOL_LIQUIDITY;
+    }
+
+    Ok(VaultMigrationPreview {
+        blockers,
+        state_vault_exists: state_vault_account.is_some(),
+        state_vault_migrated: state_vault_account
+            .as_ref()
+            .is_some_and(|account| is_migrated_state_vault(account, &state.key())),
+        state_vault_balance: state_vault_account.as_ref().map_or(0, |account| account.amount),
+        vault_exists,
+        pending_slow_fill_amount,
+        pending_claim_amount,
+        pool_liquidity,
+    })
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateFrozenVault<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Pays the rent for the new vault if it is created. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the frozen vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the frozen vault.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault ATA of the state PDA for the mint, frozen by the freeze authority of the mint. Left in place, as frozen
+    /// token accounts can neither be transferred from nor closed.
+    #[account(
+        address = get_canonical_ata(&state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        constraint = frozen_vault.is_frozen() @ SvmError::VaultNotFrozen,
+        token::token_program = token_program
+    )]
+    pub frozen_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the new vault.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, paying out once funded with new liquidity. Created
+    /// if missing.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Associated token program, used to create associated token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_frozen_vault(ctx: Context<MigrateFrozenVault>) -> Result<()> {
+    // Mints freezing new accounts by default create the new vault frozen as well, which could not pay out either.
+    if ctx.accounts.vault.is_frozen() {
+        return err!(SvmError::VaultFrozen);
+    }
+
+    emit_cpi!(MigratedFrozenVault {
+        mint: ctx.accounts.mint.key(),
+        frozen_vault: ctx.accounts.frozen_vault.key(),
+        vault: ctx.accounts.vault.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct ReconcileCounters<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to record the reconciliation time.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Writable transfer liability PDA of the reconciled mint, seeds ["transfer_liability", mint].
+    #[account(mut, seeds = [b"transfer_liability", mint.as_ref()], bump)]
+    pub transfer_liability: Account<'info, TransferLiability>,
+}
+
+pub fn reconcile_counters(
+    ctx: Context<ReconcileCounters>,
+    mint: Pubkey,
+    pending_to_hub_pool: u64,
+    force: bool,
+) -> Result<()> {
+    let transfer_liability = &mut ctx.accounts.transfer_liability;
+    let old_pending_to_hub_pool = transfer_liability.pending_to_hub_pool;
+
+    if !force && !is_within_reconcile_band(old_pending_to_hub_pool, pending_to_hub_pool) {
+        return err!(SvmError::ReconciliationOutOfBand);
+    }
+
+    transfer_liability.pending_to_hub_pool = pending_to_hub_pool;
+    ctx.accounts.state.last_counter_reconciliation_time = get_current_time(&ctx.accounts.state)?;
+
+    emit_cpi!(ReconciledCounters {
+        mint,
+        old_pending_to_hub_pool,
+        new_pending_to_hub_pool: pending_to_hub_pool,
+        forced: force,
+    });
+
+    Ok(())
+}
+
+// Counters that drifted further than the band from their recomputed values point at a bug in the off-chain
+// recomputation rather than at missed updates, so these require an explicit force.
+fn is_within_reconcile_band(current: u64, reconciled: u64) -> bool {
+    let deviation = current.abs_diff(reconciled) as u128;
+
+    deviation * BPS_DENOMINATOR as u128 <= current as u128 * RECONCILE_COUNTERS_MAX_DEVIATION_BPS as u128
+}
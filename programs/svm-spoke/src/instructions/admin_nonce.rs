@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::SvmError, state::AdminNonce, utils::assert_lamport_destination};
+
+#[derive(Accounts)]
+pub struct CloseAdminNonce<'info> {
+    /// Anyone can close expired admin nonce PDAs, as the rent is always returned to their original payer.
+    pub signer: Signer<'info>,
+
+    /// Writable admin nonce PDA being closed, seeds ["admin_nonce", seed, idempotency_key]. Closed to its original
+    /// payer. No need to check seed derivation as this method only evaluates the expiry recorded in this account.
+    #[account(mut, close = payer)]
+    pub admin_nonce: Account<'info, AdminNonce>,
+
+    /// CHECK: Writable original payer of the admin nonce PDA, receiving the reclaimed rent.
+    #[account(mut, address = admin_nonce.payer @ SvmError::InvalidOriginalPayer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn close_admin_nonce(ctx: Context<CloseAdminNonce>) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.payer)?;
+
+    // Closing re-enables the idempotency key, so resubmissions after the retention window are applied again.
+    if Clock::get()?.slot <= ctx.accounts.admin_nonce.expires_at_slot {
+        return err!(SvmError::CanOnlyCloseAdminNonceAfterExpiry);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ALLOWLIST_ROLES_MASK, DISCRIMINATOR_SIZE},
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{SetAllowlistRoles, SetPermissionedMode},
+    state::{AllowlistEntry, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPermissionedModeState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_permissioned_mode(
+    ctx: Context<SetPermissionedModeState>,
+    enabled: bool,
+    strict_slow_fill_requester: bool,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.permissioned_mode = enabled;
+    state.strict_slow_fill_requester = strict_slow_fill_requester;
+
+    emit_cpi!(SetPermissionedMode { enabled, strict_slow_fill_requester });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(account: Pubkey)]
+pub struct SetAllowlistRolesState<'info> {
+    /// Owner maintaining the allowlist. Writable signer paying the allowlist entry rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Allowlist entry PDA of the account, seeds ["allowlist", seed, account]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), account.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// Revoking all roles keeps the entry around so that its rent is not churned when roles are granted again.
+pub fn set_allowlist_roles(ctx: Context<SetAllowlistRolesState>, account: Pubkey, roles: u8) -> Result<()> {
+    if roles & !ALLOWLIST_ROLES_MASK != 0 {
+        return err!(SvmError::InvalidAllowlistRoles);
+    }
+
+    ctx.accounts.allowlist_entry.roles = roles;
+
+    emit_cpi!(SetAllowlistRoles { account, roles });
+
+    Ok(())
+}
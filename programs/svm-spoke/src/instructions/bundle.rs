@@ -1,29 +1,51 @@
-use anchor_lang::{prelude::*, solana_program::keccak};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{keccak, system_program},
+    system_program::{transfer, Transfer},
+};
 use anchor_spl::{
-    associated_token,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    constants::DISCRIMINATOR_SIZE,
+    constants::{DISCRIMINATOR_SIZE, MAX_EVENT_CPI_DATA_SIZE, UNCLAIMED_LEAVES_MAX_IDS},
     error::{CommonError, SvmError},
-    event::{ExecutedRelayerRefundRoot, TokensBridged},
-    state::{ClaimAccount, ExecuteRelayerRefundLeafParams, RootBundle, State, TransferLiability},
-    utils::{is_claimed, set_claimed, verify_merkle_proof},
+    event::{BundleAuditSnapshot, ExecutedRelayerRefundRoot, RefundDeferred, RefundPaid, TokensBridged},
+    state::{
+        BundleAudit, ClaimAccount, ClaimLiability, ErrorCounters, ExecuteRelayerRefundLeafParams, ExecutionApproval,
+        RateLimit, RootBundle, Route, State, TransferLiability, VaultLiability,
+    },
+    utils::{
+        check_refund_fill_volume, get_canonical_ata, get_current_time, get_event_cpi_data_size,
+        get_pending_slow_fill_amount, get_refund_event_chunks, is_canonical_token_program, is_claimed, is_vault,
+        process_proof, record_vault_outflow, seed_encode_struct, set_claimed, transfer_from_vault, verify_merkle_proof,
+        OrArithmeticOverflow, VaultSigner,
+    },
 };
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ExecuteRelayerRefundLeaf<'info> {
+    /// Executor of the leaf, typically a relayer or dataworker. Writable signer paying for PDA creation and root bundle
+    /// realloc.
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Instruction params PDA of the signer, seeds ["instruction_params", signer], loaded beforehand with
+    /// write_instruction_params. Contains all leaf & proof information.
     #[account(seeds = [b"instruction_params", signer.key().as_ref()], bump)]
-    pub instruction_params: Account<'info, ExecuteRelayerRefundLeafParams>, // Contains all leaf & proof information.
+    pub instruction_params: Account<'info, ExecuteRelayerRefundLeafParams>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
+    /// Writable root bundle PDA, seeds ["root_bundle", seed, root_bundle_id], tracking executed leaves.
     #[account(
         mut,
         seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), instruction_params.root_bundle_id.to_le_bytes().as_ref()], bump,
@@ -33,24 +55,33 @@ pub struct ExecuteRelayerRefundLeaf<'info> {
             root_bundle.to_account_info().data_len()
         ),
         realloc::payer = signer,
-        realloc::zero = false
+        realloc::zero = false,
+        constraint = root_bundle.has_relayer_refund_root() @ SvmError::BundleHasNoRelayerRefundRoot
     )]
     pub root_bundle: Account<'info, RootBundle>,
 
+    /// Writable vault ATA of the vault authority PDA for the leaf mint, source of the refunds.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = state, // Ensure owner is the state.
-        associated_token::token_program = token_program
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its refunds.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Mint of the leaf. Must match the mint in the relayer refund leaf.
     #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
         mint::token_program = token_program,
         address = instruction_params.relayer_refund_leaf.mint_public_key @ SvmError::InvalidMint
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Transfer liability PDA for the mint, seeds ["transfer_liability", mint].
     #[account(
         init_if_needed, // If first time creating, initialize the liability tracker, else re-use.
         payer = signer,
@@ -60,20 +91,100 @@ pub struct ExecuteRelayerRefundLeaf<'info> {
     )]
     pub transfer_liability: Account<'info, TransferLiability>,
 
+    /// Optional failure tracker, only updated when refunds are deferred. Seeds ["error_counters", seed]. Pass this
+    /// program ID to represent None.
+    #[account(mut, seeds = [b"error_counters", state.seed.to_le_bytes().as_ref()], bump)]
+    pub error_counters: Option<Account<'info, ErrorCounters>>,
+
+    /// Optional vault balance snapshots, required only when bundle audits are enabled in the state. Seeds
+    /// ["bundle_audit", seed, root_bundle_id]. Pass this program ID to represent None.
+    #[account(
+        mut,
+        seeds = [
+            b"bundle_audit",
+            state.seed.to_le_bytes().as_ref(),
+            instruction_params.root_bundle_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bundle_audit: Option<Account<'info, BundleAudit>>,
+
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required when refunds are sent
+    /// directly and the state enables vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// Writable vault liability PDA of the mint, seeds ["vault_liability", seed, mint], debited with the refunds sent
+    /// from the vault. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub vault_liability: Option<Account<'info, VaultLiability>>,
+
+    /// CHECK: Slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint], whose pending slow fill
+    /// amount refunds sent from the vault can't reach into. Read-only, treated as nothing pending when no slow fill
+    /// request of the mint created it.
+    #[account(seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// CHECK: Writable fill volume PDA of the mint, seeds ["fill_volume", seed, mint], bounding the refunds of each
+    /// bundle by the fill volume of its window. Ignored for mints that the owner did not opt into refund checks.
+    #[account(mut, seeds = [b"fill_volume", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub fill_volume: UncheckedAccount<'info>,
+
+    /// Execution approval PDA of the leaf, seeds ["execution_approval", seed, leaf_hash]. Only required to execute
+    /// leaves whose bundle refunds exceed the fill volume of the mint. Pass this program ID to represent None.
+    pub execution_approval: Option<Account<'info, ExecutionApproval>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
 
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
+
+    /// Associated token program, used to create the missing ATAs of refund addresses when refunds are sent directly.
+    /// Pass this program ID to represent None, deferring refunds to missing ATAs to claim accounts.
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// Optional route of the leaf mint towards any destination chain, whose expected decimals must match the mint.
+    /// Seeds ["route", mint, seed, destination_chain_id]. Pass this program ID to represent None.
+    #[account(
+        seeds = [
+            b"route",
+            mint.key().as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            route.destination_chain_id.to_le_bytes().as_ref(),
+        ],
+        bump = route.bump,
+        constraint = route.expected_decimals == mint.decimals @ SvmError::UnexpectedMintDecimals
+    )]
+    pub route: Option<Account<'info, Route>>,
+
+    /// Writable claim liability PDA of the mint, seeds ["claim_liability", seed, mint], credited with the refunds
+    /// accrued to claim accounts. Created if missing.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + ClaimLiability::INIT_SPACE,
+        seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub claim_liability: Box<Account<'info, ClaimLiability>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct RelayerRefundLeaf {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub amount_to_return: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub chain_id: u64,
     #[max_len(0)]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount_vec"))]
     pub refund_amounts: Vec<u64>,
     pub leaf_id: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey"))]
     pub mint_public_key: Pubkey,
     #[max_len(0)]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::pubkey_vec"))]
     pub refund_addresses: Vec<Pubkey>,
 }
 
@@ -88,7 +199,7 @@ impl RelayerRefundLeaf {
         // in interpreted by SVM, to be zero always blocking this leaf type on EVM.
         bytes.extend_from_slice(&[0u8; 64]);
 
-        AnchorSerialize::serialize(&self, &mut bytes)?;
+        seed_encode_struct(self, &mut bytes)?;
 
         Ok(bytes)
     }
@@ -138,19 +249,94 @@ where
     }
 
     // Check if vault has sufficient balance for all the refunds.
+    let vault_balance_before = ctx.accounts.vault.amount;
     let total_refund_amount: u64 = relayer_refund_leaf.refund_amounts.iter().sum();
-    if ctx.accounts.vault.amount < total_refund_amount {
+    if vault_balance_before < total_refund_amount {
         return err!(CommonError::InsufficientSpokePoolBalanceToExecuteLeaf);
     }
 
+    // Amounts returned to the hub pool are bounded together with the refunds, as both leave the vault.
+    let leaf_amount =
+        total_refund_amount.checked_add(relayer_refund_leaf.amount_to_return).or_overflow("leaf amount")?;
+    if let Some(approved) = check_refund_fill_volume(
+        state,
+        &ctx.accounts.fill_volume,
+        &ctx.accounts.execution_approval,
+        ctx.accounts.signer.key,
+        root_bundle_id,
+        relayer_refund_leaf.leaf_id,
+        ctx.accounts.mint.key(),
+        leaf,
+        leaf_amount,
+        get_current_time(state)?,
+    )? {
+        emit_cpi!(approved);
+    }
+
+    // Refunds sent from the vault can't reach into the output amount reserved for requested slow fills, while accrued
+    // refunds leave the vault balance untouched.
+    if !deferred_refunds
+        && vault_balance_before - total_refund_amount < get_pending_slow_fill_amount(&ctx.accounts.slow_fill_liability)?
+    {
+        return err!(SvmError::SlowFillLiabilityExceeded);
+    }
+
     // Depending on the called instruction flavor, we either accrue the refunds to claim accounts or transfer them.
-    match deferred_refunds {
-        true => accrue_relayer_refunds(&ctx, &relayer_refund_leaf)?,
-        false => distribute_relayer_refunds(&ctx, &relayer_refund_leaf)?,
+    // Transfers to token accounts that cannot receive them are also accrued, so the leaf is never bricked by a single
+    // relayer.
+    let (deferred_refund_count, accrued_amount) = match deferred_refunds {
+        true => {
+            accrue_relayer_refunds(&ctx, &relayer_refund_leaf)?;
+            (relayer_refund_leaf.refund_addresses.len(), total_refund_amount)
+        }
+        false => {
+            let (sent_amount, deferred_refund_count) = distribute_relayer_refunds(&ctx, &relayer_refund_leaf)?;
+
+            // Deferred refunds are only debited against the rate limit once claimed.
+            record_vault_outflow(&ctx.accounts.state, &mut ctx.accounts.rate_limit, sent_amount)?;
+            // Skipped if the caller did not pass the vault liability, which can only overstate the liabilities.
+            if let Some(vault_liability) = ctx.accounts.vault_liability.as_mut() {
+                vault_liability.record_outflow(sent_amount);
+            }
+            (deferred_refund_count, total_refund_amount - sent_amount)
+        }
+    };
+
+    // Accrued refunds stay in the vault until claimed, so that wind down sweeps leave them to the relayers.
+    if accrued_amount > 0 {
+        ctx.accounts.claim_liability.record_accrual(accrued_amount)?;
+    }
+
+    // Best-effort tracking of deferred refunds, skipped if the caller did not pass the counters account.
+    if deferred_refund_count > 0 {
+        if let Some(error_counters) = ctx.accounts.error_counters.as_mut() {
+            error_counters.record_deferred_refunds(deferred_refund_count);
+        }
+    }
+
+    // Snapshot vault balances around the leaf execution when bundle audits are enabled. Mints beyond the audit account
+    // capacity are not recorded so that auditing can never block refunds.
+    if ctx.accounts.state.bundle_audits_enabled {
+        ctx.accounts.vault.reload()?;
+        let vault_balance_after = ctx.accounts.vault.amount;
+        let mint = ctx.accounts.mint.key();
+        let bundle_audit = ctx.accounts.bundle_audit.as_mut().ok_or(SvmError::MissingBundleAudit)?;
+        if let Some(snapshot) = bundle_audit.record_snapshot(mint, vault_balance_before, vault_balance_after) {
+            emit_cpi!(BundleAuditSnapshot {
+                root_bundle_id,
+                mint,
+                balance_before: snapshot.balance_before,
+                balance_after: snapshot.balance_after,
+            });
+        }
     }
 
     if relayer_refund_leaf.amount_to_return > 0 {
-        ctx.accounts.transfer_liability.pending_to_hub_pool += relayer_refund_leaf.amount_to_return;
+        let transfer_liability = &mut ctx.accounts.transfer_liability;
+        transfer_liability.pending_to_hub_pool = transfer_liability
+            .pending_to_hub_pool
+            .checked_add(relayer_refund_leaf.amount_to_return)
+            .or_overflow("pending to hub pool")?;
 
         emit_cpi!(TokensBridged {
             amount_to_return: relayer_refund_leaf.amount_to_return,
@@ -161,7 +347,7 @@ where
         });
     }
 
-    emit_cpi!(ExecutedRelayerRefundRoot {
+    let event = ExecutedRelayerRefundRoot {
         amount_to_return: relayer_refund_leaf.amount_to_return,
         chain_id: relayer_refund_leaf.chain_id,
         refund_amounts: relayer_refund_leaf.refund_amounts,
@@ -169,22 +355,42 @@ where
         leaf_id: relayer_refund_leaf.leaf_id,
         l2_token_address: ctx.accounts.mint.key(),
         refund_addresses: relayer_refund_leaf.refund_addresses,
-        deferred_refunds,
+        deferred_refunds: deferred_refund_count > 0,
         caller: ctx.accounts.signer.key(),
-    });
+        token_decimals: ctx.accounts.mint.decimals,
+    };
+    if get_event_cpi_data_size(&event) <= MAX_EVENT_CPI_DATA_SIZE {
+        emit_cpi!(event);
+        return Ok(());
+    }
+
+    // Refunds have already moved at this point, so events too large to emit at once are chunked instead of failing.
+    for chunk in get_refund_event_chunks(&event) {
+        emit_cpi!(chunk);
+    }
 
     Ok(())
 }
 
-fn distribute_relayer_refunds<'info>(
-    ctx: &Context<'_, '_, '_, 'info, ExecuteRelayerRefundLeaf<'info>>,
+// Transfers the refunds to the relayer ATAs passed in remaining_accounts[0..n]. Refund addresses can also be depositors
+// refunded for expired deposits without an ATA for the mint, which is created when the refund address is passed at
+// remaining_accounts[2n + i]. Refunds to ATAs that cannot receive the transfer (not created, frozen or not matching the
+// mint) are accrued to the claim account of the refund address instead, passed at remaining_accounts[n + i]. Returns
+// the transferred amount and the number of accrued refunds.
+fn distribute_relayer_refunds<'c, 'info>(
+    ctx: &Context<'_, '_, 'c, 'info, ExecuteRelayerRefundLeaf<'info>>,
     relayer_refund_leaf: &RelayerRefundLeaf,
-) -> Result<()> {
-    // Derive the signer seeds for the state. The vault owns the state PDA so we need to derive this to create the
-    // signer seeds to execute the CPI transfer from the vault to the refund recipient's token account.
-    let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
-    let seeds = &[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]];
-    let signer_seeds = &[&seeds[..]];
+) -> Result<(u64, usize)>
+where
+    'c: 'info,
+{
+    // Vaults are owned by the vault authority, which signs their outflows.
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+
+    let refund_count = relayer_refund_leaf.refund_addresses.len();
+    let root_bundle_id = ctx.accounts.instruction_params.root_bundle_id;
+    let mut sent_amount: u64 = 0;
+    let mut deferred_refund_count = 0;
 
     for (i, amount) in relayer_refund_leaf.refund_amounts.iter().enumerate() {
         // We only need to check the refund account matches the associated token address for the relayer.
@@ -193,27 +399,119 @@ fn distribute_relayer_refunds<'info>(
         // It should be safe to access elements of refund_addresses and remaining_accounts as their lengths are checked
         // before calling this internal function.
         let refund_token_account = &ctx.remaining_accounts[i];
-        let associated_token_address = associated_token::get_associated_token_address_with_program_id(
-            &relayer_refund_leaf.refund_addresses[i],
-            &ctx.accounts.mint.key(),
-            &ctx.accounts.token_program.key(),
-        );
+        let refund_address = &relayer_refund_leaf.refund_addresses[i];
+        let associated_token_address = get_canonical_ata(refund_address, &ctx.accounts.mint.to_account_info());
         if refund_token_account.key() != associated_token_address {
             return Err(Error::from(SvmError::InvalidRefund).with_account_name(&format!("remaining_accounts[{}]", i)));
         }
 
-        let transfer_accounts = TransferChecked {
-            from: ctx.accounts.vault.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            to: refund_token_account.to_account_info(),
-            authority: ctx.accounts.state.to_account_info(),
-        };
-        let cpi_context =
-            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer_seeds);
-        transfer_checked(cpi_context, amount.to_owned(), ctx.accounts.mint.decimals)?;
+        let created = create_refund_token_account(ctx, refund_token_account, refund_address, 2 * refund_count + i)?;
+
+        if !can_receive_refund(refund_token_account, &ctx.accounts.mint.key(), &ctx.accounts.token_program.key()) {
+            let claim_account_index = refund_count + i;
+            let claim_account_info = ctx.remaining_accounts.get(claim_account_index).ok_or_else(|| {
+                Error::from(SvmError::MissingRefundClaimAccount)
+                    .with_account_name(format!("remaining_accounts[{}]", claim_account_index))
+            })?;
+            accrue_claim_account(
+                claim_account_info,
+                &relayer_refund_leaf.mint_public_key,
+                refund_address,
+                *amount,
+                ctx.program_id,
+            )
+            .map_err(|e| e.with_account_name(format!("remaining_accounts[{}]", claim_account_index)))?;
+            emit_cpi!(RefundDeferred {
+                root_bundle_id,
+                leaf_id: relayer_refund_leaf.leaf_id,
+                mint: relayer_refund_leaf.mint_public_key,
+                refund_address: *refund_address,
+                claim_account: claim_account_info.key(),
+                amount: *amount,
+            });
+            deferred_refund_count += 1;
+            continue;
+        }
+
+        transfer_from_vault(
+            &ctx.accounts.vault,
+            refund_token_account,
+            amount.to_owned(),
+            &vault_signer,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+        )?;
+        sent_amount = sent_amount.checked_add(*amount).or_overflow("sent refund amount")?;
+
+        if created {
+            emit_cpi!(RefundPaid {
+                root_bundle_id,
+                leaf_id: relayer_refund_leaf.leaf_id,
+                mint: relayer_refund_leaf.mint_public_key,
+                refund_address: *refund_address,
+                token_account: refund_token_account.key(),
+                amount: *amount,
+            });
+        }
     }
 
-    Ok(())
+    Ok((sent_amount, deferred_refund_count))
+}
+
+// Creates the missing ATA of a refund address at the expense of the executor, returning whether it was created. Refund
+// addresses owned by programs are PDAs whose token accounts are up to their program, so these are left to be deferred,
+// as are refund addresses the executor did not pass at remaining_accounts[refund_address_index].
+fn create_refund_token_account<'c, 'info>(
+    ctx: &Context<'_, '_, 'c, 'info, ExecuteRelayerRefundLeaf<'info>>,
+    refund_token_account: &AccountInfo<'info>,
+    refund_address: &Pubkey,
+    refund_address_index: usize,
+) -> Result<bool>
+where
+    'c: 'info,
+{
+    let Some(associated_token_program) = ctx.accounts.associated_token_program.as_ref() else {
+        return Ok(false);
+    };
+    let Some(refund_address_account) = ctx.remaining_accounts.get(refund_address_index) else {
+        return Ok(false);
+    };
+    if refund_token_account.owner != &system_program::ID
+        || refund_address_account.key() != *refund_address
+        || refund_address_account.owner != &system_program::ID
+        || refund_address_account.executable
+    {
+        return Ok(false);
+    }
+
+    let cpi_program = associated_token_program.to_account_info();
+    let cpi_accounts = associated_token::Create {
+        payer: ctx.accounts.signer.to_account_info(),
+        associated_token: refund_token_account.to_account_info(),
+        authority: refund_address_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    associated_token::create_idempotent(cpi_ctx)?;
+
+    Ok(true)
+}
+
+// Checks the conditions under which a refund transfer to an existing ATA would fail, as a failed CPI cannot be caught.
+fn can_receive_refund(refund_token_account: &AccountInfo, mint: &Pubkey, token_program: &Pubkey) -> bool {
+    if refund_token_account.owner != token_program {
+        return false; // The ATA has not been created.
+    }
+
+    let Ok(data) = refund_token_account.try_borrow_data() else {
+        return false;
+    };
+    match TokenAccount::try_deserialize(&mut &data[..]) {
+        Ok(token_account) => token_account.mint == *mint && !token_account.is_frozen(),
+        Err(_) => false,
+    }
 }
 
 fn accrue_relayer_refunds<'c, 'info>(
@@ -226,20 +524,168 @@ where
     for (i, amount) in relayer_refund_leaf.refund_amounts.iter().enumerate() {
         // It should be safe to access elements of refund_addresses and remaining_accounts as their lengths are checked
         // before calling this internal function.
-        let mut claim_account = ClaimAccount::try_from(
+        accrue_claim_account(
             &ctx.remaining_accounts[i],
             &relayer_refund_leaf.mint_public_key,
             &relayer_refund_leaf.refund_addresses[i],
+            *amount,
+            ctx.program_id,
         )
         .map_err(|e| e.with_account_name(&format!("remaining_accounts[{}]", i)))?;
+    }
+
+    Ok(())
+}
+
+// Note: Account name should be appended to any possible errors by the caller.
+fn accrue_claim_account<'info>(
+    account_info: &'info AccountInfo<'info>,
+    mint: &Pubkey,
+    refund_address: &Pubkey,
+    amount: u64,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let mut claim_account = ClaimAccount::try_from(account_info, mint, refund_address)?;
+
+    claim_account.amount = claim_account.amount.checked_add(amount).or_overflow("claim account amount")?;
+
+    // Persist the updated claim account (Anchor handles this only for static accounts).
+    claim_account.exit(program_id)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum RootType {
+    RelayerRefund,
+    SlowRelay,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeafInclusion {
+    pub included: bool,
+    pub relayer_refund_root: [u8; 32],
+    pub slow_relay_root: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct VerifyLeafInclusion<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Root bundle PDA holding the roots to verify against, seeds ["root_bundle", seed, root_bundle_id]. Read-only.
+    #[account(
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub root_bundle: Account<'info, RootBundle>,
+}
+
+pub fn verify_leaf_inclusion(
+    ctx: Context<VerifyLeafInclusion>,
+    root_type: RootType,
+    leaf_hash: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<LeafInclusion> {
+    let root_bundle = &ctx.accounts.root_bundle;
+    let root = match root_type {
+        RootType::RelayerRefund => root_bundle.relayer_refund_root,
+        RootType::SlowRelay => root_bundle.slow_relay_root,
+    };
+
+    // Unlike verify_merkle_proof, this does not fail on invalid proof so that callers can observe the result.
+    Ok(LeafInclusion {
+        included: process_proof(&proof, &leaf_hash) == root,
+        relayer_refund_root: root_bundle.relayer_refund_root,
+        slow_relay_root: root_bundle.slow_relay_root,
+    })
+}
+
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct GetUnclaimedLeaves<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
 
-        claim_account.amount += amount;
+    /// Root bundle PDA whose claimed bitmap is read, seeds ["root_bundle", seed, root_bundle_id]. Read-only.
+    #[account(
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub root_bundle: Account<'info, RootBundle>,
+}
+
+// The root bundle does not know the number of its relayer refund leaves, as its claimed bitmap only grows when leaves
+// are executed. Callers pass the leaf count of the relayed tree and page from the ID after the last one returned.
+pub fn get_unclaimed_leaves(ctx: Context<GetUnclaimedLeaves>, start_leaf_id: u32, leaf_count: u32) -> Result<Vec<u32>> {
+    let claimed_bitmap = &ctx.accounts.root_bundle.claimed_bitmap;
+
+    Ok((start_leaf_id..leaf_count)
+        .filter(|leaf_id| !is_claimed(claimed_bitmap, *leaf_id))
+        .take(UNCLAIMED_LEAVES_MAX_IDS)
+        .collect())
+}
+
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct MigrateRootBundle<'info> {
+    /// Anyone can migrate legacy root bundles. Writable signer paying the rent for their extended size.
+    #[account(mut)]
+    pub signer: Signer<'info>,
 
-        // Persist the updated claim account (Anchor handles this only for static accounts).
-        claim_account
-            .exit(ctx.program_id)
-            .map_err(|e| e.with_account_name(&format!("remaining_accounts[{}]", i)))?;
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Writable root bundle PDA of the legacy layout, seeds ["root_bundle", seed, root_bundle_id]. Deserialized
+    /// in the handler, as its size predates the current layout.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub root_bundle: UncheckedAccount<'info>,
+
+    /// System program, used to transfer the rent of the extended size.
+    pub system_program: Program<'info, System>,
+}
+
+// Legacy root bundles were relayed without execution deadline or restricted executor, which the zeroed appended fields
+// already represent, so only the id is recorded.
+pub fn migrate_root_bundle(ctx: Context<MigrateRootBundle>, root_bundle_id: u32) -> Result<()> {
+    let root_bundle_info = ctx.accounts.root_bundle.to_account_info();
+    let mut root_bundle = {
+        let data = root_bundle_info.try_borrow_data()?;
+        if !RootBundle::is_legacy_layout(&data) {
+            return err!(SvmError::NotLegacyRootBundle);
+        }
+        RootBundle::try_deserialize_any_layout(&data)?
+    };
+    root_bundle.id = root_bundle_id;
+
+    let space = root_bundle_info.data_len() + RootBundle::APPENDED_FIELDS_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(root_bundle_info.lamports());
+    if required_lamports > 0 {
+        let transfer_accounts = Transfer { from: ctx.accounts.signer.to_account_info(), to: root_bundle_info.clone() };
+        let cpi_context = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts);
+        transfer(cpi_context, required_lamports)?;
     }
+    root_bundle_info.realloc(space, false)?;
 
-    Ok(())
+    let mut data = root_bundle_info.try_borrow_mut_data()?;
+    root_bundle.try_serialize(&mut &mut data[..])
 }
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    state::{BundleAudit, RootBundle, State, VaultSnapshot},
+};
+
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct InitializeBundleAudit<'info> {
+    /// Anyone can initialize the audit. Writable signer paying the bundle audit PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Only allow auditing bundles that have been relayed. Seeds ["root_bundle", seed, root_bundle_id].
+    #[account(seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()], bump)]
+    pub root_bundle: Account<'info, RootBundle>,
+
+    /// Bundle audit PDA created for the root bundle, seeds ["bundle_audit", seed, root_bundle_id].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE
+            + BundleAudit::INIT_SPACE
+            + state.bundle_audit_max_mints as usize * VaultSnapshot::INIT_SPACE,
+        seeds = [b"bundle_audit", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bundle_audit: Account<'info, BundleAudit>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_bundle_audit(ctx: Context<InitializeBundleAudit>, root_bundle_id: u32) -> Result<()> {
+    let bundle_audit = &mut ctx.accounts.bundle_audit;
+    bundle_audit.root_bundle_id = root_bundle_id;
+    bundle_audit.max_mints = ctx.accounts.state.bundle_audit_max_mints;
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token, token_2022, token_interface::TokenAccount};
+
+use crate::{
+    constants::{BUNDLE_SUMMARY_MAX_MINTS, SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE},
+    error::SvmError,
+    state::{BundleSummaryLeaf, FillStatus, FillStatusAccount, RootBundle, SimulateBundleSummaryParams, State},
+    utils::{
+        get_current_time, get_v3_relay_hash, get_vault_authority, is_claimed, process_proof, set_claimed,
+        OrArithmeticOverflow,
+    },
+    V3SlowFill,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintOutflow {
+    pub mint: Pubkey,
+    pub vault_balance: u64, // Vault balance before simulating the bundle.
+    pub total_outflow: u64, // Sum paid out of the vault by the executable leaves.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BundleSummary {
+    pub executable: u32,
+    pub blocked_by_balance: u32, // Vault balance left after the preceding executable leaves does not cover the leaf.
+    pub blocked_by_status: u32,  // Refund leaf already claimed or slow relay already filled or expired.
+    pub invalid: u32,            // Leaf not included in the root bundle or issued for another chain.
+    pub mint_outflows: Vec<MintOutflow>,
+}
+
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct SimulateBundleSummary<'info> {
+    /// Account that loaded the leaves into its instruction params.
+    pub signer: Signer<'info>,
+
+    /// Instruction params PDA of the signer, seeds ["instruction_params", signer], loaded beforehand with
+    /// write_instruction_params. Contains the leaves to simulate with their proofs.
+    #[account(seeds = [b"instruction_params", signer.key().as_ref()], bump)]
+    pub instruction_params: Account<'info, SimulateBundleSummaryParams>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Root bundle PDA of the simulated leaves, seeds ["root_bundle", seed, root_bundle_id]. Read-only.
+    #[account(
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub root_bundle: Account<'info, RootBundle>,
+}
+
+// Leaves are simulated in order against a local copy of the claimed bitmap, fill statuses and vault balances, so that
+// later leaves observe the effects of the preceding executable ones. Slow relay outflows are accounted at their updated
+// output amount, before any route decimal shift and fees.
+pub fn simulate_bundle_summary(ctx: Context<SimulateBundleSummary>) -> Result<BundleSummary> {
+    let state = &ctx.accounts.state;
+    let root_bundle = &ctx.accounts.root_bundle;
+    let current_time = get_current_time(state)?;
+
+    let mut claimed_bitmap = root_bundle.claimed_bitmap.clone();
+    let mut filled_relay_hashes: Vec<[u8; 32]> = Vec::new();
+    let mut summary = BundleSummary::default();
+
+    let mut remaining_accounts = ctx.remaining_accounts.iter();
+    let mut next_account = || remaining_accounts.next().ok_or(ErrorCode::AccountNotEnoughKeys);
+
+    for leaf in &ctx.accounts.instruction_params.leaves {
+        let outcome = match leaf {
+            BundleSummaryLeaf::RelayerRefund { relayer_refund_leaf, proof } => {
+                let vault = next_account()?;
+                let leaf_hash = relayer_refund_leaf.to_keccak_hash()?;
+                if process_proof(proof, &leaf_hash) != root_bundle.relayer_refund_root
+                    || relayer_refund_leaf.chain_id != state.chain_id
+                    || relayer_refund_leaf.refund_addresses.len() != relayer_refund_leaf.refund_amounts.len()
+                {
+                    LeafOutcome::Invalid
+                } else if is_claimed(&claimed_bitmap, relayer_refund_leaf.leaf_id) {
+                    LeafOutcome::BlockedByStatus
+                } else {
+                    let outflow = relayer_refund_leaf
+                        .refund_amounts
+                        .iter()
+                        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+                        .or_overflow("simulated refund outflow")?;
+                    let mint = &relayer_refund_leaf.mint_public_key;
+                    let outcome = record_outflow(&mut summary, state, vault, mint, outflow)?;
+                    if matches!(outcome, LeafOutcome::Executable) {
+                        set_claimed(&mut claimed_bitmap, relayer_refund_leaf.leaf_id);
+                    }
+                    outcome
+                }
+            }
+            BundleSummaryLeaf::SlowRelay { slow_fill_leaf, proof } => {
+                let vault = next_account()?;
+                let fill_status = next_account()?;
+                let relay_data = &slow_fill_leaf.relay_data;
+                // Same as in execution, the leaf chain ID is overridden with the state chain ID.
+                let slow_fill = V3SlowFill {
+                    relay_data: relay_data.clone(),
+                    chain_id: state.chain_id,
+                    updated_output_amount: slow_fill_leaf.updated_output_amount,
+                };
+                let relay_hash = get_v3_relay_hash(relay_data, state.chain_id);
+                if fill_status.key() != Pubkey::find_program_address(&[b"fills", relay_hash.as_ref()], &crate::ID).0 {
+                    return err!(SvmError::InvalidBundleSummaryAccount);
+                }
+
+                let execution_deadline_passed =
+                    root_bundle.execution_deadline != 0 && current_time > root_bundle.execution_deadline;
+                let fill_deadline_passed =
+                    relay_data.fill_deadline < current_time && !SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE;
+
+                if process_proof(proof, &slow_fill.to_keccak_hash()?) != root_bundle.slow_relay_root {
+                    LeafOutcome::Invalid
+                } else if execution_deadline_passed
+                    || fill_deadline_passed
+                    || is_filled(fill_status)
+                    || filled_relay_hashes.contains(&relay_hash)
+                {
+                    LeafOutcome::BlockedByStatus
+                } else {
+                    let outflow = slow_fill_leaf.updated_output_amount;
+                    let outcome = record_outflow(&mut summary, state, vault, &relay_data.output_token, outflow)?;
+                    if matches!(outcome, LeafOutcome::Executable) {
+                        filled_relay_hashes.push(relay_hash);
+                    }
+                    outcome
+                }
+            }
+        };
+
+        match outcome {
+            LeafOutcome::Executable => summary.executable += 1,
+            LeafOutcome::BlockedByBalance => summary.blocked_by_balance += 1,
+            LeafOutcome::BlockedByStatus => summary.blocked_by_status += 1,
+            LeafOutcome::Invalid => summary.invalid += 1,
+        }
+    }
+
+    Ok(summary)
+}
+
+enum LeafOutcome {
+    Executable,
+    BlockedByBalance,
+    BlockedByStatus,
+    Invalid,
+}
+
+// Debits the outflow against the simulated vault balance of the mint, loading the vault on its first use.
+fn record_outflow(
+    summary: &mut BundleSummary,
+    state: &Account<State>,
+    vault: &AccountInfo,
+    mint: &Pubkey,
+    outflow: u64,
+) -> Result<LeafOutcome> {
+    // Vaults are the vault authority ATAs of either token program, selected by the owner of the passed account.
+    let token_program = match *vault.owner {
+        token_2022::ID => token_2022::ID,
+        _ => token::ID,
+    };
+    let vault_authority = get_vault_authority(&state.key());
+    let expected_vault =
+        associated_token::get_associated_token_address_with_program_id(&vault_authority, mint, &token_program);
+    if vault.key() != expected_vault {
+        return err!(SvmError::InvalidBundleSummaryAccount);
+    }
+
+    let mint_outflow = match summary.mint_outflows.iter().position(|mint_outflow| mint_outflow.mint == *mint) {
+        Some(index) => &mut summary.mint_outflows[index],
+        None => {
+            if summary.mint_outflows.len() >= BUNDLE_SUMMARY_MAX_MINTS {
+                return err!(SvmError::TooManyBundleSummaryMints);
+            }
+            let data = vault.try_borrow_data()?;
+            let vault_balance = TokenAccount::try_deserialize(&mut &data[..])
+                .map_err(|_| SvmError::InvalidBundleSummaryAccount)?
+                .amount;
+            summary.mint_outflows.push(MintOutflow { mint: *mint, vault_balance, total_outflow: 0 });
+            summary.mint_outflows.last_mut().unwrap() // Just pushed above.
+        }
+    };
+
+    let total_outflow = mint_outflow.total_outflow.checked_add(outflow).or_overflow("simulated vault outflow")?;
+    if total_outflow > mint_outflow.vault_balance {
+        return Ok(LeafOutcome::BlockedByBalance);
+    }
+    mint_outflow.total_outflow = total_outflow;
+
+    Ok(LeafOutcome::Executable)
+}
+
+// Fill status PDAs that were never created or have been closed are not filled.
+fn is_filled(fill_status: &AccountInfo) -> bool {
+    if fill_status.owner != &crate::ID {
+        return false;
+    }
+
+    let data = match fill_status.try_borrow_data() {
+        Ok(data) => data,
+        Err(_) => return false,
+    };
+    FillStatusAccount::try_deserialize(&mut &data[..]).is_ok_and(|account| account.status == FillStatus::Filled)
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::{CommonError, SvmError},
+    event::{RemovedChainAlias, SetChainAlias},
+    state::{ChainAlias, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(alias: u64)]
+pub struct SetChainAliasState<'info> {
+    /// Owner maintaining the chain aliases. Writable signer paying the chain alias rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Chain alias PDA, seeds ["chain_alias", seed, alias]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + ChainAlias::INIT_SPACE,
+        seeds = [b"chain_alias", state.seed.to_le_bytes().as_ref(), alias.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_alias: Account<'info, ChainAlias>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_chain_alias(ctx: Context<SetChainAliasState>, alias: u64, canonical_chain_id: u64) -> Result<()> {
+    if canonical_chain_id == 0 {
+        return err!(CommonError::InvalidChainId);
+    }
+    ctx.accounts.chain_alias.canonical_chain_id = canonical_chain_id;
+
+    emit_cpi!(SetChainAlias { alias, canonical_chain_id });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(alias: u64)]
+pub struct RemoveChainAlias<'info> {
+    /// Owner maintaining the chain aliases. Receives the chain alias rent.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Chain alias PDA being closed, seeds ["chain_alias", seed, alias]. Writable.
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"chain_alias", state.seed.to_le_bytes().as_ref(), alias.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_alias: Account<'info, ChainAlias>,
+}
+
+pub fn remove_chain_alias(ctx: Context<RemoveChainAlias>, alias: u64) -> Result<()> {
+    emit_cpi!(RemovedChainAlias { alias });
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{AddedComplianceEntry, RemovedComplianceEntry, SetComplianceAuthority},
+    state::{ComplianceAttestation, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetComplianceAuthorityState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_compliance_authority(
+    ctx: Context<SetComplianceAuthorityState>,
+    compliance_authority: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.state.compliance_authority = compliance_authority;
+
+    emit_cpi!(SetComplianceAuthority { compliance_authority });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(subject: Pubkey)]
+pub struct AddComplianceEntry<'info> {
+    /// Compliance authority configured in the state. Writable signer paying the attestation rent.
+    #[account(
+        mut,
+        constraint = state.compliance_authority == Some(signer.key()) @ SvmError::NotComplianceAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Compliance attestation PDA created for the subject, seeds ["compliance", seed, subject].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + ComplianceAttestation::INIT_SPACE,
+        seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), subject.as_ref()],
+        bump
+    )]
+    pub compliance_attestation: Account<'info, ComplianceAttestation>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_compliance_entry(ctx: Context<AddComplianceEntry>, subject: Pubkey) -> Result<()> {
+    ctx.accounts.compliance_attestation.subject = subject;
+
+    emit_cpi!(AddedComplianceEntry { subject });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(subject: Pubkey)]
+pub struct RemoveComplianceEntry<'info> {
+    /// Compliance authority configured in the state. Receives the attestation rent.
+    #[account(
+        mut,
+        constraint = state.compliance_authority == Some(signer.key()) @ SvmError::NotComplianceAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Compliance attestation PDA being closed, seeds ["compliance", seed, subject]. Writable.
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), subject.as_ref()],
+        bump
+    )]
+    pub compliance_attestation: Account<'info, ComplianceAttestation>,
+}
+
+pub fn remove_compliance_entry(ctx: Context<RemoveComplianceEntry>, subject: Pubkey) -> Result<()> {
+    emit_cpi!(RemovedComplianceEntry { subject });
+
+    Ok(())
+}
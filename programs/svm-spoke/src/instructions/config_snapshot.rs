@@ -0,0 +1,113 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    error::SvmError,
+    event::ConfigSnapshot,
+    state::{LookupTableConfig, State},
+    utils::get_current_time,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmitConfigSnapshot<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to record the snapshot time.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Lookup table config PDA, seeds ["lookup_table_config", seed]. Read-only, not created until
+    /// create_lookup_table is called.
+    #[account(seeds = [b"lookup_table_config", state.seed.to_le_bytes().as_ref()], bump)]
+    pub lookup_table_config: UncheckedAccount<'info>,
+}
+
+pub fn emit_config_snapshot(ctx: Context<EmitConfigSnapshot>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+
+    // The first snapshot is always allowed, later ones at most once per snapshot interval.
+    let next_snapshot_time = state.last_config_snapshot_time.saturating_add(state.snapshot_min_interval);
+    if state.last_config_snapshot_time != 0 && current_time < next_snapshot_time {
+        return err!(SvmError::ConfigSnapshotTooSoon);
+    }
+    state.last_config_snapshot_time = current_time;
+
+    let lookup_table_config = &ctx.accounts.lookup_table_config;
+    let lookup_table = match lookup_table_config.owner == &system_program::ID {
+        true => None,
+        false => {
+            require_keys_eq!(*lookup_table_config.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+            Some(LookupTableConfig::try_deserialize(&mut &lookup_table_config.try_borrow_data()?[..])?.lookup_table)
+        }
+    };
+
+    emit_cpi!(ConfigSnapshot {
+        snapshot_time: current_time,
+        seed: state.seed,
+        owner: state.owner,
+        pending_owner: state.pending_owner,
+        cross_domain_admin: state.cross_domain_admin,
+        chain_id: state.chain_id,
+        remote_domain: state.remote_domain,
+        network: state.network,
+        paused_deposits: state.paused_deposits,
+        paused_fills: state.paused_fills,
+        fills_pause_expires_at: state.fills_pause_expires_at,
+        number_of_deposits: state.number_of_deposits,
+        root_bundle_id: state.root_bundle_id,
+        deposit_quote_time_buffer: state.deposit_quote_time_buffer,
+        fill_deadline_buffer: state.fill_deadline_buffer,
+        winding_down: state.winding_down,
+        wind_down_time: state.wind_down_time,
+        wind_down_delay: state.wind_down_delay,
+        compliance_authority: state.compliance_authority,
+        bundle_audits_enabled: state.bundle_audits_enabled,
+        bundle_audit_max_mints: state.bundle_audit_max_mints,
+        slow_fill_rate_limit_enabled: state.slow_fill_rate_limit_enabled,
+        slow_fill_rate_limit: state.slow_fill_rate_limit,
+        slow_fill_rate_window: state.slow_fill_rate_window,
+        slow_fill_grace_seconds: state.slow_fill_grace_seconds,
+        fill_confirmation_oracle: state.fill_confirmation_oracle,
+        dispute_freeze_until: state.dispute_freeze_until,
+        protocol_fee_bps: state.protocol_fee_bps,
+        protocol_fee_recipient: state.protocol_fee_recipient,
+        event_commitments_enabled: state.event_commitments_enabled,
+        liquidity_pools_enabled: state.liquidity_pools_enabled,
+        liquidity_pool_fee_bps: state.liquidity_pool_fee_bps,
+        fill_index_enabled: state.fill_index_enabled,
+        vault_outflow_rate_limits_enabled: state.vault_outflow_rate_limits_enabled,
+        event_forwarder: state.event_forwarder,
+        event_forwarder_best_effort: state.event_forwarder_best_effort,
+        permissioned_mode: state.permissioned_mode,
+        strict_slow_fill_requester: state.strict_slow_fill_requester,
+        upgrade_effective_after_slot: state.upgrade_effective_after_slot,
+        execution_approver: state.execution_approver,
+        execution_approval_validity: state.execution_approval_validity,
+        execution_approval_thresholds: state.execution_approval_thresholds.clone(),
+        snapshot_min_interval: state.snapshot_min_interval,
+        lookup_table,
+        exclusive_slow_fills_disabled: state.exclusive_slow_fills_disabled,
+        canonical: state.canonical,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct IsCanonical<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn is_canonical(ctx: Context<IsCanonical>) -> Result<bool> {
+    Ok(ctx.accounts.state.canonical)
+}
@@ -8,16 +8,21 @@ use crate::error::SvmError;
 
 #[derive(Accounts)]
 pub struct CreateTokenAccounts<'info> {
+    /// Anyone can create the token accounts passed as remaining accounts. Writable signer paying their rent.
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Mint of the associated token accounts to create.
     #[account(mint::token_program = token_program)]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
 
+    /// Associated token program, used to create associated token accounts.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
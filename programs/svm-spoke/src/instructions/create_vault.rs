@@ -0,0 +1,95 @@
+use anchor_lang::{error::ErrorCode, prelude::*};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::VaultCreated,
+    state::{State, Vault},
+    utils::{get_vault_authority, is_canonical_token_program},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    /// Anyone can create the vault of a mint. Writable signer paying the vault ATA and vault PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the vault.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Owner of the vault, the vault authority PDA, seeds ["vault_authority", state].
+    #[account(constraint = authority.key() == get_vault_authority(&state.key()) @ SvmError::InvalidVault)]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: Writable vault ATA of the authority for the mint, created if missing. Vaults already created when
+    /// enabling routes are registered as they are.
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&authority.key(), &mint.key(), &token_program.key())
+            @ ErrorCode::ConstraintAssociated
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Vault PDA registering the mint, seeds ["vault", seed, mint].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + Vault::INIT_SPACE,
+        seeds = [b"vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault_registry: Account<'info, Vault>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated token program, used to create associated token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
+    let cpi_accounts = associated_token::Create {
+        payer: ctx.accounts.signer.to_account_info(),
+        associated_token: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.associated_token_program.to_account_info(), cpi_accounts);
+    associated_token::create_idempotent(cpi_ctx)?;
+
+    let vault_registry = &mut ctx.accounts.vault_registry;
+    vault_registry.mint = ctx.accounts.mint.key();
+    vault_registry.token_program = ctx.accounts.token_program.key();
+    vault_registry.authority = ctx.accounts.authority.key();
+
+    emit_cpi!(VaultCreated {
+        mint: vault_registry.mint,
+        token_program: vault_registry.token_program,
+        authority: vault_registry.authority,
+        vault: ctx.accounts.vault.key(),
+    });
+
+    Ok(())
+}
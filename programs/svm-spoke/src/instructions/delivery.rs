@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::{ClearedDeliveryPreference, SetDeliveryPreference, WithdrewEscrow},
+    state::{DeliveryMode, DeliveryPreference, State},
+    utils::transfer_from,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDeliveryPreferenceState<'info> {
+    /// Only the recipient can set its own delivery preference. Writable signer paying the delivery preference PDA rent.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Delivery preference PDA of the recipient, seeds ["delivery_preference", seed, recipient]. Created if missing and
+    /// writable.
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = DISCRIMINATOR_SIZE + DeliveryPreference::INIT_SPACE,
+        seeds = [b"delivery_preference", state.seed.to_le_bytes().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub delivery_preference: Account<'info, DeliveryPreference>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_delivery_preference(ctx: Context<SetDeliveryPreferenceState>, mode: DeliveryMode) -> Result<()> {
+    ctx.accounts.delivery_preference.mode = mode.clone();
+
+    emit_cpi!(SetDeliveryPreference { recipient: ctx.accounts.recipient.key(), mode });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClearDeliveryPreference<'info> {
+    /// Recipient that set the delivery preference. Writable signer receiving the PDA rent.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Delivery preference PDA being closed, seeds ["delivery_preference", seed, recipient]. Writable.
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"delivery_preference", state.seed.to_le_bytes().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub delivery_preference: Account<'info, DeliveryPreference>,
+}
+
+pub fn clear_delivery_preference(ctx: Context<ClearDeliveryPreference>) -> Result<()> {
+    emit_cpi!(ClearedDeliveryPreference { recipient: ctx.accounts.recipient.key() });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct InitializeRecipientEscrow<'info> {
+    /// Anyone can initialize the escrow of a recipient. Writable signer paying the token account rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the escrow.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the escrowed token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Escrow token account PDA created for the recipient, seeds ["escrow", seed, recipient, mint].
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"escrow", state.seed.to_le_bytes().as_ref(), recipient.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub recipient_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawEscrow<'info> {
+    /// Only the recipient can withdraw its escrowed tokens.
+    pub recipient: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the escrow.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the escrowed token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Writable escrow token account of the recipient, seeds ["escrow", seed, recipient, mint].
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"escrow", state.seed.to_le_bytes().as_ref(), recipient.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub recipient_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Writable token account for the mint receiving the withdrawn tokens. Recipient can withdraw to any token account,
+    /// e.g. after rotating its address.
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_escrow(ctx: Context<WithdrawEscrow>, amount: u64) -> Result<()> {
+    transfer_from(
+        &ctx.accounts.recipient_escrow,
+        &ctx.accounts.destination,
+        amount,
+        &ctx.accounts.state,
+        ctx.bumps.state,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(WithdrewEscrow {
+        recipient: ctx.accounts.recipient.key(),
+        mint: ctx.accounts.mint.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,12 +1,28 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    token::Token,
+    token_interface::{
+        close_account, sync_native, transfer_checked, CloseAccount, Mint, SyncNative, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
 
 use crate::{
-    constants::{MAX_EXCLUSIVITY_PERIOD_SECONDS, ZERO_DEPOSIT_ID},
+    constants::{DISCRIMINATOR_SIZE, NATIVE_MINT, ZERO_DEPOSIT_ID},
+    constraints::is_message_too_large,
     error::{CommonError, SvmError},
-    event::V3FundsDeposited,
-    state::{Route, State},
-    utils::{get_current_time, get_unsafe_deposit_id, transfer_from},
+    event::{SwappedForDeposit, V3FundsDeposited},
+    state::{ChainAlias, EventCommitment, Route, State, SwapAdapter, VaultLiability},
+    utils::{
+        apply_depositor_nonce, check_deposit_limit, commit_event, get_canonical_chain_id, get_current_time,
+        get_event_message, get_unsafe_deposit_id, has_transfer_fee, is_registered_token_program, is_vault,
+        parse_extra_args, resolve_chain_id, resolve_exclusivity_deadline, revoke_state_delegation, transfer_from,
+        ExtraArgs, OrArithmeticOverflow, ARGS_VERSION_V0,
+    },
 };
 
 #[event_cpi]
@@ -21,23 +37,72 @@ use crate::{
     destination_chain_id: u64,
 )]
 pub struct DepositV3<'info> {
+    /// Depositor or an account with a token delegation from the depositor. Writable signer.
     #[account(mut)]
     pub signer: Signer<'info>,
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to increment the deposit count,
+    /// deposits must not be paused and the spoke must not be winding down.
     #[account(
         mut,
-        seeds = [b"state", state.seed.to_le_bytes().as_ref()],
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
         bump,
-        constraint = !state.paused_deposits @ CommonError::DepositsArePaused
+        constraint = !state.paused_deposits @ CommonError::DepositsArePaused,
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
     )]
     pub state: Account<'info, State>,
 
+    /// Chain alias PDA of the passed destination chain ID, seeds ["chain_alias", seed, destination_chain_id]. Routes,
+    /// deposit limits and the deposit event use its canonical chain ID. Required when the state has strict chain
+    /// aliases. Pass this program ID to represent None.
     #[account(
-        seeds = [b"route", input_token.as_ref(), state.seed.to_le_bytes().as_ref(), destination_chain_id.to_le_bytes().as_ref()],
-        bump,
+        seeds = [b"chain_alias", state.seed.to_le_bytes().as_ref(), destination_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub destination_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// Enabled route PDA for the input token and canonical destination chain, seeds ["route", input_token, seed,
+    /// destination_chain_id].
+    #[account(
+        seeds = [
+            b"route",
+            input_token.as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            get_canonical_chain_id(&destination_chain_alias, destination_chain_id).to_le_bytes().as_ref()
+        ],
+        bump = route.bump,
         constraint = route.enabled @ CommonError::DisabledRoute
     )]
     pub route: Account<'info, Route>,
 
+    /// CHECK: Deposit limit PDA of the route, seeds ["deposit_limit", seed, input_token, destination_chain_id].
+    /// Read-only. Deserialized in the handler, as routes without a created deposit limit are unlimited.
+    #[account(
+        seeds = [
+            b"deposit_limit",
+            state.seed.to_le_bytes().as_ref(),
+            input_token.as_ref(),
+            get_canonical_chain_id(&destination_chain_alias, destination_chain_id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub deposit_limit: UncheckedAccount<'info>,
+
+    /// CHECK: Depositor nonce PDA, seeds ["depositor_nonce", seed, depositor]. Writable. Deserialized and incremented
+    /// in the handler, as deposits of depositors without a created depositor nonce are unordered.
+    #[account(mut, seeds = [b"depositor_nonce", state.seed.to_le_bytes().as_ref(), depositor.as_ref()], bump)]
+    pub depositor_nonce: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA of the input token, seeds ["vault", seed, input_token]. Read-only. Deposits of mints whose
+    /// vault was registered by create_vault must pass its token program, others fail with InvalidVault.
+    #[account(
+        seeds = [b"vault", state.seed.to_le_bytes().as_ref(), input_token.as_ref()],
+        bump,
+        constraint = is_registered_token_program(&vault_registry, &token_program.key()) @ SvmError::InvalidVault
+    )]
+    pub vault_registry: UncheckedAccount<'info>,
+
+    /// Writable ATA of the depositor for the input token, source of the deposited tokens.
     #[account(
         mut,
         associated_token::mint = mint,
@@ -46,21 +111,44 @@ pub struct DepositV3<'info> {
     )]
     pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Writable vault ATA of the vault authority PDA for the input token, receiving the deposited tokens. For
+    /// input tokens with a transfer fee its balance change is recorded as the deposited input_amount.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = state, // Ensure owner is the state as tokens are sent here on deposit.
-        associated_token::token_program = token_program
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Mint of the input token. Its decimals must match the decimals recorded when the route was enabled.
     #[account(
         mint::token_program = token_program,
-        constraint = mint.key() == input_token @ SvmError::InvalidMint
+        constraint = mint.key() == input_token @ SvmError::InvalidMint,
+        constraint = mint.decimals == route.expected_decimals @ SvmError::UnexpectedMintDecimals
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Vault liability PDA of the input token, seeds ["vault_liability", seed, input_token]. Created if missing and
+    /// credited with the deposited amount.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + VaultLiability::INIT_SPACE,
+        seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), input_token.as_ref()],
+        bump
+    )]
+    pub vault_liability: Account<'info, VaultLiability>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
 }
 
 pub fn _deposit_v3(
@@ -78,29 +166,27 @@ pub fn _deposit_v3(
     fill_deadline: u32,
     exclusivity_parameter: u32,
     message: Vec<u8>,
+    revoke_after: bool,
+    depositor_nonce: Option<u64>,
 ) -> Result<()> {
     let state = &mut ctx.accounts.state;
 
-    let current_time = get_current_time(state)?;
-
-    if current_time.checked_sub(quote_timestamp).unwrap_or(u32::MAX) > state.deposit_quote_time_buffer {
-        return err!(CommonError::InvalidQuoteTimestamp);
-    }
-
-    if fill_deadline < current_time || fill_deadline > current_time + state.fill_deadline_buffer {
-        return err!(CommonError::InvalidFillDeadline);
-    }
-
-    let mut exclusivity_deadline = exclusivity_parameter;
-    if exclusivity_deadline > 0 {
-        if exclusivity_deadline <= MAX_EXCLUSIVITY_PERIOD_SECONDS {
-            exclusivity_deadline += current_time;
-        }
+    let exclusivity_deadline = validate_deposit(
+        state,
+        &ctx.accounts.route,
+        exclusive_relayer,
+        quote_timestamp,
+        fill_deadline,
+        exclusivity_parameter,
+        &message,
+    )?;
+    let destination_chain_id = resolve_chain_id(state, &ctx.accounts.destination_chain_alias, destination_chain_id)?;
 
-        if exclusive_relayer == Pubkey::default() {
-            return err!(CommonError::InvalidExclusiveRelayer);
-        }
-    }
+    // Fee-on-transfer tokens credit the vault with less than input_amount, so the received amount is measured instead.
+    let transfer_fee = has_transfer_fee(&ctx.accounts.mint)?;
+    let vault_balance_before = ctx.accounts.vault.amount;
+    check_deposit_limit(&ctx.accounts.deposit_limit, vault_balance_before, input_amount)?;
+    let depositor_nonce = apply_depositor_nonce(&ctx.accounts.depositor_nonce, depositor_nonce)?;
 
     // Depositor must have delegated input_amount to the state PDA.
     transfer_from(
@@ -113,32 +199,110 @@ pub fn _deposit_v3(
         &ctx.accounts.token_program,
     )?;
 
-    let mut applied_deposit_id = deposit_id;
-    // If the passed in deposit_id is all zeros, then we use the state's number of deposits as deposit_id.
-    if deposit_id == ZERO_DEPOSIT_ID {
-        state.number_of_deposits += 1;
-        applied_deposit_id[..4].copy_from_slice(&state.number_of_deposits.to_le_bytes());
+    let mut input_amount = input_amount;
+    if transfer_fee {
+        ctx.accounts.vault.reload()?;
+        input_amount =
+            ctx.accounts.vault.amount.checked_sub(vault_balance_before).or_overflow("received deposit amount")?;
+        if input_amount == 0 {
+            return err!(SvmError::ZeroDepositAfterTransferFee);
+        }
     }
 
-    emit_cpi!(V3FundsDeposited {
+    if revoke_after {
+        revoke_state_delegation(
+            &mut ctx.accounts.depositor_token_account,
+            &ctx.accounts.signer,
+            state,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // Deposited tokens back refunds and slow fill payouts, so they can not be swept from the vault.
+    let vault_liability = &mut ctx.accounts.vault_liability;
+    vault_liability.liabilities =
+        vault_liability.liabilities.checked_add(input_amount).or_overflow("vault liabilities")?;
+
+    // The first deposit ends the genesis window, so that exported state can only be imported before the spoke is live.
+    state.genesis = false;
+
+    let (event_message, message_hash) = get_event_message(message);
+    let funds_deposited = V3FundsDeposited {
         input_token,
         output_token,
         input_amount,
         output_amount,
         destination_chain_id,
-        deposit_id: applied_deposit_id,
+        deposit_id: apply_deposit_id(state, deposit_id)?,
         quote_timestamp,
         fill_deadline,
         exclusivity_deadline,
         depositor,
         recipient,
         exclusive_relayer,
-        message,
-    });
+        message: event_message,
+        token_decimals: ctx.accounts.mint.decimals,
+        unsafe_deposit_id: deposit_id != ZERO_DEPOSIT_ID,
+        depositor_nonce,
+        message_hash,
+        exclusivity_parameter,
+        dust_refunded: None,
+    };
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, funds_deposited)?);
 
     Ok(())
 }
 
+// Validates the deposit parameters shared by all deposit instructions, returning the resolved exclusivity deadline.
+fn validate_deposit(
+    state: &State,
+    route: &Route,
+    exclusive_relayer: Pubkey,
+    quote_timestamp: u32,
+    fill_deadline: u32,
+    exclusivity_parameter: u32,
+    message: &[u8],
+) -> Result<u32> {
+    let current_time = get_current_time(state)?;
+
+    if is_message_too_large(message) {
+        return err!(SvmError::MessageTooLarge);
+    }
+
+    let disable_at = route.disable_at;
+    if disable_at != 0 && current_time >= disable_at {
+        return err!(CommonError::DisabledRoute);
+    }
+
+    if current_time.checked_sub(quote_timestamp).unwrap_or(u32::MAX) > state.deposit_quote_time_buffer {
+        return err!(CommonError::InvalidQuoteTimestamp);
+    }
+
+    let max_fill_deadline = current_time.checked_add(state.fill_deadline_buffer).or_overflow("fill deadline buffer")?;
+    if fill_deadline < current_time || fill_deadline > max_fill_deadline {
+        return err!(CommonError::InvalidFillDeadline);
+    }
+
+    // As on EVM, exclusive deposits must name their relayer so that funds aren't locked during the exclusivity period.
+    let exclusivity_deadline = resolve_exclusivity_deadline(exclusivity_parameter, current_time)?;
+    if exclusivity_parameter > 0 && exclusive_relayer == Pubkey::default() {
+        return err!(CommonError::InvalidExclusiveRelayer);
+    }
+
+    Ok(exclusivity_deadline)
+}
+
+// If the passed in deposit_id is all zeros, then we use the state's number of deposits as deposit_id.
+fn apply_deposit_id(state: &mut State, deposit_id: [u8; 32]) -> Result<[u8; 32]> {
+    let mut applied_deposit_id = deposit_id;
+    if deposit_id == ZERO_DEPOSIT_ID {
+        state.number_of_deposits = state.number_of_deposits.checked_add(1).or_overflow("number of deposits")?;
+        applied_deposit_id[..4].copy_from_slice(&state.number_of_deposits.to_le_bytes());
+    }
+
+    Ok(applied_deposit_id)
+}
+
 pub fn deposit_v3(
     ctx: Context<DepositV3>,
     depositor: Pubkey,
@@ -153,7 +317,16 @@ pub fn deposit_v3(
     fill_deadline: u32,
     exclusivity_parameter: u32,
     message: Vec<u8>,
+    revoke_after: bool,
+    args_version: u8,
+    extra: Vec<u8>,
 ) -> Result<()> {
+    // Deposits only support the depositor nonce, other extra parameters must not be silently dropped.
+    let extra_args = parse_extra_args(args_version, &extra)?;
+    if !matches!(extra_args, ExtraArgs::V0 | ExtraArgs::V5(_)) {
+        return err!(SvmError::UnsupportedArgsVersion);
+    }
+
     _deposit_v3(
         ctx,
         depositor,
@@ -169,6 +342,8 @@ pub fn deposit_v3(
         fill_deadline,
         exclusivity_parameter,
         message,
+        revoke_after,
+        extra_args.depositor_nonce(),
     )?;
 
     Ok(())
@@ -201,9 +376,12 @@ pub fn deposit_v3_now(
         destination_chain_id,
         exclusive_relayer,
         current_time,
-        current_time + fill_deadline_offset,
+        current_time.checked_add(fill_deadline_offset).or_overflow("fill deadline offset")?,
         exclusivity_period,
         message,
+        false,
+        ARGS_VERSION_V0,
+        Vec::new(),
     )?;
 
     Ok(())
@@ -242,7 +420,467 @@ pub fn unsafe_deposit_v3(
         fill_deadline,
         exclusivity_parameter,
         message,
+        false,
+        None,
     )?;
 
     Ok(())
 }
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+    recipient: Pubkey,
+    output_token: Pubkey,
+    input_amount: u64,
+    output_amount: u64,
+    destination_chain_id: u64,
+)]
+pub struct DepositV3Native<'info> {
+    /// Depositor paying the deposited lamports. Writable signer.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to increment the deposit count,
+    /// deposits must not be paused and the spoke must not be winding down.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = !state.paused_deposits @ CommonError::DepositsArePaused,
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Chain alias PDA of the passed destination chain ID, seeds ["chain_alias", seed, destination_chain_id]. Routes,
+    /// deposit limits and the deposit event use its canonical chain ID. Required when the state has strict chain
+    /// aliases. Pass this program ID to represent None.
+    #[account(
+        seeds = [b"chain_alias", state.seed.to_le_bytes().as_ref(), destination_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub destination_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// Enabled route PDA for the wSOL mint and canonical destination chain, seeds ["route", wsol_mint, seed,
+    /// destination_chain_id].
+    #[account(
+        seeds = [
+            b"route",
+            NATIVE_MINT.as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            get_canonical_chain_id(&destination_chain_alias, destination_chain_id).to_le_bytes().as_ref()
+        ],
+        bump = route.bump,
+        constraint = route.enabled @ CommonError::DisabledRoute
+    )]
+    pub route: Account<'info, Route>,
+
+    /// CHECK: Deposit limit PDA of the route, seeds ["deposit_limit", seed, wsol_mint, destination_chain_id].
+    /// Read-only. Deserialized in the handler, as routes without a created deposit limit are unlimited.
+    #[account(
+        seeds = [
+            b"deposit_limit",
+            state.seed.to_le_bytes().as_ref(),
+            NATIVE_MINT.as_ref(),
+            get_canonical_chain_id(&destination_chain_alias, destination_chain_id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub deposit_limit: UncheckedAccount<'info>,
+
+    /// Writable wSOL vault ATA of the vault authority PDA, receiving the deposited lamports that are synced
+    /// into its balance.
+    #[account(
+        mut,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The wSOL mint. Its decimals must match the decimals recorded when the route was enabled.
+    #[account(
+        address = NATIVE_MINT @ SvmError::InvalidMint,
+        constraint = mint.decimals == route.expected_decimals @ SvmError::UnexpectedMintDecimals
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault liability PDA of the wSOL mint, seeds ["vault_liability", seed, wsol_mint]. Created if missing and
+    /// credited with the deposited amount.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + VaultLiability::INIT_SPACE,
+        seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), NATIVE_MINT.as_ref()],
+        bump
+    )]
+    pub vault_liability: Account<'info, VaultLiability>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// SPL Token program owning the wSOL mint.
+    pub token_program: Program<'info, Token>,
+    /// System program, used to transfer the deposited lamports and to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// Temporary wSOL token account of the state, seeds ["native_wrap", seed, signer], created and closed back to the
+    /// signer within the deposit. Declared last so that existing clients only insert it ahead of the event accounts.
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"native_wrap", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub native_wrap_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+// Lamports are wrapped in a temporary account and exactly input_amount is moved into the vault. Syncing also wraps any
+// lamports the account held before its creation, which are returned to the depositor as dust along with the rent when
+// the account is closed. All of this happens within the instruction, so the depositor is debited exactly input_amount
+// (besides fees and less any dust) or nothing when the deposit fails.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_v3_native(
+    ctx: Context<DepositV3Native>,
+    recipient: Pubkey,
+    output_token: Pubkey,
+    input_amount: u64,
+    output_amount: u64,
+    destination_chain_id: u64,
+    exclusive_relayer: Pubkey,
+    quote_timestamp: u32,
+    fill_deadline: u32,
+    exclusivity_parameter: u32,
+    message: Vec<u8>,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+
+    let exclusivity_deadline = validate_deposit(
+        state,
+        &ctx.accounts.route,
+        exclusive_relayer,
+        quote_timestamp,
+        fill_deadline,
+        exclusivity_parameter,
+        &message,
+    )?;
+    let destination_chain_id = resolve_chain_id(state, &ctx.accounts.destination_chain_alias, destination_chain_id)?;
+
+    check_deposit_limit(&ctx.accounts.deposit_limit, ctx.accounts.vault.amount, input_amount)?;
+
+    let native_wrap_account = &mut ctx.accounts.native_wrap_account;
+    let transfer_accounts =
+        Transfer { from: ctx.accounts.signer.to_account_info(), to: native_wrap_account.to_account_info() };
+    transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts), input_amount)?;
+
+    let sync_accounts = SyncNative { account: native_wrap_account.to_account_info() };
+    sync_native(CpiContext::new(ctx.accounts.token_program.to_account_info(), sync_accounts))?;
+
+    let state_seed_bytes = state.seed.to_le_bytes();
+    let seeds = &[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_accounts = TransferChecked {
+        from: native_wrap_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: state.to_account_info(),
+    };
+    let cpi_context =
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer_seeds);
+    transfer_checked(cpi_context, input_amount, ctx.accounts.mint.decimals)?;
+
+    native_wrap_account.reload()?;
+    let dust = native_wrap_account.amount;
+
+    let close_accounts = CloseAccount {
+        account: native_wrap_account.to_account_info(),
+        destination: ctx.accounts.signer.to_account_info(),
+        authority: state.to_account_info(),
+    };
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_accounts,
+        signer_seeds,
+    ))?;
+
+    let vault_liability = &mut ctx.accounts.vault_liability;
+    vault_liability.liabilities =
+        vault_liability.liabilities.checked_add(input_amount).or_overflow("vault liabilities")?;
+
+    state.genesis = false;
+
+    let (event_message, message_hash) = get_event_message(message);
+    let funds_deposited = V3FundsDeposited {
+        input_token: NATIVE_MINT,
+        output_token,
+        input_amount,
+        output_amount,
+        destination_chain_id,
+        deposit_id: apply_deposit_id(state, ZERO_DEPOSIT_ID)?,
+        quote_timestamp,
+        fill_deadline,
+        exclusivity_deadline,
+        depositor: ctx.accounts.signer.key(),
+        recipient,
+        exclusive_relayer,
+        message: event_message,
+        token_decimals: ctx.accounts.mint.decimals,
+        unsafe_deposit_id: false,
+        depositor_nonce: None,
+        message_hash,
+        exclusivity_parameter,
+        dust_refunded: (dust > 0).then_some(dust),
+    };
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, funds_deposited)?);
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+    recipient: Pubkey,
+    input_token: Pubkey,
+    output_token: Pubkey,
+    min_input_amount: u64,
+    output_amount: u64,
+    destination_chain_id: u64,
+)]
+pub struct DepositV3WithSwap<'info> {
+    /// Depositor paying the swapped tokens. Writable signer, its signature is forwarded to the swap program.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to increment the deposit count,
+    /// deposits must not be paused and the spoke must not be winding down.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = !state.paused_deposits @ CommonError::DepositsArePaused,
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// Chain alias PDA of the passed destination chain ID, seeds ["chain_alias", seed, destination_chain_id]. Routes,
+    /// deposit limits and the deposit event use its canonical chain ID. Required when the state has strict chain
+    /// aliases. Pass this program ID to represent None.
+    #[account(
+        seeds = [b"chain_alias", state.seed.to_le_bytes().as_ref(), destination_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub destination_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// Enabled route PDA for the input token and canonical destination chain, seeds ["route", input_token, seed,
+    /// destination_chain_id].
+    #[account(
+        seeds = [
+            b"route",
+            input_token.as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            get_canonical_chain_id(&destination_chain_alias, destination_chain_id).to_le_bytes().as_ref()
+        ],
+        bump = route.bump,
+        constraint = route.enabled @ CommonError::DisabledRoute
+    )]
+    pub route: Box<Account<'info, Route>>,
+
+    /// CHECK: Deposit limit PDA of the route, seeds ["deposit_limit", seed, input_token, destination_chain_id].
+    /// Read-only. Deserialized in the handler, as routes without a created deposit limit are unlimited.
+    #[account(
+        seeds = [
+            b"deposit_limit",
+            state.seed.to_le_bytes().as_ref(),
+            input_token.as_ref(),
+            get_canonical_chain_id(&destination_chain_alias, destination_chain_id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub deposit_limit: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA of the input token, seeds ["vault", seed, input_token]. Read-only. Deposits of mints whose
+    /// vault was registered by create_vault must pass its token program, others fail with InvalidVault.
+    #[account(
+        seeds = [b"vault", state.seed.to_le_bytes().as_ref(), input_token.as_ref()],
+        bump,
+        constraint = is_registered_token_program(&vault_registry, &token_program.key()) @ SvmError::InvalidVault
+    )]
+    pub vault_registry: UncheckedAccount<'info>,
+
+    /// Swap adapter PDA of the swap program, seeds ["swap_adapter", seed, swap_program]. Must be enabled by the owner.
+    #[account(
+        seeds = [b"swap_adapter", state.seed.to_le_bytes().as_ref(), swap_program.key().as_ref()],
+        bump,
+        constraint = swap_adapter.enabled @ SvmError::DisabledSwapAdapter
+    )]
+    pub swap_adapter: Account<'info, SwapAdapter>,
+
+    /// CHECK: Swap program invoked with the swap route and the remaining accounts. Whitelisted by the swap adapter.
+    #[account(executable)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// Mint of the token swapped by the depositor.
+    #[account(mint::token_program = swap_in_token_program)]
+    pub swap_in_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Writable token account of the depositor for the swapped token. Its balance decrease over the swap is reported
+    /// as the swap_in_amount.
+    #[account(
+        mut,
+        token::mint = swap_in_mint,
+        token::authority = signer,
+        token::token_program = swap_in_token_program
+    )]
+    pub depositor_swap_in_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Writable vault ATA of the vault authority PDA for the input token, receiving the swap output. Its
+    /// balance change over the swap is recorded as the deposited input_amount.
+    #[account(
+        mut,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint of the input token. Its decimals must match the decimals recorded when the route was enabled.
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() == input_token @ SvmError::InvalidMint,
+        constraint = mint.decimals == route.expected_decimals @ SvmError::UnexpectedMintDecimals
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Vault liability PDA of the input token, seeds ["vault_liability", seed, input_token]. Created if missing and
+    /// credited with the deposited amount.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + VaultLiability::INIT_SPACE,
+        seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), input_token.as_ref()],
+        bump
+    )]
+    pub vault_liability: Box<Account<'info, VaultLiability>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// SPL Token or Token-2022 program owning the input token mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token or Token-2022 program owning the swapped token mint.
+    pub swap_in_token_program: Interface<'info, TokenInterface>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// The swap program is invoked without any spoke signature, so it can only pull the swapped tokens with the forwarded
+// depositor signature and can only credit the vault. The deposited input_amount is the measured vault balance increase,
+// which covers swap programs returning less than quoted as well as input tokens with a transfer fee.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_v3_with_swap<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositV3WithSwap<'info>>,
+    recipient: Pubkey,
+    input_token: Pubkey,
+    output_token: Pubkey,
+    min_input_amount: u64,
+    output_amount: u64,
+    destination_chain_id: u64,
+    exclusive_relayer: Pubkey,
+    quote_timestamp: u32,
+    fill_deadline: u32,
+    exclusivity_parameter: u32,
+    message: Vec<u8>,
+    swap_route: Vec<u8>,
+) -> Result<()> {
+    let exclusivity_deadline = validate_deposit(
+        &ctx.accounts.state,
+        &ctx.accounts.route,
+        exclusive_relayer,
+        quote_timestamp,
+        fill_deadline,
+        exclusivity_parameter,
+        &message,
+    )?;
+    let destination_chain_id =
+        resolve_chain_id(&ctx.accounts.state, &ctx.accounts.destination_chain_alias, destination_chain_id)?;
+
+    let vault_balance_before = ctx.accounts.vault.amount;
+    let swap_in_balance_before = ctx.accounts.depositor_swap_in_token_account.amount;
+
+    // All swap accounts are passed as remaining accounts with the privileges of the outer transaction.
+    let accounts = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        })
+        .collect();
+    let instruction = Instruction { program_id: ctx.accounts.swap_program.key(), accounts, data: swap_route };
+    invoke(&instruction, ctx.remaining_accounts)?;
+
+    ctx.accounts.vault.reload()?;
+    ctx.accounts.depositor_swap_in_token_account.reload()?;
+    let input_amount =
+        ctx.accounts.vault.amount.checked_sub(vault_balance_before).or_overflow("received swap amount")?;
+    if input_amount == 0 || input_amount < min_input_amount {
+        return err!(SvmError::SwapSlippageExceeded);
+    }
+    let swap_in_amount = swap_in_balance_before.saturating_sub(ctx.accounts.depositor_swap_in_token_account.amount);
+
+    check_deposit_limit(&ctx.accounts.deposit_limit, vault_balance_before, input_amount)?;
+
+    let vault_liability = &mut ctx.accounts.vault_liability;
+    vault_liability.liabilities =
+        vault_liability.liabilities.checked_add(input_amount).or_overflow("vault liabilities")?;
+
+    let state = &mut ctx.accounts.state;
+    state.genesis = false;
+    let deposit_id = apply_deposit_id(state, ZERO_DEPOSIT_ID)?;
+
+    emit_cpi!(SwappedForDeposit {
+        deposit_id,
+        swap_program: ctx.accounts.swap_program.key(),
+        swap_in_token: ctx.accounts.swap_in_mint.key(),
+        swap_in_amount,
+        input_token,
+        input_amount,
+    });
+
+    let (event_message, message_hash) = get_event_message(message);
+    let funds_deposited = V3FundsDeposited {
+        input_token,
+        output_token,
+        input_amount,
+        output_amount,
+        destination_chain_id,
+        deposit_id,
+        quote_timestamp,
+        fill_deadline,
+        exclusivity_deadline,
+        depositor: ctx.accounts.signer.key(),
+        recipient,
+        exclusive_relayer,
+        message: event_message,
+        token_decimals: ctx.accounts.mint.decimals,
+        unsafe_deposit_id: false,
+        depositor_nonce: None,
+        message_hash,
+        exclusivity_parameter,
+        dust_refunded: None,
+    };
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, funds_deposited)?);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::SetDepositLimit,
+    state::{DepositLimit, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, destination_chain_id: u64)]
+pub struct SetDepositLimitState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Pays the rent for the deposit limit PDA if it is created. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Deposit limit PDA of the route, seeds ["deposit_limit", seed, mint, destination_chain_id]. Created if missing
+    /// and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + DepositLimit::INIT_SPACE,
+        seeds = [
+            b"deposit_limit",
+            state.seed.to_le_bytes().as_ref(),
+            mint.as_ref(),
+            destination_chain_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub deposit_limit: Account<'info, DepositLimit>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_deposit_limit(
+    ctx: Context<SetDepositLimitState>,
+    mint: Pubkey,
+    destination_chain_id: u64,
+    max_outstanding: u64,
+) -> Result<()> {
+    // Lowering the cap below the current vault balance keeps the deposited funds and only rejects new deposits.
+    ctx.accounts.deposit_limit.max_outstanding = max_outstanding;
+
+    emit_cpi!(SetDepositLimit { mint, destination_chain_id, max_outstanding });
+
+    Ok(())
+}
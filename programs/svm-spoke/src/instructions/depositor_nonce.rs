@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::ResetDepositorNonce,
+    state::{DepositorNonce, State},
+    utils::assert_lamport_destination,
+};
+
+#[derive(Accounts)]
+pub struct InitializeDepositorNonce<'info> {
+    /// Depositor opting into ordered deposits. Writable signer paying the depositor nonce PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Depositor nonce PDA created for the signer, seeds ["depositor_nonce", seed, signer]. Starts at nonce 0.
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + DepositorNonce::INIT_SPACE,
+        seeds = [b"depositor_nonce", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub depositor_nonce: Account<'info, DepositorNonce>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDepositorNonce<'info> {
+    /// Depositor opting out of ordered deposits. Writable signer receiving the depositor nonce PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Depositor nonce PDA being closed, seeds ["depositor_nonce", seed, signer]. Writable.
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"depositor_nonce", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub depositor_nonce: Account<'info, DepositorNonce>,
+}
+
+pub fn close_depositor_nonce(ctx: Context<CloseDepositorNonce>) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.signer)?;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(depositor: Pubkey)]
+pub struct ResetDepositorNonceState<'info> {
+    /// Owner recovering the deposit ordering of a depositor.
+    #[account(address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Depositor nonce PDA being reset, seeds ["depositor_nonce", seed, depositor]. Writable.
+    #[account(
+        mut,
+        seeds = [b"depositor_nonce", state.seed.to_le_bytes().as_ref(), depositor.as_ref()],
+        bump
+    )]
+    pub depositor_nonce: Account<'info, DepositorNonce>,
+}
+
+// Deposits emitted with a nonce are final, so integrations must treat the reset as the new start of the sequence.
+pub fn reset_depositor_nonce(ctx: Context<ResetDepositorNonceState>, depositor: Pubkey, next_nonce: u64) -> Result<()> {
+    ctx.accounts.depositor_nonce.next_nonce = next_nonce;
+
+    emit_cpi!(ResetDepositorNonce { depositor, next_nonce });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    state::{ErrorCounters, State},
+};
+
+#[derive(Accounts)]
+pub struct InitializeErrorCounters<'info> {
+    /// Anyone can initialize the error counters. Writable signer paying the PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Error counters PDA created for the state, seeds ["error_counters", seed].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + ErrorCounters::INIT_SPACE,
+        seeds = [b"error_counters", state.seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub error_counters: Account<'info, ErrorCounters>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetErrorCounters<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Error counters PDA of the state, seeds ["error_counters", seed]. Read-only.
+    #[account(seeds = [b"error_counters", state.seed.to_le_bytes().as_ref()], bump)]
+    pub error_counters: Account<'info, ErrorCounters>,
+}
+
+pub fn get_error_counters(ctx: Context<GetErrorCounters>) -> Result<ErrorCounters> {
+    let error_counters = &ctx.accounts.error_counters;
+
+    Ok(ErrorCounters {
+        deferred_refund_leaves: error_counters.deferred_refund_leaves,
+        deferred_refunds: error_counters.deferred_refunds,
+    })
+}
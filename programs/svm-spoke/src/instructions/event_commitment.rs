@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::SetEventCommitments,
+    state::{EventCommitment, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEventCommitmentsState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_event_commitments(ctx: Context<SetEventCommitmentsState>, enabled: bool) -> Result<()> {
+    ctx.accounts.state.event_commitments_enabled = enabled;
+
+    emit_cpi!(SetEventCommitments { enabled });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u32)]
+pub struct InitializeEventCommitment<'info> {
+    /// Anyone can initialize the event commitment of an epoch. Writable signer paying the PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Event commitment PDA created for the epoch, seeds ["event_commitment", seed, epoch].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + EventCommitment::INIT_SPACE,
+        seeds = [b"event_commitment", state.seed.to_le_bytes().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub event_commitment: Account<'info, EventCommitment>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_event_commitment(ctx: Context<InitializeEventCommitment>, epoch: u32) -> Result<()> {
+    let event_commitment = &mut ctx.accounts.event_commitment;
+    event_commitment.seed = ctx.accounts.state.seed;
+    event_commitment.epoch = epoch;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u32)]
+pub struct GetEventCommitment<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Event commitment PDA of the epoch, seeds ["event_commitment", seed, epoch]. Read-only.
+    #[account(
+        seeds = [b"event_commitment", state.seed.to_le_bytes().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub event_commitment: Account<'info, EventCommitment>,
+}
+
+pub fn get_event_commitment(ctx: Context<GetEventCommitment>) -> Result<EventCommitment> {
+    let event_commitment = &ctx.accounts.event_commitment;
+
+    Ok(EventCommitment {
+        seed: event_commitment.seed,
+        epoch: event_commitment.epoch,
+        hash: event_commitment.hash,
+        event_count: event_commitment.event_count,
+    })
+}
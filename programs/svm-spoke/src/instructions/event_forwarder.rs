@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{constraints::is_local_or_remote_owner, error::SvmError, event::SetEventForwarder, state::State};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEventForwarderState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_event_forwarder(
+    ctx: Context<SetEventForwarderState>,
+    event_forwarder: Option<Pubkey>,
+    best_effort: bool,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.event_forwarder = event_forwarder;
+    state.event_forwarder_best_effort = best_effort;
+
+    emit_cpi!(SetEventForwarder { event_forwarder, best_effort });
+
+    Ok(())
+}
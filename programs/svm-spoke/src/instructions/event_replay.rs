@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::FilledV3RelayReplay,
+    state::{EventReplayActivity, FillStatus, FillStatusAccount, State},
+    utils::get_current_time,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32])]
+pub struct ReEmitFillEvent<'info> {
+    /// Owner replaying the event. Writable signer paying the replay activity PDA rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill status PDA of the relay, seeds ["fills", relay_hash]. Read-only.
+    #[account(seeds = [b"fills", relay_hash.as_ref()], bump)]
+    pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// Replay rate limit PDA of the state, seeds ["event_replay_activity", seed]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + EventReplayActivity::INIT_SPACE,
+        seeds = [b"event_replay_activity", state.seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub event_replay_activity: Account<'info, EventReplayActivity>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// Only the rate limit activity is updated, the fill status PDA is left untouched.
+pub fn re_emit_fill_event(ctx: Context<ReEmitFillEvent>, relay_hash: [u8; 32]) -> Result<()> {
+    let fill_status = &ctx.accounts.fill_status;
+    if fill_status.status != FillStatus::Filled {
+        return err!(SvmError::FillNotTerminal);
+    }
+
+    let current_time = get_current_time(&ctx.accounts.state)?;
+    ctx.accounts.event_replay_activity.record_replay(current_time)?;
+
+    emit_cpi!(FilledV3RelayReplay {
+        relay_hash,
+        relayer: fill_status.relayer,
+        fill_deadline: fill_status.fill_deadline,
+        requester: fill_status.requester,
+        replay: true,
+    });
+
+    Ok(())
+}
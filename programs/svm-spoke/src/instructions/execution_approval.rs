@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_EXECUTION_APPROVAL_THRESHOLDS},
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{CreatedExecutionApproval, RevokedExecutionApproval, SetExecutionApprovalThreshold, SetExecutionApprover},
+    state::{ExecutionApproval, ExecutionApprovalThreshold, State},
+    utils::get_current_time,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetExecutionApprovalConfigState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_execution_approver(
+    ctx: Context<SetExecutionApprovalConfigState>,
+    execution_approver: Option<Pubkey>,
+    execution_approval_validity: u32,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.execution_approver = execution_approver;
+    state.execution_approval_validity = execution_approval_validity;
+
+    emit_cpi!(SetExecutionApprover { execution_approver, execution_approval_validity });
+
+    Ok(())
+}
+
+pub fn set_execution_approval_threshold(
+    ctx: Context<SetExecutionApprovalConfigState>,
+    mint: Pubkey,
+    threshold: Option<u64>,
+) -> Result<()> {
+    let thresholds = &mut ctx.accounts.state.execution_approval_thresholds;
+    let index = thresholds.iter().position(|entry| entry.mint == mint);
+    match (index, threshold) {
+        (Some(index), Some(threshold)) => thresholds[index].threshold = threshold,
+        (Some(index), None) => {
+            thresholds.swap_remove(index);
+        }
+        (None, Some(threshold)) => {
+            if thresholds.len() >= MAX_EXECUTION_APPROVAL_THRESHOLDS {
+                return err!(SvmError::TooManyExecutionApprovalThresholds);
+            }
+            thresholds.push(ExecutionApprovalThreshold { mint, threshold });
+        }
+        (None, None) => {}
+    }
+
+    emit_cpi!(SetExecutionApprovalThreshold { mint, threshold });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf_hash: [u8; 32])]
+pub struct CreateExecutionApproval<'info> {
+    /// Execution approver of the state. Writable signer paying the rent for the execution approval PDA.
+    #[account(mut, constraint = state.execution_approver == Some(signer.key()) @ SvmError::NotExecutionApprover)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Execution approval PDA created for the slow fill or relayer refund leaf, seeds ["execution_approval", seed,
+    /// leaf_hash].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + ExecutionApproval::INIT_SPACE,
+        seeds = [b"execution_approval", state.seed.to_le_bytes().as_ref(), leaf_hash.as_ref()],
+        bump
+    )]
+    pub execution_approval: Account<'info, ExecutionApproval>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_execution_approval(ctx: Context<CreateExecutionApproval>, leaf_hash: [u8; 32]) -> Result<()> {
+    let approver = ctx.accounts.signer.key();
+    let approved_at = get_current_time(&ctx.accounts.state)?;
+
+    let execution_approval = &mut ctx.accounts.execution_approval;
+    execution_approval.leaf_hash = leaf_hash;
+    execution_approval.approver = approver;
+    execution_approval.approved_at = approved_at;
+
+    emit_cpi!(CreatedExecutionApproval { leaf_hash, approver, approved_at });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf_hash: [u8; 32])]
+pub struct RevokeExecutionApproval<'info> {
+    /// Approver that created the execution approval. Writable signer receiving the reclaimed rent.
+    #[account(mut, address = execution_approval.approver @ SvmError::NotExecutionApprover)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Execution approval PDA of the slow fill leaf, seeds ["execution_approval", seed, leaf_hash]. Closed to the
+    /// signer.
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"execution_approval", state.seed.to_le_bytes().as_ref(), leaf_hash.as_ref()],
+        bump
+    )]
+    pub execution_approval: Account<'info, ExecutionApproval>,
+}
+
+pub fn revoke_execution_approval(ctx: Context<RevokeExecutionApproval>, leaf_hash: [u8; 32]) -> Result<()> {
+    emit_cpi!(RevokedExecutionApproval { leaf_hash, approver: ctx.accounts.signer.key() });
+
+    Ok(())
+}
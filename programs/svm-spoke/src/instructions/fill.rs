@@ -1,69 +1,168 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar,
+    system_program::{transfer, Transfer},
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{self, AssociatedToken},
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
     common::V3RelayData,
-    constants::DISCRIMINATOR_SIZE,
-    constraints::is_relay_hash_valid,
+    constants::{
+        ALLOWLIST_ROLE_RELAYER, DISCRIMINATOR_SIZE, FILL_STATUSES_PACKED_ENTRY_SIZE,
+        FILL_STATUSES_PACKED_FILL_DEADLINE_OFFSET, FILL_STATUSES_PACKED_INVALID_STATUS,
+        FILL_STATUSES_PACKED_MAX_ACCOUNTS, FILL_STATUSES_PACKED_RELAYER_OFFSET, FILL_STATUSES_PACKED_STATUS_OFFSET,
+        LEGACY_FILL_STATUS_ACCOUNT_SIZES, MEMO_PROGRAM_ID, REQUESTED_SLOW_FILL_CLOSE_DELAY,
+    },
+    constraints::{is_empty_relay, is_message_too_large, is_relay_hash_valid, validate_relay_data},
     error::{CommonError, SvmError},
-    event::{FillType, FilledV3Relay, V3RelayExecutionEventInfo},
-    state::{FillStatus, FillStatusAccount, FillV3RelayParams, State},
-    utils::{get_current_time, hash_non_empty_message, invoke_handler, transfer_from},
+    event::{FillStatusExpired, FillType, FilledV3Relay, PauseExpired, V3RelayExecutionEventInfo},
+    relay_admission::{self, Admission, CallerRole},
+    state::{
+        AllowlistEntry, ChainAlias, DeliveryPreference, EventCommitment, FillIndexShard, FillStatus, FillStatusAccount,
+        FillV3RelayParams, RelayerStats, Route, State, UsedSignature,
+    },
+    utils::{
+        assert_closes_not_frozen, assert_lamport_destination, check_allowlist_role, check_compliance,
+        check_origin_chain_id, commit_event, forward_event, get_current_time, get_fill_index_bucket,
+        get_payout_destination, get_updated_deposit_hash, get_used_signature_key, hash_non_empty_message,
+        hash_payout_memo, invoke_handler, invoke_payout_memo, is_canonical_token_program, is_escrow_delivery,
+        is_native_delivery, is_registered_token_program, is_relayer_fee_below_floor, normalize_output_amount,
+        parse_extra_args, record_fill_index, record_fill_volume, record_relayer_outcome, release_requested_slow_fill,
+        transfer_from, unwrap_native_payout, validate_message_schema, validate_payout_memo, validate_repayment,
+        verify_and_consume_signature, ExtraArgs, Null,
+    },
 };
 
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(relay_hash: [u8; 32], relay_data: Option<V3RelayData>)]
 pub struct FillV3Relay<'info> {
+    /// Relayer filling the relay, recorded as the relayer of the fill status PDA. Writable signer.
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    // This is required as fallback when None instruction params are passed in arguments.
+    /// This is required as fallback when None instruction params are passed in arguments. Seeds ["instruction_params",
+    /// signer], closed to the signer after the fill. Pass this program ID to represent None.
     #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump, close = signer)]
     pub instruction_params: Option<Account<'info, FillV3RelayParams>>,
 
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Fills must not be paused.
     #[account(
-        seeds = [b"state", state.seed.to_le_bytes().as_ref()],
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
         bump,
-        constraint = !state.paused_fills @ CommonError::FillsArePaused
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
     )]
-    pub state: Account<'info, State>,
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: Vault PDA of the mint, seeds ["vault", seed, mint]. Read-only. Fills of mints whose vault was registered
+    /// by create_vault must pass its token program, others fail with InvalidVault.
+    #[account(
+        seeds = [b"vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+        constraint = is_registered_token_program(&vault_registry, &token_program.key()) @ SvmError::InvalidVault
+    )]
+    pub vault_registry: UncheckedAccount<'info>,
 
+    /// Mint of the output token of the relay.
     #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
         mint::token_program = token_program,
         address = relay_data
             .clone()
             .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
             .output_token @ SvmError::InvalidMint
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// Writable token account of the relayer for the mint, source of the filled tokens.
     #[account(
         mut,
         token::mint = mint,
         token::authority = signer,
         token::token_program = token_program
     )]
-    pub relayer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub relayer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Not required when the recipient opted into escrow delivery or for message-only relays with a zero output amount.
+    /// Writable token account of the relay recipient for the mint, usually their ATA. Any other token account owned by
+    /// the recipient is accepted, e.g. for PDA recipients of programs using custom token accounts. Pass this program ID
+    /// to represent None.
     #[account(
         mut,
-        associated_token::mint = mint,
-        // Ensures tokens go to ATA owned by the recipient.
-        associated_token::authority = relay_data
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        token::mint = mint,
+        // Ensures tokens go to an account owned by the recipient. Delegates of the account do not own it.
+        token::authority = relay_data
             .clone()
             .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
             .recipient,
-        associated_token::token_program = token_program
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Optional delivery preference set by the recipient. Pass this program ID to represent None. Seeds
+    /// ["delivery_preference", seed, recipient].
+    #[account(
+        seeds = [
+            b"delivery_preference",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .recipient
+                .as_ref()
+        ],
+        bump
+    )]
+    pub delivery_preference: Option<Account<'info, DeliveryPreference>>,
+
+    /// Only required when the recipient opted into escrow delivery. Pass this program ID to represent None. Writable
+    /// escrow token account of the recipient, seeds ["escrow", seed, recipient, mint].
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [
+            b"escrow",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .recipient
+                .as_ref(),
+            mint.key().as_ref()
+        ],
+        bump
     )]
-    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub recipient_escrow: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Only required for wSOL outputs when the recipient opted into native delivery. Pass this program ID to represent
+    /// None. Temporary wSOL token account of the state, seeds ["native_unwrap", seed, signer], created and closed
+    /// within the fill.
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"native_unwrap", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Relay recipient receiving the unwrapped lamports, only required together with the native unwrap account.
+    /// Pass this program ID to represent None. Checked against the relay recipient in the handler.
+    #[account(mut)]
+    pub native_recipient: Option<UncheckedAccount<'info>>,
 
+    /// Fill status PDA of the relay, seeds ["fills", relay_hash]. Created on first fill and writable.
     #[account(
         init_if_needed,
-        payer = signer,
+        payer = payer,
         space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
         seeds = [b"fills", relay_hash.as_ref()],
         bump,
@@ -74,17 +173,220 @@ pub struct FillV3Relay<'info> {
     )]
     pub fill_status: Account<'info, FillStatusAccount>,
 
+    /// CHECK: Writable slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint]. Only required
+    /// when replacing a requested slow fill, releasing its reserved output amount. Pass this program ID to represent
+    /// None.
+    #[account(mut, seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Writable fill volume PDA of the mint, seeds ["fill_volume", seed, mint], credited with the filled output
+    /// amount. Ignored for mints that the owner did not opt into refund checks.
+    #[account(mut, seeds = [b"fill_volume", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub fill_volume: UncheckedAccount<'info>,
+
+    /// CHECK: Schema PDA of the recipient, seeds ["message_schema", recipient]. Non-empty messages are validated against
+    /// it whenever the recipient registered a schema.
+    #[account(
+        seeds = [
+            b"message_schema",
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .recipient
+                .as_ref()
+        ],
+        bump
+    )]
+    pub message_schema: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry of the relay depositor, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    #[account(
+        seeds = [
+            b"compliance",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .depositor
+                .as_ref()
+        ],
+        bump
+    )]
+    pub depositor_compliance: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry of the relay recipient, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    #[account(
+        seeds = [
+            b"compliance",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .recipient
+                .as_ref()
+        ],
+        bump
+    )]
+    pub recipient_compliance: Option<UncheckedAccount<'info>>,
+
+    /// Allowlist entry of the signer, seeds ["allowlist", seed, signer]. Only required in permissioned mode for
+    /// signers holding the relayer role. Pass this program ID to represent None.
+    #[account(seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()], bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// Route of the output token towards the origin chain whose decimal shift scales the output amount. Must be passed
+    /// for corridors with a non-zero decimal shift, None defaults to no shift and skips the relayer fee floor of the
+    /// route. Its expected decimals must match the mint. Seeds ["route", mint, seed, origin_chain_id]. Pass this program
+    /// ID to represent None.
+    #[account(
+        seeds = [
+            b"route",
+            mint.key().as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .origin_chain_id
+                .to_le_bytes()
+                .as_ref(),
+        ],
+        bump = route.bump,
+        constraint = route.expected_decimals == mint.decimals @ SvmError::UnexpectedMintDecimals
+    )]
+    pub route: Option<Account<'info, Route>>,
+
+    /// CHECK: Repayment address of the relayer, only required when the extra args request creating the repayment
+    /// token account. Pass this program ID to represent None. Checked against the repayment address in the handler.
+    pub repayment_address_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Writable ATA of the repayment address for the mint, only required when the extra args request creating
+    /// the repayment token account. Pass this program ID to represent None. Checked within the ATA creation CPI.
+    #[account(mut)]
+    pub repayment_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// Fill index shard of the deposit id, seeds ["fill_index", seed, origin_chain_id, deposit_id / 1024]. Only
+    /// required when the state enables the fill index and the deposit id fits into u64. Pass this program ID to
+    /// represent None. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + FillIndexShard::INIT_SPACE,
+        seeds = [
+            b"fill_index",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .origin_chain_id
+                .to_le_bytes()
+                .as_ref(),
+            get_fill_index_bucket(
+                &relay_data
+                    .clone()
+                    .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                    .deposit_id
+            )
+            .to_le_bytes()
+            .as_ref()
+        ],
+        bump
+    )]
+    pub fill_index_shard: Option<Account<'info, FillIndexShard>>,
+
+    /// Chain alias of the relay origin chain ID, seeds ["chain_alias", seed, origin_chain_id]. Required in strict chain
+    /// alias mode and rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent
+    /// None.
+    #[account(
+        seeds = [
+            b"chain_alias",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .origin_chain_id
+                .to_le_bytes()
+                .as_ref(),
+        ],
+        bump
+    )]
+    pub origin_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// Optional stats of the exclusive relayer, only passed when the relay names one. Pass this program ID to
+    /// represent None. Seeds ["relayer_stats", seed, exclusive_relayer]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + RelayerStats::INIT_SPACE,
+        seeds = [
+            b"relayer_stats",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .exclusive_relayer
+                .as_ref()
+        ],
+        bump
+    )]
+    pub relayer_stats: Option<Account<'info, RelayerStats>>,
+
+    /// CHECK: SPL Memo program, only required when a payout memo is passed in the extra args. Pass this program ID to
+    /// represent None.
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Event forwarder program, only required when the state has an event forwarder. Pass this program ID to
+    /// represent None. Checked against the state when forwarding the fill event.
+    pub event_forwarder: Option<UncheckedAccount<'info>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
+    /// Associated token program, used to create associated token accounts.
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
+
+    /// Pays the rent for the fill status PDA and the other accounts created by the fill, which it reclaims when they
+    /// are closed. Writable signer, passed as the same account as the signer unless a sponsor funds the relayer.
+    /// Breaks clients built against the previous account list, which must pass it as well.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Relay recipient, only required when the extra args request creating the recipient token account. Pass
+    /// this program ID to represent None. Checked against the relay recipient in the handler.
+    pub recipient_address_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Writable ATA of the relay recipient for the mint, only required when the extra args request creating the
+    /// recipient token account, in which case it receives the payout. Pass this program ID to represent None. Checked
+    /// within the ATA creation CPI.
+    #[account(mut)]
+    pub recipient_ata: Option<UncheckedAccount<'info>>,
 }
 
 pub fn fill_v3_relay<'info>(
     ctx: Context<'_, '_, '_, 'info, FillV3Relay<'info>>,
+    relay_hash: [u8; 32],
     relay_data: Option<V3RelayData>,
     repayment_chain_id: Option<u64>,
     repayment_address: Option<Pubkey>,
+    args_version: u8,
+    extra: Vec<u8>,
 ) -> Result<()> {
+    let extra_args = parse_extra_args(args_version, &extra)?;
+    // Version 5 only carries the depositor nonce, which is only meaningful for deposits.
+    if matches!(extra_args, ExtraArgs::V5(_)) {
+        return err!(SvmError::UnsupportedArgsVersion);
+    }
+    let memo = extra_args.payout_memo();
+    validate_payout_memo(memo)?;
+
     let FillV3RelayParams { relay_data, repayment_chain_id, repayment_address } = unwrap_fill_v3_relay_params(
         relay_data,
         repayment_chain_id,
@@ -95,57 +397,161 @@ pub fn fill_v3_relay<'info>(
     let state = &ctx.accounts.state;
     let current_time = get_current_time(state)?;
 
-    // Check if the exclusivity deadline has passed or if the caller is the exclusive relayer
-    if relay_data.exclusive_relayer != ctx.accounts.signer.key()
-        && relay_data.exclusivity_deadline >= current_time
-        && relay_data.exclusive_relayer != Pubkey::default()
-    {
-        return err!(CommonError::NotExclusiveRelayer);
-    }
+    validate_relay_data(&relay_data, state, current_time)?;
+    check_origin_chain_id(state, &ctx.accounts.origin_chain_alias, relay_data.origin_chain_id)?;
+    validate_repayment(state, repayment_chain_id, &repayment_address)?;
+
+    let caller_role = CallerRole::Relayer(ctx.accounts.signer.key());
+    let admission =
+        relay_admission::check(&relay_data, &ctx.accounts.fill_status.status, state, current_time, caller_role)?;
+    let fill_type = get_fill_type(admission);
 
-    // Check if the fill deadline has passed
-    if relay_data.fill_deadline < current_time {
-        return err!(CommonError::ExpiredFillDeadline);
+    // Being admitted with the flag still set means that the pause has expired.
+    if state.paused_fills {
+        emit_cpi!(PauseExpired { expires_at: state.fills_pause_expires_at });
     }
 
-    // Check the fill status and set the fill type
-    let fill_status_account = &mut ctx.accounts.fill_status;
-    let fill_type = match fill_status_account.status {
-        FillStatus::Filled => {
-            return err!(CommonError::RelayFilled);
+    validate_message_schema(&relay_data.message, &ctx.accounts.message_schema)?;
+
+    check_compliance(state, &[&ctx.accounts.depositor_compliance, &ctx.accounts.recipient_compliance])?;
+
+    check_allowlist_role(state, &ctx.accounts.allowlist_entry, ALLOWLIST_ROLE_RELAYER)?;
+
+    // Replaced slow fills can't be executed anymore, so their output amount is no longer reserved in the vault.
+    if fill_type == FillType::ReplacedSlowFill {
+        if let Some(liability_changed) = release_requested_slow_fill(&ctx.accounts.slow_fill_liability, &relay_data)? {
+            emit_cpi!(liability_changed);
         }
-        FillStatus::RequestedSlowFill => FillType::ReplacedSlowFill,
-        _ => FillType::FastFill,
-    };
+    }
 
-    // If relayer and receiver are the same, there is no need to do the transfer. This might be a case when relayers
-    // intentionally self-relay in a capital efficient way (no need to have funds on the destination).
-    if ctx.accounts.relayer_token_account.key() != ctx.accounts.recipient_token_account.key() {
-        // Relayer must have delegated output_amount to the state PDA (but only if not self-relaying)
-        transfer_from(
-            &ctx.accounts.relayer_token_account,
-            &ctx.accounts.recipient_token_account,
-            relay_data.output_amount,
-            state,
-            ctx.bumps.state,
-            &ctx.accounts.mint,
-            &ctx.accounts.token_program,
-        )?;
+    let decimal_shift = ctx.accounts.route.as_ref().map_or(0, |route| route.decimal_shift);
+    let normalized_output_amount = normalize_output_amount(relay_data.output_amount, decimal_shift)?;
+
+    // The fee floor is only known when the route of the corridor is passed, otherwise the check is skipped.
+    if let Some(route) = &ctx.accounts.route {
+        if route.min_relayer_fee_bps > 0
+            && !extra_args.accept_low_fee()
+            && is_relayer_fee_below_floor(
+                relay_data.input_amount,
+                normalized_output_amount,
+                decimal_shift,
+                route.min_relayer_fee_bps,
+            )
+        {
+            return err!(SvmError::RelayerFeeBelowFloor);
+        }
     }
 
-    // Update the fill status to Filled, set the relayer and fill deadline
+    // Message-only relays have nothing to pay out, so they don't need any payout destination account.
+    let message_only = relay_data.output_amount == 0;
+    let escrow = match message_only {
+        true => None,
+        false => {
+            // Fills to fresh wallets can create the recipient ATA in place of passing an existing token account. The
+            // escrow and native deliveries opted into by the recipient don't pay out to it, so they never create it.
+            let delivery_preference = &ctx.accounts.delivery_preference;
+            let create_recipient_ata = extra_args.create_recipient_token_account()
+                && !is_escrow_delivery(delivery_preference)
+                && !is_native_delivery(delivery_preference, &relay_data.output_token);
+            let (payout_destination, escrow) = match create_recipient_ata {
+                true => (create_recipient_token_account(&ctx, &relay_data.recipient)?, None),
+                false => {
+                    let (payout_destination, escrow) = get_payout_destination(
+                        delivery_preference,
+                        &relay_data.output_token,
+                        &ctx.accounts.recipient_token_account,
+                        &ctx.accounts.recipient_escrow,
+                        &ctx.accounts.native_unwrap_account,
+                    )?;
+                    (payout_destination.to_account_info(), escrow)
+                }
+            };
+
+            // If relayer and receiver are the same, there is no need to do the transfer. This might be a case when
+            // relayers intentionally self-relay in a capital efficient way (no need to have funds on the destination).
+            if ctx.accounts.relayer_token_account.key() != payout_destination.key() {
+                invoke_payout_memo(memo, &ctx.accounts.memo_program, &payout_destination)?;
+
+                // Relayer must have delegated the normalized output_amount to the state PDA (but only if not
+                // self-relaying)
+                transfer_from(
+                    &*ctx.accounts.relayer_token_account,
+                    &payout_destination,
+                    normalized_output_amount,
+                    state,
+                    ctx.bumps.state,
+                    &ctx.accounts.mint,
+                    &ctx.accounts.token_program,
+                )?;
+            }
+
+            if is_native_delivery(delivery_preference, &relay_data.output_token) {
+                let native_unwrap_account =
+                    ctx.accounts.native_unwrap_account.as_deref().ok_or(SvmError::MissingNativeUnwrapAccount)?;
+                unwrap_native_payout(
+                    native_unwrap_account,
+                    &ctx.accounts.native_recipient,
+                    &relay_data.recipient,
+                    &ctx.accounts.payer,
+                    normalized_output_amount,
+                    state,
+                    ctx.bumps.state,
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.system_program,
+                )?;
+            }
+
+            escrow
+        }
+    };
+
+    // Update the fill status to Filled, set the relayer and fill deadline. The relayer is always overwritten, as a
+    // preceding slow fill request records its requester there and the filler must be the one closing the fill status.
+    let fill_status_account = &mut ctx.accounts.fill_status;
     fill_status_account.status = FillStatus::Filled;
     fill_status_account.relayer = *ctx.accounts.signer.key;
     fill_status_account.fill_deadline = relay_data.fill_deadline;
+    fill_status_account.record_payer(ctx.accounts.payer.key());
+
+    record_fill_index(state, &mut ctx.accounts.fill_index_shard, &relay_data)?;
+    record_fill_volume(&ctx.accounts.fill_volume, normalized_output_amount)?;
+
+    // Only fills by the exclusive relayer within their window count towards their stats, later fills count as neither.
+    if relay_data.exclusive_relayer == ctx.accounts.signer.key() && current_time <= relay_data.exclusivity_deadline {
+        let exclusive_relayer = &relay_data.exclusive_relayer;
+        if let Some(crossed) = record_relayer_outcome(state, &mut ctx.accounts.relayer_stats, exclusive_relayer, false)
+        {
+            emit_cpi!(crossed);
+        }
+    }
+
+    // Relayers taking repayment on this chain can create their refund ATA upfront so that the refund leaf is never
+    // deferred because of a missing token account.
+    let repayment_token_account_created = match extra_args.create_repayment_token_account() {
+        true => {
+            if repayment_chain_id != state.chain_id {
+                return err!(CommonError::InvalidChainId);
+            }
+            create_repayment_token_account(&ctx, &repayment_address)?
+        }
+        false => false,
+    };
 
     if !relay_data.message.is_empty() {
-        invoke_handler(ctx.accounts.signer.as_ref(), ctx.remaining_accounts, &relay_data.message)?;
+        invoke_handler(
+            ctx.accounts.signer.as_ref(),
+            &ctx.accounts.state.key(),
+            ctx.remaining_accounts,
+            &relay_data.message,
+            &relay_data.output_token,
+            normalized_output_amount,
+        )?;
     }
 
     // Empty message is not hashed and emits zeroed bytes32 for easier human observability.
     let message_hash = hash_non_empty_message(&relay_data.message);
 
-    emit_cpi!(FilledV3Relay {
+    let filled_relay = FilledV3Relay {
         input_token: relay_data.input_token,
         output_token: relay_data.output_token,
         input_amount: relay_data.input_amount,
@@ -165,12 +571,86 @@ pub fn fill_v3_relay<'info>(
             updated_message_hash: message_hash,
             updated_output_amount: relay_data.output_amount,
             fill_type,
+            root_bundle_id: None,
+            escrow,
+            memo_hash: hash_payout_memo(memo),
+            normalized_output_amount,
+            repayment_token_account_created,
+            emergency_execution: false,
+            origin_tx_ref: extra_args.origin_tx_ref(),
+            message_only,
         },
-    });
+        token_decimals: ctx.accounts.mint.decimals,
+        relay_hash,
+    };
+    let filled_relay = forward_event(state, &ctx.accounts.event_forwarder, filled_relay)?;
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, filled_relay)?);
 
     Ok(())
 }
 
+// Idempotently creates the repayment address ATA for the mint, returning whether it did not exist before.
+fn create_repayment_token_account(ctx: &Context<FillV3Relay>, repayment_address: &Pubkey) -> Result<bool> {
+    let repayment_address_account =
+        ctx.accounts.repayment_address_account.as_ref().ok_or(SvmError::MissingRepaymentTokenAccount)?;
+    let repayment_token_account =
+        ctx.accounts.repayment_token_account.as_ref().ok_or(SvmError::MissingRepaymentTokenAccount)?;
+    if repayment_address_account.key() != *repayment_address {
+        return err!(SvmError::InvalidRepaymentTokenAccount);
+    }
+
+    let created = repayment_token_account.data_is_empty();
+
+    let cpi_program = ctx.accounts.associated_token_program.to_account_info();
+    let cpi_accounts = associated_token::Create {
+        payer: ctx.accounts.payer.to_account_info(),
+        associated_token: repayment_token_account.to_account_info(),
+        authority: repayment_address_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    associated_token::create_idempotent(cpi_ctx)?;
+
+    Ok(created)
+}
+
+// Returns the recipient ATA as the payout destination, which the ATA program has checked to be owned by the recipient.
+fn create_recipient_token_account<'info>(
+    ctx: &Context<'_, '_, '_, 'info, FillV3Relay<'info>>,
+    recipient: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    let recipient_address_account =
+        ctx.accounts.recipient_address_account.as_ref().ok_or(SvmError::MissingRecipientAta)?;
+    let recipient_ata = ctx.accounts.recipient_ata.as_ref().ok_or(SvmError::MissingRecipientAta)?;
+    if recipient_address_account.key() != *recipient {
+        return err!(SvmError::InvalidRecipientAta);
+    }
+
+    let cpi_program = ctx.accounts.associated_token_program.to_account_info();
+    let cpi_accounts = associated_token::Create {
+        payer: ctx.accounts.payer.to_account_info(),
+        associated_token: recipient_ata.to_account_info(),
+        authority: recipient_address_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    associated_token::create_idempotent(cpi_ctx)?;
+
+    Ok(recipient_ata.to_account_info())
+}
+
+// Relayers are only admitted to fast fills, replacing the slow fill request of the relay if there is one.
+fn get_fill_type(admission: Admission) -> FillType {
+    match admission {
+        Admission::ReplacedSlowFill => FillType::ReplacedSlowFill,
+        _ => FillType::FastFill,
+    }
+}
+
 // Helper to unwrap optional instruction params with fallback loading from buffer account.
 fn unwrap_fill_v3_relay_params(
     relay_data: Option<V3RelayData>,
@@ -193,27 +673,665 @@ fn unwrap_fill_v3_relay_params(
     }
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+    relay_hash: [u8; 32],
+    relay_data: V3RelayData,
+    repayment_chain_id: u64,
+    repayment_address: Pubkey,
+    updated_output_amount: u64,
+    updated_recipient: Pubkey,
+    updated_message: Vec<u8>,
+    depositor_signature: Vec<u8>,
+)]
+pub struct FillV3RelayWithUpdatedDeposit<'info> {
+    /// Relayer filling the relay, recorded as the relayer of the fill status PDA. Writable signer.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Fills must not be paused.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: Vault PDA of the mint, seeds ["vault", seed, mint]. Read-only. Fills of mints whose vault was registered
+    /// by create_vault must pass its token program, others fail with InvalidVault.
+    #[account(
+        seeds = [b"vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+        constraint = is_registered_token_program(&vault_registry, &token_program.key()) @ SvmError::InvalidVault
+    )]
+    pub vault_registry: UncheckedAccount<'info>,
+
+    /// Mint of the output token of the relay.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program,
+        address = relay_data.output_token @ SvmError::InvalidMint
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Writable token account of the relayer for the mint, source of the filled tokens.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program
+    )]
+    pub relayer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Writable ATA of the updated recipient for the mint. Not required when the updated output amount is zero, so that
+    /// only the updated message runs. Pass this program ID to represent None.
+    #[account(
+        mut,
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        associated_token::mint = mint,
+        associated_token::authority = updated_recipient, // Ensures tokens go to ATA owned by the updated recipient.
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Fill status PDA of the original relay, seeds ["fills", relay_hash]. Created on first fill and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
+        seeds = [b"fills", relay_hash.as_ref()],
+        bump,
+        constraint = is_relay_hash_valid(&relay_hash, &relay_data, &state) @ SvmError::InvalidRelayHash
+    )]
+    pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// CHECK: Writable slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint]. Only required
+    /// when replacing a requested slow fill, releasing its reserved output amount. Pass this program ID to represent
+    /// None.
+    #[account(mut, seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Writable fill volume PDA of the mint, seeds ["fill_volume", seed, mint], credited with the filled output
+    /// amount. Ignored for mints that the owner did not opt into refund checks.
+    #[account(mut, seeds = [b"fill_volume", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub fill_volume: UncheckedAccount<'info>,
+
+    /// Used signature PDA of the depositor signature rejecting replays of the signed update, seeds ["used_signature",
+    /// seed, keccak(depositor_signature)[..16]]. Created on first use and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + UsedSignature::INIT_SPACE,
+        seeds = [
+            b"used_signature",
+            state.seed.to_le_bytes().as_ref(),
+            get_used_signature_key(&depositor_signature).as_ref()
+        ],
+        bump
+    )]
+    pub used_signature: Account<'info, UsedSignature>,
+
+    /// CHECK: Schema PDA of the updated recipient, seeds ["message_schema", updated_recipient]. Non-empty updated
+    /// messages are validated against it whenever the updated recipient registered a schema.
+    #[account(seeds = [b"message_schema", updated_recipient.as_ref()], bump)]
+    pub message_schema: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry of the relay depositor, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    #[account(seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), relay_data.depositor.as_ref()], bump)]
+    pub depositor_compliance: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry of the updated recipient, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,updated_recipient].
+    #[account(seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), updated_recipient.as_ref()], bump)]
+    pub recipient_compliance: Option<UncheckedAccount<'info>>,
+
+    /// Allowlist entry of the signer, seeds ["allowlist", seed, signer]. Only required in permissioned mode for
+    /// signers holding the relayer role. Pass this program ID to represent None.
+    #[account(seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()], bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// Route of the output token towards the origin chain whose decimal shift scales the updated output amount. Must
+    /// be passed for corridors with a non-zero decimal shift, None defaults to no shift. Its expected decimals must
+    /// match the mint. Seeds ["route", mint, seed, origin_chain_id]. Pass this program ID to represent None.
+    #[account(
+        seeds = [
+            b"route",
+            mint.key().as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            relay_data.origin_chain_id.to_le_bytes().as_ref(),
+        ],
+        bump = route.bump,
+        constraint = route.expected_decimals == mint.decimals @ SvmError::UnexpectedMintDecimals
+    )]
+    pub route: Option<Account<'info, Route>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// Fill index shard of the deposit id, seeds ["fill_index", seed, origin_chain_id, deposit_id / 1024]. Only
+    /// required when the state enables the fill index and the deposit id fits into u64. Pass this program ID to
+    /// represent None. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + FillIndexShard::INIT_SPACE,
+        seeds = [
+            b"fill_index",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data.origin_chain_id.to_le_bytes().as_ref(),
+            get_fill_index_bucket(&relay_data.deposit_id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub fill_index_shard: Option<Account<'info, FillIndexShard>>,
+
+    /// Chain alias of the relay origin chain ID, seeds ["chain_alias", seed, origin_chain_id]. Required in strict chain
+    /// alias mode and rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent
+    /// None.
+    #[account(
+        seeds = [
+            b"chain_alias",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data.origin_chain_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub origin_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// CHECK: Instructions sysvar, used to load the Ed25519 signature verification instruction of Solana depositors.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Associated token program, used to create associated token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// Pays the rent for the fill status PDA and the other accounts created by the fill, which it reclaims when they
+    /// are closed. Writable signer, passed as the same account as the signer unless a sponsor funds the relayer. A
+    /// breaking addition to the account list, same as in FillV3Relay.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Blocklist entry of the original relay recipient, only required when the state has a compliance
+    /// authority, so that depositors can't route fills of a blocked recipient to another one. Pass this program ID to
+    /// represent None. Seed: ["compliance",state.seed,recipient].
+    #[account(seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), relay_data.recipient.as_ref()], bump)]
+    pub original_recipient_compliance: Option<UncheckedAccount<'info>>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fill_v3_relay_with_updated_deposit<'info>(
+    ctx: Context<'_, '_, '_, 'info, FillV3RelayWithUpdatedDeposit<'info>>,
+    relay_hash: [u8; 32],
+    relay_data: V3RelayData,
+    repayment_chain_id: u64,
+    repayment_address: Pubkey,
+    updated_output_amount: u64,
+    updated_recipient: Pubkey,
+    updated_message: Vec<u8>,
+    depositor_signature: Vec<u8>,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+
+    validate_relay_data(&relay_data, state, current_time)?;
+    check_origin_chain_id(state, &ctx.accounts.origin_chain_alias, relay_data.origin_chain_id)?;
+    validate_repayment(state, repayment_chain_id, &repayment_address)?;
+
+    let caller_role = CallerRole::Relayer(ctx.accounts.signer.key());
+    let admission =
+        relay_admission::check(&relay_data, &ctx.accounts.fill_status.status, state, current_time, caller_role)?;
+    let fill_type = get_fill_type(admission);
+
+    let updated_deposit_hash = get_updated_deposit_hash(
+        state.chain_id,
+        &relay_hash,
+        updated_output_amount,
+        &updated_recipient,
+        &updated_message,
+    );
+    // Expired deposits are refunded on the origin chain, so their signed updates expire at the fill deadline.
+    verify_and_consume_signature(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &relay_data.depositor,
+        &updated_deposit_hash,
+        &depositor_signature,
+        SvmError::InvalidDepositorSignature,
+        &mut ctx.accounts.used_signature,
+        relay_data.fill_deadline,
+        current_time,
+        ctx.accounts.payer.key,
+    )?;
+
+    // Being admitted with the flag still set means that the pause has expired.
+    if state.paused_fills {
+        emit_cpi!(PauseExpired { expires_at: state.fills_pause_expires_at });
+    }
+
+    // Depositors can update the output amount to zero only if the updated message is left to run.
+    if is_empty_relay(updated_output_amount, &updated_message) {
+        return err!(SvmError::EmptyRelay);
+    }
+    if is_message_too_large(&updated_message) {
+        return err!(SvmError::MessageTooLarge);
+    }
+    validate_message_schema(&updated_message, &ctx.accounts.message_schema)?;
+
+    check_compliance(
+        state,
+        &[
+            &ctx.accounts.depositor_compliance,
+            &ctx.accounts.recipient_compliance,
+            &ctx.accounts.original_recipient_compliance,
+        ],
+    )?;
+
+    check_allowlist_role(state, &ctx.accounts.allowlist_entry, ALLOWLIST_ROLE_RELAYER)?;
+
+    // Replaced slow fills can't be executed anymore, so their output amount is no longer reserved in the vault.
+    if fill_type == FillType::ReplacedSlowFill {
+        if let Some(liability_changed) = release_requested_slow_fill(&ctx.accounts.slow_fill_liability, &relay_data)? {
+            emit_cpi!(liability_changed);
+        }
+    }
+
+    let decimal_shift = ctx.accounts.route.as_ref().map_or(0, |route| route.decimal_shift);
+    let normalized_output_amount = normalize_output_amount(updated_output_amount, decimal_shift)?;
+
+    // Message-only updates have nothing to pay out, so they don't need the recipient token account.
+    let message_only = updated_output_amount == 0;
+    if !message_only {
+        let recipient_token_account =
+            ctx.accounts.recipient_token_account.as_deref().ok_or(SvmError::MissingRecipientTokenAccount)?;
+        if ctx.accounts.relayer_token_account.key() != recipient_token_account.key() {
+            // Relayer must have delegated the normalized updated_output_amount to the state PDA.
+            transfer_from(
+                &*ctx.accounts.relayer_token_account,
+                recipient_token_account,
+                normalized_output_amount,
+                state,
+                ctx.bumps.state,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_program,
+            )?;
+        }
+    }
+
+    // Overwrites the requester of a preceding slow fill request, as in fill_v3_relay.
+    let fill_status_account = &mut ctx.accounts.fill_status;
+    fill_status_account.status = FillStatus::Filled;
+    fill_status_account.relayer = *ctx.accounts.signer.key;
+    fill_status_account.fill_deadline = relay_data.fill_deadline;
+    fill_status_account.record_payer(ctx.accounts.payer.key());
+
+    record_fill_index(state, &mut ctx.accounts.fill_index_shard, &relay_data)?;
+    record_fill_volume(&ctx.accounts.fill_volume, normalized_output_amount)?;
+
+    if !updated_message.is_empty() {
+        invoke_handler(
+            ctx.accounts.signer.as_ref(),
+            &ctx.accounts.state.key(),
+            ctx.remaining_accounts,
+            &updated_message,
+            &relay_data.output_token,
+            normalized_output_amount,
+        )?;
+    }
+
+    // The original relay values are emitted so that the fill can be matched with its deposit, while the execution info
+    // carries the updated values that were actually used.
+    let filled_relay = FilledV3Relay {
+        input_token: relay_data.input_token,
+        output_token: relay_data.output_token,
+        input_amount: relay_data.input_amount,
+        output_amount: relay_data.output_amount,
+        repayment_chain_id,
+        origin_chain_id: relay_data.origin_chain_id,
+        deposit_id: relay_data.deposit_id,
+        fill_deadline: relay_data.fill_deadline,
+        exclusivity_deadline: relay_data.exclusivity_deadline,
+        exclusive_relayer: relay_data.exclusive_relayer,
+        relayer: repayment_address,
+        depositor: relay_data.depositor,
+        recipient: relay_data.recipient,
+        message_hash: hash_non_empty_message(&relay_data.message),
+        relay_execution_info: V3RelayExecutionEventInfo {
+            updated_recipient,
+            updated_message_hash: hash_non_empty_message(&updated_message),
+            updated_output_amount,
+            fill_type,
+            root_bundle_id: None,
+            escrow: None,
+            memo_hash: hash_payout_memo(None),
+            normalized_output_amount,
+            repayment_token_account_created: false,
+            emergency_execution: false,
+            origin_tx_ref: None,
+            message_only,
+        },
+        token_decimals: ctx.accounts.mint.decimals,
+        relay_hash,
+    };
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, filled_relay)?);
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CloseFillPda<'info> {
-    #[account(mut, address = fill_status.relayer @ SvmError::NotRelayer)]
+    /// Relayer that filled the relay. Writable signer receiving the fill status PDA rent, so it must also have paid it.
+    /// Fill status PDAs paid by a sponsor are closed to the sponsor through close_fill_status_account.
+    #[account(
+        mut,
+        address = fill_status.relayer @ SvmError::NotRelayer,
+        constraint = is_relayer_payer(&fill_status, &signer.key()) @ SvmError::InvalidOriginalPayer
+    )]
     pub signer: Signer<'info>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
-    // No need to check seed derivation as this method only evaluates fill deadline that is recorded in this account.
+    /// Writable fill status PDA being closed, seeds ["fills", relay_hash]. No need to check seed derivation as this
+    /// method only evaluates fill deadline that is recorded in this account.
     #[account(mut, close = signer)]
     pub fill_status: Account<'info, FillStatusAccount>,
 }
 
+// Fill status PDAs created before their payer was recorded were always paid by the relayer.
+fn is_relayer_payer(fill_status: &FillStatusAccount, relayer: &Pubkey) -> bool {
+    fill_status.payer == *relayer || fill_status.payer == Pubkey::default()
+}
+
 pub fn close_fill_pda(ctx: Context<CloseFillPda>) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.signer)?;
+
     let state = &ctx.accounts.state;
+    assert_closes_not_frozen(state)?;
+
     let current_time = get_current_time(state)?;
 
     // Check if the deposit has expired
     if current_time <= ctx.accounts.fill_status.fill_deadline {
+        msg!("Fill deadline {} not passed, current time {}", ctx.accounts.fill_status.fill_deadline, current_time);
+        return err!(SvmError::CanOnlyCloseFillStatusPdaIfFillDeadlinePassed);
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32], relay_data: V3RelayData)]
+pub struct CloseFillStatusAccount<'info> {
+    /// Anyone can close expired fill status PDAs, as the rent is always returned to their original payer.
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Writable fill status PDA being closed, seeds ["fills", relay_hash]. Closed to its original payer.
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"fills", relay_hash.as_ref()],
+        bump,
+        // Make sure caller provided relay_hash used in PDA seeds is valid.
+        constraint = is_relay_hash_valid(&relay_hash, &relay_data, &state) @ SvmError::InvalidRelayHash
+    )]
+    pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// CHECK: Writable original payer of the fill status PDA, receiving the reclaimed rent.
+    #[account(mut, address = fill_status.payer @ SvmError::InvalidOriginalPayer)]
+    pub payer: UncheckedAccount<'info>,
+    /// CHECK: Writable slow fill liability PDA of the output token, seeds ["slow_fill_liability", seed, output_token].
+    /// Only required for requested slow fills that expired unexecuted, releasing their reserved output amount. Pass
+    /// this program ID to represent None.
+    #[account(
+        mut,
+        seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), relay_data.output_token.as_ref()],
+        bump
+    )]
+    pub slow_fill_liability: Option<UncheckedAccount<'info>>,
+}
+
+pub fn close_fill_status_account(ctx: Context<CloseFillStatusAccount>, relay_data: V3RelayData) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.payer)?;
+
+    let state = &ctx.accounts.state;
+    assert_closes_not_frozen(state)?;
+
+    let current_time = get_current_time(state)?;
+    if current_time <= relay_data.fill_deadline {
+        msg!("Fill deadline {} not passed, current time {}", relay_data.fill_deadline, current_time);
         return err!(SvmError::CanOnlyCloseFillStatusPdaIfFillDeadlinePassed);
     }
 
+    // Pending slow fill requests are kept until root bundles including their leaf can no longer be executed, so that
+    // their status can't be deleted from under a slow fill execution.
+    let bundle_window_end = relay_data.fill_deadline.saturating_add(REQUESTED_SLOW_FILL_CLOSE_DELAY);
+    if ctx.accounts.fill_status.status == FillStatus::RequestedSlowFill && current_time <= bundle_window_end {
+        msg!("Requested slow fill closable after {}, current time {}", bundle_window_end, current_time);
+        return err!(SvmError::CanOnlyCloseRequestedSlowFillAfterBundleWindow);
+    }
+
+    if ctx.accounts.fill_status.status == FillStatus::RequestedSlowFill {
+        if let Some(liability_changed) = release_requested_slow_fill(&ctx.accounts.slow_fill_liability, &relay_data)? {
+            emit_cpi!(liability_changed);
+        }
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32], relay_data: V3RelayData)]
+pub struct MarkExpired<'info> {
+    /// Anyone can mark expired relays. Writable signer paying the fill status PDA rent when it does not exist yet.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill status PDA of the relay, seeds ["fills", relay_hash]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
+        seeds = [b"fills", relay_hash.as_ref()],
+        bump,
+        // Make sure caller provided relay_hash used in PDA seeds is valid.
+        constraint = is_relay_hash_valid(&relay_hash, &relay_data, &state) @ SvmError::InvalidRelayHash
+    )]
+    pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// CHECK: Writable slow fill liability PDA of the output token, seeds ["slow_fill_liability", seed, output_token].
+    /// Only required for requested slow fills that expired unexecuted, releasing their reserved output amount. Pass
+    /// this program ID to represent None.
+    #[account(
+        mut,
+        seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), relay_data.output_token.as_ref()],
+        bump
+    )]
+    pub slow_fill_liability: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mark_expired(ctx: Context<MarkExpired>, relay_hash: [u8; 32], relay_data: V3RelayData) -> Result<()> {
+    let current_time = get_current_time(&ctx.accounts.state)?;
+    if current_time <= relay_data.fill_deadline {
+        msg!("Fill deadline {} not passed, current time {}", relay_data.fill_deadline, current_time);
+        return err!(SvmError::CanOnlyMarkExpiredAfterFillDeadline);
+    }
+
+    let fill_status_account = &mut ctx.accounts.fill_status;
+    let bundle_window_end = relay_data.fill_deadline.saturating_add(REQUESTED_SLOW_FILL_CLOSE_DELAY);
+    let error = match fill_status_account.status {
+        FillStatus::Filled => Some(error!(CommonError::RelayFilled)),
+        FillStatus::Expired => Some(error!(SvmError::RelayExpired)),
+        // Same window as for closing, so that a slow fill leaf of an already relayed root bundle can't be executed
+        // after its relay was reported for refund on the origin chain.
+        FillStatus::RequestedSlowFill if current_time <= bundle_window_end => {
+            msg!("Requested slow fill expirable after {}, current time {}", bundle_window_end, current_time);
+            Some(error!(SvmError::CanOnlyMarkRequestedSlowFillExpiredAfterBundleWindow))
+        }
+        _ => None,
+    };
+    if let Some(error) = error {
+        msg!("Relay has fill status {:?}", fill_status_account.status);
+        return Err(error);
+    }
+
+    if fill_status_account.status == FillStatus::RequestedSlowFill {
+        if let Some(liability_changed) = release_requested_slow_fill(&ctx.accounts.slow_fill_liability, &relay_data)? {
+            emit_cpi!(liability_changed);
+        }
+    }
+
+    fill_status_account.status = FillStatus::Expired;
+    fill_status_account.fill_deadline = relay_data.fill_deadline;
+    fill_status_account.record_payer(ctx.accounts.signer.key());
+
+    emit_cpi!(FillStatusExpired {
+        relay_hash,
+        origin_chain_id: relay_data.origin_chain_id,
+        deposit_id: relay_data.deposit_id,
+        fill_deadline: relay_data.fill_deadline,
+    });
+
     Ok(())
 }
+
+pub fn get_fill_statuses_packed(ctx: Context<Null>) -> Result<Vec<u8>> {
+    if ctx.remaining_accounts.len() > FILL_STATUSES_PACKED_MAX_ACCOUNTS {
+        return err!(SvmError::TooManyFillStatusAccounts);
+    }
+
+    let mut packed = vec![0u8; ctx.remaining_accounts.len() * FILL_STATUSES_PACKED_ENTRY_SIZE];
+    for (entry, account) in packed.chunks_exact_mut(FILL_STATUSES_PACKED_ENTRY_SIZE).zip(ctx.remaining_accounts) {
+        match read_fill_status_account(account) {
+            Some(fill_status) => {
+                entry[FILL_STATUSES_PACKED_STATUS_OFFSET] = fill_status.status as u8;
+                entry[FILL_STATUSES_PACKED_RELAYER_OFFSET..FILL_STATUSES_PACKED_FILL_DEADLINE_OFFSET]
+                    .copy_from_slice(fill_status.relayer.as_ref());
+                entry[FILL_STATUSES_PACKED_FILL_DEADLINE_OFFSET..]
+                    .copy_from_slice(&fill_status.fill_deadline.to_le_bytes());
+            }
+            None => entry[FILL_STATUSES_PACKED_STATUS_OFFSET] = FILL_STATUSES_PACKED_INVALID_STATUS,
+        }
+    }
+
+    Ok(packed)
+}
+
+// Closed accounts are owned by the system program and foreign accounts fail the owner or discriminator checks.
+fn read_fill_status_account(account: &AccountInfo) -> Option<FillStatusAccount> {
+    if account.owner != &crate::ID {
+        return None;
+    }
+
+    let data = account.try_borrow_data().ok()?;
+    FillStatusAccount::try_deserialize(&mut &data[..]).ok()
+}
+
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32])]
+pub struct GetFillStatus<'info> {
+    /// CHECK: Fill status PDA of the relay, seeds ["fills", relay_hash]. Read-only. Deserialized in the handler, as
+    /// PDAs that were never created or already closed are reported as unfilled.
+    #[account(seeds = [b"fills", relay_hash.as_ref()], bump)]
+    pub fill_status: UncheckedAccount<'info>,
+}
+
+pub fn get_fill_status(ctx: Context<GetFillStatus>) -> Result<FillStatusAccount> {
+    let fill_status = &ctx.accounts.fill_status;
+    if fill_status.owner != &crate::ID {
+        return Ok(FillStatusAccount {
+            status: FillStatus::Unfilled,
+            relayer: Pubkey::default(),
+            fill_deadline: 0,
+            requester: Pubkey::default(),
+            payer: Pubkey::default(),
+        });
+    }
+
+    let data = fill_status.try_borrow_data()?;
+    FillStatusAccount::try_deserialize_any_layout(&data)
+}
+
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32], relay_data: V3RelayData)]
+pub struct MigrateFillStatusAccount<'info> {
+    /// Anyone can migrate legacy fill status PDAs. Writable signer paying the rent for their extended size.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Writable fill status PDA of a legacy layout, seeds ["fills", relay_hash]. Deserialized in the handler, as
+    /// its size predates the current layout.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"fills", relay_hash.as_ref()],
+        bump,
+        // Make sure caller provided relay_hash used in PDA seeds is valid.
+        constraint = is_relay_hash_valid(&relay_hash, &relay_data, &state) @ SvmError::InvalidRelayHash
+    )]
+    pub fill_status: UncheckedAccount<'info>,
+
+    /// System program, used to transfer the rent of the extended size.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_fill_status_account(ctx: Context<MigrateFillStatusAccount>, relay_data: V3RelayData) -> Result<()> {
+    let fill_status_info = ctx.accounts.fill_status.to_account_info();
+    if !LEGACY_FILL_STATUS_ACCOUNT_SIZES.contains(&fill_status_info.data_len()) {
+        return err!(SvmError::NotLegacyFillStatusAccount);
+    }
+
+    let mut fill_status = FillStatusAccount::try_deserialize_any_layout(&fill_status_info.try_borrow_data()?)?;
+    fill_status.migrate_legacy_layout(relay_data.fill_deadline);
+
+    let space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(fill_status_info.lamports());
+    if required_lamports > 0 {
+        let transfer_accounts = Transfer { from: ctx.accounts.signer.to_account_info(), to: fill_status_info.clone() };
+        let cpi_context = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts);
+        transfer(cpi_context, required_lamports)?;
+    }
+    fill_status_info.realloc(space, false)?;
+
+    let mut data = fill_status_info.try_borrow_mut_data()?;
+    fill_status.try_serialize(&mut &mut data[..])
+}
@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{DepositFilled, SetFillConfirmationOracle},
+    state::{FillConfirmation, State},
+    utils::{get_current_time, verify_ed25519_signature},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFillConfirmationOracleState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_fill_confirmation_oracle(
+    ctx: Context<SetFillConfirmationOracleState>,
+    fill_confirmation_oracle: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.state.fill_confirmation_oracle = fill_confirmation_oracle;
+
+    emit_cpi!(SetFillConfirmationOracle { fill_confirmation_oracle });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(deposit_id: [u8; 32])]
+pub struct PostFillConfirmation<'info> {
+    /// Anyone can post the oracle attestation. Signer pays the rent for the fill confirmation PDA.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill confirmation PDA created for the deposit, seeds ["deposit_filled", seed, deposit_id].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + FillConfirmation::INIT_SPACE,
+        seeds = [b"deposit_filled", state.seed.to_le_bytes().as_ref(), deposit_id.as_ref()],
+        bump
+    )]
+    pub fill_confirmation: Account<'info, FillConfirmation>,
+
+    /// CHECK: Instructions sysvar, used to load the Ed25519 signature verification instruction.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn post_fill_confirmation(
+    ctx: Context<PostFillConfirmation>,
+    deposit_id: [u8; 32],
+    destination_fill_event_hash: [u8; 32],
+    attestation: [u8; 64],
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let oracle = state.fill_confirmation_oracle.ok_or(SvmError::FillConfirmationOracleNotSet)?;
+
+    // Oracle signs the deposit ID followed by the destination fill event hash.
+    let mut payload = Vec::with_capacity(deposit_id.len() + destination_fill_event_hash.len());
+    payload.extend_from_slice(&deposit_id);
+    payload.extend_from_slice(&destination_fill_event_hash);
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &oracle,
+        &payload,
+        &attestation,
+        SvmError::InvalidFillAttestation,
+    )?;
+
+    let fill_confirmation = &mut ctx.accounts.fill_confirmation;
+    fill_confirmation.deposit_id = deposit_id;
+    fill_confirmation.destination_fill_event_hash = destination_fill_event_hash;
+    fill_confirmation.oracle = oracle;
+    fill_confirmation.confirmed_at = get_current_time(state)?;
+
+    emit_cpi!(DepositFilled { deposit_id, destination_fill_event_hash, oracle });
+
+    Ok(())
+}
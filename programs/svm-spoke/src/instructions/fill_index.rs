@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::SetFillIndex,
+    state::{FillIndexShard, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFillIndexState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_fill_index(ctx: Context<SetFillIndexState>, enabled: bool) -> Result<()> {
+    ctx.accounts.state.fill_index_enabled = enabled;
+
+    emit_cpi!(SetFillIndex { enabled });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(origin_chain_id: u64, bucket: u64)]
+pub struct GetFillIndexShard<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill index shard PDA, seeds ["fill_index", seed, origin_chain_id, bucket]. Read-only.
+    #[account(
+        seeds = [
+            b"fill_index",
+            state.seed.to_le_bytes().as_ref(),
+            origin_chain_id.to_le_bytes().as_ref(),
+            bucket.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub fill_index_shard: Account<'info, FillIndexShard>,
+}
+
+pub fn get_fill_index_shard(ctx: Context<GetFillIndexShard>) -> Result<FillIndexShard> {
+    let fill_index_shard = &ctx.accounts.fill_index_shard;
+
+    Ok(FillIndexShard {
+        origin_chain_id: fill_index_shard.origin_chain_id,
+        bucket: fill_index_shard.bucket,
+        bitmap: fill_index_shard.bitmap,
+    })
+}
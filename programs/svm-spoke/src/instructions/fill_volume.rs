@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::{ReconciledFillVolume, SetFillVolumeTolerance},
+    state::{FillVolume, State},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct SetFillVolumeToleranceState<'info> {
+    /// Owner opting the mint into refund checks. Writable signer paying the fill volume PDA rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill volume PDA of the mint, seeds ["fill_volume", seed, mint]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + FillVolume::INIT_SPACE,
+        seeds = [b"fill_volume", state.seed.to_le_bytes().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub fill_volume: Account<'info, FillVolume>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// Fills are only tracked from the creation of the PDA, so the first bundle window of a newly opted in mint undercounts
+// its fill volume and may need reconcile_fill_volume or execution approvals for its refunds.
+pub fn set_fill_volume_tolerance(
+    ctx: Context<SetFillVolumeToleranceState>,
+    mint: Pubkey,
+    tolerance: u64,
+) -> Result<()> {
+    ctx.accounts.fill_volume.tolerance = tolerance;
+
+    emit_cpi!(SetFillVolumeTolerance { mint, tolerance });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct ReconcileFillVolumeState<'info> {
+    /// Owner reconciling the fill volume of the mint.
+    #[account(address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill volume PDA of the mint, seeds ["fill_volume", seed, mint]. Writable.
+    #[account(mut, seeds = [b"fill_volume", state.seed.to_le_bytes().as_ref(), mint.as_ref()], bump)]
+    pub fill_volume: Account<'info, FillVolume>,
+}
+
+// Only the open window is reconciled, the refunds of the current bundle stay bounded by the fill volume of its window.
+pub fn reconcile_fill_volume(ctx: Context<ReconcileFillVolumeState>, mint: Pubkey, fill_volume: u64) -> Result<()> {
+    let previous_fill_volume = ctx.accounts.fill_volume.fill_volume;
+    ctx.accounts.fill_volume.fill_volume = fill_volume;
+
+    emit_cpi!(ReconciledFillVolume { mint, previous_fill_volume, fill_volume });
+
+    Ok(())
+}
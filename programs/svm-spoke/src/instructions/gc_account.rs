@@ -0,0 +1,156 @@
+use anchor_lang::{prelude::*, solana_program::system_program};
+
+use crate::{
+    constants::{
+        EVENT_COMMITMENT_EPOCH_DURATION, GC_CALLER_INCENTIVE_BPS, GC_EVENT_COMMITMENT_RETENTION,
+        GC_REQUESTER_ACTIVITY_RETENTION, GC_ROUTE_DISABLED_PERIOD,
+    },
+    error::SvmError,
+    event::GarbageCollectedAccount,
+    state::{EventCommitment, GcAccountKind, RequesterActivity, Route, SettlementMode, State},
+    utils::{assert_lamport_destination, calculate_bps_fee, get_current_time, OrArithmeticOverflow},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GcAccount<'info> {
+    /// Anyone can close stale accounts. Writable signer receiving the caller share of the rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Writable account being closed. Its address, type and eligibility are checked against the kind.
+    #[account(mut)]
+    pub target: UncheckedAccount<'info>,
+
+    /// CHECK: Writable original payer receiving the rest of the rent, only required for kinds that record it. Pass this
+    /// program ID to represent None.
+    #[account(mut)]
+    pub original_payer: Option<UncheckedAccount<'info>>,
+}
+
+pub fn gc_account(ctx: Context<GcAccount>, kind: GcAccountKind) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+    let seed_bytes = state.seed.to_le_bytes();
+    let target = ctx.accounts.target.to_account_info();
+
+    // Check the target is the PDA of the kind and that it is stale, also returning its original payer when recorded.
+    let original_payer = match &kind {
+        GcAccountKind::Route { origin_token, destination_chain_id } => {
+            let chain_id_bytes = destination_chain_id.to_le_bytes();
+            assert_gc_target(
+                &target,
+                &[b"route", origin_token.as_ref(), seed_bytes.as_ref(), chain_id_bytes.as_ref()],
+            )?;
+            let route = Route::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+
+            // Routes that still configure fills of their token are kept even when disabled for deposits, including their
+            // fee floor and the mint decimals that fills passing the route are checked against.
+            let disabled_since = get_route_disabled_since(&route, current_time);
+            if disabled_since == 0
+                || current_time < disabled_since.saturating_add(GC_ROUTE_DISABLED_PERIOD)
+                || route.decimal_shift != 0
+                || route.settlement_mode != SettlementMode::VaultTransfer
+                || route.min_relayer_fee_bps != 0
+                || route.expected_decimals != 0
+            {
+                return err!(SvmError::AccountNotCollectable);
+            }
+
+            None
+        }
+        GcAccountKind::RequesterActivity { requester } => {
+            assert_gc_target(&target, &[b"requester_activity", seed_bytes.as_ref(), requester.as_ref()])?;
+            let requester_activity = RequesterActivity::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+
+            let window_end = requester_activity.window_start.saturating_add(state.slow_fill_rate_window);
+            if current_time < window_end.saturating_add(GC_REQUESTER_ACTIVITY_RETENTION) {
+                return err!(SvmError::AccountNotCollectable);
+            }
+
+            Some(*requester) // Requester activity PDAs are paid by the requester they are derived for.
+        }
+        GcAccountKind::EventCommitment { epoch } => {
+            assert_gc_target(&target, &[b"event_commitment", seed_bytes.as_ref(), epoch.to_le_bytes().as_ref()])?;
+            EventCommitment::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+
+            let epoch_end = epoch.saturating_add(1).saturating_mul(EVENT_COMMITMENT_EPOCH_DURATION);
+            if current_time < epoch_end.saturating_add(GC_EVENT_COMMITMENT_RETENTION) {
+                return err!(SvmError::AccountNotCollectable);
+            }
+
+            None
+        }
+    };
+
+    let rent = target.lamports();
+    let caller = ctx.accounts.signer.to_account_info();
+    assert_lamport_destination(&caller)?;
+
+    // The caller is paid a share of the rent as incentive and any rest is returned to the recorded original payer.
+    let original_payer_rent = match original_payer {
+        Some(original_payer) => {
+            let original_payer_account = match &ctx.accounts.original_payer {
+                Some(account) if account.key() == original_payer => account.to_account_info(),
+                _ => return err!(SvmError::InvalidOriginalPayer),
+            };
+            assert_lamport_destination(&original_payer_account)?;
+
+            let original_payer_rent = rent.saturating_sub(calculate_bps_fee(rent, GC_CALLER_INCENTIVE_BPS));
+            credit_lamports(&original_payer_account, original_payer_rent)?;
+            original_payer_rent
+        }
+        None => 0,
+    };
+    let caller_rent = rent - original_payer_rent;
+    credit_lamports(&caller, caller_rent)?;
+
+    // Close the target same as the anchor close constraint, that cannot be used with the unchecked target account.
+    **target.lamports.borrow_mut() = 0;
+    target.assign(&system_program::ID);
+    target.realloc(0, false)?;
+
+    emit_cpi!(GarbageCollectedAccount {
+        kind,
+        account: target.key(),
+        caller: caller.key(),
+        caller_rent,
+        original_payer,
+        original_payer_rent,
+    });
+
+    Ok(())
+}
+
+// Routes disabled before their disable time was recorded are never collectable, as their staleness is unknown.
+fn get_route_disabled_since(route: &Route, current_time: u32) -> u32 {
+    match route.enabled {
+        false => route.disabled_at,
+        true if route.disable_at != 0 && current_time >= route.disable_at => route.disable_at,
+        true => 0,
+    }
+}
+
+fn assert_gc_target(target: &AccountInfo, seeds: &[&[u8]]) -> Result<()> {
+    let (expected_address, _) = Pubkey::find_program_address(seeds, &crate::ID);
+    if target.key() != expected_address || target.owner != &crate::ID {
+        return err!(SvmError::InvalidGcAccount);
+    }
+
+    Ok(())
+}
+
+fn credit_lamports(account: &AccountInfo, lamports: u64) -> Result<()> {
+    let starting_lamports = account.lamports();
+    **account.lamports.borrow_mut() = starting_lamports.checked_add(lamports).or_overflow("garbage collected rent")?;
+
+    Ok(())
+}
@@ -5,33 +5,38 @@ use anchor_lang::{
 
 use crate::{
     constants::MESSAGE_TRANSMITTER_PROGRAM_ID,
-    error::{CallDataError, SvmError},
+    error::SvmError,
     program::SvmSpoke,
     state::State,
-    utils::{self, EncodeInstructionData},
+    utils::AdminMessage,
 };
 
 #[derive(Accounts)]
 #[instruction(params: HandleReceiveMessageParams)]
 pub struct HandleReceiveMessage<'info> {
-    // authority_pda is a Signer to ensure that this instruction can only be called by the Message Transmitter.
+    /// authority_pda is a Signer to ensure that this instruction can only be called by the Message Transmitter, or by
+    /// the admin messenger instead when the state sets one. Provided by the messenger relaying the HubPool message.
     #[account(
         seeds = [b"message_transmitter_authority", SvmSpoke::id().as_ref()],
         bump = params.authority_bump,
-        seeds::program = MESSAGE_TRANSMITTER_PROGRAM_ID
+        seeds::program = state.admin_messenger.unwrap_or(MESSAGE_TRANSMITTER_PROGRAM_ID)
     )]
     pub authority_pda: Signer<'info>,
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. The message must come from its remote domain
+    /// and cross-domain admin.
     #[account(
-        seeds = [b"state", state.seed.to_le_bytes().as_ref()],
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
         bump,
         constraint = params.remote_domain == state.remote_domain @ SvmError::InvalidRemoteDomain,
         constraint = params.sender == state.cross_domain_admin @ SvmError::InvalidRemoteSender,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
     )]
     pub state: Account<'info, State>,
 
     /// CHECK: empty PDA, used in authenticating self-CPI invoked by the received message.
     #[account(seeds = [b"self_authority"], bump)]
     pub self_authority: UncheckedAccount<'info>,
+    /// This program, invoked through self-CPI to execute the admin action from the message.
     pub program: Program<'info, SvmSpoke>,
 }
 
@@ -47,52 +52,11 @@ pub fn handle_receive_message<'info>(
     ctx: Context<'_, '_, '_, 'info, HandleReceiveMessage<'info>>,
     params: HandleReceiveMessageParams,
 ) -> Result<()> {
-    let self_ix_data = translate_message(&params.message_body)?;
+    let self_ix_data = AdminMessage::decode(&params.message_body)?.to_instruction_data()?;
 
     invoke_self(&ctx, &self_ix_data)
 }
 
-fn translate_message(data: &Vec<u8>) -> Result<Vec<u8>> {
-    match utils::get_solidity_selector(data)? {
-        s if s == utils::encode_solidity_selector("pauseDeposits(bool)") => {
-            let pause = utils::decode_solidity_bool(&utils::get_solidity_arg(data, 0)?)?;
-
-            pause.encode_instruction_data("global:pause_deposits")
-        }
-        s if s == utils::encode_solidity_selector("pauseFills(bool)") => {
-            let pause = utils::decode_solidity_bool(&utils::get_solidity_arg(data, 0)?)?;
-
-            pause.encode_instruction_data("global:pause_fills")
-        }
-        s if s == utils::encode_solidity_selector("setCrossDomainAdmin(address)") => {
-            let new_cross_domain_admin = utils::decode_solidity_address(&utils::get_solidity_arg(data, 0)?)?;
-
-            new_cross_domain_admin.encode_instruction_data("global:set_cross_domain_admin")
-        }
-        // The EVM function signature is setEnableRoute(address,uint256,bool).
-        // The EVM Solana adapter translates this to the expected Solana format: setEnableRoute(bytes32,uint64,bool).
-        s if s == utils::encode_solidity_selector("setEnableRoute(bytes32,uint64,bool)") => {
-            let origin_token = Pubkey::new_from_array(utils::get_solidity_arg(data, 0)?);
-            let destination_chain_id = utils::decode_solidity_uint64(&utils::get_solidity_arg(data, 1)?)?;
-            let enabled = utils::decode_solidity_bool(&utils::get_solidity_arg(data, 2)?)?;
-
-            (origin_token, destination_chain_id, enabled).encode_instruction_data("global:set_enable_route")
-        }
-        s if s == utils::encode_solidity_selector("relayRootBundle(bytes32,bytes32)") => {
-            let relayer_refund_root = utils::get_solidity_arg(data, 0)?;
-            let slow_relay_root = utils::get_solidity_arg(data, 1)?;
-
-            (relayer_refund_root, slow_relay_root).encode_instruction_data("global:relay_root_bundle")
-        }
-        s if s == utils::encode_solidity_selector("emergencyDeleteRootBundle(uint256)") => {
-            let root_id = utils::decode_solidity_uint32(&utils::get_solidity_arg(data, 0)?)?;
-
-            root_id.encode_instruction_data("global:emergency_delete_root_bundle")
-        }
-        _ => Err(CallDataError::UnsupportedSelector.into()),
-    }
-}
-
 // Invokes self CPI for remote domain invoked message calls. We use low level invoke_signed with seeds corresponding to
 // the self_authority account and passing all remaining accounts from the context. Instruction data is obtained within
 // handle_receive_message by translating the received message body into a valid instruction data for the invoked CPI.
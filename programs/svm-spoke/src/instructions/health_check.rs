@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::TokenAccount,
+    token_interface::{Mint, TokenAccount as InterfaceTokenAccount},
+};
+
+use crate::{
+    constants::{
+        HEALTH_CHECK_ALL, HEALTH_CHECK_COUNTERS, HEALTH_CHECK_PAUSE_FLAGS, HEALTH_CHECK_STATE_BUMP,
+        HEALTH_CHECK_WSOL_VAULT, MAX_EXECUTION_APPROVAL_THRESHOLDS, MAX_TOTAL_FEE_BPS, NATIVE_MINT,
+    },
+    error::SvmError,
+    event::{HealthCheckFailed, VaultFrozen, VaultMissing},
+    state::{State, VaultLiability},
+    utils::{
+        get_current_time, get_pending_slow_fill_amount, get_vault_authority, is_vault, is_vault_address,
+        is_vault_frozen, is_vault_initialized,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct HealthCheck<'info> {
+    /// CHECK: Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only. Deserialized and
+    /// re-derived by the health check itself, so that a broken state is reported instead of failing the instruction.
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: wSOL vault ATA of the vault authority PDA, checked to be synced with its lamports. Pass this
+    /// program ID to represent None, e.g. when no wSOL route was ever enabled.
+    pub wsol_vault: Option<UncheckedAccount<'info>>,
+}
+
+pub fn health_check(ctx: Context<HealthCheck>) -> Result<u32> {
+    let state_info = ctx.accounts.state.to_account_info();
+
+    // All other checks depend on the state, so they are reported as failed when it does not deserialize.
+    let failed_checks = match deserialize_state(&state_info) {
+        Some(state) => get_failed_checks(&state, &state_info.key(), &ctx.accounts.wsol_vault)?,
+        None => HEALTH_CHECK_ALL,
+    };
+
+    // Only failures are emitted, so that healthy probes don't spam indexers with events.
+    if failed_checks != 0 {
+        emit_cpi!(HealthCheckFailed { state: state_info.key(), failed_checks });
+    }
+
+    Ok(failed_checks)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReportMissingVault<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only, deriving the vault authority.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the reported vault.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Vault ATA of the vault authority PDA for the mint, derived with the token program owning the mint.
+    /// Existence, mint and authority are checked by the report itself.
+    #[account(
+        constraint = is_vault_address(vault.key, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAddress
+    )]
+    pub vault: UncheckedAccount<'info>,
+}
+
+pub fn report_missing_vault(ctx: Context<ReportMissingVault>) -> Result<bool> {
+    let vault = &ctx.accounts.vault;
+    let missing = !is_vault_initialized(vault, &ctx.accounts.state.key(), &ctx.accounts.mint.to_account_info());
+
+    // Only missing vaults are emitted, so that probing existing vaults doesn't spam indexers with events.
+    if missing {
+        emit_cpi!(VaultMissing { mint: ctx.accounts.mint.key(), vault: vault.key() });
+    }
+
+    Ok(missing)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReportFrozenVault<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only, deriving the vault authority.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the reported vault.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Vault ATA of the vault authority PDA for the mint, derived with the token program owning the mint.
+    /// Existence, mint and authority are checked by the report itself.
+    #[account(
+        constraint = is_vault_address(vault.key, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAddress
+    )]
+    pub vault: UncheckedAccount<'info>,
+}
+
+pub fn report_frozen_vault(ctx: Context<ReportFrozenVault>) -> Result<bool> {
+    let vault = &ctx.accounts.vault;
+    let frozen = is_vault_initialized(vault, &ctx.accounts.state.key(), &ctx.accounts.mint.to_account_info())
+        && is_vault_frozen(vault)?;
+
+    // Only frozen vaults are emitted, so that probing vaults that can pay out doesn't spam indexers with events.
+    if frozen {
+        emit_cpi!(VaultFrozen { mint: ctx.accounts.mint.key(), vault: vault.key() });
+    }
+
+    Ok(frozen)
+}
+
+#[derive(Accounts)]
+pub struct GetVaultCapacity<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only, deriving the vault authority.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the vault.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault ATA of the vault authority PDA for the mint. Read-only.
+    #[account(constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated)]
+    pub vault: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// Vault liability PDA of the mint, seeds ["vault_liability", seed, mint]. Read-only. Pass this program ID to
+    /// represent None, e.g. for mints that were never deposited.
+    #[account(seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub vault_liability: Option<Account<'info, VaultLiability>>,
+
+    /// CHECK: Slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint]. Read-only, treated as
+    /// nothing pending when no slow fill request of the mint created it.
+    #[account(seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VaultCapacity {
+    pub vault_balance: u64,
+    pub pending_slow_fill_amount: u64, // Output amount of requested slow fills pending execution.
+    pub pending_refund_amount: u64,    // Vault liabilities backing deposits still owed as refunds.
+    pub free_capacity: u64,            // Vault balance exceeding both pending amounts, before the pool liquidity.
+    // Time of the last reconcile_counters, counters can have drifted since. 0 when counters were never reconciled.
+    pub last_counter_reconciliation_time: u32,
+}
+
+pub fn get_vault_capacity(ctx: Context<GetVaultCapacity>) -> Result<VaultCapacity> {
+    let vault_balance = ctx.accounts.vault.amount;
+    let pending_slow_fill_amount = get_pending_slow_fill_amount(&ctx.accounts.slow_fill_liability)?;
+    let pending_refund_amount = ctx.accounts.vault_liability.as_ref().map_or(0, |liability| liability.liabilities);
+
+    // Same obligations as the sweep surplus, so that the free capacity is exactly what can be swept.
+    let obligations = pending_refund_amount.saturating_add(pending_slow_fill_amount);
+
+    Ok(VaultCapacity {
+        vault_balance,
+        pending_slow_fill_amount,
+        pending_refund_amount,
+        free_capacity: vault_balance.saturating_sub(obligations),
+        last_counter_reconciliation_time: ctx.accounts.state.last_counter_reconciliation_time,
+    })
+}
+
+fn get_failed_checks(state: &State, state_key: &Pubkey, wsol_vault: &Option<UncheckedAccount>) -> Result<u32> {
+    let mut failed_checks = 0;
+
+    let (state_pda, _) = Pubkey::find_program_address(&[b"state", state.seed.to_le_bytes().as_ref()], &crate::ID);
+    if state_key != &state_pda {
+        failed_checks |= HEALTH_CHECK_STATE_BUMP;
+    }
+
+    // Unpausing clears the expiry, so an expiry is only ever set on paused fills.
+    if !state.paused_fills && state.fills_pause_expires_at != 0 {
+        failed_checks |= HEALTH_CHECK_PAUSE_FLAGS;
+    }
+
+    if let Some(wsol_vault) = wsol_vault {
+        if !is_wsol_vault_synced(wsol_vault, state_key) {
+            failed_checks |= HEALTH_CHECK_WSOL_VAULT;
+        }
+    }
+
+    if !are_counters_sane(state, get_current_time(state)?) {
+        failed_checks |= HEALTH_CHECK_COUNTERS;
+    }
+
+    Ok(failed_checks)
+}
+
+fn deserialize_state(account: &AccountInfo) -> Option<State> {
+    if account.owner != &crate::ID {
+        return None;
+    }
+
+    let data = account.try_borrow_data().ok()?;
+    State::try_deserialize(&mut &data[..]).ok()
+}
+
+// sync_native sets the vault amount to its lamports above the rent exempt reserve, so lamports sent straight to the
+// vault are only accounted for once synced.
+fn is_wsol_vault_synced(wsol_vault: &AccountInfo, state: &Pubkey) -> bool {
+    if wsol_vault.key() != get_associated_token_address(&get_vault_authority(state), &NATIVE_MINT)
+        || wsol_vault.owner != &anchor_spl::token::ID
+    {
+        return false;
+    }
+
+    let Ok(data) = wsol_vault.try_borrow_data() else {
+        return false;
+    };
+    let Ok(vault) = TokenAccount::try_deserialize(&mut &data[..]) else {
+        return false;
+    };
+
+    match Option::<u64>::from(vault.is_native) {
+        Some(rent_exempt_reserve) => rent_exempt_reserve.checked_add(vault.amount) == Some(wsol_vault.lamports()),
+        None => false,
+    }
+}
+
+fn are_counters_sane(state: &State, current_time: u32) -> bool {
+    let wind_down_valid =
+        if state.winding_down { state.wind_down_time <= current_time } else { state.wind_down_time == 0 };
+
+    wind_down_valid
+        && state.last_config_snapshot_time <= current_time
+        && state.protocol_fee_bps as u64 + state.liquidity_pool_fee_bps as u64 <= MAX_TOTAL_FEE_BPS
+        && state.execution_approval_thresholds.len() <= MAX_EXECUTION_APPROVAL_THRESHOLDS
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::SvmError, metadata, utils::Null};
+
+pub fn get_instruction_metadata(_ctx: Context<Null>, discriminator: [u8; 8]) -> Result<Vec<u8>> {
+    let accounts = metadata::get_instruction_metadata(&discriminator).ok_or(SvmError::MissingInstructionMetadata)?;
+
+    Ok(metadata::encode_instruction_metadata(accounts))
+}
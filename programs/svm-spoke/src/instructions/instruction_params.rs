@@ -1,10 +1,18 @@
 use anchor_lang::{prelude::*, solana_program::system_program};
 
-use crate::error::SvmError;
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    constraints::is_upgrade_pending,
+    error::SvmError,
+    event::ClosedStaleParamsBuffer,
+    state::{InstructionParamsHeader, State},
+    utils::{assert_lamport_destination, get_current_time, OrArithmeticOverflow},
+};
 
 #[derive(Accounts)]
 #[instruction(total_size: u32)]
 pub struct InitializeInstructionParams<'info> {
+    /// Account that will use the instruction params. Writable signer paying the PDA rent.
     #[account(mut)]
     pub signer: Signer<'info>,
 
@@ -18,18 +26,50 @@ pub struct InitializeInstructionParams<'info> {
     )]
     pub instruction_params: UncheckedAccount<'info>,
 
+    /// Header of the instruction params recording their payer and creation time, seeds ["instruction_params_header",
+    /// signer]. Reused when left behind by instructions that close the instruction params after consuming them.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + InstructionParamsHeader::INIT_SPACE,
+        seeds = [b"instruction_params_header", signer.key().as_ref()],
+        bump
+    )]
+    pub instruction_params_header: Account<'info, InstructionParamsHeader>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. New instruction params can't be initialized
+    /// while a program upgrade is pending, so that they are never consumed by a program with a different layout.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = !is_upgrade_pending(&state)? @ SvmError::UpgradePending,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
+pub fn initialize_instruction_params(ctx: Context<InitializeInstructionParams>) -> Result<()> {
+    let header = &mut ctx.accounts.instruction_params_header;
+    header.payer = ctx.accounts.signer.key();
+    header.created_at = get_current_time(&ctx.accounts.state)?;
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(offset: u32, fragment: Vec<u8>)]
 pub struct WriteInstructionParamsFragment<'info> {
+    /// Account that initialized the instruction params.
     pub signer: Signer<'info>,
 
     /// CHECK: use unchecked account in order to be able writing raw data fragments.
     #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump)]
     pub instruction_params: UncheckedAccount<'info>,
 
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
@@ -54,22 +94,89 @@ pub fn write_instruction_params_fragment(
 
 #[derive(Accounts)]
 pub struct CloseInstructionParams<'info> {
+    /// Account that initialized the instruction params. Writable signer receiving the PDA rent.
     #[account(mut)]
     pub signer: Signer<'info>,
 
     /// CHECK: We cannot check account type as its discriminator could have been overwritten.
     #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump)]
     pub instruction_params: UncheckedAccount<'info>,
+
+    /// CHECK: Writable header of the instruction params, seeds ["instruction_params_header", signer]. Unchecked so
+    /// that instruction params initialized before headers were recorded can still be closed.
+    #[account(mut, seeds = [b"instruction_params_header", signer.key().as_ref()], bump)]
+    pub instruction_params_header: UncheckedAccount<'info>,
 }
 
-// Reimplements close from anchor common module that is private. We cannot use anchor close constraint for unchecked accounts.
 pub fn close_instruction_params(ctx: Context<CloseInstructionParams>) -> Result<()> {
-    let closed_account = ctx.accounts.instruction_params.to_account_info();
-    let sol_destination = ctx.accounts.signer.to_account_info();
+    close_unchecked_account(&ctx.accounts.instruction_params, &ctx.accounts.signer)?;
+    close_unchecked_account(&ctx.accounts.instruction_params_header, &ctx.accounts.signer)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseStaleInstructionParams<'info> {
+    /// Anyone can close stale instruction params, as the rent is always returned to their original payer.
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: Writable original payer of the instruction params, receiving the reclaimed rent.
+    #[account(mut, address = instruction_params_header.payer @ SvmError::InvalidOriginalPayer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: We cannot check account type as its discriminator could have been overwritten.
+    #[account(mut, seeds = [b"instruction_params", payer.key().as_ref()], bump)]
+    pub instruction_params: UncheckedAccount<'info>,
+
+    /// Writable header of the instruction params, seeds ["instruction_params_header", payer]. Closed to the payer.
+    #[account(mut, close = payer, seeds = [b"instruction_params_header", payer.key().as_ref()], bump)]
+    pub instruction_params_header: Account<'info, InstructionParamsHeader>,
+}
+
+pub fn close_stale_instruction_params(ctx: Context<CloseStaleInstructionParams>) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let created_at = ctx.accounts.instruction_params_header.created_at;
+
+    // Zero TTL disables closing by third parties, so buffers can then only be closed by their payer.
+    let stale_at = created_at.checked_add(state.params_buffer_ttl).or_overflow("instruction params stale time")?;
+    if state.params_buffer_ttl == 0 || get_current_time(state)? < stale_at {
+        return err!(SvmError::CanOnlyCloseParamsBufferAfterTtl);
+    }
+
+    close_unchecked_account(&ctx.accounts.instruction_params, &ctx.accounts.payer)?;
+
+    emit_cpi!(ClosedStaleParamsBuffer {
+        payer: ctx.accounts.payer.key(),
+        closer: ctx.accounts.signer.key(),
+        created_at,
+    });
+
+    Ok(())
+}
+
+// Reimplements close from anchor common module that is private. We cannot use anchor close constraint for unchecked
+// accounts.
+fn close_unchecked_account(closed_account: &AccountInfo, sol_destination: &AccountInfo) -> Result<()> {
+    assert_lamport_destination(sol_destination)?;
+
+    // Instructions consuming the instruction params close them without their header, which is then closed on its own.
+    // Instruction params initialized before headers were recorded don't have one.
+    if closed_account.lamports() == 0 {
+        return Ok(());
+    }
 
     // Transfer tokens from the account to the sol_destination.
     let dest_starting_lamports = sol_destination.lamports();
-    **sol_destination.lamports.borrow_mut() = dest_starting_lamports.checked_add(closed_account.lamports()).unwrap();
+    **sol_destination.lamports.borrow_mut() = dest_starting_lamports
+        .checked_add(closed_account.lamports())
+        .or_overflow("closed instruction params lamports")?;
     **closed_account.lamports.borrow_mut() = 0;
 
     closed_account.assign(&system_program::ID);
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_TOTAL_FEE_BPS},
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{ProvidedLiquidity, RemovedLiquidity, SetLiquidityPoolConfig},
+    state::{LiquidityPool, LiquidityPosition, State},
+    utils::{is_vault, transfer_from, transfer_from_vault, OrArithmeticOverflow, VaultSigner},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLiquidityPoolConfigState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_liquidity_pool_config(ctx: Context<SetLiquidityPoolConfigState>, enabled: bool, fee_bps: u16) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+
+    // Pool and protocol fees are skimmed from the same slow fill payouts.
+    if fee_bps as u64 + state.protocol_fee_bps as u64 > MAX_TOTAL_FEE_BPS {
+        return err!(SvmError::InvalidLiquidityPoolFee);
+    }
+
+    state.liquidity_pools_enabled = enabled;
+    state.liquidity_pool_fee_bps = fee_bps;
+
+    emit_cpi!(SetLiquidityPoolConfig { enabled, fee_bps });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeLiquidityPool<'info> {
+    /// Owner opting the mint into external liquidity. Writable signer paying the liquidity pool PDA rent.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the pooled token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Liquidity pool PDA created for the mint, seeds ["liquidity_pool", seed, mint].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + LiquidityPool::INIT_SPACE,
+        seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_liquidity_pool(ctx: Context<InitializeLiquidityPool>) -> Result<()> {
+    ctx.accounts.liquidity_pool.mint = ctx.accounts.mint.key();
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProvideLiquidity<'info> {
+    /// Liquidity provider or an account with a token delegation from it. Writable signer paying the liquidity
+    /// position PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Liquidity pools must be enabled.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.liquidity_pools_enabled @ SvmError::LiquidityPoolsDisabled,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the pooled token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Writable liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint].
+    #[account(mut, seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    /// Liquidity position PDA of the signer, seeds ["liquidity_position", seed, mint, signer]. Created if missing and
+    /// writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + LiquidityPosition::INIT_SPACE,
+        seeds = [
+            b"liquidity_position",
+            state.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump
+    )]
+    pub liquidity_position: Account<'info, LiquidityPosition>,
+
+    /// Writable token account of the provider for the mint, source of the liquidity. The provider must have delegated
+    /// the amount to the state PDA.
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, receiving the liquidity.
+    #[account(
+        mut,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn provide_liquidity(ctx: Context<ProvideLiquidity>, amount: u64) -> Result<()> {
+    let shares = ctx.accounts.liquidity_pool.shares_for_liquidity(amount)?;
+    if shares == 0 {
+        return err!(SvmError::InvalidLiquidityAmount);
+    }
+
+    transfer_from(
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.vault,
+        amount,
+        &ctx.accounts.state,
+        ctx.bumps.state,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    liquidity_pool.total_shares =
+        liquidity_pool.total_shares.checked_add(shares).ok_or(SvmError::InvalidLiquidityAmount)?;
+    liquidity_pool.total_liquidity =
+        liquidity_pool.total_liquidity.checked_add(amount).ok_or(SvmError::InvalidLiquidityAmount)?;
+    let liquidity_position = &mut ctx.accounts.liquidity_position;
+    liquidity_position.shares =
+        liquidity_position.shares.checked_add(shares).or_overflow("liquidity position shares")?;
+
+    emit_cpi!(ProvidedLiquidity { provider: ctx.accounts.signer.key(), mint: ctx.accounts.mint.key(), amount, shares });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    /// Liquidity provider owning the liquidity position. Liquidity can be removed even when liquidity pools are
+    /// disabled.
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the pooled token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Writable liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint].
+    #[account(mut, seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    /// Writable liquidity position PDA of the signer, seeds ["liquidity_position", seed, mint, signer].
+    #[account(
+        mut,
+        seeds = [
+            b"liquidity_position",
+            state.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump
+    )]
+    pub liquidity_position: Account<'info, LiquidityPosition>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the removed liquidity.
+    #[account(
+        mut,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Writable token account for the mint receiving the removed liquidity, chosen by the provider.
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, shares: u64) -> Result<()> {
+    if shares > ctx.accounts.liquidity_position.shares {
+        return err!(SvmError::InsufficientLiquidityShares);
+    }
+
+    let amount = ctx.accounts.liquidity_pool.liquidity_for_shares(shares)?;
+
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    liquidity_pool.total_shares = liquidity_pool.total_shares.checked_sub(shares).or_overflow("total shares")?;
+    liquidity_pool.total_liquidity =
+        liquidity_pool.total_liquidity.checked_sub(amount).or_overflow("total liquidity")?;
+    let liquidity_position = &mut ctx.accounts.liquidity_position;
+    liquidity_position.shares =
+        liquidity_position.shares.checked_sub(shares).or_overflow("liquidity position shares")?;
+
+    // Fails while relayer refunds or slow fills drew the vault below the owed liquidity until the HubPool rebalances.
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+    transfer_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.destination.to_account_info(),
+        amount,
+        &vault_signer,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(RemovedLiquidity { provider: ctx.accounts.signer.key(), mint: ctx.accounts.mint.key(), amount, shares });
+
+    Ok(())
+}
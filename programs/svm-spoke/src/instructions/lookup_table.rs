@@ -0,0 +1,158 @@
+use anchor_lang::{prelude::*, solana_program::address_lookup_table};
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::{CreatedLookupTable, ExtendedLookupTable},
+    state::{LookupTableConfig, State},
+    utils::{create_state_lookup_table, extend_state_lookup_table, get_static_lookup_table_addresses},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(recent_slot: u64)]
+pub struct CreateLookupTable<'info> {
+    /// Owner of the spoke. Writable signer paying the rent of the lookup table and its config PDA.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only, signs as the table authority.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Lookup table config PDA, seeds ["lookup_table_config", seed]. Created once, so there is a single canonical
+    /// lookup table per spoke.
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + LookupTableConfig::INIT_SPACE,
+        seeds = [b"lookup_table_config", state.seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lookup_table_config: Account<'info, LookupTableConfig>,
+
+    /// CHECK: Lookup table created by the Address Lookup Table program, derived from the state and the recent slot.
+    #[account(
+        mut,
+        address = address_lookup_table::instruction::derive_lookup_table_address(&state.key(), recent_slot).0
+            @ SvmError::InvalidLookupTable
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: Address Lookup Table program.
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let lookup_table = ctx.accounts.lookup_table.to_account_info();
+    let signer = ctx.accounts.signer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let address_lookup_table_program = ctx.accounts.address_lookup_table_program.to_account_info();
+
+    create_state_lookup_table(
+        &lookup_table,
+        state,
+        ctx.bumps.state,
+        &signer,
+        &system_program,
+        &address_lookup_table_program,
+        recent_slot,
+    )?;
+
+    let lookup_table_config = &mut ctx.accounts.lookup_table_config;
+    lookup_table_config.lookup_table = lookup_table.key();
+    lookup_table_config.recent_slot = recent_slot;
+
+    emit_cpi!(CreatedLookupTable { lookup_table: lookup_table.key(), recent_slot });
+
+    let addresses = extend_state_lookup_table(
+        &lookup_table,
+        state,
+        ctx.bumps.state,
+        &signer,
+        &system_program,
+        &address_lookup_table_program,
+        &get_static_lookup_table_addresses(&state.key()),
+    )?;
+
+    emit_cpi!(ExtendedLookupTable { lookup_table: lookup_table.key(), addresses });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    /// Owner of the spoke. Writable signer paying the rent of the extended lookup table.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only, signs as the table authority.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Lookup table config PDA, seeds ["lookup_table_config", seed]. Read-only.
+    #[account(seeds = [b"lookup_table_config", state.seed.to_le_bytes().as_ref()], bump)]
+    pub lookup_table_config: Account<'info, LookupTableConfig>,
+
+    /// CHECK: Canonical lookup table of the spoke, as recorded in the lookup table config.
+    #[account(mut, address = lookup_table_config.lookup_table @ SvmError::InvalidLookupTable)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: Address Lookup Table program.
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    /// System program, used to transfer the additional rent of the lookup table.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+    let lookup_table = ctx.accounts.lookup_table.to_account_info();
+
+    let addresses = extend_state_lookup_table(
+        &lookup_table,
+        &ctx.accounts.state,
+        ctx.bumps.state,
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.address_lookup_table_program.to_account_info(),
+        &addresses,
+    )?;
+
+    emit_cpi!(ExtendedLookupTable { lookup_table: lookup_table.key(), addresses });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetLookupTable<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Lookup table config PDA, seeds ["lookup_table_config", seed]. Read-only.
+    #[account(seeds = [b"lookup_table_config", state.seed.to_le_bytes().as_ref()], bump)]
+    pub lookup_table_config: Account<'info, LookupTableConfig>,
+}
+
+pub fn get_lookup_table(ctx: Context<GetLookupTable>) -> Result<Pubkey> {
+    Ok(ctx.accounts.lookup_table_config.lookup_table)
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::AcrossPlusError,
+    event::{DeregisteredMessageSchema, RegisteredMessageSchema},
+    state::MessageSchema,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterMessageSchema<'info> {
+    /// Pays the rent for the message schema PDA if it is created. Writable signer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Only the recipient (typically a handler program PDA signing via CPI) can register its own message schema.
+    pub recipient: Signer<'info>,
+
+    /// Message schema PDA of the recipient, seeds ["message_schema", recipient]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + MessageSchema::INIT_SPACE,
+        seeds = [b"message_schema", recipient.key().as_ref()],
+        bump
+    )]
+    pub message_schema: Account<'info, MessageSchema>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_message_schema(
+    ctx: Context<RegisterMessageSchema>,
+    min_message_size: u32,
+    max_message_size: u32,
+    schema_tag: [u8; 4],
+) -> Result<()> {
+    // Schema tag occupies the first 4 bytes of the message, so the minimum size cannot be below it.
+    if min_message_size < schema_tag.len() as u32 || min_message_size > max_message_size {
+        return err!(AcrossPlusError::InvalidMessageSchema);
+    }
+
+    let message_schema = &mut ctx.accounts.message_schema;
+    message_schema.min_message_size = min_message_size;
+    message_schema.max_message_size = max_message_size;
+    message_schema.schema_tag = schema_tag;
+
+    emit_cpi!(RegisteredMessageSchema {
+        recipient: ctx.accounts.recipient.key(),
+        min_message_size,
+        max_message_size,
+        schema_tag,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeregisterMessageSchema<'info> {
+    /// Writable system account receiving the message schema rent. We do not restrict who receives lamports from closing
+    /// the schema as this is authorized by the recipient.
+    #[account(mut)]
+    pub closer: SystemAccount<'info>,
+
+    /// Recipient that registered the message schema.
+    pub recipient: Signer<'info>,
+
+    /// Message schema PDA being closed, seeds ["message_schema", recipient]. Writable.
+    #[account(mut, seeds = [b"message_schema", recipient.key().as_ref()], bump, close = closer)]
+    pub message_schema: Account<'info, MessageSchema>,
+}
+
+pub fn deregister_message_schema(ctx: Context<DeregisterMessageSchema>) -> Result<()> {
+    emit_cpi!(DeregisteredMessageSchema { recipient: ctx.accounts.recipient.key() });
+
+    Ok(())
+}
@@ -1,21 +1,87 @@
 mod admin;
+mod admin_nonce;
+mod allowlist;
 mod bundle;
+mod bundle_audit;
+mod bundle_summary;
+mod chain_alias;
+mod compliance;
+mod config_snapshot;
 mod create_token_accounts;
+mod create_vault;
+mod delivery;
 mod deposit;
+mod deposit_limit;
+mod depositor_nonce;
+mod error_counters;
+mod event_commitment;
+mod event_forwarder;
+mod event_replay;
+mod execution_approval;
 mod fill;
+mod fill_confirmation;
+mod fill_index;
+mod fill_volume;
+mod gc_account;
 mod handle_receive_message;
+mod health_check;
+mod instruction_metadata;
 mod instruction_params;
+mod liquidity_pool;
+mod lookup_table;
+mod message_schema;
+mod protocol_fees;
+mod rate_limit;
 mod refund_claims;
+mod relayer_stats;
+mod requester_activity;
 mod slow_fill;
+mod state_export;
+mod swap_adapter;
+mod test_utils;
 mod token_bridge;
+mod used_signature;
 
 pub use admin::*;
+pub use admin_nonce::*;
+pub use allowlist::*;
 pub use bundle::*;
+pub use bundle_audit::*;
+pub use bundle_summary::*;
+pub use chain_alias::*;
+pub use compliance::*;
+pub use config_snapshot::*;
 pub use create_token_accounts::*;
+pub use create_vault::*;
+pub use delivery::*;
 pub use deposit::*;
+pub use deposit_limit::*;
+pub use depositor_nonce::*;
+pub use error_counters::*;
+pub use event_commitment::*;
+pub use event_forwarder::*;
+pub use event_replay::*;
+pub use execution_approval::*;
 pub use fill::*;
+pub use fill_confirmation::*;
+pub use fill_index::*;
+pub use fill_volume::*;
+pub use gc_account::*;
 pub use handle_receive_message::*;
+pub use health_check::*;
+pub use instruction_metadata::*;
 pub use instruction_params::*;
+pub use liquidity_pool::*;
+pub use lookup_table::*;
+pub use message_schema::*;
+pub use protocol_fees::*;
+pub use rate_limit::*;
 pub use refund_claims::*;
+pub use relayer_stats::*;
+pub use requester_activity::*;
 pub use slow_fill::*;
+pub use state_export::*;
+pub use swap_adapter::*;
+pub use test_utils::*;
 pub use token_bridge::*;
+pub use used_signature::*;
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::MAX_TOTAL_FEE_BPS,
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{SetProtocolFee, WithdrewProtocolFees},
+    state::State,
+    utils::transfer_from,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolFeeState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_protocol_fee(
+    ctx: Context<SetProtocolFeeState>,
+    protocol_fee_bps: u16,
+    protocol_fee_recipient: Option<Pubkey>,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+
+    // Protocol and liquidity pool fees are skimmed from the same slow fill payouts.
+    if protocol_fee_bps as u64 + state.liquidity_pool_fee_bps as u64 > MAX_TOTAL_FEE_BPS {
+        return err!(SvmError::InvalidProtocolFee);
+    }
+
+    state.protocol_fee_bps = protocol_fee_bps;
+    state.protocol_fee_recipient = protocol_fee_recipient;
+
+    emit_cpi!(SetProtocolFee { protocol_fee_bps, protocol_fee_recipient });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    /// Anyone can initialize the fee vault of a mint. Writable signer paying the token account rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the fee vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the fee token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Fee vault token account PDA created for the mint, seeds ["fee_vault", seed, mint].
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"fee_vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    /// Protocol fee recipient configured in the state. The owner cannot withdraw fees on its behalf.
+    #[account(
+        constraint = state.protocol_fee_recipient == Some(signer.key()) @ SvmError::NotProtocolFeeRecipient
+    )]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the fee vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the fee token.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Writable fee vault token account of the mint, seeds ["fee_vault", seed, mint].
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"fee_vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Writable token account for the mint receiving the withdrawn fees, chosen by the fee recipient.
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    if amount > ctx.accounts.fee_vault.amount {
+        return err!(SvmError::InsufficientProtocolFees);
+    }
+
+    transfer_from(
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.destination,
+        amount,
+        &ctx.accounts.state,
+        ctx.bumps.state,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(WithdrewProtocolFees {
+        recipient: ctx.accounts.signer.key(),
+        mint: ctx.accounts.mint.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+
+    Ok(())
+}
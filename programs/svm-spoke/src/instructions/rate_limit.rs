@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    constraints::is_local_or_remote_owner,
+    error::SvmError,
+    event::{SetVaultOutflowRateLimit, SetVaultOutflowRateLimits},
+    state::{RateLimit, State},
+    utils::get_current_time,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVaultOutflowRateLimitsState<'info> {
+    /// Owner, or the self authority PDA when relayed from the HubPool through handle_receive_message.
+    #[account(constraint = is_local_or_remote_owner(&signer, &state) @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Writable to update its configuration.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_vault_outflow_rate_limits(ctx: Context<SetVaultOutflowRateLimitsState>, enabled: bool) -> Result<()> {
+    ctx.accounts.state.vault_outflow_rate_limits_enabled = enabled;
+
+    emit_cpi!(SetVaultOutflowRateLimits { enabled });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVaultOutflowRateLimitState<'info> {
+    /// Owner configuring the rate limit of the mint. Writable signer paying the rate limit PDA rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Mint of the rate limited vault.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + RateLimit::INIT_SPACE,
+        seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub rate_limit: Account<'info, RateLimit>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_vault_outflow_rate_limit(
+    ctx: Context<SetVaultOutflowRateLimitState>,
+    limit: u64,
+    window: u32,
+) -> Result<()> {
+    if window == 0 {
+        return err!(SvmError::InvalidRateLimitWindow);
+    }
+
+    // Window schedule starts when the rate limit is created. Updates keep the outflow already recorded in the current
+    // window, so raising the limit immediately lifts rejected outflows.
+    let rate_limit = &mut ctx.accounts.rate_limit;
+    if rate_limit.window == 0 {
+        rate_limit.window_start = get_current_time(&ctx.accounts.state)?;
+    }
+    rate_limit.limit = limit;
+    rate_limit.window = window;
+
+    emit_cpi!(SetVaultOutflowRateLimit { mint: ctx.accounts.mint.key(), limit, window });
+
+    Ok(())
+}
@@ -1,19 +1,28 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
 use crate::{
     constants::DISCRIMINATOR_SIZE,
     error::SvmError,
     event::ClaimedRelayerRefund,
-    state::{ClaimAccount, State},
+    state::{ClaimAccount, RateLimit, State},
+    utils::{
+        assert_closes_not_frozen, assert_lamport_destination, is_canonical_token_program, is_vault, pay_from_vault,
+        release_claim_liability, VaultSigner,
+    },
 };
 
 #[derive(Accounts)]
 #[instruction(mint: Pubkey, refund_address: Pubkey)]
 pub struct InitializeClaimAccount<'info> {
+    /// Anyone can initialize a claim account. Writable signer paying its rent and recorded as its initializer.
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Claim account PDA created for the mint and refund address, seeds ["claim_account", mint, refund_address].
     #[account(
         init,
         payer = signer,
@@ -23,12 +32,14 @@ pub struct InitializeClaimAccount<'info> {
     )]
     pub claim_account: Account<'info, ClaimAccount>,
 
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
 pub fn initialize_claim_account(ctx: Context<InitializeClaimAccount>) -> Result<()> {
     // Store the initializer so only it can receive lamports from closing the account upon claiming the refund.
     ctx.accounts.claim_account.initializer = ctx.accounts.signer.key();
+    ctx.accounts.claim_account.bump = ctx.bumps.claim_account;
 
     Ok(())
 }
@@ -36,65 +47,93 @@ pub fn initialize_claim_account(ctx: Context<InitializeClaimAccount>) -> Result<
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimRelayerRefund<'info> {
+    /// Relayer claiming its own refund.
     pub signer: Signer<'info>,
 
     /// CHECK: We don't need any additional checks as long as this is the same account that initialized the claim account.
     #[account(mut, address = claim_account.initializer @ SvmError::InvalidClaimInitializer)]
     pub initializer: UncheckedAccount<'info>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the refund.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = state,
-        associated_token::token_program = token_program
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
-    // Mint address has been checked when executing the relayer refund leaf and it is part of claim account derivation.
-    #[account(mint::token_program = token_program)]
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Mint address has been checked when executing the relayer refund leaf and it is part of claim account derivation.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
     pub mint: InterfaceAccount<'info, Mint>,
 
-    // This method allows relayer to claim refunds on any custom token account.
+    /// Writable token account for the mint receiving the refund. This method allows relayer to claim refunds on any
+    /// custom token account.
     #[account(mut, token::mint = mint, token::token_program = token_program)]
     pub token_account: InterfaceAccount<'info, TokenAccount>,
 
-    // Only relayer can claim the refund with this method as the claim account is derived from the relayer's address.
+    /// Only relayer can claim the refund with this method as the claim account is derived from the relayer's address.
+    /// Writable claim account PDA, seeds ["claim_account", mint, signer].
     #[account(
         mut,
         close = initializer,
         seeds = [b"claim_account", mint.key().as_ref(), signer.key().as_ref()],
-        bump
+        bump = claim_account.bump
     )]
     pub claim_account: Account<'info, ClaimAccount>,
 
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required when the state enables
+    /// vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Writable claim liability PDA of the mint, seeds ["claim_liability", seed, mint], released by the claimed
+    /// amount. Pass this program ID to represent None, which only overstates the refunds owed to claim accounts.
+    #[account(mut, seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub claim_liability: Option<UncheckedAccount<'info>>,
 }
 
 pub fn claim_relayer_refund(ctx: Context<ClaimRelayerRefund>) -> Result<()> {
+    // Claim account rent is returned to the initializer when closing the account.
+    assert_lamport_destination(&ctx.accounts.initializer)?;
+
     // Ensure the claim account holds a non-zero amount.
     let claim_amount = ctx.accounts.claim_account.amount;
     if claim_amount == 0 {
         return err!(SvmError::ZeroRefundClaim);
     }
 
-    // Derive the signer seeds for the state required for the transfer form vault.
-    let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
-    let seeds = &[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]];
-    let signer_seeds = &[&seeds[..]];
-
     // Transfer the claim amount from the vault to the relayer token account.
-    let transfer_accounts = TransferChecked {
-        from: ctx.accounts.vault.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.token_account.to_account_info(),
-        authority: ctx.accounts.state.to_account_info(),
-    };
-    let cpi_context =
-        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer_seeds);
-    transfer_checked(cpi_context, claim_amount, ctx.accounts.mint.decimals)?;
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+    pay_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.token_account.to_account_info(),
+        claim_amount,
+        &ctx.accounts.state,
+        &vault_signer,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.rate_limit,
+    )?;
+    release_claim_liability(&ctx.accounts.claim_liability, claim_amount)?;
 
     emit_cpi!(ClaimedRelayerRefund {
         l2_token_address: ctx.accounts.mint.key(),
@@ -109,93 +148,249 @@ pub fn claim_relayer_refund(ctx: Context<ClaimRelayerRefund>) -> Result<()> {
 #[derive(Accounts)]
 #[instruction(refund_address: Pubkey)]
 pub struct ClaimRelayerRefundFor<'info> {
+    /// Anyone can claim the refund on behalf of the refund address.
     pub signer: Signer<'info>,
 
     /// CHECK: We don't need any additional checks as long as this is the same account that initialized the claim account.
     #[account(mut, address = claim_account.initializer @ SvmError::InvalidClaimInitializer)]
     pub initializer: UncheckedAccount<'info>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the refund.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = state,
-        associated_token::token_program = token_program
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
-    // Mint address has been checked when executing the relayer refund leaf and it is part of claim account derivation.
-    #[account(mint::token_program = token_program)]
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Mint address has been checked when executing the relayer refund leaf and it is part of claim account derivation.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Writable ATA of the refund address for the mint receiving the refund.
     #[account(
         mut,
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
         associated_token::mint = mint,
         associated_token::authority = refund_address,
         associated_token::token_program = token_program
     )]
     pub token_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Writable claim account PDA, seeds ["claim_account", mint, refund_address].
     #[account(
         mut,
         close = initializer,
         seeds = [b"claim_account", mint.key().as_ref(), refund_address.as_ref()],
-        bump
+        bump = claim_account.bump
     )]
     pub claim_account: Account<'info, ClaimAccount>,
 
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required when the state enables
+    /// vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Writable claim liability PDA of the mint, seeds ["claim_liability", seed, mint], released by the claimed
+    /// amount. Pass this program ID to represent None, which only overstates the refunds owed to claim accounts.
+    #[account(mut, seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub claim_liability: Option<UncheckedAccount<'info>>,
 }
 
 pub fn claim_relayer_refund_for(ctx: Context<ClaimRelayerRefundFor>, refund_address: Pubkey) -> Result<()> {
+    // Claim account rent is returned to the initializer when closing the account.
+    assert_lamport_destination(&ctx.accounts.initializer)?;
+
     // Ensure the claim account holds a non-zero amount.
     let claim_amount = ctx.accounts.claim_account.amount;
     if claim_amount == 0 {
         return err!(SvmError::ZeroRefundClaim);
     }
 
-    // Derive the signer seeds for the state required for the transfer form vault.
-    let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
-    let seeds = &[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]];
-    let signer_seeds = &[&seeds[..]];
-
     // Transfer the claim amount from the vault to the relayer token account.
-    let transfer_accounts = TransferChecked {
-        from: ctx.accounts.vault.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.token_account.to_account_info(),
-        authority: ctx.accounts.state.to_account_info(),
-    };
-    let cpi_context =
-        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer_seeds);
-    transfer_checked(cpi_context, claim_amount, ctx.accounts.mint.decimals)?;
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+    pay_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.token_account.to_account_info(),
+        claim_amount,
+        &ctx.accounts.state,
+        &vault_signer,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.rate_limit,
+    )?;
+    release_claim_liability(&ctx.accounts.claim_liability, claim_amount)?;
 
     emit_cpi!(ClaimedRelayerRefund { l2_token_address: ctx.accounts.mint.key(), claim_amount, refund_address });
 
     Ok(()) // There is no need to reset the claim amount as the account will be closed at the end of instruction.
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimRefundFor<'info> {
+    /// Anyone can push the refund to the refund address. Writable signer paying the rent of its ATA when missing.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: We don't need any additional checks as long as this is the same account that initialized the claim account.
+    #[account(mut, address = claim_account.initializer @ SvmError::InvalidClaimInitializer)]
+    pub initializer: UncheckedAccount<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the refund.
+    #[account(
+        mut,
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Mint address has been checked when executing the relayer refund leaf and it is part of claim account derivation.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Refund address of the claim account, only used to derive the claim account and its ATA.
+    pub refund_address: UncheckedAccount<'info>,
+
+    /// Writable ATA of the refund address for the mint receiving the refund. Created if missing.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = refund_address,
+        associated_token::token_program = token_program
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Writable claim account PDA, seeds ["claim_account", mint, refund_address].
+    #[account(
+        mut,
+        close = initializer,
+        seeds = [b"claim_account", mint.key().as_ref(), refund_address.key().as_ref()],
+        bump = claim_account.bump
+    )]
+    pub claim_account: Account<'info, ClaimAccount>,
+
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required when the state enables
+    /// vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated token program, used to create the ATA of the refund address.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Writable claim liability PDA of the mint, seeds ["claim_liability", seed, mint], released by the claimed
+    /// amount. Pass this program ID to represent None, which only overstates the refunds owed to claim accounts.
+    #[account(mut, seeds = [b"claim_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub claim_liability: Option<UncheckedAccount<'info>>,
+}
+
+pub fn claim_refund_for(ctx: Context<ClaimRefundFor>) -> Result<()> {
+    // Claim account rent is returned to the initializer when closing the account.
+    assert_lamport_destination(&ctx.accounts.initializer)?;
+
+    // Ensure the claim account holds a non-zero amount.
+    let claim_amount = ctx.accounts.claim_account.amount;
+    if claim_amount == 0 {
+        return err!(SvmError::ZeroRefundClaim);
+    }
+
+    // Transfer the claim amount from the vault to the ATA of the refund address.
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+    pay_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.token_account.to_account_info(),
+        claim_amount,
+        &ctx.accounts.state,
+        &vault_signer,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.rate_limit,
+    )?;
+    release_claim_liability(&ctx.accounts.claim_liability, claim_amount)?;
+
+    emit_cpi!(ClaimedRelayerRefund {
+        l2_token_address: ctx.accounts.mint.key(),
+        claim_amount,
+        refund_address: ctx.accounts.refund_address.key(),
+    });
+
+    Ok(()) // There is no need to reset the claim amount as the account will be closed at the end of instruction.
+}
+
 // Though claim accounts are being closed automatically when claiming the refund, there might be a scenario where
 // relayer refunds were executed with ATA after initializing the claim account. In such cases, the initializer should be
 // able to close the claim account manually.
 #[derive(Accounts)]
 #[instruction(mint: Pubkey, refund_address: Pubkey)]
 pub struct CloseClaimAccount<'info> {
+    /// Initializer of the claim account. Writable signer receiving its rent.
     #[account(mut, address = claim_account.initializer @ SvmError::InvalidClaimInitializer)]
     pub signer: Signer<'info>,
 
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Claim account PDA being closed, seeds ["claim_account", mint, refund_address]. Writable.
     #[account(
         mut,
         close = signer,
         seeds = [b"claim_account", mint.key().as_ref(), refund_address.key().as_ref()],
-        bump
+        bump = claim_account.bump
     )]
     pub claim_account: Account<'info, ClaimAccount>,
 }
 
 pub fn close_claim_account(ctx: Context<CloseClaimAccount>) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.signer)?;
+    assert_closes_not_frozen(&ctx.accounts.state)?;
+
     // Ensure the account does not hold any outstanding claims.
     let claim_amount = ctx.accounts.claim_account.amount;
     if claim_amount > 0 {
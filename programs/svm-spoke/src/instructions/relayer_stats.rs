@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::SvmError,
+    state::{RelayerStats, State},
+};
+
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct GetRelayerStats<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Relayer stats PDA of the relayer, seeds ["relayer_stats", seed, relayer]. Read-only.
+    #[account(seeds = [b"relayer_stats", state.seed.to_le_bytes().as_ref(), relayer.as_ref()], bump)]
+    pub relayer_stats: Account<'info, RelayerStats>,
+}
+
+pub fn get_relayer_stats(ctx: Context<GetRelayerStats>) -> Result<RelayerStats> {
+    let relayer_stats = &ctx.accounts.relayer_stats;
+
+    Ok(RelayerStats {
+        relayer: relayer_stats.relayer,
+        fills_in_window: relayer_stats.fills_in_window,
+        lapses: relayer_stats.lapses,
+    })
+}
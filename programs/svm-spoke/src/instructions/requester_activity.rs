@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    state::{RequesterActivity, State},
+    utils::{assert_lamport_destination, get_current_time},
+};
+
+#[derive(Accounts)]
+pub struct InitializeRequesterActivity<'info> {
+    /// Slow fill requester. Writable signer paying the requester activity PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Requester activity PDA created for the signer, seeds ["requester_activity", seed, signer].
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + RequesterActivity::INIT_SPACE,
+        seeds = [b"requester_activity", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub requester_activity: Account<'info, RequesterActivity>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRequesterActivity<'info> {
+    /// Slow fill requester. Writable signer receiving the requester activity PDA rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Requester activity PDA being closed, seeds ["requester_activity", seed, signer]. Writable.
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"requester_activity", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub requester_activity: Account<'info, RequesterActivity>,
+}
+
+pub fn close_requester_activity(ctx: Context<CloseRequesterActivity>) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.signer)?;
+
+    // Closing the account resets the request count, so only allow it once the current window has passed.
+    let state = &ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+    let window_end = ctx.accounts.requester_activity.window_start.saturating_add(state.slow_fill_rate_window);
+    if current_time < window_end {
+        return err!(SvmError::RequesterActivityStillActive);
+    }
+
+    Ok(())
+}
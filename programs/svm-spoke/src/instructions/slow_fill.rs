@@ -1,37 +1,73 @@
-use anchor_lang::{prelude::*, solana_program::keccak};
-use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::{keccak, sysvar},
+};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 
-use crate::event::{FillType, FilledV3Relay, RequestedV3SlowFill, V3RelayExecutionEventInfo};
+use crate::event::{
+    AccruedLiquidityPoolFees, AccruedProtocolFees, FillType, FilledV3Relay, PauseExpired, RequestedV3SlowFill,
+    RequestedV3SlowFill2, RequestedV3SlowFillsBatch, V3RelayExecutionEventInfo,
+};
 use crate::{
     common::V3RelayData,
-    constants::DISCRIMINATOR_SIZE,
-    constraints::is_relay_hash_valid,
+    constants::{
+        ALLOWLIST_ROLE_EXECUTOR, DISCRIMINATOR_SIZE, MAX_SLOW_FILL_REQUESTS_PER_BATCH, MAX_SLOW_RELAY_LEAVES_PER_BATCH,
+        MEMO_PROGRAM_ID, SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE,
+    },
+    constraints::{
+        are_fills_paused, check_destination_chain_id, is_empty_relay, is_relay_hash_valid, validate_relay_data,
+    },
     error::{CommonError, SvmError},
-    state::{ExecuteV3SlowRelayLeafParams, FillStatus, FillStatusAccount, RequestV3SlowFillParams, RootBundle, State},
-    utils::{get_current_time, hash_non_empty_message, invoke_handler, verify_merkle_proof},
+    relay_admission::{self, get_slow_fill_request_time, CallerRole},
+    state::{
+        AllowlistEntry, ChainAlias, DeliveryMode, DeliveryPreference, EventCommitment, ExecuteV3SlowRelayLeafParams,
+        ExecuteV3SlowRelayLeavesParams, ExecutionApproval, FillIndexShard, FillStatus, FillStatusAccount,
+        LiquidityPool, RateLimit, RelayerStats, RequestV3SlowFillParams, RequestV3SlowFillsBatchParams,
+        RequesterActivity, RequesterNonce, RootBundle, Route, SettlementMode, SlowFillLiability,
+        SlowRelayLeafExecution, State, UsedSignature, VaultLiability,
+    },
+    utils::{
+        calculate_bps_fee, check_allowlist_role, check_compliance, check_origin_chain_id, check_slow_fill_requester,
+        check_vault, commit_event, create_pda_account, get_canonical_ata, get_capped_fee_bps, get_current_time,
+        get_fill_index_bucket, get_payout_destination, get_transfer_fee_inclusive_amount, get_used_signature_key,
+        get_v3_relay_hash, hash_non_empty_message, hash_payout_memo, invoke_handler, invoke_payout_memo,
+        is_canonical_token_program, is_native_delivery, is_vault, normalize_output_amount, parse_extra_args,
+        pay_from_vault, record_fill_index, record_relayer_outcome, record_slow_fill_liability,
+        release_slow_fill_liability, seed_encode_struct, transfer_from_vault, unwrap_native_payout,
+        validate_message_schema, validate_payout_memo, verify_and_consume_signature, verify_execution_approval,
+        verify_merkle_proof, ExtraArgs, OrArithmeticOverflow, VaultSigner,
+    },
 };
 
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(relay_hash: [u8; 32], relay_data: Option<V3RelayData>)]
 pub struct RequestV3SlowFill<'info> {
+    /// Slow fill requester, typically the relayer or the recipient, recorded as the requester and relayer of the fill
+    /// status PDA. Writable signer.
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    // This is required as fallback when None instruction params are passed in arguments.
+    /// This is required as fallback when None instruction params are passed in arguments. Seeds ["instruction_params",
+    /// signer], closed to the signer afterwards. Pass this program ID to represent None.
     #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump, close = signer)]
     pub instruction_params: Option<Account<'info, RequestV3SlowFillParams>>,
 
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Fills must not be paused and the spoke must
+    /// not be winding down.
     #[account(
-        seeds = [b"state", state.seed.to_le_bytes().as_ref()],
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
         bump,
-        constraint = !state.paused_fills @ CommonError::FillsArePaused
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
     )]
     pub state: Account<'info, State>,
 
+    /// Fill status PDA of the relay, seeds ["fills", relay_hash]. Created if missing and writable.
     #[account(
         init_if_needed,
-        payer = signer,
+        payer = payer,
         space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
         seeds = [b"fills", relay_hash.as_ref()],
         bump,
@@ -41,10 +77,174 @@ pub struct RequestV3SlowFill<'info> {
             &state) @ SvmError::InvalidRelayHash
     )]
     pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// Slow fill liability PDA of the output token, seeds ["slow_fill_liability", seed, output_token]. Created if
+    /// missing and writable, reserving the output amount of the requested slow fill in the vault.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + SlowFillLiability::INIT_SPACE,
+        seeds = [
+            b"slow_fill_liability",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .output_token
+                .as_ref()
+        ],
+        bump
+    )]
+    pub slow_fill_liability: Account<'info, SlowFillLiability>,
+
+    /// CHECK: Schema PDA of the recipient, seeds ["message_schema", recipient]. Non-empty messages are validated against
+    /// it whenever the recipient registered a schema.
+    #[account(
+        seeds = [
+            b"message_schema",
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .recipient
+                .as_ref()
+        ],
+        bump
+    )]
+    pub message_schema: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry of the relay depositor, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    #[account(
+        seeds = [
+            b"compliance",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .depositor
+                .as_ref()
+        ],
+        bump
+    )]
+    pub depositor_compliance: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry of the relay recipient, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    #[account(
+        seeds = [
+            b"compliance",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .recipient
+                .as_ref()
+        ],
+        bump
+    )]
+    pub recipient_compliance: Option<UncheckedAccount<'info>>,
+
+    /// Allowlist entry of the signer, seeds ["allowlist", seed, signer]. Only required in permissioned mode for
+    /// signers holding the requester role, or the relayer role with the strict requester check. Pass this program ID
+    /// to represent None.
+    #[account(seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()], bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// Optional per-signer request counter, only required when the state enables slow fill rate limits. Pass this
+    /// program ID to represent None. Seeds ["requester_activity", seed, signer].
+    #[account(
+        mut,
+        seeds = [b"requester_activity", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub requester_activity: Option<Account<'info, RequesterActivity>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// Fill index shard of the deposit id, seeds ["fill_index", seed, origin_chain_id, deposit_id / 1024]. Only
+    /// required when the state enables the fill index and the deposit id fits into u64. Pass this program ID to
+    /// represent None. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + FillIndexShard::INIT_SPACE,
+        seeds = [
+            b"fill_index",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .origin_chain_id
+                .to_le_bytes()
+                .as_ref(),
+            get_fill_index_bucket(
+                &relay_data
+                    .clone()
+                    .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                    .deposit_id
+            )
+            .to_le_bytes()
+            .as_ref()
+        ],
+        bump
+    )]
+    pub fill_index_shard: Option<Account<'info, FillIndexShard>>,
+
+    /// Chain alias of the relay origin chain ID, seeds ["chain_alias", seed, origin_chain_id]. Required in strict chain
+    /// alias mode and rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent
+    /// None.
+    #[account(
+        seeds = [
+            b"chain_alias",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .origin_chain_id
+                .to_le_bytes()
+                .as_ref(),
+        ],
+        bump
+    )]
+    pub origin_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// Optional stats of the exclusive relayer, only passed when the relay names one. Pass this program ID to
+    /// represent None. Seeds ["relayer_stats", seed, exclusive_relayer]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR_SIZE + RelayerStats::INIT_SPACE,
+        seeds = [
+            b"relayer_stats",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().relay_data.clone())
+                .exclusive_relayer
+                .as_ref()
+        ],
+        bump
+    )]
+    pub relayer_stats: Option<Account<'info, RelayerStats>>,
+
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
+
+    /// Pays the rent for the fill status PDA and the other accounts created by the request, which it reclaims when
+    /// they are closed. Writable signer, passed as the same account as the signer unless a sponsor funds the requester.
+    /// A breaking addition to the account list, same as in FillV3Relay.
+    #[account(mut)]
+    pub payer: Signer<'info>,
 }
 
-pub fn request_v3_slow_fill(ctx: Context<RequestV3SlowFill>, relay_data: Option<V3RelayData>) -> Result<()> {
+pub fn request_v3_slow_fill(
+    ctx: Context<RequestV3SlowFill>,
+    relay_hash: [u8; 32],
+    relay_data: Option<V3RelayData>,
+) -> Result<()> {
     let RequestV3SlowFillParams { relay_data } =
         unwrap_request_v3_slow_fill_params(relay_data, &ctx.accounts.instruction_params);
 
@@ -52,28 +252,486 @@ pub fn request_v3_slow_fill(ctx: Context<RequestV3SlowFill>, relay_data: Option<
 
     let current_time = get_current_time(state)?;
 
-    // Check if the fill is past the exclusivity window & within the fill deadline.
-    if relay_data.exclusivity_deadline >= current_time {
-        return err!(CommonError::NoSlowFillsInExclusivityWindow);
+    validate_relay_data(&relay_data, state, current_time)?;
+    check_origin_chain_id(state, &ctx.accounts.origin_chain_alias, relay_data.origin_chain_id)?;
+
+    let fill_status = &ctx.accounts.fill_status.status;
+    let caller_role = CallerRole::SlowFillRequester(ctx.accounts.signer.key());
+    relay_admission::check(&relay_data, fill_status, state, current_time, caller_role)?;
+
+    // Being admitted with the flag still set means that the pause has expired.
+    if state.paused_fills {
+        emit_cpi!(PauseExpired { expires_at: state.fills_pause_expires_at });
     }
-    if relay_data.fill_deadline < current_time {
-        return err!(CommonError::ExpiredFillDeadline);
+
+    validate_message_schema(&relay_data.message, &ctx.accounts.message_schema)?;
+
+    check_compliance(state, &[&ctx.accounts.depositor_compliance, &ctx.accounts.recipient_compliance])?;
+
+    let signer = ctx.accounts.signer.key();
+    check_slow_fill_requester(state, &ctx.accounts.allowlist_entry, &signer, &relay_data)?;
+
+    check_slow_fill_rate_limit(state, &mut ctx.accounts.requester_activity, current_time)?;
+
+    let payer = ctx.accounts.payer.key();
+    record_slow_fill_request(&mut ctx.accounts.fill_status, &relay_data, signer, signer, payer);
+
+    record_fill_index(state, &mut ctx.accounts.fill_index_shard, &relay_data)?;
+
+    let slow_fill_liability = &mut ctx.accounts.slow_fill_liability;
+    emit_cpi!(record_slow_fill_liability(slow_fill_liability, relay_data.output_token, relay_data.output_amount)?);
+
+    // Slow fills are only requested after the exclusivity window or by the exclusive relayer itself instead of filling,
+    // so the deposit lapsed for its exclusive relayer either way.
+    if let Some(crossed) =
+        record_relayer_outcome(state, &mut ctx.accounts.relayer_stats, &relay_data.exclusive_relayer, true)
+    {
+        emit_cpi!(crossed);
     }
 
-    // Check the fill status is unfilled.
-    let fill_status_account = &mut ctx.accounts.fill_status;
-    if fill_status_account.status != FillStatus::Unfilled {
-        return err!(CommonError::InvalidSlowFillRequest);
+    let requested_slow_fill = requested_v3_slow_fill_event(&relay_data, relay_hash, current_time);
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, requested_slow_fill)?);
+    let requested_slow_fill = requested_v3_slow_fill_2_event(&relay_data, relay_hash, state.chain_id, signer);
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, requested_slow_fill)?);
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32], relay_data: V3RelayData, nonce: u64, requester_signature: [u8; 64])]
+pub struct RequestV3SlowFillFor<'info> {
+    /// Any payer can submit the request signed by the recipient. Signer pays the rent and can close the fill_status
+    /// PDA.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Fills must not be paused and the spoke must
+    /// not be winding down.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill status PDA of the relay, seeds ["fills", relay_hash]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
+        seeds = [b"fills", relay_hash.as_ref()],
+        bump,
+        constraint = is_relay_hash_valid(&relay_hash, &relay_data, &state) @ SvmError::InvalidRelayHash
+    )]
+    pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// Slow fill liability PDA of the output token, seeds ["slow_fill_liability", seed, output_token]. Created if
+    /// missing and writable, reserving the output amount of the requested slow fill in the vault.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + SlowFillLiability::INIT_SPACE,
+        seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), relay_data.output_token.as_ref()],
+        bump
+    )]
+    pub slow_fill_liability: Account<'info, SlowFillLiability>,
+
+    /// Tracks signed request nonces of the recipient so that the same payload cannot be replayed even if the
+    /// fill_status PDA gets closed and recreated. Seeds ["requester_nonce", recipient], created if missing.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + RequesterNonce::INIT_SPACE,
+        seeds = [b"requester_nonce", relay_data.recipient.as_ref()],
+        bump
+    )]
+    pub requester_nonce: Account<'info, RequesterNonce>,
+
+    /// Used signature PDA of the requester signature rejecting replays of the signed request, seeds ["used_signature",
+    /// seed, keccak(requester_signature)[..16]]. Created on first use and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + UsedSignature::INIT_SPACE,
+        seeds = [
+            b"used_signature",
+            state.seed.to_le_bytes().as_ref(),
+            get_used_signature_key(&requester_signature).as_ref()
+        ],
+        bump
+    )]
+    pub used_signature: Account<'info, UsedSignature>,
+
+    /// CHECK: Schema PDA of the recipient, seeds ["message_schema", recipient]. Non-empty messages are validated against
+    /// it whenever the recipient registered a schema.
+    #[account(seeds = [b"message_schema", relay_data.recipient.as_ref()], bump)]
+    pub message_schema: UncheckedAccount<'info>,
+
+    /// CHECK: Blocklist entry of the relay depositor, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    #[account(seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), relay_data.depositor.as_ref()], bump)]
+    pub depositor_compliance: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry of the relay recipient, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    #[account(seeds = [b"compliance", state.seed.to_le_bytes().as_ref(), relay_data.recipient.as_ref()], bump)]
+    pub recipient_compliance: Option<UncheckedAccount<'info>>,
+
+    /// Optional per-signer request counter, only required when the state enables slow fill rate limits. Pass this
+    /// program ID to represent None. Seeds ["requester_activity", seed, signer].
+    #[account(
+        mut,
+        seeds = [b"requester_activity", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub requester_activity: Option<Account<'info, RequesterActivity>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// Fill index shard of the deposit id, seeds ["fill_index", seed, origin_chain_id, deposit_id / 1024]. Only
+    /// required when the state enables the fill index and the deposit id fits into u64. Pass this program ID to
+    /// represent None. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + FillIndexShard::INIT_SPACE,
+        seeds = [
+            b"fill_index",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data.origin_chain_id.to_le_bytes().as_ref(),
+            get_fill_index_bucket(&relay_data.deposit_id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub fill_index_shard: Option<Account<'info, FillIndexShard>>,
+
+    /// Chain alias of the relay origin chain ID, seeds ["chain_alias", seed, origin_chain_id]. Required in strict chain
+    /// alias mode and rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent
+    /// None.
+    #[account(
+        seeds = [
+            b"chain_alias",
+            state.seed.to_le_bytes().as_ref(),
+            relay_data.origin_chain_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub origin_chain_alias: Option<Account<'info, ChainAlias>>,
+
+    /// CHECK: Instructions sysvar, used to load the Ed25519 signature verification instruction.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn request_v3_slow_fill_for(
+    ctx: Context<RequestV3SlowFillFor>,
+    relay_hash: [u8; 32],
+    relay_data: V3RelayData,
+    nonce: u64,
+    requester_signature: [u8; 64],
+) -> Result<()> {
+    // Signed requests have the recipient as requester, which has no allowlist role to check in permissioned mode.
+    if ctx.accounts.state.permissioned_mode {
+        return err!(SvmError::SlowFillRequesterNotAllowed);
+    }
+
+    let current_time = get_current_time(&ctx.accounts.state)?;
+
+    validate_relay_data(&relay_data, &ctx.accounts.state, current_time)?;
+    check_origin_chain_id(&ctx.accounts.state, &ctx.accounts.origin_chain_alias, relay_data.origin_chain_id)?;
+
+    // The recipient signing the request is the requester, not the submitting signer.
+    let fill_status = &ctx.accounts.fill_status.status;
+    let caller_role = CallerRole::SlowFillRequester(relay_data.recipient);
+    relay_admission::check(&relay_data, fill_status, &ctx.accounts.state, current_time, caller_role)?;
+
+    // Recipient signs the relay hash followed by the little-endian encoded nonce. The signed request can't be used for
+    // a slow fill after the fill deadline, so its signature expires then.
+    let mut payload = Vec::with_capacity(relay_hash.len() + 8);
+    payload.extend_from_slice(&relay_hash);
+    payload.extend_from_slice(&nonce.to_le_bytes());
+    verify_and_consume_signature(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &relay_data.recipient,
+        &payload,
+        &requester_signature,
+        SvmError::InvalidRequesterSignature,
+        &mut ctx.accounts.used_signature,
+        relay_data.fill_deadline,
+        current_time,
+        ctx.accounts.signer.key,
+    )?;
+
+    let requester_nonce = &mut ctx.accounts.requester_nonce;
+    if nonce < requester_nonce.next_nonce {
+        return err!(SvmError::InvalidRequesterNonce);
+    }
+    requester_nonce.next_nonce = nonce.checked_add(1).ok_or(SvmError::InvalidRequesterNonce)?;
+
+    if ctx.accounts.state.paused_fills {
+        emit_cpi!(PauseExpired { expires_at: ctx.accounts.state.fills_pause_expires_at });
+    }
+
+    validate_message_schema(&relay_data.message, &ctx.accounts.message_schema)?;
+
+    check_compliance(&ctx.accounts.state, &[&ctx.accounts.depositor_compliance, &ctx.accounts.recipient_compliance])?;
+
+    check_slow_fill_rate_limit(&ctx.accounts.state, &mut ctx.accounts.requester_activity, current_time)?;
+
+    record_slow_fill_request(
+        &mut ctx.accounts.fill_status,
+        &relay_data,
+        ctx.accounts.signer.key(),
+        relay_data.recipient,
+        ctx.accounts.signer.key(),
+    );
+
+    record_fill_index(&ctx.accounts.state, &mut ctx.accounts.fill_index_shard, &relay_data)?;
+
+    let slow_fill_liability = &mut ctx.accounts.slow_fill_liability;
+    emit_cpi!(record_slow_fill_liability(slow_fill_liability, relay_data.output_token, relay_data.output_amount)?);
+
+    let state = &ctx.accounts.state;
+    let requested_slow_fill = requested_v3_slow_fill_event(&relay_data, relay_hash, current_time);
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, requested_slow_fill)?);
+    let requested_slow_fill =
+        requested_v3_slow_fill_2_event(&relay_data, relay_hash, state.chain_id, relay_data.recipient);
+    emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, requested_slow_fill)?);
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(output_token: Pubkey)]
+pub struct RequestV3SlowFillsBatch<'info> {
+    /// Keeper requesting the slow fills. Writable signer paying the rent of the created fill status PDAs.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// This is required as fallback when None relays are passed in arguments. Seeds ["instruction_params", signer],
+    /// closed to the signer afterwards. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump, close = signer)]
+    pub instruction_params: Option<Account<'info, RequestV3SlowFillsBatchParams>>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Fills must not be paused and the spoke must
+    /// not be winding down.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = !state.winding_down @ SvmError::WindingDown,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Slow fill liability PDA of the output token, seeds ["slow_fill_liability", seed, output_token]. Created if
+    /// missing and writable, reserving the output amounts of all requested slow fills in the vault.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + SlowFillLiability::INIT_SPACE,
+        seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), output_token.as_ref()],
+        bump
+    )]
+    pub slow_fill_liability: Account<'info, SlowFillLiability>,
+
+    /// Allowlist entry of the signer, seeds ["allowlist", seed, signer]. Only required in permissioned mode for
+    /// signers holding the requester role, or the relayer role with the strict requester check. Pass this program ID
+    /// to represent None.
+    #[account(seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()], bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// Optional per-signer request counter, only required when the state enables slow fill rate limits. Pass this
+    /// program ID to represent None. Seeds ["requester_activity", seed, signer].
+    #[account(
+        mut,
+        seeds = [b"requester_activity", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub requester_activity: Option<Account<'info, RequesterActivity>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// Requests slow fills of relays with the same output token as request_v3_slow_fill would, creating their fill status
+// PDAs passed in remaining accounts in order. Relays that are already filled, requested or expired are skipped rather
+// than reverting the batch, as keepers can't know whether a relayer filled them in the meantime. Any other failing
+// relay reverts the whole batch. Optional accounts of the single request other than the allowlist entry and requester
+// activity are not passed, so relays needing compliance attestations, chain aliases or fill index shards fail as if
+// they were missing, and relayer stats are not updated.
+pub fn request_v3_slow_fills_batch<'c, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RequestV3SlowFillsBatch<'info>>,
+    output_token: Pubkey,
+    relays: Option<Vec<V3RelayData>>,
+) -> Result<()>
+where
+    'c: 'info,
+{
+    let relays = match relays {
+        Some(relays) => relays,
+        None => ctx.accounts.instruction_params.as_ref().ok_or(ErrorCode::ConstraintAccountIsNone)?.relays.clone(),
+    };
+    if relays.is_empty() || relays.len() > MAX_SLOW_FILL_REQUESTS_PER_BATCH {
+        return err!(SvmError::InvalidSlowFillRequestCount);
+    }
+    if ctx.remaining_accounts.len() != relays.len() {
+        return err!(SvmError::InvalidSlowFillRequestAccounts);
+    }
+
+    let state = &ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+    let signer = ctx.accounts.signer.key();
+
+    let (mut requested, mut skipped, mut requested_amount) = (0u32, 0u32, 0u64);
+    for (i, (relay_data, fill_status_info)) in relays.into_iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+        if relay_data.output_token != output_token {
+            return err!(SvmError::InvalidMint);
+        }
+        let relay_hash = get_v3_relay_hash(&relay_data, state.chain_id);
+        let bump = check_remaining_pda(fill_status_info, &[b"fills", relay_hash.as_ref()], i)?;
+
+        // Checked before validating the relay data, so that stale relays filled long ago are skipped as well.
+        let created = fill_status_info.data_is_empty();
+        if !created {
+            let status = FillStatusAccount::try_deserialize_any_layout(&fill_status_info.try_borrow_data()?)?.status;
+            if status != FillStatus::Unfilled {
+                skipped += 1;
+                continue;
+            }
+        }
+
+        validate_relay_data(&relay_data, state, current_time)?;
+        check_origin_chain_id(state, &None, relay_data.origin_chain_id)?;
+        let caller_role = CallerRole::SlowFillRequester(signer);
+        relay_admission::check(&relay_data, &FillStatus::Unfilled, state, current_time, caller_role)?;
+        check_compliance(state, &[&None, &None])?;
+        check_slow_fill_requester(state, &ctx.accounts.allowlist_entry, &signer, &relay_data)?;
+        check_slow_fill_rate_limit(state, &mut ctx.accounts.requester_activity, current_time)?;
+
+        let mut fill_status = match created {
+            true => {
+                let bump_seed = [bump];
+                let signer_seeds: &[&[u8]] = &[b"fills", relay_hash.as_ref(), &bump_seed];
+                create_pda_account(
+                    &ctx.accounts.signer.to_account_info(),
+                    fill_status_info,
+                    &ctx.accounts.system_program.to_account_info(),
+                    DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
+                    signer_seeds,
+                )?;
+                // Zeroed data of the created PDA decodes as an unfilled status, written with its discriminator on exit.
+                Account::<FillStatusAccount>::try_from_unchecked(fill_status_info)?
+            }
+            false => Account::<FillStatusAccount>::try_from(fill_status_info)?,
+        };
+        record_slow_fill_request(&mut fill_status, &relay_data, signer, signer, signer);
+        fill_status.exit(&crate::ID)?;
+
+        record_fill_index(state, &mut None, &relay_data)?;
+
+        let requested_slow_fill = requested_v3_slow_fill_event(&relay_data, relay_hash, current_time);
+        emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, requested_slow_fill)?);
+        let requested_slow_fill = requested_v3_slow_fill_2_event(&relay_data, relay_hash, state.chain_id, signer);
+        emit_cpi!(commit_event(state, &mut ctx.accounts.event_commitment, requested_slow_fill)?);
+
+        requested += 1;
+        requested_amount = requested_amount.checked_add(relay_data.output_amount).or_overflow("requested amount")?;
+    }
+
+    if requested > 0 {
+        // Being admitted with the flag still set means that the pause has expired.
+        if state.paused_fills {
+            emit_cpi!(PauseExpired { expires_at: state.fills_pause_expires_at });
+        }
+
+        let slow_fill_liability = &mut ctx.accounts.slow_fill_liability;
+        emit_cpi!(record_slow_fill_liability(slow_fill_liability, output_token, requested_amount)?);
+    }
+
+    emit_cpi!(RequestedV3SlowFillsBatch { requester: signer, requested, skipped });
+
+    Ok(())
+}
+
+// Counts the request against the signer's rate limit window when slow fill rate limits are enabled.
+fn check_slow_fill_rate_limit(
+    state: &State,
+    requester_activity: &mut Option<Account<RequesterActivity>>,
+    current_time: u32,
+) -> Result<()> {
+    if !state.slow_fill_rate_limit_enabled {
+        return Ok(());
     }
 
+    let requester_activity = requester_activity.as_mut().ok_or(SvmError::MissingRequesterActivity)?;
+    requester_activity.record_request(current_time, state.slow_fill_rate_window, state.slow_fill_rate_limit)
+}
+
+// Records the slow fill request admitted by relay_admission::check in the fill status PDA.
+fn record_slow_fill_request(
+    fill_status_account: &mut FillStatusAccount,
+    relay_data: &V3RelayData,
+    relayer: Pubkey,
+    requester: Pubkey,
+    payer: Pubkey,
+) {
     fill_status_account.status = FillStatus::RequestedSlowFill; // Update the fill status to RequestedSlowFill
-    fill_status_account.relayer = ctx.accounts.signer.key();
+    fill_status_account.relayer = relayer;
     fill_status_account.fill_deadline = relay_data.fill_deadline;
+    fill_status_account.requester = requester;
+    fill_status_account.record_payer(payer);
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayTiming {
+    pub exclusivity_deadline: u32,   // Exclusive relayer can fill until and including this time.
+    pub slow_fill_request_time: u32, // Slow fills can be requested from this time, after the state grace period.
+    pub fill_deadline: u32,          // Neither fills nor slow fill requests are accepted after this time.
+}
+
+#[derive(Accounts)]
+pub struct GetRelayTiming<'info> {
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn get_relay_timing(ctx: Context<GetRelayTiming>, relay_data: V3RelayData) -> Result<RelayTiming> {
+    Ok(RelayTiming {
+        exclusivity_deadline: relay_data.exclusivity_deadline,
+        slow_fill_request_time: get_slow_fill_request_time(&ctx.accounts.state, &relay_data),
+        fill_deadline: relay_data.fill_deadline,
+    })
+}
 
-    // Emit the RequestedV3SlowFill event. Empty message is not hashed and emits zeroed bytes32 for easier observability
+// Requests are rejected after the fill deadline, so the time left is only clamped to guard against underflow.
+fn requested_v3_slow_fill_event(
+    relay_data: &V3RelayData,
+    relay_hash: [u8; 32],
+    current_time: u32,
+) -> RequestedV3SlowFill {
+    // Empty message is not hashed and emits zeroed bytes32 for easier observability
     let message_hash = hash_non_empty_message(&relay_data.message);
 
-    emit_cpi!(RequestedV3SlowFill {
+    RequestedV3SlowFill {
         input_token: relay_data.input_token,
         output_token: relay_data.output_token,
         input_amount: relay_data.input_amount,
@@ -86,9 +744,37 @@ pub fn request_v3_slow_fill(ctx: Context<RequestV3SlowFill>, relay_data: Option<
         depositor: relay_data.depositor,
         recipient: relay_data.recipient,
         message_hash,
-    });
+        relay_hash,
+        current_time,
+        seconds_until_fill_deadline: relay_data.fill_deadline.saturating_sub(current_time),
+    }
+}
 
-    Ok(())
+// The relay hash is the one validated against the fill status PDA seeds, so it is not hashed again.
+fn requested_v3_slow_fill_2_event(
+    relay_data: &V3RelayData,
+    relay_hash: [u8; 32],
+    destination_chain_id: u64,
+    requester: Pubkey,
+) -> RequestedV3SlowFill2 {
+    RequestedV3SlowFill2 {
+        input_token: relay_data.input_token,
+        output_token: relay_data.output_token,
+        input_amount: relay_data.input_amount,
+        output_amount: relay_data.output_amount,
+        origin_chain_id: relay_data.origin_chain_id,
+        deposit_id: relay_data.deposit_id,
+        fill_deadline: relay_data.fill_deadline,
+        exclusivity_deadline: relay_data.exclusivity_deadline,
+        exclusive_relayer: relay_data.exclusive_relayer,
+        depositor: relay_data.depositor,
+        recipient: relay_data.recipient,
+        message_hash: hash_non_empty_message(&relay_data.message),
+        destination_chain_id,
+        requester,
+        relay_hash,
+        message_only: relay_data.output_amount == 0,
+    }
 }
 
 // Helper to unwrap optional instruction params with fallback loading from buffer account.
@@ -106,12 +792,22 @@ fn unwrap_request_v3_slow_fill_params(
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
 pub struct V3SlowFill {
     pub relay_data: V3RelayData,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub chain_id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_utils::amount"))]
     pub updated_output_amount: u64,
 }
 
+// Slow relay leaves use an explicit SVM leaf format instead of the abi.encode format of EVM leaves. The HubPool never
+// hashes leaves, it only relays the root, so the dataworker builds slow relay roots for this spoke with the same format
+// (slowFillHashFn in src/svm). The leaf hash pre-image is 64 zero bytes followed by the borsh encoded V3SlowFill:
+// depositor, recipient, exclusive_relayer, input_token, output_token (32 bytes each), input_amount, output_amount,
+// origin_chain_id (u64 LE), deposit_id (32 bytes), fill_deadline, exclusivity_deadline (u32 LE), message (u32 LE length
+// prefixed bytes), chain_id and updated_output_amount (u64 LE). Known leaf hashes are pinned in
+// test/svm/fixtures/hashes.json.
 impl V3SlowFill {
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
         let mut bytes = Vec::new();
@@ -120,7 +816,7 @@ impl V3SlowFill {
         // from ever being used on SVM (and vice versa). This covers the deposit and recipient fields.
         bytes.extend_from_slice(&[0u8; 64]);
 
-        AnchorSerialize::serialize(&self, &mut bytes)?;
+        seed_encode_struct(self, &mut bytes)?;
 
         Ok(bytes)
     }
@@ -135,15 +831,25 @@ impl V3SlowFill {
 #[derive(Accounts)]
 #[instruction(relay_hash: [u8; 32], slow_fill_leaf: Option<V3SlowFill>, root_bundle_id: Option<u32>)]
 pub struct ExecuteV3SlowRelayLeaf<'info> {
+    /// Executor of the slow fill leaf, typically a dataworker. Must be the restricted executor of the root bundle
+    /// during its grace period. Writable to pay the native unwrap account rent that is returned within the execution.
+    #[account(mut)]
     pub signer: Signer<'info>,
 
-    // This is required as fallback when None instruction params are passed in arguments.
+    /// This is required as fallback when None instruction params are passed in arguments. Seeds ["instruction_params",
+    /// signer], closed to the signer after execution. Pass this program ID to represent None.
     #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump, close = signer)]
     pub instruction_params: Option<Account<'info, ExecuteV3SlowRelayLeafParams>>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
-    pub state: Account<'info, State>,
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Box<Account<'info, State>>,
 
+    /// Root bundle PDA containing the slow relay root, seeds ["root_bundle", seed, root_bundle_id]. Read-only.
     #[account(
         seeds = [
             b"root_bundle",
@@ -153,10 +859,16 @@ pub struct ExecuteV3SlowRelayLeaf<'info> {
                 .to_le_bytes()
                 .as_ref(),
         ],
-        bump
+        bump,
+        // Also verify the stored ID to protect against PDA derivation drift and surface a dedicated error.
+        constraint = root_bundle.id == root_bundle_id
+            .unwrap_or_else(|| instruction_params.as_ref().unwrap().root_bundle_id) @ SvmError::RootBundleIdMismatch,
+        constraint = root_bundle.has_slow_relay_root() @ SvmError::BundleHasNoSlowRelayRoot
     )]
-    pub root_bundle: Account<'info, RootBundle>,
+    pub root_bundle: Box<Account<'info, RootBundle>>,
 
+    /// Writable fill status PDA of the relay, seeds ["fills", relay_hash]. Must have been created by a slow fill
+    /// request.
     #[account(
         mut,
         seeds = [b"fills", relay_hash.as_ref()],
@@ -172,7 +884,9 @@ pub struct ExecuteV3SlowRelayLeaf<'info> {
     )]
     pub fill_status: Account<'info, FillStatusAccount>,
 
+    /// Mint of the output token of the relay.
     #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
         mint::token_program = token_program,
         address = slow_fill_leaf
             .clone()
@@ -180,44 +894,266 @@ pub struct ExecuteV3SlowRelayLeaf<'info> {
             .relay_data
             .output_token @ SvmError::InvalidMint
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Optional route of the output token towards the origin chain that selects the settlement mode and decimal shift.
+    /// When None, slow fills default to transferring the unscaled amount from the vault. Its expected decimals must
+    /// match the mint. Seeds ["route", mint, seed, origin_chain_id]. Pass this program ID to represent None.
+    #[account(
+        seeds = [
+            b"route",
+            mint.key().as_ref(),
+            state.seed.to_le_bytes().as_ref(),
+            slow_fill_leaf
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().slow_fill_leaf.clone())
+                .relay_data
+                .origin_chain_id
+                .to_le_bytes()
+                .as_ref(),
+        ],
+        bump = route.bump,
+        constraint = route.expected_decimals == mint.decimals @ SvmError::UnexpectedMintDecimals
+    )]
+    pub route: Option<Account<'info, Route>>,
 
+    /// Not required when the route settles by burning from the vault. Writable token account of the relay recipient
+    /// for the mint, usually their ATA. Any other token account owned by the recipient is accepted, e.g. for PDA
+    /// recipients of programs using custom token accounts. Pass this program ID to represent None.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = slow_fill_leaf
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        token::mint = mint,
+        // Ensures tokens go to an account owned by the recipient. Delegates of the account do not own it.
+        token::authority = slow_fill_leaf
             .clone()
             .unwrap_or_else(|| instruction_params.as_ref().unwrap().slow_fill_leaf.clone())
             .relay_data
             .recipient,
-        associated_token::token_program = token_program
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Optional delivery preference set by the recipient. Pass this program ID to represent None. Seeds
+    /// ["delivery_preference", seed, recipient].
+    #[account(
+        seeds = [
+            b"delivery_preference",
+            state.seed.to_le_bytes().as_ref(),
+            slow_fill_leaf
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().slow_fill_leaf.clone())
+                .relay_data
+                .recipient
+                .as_ref()
+        ],
+        bump
+    )]
+    pub delivery_preference: Option<Account<'info, DeliveryPreference>>,
+
+    /// Only required for vault transfer settlement when the recipient opted into escrow delivery. Pass this program ID
+    /// to represent None. Writable escrow token account of the recipient, seeds ["escrow", seed, recipient, mint].
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [
+            b"escrow",
+            state.seed.to_le_bytes().as_ref(),
+            slow_fill_leaf
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().slow_fill_leaf.clone())
+                .relay_data
+                .recipient
+                .as_ref(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub recipient_escrow: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Only required for vault transfer settlement of wSOL outputs when the recipient opted into native delivery. Pass
+    /// this program ID to represent None. Temporary wSOL token account of the state, seeds ["native_unwrap", seed,
+    /// signer], created and closed within the execution.
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"native_unwrap", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Relay recipient receiving the unwrapped lamports, only required together with the native unwrap account.
+    /// Pass this program ID to represent None. Checked against the relay recipient in the handler.
+    #[account(mut)]
+    pub native_recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry of the relay depositor, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    #[account(
+        seeds = [
+            b"compliance",
+            state.seed.to_le_bytes().as_ref(),
+            slow_fill_leaf
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().slow_fill_leaf.clone())
+                .relay_data
+                .depositor
+                .as_ref()
+        ],
+        bump
+    )]
+    pub depositor_compliance: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Blocklist entry of the relay recipient, only required when the state has a compliance authority.
+    /// Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    #[account(
+        seeds = [
+            b"compliance",
+            state.seed.to_le_bytes().as_ref(),
+            slow_fill_leaf
+                .clone()
+                .unwrap_or_else(|| instruction_params.as_ref().unwrap().slow_fill_leaf.clone())
+                .relay_data
+                .recipient
+                .as_ref()
+        ],
+        bump
     )]
-    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub recipient_compliance: Option<UncheckedAccount<'info>>,
+
+    /// Allowlist entry of the signer, seeds ["allowlist", seed, signer]. Only required in permissioned mode for
+    /// signers holding the executor role. Pass this program ID to represent None.
+    #[account(seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()], bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
 
+    /// CHECK: Writable vault ATA of the vault authority PDA for the mint, source of the slow fill. Checked in
+    /// the handler, so that a missing vault fails with VaultMissingForMint.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = state,
-        associated_token::token_program = token_program
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its payout.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Writable fee vault of the mint, seeds ["fee_vault", seed, mint]. Only required for vault transfer settlement
+    /// when the state charges a protocol fee. Pass this program ID to represent None.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"fee_vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Writable liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint]. Only required for vault transfer
+    /// settlement when the state enables liquidity pools with a fee. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: Option<Account<'info, LiquidityPool>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// CHECK: SPL Memo program, only required when a payout memo is passed in the extra args. Pass this program ID to
+    /// represent None.
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required for vault transfer
+    /// settlement when the state enables vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
 
+    /// Writable vault liability PDA of the mint, seeds ["vault_liability", seed, mint], debited with the slow fill
+    /// payout. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub vault_liability: Option<Account<'info, VaultLiability>>,
+
+    /// CHECK: Writable slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint], released by the
+    /// executed slow fill. Skipped when no slow fill request of the mint created it.
+    #[account(mut, seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// Execution approval PDA of the slow fill leaf, seeds ["execution_approval", seed, leaf_hash]. Only required when
+    /// the leaf exceeds the execution approval threshold of its mint. Pass this program ID to represent None.
+    pub execution_approval: Option<Account<'info, ExecutionApproval>>,
+
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
 pub fn execute_v3_slow_relay_leaf<'info>(
     ctx: Context<'_, '_, '_, 'info, ExecuteV3SlowRelayLeaf<'info>>,
+    relay_hash: [u8; 32],
     slow_fill_leaf: Option<V3SlowFill>,
     proof: Option<Vec<[u8; 32]>>,
+    args_version: u8,
+    extra: Vec<u8>,
 ) -> Result<()> {
+    let extra_args = parse_extra_args(args_version, &extra)?;
+    // Versions 2 to 4 and 6 only add repayment and recipient token account creation, the origin transaction reference
+    // and the low fee override, which are only meaningful for relayer fills, and version 5 only carries the depositor
+    // nonce.
+    if matches!(
+        extra_args,
+        ExtraArgs::V2(_) | ExtraArgs::V3(_) | ExtraArgs::V4(_) | ExtraArgs::V5(_) | ExtraArgs::V6(_)
+    ) {
+        return err!(SvmError::UnsupportedArgsVersion);
+    }
+    let memo = extra_args.payout_memo();
+    validate_payout_memo(memo)?;
+
     let ExecuteV3SlowRelayLeafParams { slow_fill_leaf, proof, .. } =
         unwrap_execute_v3_slow_relay_leaf_params(slow_fill_leaf, proof, &ctx.accounts.instruction_params);
 
     let current_time = get_current_time(&ctx.accounts.state)?;
 
+    // Check if the root bundle execution window has closed. Zero deadline means the bundle never expires.
+    let execution_deadline = ctx.accounts.root_bundle.execution_deadline;
+    if execution_deadline != 0 && current_time > execution_deadline {
+        return err!(SvmError::BundleExecutionWindowClosed);
+    }
+
+    // Bundles relayed with a restricted executor can only have their leaves executed by it until the grace period ends.
+    let root_bundle = &ctx.accounts.root_bundle;
+    if let Some(restricted_executor) = root_bundle.restricted_executor {
+        if current_time < root_bundle.restricted_until && ctx.accounts.signer.key() != restricted_executor {
+            return err!(SvmError::NotRestrictedExecutor);
+        }
+    }
+
+    check_allowlist_role(&ctx.accounts.state, &ctx.accounts.allowlist_entry, ALLOWLIST_ROLE_EXECUTOR)?;
+
+    // While fills are paused, only the emergency executor can execute slow fill leaves, each separately approved.
+    let emergency_execution = are_fills_paused(&ctx.accounts.state)?;
+    if emergency_execution && ctx.accounts.state.emergency_executor != Some(ctx.accounts.signer.key()) {
+        return err!(CommonError::FillsArePaused);
+    }
+
     let relay_data = slow_fill_leaf.relay_data;
 
+    // Leaves can update the output amount to zero, leaving only the message to run as for message-only relays.
+    if is_empty_relay(slow_fill_leaf.updated_output_amount, &relay_data.message) {
+        return err!(SvmError::EmptyRelay);
+    }
+    let message_only = slow_fill_leaf.updated_output_amount == 0;
+
+    check_compliance(&ctx.accounts.state, &[&ctx.accounts.depositor_compliance, &ctx.accounts.recipient_compliance])?;
+    check_destination_chain_id(&ctx.accounts.state, slow_fill_leaf.chain_id)?;
+
     let slow_fill = V3SlowFill {
         relay_data: relay_data.clone(),        // Clone relay_data to avoid move
         chain_id: ctx.accounts.state.chain_id, // This overrides caller provided chain_id, same as in EVM SpokePool.
@@ -228,44 +1164,168 @@ pub fn execute_v3_slow_relay_leaf<'info>(
     let leaf = slow_fill.to_keccak_hash()?;
     verify_merkle_proof(root, leaf, proof)?;
 
-    // Check if the fill deadline has passed
-    if relay_data.fill_deadline < current_time {
+    if emergency_execution {
+        check_emergency_execution_approval(
+            &ctx.accounts.state,
+            &ctx.accounts.execution_approval,
+            ctx.accounts.signer.key,
+            leaf,
+            current_time,
+        )?;
+    } else {
+        check_execution_approval(
+            &ctx.accounts.state,
+            &ctx.accounts.execution_approval,
+            &relay_data.output_token,
+            slow_fill_leaf.updated_output_amount,
+            leaf,
+            current_time,
+        )?;
+    }
+
+    // Check if the fill deadline has passed, even when the root was built before the expiry.
+    if relay_data.fill_deadline < current_time && !SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE {
+        msg!("Fill deadline {} passed, current time {}", relay_data.fill_deadline, current_time);
         return err!(CommonError::ExpiredFillDeadline);
     }
 
-    // Check if the fill status is not filled
+    // Check that the slow fill is still pending.
     let fill_status_account = &mut ctx.accounts.fill_status;
-    if fill_status_account.status == FillStatus::Filled {
-        return err!(CommonError::RelayFilled);
-    }
+    check_requested_slow_fill(&fill_status_account.status)?;
+
+    check_vault(&ctx.accounts.vault, &ctx.accounts.state.key(), &ctx.accounts.mint.to_account_info())?;
 
-    // Derive the signer seeds for the state
-    let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
-    let seeds = &[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]];
-    let signer_seeds = &[&seeds[..]];
+    // Vaults are owned by the vault authority, which signs their outflows.
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
 
-    // Invoke the transfer_checked instruction on the token program
-    let transfer_accounts = TransferChecked {
-        from: ctx.accounts.vault.to_account_info(), // Pull from the vault
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.recipient_token_account.to_account_info(), // Send to the recipient
-        authority: ctx.accounts.state.to_account_info(),            // Authority is the state (owner of the vault)
+    let (settlement_mode, decimal_shift) = match &ctx.accounts.route {
+        Some(route) => (route.settlement_mode.clone(), route.decimal_shift),
+        None => (SettlementMode::VaultTransfer, 0),
     };
-    let cpi_context =
-        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer_seeds);
-    transfer_checked(cpi_context, slow_fill_leaf.updated_output_amount, ctx.accounts.mint.decimals)?;
+    let normalized_output_amount = normalize_output_amount(slow_fill_leaf.updated_output_amount, decimal_shift)?;
+
+    // Message handlers are notified of the amount received by the recipient, or of the burnt amount. Message-only
+    // leaves have nothing to settle, so they don't need any payout destination account.
+    let (escrow, handler_amount) = match settlement_mode {
+        _ if message_only => (None, 0),
+        SettlementMode::VaultTransfer => {
+            let (payout_destination, escrow) = get_payout_destination(
+                &ctx.accounts.delivery_preference,
+                &relay_data.output_token,
+                &ctx.accounts.recipient_token_account,
+                &ctx.accounts.recipient_escrow,
+                &ctx.accounts.native_unwrap_account,
+            )?;
+
+            let (protocol_fee, liquidity_pool_fee) = charge_slow_fill_fees(
+                &ctx.accounts.state,
+                &vault_signer,
+                normalized_output_amount,
+                &ctx.accounts.vault,
+                &ctx.accounts.fee_vault,
+                &mut ctx.accounts.liquidity_pool,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_program,
+            )?;
+            if protocol_fee > 0 {
+                emit_cpi!(commit_event(
+                    &ctx.accounts.state,
+                    &mut ctx.accounts.event_commitment,
+                    AccruedProtocolFees { mint: ctx.accounts.mint.key(), amount: protocol_fee }
+                )?);
+            }
+            if let Some(amount) = liquidity_pool_fee {
+                emit_cpi!(commit_event(
+                    &ctx.accounts.state,
+                    &mut ctx.accounts.event_commitment,
+                    AccruedLiquidityPoolFees { mint: ctx.accounts.mint.key(), amount }
+                )?);
+            }
+            let liquidity_pool_fee = liquidity_pool_fee.unwrap_or(0);
+
+            invoke_payout_memo(memo, &ctx.accounts.memo_program, payout_destination)?;
+
+            // Pull from the vault and send to the recipient or its escrow.
+            let payout_amount = normalized_output_amount
+                .checked_sub(protocol_fee)
+                .and_then(|amount| amount.checked_sub(liquidity_pool_fee))
+                .or_overflow("slow fill payout amount")?;
+            // Fee-on-transfer tokens withhold part of the transfer, so it is grossed up for the payout to be received.
+            pay_from_vault(
+                &ctx.accounts.vault,
+                &payout_destination.to_account_info(),
+                get_transfer_fee_inclusive_amount(&ctx.accounts.mint, payout_amount)?,
+                &ctx.accounts.state,
+                &vault_signer,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_program,
+                &mut ctx.accounts.rate_limit,
+            )?;
+
+            if is_native_delivery(&ctx.accounts.delivery_preference, &relay_data.output_token) {
+                unwrap_native_payout(
+                    payout_destination,
+                    &ctx.accounts.native_recipient,
+                    &relay_data.recipient,
+                    &ctx.accounts.signer,
+                    payout_amount,
+                    &ctx.accounts.state,
+                    ctx.bumps.state,
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.system_program,
+                )?;
+            }
+
+            (escrow, payout_amount)
+        }
+        SettlementMode::BurnFromVault => {
+            // Burn the synthetic/receipt tokens held by the vault. The recipient handler is notified below.
+            let burn_accounts = Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.vault.to_account_info(),
+                authority: vault_signer.authority.clone(),
+            };
+            let seeds = vault_signer.seeds();
+            let signer_seeds = &[&seeds[..]];
+            let cpi_context =
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), burn_accounts, signer_seeds);
+            burn(cpi_context, normalized_output_amount)?;
+
+            (None, normalized_output_amount)
+        }
+    };
+
+    // Skipped if the caller did not pass the vault liability, which can only overstate the liabilities.
+    if let Some(vault_liability) = ctx.accounts.vault_liability.as_mut() {
+        vault_liability.record_outflow(normalized_output_amount);
+    }
+
+    // Releases the output amount recorded by the slow fill request, not the updated output amount of the leaf.
+    let (mint, released_amount) = (ctx.accounts.mint.key(), relay_data.output_amount);
+    if let Some(liability_changed) =
+        release_slow_fill_liability(&ctx.accounts.slow_fill_liability, mint, released_amount)?
+    {
+        emit_cpi!(liability_changed);
+    }
 
     // Update the fill status. We don't set the relayer and fill deadline as it is set when the slow fill was requested.
     fill_status_account.status = FillStatus::Filled;
 
     if !relay_data.message.is_empty() {
-        invoke_handler(ctx.accounts.signer.as_ref(), ctx.remaining_accounts, &relay_data.message)?;
+        invoke_handler(
+            ctx.accounts.signer.as_ref(),
+            &ctx.accounts.state.key(),
+            ctx.remaining_accounts,
+            &relay_data.message,
+            &relay_data.output_token,
+            handler_amount,
+        )?;
     }
 
     // Empty message is not hashed and emits zeroed bytes32 for easier human observability.
     let message_hash = hash_non_empty_message(&relay_data.message);
 
-    emit_cpi!(FilledV3Relay {
+    let filled_relay = FilledV3Relay {
         input_token: relay_data.input_token,
         output_token: relay_data.output_token,
         input_amount: relay_data.input_amount,
@@ -285,8 +1345,19 @@ pub fn execute_v3_slow_relay_leaf<'info>(
             updated_message_hash: message_hash,
             updated_output_amount: slow_fill_leaf.updated_output_amount,
             fill_type: FillType::SlowFill,
+            root_bundle_id: Some(ctx.accounts.root_bundle.id),
+            escrow,
+            memo_hash: hash_payout_memo(memo),
+            normalized_output_amount,
+            repayment_token_account_created: false,
+            emergency_execution,
+            origin_tx_ref: None,
+            message_only,
         },
-    });
+        token_decimals: ctx.accounts.mint.decimals,
+        relay_hash,
+    };
+    emit_cpi!(commit_event(&ctx.accounts.state, &mut ctx.accounts.event_commitment, filled_relay)?);
 
     Ok(())
 }
@@ -313,3 +1384,445 @@ fn unwrap_execute_v3_slow_relay_leaf_params(
             .unwrap(), // We do not expect this to panic here as missing instruction_params is unwrapped in context.
     }
 }
+
+// Protocol fee of vault transfer slow fills is skimmed into the fee vault of the mint and the liquidity pool fee stays
+// in the vault, credited to the liquidity owed to the pool share holders. Returns the protocol fee and the liquidity
+// pool fee when it accrued, so that callers can emit the fee events. The recipient receives the rest of the payout.
+#[allow(clippy::too_many_arguments)]
+fn charge_slow_fill_fees<'info>(
+    state: &Account<'info, State>,
+    vault_signer: &VaultSigner<'info>,
+    normalized_output_amount: u64,
+    vault: &impl ToAccountInfo<'info>,
+    fee_vault: &Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    liquidity_pool: &mut Option<Account<'info, LiquidityPool>>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<(u64, Option<u64>)> {
+    let (protocol_fee_bps, liquidity_pool_fee_bps) =
+        get_capped_fee_bps(state.protocol_fee_bps, state.liquidity_pool_fee_bps);
+
+    let protocol_fee = calculate_bps_fee(normalized_output_amount, protocol_fee_bps);
+    if protocol_fee > 0 {
+        let fee_vault = fee_vault.as_ref().ok_or(SvmError::MissingFeeVault)?;
+        transfer_from_vault(vault, &fee_vault.to_account_info(), protocol_fee, vault_signer, mint, token_program)?;
+    }
+
+    let mut liquidity_pool_fee = None;
+    if state.liquidity_pools_enabled && liquidity_pool_fee_bps > 0 {
+        let liquidity_pool = liquidity_pool.as_mut().ok_or(SvmError::MissingLiquidityPool)?;
+        // Fees only accrue once the pool has share holders to earn them.
+        if liquidity_pool.total_shares > 0 {
+            let fee = calculate_bps_fee(normalized_output_amount, liquidity_pool_fee_bps);
+            liquidity_pool.total_liquidity = liquidity_pool.total_liquidity.saturating_add(fee);
+            liquidity_pool_fee = Some(fee);
+        }
+    }
+
+    Ok((protocol_fee, liquidity_pool_fee))
+}
+
+// Slow fill leaves above the execution approval threshold of their mint additionally require a recent approval of the
+// leaf by the execution approver, while smaller leaves stay permissionless.
+fn check_execution_approval(
+    state: &State,
+    execution_approval: &Option<Account<ExecutionApproval>>,
+    mint: &Pubkey,
+    amount: u64,
+    leaf_hash: [u8; 32],
+    current_time: u32,
+) -> Result<()> {
+    let approver = match state.execution_approver {
+        Some(approver) => approver,
+        None => return Ok(()),
+    };
+    let above_threshold =
+        state.execution_approval_thresholds.iter().any(|entry| entry.mint == *mint && amount > entry.threshold);
+    if !above_threshold {
+        return Ok(());
+    }
+
+    verify_execution_approval(state, execution_approval, approver, leaf_hash, current_time)
+}
+
+// Emergency executions always require an approval, so that executing a leaf during a pause takes two keys.
+fn check_emergency_execution_approval(
+    state: &State,
+    execution_approval: &Option<Account<ExecutionApproval>>,
+    emergency_executor: &Pubkey,
+    leaf_hash: [u8; 32],
+    current_time: u32,
+) -> Result<()> {
+    let approver = match state.execution_approver {
+        Some(approver) if approver != *emergency_executor => approver,
+        _ => return err!(SvmError::MissingEmergencyApprover),
+    };
+
+    verify_execution_approval(state, execution_approval, approver, leaf_hash, current_time)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct ExecuteV3SlowRelayLeaves<'info> {
+    /// Executor of the slow fill leaves, typically a dataworker. Must be the restricted executor of the root bundle
+    /// during its grace period. Writable to receive the instruction params rent when they are closed.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// This is required as fallback when None slow relay leaves are passed in arguments. Seeds ["instruction_params",
+    /// signer], closed to the signer after execution. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"instruction_params", signer.key().as_ref()], bump, close = signer)]
+    pub instruction_params: Option<Account<'info, ExecuteV3SlowRelayLeavesParams>>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only. Fills must not be paused, as
+    /// emergency executions require their individual approval.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = !are_fills_paused(&state)? @ CommonError::FillsArePaused,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// Root bundle PDA containing the slow relay root, seeds ["root_bundle", seed, root_bundle_id]. Read-only.
+    #[account(
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = root_bundle.id == root_bundle_id @ SvmError::RootBundleIdMismatch,
+        constraint = root_bundle.has_slow_relay_root() @ SvmError::BundleHasNoSlowRelayRoot
+    )]
+    pub root_bundle: Box<Account<'info, RootBundle>>,
+
+    /// Allowlist entry of the signer, seeds ["allowlist", seed, signer]. Only required in permissioned mode for
+    /// signers holding the executor role. Pass this program ID to represent None.
+    #[account(seeds = [b"allowlist", state.seed.to_le_bytes().as_ref(), signer.key().as_ref()], bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// Mint of the output token shared by all slow relay leaves.
+    #[account(
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the slow fills.
+    #[account(
+        mut,
+        constraint = is_canonical_token_program(&mint, &token_program) @ SvmError::TokenProgramMismatch,
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its payouts.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Writable fee vault of the mint, seeds ["fee_vault", seed, mint]. Only required when the state charges a
+    /// protocol fee. Pass this program ID to represent None.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = state,
+        token::token_program = token_program,
+        seeds = [b"fee_vault", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Writable liquidity pool PDA of the mint, seeds ["liquidity_pool", seed, mint]. Only required when the state
+    /// enables liquidity pools with a fee. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"liquidity_pool", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub liquidity_pool: Option<Account<'info, LiquidityPool>>,
+
+    /// Event commitment PDA of the current epoch, seeds ["event_commitment", seed, epoch]. Only required when the state
+    /// enables event commitments. Pass this program ID to represent None.
+    #[account(mut)]
+    pub event_commitment: Option<Account<'info, EventCommitment>>,
+
+    /// Writable rate limit PDA of the mint, seeds ["rate_limit", seed, mint]. Only required when the state enables
+    /// vault outflow rate limits. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"rate_limit", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    /// Writable vault liability PDA of the mint, seeds ["vault_liability", seed, mint], debited with the slow fill
+    /// payouts. Pass this program ID to represent None.
+    #[account(mut, seeds = [b"vault_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub vault_liability: Option<Account<'info, VaultLiability>>,
+
+    /// CHECK: Writable slow fill liability PDA of the mint, seeds ["slow_fill_liability", seed, mint], released by the
+    /// executed slow fills. Skipped when no slow fill request of the mint created it.
+    #[account(mut, seeds = [b"slow_fill_liability", state.seed.to_le_bytes().as_ref(), mint.key().as_ref()], bump)]
+    pub slow_fill_liability: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Executes slow relay leaves of the same root bundle and output mint as execute_v3_slow_relay_leaf would, settling each
+// by vault transfer to the recipient's associated token account. Remaining accounts hold the writable fill status PDA,
+// the optional route PDA, the optional delivery preference PDA and the writable recipient token account of each leaf
+// in order, where this program ID represents None. Leaves needing other accounts (message handlers, compliance
+// attestations, execution approvals, burn settlement, escrow or native delivery) are rejected, and any failing leaf
+// reverts the whole batch, so that fill statuses can not desync from the bundle accounting.
+pub fn execute_v3_slow_relay_leaves<'c, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ExecuteV3SlowRelayLeaves<'info>>,
+    slow_relay_leaves: Option<Vec<SlowRelayLeafExecution>>,
+) -> Result<()>
+where
+    'c: 'info,
+{
+    let leaves = match slow_relay_leaves {
+        Some(leaves) => leaves,
+        None => ctx.accounts.instruction_params.as_ref().ok_or(ErrorCode::ConstraintAccountIsNone)?.leaves.clone(),
+    };
+    if leaves.is_empty() || leaves.len() > MAX_SLOW_RELAY_LEAVES_PER_BATCH {
+        return err!(SvmError::InvalidSlowRelayLeafCount);
+    }
+    if ctx.remaining_accounts.len() != leaves.len() * 4 {
+        return err!(SvmError::InvalidSlowRelayLeafAccounts);
+    }
+
+    let state = &ctx.accounts.state;
+    let current_time = get_current_time(state)?;
+
+    // Check if the root bundle execution window has closed. Zero deadline means the bundle never expires.
+    let root_bundle = &ctx.accounts.root_bundle;
+    if root_bundle.execution_deadline != 0 && current_time > root_bundle.execution_deadline {
+        return err!(SvmError::BundleExecutionWindowClosed);
+    }
+    if let Some(restricted_executor) = root_bundle.restricted_executor {
+        if current_time < root_bundle.restricted_until && ctx.accounts.signer.key() != restricted_executor {
+            return err!(SvmError::NotRestrictedExecutor);
+        }
+    }
+
+    check_allowlist_role(state, &ctx.accounts.allowlist_entry, ALLOWLIST_ROLE_EXECUTOR)?;
+
+    // Compliance attestations are specific to the depositor and recipient of each relay.
+    if state.compliance_authority.is_some() {
+        return err!(SvmError::UnsupportedBatchedSlowRelayLeaf);
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let state_seed_bytes = state.seed.to_le_bytes();
+    let vault_signer = VaultSigner::new(state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
+
+    let mut released_slow_fill_amount = 0u64;
+    for (i, (leaf, accounts)) in leaves.into_iter().zip(ctx.remaining_accounts.chunks(4)).enumerate() {
+        let SlowRelayLeafExecution { slow_fill_leaf, proof } = leaf;
+        let relay_data = slow_fill_leaf.relay_data;
+        let (fill_status_info, route_info, delivery_preference_info, recipient_token_account_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+        if relay_data.output_token != mint_key {
+            return err!(SvmError::InvalidMint);
+        }
+        // Message handlers need their own remaining accounts, so message-only leaves can't be batched either.
+        if !relay_data.message.is_empty() {
+            return err!(SvmError::UnsupportedBatchedSlowRelayLeaf);
+        }
+        if is_empty_relay(slow_fill_leaf.updated_output_amount, &relay_data.message) {
+            return err!(SvmError::EmptyRelay);
+        }
+        check_destination_chain_id(state, slow_fill_leaf.chain_id)?;
+
+        let slow_fill = V3SlowFill {
+            relay_data: relay_data.clone(),
+            chain_id: state.chain_id, // This overrides caller provided chain_id, same as in EVM SpokePool.
+            updated_output_amount: slow_fill_leaf.updated_output_amount,
+        };
+        let leaf_hash = slow_fill.to_keccak_hash()?;
+        verify_merkle_proof(root_bundle.slow_relay_root, leaf_hash, proof)?;
+
+        // Leaves above the execution approval threshold need their own approval account.
+        check_execution_approval(
+            state,
+            &None,
+            &relay_data.output_token,
+            slow_fill_leaf.updated_output_amount,
+            leaf_hash,
+            current_time,
+        )?;
+
+        if relay_data.fill_deadline < current_time && !SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE {
+            msg!("Fill deadline {} passed, current time {}", relay_data.fill_deadline, current_time);
+            return err!(CommonError::ExpiredFillDeadline);
+        }
+
+        let relay_hash = get_v3_relay_hash(&relay_data, state.chain_id);
+        check_remaining_pda(fill_status_info, &[b"fills", relay_hash.as_ref()], i * 4)?;
+        // Fill status must have been created by a slow fill request.
+        let mut fill_status = Account::<FillStatusAccount>::try_from(fill_status_info)?;
+        check_requested_slow_fill(&fill_status.status)?;
+
+        let origin_chain_id_bytes = relay_data.origin_chain_id.to_le_bytes();
+        let route_seeds = [b"route".as_ref(), mint_key.as_ref(), state_seed_bytes.as_ref(), &origin_chain_id_bytes];
+        let route: Option<Account<Route>> = load_optional_remaining_pda(route_info, &route_seeds, i * 4 + 1)?;
+        let decimal_shift = match &route {
+            Some(route) if route.expected_decimals != ctx.accounts.mint.decimals => {
+                return Err(Error::from(SvmError::UnexpectedMintDecimals)
+                    .with_account_name(format!("remaining_accounts[{}]", i * 4 + 1)));
+            }
+            Some(route) if route.settlement_mode == SettlementMode::BurnFromVault => {
+                return err!(SvmError::UnsupportedBatchedSlowRelayLeaf);
+            }
+            Some(route) => route.decimal_shift,
+            None => 0,
+        };
+
+        let delivery_preference_seeds =
+            [b"delivery_preference".as_ref(), state_seed_bytes.as_ref(), relay_data.recipient.as_ref()];
+        let delivery_preference: Option<Account<DeliveryPreference>> =
+            load_optional_remaining_pda(delivery_preference_info, &delivery_preference_seeds, i * 4 + 2)?;
+        let escrow_delivery =
+            delivery_preference.as_ref().is_some_and(|preference| preference.mode == DeliveryMode::Escrow);
+        if escrow_delivery || is_native_delivery(&delivery_preference, &relay_data.output_token) {
+            return err!(SvmError::UnsupportedBatchedSlowRelayLeaf);
+        }
+
+        let recipient_token_account_key =
+            get_canonical_ata(&relay_data.recipient, &ctx.accounts.mint.to_account_info());
+        if recipient_token_account_info.key() != recipient_token_account_key {
+            return Err(Error::from(ErrorCode::ConstraintAssociated)
+                .with_account_name(format!("remaining_accounts[{}]", i * 4 + 3))
+                .with_pubkeys((recipient_token_account_info.key(), recipient_token_account_key)));
+        }
+
+        let normalized_output_amount = normalize_output_amount(slow_fill_leaf.updated_output_amount, decimal_shift)?;
+        let (protocol_fee, liquidity_pool_fee) = charge_slow_fill_fees(
+            &ctx.accounts.state,
+            &vault_signer,
+            normalized_output_amount,
+            &*ctx.accounts.vault,
+            &ctx.accounts.fee_vault,
+            &mut ctx.accounts.liquidity_pool,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+        )?;
+        if protocol_fee > 0 {
+            emit_cpi!(commit_event(
+                &ctx.accounts.state,
+                &mut ctx.accounts.event_commitment,
+                AccruedProtocolFees { mint: mint_key, amount: protocol_fee }
+            )?);
+        }
+        if let Some(amount) = liquidity_pool_fee {
+            emit_cpi!(commit_event(
+                &ctx.accounts.state,
+                &mut ctx.accounts.event_commitment,
+                AccruedLiquidityPoolFees { mint: mint_key, amount }
+            )?);
+        }
+
+        let payout_amount = normalized_output_amount
+            .checked_sub(protocol_fee)
+            .and_then(|amount| amount.checked_sub(liquidity_pool_fee.unwrap_or(0)))
+            .or_overflow("slow fill payout amount")?;
+        pay_from_vault(
+            &*ctx.accounts.vault,
+            recipient_token_account_info,
+            get_transfer_fee_inclusive_amount(&ctx.accounts.mint, payout_amount)?,
+            &ctx.accounts.state,
+            &vault_signer,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &mut ctx.accounts.rate_limit,
+        )?;
+        if let Some(vault_liability) = ctx.accounts.vault_liability.as_mut() {
+            vault_liability.record_outflow(normalized_output_amount);
+        }
+        released_slow_fill_amount = released_slow_fill_amount.saturating_add(relay_data.output_amount);
+
+        // Persist the fill status before the next leaf, so that duplicate leaves of the batch are rejected as filled.
+        fill_status.status = FillStatus::Filled;
+        fill_status.exit(&crate::ID)?;
+
+        let message_hash = hash_non_empty_message(&relay_data.message);
+        let filled_relay = FilledV3Relay {
+            input_token: relay_data.input_token,
+            output_token: relay_data.output_token,
+            input_amount: relay_data.input_amount,
+            output_amount: relay_data.output_amount,
+            repayment_chain_id: 0, // There is no repayment chain id for slow fills.
+            origin_chain_id: relay_data.origin_chain_id,
+            deposit_id: relay_data.deposit_id,
+            fill_deadline: relay_data.fill_deadline,
+            exclusivity_deadline: relay_data.exclusivity_deadline,
+            exclusive_relayer: relay_data.exclusive_relayer,
+            relayer: Pubkey::default(), // There is no repayment address for slow
+            depositor: relay_data.depositor,
+            recipient: relay_data.recipient,
+            message_hash,
+            relay_execution_info: V3RelayExecutionEventInfo {
+                updated_recipient: relay_data.recipient,
+                updated_message_hash: message_hash,
+                updated_output_amount: slow_fill_leaf.updated_output_amount,
+                fill_type: FillType::SlowFill,
+                root_bundle_id: Some(ctx.accounts.root_bundle.id),
+                escrow: None,
+                memo_hash: hash_payout_memo(None),
+                normalized_output_amount,
+                repayment_token_account_created: false,
+                emergency_execution: false,
+                origin_tx_ref: None,
+                message_only: false,
+            },
+            token_decimals: ctx.accounts.mint.decimals,
+            relay_hash,
+        };
+        emit_cpi!(commit_event(&ctx.accounts.state, &mut ctx.accounts.event_commitment, filled_relay)?);
+    }
+
+    let slow_fill_liability = &ctx.accounts.slow_fill_liability;
+    if let Some(liability_changed) =
+        release_slow_fill_liability(slow_fill_liability, mint_key, released_slow_fill_amount)?
+    {
+        emit_cpi!(liability_changed);
+    }
+
+    Ok(())
+}
+
+// Slow fill leaves can only be executed for relays whose slow fill was requested and neither filled nor expired since.
+// Rejections log the status found, as the fill status PDA of unrequested relays can still exist, e.g. when imported.
+fn check_requested_slow_fill(status: &FillStatus) -> Result<()> {
+    let error = match status {
+        FillStatus::RequestedSlowFill => return Ok(()),
+        FillStatus::Unfilled => error!(SvmError::SlowFillNotRequested),
+        FillStatus::Filled => error!(CommonError::RelayFilled),
+        FillStatus::Expired => error!(SvmError::RelayExpired),
+    };
+    msg!("Relay has fill status {:?}", status);
+
+    Err(error)
+}
+
+// Loads an optional PDA passed in remaining accounts, where this program ID represents None as for optional accounts of
+// the instruction context.
+fn load_optional_remaining_pda<'info, T: AccountSerialize + AccountDeserialize + Owner + Clone>(
+    account_info: &'info AccountInfo<'info>,
+    seeds: &[&[u8]],
+    account_index: usize,
+) -> Result<Option<Account<'info, T>>> {
+    if account_info.key() == crate::ID {
+        return Ok(None);
+    }
+    check_remaining_pda(account_info, seeds, account_index)?;
+
+    Account::try_from(account_info).map(Some)
+}
+
+// Checks that the remaining account at account_index is the canonical PDA of this program for the seeds, returning its
+// bump.
+fn check_remaining_pda(account_info: &AccountInfo, seeds: &[&[u8]], account_index: usize) -> Result<u8> {
+    let (expected_key, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    if account_info.key() != expected_key {
+        return Err(Error::from(ErrorCode::ConstraintSeeds)
+            .with_account_name(format!("remaining_accounts[{}]", account_index))
+            .with_pubkeys((account_info.key(), expected_key)));
+    }
+
+    Ok(bump)
+}
@@ -0,0 +1,243 @@
+use anchor_lang::{error::ErrorCode, prelude::*};
+
+use crate::{
+    constants::{
+        DISCRIMINATOR_SIZE, EXPORT_CLAIM_ACCOUNT_RECORD_SIZE, EXPORT_FILL_STATUS_RECORD_SIZE, EXPORT_HEADER_SIZE,
+        EXPORT_LAYOUT_VERSION, EXPORT_LIABILITY_RECORD_SIZE, MAX_EXPORT_PAGE_SIZE,
+    },
+    error::SvmError,
+    state::{ClaimAccount, ClaimAccountKey, FillStatus, FillStatusAccount, State, TransferLiability},
+    utils::{create_pda_account, create_pda_with_bump, Null},
+};
+
+// Records are borsh serialized, which packs them without padding in the EXPORT_*_RECORD_SIZE layouts.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct FillStatusRecord {
+    relay_hash: [u8; 32],
+    status: FillStatus,
+    relayer: Pubkey,
+    fill_deadline: u32,
+    requester: Pubkey,
+    payer: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct ClaimAccountRecord {
+    mint: Pubkey,
+    refund_address: Pubkey,
+    amount: u64,
+    initializer: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct LiabilityRecord {
+    mint: Pubkey,
+    pending_to_hub_pool: u64,
+}
+
+pub fn export_fill_statuses(ctx: Context<Null>, page: u32, relay_hashes: Vec<[u8; 32]>) -> Result<Vec<u8>> {
+    export_page(page, &relay_hashes, ctx.remaining_accounts, EXPORT_FILL_STATUS_RECORD_SIZE, |relay_hash, account| {
+        let (fill_status_pda, _) = Pubkey::find_program_address(&[b"fills", relay_hash.as_ref()], &crate::ID);
+        check_address(account, fill_status_pda)?;
+        check_owner(account)?;
+
+        // Legacy layouts are exported like the current one, so that imports also migrate them.
+        let fill_status = FillStatusAccount::try_deserialize_any_layout(&account.try_borrow_data()?)?;
+        Ok(FillStatusRecord {
+            relay_hash: *relay_hash,
+            status: fill_status.status,
+            relayer: fill_status.relayer,
+            fill_deadline: fill_status.fill_deadline,
+            requester: fill_status.requester,
+            payer: fill_status.payer,
+        })
+    })
+}
+
+pub fn export_claim_accounts(ctx: Context<Null>, page: u32, claim_keys: Vec<ClaimAccountKey>) -> Result<Vec<u8>> {
+    export_page(page, &claim_keys, ctx.remaining_accounts, EXPORT_CLAIM_ACCOUNT_RECORD_SIZE, |claim_key, account| {
+        check_owner(account)?;
+        let claim_account = ClaimAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        let seeds = [b"claim_account".as_ref(), claim_key.mint.as_ref(), claim_key.refund_address.as_ref()];
+        check_address(account, create_pda_with_bump(&seeds, claim_account.bump)?)?;
+
+        Ok(ClaimAccountRecord {
+            mint: claim_key.mint,
+            refund_address: claim_key.refund_address,
+            amount: claim_account.amount,
+            initializer: claim_account.initializer,
+        })
+    })
+}
+
+pub fn export_liabilities(ctx: Context<Null>, page: u32, mints: Vec<Pubkey>) -> Result<Vec<u8>> {
+    export_page(page, &mints, ctx.remaining_accounts, EXPORT_LIABILITY_RECORD_SIZE, |mint, account| {
+        let (transfer_liability_pda, _) =
+            Pubkey::find_program_address(&[b"transfer_liability", mint.as_ref()], &crate::ID);
+        check_address(account, transfer_liability_pda)?;
+        check_owner(account)?;
+
+        let transfer_liability = TransferLiability::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        Ok(LiabilityRecord { mint: *mint, pending_to_hub_pool: transfer_liability.pending_to_hub_pool })
+    })
+}
+
+#[derive(Accounts)]
+pub struct ImportState<'info> {
+    /// Owner importing the exported state. Writable signer paying the rent of the created PDAs.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only. Only accepts imports during its
+    /// genesis window, before the first deposit or relayed root bundle.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.genesis @ SvmError::ImportsDisabled,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn import_fill_statuses<'info>(ctx: Context<'_, '_, '_, 'info, ImportState<'info>>, page: Vec<u8>) -> Result<()> {
+    for (record, account) in import_page(&page, EXPORT_FILL_STATUS_RECORD_SIZE, ctx.remaining_accounts)? {
+        let record = FillStatusRecord::try_from_slice(record).map_err(|_| SvmError::InvalidExportPage)?;
+        let (fill_status_pda, bump) = Pubkey::find_program_address(&[b"fills", record.relay_hash.as_ref()], &crate::ID);
+        check_address(account, fill_status_pda)?;
+
+        // The exported payer is kept, as it paid the rent of the original PDA that is only returned when closing.
+        let fill_status = FillStatusAccount {
+            status: record.status,
+            relayer: record.relayer,
+            fill_deadline: record.fill_deadline,
+            requester: record.requester,
+            payer: record.payer,
+        };
+        let seeds = [b"fills".as_ref(), record.relay_hash.as_ref(), &[bump]];
+        let space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE;
+        create_imported_account(&ctx, account, space, &seeds, &fill_status)?;
+    }
+
+    Ok(())
+}
+
+pub fn import_claim_accounts<'info>(ctx: Context<'_, '_, '_, 'info, ImportState<'info>>, page: Vec<u8>) -> Result<()> {
+    for (record, account) in import_page(&page, EXPORT_CLAIM_ACCOUNT_RECORD_SIZE, ctx.remaining_accounts)? {
+        let record = ClaimAccountRecord::try_from_slice(record).map_err(|_| SvmError::InvalidExportPage)?;
+        let (claim_account_pda, bump) = Pubkey::find_program_address(
+            &[b"claim_account", record.mint.as_ref(), record.refund_address.as_ref()],
+            &crate::ID,
+        );
+        check_address(account, claim_account_pda)?;
+
+        let claim_account = ClaimAccount { amount: record.amount, initializer: record.initializer, bump };
+        let seeds = [b"claim_account".as_ref(), record.mint.as_ref(), record.refund_address.as_ref(), &[bump]];
+        let space = DISCRIMINATOR_SIZE + ClaimAccount::INIT_SPACE;
+        create_imported_account(&ctx, account, space, &seeds, &claim_account)?;
+    }
+
+    Ok(())
+}
+
+pub fn import_liabilities<'info>(ctx: Context<'_, '_, '_, 'info, ImportState<'info>>, page: Vec<u8>) -> Result<()> {
+    for (record, account) in import_page(&page, EXPORT_LIABILITY_RECORD_SIZE, ctx.remaining_accounts)? {
+        let record = LiabilityRecord::try_from_slice(record).map_err(|_| SvmError::InvalidExportPage)?;
+        let (transfer_liability_pda, bump) =
+            Pubkey::find_program_address(&[b"transfer_liability", record.mint.as_ref()], &crate::ID);
+        check_address(account, transfer_liability_pda)?;
+
+        let transfer_liability = TransferLiability { pending_to_hub_pool: record.pending_to_hub_pool };
+        let seeds = [b"transfer_liability".as_ref(), record.mint.as_ref(), &[bump]];
+        let space = DISCRIMINATOR_SIZE + TransferLiability::INIT_SPACE;
+        create_imported_account(&ctx, account, space, &seeds, &transfer_liability)?;
+    }
+
+    Ok(())
+}
+
+// Packs the records of the accounts from the page index until the page is full. The continuation token is set to the
+// index of the first account that did not fit, so that callers can pass the same accounts again to read the rest.
+fn export_page<K, R: AnchorSerialize>(
+    page: u32,
+    keys: &[K],
+    accounts: &[AccountInfo],
+    record_size: usize,
+    mut read_record: impl FnMut(&K, &AccountInfo) -> Result<R>,
+) -> Result<Vec<u8>> {
+    let start = page as usize;
+    if keys.len() != accounts.len() || start > keys.len() {
+        return err!(SvmError::InvalidExportPage);
+    }
+
+    let end = keys.len().min(start + (MAX_EXPORT_PAGE_SIZE - EXPORT_HEADER_SIZE) / record_size);
+    let next_page = if end < keys.len() { end as u32 } else { 0 };
+
+    let mut exported = Vec::with_capacity(EXPORT_HEADER_SIZE + (end - start) * record_size);
+    exported.push(EXPORT_LAYOUT_VERSION);
+    exported.push((end - start) as u8);
+    exported.extend_from_slice(&next_page.to_le_bytes());
+    for (key, account) in keys[start..end].iter().zip(&accounts[start..end]) {
+        read_record(key, account)?.serialize(&mut exported)?;
+    }
+
+    Ok(exported)
+}
+
+// Splits an exported page into its records, each matched with the remaining account to create in its place.
+fn import_page<'a, 'info>(
+    page: &'a [u8],
+    record_size: usize,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<impl Iterator<Item = (&'a [u8], &'a AccountInfo<'info>)>> {
+    if page.len() < EXPORT_HEADER_SIZE || page[0] != EXPORT_LAYOUT_VERSION {
+        return err!(SvmError::InvalidExportPage);
+    }
+
+    let record_count = page[1] as usize;
+    let records = &page[EXPORT_HEADER_SIZE..];
+    if records.len() != record_count * record_size || accounts.len() != record_count {
+        return err!(SvmError::InvalidExportPage);
+    }
+
+    Ok(records.chunks_exact(record_size).zip(accounts))
+}
+
+// Creating the PDA fails for accounts that already exist, so imports never overwrite state of the new deployment.
+fn create_imported_account<'info, T: AccountSerialize>(
+    ctx: &Context<'_, '_, '_, 'info, ImportState<'info>>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    imported: &T,
+) -> Result<()> {
+    create_pda_account(
+        &ctx.accounts.signer.to_account_info(),
+        account,
+        &ctx.accounts.system_program.to_account_info(),
+        space,
+        signer_seeds,
+    )?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    imported.try_serialize(&mut &mut data[..])
+}
+
+fn check_address(account: &AccountInfo, pda: Pubkey) -> Result<()> {
+    if account.key() != pda {
+        return Err(Error::from(ErrorCode::ConstraintSeeds).with_pubkeys((account.key(), pda)));
+    }
+
+    Ok(())
+}
+
+// try_deserialize only checks the discriminator, so exported accounts are checked to be owned by this program first.
+fn check_owner(account: &AccountInfo) -> Result<()> {
+    if account.owner != &crate::ID {
+        return Err(Error::from(ErrorCode::AccountOwnedByWrongProgram).with_pubkeys((*account.owner, crate::ID)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    event::SetSwapAdapter,
+    state::{State, SwapAdapter},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(swap_program: Pubkey)]
+pub struct SetSwapAdapterState<'info> {
+    /// Owner maintaining the swap adapters. Writable signer paying the swap adapter rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Swap adapter PDA of the swap program, seeds ["swap_adapter", seed, swap_program]. Created if missing and
+    /// writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + SwapAdapter::INIT_SPACE,
+        seeds = [b"swap_adapter", state.seed.to_le_bytes().as_ref(), swap_program.as_ref()],
+        bump
+    )]
+    pub swap_adapter: Account<'info, SwapAdapter>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_swap_adapter(ctx: Context<SetSwapAdapterState>, swap_program: Pubkey, enabled: bool) -> Result<()> {
+    ctx.accounts.swap_adapter.enabled = enabled;
+
+    emit_cpi!(SetSwapAdapter { swap_program, enabled });
+
+    Ok(())
+}
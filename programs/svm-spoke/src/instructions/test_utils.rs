@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    error::SvmError,
+    state::{FillStatus, FillStatusAccount, RootBundle, State},
+};
+
+// Test-only shortcuts to the state that flows would otherwise have to replay, so that tests of a single instruction can
+// set up its preconditions directly. The program macro dispatches its instructions regardless of their cfg attributes,
+// so these are part of every build, but production builds reject them and revert any account they would create.
+
+#[derive(Accounts)]
+pub struct SetCurrentTime<'info> {
+    /// Owner setting the current time.
+    #[account(address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed]. Writable to update the current time used in tests.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+}
+
+pub fn set_current_time(ctx: Context<SetCurrentTime>, new_time: u32) -> Result<()> {
+    require!(cfg!(feature = "test"), SvmError::CannotSetCurrentTime);
+
+    ctx.accounts.state.current_time = new_time;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(root_bundle_id: u32)]
+pub struct SetRootBundle<'info> {
+    /// Owner setting the root bundle. Writable signer paying the root bundle PDA rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed]. Writable to move root_bundle_id past the set root bundle.
+    #[account(
+        mut,
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Root bundle PDA, seeds ["root_bundle", seed, root_bundle_id]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + RootBundle::INIT_SPACE,
+        seeds = [b"root_bundle", state.seed.to_le_bytes().as_ref(), root_bundle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub root_bundle: Account<'info, RootBundle>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// Root bundles are set without execution deadline, restricted executor or claimed leaves, as relayed by the HubPool
+// with default arguments. Existing root bundles are overwritten so that tests can reuse their id.
+pub fn set_root_bundle(
+    ctx: Context<SetRootBundle>,
+    root_bundle_id: u32,
+    relayer_refund_root: [u8; 32],
+    slow_relay_root: [u8; 32],
+) -> Result<()> {
+    require!(cfg!(feature = "test"), SvmError::TestOnlyInstruction);
+
+    let root_bundle = &mut ctx.accounts.root_bundle;
+    root_bundle.relayer_refund_root = relayer_refund_root;
+    root_bundle.slow_relay_root = slow_relay_root;
+    root_bundle.id = root_bundle_id;
+    root_bundle.execution_deadline = 0;
+    root_bundle.restricted_executor = None;
+    root_bundle.restricted_until = 0;
+    root_bundle.claimed_bitmap = Vec::new();
+
+    let state = &mut ctx.accounts.state;
+    state.root_bundle_id = std::cmp::max(state.root_bundle_id, root_bundle_id.saturating_add(1));
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(relay_hash: [u8; 32])]
+pub struct SetFillStatus<'info> {
+    /// Owner setting the fill status. Writable signer paying the fill status PDA rent if it is created.
+    #[account(mut, address = state.owner @ SvmError::NotOwner)]
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed]. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Fill status PDA of the relay, seeds ["fills", relay_hash]. Created if missing and writable.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE,
+        seeds = [b"fills", relay_hash.as_ref()],
+        bump
+    )]
+    pub fill_status: Account<'info, FillStatusAccount>,
+
+    /// System program, used to create accounts.
+    pub system_program: Program<'info, System>,
+}
+
+// Created fill statuses record the signer as relayer, requester and payer, with a zero fill deadline.
+pub fn set_fill_status(ctx: Context<SetFillStatus>, status: FillStatus) -> Result<()> {
+    require!(cfg!(feature = "test"), SvmError::TestOnlyInstruction);
+
+    let signer = ctx.accounts.signer.key();
+    let fill_status = &mut ctx.accounts.fill_status;
+    if fill_status.payer == Pubkey::default() {
+        fill_status.relayer = signer;
+        fill_status.requester = signer;
+        fill_status.payer = signer;
+    }
+    fill_status.status = status;
+
+    Ok(())
+}
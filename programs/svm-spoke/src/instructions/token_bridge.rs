@@ -6,36 +6,51 @@ use crate::{
     event::BridgedToHubPool,
     message_transmitter::program::MessageTransmitter,
     token_messenger_minter::{
-        self, cpi::accounts::DepositForBurn, program::TokenMessengerMinter, types::DepositForBurnParams,
+        self, accounts::LocalToken, cpi::accounts::DepositForBurn, program::TokenMessengerMinter,
+        types::DepositForBurnParams,
     },
+    utils::{is_vault, OrArithmeticOverflow, VaultSigner},
     State, TransferLiability,
 };
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct BridgeTokensToHubPool<'info> {
+    /// Anyone can bridge the pending liability to the HubPool.
     pub signer: Signer<'info>,
 
+    /// Pays the rent for the CCTP message sent event data account. Writable signer.
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// Writable mint of the bridged token, burned through CCTP.
     #[account(mut, mint::token_program = token_program)]
     pub mint: InterfaceAccount<'info, Mint>,
 
-    #[account(seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Authority of the vault.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
     pub state: Account<'info, State>,
 
+    /// Writable transfer liability PDA for the mint, seeds ["transfer_liability", mint].
     #[account(mut, seeds = [b"transfer_liability", mint.key().as_ref()], bump)]
     pub transfer_liability: Account<'info, TransferLiability>,
 
+    /// Writable vault ATA of the vault authority PDA for the mint, source of the bridged tokens.
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = state,
-        associated_token::token_program = token_program
+        constraint = is_vault(&vault, &state.key(), &mint.to_account_info()) @ ErrorCode::ConstraintAssociated,
+        token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// CHECK: Vault authority PDA, seeds ["vault_authority", state]. Owner of the vault, signing its outflows.
+    #[account(seeds = [b"vault_authority", state.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
     /// CHECK: empty PDA, checked in CCTP. Seeds must be \["sender_authority"\] (CCTP Token Messenger Minter program).
     pub token_messenger_minter_sender_authority: UncheckedAccount<'info>,
 
@@ -55,24 +70,29 @@ pub struct BridgeTokensToHubPool<'info> {
     /// CHECK: TokenMinter is checked in CCTP. Seeds must be \["token_minter"\] (CCTP Token Messenger Minter program).
     pub token_minter: UncheckedAccount<'info>,
 
-    /// CHECK: LocalToken is checked in CCTP. Seeds must be \["local_token", mint\] (CCTP Token Messenger Minter
-    /// program).
+    /// LocalToken seeds are checked in CCTP. Seeds must be \["local_token", mint\] (CCTP Token Messenger Minter
+    /// program). Owner and discriminator are checked here so that its burn limit can be read before the CPI.
     #[account(mut)]
-    pub local_token: UncheckedAccount<'info>,
+    pub local_token: Box<Account<'info, LocalToken>>,
 
     /// CHECK: EventAuthority is checked in CCTP. Seeds must be \["__event_authority"\] (CCTP Token Messenger Minter
     /// program).
     pub cctp_event_authority: UncheckedAccount<'info>,
 
+    /// New keypair for the CCTP message sent event data account. Writable signer.
     #[account(mut)]
     pub message_sent_event_data: Signer<'info>,
 
+    /// CCTP Message Transmitter program.
     pub message_transmitter_program: Program<'info, MessageTransmitter>,
 
+    /// CCTP Token Messenger Minter program.
     pub token_messenger_minter_program: Program<'info, TokenMessengerMinter>,
 
+    /// SPL Token or Token-2022 program owning the mint.
     pub token_program: Interface<'info, TokenInterface>,
 
+    /// System program, used to create accounts.
     pub system_program: Program<'info, System>,
 }
 
@@ -80,12 +100,17 @@ pub fn bridge_tokens_to_hub_pool(ctx: Context<BridgeTokensToHubPool>, amount: u6
     if amount > ctx.accounts.transfer_liability.pending_to_hub_pool {
         return err!(SvmError::ExceededPendingBridgeAmount);
     }
-    ctx.accounts.transfer_liability.pending_to_hub_pool -= amount;
 
-    // Invoke CCTP to bridge vault tokens from state account.
+    // Callers should split larger liabilities into multiple calls not exceeding the CCTP per message burn limit.
+    if amount > ctx.accounts.local_token.burn_limit_per_message {
+        return err!(SvmError::ExceededCctpBurnLimit);
+    }
+
+    // Invoke CCTP to bridge vault tokens from the vault owned by the vault authority.
+    let vault_signer = VaultSigner::new(&ctx.accounts.state, &ctx.accounts.vault_authority, ctx.bumps.vault_authority);
     let cpi_program = ctx.accounts.token_messenger_minter_program.to_account_info();
     let cpi_accounts = DepositForBurn {
-        owner: ctx.accounts.state.to_account_info(),
+        owner: vault_signer.authority.clone(),
         event_rent_payer: ctx.accounts.payer.to_account_info(),
         sender_authority_pda: ctx.accounts.token_messenger_minter_sender_authority.to_account_info(),
         burn_token_account: ctx.accounts.vault.to_account_info(),
@@ -103,17 +128,23 @@ pub fn bridge_tokens_to_hub_pool(ctx: Context<BridgeTokensToHubPool>, amount: u6
         event_authority: ctx.accounts.cctp_event_authority.to_account_info(),
         program: ctx.accounts.token_messenger_minter_program.to_account_info(),
     };
-    let state_seed_bytes = ctx.accounts.state.seed.to_le_bytes();
-    let state_seeds: &[&[&[u8]]] = &[&[b"state", state_seed_bytes.as_ref(), &[ctx.bumps.state]]];
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, state_seeds);
+    let seeds = vault_signer.seeds();
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     let params = DepositForBurnParams {
         amount,
         destination_domain: ctx.accounts.state.remote_domain, // CCTP domain for Mainnet Ethereum.
         mint_recipient: ctx.accounts.state.cross_domain_admin, // This is same as HubPool.
     };
-    token_messenger_minter::cpi::deposit_for_burn(cpi_ctx, params)?;
+    let nonce = token_messenger_minter::cpi::deposit_for_burn(cpi_ctx, params)?.get();
+
+    // Only decrement the pending liability after a successful burn. Leaf execution only credits this ledger, so any
+    // failure here leaves the liability intact for the next bridging attempt.
+    let transfer_liability = &mut ctx.accounts.transfer_liability;
+    transfer_liability.pending_to_hub_pool =
+        transfer_liability.pending_to_hub_pool.checked_sub(amount).or_overflow("pending to hub pool")?;
 
-    emit_cpi!(BridgedToHubPool { amount, mint: ctx.accounts.mint.key() });
+    emit_cpi!(BridgedToHubPool { amount, mint: ctx.accounts.mint.key(), nonce });
 
     Ok(())
 }
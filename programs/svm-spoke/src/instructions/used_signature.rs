@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::SvmError,
+    state::{State, UsedSignature},
+    utils::{assert_lamport_destination, get_current_time},
+};
+
+#[derive(Accounts)]
+pub struct CloseUsedSignature<'info> {
+    /// Anyone can close expired used signature PDAs, as the rent is always returned to their original payer.
+    pub signer: Signer<'info>,
+
+    /// Spoke state PDA, seeds ["state", seed] where seed is 0 on mainnet. Read-only.
+    #[account(
+        seeds = [b"state", state.pda_seed().to_le_bytes().as_ref()],
+        bump,
+        constraint = state.is_current_version() @ SvmError::StateNotMigrated
+    )]
+    pub state: Account<'info, State>,
+
+    /// Writable used signature PDA being closed, seeds ["used_signature", seed, signature_key]. Closed to its original
+    /// payer. No need to check seed derivation as this method only evaluates the expiry recorded in this account.
+    #[account(mut, close = payer)]
+    pub used_signature: Account<'info, UsedSignature>,
+
+    /// CHECK: Writable original payer of the used signature PDA, receiving the reclaimed rent.
+    #[account(mut, address = used_signature.payer @ SvmError::InvalidOriginalPayer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn close_used_signature(ctx: Context<CloseUsedSignature>) -> Result<()> {
+    assert_lamport_destination(&ctx.accounts.payer)?;
+
+    // Consuming instructions reject signatures after their expiry, so the PDA is no longer needed to reject replays.
+    let current_time = get_current_time(&ctx.accounts.state)?;
+    if current_time <= ctx.accounts.used_signature.expires_at {
+        return err!(SvmError::CanOnlyCloseUsedSignatureAfterExpiry);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+// Read interface for programs composing with the spoke, such as intent settlement programs checking inside their own
+// instructions whether a relay was filled. These programs depend on this crate with the cpi feature, that leaves out
+// the program entrypoint, and only need the items below: derive the fill status PDA of the relay hash, check that the
+// passed account is that PDA and read its status with deserialize_fill_status. Items are re-exported from where the
+// spoke defines them, so that this interface can't drift from the accounts the spoke writes.
+
+pub use crate::{
+    common::V3RelayData,
+    state::{FillStatus, FillStatusAccount},
+    utils::{derive_fill_status_pda, deserialize_fill_status, get_v3_relay_hash},
+};
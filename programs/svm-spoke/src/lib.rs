@@ -1,3 +1,5 @@
+#![deny(arithmetic_overflow)]
+
 use anchor_lang::prelude::*;
 
 declare_id!("DnLjPzpMCW2CF99URhGF3jDYnVRcCJMjUWsbPb4xVoBn");
@@ -14,12 +16,18 @@ declare_program!(token_messenger_minter);
 /// this spoke pool is instructed by the EVM hubpool for pool rebalancing and relayer repayment.
 ///
 /// For any issues, please reach out to bugs@across.to.
+#[cfg(feature = "no-entrypoint")]
+pub mod client;
 pub mod common;
 pub mod constants;
 mod constraints;
 pub mod error;
 pub mod event;
 mod instructions;
+pub mod interface;
+pub mod merkle;
+pub mod metadata;
+pub mod relay_admission;
 mod state;
 pub mod utils;
 
@@ -38,19 +46,26 @@ pub mod svm_spoke {
 
     /// Initializes the state for the SVM Spoke Pool. Only callable once.
     ///
+    /// Builds without the test feature reject non-zero seeds with InvalidProductionSeed. Production builds also opt into
+    /// the single-state feature, which pins the state seeds constraints of all instructions to 0, so that funds can't
+    /// be fragmented across states. The state initialized by such builds is marked canonical. Test builds keep
+    /// arbitrary seeds.
+    ///
     /// ### Required Accounts:
     /// - signer (Writable, Signer): The account that pays for the transaction and will own the state.
     /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
     /// - system_program: The system program required for account creation.
     ///
     /// ### Parameters:
-    /// - seed: A unique seed used to derive the state account's address. Must be 0 on Mainnet.
+    /// - seed: A unique seed used to derive the state account's address. Must be 0 unless built with the test feature.
     /// - initial_number_of_deposits: The initial number of deposits. Used to offset in upgrades.
     /// - chain_id: The chain ID for Solana, used to identify the Solana spoke in the rest of the Across protocol.
     /// - remote_domain: The CCTP domain for Mainnet Ethereum.
     /// - cross_domain_admin: The HubPool on Mainnet Ethereum.
     /// - deposit_quote_time_buffer: Quote timestamps can't be set more than this amount into the past from deposit.
     /// - fill_deadline_buffer: Fill deadlines can't be set more than this amount into the future from deposit.
+    /// - network: Network tag, 0 for mainnet, 1 for devnet and 2 for localnet. Mainnet and devnet require their Across
+    ///   Solana chain_id, while localnet rejects both of them.
     pub fn initialize(
         ctx: Context<Initialize>,
         seed: u64,
@@ -60,6 +75,7 @@ pub mod svm_spoke {
         cross_domain_admin: Pubkey,
         deposit_quote_time_buffer: u32,
         fill_deadline_buffer: u32,
+        network: u8,
     ) -> Result<()> {
         instructions::initialize(
             ctx,
@@ -70,9 +86,32 @@ pub mod svm_spoke {
             cross_domain_admin,
             deposit_quote_time_buffer,
             fill_deadline_buffer,
+            network,
         )
     }
 
+    /// Migrates the state PDA of a previous version to the current layout. Only callable by the owner.
+    ///
+    /// Accounts are not resized with the State struct, so states created before fields were appended to it are too
+    /// small for the current layout, and all other instructions reject states whose version is not
+    /// CURRENT_STATE_VERSION with StateNotMigrated. This reallocates the state to the current size, with the signer
+    /// paying the additional rent, zero-fills the appended fields and records the current version. States created
+    /// before the version was recorded get the network of their chain ID instead of the zeroed mainnet. Each version is
+    /// migrated once, states of the current version are rejected with StateAlreadyMigrated. States migrated to version
+    /// 3 by single-state builds are marked canonical, while states of non-zero seeds are rejected by such builds with
+    /// InvalidProductionSeed. Emits a MigratedState event.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner of the state, paying the rent for the extended size.
+    /// - state (Writable): Spoke state PDA of a previous version. Seed: ["state",seed] where seed is 0 on mainnet.
+    /// - system_program (Program): The system program.
+    ///
+    /// ### Parameters:
+    /// - _seed: The seed of the state, used to derive the state PDA.
+    pub fn migrate_state(ctx: Context<MigrateState>, _seed: u64) -> Result<()> {
+        instructions::migrate_state(ctx)
+    }
+
     /// Pauses the Spoke Pool from accepting deposits. Only callable by the owner.
     ///
     /// ### Required Accounts:
@@ -97,20 +136,55 @@ pub mod svm_spoke {
         instructions::pause_fills(ctx, pause)
     }
 
-    /// Transfers ownership of the Spoke Pool. Only callable by the current owner.
+    /// Pauses or unpauses fills with an optional auto-expiry. Only callable by the owner.
+    ///
+    /// Once the expiry has passed, fill instructions treat fills as unpaused and emit a PauseExpired event while the
+    /// stored flag is still set. Fill instructions keep the state read-only, so the flag itself is only cleared by the
+    /// next pause call.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the pause.
+    /// - state (Writable): The Spoke state PDA. Seed: ["state",state.seed], where `seed` is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - paused: `true` to pause fills, `false` to unpause them.
+    /// - expires_at: Timestamp when the pause expires. Must be in the future, or 0 to keep the pause indefinite.
+    ///   Ignored when unpausing.
+    pub fn pause_fills_with_expiry(ctx: Context<PauseFills>, paused: bool, expires_at: u32) -> Result<()> {
+        instructions::pause_fills_with_expiry(ctx, paused, expires_at)
+    }
+
+    /// Starts the transfer of ownership of the Spoke Pool. Only callable by the current owner.
+    ///
+    /// The new owner is only stored as pending and becomes the owner once it calls accept_ownership, so that a wrong
+    /// key cannot take over admin. Proposing another owner replaces the pending one.
     ///
     /// ### Required Accounts:
     /// - signer (Signer): The account that must be the current owner to authorize the transfer.
     /// - state (Writable): The Spoke state PDA. Seed: ["state",state.seed] where `seed` is 0 on mainnet.
     ///
     /// ### Parameters:
-    /// - new_owner: The public key of the new owner.
+    /// - new_owner: The public key of the pending owner.
     pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pubkey) -> Result<()> {
         instructions::transfer_ownership(ctx, new_owner)
     }
 
+    /// Completes the transfer of ownership of the Spoke Pool. Only callable by the pending owner.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The pending owner set by transfer_ownership.
+    /// - state (Writable): The Spoke state PDA. Seed: ["state",state.seed] where `seed` is 0 on mainnet.
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        instructions::accept_ownership(ctx)
+    }
+
     /// Enables or disables a route for deposits from origin token to destination chain ID. Callable only by the owner.
     ///
+    /// Enabling a route snapshots the origin token mint decimals that deposits on this route are then required to
+    /// match, as are fills, slow fill executions and refunds passing the route. Routes can not be enabled for Token-2022
+    /// mints with the PermanentDelegate or TransferHook extensions, as these make vault balances unreliable. Legacy
+    /// route PDAs must be migrated with migrate_route first.
+    ///
     /// ### Required Accounts:
     /// - signer (Signer): The account that must be the owner to authorize the route change.
     /// - payer (Signer): The account responsible for paying the transaction fees.
@@ -118,23 +192,168 @@ pub mod svm_spoke {
     /// - route (Writable): PDA to store route information. Created on the first call, updated subsequently.
     ///   Seed: ["route",origin_token,state.seed,destination_chain_id].
     /// - vault (Writable): ATA to hold the origin token for the associated route. Created on the first call.
-    ///   Authority must be set as the state, and mint must be the origin_token_mint.
+    ///   Authority must be set as the vault authority, and mint must be the origin_token_mint.
     /// - origin_token_mint: The mint account for the origin token.
     /// - token_program: The token program.
     /// - associated_token_program: The associated token program.
     /// - system_program: The system program required for account creation.
+    /// - lookup_table_config (Optional): The lookup table config PDA. Seed: ["lookup_table_config",state.seed]. When
+    ///   provided, enabling the route extends the canonical lookup table with the addresses of its corridor that it
+    ///   does not hold yet. Pass this program ID to represent None.
+    /// - lookup_table (Optional Writable): The canonical lookup table recorded in the lookup table config. Required
+    ///   with lookup_table_config. Pass this program ID to represent None.
+    /// - address_lookup_table_program (Optional): The Address Lookup Table program. Required with
+    ///   lookup_table_config. Pass this program ID to represent None.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning the vault. Seed: ["vault_authority",state].
     ///
     /// ### Parameters:
     /// - origin_token: The public key of the origin token.
     /// - destination_chain_id: The chain ID of the destination.
     /// - enabled: Boolean indicating whether the route is enabled or disabled.
+    /// - disable_at: Timestamp from which deposits on an enabled route are rejected, giving integrators advance notice
+    ///   before the route is sunset. Set to 0 for no scheduled disable. Replaces any previously scheduled time.
+    /// - decimal_shift: Power of 10 by which fills of origin_token from destination_chain_id scale output amounts, for
+    ///   corridors where the token has different decimals on both chains. Negative shifts round down and the absolute
+    ///   value must not exceed 18. Only applied when enabling the route, 0 keeps amounts unchanged.
     pub fn set_enable_route(
         ctx: Context<SetEnableRoute>,
         origin_token: Pubkey,
         destination_chain_id: u64,
         enabled: bool,
+        disable_at: u32,
+        decimal_shift: i8,
+    ) -> Result<()> {
+        instructions::set_enable_route(ctx, origin_token, destination_chain_id, enabled, disable_at, decimal_shift)
+    }
+
+    /// Enables or disables up to 10 routes in a single instruction. Callable only by the owner.
+    ///
+    /// Each route update is applied as in set_enable_route, creating its route PDA and vault if missing and emitting
+    /// an EnabledDepositRoute event. Any invalid route update reverts the whole batch.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the route changes.
+    /// - payer (Signer): The account responsible for paying the rent of created accounts.
+    /// - state: The Spoke state PDA. Seed: ["state",state.seed] where `seed` is 0 on mainnet.
+    /// - token_program: The token program owning the origin token mints of all route updates.
+    /// - associated_token_program: The associated token program.
+    /// - system_program: The system program required for account creation.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning the vaults. Seed: ["vault_authority",state].
+    /// - remaining_accounts: For each route update in order, the writable route PDA with seeds
+    ///   ["route",origin_token,state.seed,destination_chain_id], the writable vault ATA of the vault authority and
+    ///   the origin token mint.
+    ///
+    /// ### Parameters:
+    /// - route_updates: The origin_token, destination_chain_id, enabled, disable_at and decimal_shift of each route,
+    ///   with the same meaning as the set_enable_route parameters. Must hold between 1 and 10 entries.
+    pub fn set_enable_routes_batch<'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SetEnableRoutesBatch<'info>>,
+        route_updates: Vec<RouteUpdate>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        instructions::set_enable_routes_batch(ctx, route_updates)
+    }
+
+    /// Caps the vault balance that deposits through a route can build up to. Callable only by the owner.
+    ///
+    /// Deposits are rejected with DepositLimitExceeded when the vault balance plus the deposited amount would exceed
+    /// the cap. The vault is shared by all routes of the mint, so setting the same cap on all routes caps the mint
+    /// globally. Only new deposits are checked: fills, relayer refunds and slow fill payouts are never blocked, and
+    /// lowering the cap below the current vault balance keeps the existing funds.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - payer (Signer): The account responsible for paying the rent of the deposit limit PDA if it is created.
+    /// - state: The Spoke state PDA. Seed: ["state",state.seed] where `seed` is 0 on mainnet.
+    /// - deposit_limit (Writable): The deposit limit PDA of the route. Created on the first call, updated subsequently.
+    ///   Seed: ["deposit_limit",state.seed,mint,destination_chain_id].
+    /// - system_program: The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - mint: The input token mint of the route.
+    /// - destination_chain_id: The chain ID of the destination.
+    /// - max_outstanding: Maximum vault balance after a deposit through the route. u64::MAX for unlimited.
+    pub fn set_deposit_limit(
+        ctx: Context<SetDepositLimitState>,
+        mint: Pubkey,
+        destination_chain_id: u64,
+        max_outstanding: u64,
+    ) -> Result<()> {
+        instructions::set_deposit_limit(ctx, mint, destination_chain_id, max_outstanding)
+    }
+
+    /// Sets how slow fills of a route's token are settled. Callable only by the owner.
+    ///
+    /// In the default VaultTransfer mode slow fills transfer output tokens from the vault to the recipient. In the
+    /// BurnFromVault mode, meant for synthetic/receipt tokens, slow fills instead burn the vault held tokens and only
+    /// invoke the recipient handler. Slow fills look up the route keyed by their output token and origin chain ID.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the settlement mode change.
+    /// - state (Account): The Spoke state PDA. Seed: ["state",state.seed] where `seed` is 0 on mainnet.
+    /// - route (Writable): Existing route PDA. Seed: ["route",origin_token,state.seed,destination_chain_id].
+    ///
+    /// ### Parameters:
+    /// - origin_token: The public key of the origin token.
+    /// - destination_chain_id: The chain ID of the destination.
+    /// - settlement_mode: Settlement mode to use for the route.
+    pub fn set_route_settlement_mode(
+        ctx: Context<SetRouteSettlement>,
+        origin_token: Pubkey,
+        destination_chain_id: u64,
+        settlement_mode: SettlementMode,
+    ) -> Result<()> {
+        instructions::set_route_settlement_mode(ctx, origin_token, destination_chain_id, settlement_mode)
+    }
+
+    /// Sets the relayer fee floor of a route. Callable only by the owner.
+    ///
+    /// The floor is informational, helping relayers to catch mispriced fills. Fast fills passing the route of their
+    /// output token and origin chain are rejected with RelayerFeeBelowFloor when the fee implied by the input amount
+    /// and the normalized output amount is below the floor, unless the relayer accepts the low fee in the extra args.
+    /// Fills not passing the route are never checked.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the fee floor change.
+    /// - state (Account): The Spoke state PDA. Seed: ["state",state.seed] where `seed` is 0 on mainnet.
+    /// - route (Writable): Existing route PDA. Seed: ["route",origin_token,state.seed,destination_chain_id].
+    ///
+    /// ### Parameters:
+    /// - origin_token: The public key of the origin token.
+    /// - destination_chain_id: The chain ID of the destination.
+    /// - min_relayer_fee_bps: Minimum implied relayer fee of fast fills in bps, at most 10000. 0 disables the floor.
+    pub fn set_route_min_relayer_fee(
+        ctx: Context<SetRouteSettlement>,
+        origin_token: Pubkey,
+        destination_chain_id: u64,
+        min_relayer_fee_bps: u16,
     ) -> Result<()> {
-        instructions::set_enable_route(ctx, origin_token, destination_chain_id, enabled)
+        instructions::set_route_min_relayer_fee(ctx, origin_token, destination_chain_id, min_relayer_fee_bps)
+    }
+
+    /// Migrates a legacy Route PDA to the current layout. Permissionless.
+    ///
+    /// PDAs created when routes only tracked whether they were enabled have a smaller size that the instructions
+    /// reading routes, including set_enable_route, fail to deserialize. This reallocates them to the current size, with
+    /// the signer paying the additional rent, and records the canonical bump, the destination chain and the origin
+    /// token mint decimals checked on deposits, fills and refunds. Mint decimals are immutable, so the snapshot matches
+    /// the one the legacy route would have taken when it was enabled. All other appended fields keep their defaults.
+    /// PDAs of the current layout are rejected.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The account paying the rent for the extended size.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - route (Writable): The legacy route PDA to migrate. Seed: ["route",origin_token,state.seed,destination_chain_id].
+    /// - origin_token_mint (Account): Mint of the origin token.
+    /// - system_program (Program): The system program.
+    ///
+    /// ### Parameters:
+    /// - _origin_token: The public key of the origin token, used to derive the route PDA.
+    /// - destination_chain_id: The chain ID of the destination.
+    pub fn migrate_route(ctx: Context<MigrateRoute>, _origin_token: Pubkey, destination_chain_id: u64) -> Result<()> {
+        instructions::migrate_route(ctx, destination_chain_id)
     }
 
     /// Sets the cross-domain admin for the Spoke Pool. Only callable by owner. Used if Hubpool upgrades.
@@ -149,34 +368,232 @@ pub mod svm_spoke {
         instructions::set_cross_domain_admin(ctx, cross_domain_admin)
     }
 
+    /// Sets the messenger program relaying HubPool messages to handle_receive_message. Only callable by the local
+    /// owner, never relayed remotely.
+    ///
+    /// The messenger replaces the CCTP Message Transmitter as the only caller of handle_receive_message and must sign
+    /// with its ["message_transmitter_authority",svm_spoke_program_id] PDA. Messages are still authenticated against
+    /// the remote domain and cross-domain admin of the state. The messenger is trusted to reject replayed messages, as
+    /// the Message Transmitter does with its used nonces.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - admin_messenger: The messenger program, or None to receive messages from the Message Transmitter again.
+    pub fn set_admin_messenger(ctx: Context<SetAdminMessengerState>, admin_messenger: Option<Pubkey>) -> Result<()> {
+        instructions::set_admin_messenger(ctx, admin_messenger)
+    }
+
+    /// Configures vault balance snapshots of relayer refund bundles. Only callable by the owner.
+    ///
+    /// When enabled, every relayer refund leaf execution requires the bundle audit PDA and records the vault balance
+    /// before the first and after the latest leaf for each mint. Cost-sensitive deployments leave this disabled.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: Whether relayer refund leaf executions snapshot vault balances.
+    /// - max_mints: Number of mints each newly initialized bundle audit PDA can track.
+    pub fn set_bundle_audit_config(
+        ctx: Context<SetBundleAuditConfigState>,
+        enabled: bool,
+        max_mints: u8,
+    ) -> Result<()> {
+        instructions::set_bundle_audit_config(ctx, enabled, max_mints)
+    }
+
+    /// Configures per-signer rate limits of slow fill requests. Only callable by the owner.
+    ///
+    /// When enabled, each signer requesting slow fills must pass their requester activity PDA and can make at most
+    /// limit requests within each window. Rate limits are disabled by default.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: Whether slow fill requests are rate limited.
+    /// - limit: Maximum number of slow fill requests per signer within each window.
+    /// - window: Length of the rate limit window in seconds.
+    pub fn set_slow_fill_rate_limit(
+        ctx: Context<SetSlowFillRateLimitState>,
+        enabled: bool,
+        limit: u32,
+        window: u32,
+    ) -> Result<()> {
+        instructions::set_slow_fill_rate_limit(ctx, enabled, limit, window)
+    }
+
+    /// Sets the deposit quote time buffer. Only callable by the owner.
+    ///
+    /// Deposits are rejected with InvalidQuoteTimestamp when their quote timestamp is in the future or more than this
+    /// amount into the past, while a quote timestamp exactly at the buffer edge is accepted, same as in the EVM
+    /// SpokePool. Emits a SetDepositQuoteTimeBuffer event.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - deposit_quote_time_buffer: The new buffer in seconds. Must be non-zero and at most MAX_DEPOSIT_BUFFER, else
+    ///   this fails with InvalidDepositBuffer.
+    pub fn set_deposit_quote_time_buffer(
+        ctx: Context<SetDepositBufferState>,
+        deposit_quote_time_buffer: u32,
+    ) -> Result<()> {
+        instructions::set_deposit_quote_time_buffer(ctx, deposit_quote_time_buffer)
+    }
+
+    /// Sets the fill deadline buffer. Only callable by the owner.
+    ///
+    /// Deposits are rejected with InvalidFillDeadline when their fill deadline is in the past or more than this amount
+    /// into the future, while a fill deadline exactly at the buffer edge is accepted, same as in the EVM SpokePool.
+    /// Fills and slow fill requests also reject relays whose fill deadline is older than this buffer. Emits a
+    /// SetFillDeadlineBuffer event.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - fill_deadline_buffer: The new buffer in seconds. Must be non-zero and at most MAX_DEPOSIT_BUFFER, else this
+    ///   fails with InvalidDepositBuffer.
+    pub fn set_fill_deadline_buffer(ctx: Context<SetDepositBufferState>, fill_deadline_buffer: u32) -> Result<()> {
+        instructions::set_fill_deadline_buffer(ctx, fill_deadline_buffer)
+    }
+
+    /// Sets a State config field by key. Only callable by the owner.
+    ///
+    /// This is the generic escape hatch for minor config additions that the HubPool can relay with the
+    /// setConfig(bytes32,bytes32) message, without requiring a dedicated message type. Supported keys are
+    /// deposit_quote_time_buffer, fill_deadline_buffer, slow_fill_rate_limit, slow_fill_rate_window,
+    /// slow_fill_grace_seconds, snapshot_min_interval, params_buffer_ttl, relayer_lapse_threshold_bps,
+    /// strict_chain_aliases, exclusive_slow_fills_disabled and emergency_executor. The emergency_executor
+    /// value is the executor public key, or zero bytes to clear it. Boolean values are ABI encoded bools. Deposit
+    /// buffers are bounded the same as in set_deposit_quote_time_buffer and set_fill_deadline_buffer.
+    ///
+    /// Returns whether the change was applied, i.e. false for resubmissions of an already consumed idempotency key.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change. Pays the rent of the
+    ///   admin_nonce PDA when passing an idempotency key.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - admin_nonce (Writable): Optional admin nonce recording the idempotency key, only required when passing one.
+    ///   Pass this program ID to represent None. Seed: ["admin_nonce",state.seed,idempotency_key].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - key: The State field name, right padded with zero bytes.
+    /// - value: The new value of the field, ABI encoded as a 32 bytes big-endian word.
+    /// - idempotency_key: Optional key of automation resubmitting this instruction. Resubmissions with a consumed key
+    ///   return false without re-applying the instruction or emitting its events.
+    pub fn set_config(
+        ctx: Context<SetConfigState>,
+        key: [u8; 32],
+        value: [u8; 32],
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<bool> {
+        instructions::set_config(ctx, key, value, idempotency_key)
+    }
+
+    /// Freezes closing of fill status, claim account and root bundle PDAs until the given time. Only callable by the
+    /// owner, typically relayed by the HubPool while a bundle containing fills of this spoke is under dispute.
+    ///
+    /// The freeze also applies to closes of accounts that were already eligible for closing. It ends on expiry or by
+    /// setting dispute_freeze_until to 0.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - dispute_freeze_until: Time until which closing PDAs is rejected, or 0 to clear the freeze.
+    pub fn set_dispute_freeze(ctx: Context<SetDisputeFreezeState>, dispute_freeze_until: u32) -> Result<()> {
+        instructions::set_dispute_freeze(ctx, dispute_freeze_until)
+    }
+
+    /// Announces a program upgrade. Only callable by the owner.
+    ///
+    /// From the effective slot until the announcement is cleared, instructions that start multi-transaction flows, i.e.
+    /// initializing instruction params, are rejected with UpgradePending so that no state is left partially processed
+    /// across a change of account layouts. Completing already started flows remains allowed.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - effective_after_slot: Slot from which new multi-transaction flows are rejected.
+    pub fn announce_upgrade(ctx: Context<UpgradeAnnouncementState>, effective_after_slot: u64) -> Result<()> {
+        instructions::announce_upgrade(ctx, effective_after_slot)
+    }
+
+    /// Clears the announced program upgrade, typically once the upgrade has been deployed. Only callable by the owner.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    pub fn clear_upgrade_announcement(ctx: Context<UpgradeAnnouncementState>) -> Result<()> {
+        instructions::clear_upgrade_announcement(ctx)
+    }
+
     /// Stores a new root bundle for later execution. Only callable by the owner.
     ///
     /// Once stored, these roots are used to execute relayer refunds, slow fills, and pool rebalancing actions.
     /// This method initializes a root_bundle PDA to store the root bundle data. The caller
     /// of this method is responsible for paying the rent for this PDA.
     ///
+    /// Returns whether the bundle was relayed, i.e. false for resubmissions of an already consumed idempotency key.
+    /// Such resubmissions must derive the root_bundle PDA from the current root_bundle_id, as automation rebuilding
+    /// the transaction does, and the root_bundle PDA is closed again within the same instruction.
+    ///
     /// ### Required Accounts:
     /// - signer (Signer): The account that must be the owner to authorize the addition of the new root bundle.
-    /// - payer (Signer): The account who pays rent to create root_bundle PDA.
+    /// - payer (Signer): The account who pays rent to create root_bundle and admin_nonce PDAs.
     /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
     /// - root_bundle (Writable): The newly created bundle PDA to store root bundle data. Each root bundle has an
     ///   incrementing ID, stored in the state. Seed: ["root_bundle",state.seed,root_bundle_id].
     /// - system_program (Program): The system program required for account creation.
+    /// - admin_nonce (Writable): Optional admin nonce recording the idempotency key, only required when passing one.
+    ///   Pass this program ID to represent None. Seed: ["admin_nonce",state.seed,idempotency_key].
     ///
     /// ### Parameters:
     /// - relayer_refund_root: Merkle root of the relayer refund tree.
     /// - slow_relay_root: Merkle root of the slow relay tree.
+    /// - execution_deadline: Time after which slow relay leaves from this bundle can no longer be executed. Set to 0
+    ///   for no deadline.
+    /// - restricted_executor: If set, only this key can execute slow relay leaves from this bundle until the executor
+    ///   grace period has passed, after which execution becomes permissionless. Set to None for no restriction.
+    /// - executor_grace_period: Seconds from relaying the bundle during which the restricted executor applies.
+    /// - idempotency_key: Optional key of automation resubmitting this instruction. Resubmissions with a consumed key
+    ///   return false without re-applying the instruction or emitting its events.
     pub fn relay_root_bundle(
         ctx: Context<RelayRootBundle>,
         relayer_refund_root: [u8; 32],
         slow_relay_root: [u8; 32],
-    ) -> Result<()> {
-        instructions::relay_root_bundle(ctx, relayer_refund_root, slow_relay_root)
+        execution_deadline: u32,
+        restricted_executor: Option<Pubkey>,
+        executor_grace_period: u32,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<bool> {
+        instructions::relay_root_bundle(
+            ctx,
+            relayer_refund_root,
+            slow_relay_root,
+            execution_deadline,
+            restricted_executor,
+            executor_grace_period,
+            idempotency_key,
+        )
     }
 
     /// Deletes a root bundle in case of emergencies where bad bundle has reached the Spoke. Only callable by the owner.
     ///
-    /// This function will close the PDA for the associated `root_bundle_id`.
+    /// This function will close the PDA for the associated `root_bundle_id`. Rejected during the dispute freeze window.
     /// Note: Using this function does not decrement `state.root_bundle_id`.
     ///
     /// ### Required Accounts:
@@ -194,92 +611,546 @@ pub mod svm_spoke {
         instructions::emergency_delete_root_bundle(ctx, root_bundle_id)
     }
 
-    /// **************************************
-    ///          DEPOSIT FUNCTIONS           *
-    /// *************************************
+    /// Starts an irreversible wind down of the Spoke Pool when it is being decommissioned. Only callable by the owner.
+    ///
+    /// Deposits and new slow fill requests are blocked immediately, while existing fills and root bundles can still
+    /// be executed. After wind_down_delay has passed, remaining vault balances can be swept with sweep_vault_to_hub.
+    /// The delay must be at least MIN_WIND_DOWN_DELAY, so that relays of deposits made right before the wind down can
+    /// still be filled and refunded.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the wind down.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - wind_down_delay: Seconds after the wind down start before vaults can be swept.
+    pub fn wind_down(ctx: Context<WindDown>, wind_down_delay: u32) -> Result<()> {
+        instructions::wind_down(ctx, wind_down_delay)
+    }
 
-    /// Request to bridge input_token to a target chain and receive output_token.
+    /// Sweeps the remaining vault balance back to the hub once the wind down delay has passed. Only callable by the
+    /// owner, even for tokens with enabled routes.
     ///
-    /// The fee paid to relayers and the system is captured in the spread between the input and output amounts,
-    /// denominated in the input token. A relayer on the destination chain will send `output_amount` of `output_token`
-    /// to the recipient and receive `input_token` on a repayment chain of their choice. The fee accounts for:
-    /// Destination transaction costs, relayer's opportunity cost of capital while waiting for a refund during the
-    /// optimistic challenge window in the HubPool, and the system fee charged to the relayer.
+    /// Pending slow fills, refunds owed to claim accounts and liquidity owed to the pool of the mint stay in the vault,
+    /// so that they can still be paid out. For CCTP supported tokens (USDC) the remaining vault balance not yet pending
+    /// to the Hub Pool is added to the transfer_liability, so it can be bridged to the Hub Pool with the permissionless
+    /// bridge_tokens_to_hub_pool. Other tokens are recovered to the owner's ATA.
     ///
-    /// On the destination chain, a unique hash of the deposit data is used to identify this deposit. Modifying any
-    /// parameters will result in a different hash, creating a separate deposit. The hash is computed using all parameters
-    /// of this function along with the chain's `chainId()`. Relayers are refunded only for deposits with hashes that
-    /// exactly match those emitted by this contract.
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the sweep.
+    /// - payer (Signer): The account who pays rent to create transfer_liability PDA if needed.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (InterfaceAccount): The mint account for the token being swept.
+    /// - vault (Writable): The ATA for the swept mint. Authority must be the vault authority.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - transfer_liability (Writable): Account tracking the pending amount to be sent to the Hub Pool.
+    ///   Seed: ["transfer_liability",mint].
+    /// - local_token (Account): Optional CCTP local token account. Pass this program ID to represent None for non-CCTP
+    ///   tokens. Seed: ["local_token",mint] (CCTP Token Messenger Minter program).
+    /// - owner_token_account (Writable): Optional ATA of the owner receiving non-CCTP tokens. Pass this program ID to
+    ///   represent None for CCTP tokens.
+    /// - rate_limit (Writable): Optional rate limit of the mint, only required for sweeps to the owner when vault
+    ///   outflow rate limits are enabled. Pass this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    /// - slow_fill_liability (Account): Pending slow fill amount of the mint, which is not swept. Treated as nothing
+    ///   pending if not created yet. Seed: ["slow_fill_liability",state.seed,mint].
+    /// - claim_liability (Account): Refunds of the mint owed to claim accounts, which are not swept. Treated as nothing
+    ///   owed if not created yet. Seed: ["claim_liability",state.seed,mint].
+    /// - liquidity_pool (Account): Liquidity pool of the mint, whose owed liquidity is not swept. Treated as nothing
+    ///   owed if not initialized. Seed: ["liquidity_pool",state.seed,mint].
+    pub fn sweep_vault_to_hub(ctx: Context<SweepVaultToHub>) -> Result<()> {
+        instructions::sweep_vault_to_hub(ctx)
+    }
+
+    /// Sweeps tokens sent to a vault outside of deposits, such as airdrops or mistaken transfers. Only callable by the
+    /// owner.
+    ///
+    /// Deposits credit the vault liability of their mint while sent relayer refunds and slow fill payouts debit it, so
+    /// only the vault balance exceeding the liabilities can be swept. Emits a TokensSwept event. Returns whether the
+    /// tokens were swept, i.e. false for resubmissions of an already consumed idempotency key.
     ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the deposit.
-    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
-    /// - route (Account): The route PDA for the particular bridged route in question. Validates a route is enabled.
-    ///   Seed: ["route",input_token,state.seed,destination_chain_id].
-    /// - depositor_token_account (Writable): The depositor's ATA for the input token.
-    /// - vault (Writable): Programs ATA for the associated input token. This is where the depositor's assets are sent.
-    ///   Authority must be the state.
-    /// - mint (Account): The mint account for the input token.
+    /// - signer (Signer): The account that must be the owner to authorize the sweep.
+    /// - payer (Signer): The account who pays rent to create vault_liability and admin_nonce PDAs if needed.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (InterfaceAccount): The mint account for the token being swept.
+    /// - vault (Writable): The ATA for the swept mint. Authority must be the vault authority.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - vault_liability (Writable): Liabilities of the vault. Seed: ["vault_liability",state.seed,mint].
+    /// - slow_fill_liability (Account): Pending slow fill amount of the mint, which is not swept either. Treated as
+    ///   nothing pending if not created yet. Seed: ["slow_fill_liability",state.seed,mint].
+    /// - liquidity_pool (Account): Liquidity pool of the mint, whose owed liquidity is not swept either, even while
+    ///   liquidity pools are disabled. Treated as nothing owed if not initialized.
+    ///   Seed: ["liquidity_pool",state.seed,mint].
+    /// - recipient_token_account (Writable): Token account of the recipient for the swept mint.
+    /// - rate_limit (Writable): Optional rate limit of the mint, only required when vault outflow rate limits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
     /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    /// - admin_nonce (Writable): Optional admin nonce recording the idempotency key, only required when passing one.
+    ///   Pass this program ID to represent None. Seed: ["admin_nonce",state.seed,idempotency_key].
     ///
-    /// ### Parameters
-    /// - depositor: The account credited with the deposit. Can be different from the signer.
-    /// - recipient: The account receiving funds on the destination chain. Depending on the output chain can be an ETH
-    ///     address or a contract address or any other address type encoded as a bytes32 field.
-    /// - input_token: The token pulled from the caller's account and locked into this program's vault on deposit.
-    /// - output_token: The token that the relayer will send to the recipient on the destination chain.
-    /// - input_amount: The amount of input tokens to pull from the caller's account and lock into the vault. This
-    ///   amount will be sent to the relayer on their repayment chain of choice as a refund following an optimistic
-    ///   challenge window in the HubPool, less a system fee.
-    /// - output_amount: The amount of output tokens that the relayer will send to the recipient on the destination.
-    /// - destination_chain_id: The destination chain identifier. Must be enabled along with the input token as a valid
-    ///   deposit route from this spoke pool or this transaction will revert.
-    /// - exclusive_relayer: The relayer that will be exclusively allowed to fill this deposit before the exclusivity
-    ///   deadline timestamp. This must be a valid, non-zero address if the exclusivity deadline is greater than the
-    ///   current block timestamp.
-    /// - quote_timestamp: The HubPool timestamp that is used to determine the system fee paid by the depositor. This
-    ///   must be set to some time between [currentTime - depositQuoteTimeBuffer, currentTime].
-    /// - fill_deadline: The deadline for the relayer to fill the deposit. After this destination chain timestamp, the
-    ///   fill will revert on the destination chain. Must be set between [currentTime,currentTime+fillDeadlineBuffer].
-    /// - exclusivity_parameter: Sets the exclusivity deadline timestamp for the exclusiveRelayer to fill the deposit.
-    ///   1. If 0, no exclusivity period.
-    ///   2. If less than MAX_EXCLUSIVITY_PERIOD_SECONDS, adds this value to the current block timestamp.
-    ///   3. Otherwise, uses this value as the exclusivity deadline timestamp.
-    /// - message: The message to send to the recipient on the destination chain if the recipient is a contract.
-    ///   If not empty, the recipient contract must implement handleV3AcrossMessage() or the fill will revert.
-    pub fn deposit_v3(
-        ctx: Context<DepositV3>,
-        depositor: Pubkey,
+    /// ### Parameters:
+    /// - amount: Amount of tokens to sweep. Must not exceed the vault balance over its liabilities.
+    /// - recipient: Owner of the recipient token account.
+    /// - idempotency_key: Optional key of automation resubmitting this instruction. Resubmissions with a consumed key
+    ///   return false without re-applying the instruction or emitting its events.
+    pub fn sweep_tokens(
+        ctx: Context<SweepTokens>,
+        amount: u64,
         recipient: Pubkey,
-        input_token: Pubkey,
-        output_token: Pubkey,
-        input_amount: u64,
-        output_amount: u64,
-        destination_chain_id: u64,
-        exclusive_relayer: Pubkey,
-        quote_timestamp: u32,
-        fill_deadline: u32,
-        exclusivity_parameter: u32,
-        message: Vec<u8>,
-    ) -> Result<()> {
-        instructions::deposit_v3(
-            ctx,
-            depositor,
-            recipient,
-            input_token,
-            output_token,
-            input_amount,
-            output_amount,
-            destination_chain_id,
-            exclusive_relayer,
-            quote_timestamp,
-            fill_deadline,
-            exclusivity_parameter,
-            message,
-        )
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<bool> {
+        instructions::sweep_tokens(ctx, amount, recipient, idempotency_key)
     }
 
-    // Equivalent to deposit_v3 except quote_timestamp is set to the current time.
+    /// Moves the vault of a mint from the state PDA to the vault authority PDA. Only callable by the owner.
+    ///
+    /// The state PDA signs configuration changes, so vaults are owned by a dedicated authority that keeps their funds
+    /// out of reach of any instruction exposing the state as a signer. Vaults are created for the vault authority, and
+    /// vault instructions only accept its ATA, so state vaults created before it hold funds that only this migration
+    /// moves, being the only vault outflow signed by the state. The whole state vault balance is transferred to the ATA
+    /// of the vault authority, created if missing. The state vault is kept open with the vault authority as its close
+    /// authority, which marks it as migrated. Closing it instead would let anyone recreate it, and would fail for
+    /// Token-2022 vaults holding withheld transfer fees. Fails with VaultMigrationBlocked whenever
+    /// preview_vault_migration reports blockers, i.e. while either vault is frozen or slow fills, claims or pool
+    /// liquidity of the mint are pending. Emits a MigratedVaultAuthority event. Returns whether tokens were migrated,
+    /// i.e. false when the state vault was already migrated and holds no tokens, while tokens sent to it afterwards are
+    /// moved by repeated migrations.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the migration.
+    /// - payer (Signer): The account who pays rent to create the new vault.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (InterfaceAccount): The mint account of the migrated vault.
+    /// - state_vault (Writable): The ATA of the state for the mint, marked as migrated by the migration.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA. Seed: ["vault_authority",state].
+    /// - vault (Writable): The ATA of the vault authority for the mint. Created if missing.
+    /// - token_program (Interface): The token program.
+    /// - associated_token_program (Interface): The associated token program.
+    /// - system_program (Program): The system program required for account creation.
+    /// - slow_fill_liability (UncheckedAccount): Slow fill liability PDA of the mint, treated as nothing pending when
+    ///   missing. Seed: ["slow_fill_liability",state.seed,mint].
+    /// - claim_liability (UncheckedAccount): Claim liability PDA of the mint, treated as nothing owed when missing.
+    ///   Seed: ["claim_liability",state.seed,mint].
+    /// - liquidity_pool (UncheckedAccount): Liquidity pool PDA of the mint, treated as nothing owed when missing.
+    ///   Seed: ["liquidity_pool",state.seed,mint].
+    pub fn migrate_vault_authority(ctx: Context<MigrateVaultAuthority>) -> Result<bool> {
+        instructions::migrate_vault_authority(ctx)
+    }
+
+    /// Previews migrate_vault_authority for a mint without migrating it. This acts like a "view" function for
+    /// operators checking the migration before running it, and emits no events.
+    ///
+    /// Runs the same checks as the migration, which fails with VaultMigrationBlocked exactly when blockers are
+    /// reported. The returned VaultMigrationPreview holds the VAULT_MIGRATION_* bits of the failed checks along with
+    /// the state vault balance moved by the migration, whether the vault authority ATA already exists and the
+    /// obligations pending against the mint:
+    /// - VAULT_MIGRATION_STATE: The state is new_state and has the current layout.
+    /// - VAULT_MIGRATION_FROZEN: Neither the state vault nor the vault authority ATA is frozen.
+    /// - VAULT_MIGRATION_SLOW_FILLS: No requested slow fills of the mint are pending execution.
+    /// - VAULT_MIGRATION_CLAIMS: No relayer refunds of the mint are pending in claim accounts.
+    /// - VAULT_MIGRATION_POOL_LIQUIDITY: No liquidity is owed to the pool of the mint.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (InterfaceAccount): The mint account of the previewed vault.
+    /// - state_vault (UncheckedAccount): The ATA of the state for the mint, which may not exist.
+    /// - vault (UncheckedAccount): The ATA of the vault authority for the mint, which may not exist.
+    /// - slow_fill_liability (UncheckedAccount): Slow fill liability PDA of the mint, treated as nothing pending when
+    ///   missing. Seed: ["slow_fill_liability",state.seed,mint].
+    /// - claim_liability (UncheckedAccount): Claim liability PDA of the mint, treated as nothing owed when missing.
+    ///   Seed: ["claim_liability",state.seed,mint].
+    /// - liquidity_pool (UncheckedAccount): Liquidity pool PDA of the mint, treated as nothing owed when missing.
+    ///   Seed: ["liquidity_pool",state.seed,mint].
+    ///
+    /// ### Parameters:
+    /// - mint: The mint of the previewed vault, which must match the mint account.
+    /// - new_state: The state whose vault authority receives the vault, which must match the state account.
+    pub fn preview_vault_migration(
+        ctx: Context<PreviewVaultMigration>,
+        mint: Pubkey,
+        new_state: Pubkey,
+    ) -> Result<VaultMigrationPreview> {
+        instructions::preview_vault_migration(ctx, mint, new_state)
+    }
+
+    /// Moves the vault flows of a mint off its frozen state vault to the vault authority PDA. Only callable by the
+    /// owner.
+    ///
+    /// Freeze authorities of mints can freeze the state vault, which can then neither pay out nor be migrated by
+    /// migrate_vault_authority. The ATA of the vault authority is created if missing, so that slow fills, refunds and
+    /// deposits passing it run again once it is funded with new liquidity, while payouts from the frozen vault fail
+    /// with VaultFrozen. The frozen vault is left in place, and migrate_vault_authority moves its balance once thawed.
+    /// Fails with VaultFrozen when the mint freezes the new vault as well. Emits a MigratedFrozenVault event.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the migration.
+    /// - payer (Signer): The account who pays rent to create the new vault.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (InterfaceAccount): The mint account of the frozen vault.
+    /// - frozen_vault (InterfaceAccount): The frozen ATA of the state for the mint.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA. Seed: ["vault_authority",state].
+    /// - vault (Writable): The ATA of the vault authority for the mint. Created if missing.
+    /// - token_program (Interface): The token program.
+    /// - associated_token_program (Interface): The associated token program.
+    /// - system_program (Program): The system program required for account creation.
+    pub fn migrate_frozen_vault(ctx: Context<MigrateFrozenVault>) -> Result<()> {
+        instructions::migrate_frozen_vault(ctx)
+    }
+
+    /// Overwrites the transfer liability ledger of a mint with its recomputed value. Only callable by the owner.
+    ///
+    /// The pending amount to the Hub Pool is best-effort and can drift after emergency root bundle deletions or
+    /// migrations. The authoritative value is recomputed off-chain from the emitted events and must be within
+    /// RECONCILE_COUNTERS_MAX_DEVIATION_BPS of the current value unless force is set. Old and new values are emitted
+    /// in the ReconciledCounters event. The reconciliation time is recorded in the state and reported by
+    /// get_vault_capacity.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the reconciliation.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - transfer_liability (Writable): Account tracking the pending amount to be sent to the Hub Pool.
+    ///   Seed: ["transfer_liability",mint].
+    ///
+    /// ### Parameters:
+    /// - mint: The mint whose counters are reconciled.
+    /// - pending_to_hub_pool: The recomputed amount pending to be sent to the Hub Pool.
+    /// - force: True to write values outside of the sanity band around the current values.
+    pub fn reconcile_counters(
+        ctx: Context<ReconcileCounters>,
+        mint: Pubkey,
+        pending_to_hub_pool: u64,
+        force: bool,
+    ) -> Result<()> {
+        instructions::reconcile_counters(ctx, mint, pending_to_hub_pool, force)
+    }
+
+    /// Sets or clears the compliance authority. Callable only by the owner.
+    ///
+    /// When set, fills, slow fill requests and slow fill leaf executions reject relays whose depositor or recipient is
+    /// on the blocklist maintained by the compliance authority. When None, no compliance accounts are required.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - compliance_authority: The account allowed to maintain the blocklist, or None to disable compliance checks.
+    pub fn set_compliance_authority(
+        ctx: Context<SetComplianceAuthorityState>,
+        compliance_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_compliance_authority(ctx, compliance_authority)
+    }
+
+    /// Adds an address to the blocklist. Callable only by the compliance authority.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The compliance authority that also pays for the blocklist entry creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - compliance_attestation (Writable): The blocklist entry PDA to create. Seed: ["compliance",state.seed,subject].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - subject: The depositor or recipient address to block.
+    pub fn add_compliance_entry(ctx: Context<AddComplianceEntry>, subject: Pubkey) -> Result<()> {
+        instructions::add_compliance_entry(ctx, subject)
+    }
+
+    /// Removes an address from the blocklist. Callable only by the compliance authority that receives the rent.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The compliance authority.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - compliance_attestation (Writable): The blocklist entry PDA to close. Seed: ["compliance",state.seed,subject].
+    ///
+    /// ### Parameters:
+    /// - subject: The depositor or recipient address to unblock.
+    pub fn remove_compliance_entry(ctx: Context<RemoveComplianceEntry>, subject: Pubkey) -> Result<()> {
+        instructions::remove_compliance_entry(ctx, subject)
+    }
+
+    /// Sets, rotates or clears the fill confirmation oracle. Callable only by the owner.
+    ///
+    /// Fill confirmations already posted keep the oracle key that attested them.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - fill_confirmation_oracle: The oracle key attesting destination fills, or None to disable fill confirmations.
+    pub fn set_fill_confirmation_oracle(
+        ctx: Context<SetFillConfirmationOracleState>,
+        fill_confirmation_oracle: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_fill_confirmation_oracle(ctx, fill_confirmation_oracle)
+    }
+
+    /// Sets, rotates or clears the event forwarder program. Callable only by the owner.
+    ///
+    /// When set, fill_v3_relay additionally invokes the forward_event(event: Vec<u8>) instruction of the forwarder
+    /// without accounts, passing the discriminator prefixed FilledV3Relay event data of up to 1024 bytes.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - event_forwarder: The program that fill events are forwarded to, or None to disable event forwarding.
+    /// - best_effort: When set, fills skip forwarding instead of failing when the forwarder account is missing or
+    ///   invalid or the event is too large. Errors returned by the forwarder program still fail the fill.
+    pub fn set_event_forwarder(
+        ctx: Context<SetEventForwarderState>,
+        event_forwarder: Option<Pubkey>,
+        best_effort: bool,
+    ) -> Result<()> {
+        instructions::set_event_forwarder(ctx, event_forwarder, best_effort)
+    }
+
+    /// Exports fill status PDAs for disaster recovery. This acts like a "view" function for off-chain actors reading
+    /// the state to import into a new deployment of the program.
+    ///
+    /// Returns an export page of EXPORT_FILL_STATUS_RECORD_SIZE records packing the relay hash, status (u8), relayer,
+    /// fill_deadline (u32, little-endian), requester and payer of each fill status. Pages start with an
+    /// EXPORT_HEADER_SIZE header of the layout version, record count and continuation token documented next to the
+    /// EXPORT_* constants. Legacy fill status layouts are exported with their missing fields zeroed.
+    ///
+    /// ### Required Accounts:
+    /// - remaining_accounts: FillStatusAccount PDAs of the passed relay hashes, in the same order.
+    ///   Seed: ["fills",relay_hash].
+    ///
+    /// ### Parameters:
+    /// - page: Index of the first account to export. 0 for the first page, else the continuation token of the
+    ///   previous page that is 0 once all accounts were exported.
+    /// - relay_hashes: The relay hashes of the fill status PDAs, as they can't be derived back from the PDAs.
+    pub fn export_fill_statuses(ctx: Context<Null>, page: u32, relay_hashes: Vec<[u8; 32]>) -> Result<Vec<u8>> {
+        instructions::export_fill_statuses(ctx, page, relay_hashes)
+    }
+
+    /// Exports claim account PDAs for disaster recovery. This acts like a "view" function for off-chain actors
+    /// reading the state to import into a new deployment of the program.
+    ///
+    /// Returns an export page of EXPORT_CLAIM_ACCOUNT_RECORD_SIZE records packing the mint, refund address, amount
+    /// (u64, little-endian) and initializer of each claim account.
+    ///
+    /// ### Required Accounts:
+    /// - remaining_accounts: ClaimAccount PDAs of the passed claim keys, in the same order.
+    ///   Seed: ["claim_account",mint,refund_address].
+    ///
+    /// ### Parameters:
+    /// - page: Index of the first account to export. 0 for the first page, else the continuation token of the
+    ///   previous page that is 0 once all accounts were exported.
+    /// - claim_keys: The mint and refund address seeds of the claim account PDAs.
+    pub fn export_claim_accounts(ctx: Context<Null>, page: u32, claim_keys: Vec<ClaimAccountKey>) -> Result<Vec<u8>> {
+        instructions::export_claim_accounts(ctx, page, claim_keys)
+    }
+
+    /// Exports transfer liability PDAs for disaster recovery. This acts like a "view" function for off-chain actors
+    /// reading the state to import into a new deployment of the program.
+    ///
+    /// Returns an export page of EXPORT_LIABILITY_RECORD_SIZE records packing the mint and pending_to_hub_pool (u64,
+    /// little-endian) of each transfer liability.
+    ///
+    /// ### Required Accounts:
+    /// - remaining_accounts: TransferLiability PDAs of the passed mints, in the same order.
+    ///   Seed: ["transfer_liability",mint].
+    ///
+    /// ### Parameters:
+    /// - page: Index of the first account to export. 0 for the first page, else the continuation token of the
+    ///   previous page that is 0 once all accounts were exported.
+    /// - mints: The mints of the transfer liability PDAs.
+    pub fn export_liabilities(ctx: Context<Null>, page: u32, mints: Vec<Pubkey>) -> Result<Vec<u8>> {
+        instructions::export_liabilities(ctx, page, mints)
+    }
+
+    /// Imports a page of fill statuses exported from a previous deployment. Callable only by the owner while the
+    /// state is in its genesis window, i.e. before its first deposit or relayed root bundle.
+    ///
+    /// Each record creates its FillStatusAccount PDA, so records of PDAs that already exist fail the import. Records
+    /// keep their exported payer, which receives the rent when the PDA is closed.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner of the state, paying the rent of the created PDAs.
+    /// - state: Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - system_program: The system program required for account creation.
+    /// - remaining_accounts: Writable FillStatusAccount PDAs to create, in the order of the records.
+    ///   Seed: ["fills",relay_hash].
+    ///
+    /// ### Parameters:
+    /// - page: The export page as returned by export_fill_statuses.
+    pub fn import_fill_statuses<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportState<'info>>,
+        page: Vec<u8>,
+    ) -> Result<()> {
+        instructions::import_fill_statuses(ctx, page)
+    }
+
+    /// Imports a page of claim accounts exported from a previous deployment. Callable only by the owner while the
+    /// state is in its genesis window, i.e. before its first deposit or relayed root bundle.
+    ///
+    /// Each record creates its ClaimAccount PDA, so records of PDAs that already exist fail the import. The imported
+    /// amounts are only claimable once the vaults of the new deployment hold them.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner of the state, paying the rent of the created PDAs.
+    /// - state: Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - system_program: The system program required for account creation.
+    /// - remaining_accounts: Writable ClaimAccount PDAs to create, in the order of the records.
+    ///   Seed: ["claim_account",mint,refund_address].
+    ///
+    /// ### Parameters:
+    /// - page: The export page as returned by export_claim_accounts.
+    pub fn import_claim_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportState<'info>>,
+        page: Vec<u8>,
+    ) -> Result<()> {
+        instructions::import_claim_accounts(ctx, page)
+    }
+
+    /// Imports a page of transfer liabilities exported from a previous deployment. Callable only by the owner while
+    /// the state is in its genesis window, i.e. before its first deposit or relayed root bundle.
+    ///
+    /// Each record creates its TransferLiability PDA, so records of PDAs that already exist fail the import.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner of the state, paying the rent of the created PDAs.
+    /// - state: Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - system_program: The system program required for account creation.
+    /// - remaining_accounts: Writable TransferLiability PDAs to create, in the order of the records.
+    ///   Seed: ["transfer_liability",mint].
+    ///
+    /// ### Parameters:
+    /// - page: The export page as returned by export_liabilities.
+    pub fn import_liabilities<'info>(ctx: Context<'_, '_, '_, 'info, ImportState<'info>>, page: Vec<u8>) -> Result<()> {
+        instructions::import_liabilities(ctx, page)
+    }
+
+    /// **************************************
+    ///          DEPOSIT FUNCTIONS           *
+    /// *************************************
+
+    /// Request to bridge input_token to a target chain and receive output_token.
+    ///
+    /// The fee paid to relayers and the system is captured in the spread between the input and output amounts,
+    /// denominated in the input token. A relayer on the destination chain will send `output_amount` of `output_token`
+    /// to the recipient and receive `input_token` on a repayment chain of their choice. The fee accounts for:
+    /// Destination transaction costs, relayer's opportunity cost of capital while waiting for a refund during the
+    /// optimistic challenge window in the HubPool, and the system fee charged to the relayer.
+    ///
+    /// On the destination chain, a unique hash of the deposit data is used to identify this deposit. Modifying any
+    /// parameters will result in a different hash, creating a separate deposit. The hash is computed using all parameters
+    /// of this function along with the chain's `chainId()`. Relayers are refunded only for deposits with hashes that
+    /// exactly match those emitted by this contract.
+    ///
+    /// Depositors that initialized a depositor nonce have their deposits strictly ordered: each deposit must pass the
+    /// next nonce of the depositor via version 5 extra args, which is incremented and emitted with the deposit, and
+    /// deposits with any other or no nonce are rejected with NonceMismatch. As deposit_v3_now and unsafe_deposit_v3 can
+    /// not pass a nonce, they are rejected for such depositors.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the deposit.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - destination_chain_alias (Account): Chain alias of the destination chain ID, translated into the canonical
+    ///   chain ID of the route, deposit limit and emitted event. Only required for aliases and in strict chain alias
+    ///   mode. Pass this program ID to represent None. Seed: ["chain_alias",state.seed,destination_chain_id].
+    /// - route (Account): The route PDA for the particular bridged route in question. Validates a route is enabled.
+    ///   Seed: ["route",input_token,state.seed,destination_chain_id].
+    /// - deposit_limit (Account): The deposit limit PDA of the route, rejecting deposits that would make the vault
+    ///   balance exceed its cap. Routes without a created deposit limit are unlimited.
+    ///   Seed: ["deposit_limit",state.seed,input_token,destination_chain_id].
+    /// - depositor_nonce (Writable): The depositor nonce PDA of the depositor, incremented by the deposit. Deposits of
+    ///   depositors without a created depositor nonce are unordered. Seed: ["depositor_nonce",state.seed,depositor].
+    /// - vault_registry (Account): The vault PDA of the input token. Mints whose vault was registered by create_vault
+    ///   must pass its token program, others fail with InvalidVault. Seed: ["vault",state.seed,input_token].
+    /// - depositor_token_account (Writable): The depositor's ATA for the input token.
+    /// - vault (Writable): Programs ATA for the associated input token. This is where the depositor's assets are sent.
+    ///   Authority must be the state.
+    /// - mint (Account): The mint account for the input token.
+    /// - vault_liability (Writable): Liabilities of the input token vault, credited with the deposited amount. Created
+    ///   if needed. Seed: ["vault_liability",state.seed,input_token].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters
+    /// - depositor: The account credited with the deposit. Can be different from the signer.
+    /// - recipient: The account receiving funds on the destination chain. Depending on the output chain can be an ETH
+    ///     address or a contract address or any other address type encoded as a bytes32 field.
+    /// - input_token: The token pulled from the caller's account and locked into this program's vault on deposit.
+    /// - output_token: The token that the relayer will send to the recipient on the destination chain.
+    /// - input_amount: The amount of input tokens to pull from the caller's account and lock into the vault. This
+    ///   amount will be sent to the relayer on their repayment chain of choice as a refund following an optimistic
+    ///   challenge window in the HubPool, less a system fee. For Token-2022 input tokens with a transfer fee, the
+    ///   amount actually received by the vault is recorded and emitted instead.
+    /// - output_amount: The amount of output tokens that the relayer will send to the recipient on the destination.
+    /// - destination_chain_id: The destination chain identifier. Must be enabled along with the input token as a valid
+    ///   deposit route from this spoke pool or this transaction will revert.
+    /// - exclusive_relayer: The relayer that will be exclusively allowed to fill this deposit before the exclusivity
+    ///   deadline timestamp. This must be a valid, non-zero address if the exclusivity deadline is greater than the
+    ///   current block timestamp.
+    /// - quote_timestamp: The HubPool timestamp that is used to determine the system fee paid by the depositor. This
+    ///   must be set to some time between [currentTime - depositQuoteTimeBuffer, currentTime].
+    /// - fill_deadline: The deadline for the relayer to fill the deposit. After this destination chain timestamp, the
+    ///   fill will revert on the destination chain. Must be set between [currentTime,currentTime+fillDeadlineBuffer].
+    /// - exclusivity_parameter: Sets the exclusivity deadline timestamp for the exclusiveRelayer to fill the deposit.
+    ///   1. If 0, no exclusivity period.
+    ///   2. If at most MAX_EXCLUSIVITY_PERIOD_SECONDS, adds this value to the current block timestamp.
+    ///   3. Otherwise, uses this value as the exclusivity deadline timestamp.
+    ///   This mirrors the EVM SpokePool, and V3FundsDeposited carries both this parameter and the resolved deadline.
+    /// - message: The message to send to the recipient on the destination chain if the recipient is a contract.
+    ///   If not empty, the recipient contract must implement handleV3AcrossMessage() or the fill will revert.
+    ///   Messages above MAX_MESSAGE_SIZE bytes are rejected (MessageTooLarge), and messages above
+    ///   MAX_INLINE_EVENT_MESSAGE_SIZE bytes are emitted empty in V3FundsDeposited alongside their keccak message_hash.
+    /// - revoke_after: If true, revokes any delegation of the state PDA left on the depositor token account after
+    ///   pulling the input tokens. Only applies when the depositor signs this instruction directly, otherwise ignored.
+    /// - args_version: Version of the extra args blob. Only versions 0 and 5 are supported, others are rejected.
+    /// - extra: Extra args encoded according to args_version. Must be empty for version 0, version 5 carries the
+    ///   depositor nonce.
+    pub fn deposit_v3(
+        ctx: Context<DepositV3>,
+        depositor: Pubkey,
+        recipient: Pubkey,
+        input_token: Pubkey,
+        output_token: Pubkey,
+        input_amount: u64,
+        output_amount: u64,
+        destination_chain_id: u64,
+        exclusive_relayer: Pubkey,
+        quote_timestamp: u32,
+        fill_deadline: u32,
+        exclusivity_parameter: u32,
+        message: Vec<u8>,
+        revoke_after: bool,
+        args_version: u8,
+        extra: Vec<u8>,
+    ) -> Result<()> {
+        instructions::deposit_v3(
+            ctx,
+            depositor,
+            recipient,
+            input_token,
+            output_token,
+            input_amount,
+            output_amount,
+            destination_chain_id,
+            exclusive_relayer,
+            quote_timestamp,
+            fill_deadline,
+            exclusivity_parameter,
+            message,
+            revoke_after,
+            args_version,
+            extra,
+        )
+    }
+
+    // Equivalent to deposit_v3 except quote_timestamp is set to the current time.
     pub fn deposit_v3_now(
         ctx: Context<DepositV3>,
         depositor: Pubkey,
@@ -313,6 +1184,10 @@ pub mod svm_spoke {
     /// Equivalent to deposit_v3 except the deposit_nonce is not used to derive the deposit_id for the depositor. This
     /// Lets the caller influence the deposit ID to make it deterministic for the depositor. The computed depositID is
     /// the keccak256 hash of [signer, depositor, deposit_nonce].
+    ///
+    /// Same as on EVM, the chain ID is not hashed as relay hashes already bind the origin chain. Emitted
+    /// V3FundsDeposited events set unsafe_deposit_id to tell these IDs apart from the sequential ones. Nonces are not
+    /// tracked, so reusing one repeats the deposit ID and identical deposits can then only be filled once.
     pub fn unsafe_deposit_v3(
         ctx: Context<DepositV3>,
         depositor: Pubkey,
@@ -347,58 +1222,330 @@ pub mod svm_spoke {
         )
     }
 
-    /// Computes the deposit ID for the depositor using the provided deposit_nonce. This acts like a "view" function for
-    /// off-chain actors to compute what the expected deposit ID is for a given depositor and deposit nonce will be.
-    ///
-    /// ### Parameters:
-    /// - signer: The public key of the depositor sender.
-    /// - depositor: The public key of the depositor.
-    /// - deposit_nonce: The nonce used to derive the deposit ID.
-    pub fn get_unsafe_deposit_id(
-        _ctx: Context<Null>,
-        signer: Pubkey,
-        depositor: Pubkey,
-        deposit_nonce: u64,
-    ) -> Result<[u8; 32]> {
-        Ok(utils::get_unsafe_deposit_id(signer, depositor, deposit_nonce))
-    }
-
-    /// **************************************
-    ///          RELAYER FUNCTIONS           *
-    /// *************************************
-
-    /// Fulfill request to bridge cross chain by sending specified output tokens to recipient.
-    ///
-    /// Relayer & system fee is captured in the spread between input and output amounts. This fee accounts for tx costs,
-    /// relayer's capital opportunity cost, and a system fee. The relay_data hash uniquely identifies the deposit to
-    /// fill, ensuring relayers are refunded only for deposits matching the original hash from the origin SpokePool.
-    /// This hash includes all parameters from deposit_v3() and must match the destination_chain_id. Note the relayer
-    /// creates an ATA in calling this method to store the fill_status. This should be closed once the deposit has
-    /// expired to let the relayer re-claim their rent. Cannot fill more than once. Partial fills are not supported.
+    /// Equivalent to deposit_v3 except the deposit is paid in native SOL by the signer. The lamports are wrapped into
+    /// the wSOL vault of the state and the deposit event is emitted with the wSOL mint as input_token.
     ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the fill (filler). No permission requirements.
-    /// - instruction_params (Account): Optional account to load instruction parameters when they are not passed in the
-    ///   instruction data due to message size constraints. Pass this program ID to represent None. When Some, this must
-    ///   be derived from the signer's public key with seed ["instruction_params",signer].
+    /// - signer (Signer): The depositor paying the deposited lamports.
     /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
-    /// - route (Account): The route PDA for the particular bridged route in question. Validates a route is enabled.
-    ///   Seed: ["route",input_token,state.seed,destination_chain_id].
-    /// - vault (Writable): The ATA for refunded mint. Authority must be the state.
-    /// - mint (Account): The mint of the output token, sent from the relayer to the recipient.
-    /// - relayer_token_account (Writable): The relayer's ATA for the input token.
-    /// - recipient_token_account (Writable): The recipient's ATA for the output token.
-    /// - fill_status (Writable): The fill status PDA, created on this function call to track the fill status to prevent
-    ///   re-entrancy & double fills. Also used to track requested slow fills. Seed: ["fills",relay_hash].
-    /// - token_program (Interface): The token program.
-    /// - associated_token_program (Interface): The associated token program.
-    /// - system_program (Interface): The system program.
+    /// - destination_chain_alias (Account): Chain alias of the destination chain ID, translated into the canonical
+    ///   chain ID of the route, deposit limit and emitted event. Only required for aliases and in strict chain alias
+    ///   mode. Pass this program ID to represent None. Seed: ["chain_alias",state.seed,destination_chain_id].
+    /// - route (Account): The route PDA of the wSOL mint towards the destination chain. Validates a route is enabled.
+    ///   Seed: ["route",wsol_mint,state.seed,destination_chain_id].
+    /// - deposit_limit (Account): The deposit limit PDA of the route, as in deposit_v3.
+    ///   Seed: ["deposit_limit",state.seed,wsol_mint,destination_chain_id].
+    /// - vault (Writable): Programs wSOL ATA, receiving the wrapped lamports. Authority must be the vault authority.
+    /// - mint (Account): The wSOL mint account.
+    /// - vault_liability (Writable): Liabilities of the wSOL vault, credited with the deposited amount. Created if
+    ///   needed. Seed: ["vault_liability",state.seed,wsol_mint].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - token_program (Program): The SPL Token program.
+    /// - system_program (Program): The system program.
+    /// - native_wrap_account (Writable): Temporary wSOL account of the state, wrapping the deposited lamports before
+    ///   they are moved into the vault. Created and closed within the deposit, returning its rent and any lamports it
+    ///   held before to the signer. These leftovers are emitted as dust_refunded in the V3FundsDeposited event.
+    ///   Seed: ["native_wrap",state.seed,signer].
     ///
     /// ### Parameters:
-    /// - _relay_hash: The hash identifying the deposit to be filled. Caller must pass this in. Computed as hash of
-    ///   the flattened relay_data & destination_chain_id.
+    /// - recipient: The account receiving funds on the destination chain.
+    /// - output_token: The token that the relayer will send to the recipient on the destination chain.
+    /// - input_amount: The amount of lamports to wrap into the vault.
+    /// - output_amount: The amount of output tokens that the relayer will send to the recipient on the destination.
+    /// - destination_chain_id: The destination chain identifier, must be enabled along with the wSOL mint.
+    /// - exclusive_relayer, quote_timestamp, fill_deadline, exclusivity_parameter, message: Same as in deposit_v3.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_v3_native(
+        ctx: Context<DepositV3Native>,
+        recipient: Pubkey,
+        output_token: Pubkey,
+        input_amount: u64,
+        output_amount: u64,
+        destination_chain_id: u64,
+        exclusive_relayer: Pubkey,
+        quote_timestamp: u32,
+        fill_deadline: u32,
+        exclusivity_parameter: u32,
+        message: Vec<u8>,
+    ) -> Result<()> {
+        instructions::deposit_v3_native(
+            ctx,
+            recipient,
+            output_token,
+            input_amount,
+            output_amount,
+            destination_chain_id,
+            exclusive_relayer,
+            quote_timestamp,
+            fill_deadline,
+            exclusivity_parameter,
+            message,
+        )
+    }
+
+    /// Equivalent to deposit_v3 except the depositor pays another token that is swapped into the input token within
+    /// the same transaction. The swap program must be enabled with set_swap_adapter and is invoked with the swap_route
+    /// as instruction data and the remaining accounts, without any signature of this program. The vault balance
+    /// increase over the swap is deposited as the input_amount, and the whole transaction reverts when it is below
+    /// min_input_amount. SwappedForDeposit is emitted along with V3FundsDeposited to report the swapped token and
+    /// amount.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The depositor paying the swapped tokens. Its signature is forwarded to the swap program.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - destination_chain_alias (Account): Chain alias of the destination chain ID, translated into the canonical
+    ///   chain ID of the route, deposit limit and emitted event. Only required for aliases and in strict chain alias
+    ///   mode. Pass this program ID to represent None. Seed: ["chain_alias",state.seed,destination_chain_id].
+    /// - route (Account): The route PDA of the input token towards the destination chain, as in deposit_v3.
+    ///   Seed: ["route",input_token,state.seed,destination_chain_id].
+    /// - deposit_limit (Account): The deposit limit PDA of the route, as in deposit_v3.
+    ///   Seed: ["deposit_limit",state.seed,input_token,destination_chain_id].
+    /// - vault_registry (Account): The vault PDA of the input token, as in deposit_v3.
+    ///   Seed: ["vault",state.seed,input_token].
+    /// - swap_adapter (Account): The enabled swap adapter PDA of the swap program.
+    ///   Seed: ["swap_adapter",state.seed,swap_program].
+    /// - swap_program (Program): The swap program to invoke.
+    /// - swap_in_mint (Account): The mint of the token swapped by the depositor.
+    /// - depositor_swap_in_token_account (Writable): The depositor's token account of the swapped token.
+    /// - vault (Writable): Programs ATA for the input token, receiving the swap output. Authority must be the vault
+    ///   authority.
+    /// - mint (Account): The mint account for the input token.
+    /// - vault_liability (Writable): Liabilities of the input token vault, credited with the deposited amount. Created
+    ///   if needed. Seed: ["vault_liability",state.seed,input_token].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - token_program (Interface): The token program of the input token.
+    /// - swap_in_token_program (Interface): The token program of the swapped token.
+    /// - system_program (Program): The system program required for account creation.
+    /// - remaining_accounts: The accounts of the swap instruction, passed to the swap program in order with their
+    ///   signer and writable privileges.
+    ///
+    /// ### Parameters:
+    /// - recipient, input_token, output_token, output_amount, destination_chain_id: Same as in deposit_v3.
+    /// - min_input_amount: The minimum amount of input tokens the swap must credit to the vault.
+    /// - exclusive_relayer, quote_timestamp, fill_deadline, exclusivity_parameter, message: Same as in deposit_v3.
+    /// - swap_route: Instruction data of the swap program.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_v3_with_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositV3WithSwap<'info>>,
+        recipient: Pubkey,
+        input_token: Pubkey,
+        output_token: Pubkey,
+        min_input_amount: u64,
+        output_amount: u64,
+        destination_chain_id: u64,
+        exclusive_relayer: Pubkey,
+        quote_timestamp: u32,
+        fill_deadline: u32,
+        exclusivity_parameter: u32,
+        message: Vec<u8>,
+        swap_route: Vec<u8>,
+    ) -> Result<()> {
+        instructions::deposit_v3_with_swap(
+            ctx,
+            recipient,
+            input_token,
+            output_token,
+            min_input_amount,
+            output_amount,
+            destination_chain_id,
+            exclusive_relayer,
+            quote_timestamp,
+            fill_deadline,
+            exclusivity_parameter,
+            message,
+            swap_route,
+        )
+    }
+
+    /// Initializes the depositor nonce PDA of the signer, opting its deposits into strict ordering.
+    ///
+    /// Once created, every deposit_v3 of the signer as depositor must pass the next nonce, starting at 0.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The depositor whose deposits are ordered. Pays for the account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - depositor_nonce (Writable): The depositor nonce PDA to initialize.
+    ///   Seed: ["depositor_nonce",state.seed,signer].
+    /// - system_program: The system program required for account creation.
+    pub fn initialize_depositor_nonce(_ctx: Context<InitializeDepositorNonce>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Closes the signer's depositor nonce PDA and returns its rent, opting its deposits out of ordering.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The depositor whose depositor nonce is closed. Receives the rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - depositor_nonce (Writable): The depositor nonce PDA to close.
+    ///   Seed: ["depositor_nonce",state.seed,signer].
+    pub fn close_depositor_nonce(ctx: Context<CloseDepositorNonce>) -> Result<()> {
+        instructions::close_depositor_nonce(ctx)
+    }
+
+    /// Sets the next nonce of a depositor, e.g. to recover an integration that lost track of its sequence. Only
+    /// callable by the owner.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the reset.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - depositor_nonce (Writable): The depositor nonce PDA to reset.
+    ///   Seed: ["depositor_nonce",state.seed,depositor].
+    ///
+    /// ### Parameters:
+    /// - depositor: The depositor whose nonce is reset.
+    /// - next_nonce: The nonce required in the next deposit of the depositor.
+    pub fn reset_depositor_nonce(
+        ctx: Context<ResetDepositorNonceState>,
+        depositor: Pubkey,
+        next_nonce: u64,
+    ) -> Result<()> {
+        instructions::reset_depositor_nonce(ctx, depositor, next_nonce)
+    }
+
+    /// Computes the deposit ID for the depositor using the provided deposit_nonce. This acts like a "view" function for
+    /// off-chain actors to compute what the expected deposit ID is for a given depositor and deposit nonce will be.
+    ///
+    /// ### Parameters:
+    /// - signer: The public key of the depositor sender.
+    /// - depositor: The public key of the depositor.
+    /// - deposit_nonce: The nonce used to derive the deposit ID.
+    pub fn get_unsafe_deposit_id(
+        _ctx: Context<Null>,
+        signer: Pubkey,
+        depositor: Pubkey,
+        deposit_nonce: u64,
+    ) -> Result<[u8; 32]> {
+        Ok(utils::get_unsafe_deposit_id(signer, depositor, deposit_nonce))
+    }
+
+    /// Records that an SVM-origin deposit was filled on its destination chain, as attested by the fill confirmation
+    /// oracle.
+    ///
+    /// This is informational only and does not move any funds. Anyone can post the attestation, which is the oracle's
+    /// signature over the deposit_id followed by the destination_fill_event_hash. The signature must be verified by an
+    /// Ed25519 program instruction immediately preceding this instruction. Each deposit can only be confirmed once.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that posts the attestation and pays the rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_confirmation (Writable): The fill confirmation PDA, created on this function call.
+    ///   Seed: ["deposit_filled",state.seed,deposit_id].
+    /// - instructions_sysvar (Account): The instructions sysvar used to load the Ed25519 verification instruction.
+    /// - system_program (Interface): The system program.
+    ///
+    /// ### Parameters:
+    /// - deposit_id: The ID of the deposit made on this spoke.
+    /// - destination_fill_event_hash: The hash of the fill event on the destination chain.
+    /// - attestation: The oracle's Ed25519 signature over the deposit_id and destination_fill_event_hash.
+    pub fn post_fill_confirmation(
+        ctx: Context<PostFillConfirmation>,
+        deposit_id: [u8; 32],
+        destination_fill_event_hash: [u8; 32],
+        attestation: [u8; 64],
+    ) -> Result<()> {
+        instructions::post_fill_confirmation(ctx, deposit_id, destination_fill_event_hash, attestation)
+    }
+
+    /// **************************************
+    ///          RELAYER FUNCTIONS           *
+    /// *************************************
+
+    /// Fulfill request to bridge cross chain by sending specified output tokens to recipient.
+    ///
+    /// Relayer & system fee is captured in the spread between input and output amounts. This fee accounts for tx costs,
+    /// relayer's capital opportunity cost, and a system fee. The relay_data hash uniquely identifies the deposit to
+    /// fill, ensuring relayers are refunded only for deposits matching the original hash from the origin SpokePool.
+    /// This hash includes all parameters from deposit_v3() and must match the destination_chain_id. Note the relayer
+    /// creates an ATA in calling this method to store the fill_status. This should be closed once the deposit has
+    /// expired to let the relayer re-claim their rent. Cannot fill more than once. Partial fills are not supported.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the fill (filler), recorded as the relayer in the fill_status
+    ///   PDA. No permission requirements.
+    /// - instruction_params (Account): Optional account to load instruction parameters when they are not passed in the
+    ///   instruction data due to message size constraints. Pass this program ID to represent None. When Some, this must
+    ///   be derived from the signer's public key with seed ["instruction_params",signer].
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - route (Account): Route of the output token towards the origin chain, only required for corridors with a
+    ///   non-zero decimal shift. Also checks the implied relayer fee against the fee floor of the route when passed.
+    ///   Pass this program ID to represent None. Seed: ["route",mint,state.seed,origin_chain_id].
+    /// - vault (Writable): The ATA for refunded mint. Authority must be the state.
+    /// - vault_registry (Account): The vault PDA of the mint. Mints whose vault was registered by create_vault must
+    ///   pass its token program, others fail with InvalidVault. Seed: ["vault",state.seed,mint].
+    /// - mint (Account): The mint of the output token, sent from the relayer to the recipient.
+    /// - relayer_token_account (Writable): The relayer's ATA for the input token.
+    /// - recipient_token_account (Writable): The recipient's token account for the output token, usually their ATA. Any
+    ///   token account whose owner is the recipient is accepted. Pass this program ID to represent None when the
+    ///   recipient opted into escrow delivery or for message-only relays.
+    /// - delivery_preference (Account): Optional delivery preference set by the recipient. When its mode is Escrow, the
+    ///   output tokens are sent to the recipient escrow. Pass this program ID to represent None.
+    ///   Seed: ["delivery_preference",state.seed,recipient].
+    /// - recipient_escrow (Writable): The recipient escrow for the output token, only required when the recipient
+    ///   opted into escrow delivery. Pass this program ID to represent None.
+    ///   Seed: ["escrow",state.seed,recipient,mint].
+    /// - native_unwrap_account (Writable): Temporary wSOL account of the state, only required for wSOL outputs when
+    ///   the recipient opted into native delivery. Created and closed within the fill so that the recipient receives
+    ///   lamports. Pass this program ID to represent None. Seed: ["native_unwrap",state.seed,signer].
+    /// - native_recipient (Writable): The relay recipient receiving the unwrapped lamports, only required together with
+    ///   the native unwrap account. Pass this program ID to represent None.
+    /// - fill_status (Writable): The fill status PDA, created on this function call to track the fill status to prevent
+    ///   re-entrancy & double fills. Also used to track requested slow fills. Seed: ["fills",relay_hash].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the mint, only required when replacing a requested
+    ///   slow fill, releasing its output amount. Pass this program ID to represent None.
+    ///   Seed: ["slow_fill_liability",state.seed,mint].
+    /// - fill_volume (Writable): Fill volume of the mint, credited with the normalized output amount. Ignored if the
+    ///   owner did not opt the mint into refund checks. Seed: ["fill_volume",state.seed,mint].
+    /// - message_schema (Account): Message schema PDA of the recipient, required even when no schema is registered.
+    ///   Non-empty messages must match the size bounds and schema tag of a registered schema.
+    ///   Seed: ["message_schema",recipient].
+    /// - depositor_compliance (Account): Blocklist entry of the depositor, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    /// - recipient_compliance (Account): Blocklist entry of the recipient, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    /// - allowlist_entry (Account): Allowlist entry of the signer, only required in permissioned mode for signers
+    ///   holding the relayer role. Pass this program ID to represent None. Seed: ["allowlist",state.seed,signer].
+    /// - repayment_address_account (Account): The repayment address, only required when the extra args request
+    ///   creating the repayment token account. Pass this program ID to represent None.
+    /// - repayment_token_account (Writable): The repayment address ATA for the output token, created idempotently when
+    ///   the extra args request it. Pass this program ID to represent None.
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - fill_index_shard (Writable): Fill index shard of the deposit id, only required when the fill index is enabled
+    ///   and the deposit id fits into u64. Created if missing. Pass this program ID to represent None.
+    ///   Seed: ["fill_index",state.seed,origin_chain_id,deposit_id/1024].
+    /// - origin_chain_alias (Account): Chain alias of the origin chain ID, only required in strict chain alias mode.
+    ///   Rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent None.
+    ///   Seed: ["chain_alias",state.seed,origin_chain_id].
+    /// - relayer_stats (Writable): Stats of the exclusive relayer, counting fills by them within the exclusivity
+    ///   window. Only pass when the relay names an exclusive relayer. Created if missing. Pass this program ID to
+    ///   represent None. Seed: ["relayer_stats",state.seed,exclusive_relayer].
+    /// - memo_program (Account): The SPL Memo program, only required when a payout memo is passed in the extra args.
+    ///   Pass this program ID to represent None.
+    /// - event_forwarder (Account): The event forwarder program, only required when the state has an event forwarder.
+    ///   Pass this program ID to represent None.
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - associated_token_program (Interface): The associated token program.
+    /// - system_program (Interface): The system program.
+    /// - payer (Writable, Signer): The account paying the rent of the fill_status PDA and any other account created by
+    ///   the fill, which it reclaims when they are closed. Pass the signer when the relayer pays its own rent, or a
+    ///   separate funding account so that the relayer authority can hold no SOL.
+    /// - recipient_address_account (Account): The relay recipient, only required when the extra args request creating
+    ///   the recipient token account. Pass this program ID to represent None.
+    /// - recipient_ata (Writable): The recipient ATA for the output token, created idempotently by the payer when the
+    ///   extra args request it and receiving the payout in place of recipient_token_account, which is then passed as
+    ///   None. Pass this program ID to represent None.
+    ///
+    /// ### Parameters:
+    /// - relay_hash: The hash identifying the deposit to be filled. Caller must pass this in. Computed as hash of
+    ///   the flattened relay_data & destination_chain_id. Emitted in the FilledV3Relay event.
     /// - relay_data: Struct containing all the data needed to identify the deposit to be filled. Should match
-    ///   all the same-named parameters emitted in the origin chain V3FundsDeposited event.
+    ///   all the same-named parameters emitted in the origin chain V3FundsDeposited event. Relay data with a default
+    ///   recipient or output_token, or a fill_deadline older than the fill deadline buffer is rejected, as are zero
+    ///   output_amount relays with an empty message (EmptyRelay). Message-only relays with a zero output_amount skip
+    ///   the token transfer and the recipient token account, only running the message handler, and are flagged as
+    ///   message_only in the FilledV3Relay event.
     ///   - depositor: The account credited with the deposit.
     ///   - recipient: The account receiving funds on this chain.
     ///   - input_token: The token pulled from the caller's account to initiate the deposit. The equivalent of this
@@ -414,137 +1561,1330 @@ pub mod svm_spoke {
     ///   - exclusivity_deadline: The deadline for the exclusive relayer to fill the deposit. After this timestamp,
     ///     anyone can fill this deposit.
     ///   - message: The message to send to the recipient if the recipient is a contract that implements a
-    ///     handle_v3_across_message() public function.
+    ///     handle_v3_across_message() public function. Messages prefixed with the "ACXA" tag and a version byte are
+    ///     decoded as an action list invoking up to 4 handlers in order over the remaining accounts. Handlers are
+    ///     invoked with the output token, the sent amount, the signer as relayer and the handler message. Any handler
+    ///     failure reverts the fill and handlers that are not executable programs are rejected. Messages above
+    ///     MAX_MESSAGE_SIZE bytes are rejected (MessageTooLarge).
     /// - repayment_chain_id: Chain of SpokePool where relayer wants to be refunded after the challenge window has
-    ///     passed. Will receive input_amount of the equivalent token to input_token on the repayment chain.
-    /// - repayment_address: The address of the recipient on the repayment chain that they want to be refunded to.
+    ///     passed. Will receive input_amount of the equivalent token to input_token on the repayment chain. Must not be
+    ///     zero.
+    /// - repayment_address: The address of the recipient on the repayment chain that they want to be refunded to, as
+    ///     bytes32 with EVM addresses left padded with zeros. Must not be zero, nor an EVM address when repaid on this
+    ///     chain, where refunds go to its ATA or accrue to its claim account while the ATA does not exist.
+    /// - args_version: Version of the extra args blob. Versions 0 to 4 and 6 are supported, unknown versions are
+    ///   rejected.
+    /// - extra: Extra args encoded according to args_version. Must be empty for version 0. Version 1 is the borsh
+    ///   serialized optional payout memo of 1 to 64 bytes, logged via the SPL Memo program right before the payout
+    ///   transfer and hashed into the emitted memo_hash. The memo is mandatory for Token-2022 recipient accounts that
+    ///   require incoming transfer memos. Version 2 adds a create_repayment_token_account flag after the memo, only
+    ///   allowed when repayment_chain_id is this chain, so that the refund leaf can pay the relayer directly. Version 3
+    ///   adds an optional 32 byte origin_tx_ref after the flag, emitted as is in the fill event so that the fill can be
+    ///   traced back to its origin chain deposit transaction. It is neither hashed nor validated and all-zero is
+    ///   treated as absent. Version 4 adds an accept_low_fee flag after the origin_tx_ref, proceeding with fills whose
+    ///   implied relayer fee is below the fee floor of the passed route instead of failing with RelayerFeeBelowFloor.
+    ///   Version 6 adds a create_recipient_token_account flag after the accept_low_fee flag, idempotently creating the
+    ///   recipient ATA to pay out to so that fills to fresh wallets need no separate ATA creation. It is ignored when
+    ///   the recipient opted into escrow or native delivery.
     /// Note: relay_data, repayment_chain_id, and repayment_address are optional parameters. If None for any of these
     /// is passed, the caller must load them via the instruction_params account.
     pub fn fill_v3_relay<'info>(
         ctx: Context<'_, '_, '_, 'info, FillV3Relay<'info>>,
-        _relay_hash: [u8; 32],
+        relay_hash: [u8; 32],
         relay_data: Option<V3RelayData>,
         repayment_chain_id: Option<u64>,
         repayment_address: Option<Pubkey>,
+        args_version: u8,
+        extra: Vec<u8>,
+    ) -> Result<()> {
+        instructions::fill_v3_relay(
+            ctx,
+            relay_hash,
+            relay_data,
+            repayment_chain_id,
+            repayment_address,
+            args_version,
+            extra,
+        )
+    }
+
+    /// Fulfills a deposit with the output amount, recipient and message updated by the depositor.
+    ///
+    /// Equivalent of fillV3RelayWithUpdatedDeposit on the EVM SpokePool, allowing depositors to speed up deposits.
+    /// The fill_status PDA is still keyed on the original relay hash, so that the deposit can be filled only once with
+    /// either its original or updated values. The depositor signs the keccak hash of the borsh serialized chain_id of
+    /// this spoke, relay_hash, updated_output_amount, updated_recipient and the hash of the updated_message (zeroed
+    /// bytes32 for empty message). Depositors with EVM addresses sign with secp256k1, while Solana depositors sign with
+    /// Ed25519 and the signature must be verified by an Ed25519 program instruction immediately preceding this
+    /// instruction. All other checks are the same as in fill_v3_relay, except that escrow delivery, payout memos and
+    /// event forwarding are not supported. Signed updates cannot be used after the fill deadline and each signature
+    /// can only be used once.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The relayer filling the relay, recorded as the relayer in the fill_status PDA.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - vault_registry (Account): The vault PDA of the mint. Mints whose vault was registered by create_vault must
+    ///   pass its token program, others fail with InvalidVault. Seed: ["vault",state.seed,mint].
+    /// - mint (Account): The mint of the output token, sent from the relayer to the updated recipient.
+    /// - relayer_token_account (Writable): The relayer's token account for the output token.
+    /// - recipient_token_account (Writable): The updated recipient's ATA for the output token. Pass this program ID to
+    ///   represent None when the updated_output_amount is zero.
+    /// - fill_status (Writable): The fill status PDA of the original relay, created on this function call.
+    ///   Seed: ["fills",relay_hash].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the mint, only required when replacing a requested
+    ///   slow fill, releasing its output amount. Pass this program ID to represent None.
+    ///   Seed: ["slow_fill_liability",state.seed,mint].
+    /// - fill_volume (Writable): Fill volume of the mint, credited with the normalized output amount. Ignored if the
+    ///   owner did not opt the mint into refund checks. Seed: ["fill_volume",state.seed,mint].
+    /// - used_signature (Writable): Records the consumed depositor signature to reject its replays, created on this
+    ///   function call. Seed: ["used_signature",state.seed,keccak(depositor_signature)[..16]].
+    /// - message_schema (Account): Message schema PDA of the updated recipient, required even when no schema is
+    ///   registered. Non-empty updated messages must match the size bounds and schema tag of a registered schema.
+    ///   Seed: ["message_schema",updated_recipient].
+    /// - depositor_compliance (Account): Blocklist entry of the depositor, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    /// - recipient_compliance (Account): Blocklist entry of the updated recipient, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None.
+    ///   Seed: ["compliance",state.seed,updated_recipient].
+    /// - allowlist_entry (Account): Allowlist entry of the signer, only required in permissioned mode for signers
+    ///   holding the relayer role. Pass this program ID to represent None. Seed: ["allowlist",state.seed,signer].
+    /// - route (Account): Route of the output token towards the origin chain, only required for corridors with a
+    ///   non-zero decimal shift. Pass this program ID to represent None.
+    ///   Seed: ["route",mint,state.seed,origin_chain_id].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - fill_index_shard (Writable): Fill index shard of the deposit id, only required when the fill index is enabled
+    ///   and the deposit id fits into u64. Created if missing. Pass this program ID to represent None.
+    ///   Seed: ["fill_index",state.seed,origin_chain_id,deposit_id/1024].
+    /// - origin_chain_alias (Account): Chain alias of the origin chain ID, only required in strict chain alias mode.
+    ///   Rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent None.
+    ///   Seed: ["chain_alias",state.seed,origin_chain_id].
+    /// - instructions_sysvar (Account): The instructions sysvar used to load the Ed25519 verification instruction.
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - associated_token_program (Interface): The associated token program.
+    /// - system_program (Interface): The system program.
+    /// - payer (Writable, Signer): The account paying the rent of the fill_status and used_signature PDAs, same as in
+    ///   fill_v3_relay.
+    /// - original_recipient_compliance (Account): Blocklist entry of the original recipient, only required when the
+    ///   state has a compliance authority, as blocked recipients can't be updated away. Pass this program ID to
+    ///   represent None. Seed: ["compliance",state.seed,recipient].
+    ///
+    /// ### Parameters:
+    /// - relay_hash: The hash identifying the original deposit to be filled. Computed as hash of the flattened
+    ///   relay_data & destination_chain_id.
+    /// - relay_data: Struct containing all the data of the original deposit, same as in fill_v3_relay.
+    /// - repayment_chain_id: Chain of SpokePool where relayer wants to be refunded after the challenge window has
+    ///   passed. Must not be zero.
+    /// - repayment_address: The address of the recipient on the repayment chain that they want to be refunded to, as
+    ///   bytes32 with EVM addresses left padded with zeros. Must not be zero, nor an EVM address when repaid on this
+    ///   chain.
+    /// - updated_output_amount: The updated amount of output tokens that the relayer sends to the updated recipient.
+    ///   Zero skips the token transfer as for message-only relays, rejected with EmptyRelay for an empty
+    ///   updated_message.
+    /// - updated_recipient: The updated account receiving funds on this chain.
+    /// - updated_message: The updated message to send to the updated recipient, handled the same as in fill_v3_relay.
+    /// - depositor_signature: The depositor's signature over the updated deposit hash. 65 byte r || s || v secp256k1
+    ///   signature for EVM depositors or 64 byte Ed25519 signature for Solana depositors.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_v3_relay_with_updated_deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, FillV3RelayWithUpdatedDeposit<'info>>,
+        relay_hash: [u8; 32],
+        relay_data: V3RelayData,
+        repayment_chain_id: u64,
+        repayment_address: Pubkey,
+        updated_output_amount: u64,
+        updated_recipient: Pubkey,
+        updated_message: Vec<u8>,
+        depositor_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::fill_v3_relay_with_updated_deposit(
+            ctx,
+            relay_hash,
+            relay_data,
+            repayment_chain_id,
+            repayment_address,
+            updated_output_amount,
+            updated_recipient,
+            updated_message,
+            depositor_signature,
+        )
+    }
+
+    /// Closes the FillStatusAccount PDA to reclaim relayer rent.
+    ///
+    /// This function is used to close the FillStatusAccount associated with a specific relay hash, effectively marking
+    /// the end of its lifecycle. This can only be done once the fill deadline has passed. Relayers should do this for
+    /// all fills once they expire to reclaim their rent. Closing is rejected during the dispute freeze window.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the closure. Must be the relayer in the fill_status PDA and
+    ///   owned by the system program as it receives the reclaimed rent. Rejected with InvalidOriginalPayer if another
+    ///   account paid the rent, which close_fill_status_account returns to that payer instead.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_status (Writable): The FillStatusAccount PDA to be closed.
+    pub fn close_fill_pda(ctx: Context<CloseFillPda>) -> Result<()> {
+        instructions::close_fill_pda(ctx)
+    }
+
+    /// Closes the FillStatusAccount PDA of an expired relay, returning its rent to the original payer.
+    ///
+    /// Anyone can call this once the fill deadline of the relay has passed, as the rent is always sent to the relayer
+    /// or slow fill requester that created the PDA, even when another relayer later filled the relay. PDAs of pending
+    /// slow fill requests can only be closed REQUESTED_SLOW_FILL_CLOSE_DELAY after the fill deadline, so that their
+    /// status is kept while a root bundle with their slow fill leaf can still be executed. Closing is rejected during
+    /// the dispute freeze window.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the closure. No permission requirements.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_status (Writable): The FillStatusAccount PDA to be closed. Seed: ["fills",relay_hash].
+    /// - payer (Writable): The original payer recorded in the fill_status PDA, receiving the reclaimed rent. Must be
+    ///   owned by the system program.
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the output token, only required when closing a
+    ///   requested slow fill, releasing its output amount. Pass this program ID to represent None.
+    ///   Seed: ["slow_fill_liability",state.seed,output_token].
+    ///
+    /// ### Parameters:
+    /// - _relay_hash: The hash identifying the relay of the fill_status PDA.
+    /// - relay_data: Struct containing all the data needed to identify the relay, same as in fill_v3_relay().
+    pub fn close_fill_status_account(
+        ctx: Context<CloseFillStatusAccount>,
+        _relay_hash: [u8; 32],
+        relay_data: V3RelayData,
+    ) -> Result<()> {
+        instructions::close_fill_status_account(ctx, relay_data)
+    }
+
+    /// Marks an unfilled relay expired once its fill deadline has passed, so that indexers get a positive signal for
+    /// deposits to refund on the origin chain instead of inferring it from a missing or Unfilled fill status PDA.
+    ///
+    /// Anyone can call this. The fill status PDA is created if missing and its status set to Expired, after which
+    /// slow fill leaves of the relay can no longer be executed. Pending slow fill requests can only be marked expired
+    /// REQUESTED_SLOW_FILL_CLOSE_DELAY after the fill deadline, while a root bundle with their slow fill leaf can
+    /// still be executed. Filled relays can't be marked expired. Emits a FillStatusExpired event.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that marks the relay expired. Writable as it pays the fill_status PDA rent if it
+    ///   does not exist yet, and is then its recorded payer.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_status (Writable): The FillStatusAccount PDA of the relay. Seed: ["fills",relay_hash].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the output token, only required when marking a
+    ///   requested slow fill expired, releasing its output amount. Pass this program ID to represent None.
+    ///   Seed: ["slow_fill_liability",state.seed,output_token].
+    /// - system_program (Program): The system program.
+    ///
+    /// ### Parameters:
+    /// - relay_hash: The hash identifying the relay of the fill_status PDA.
+    /// - relay_data: Struct containing all the data needed to identify the relay, same as in fill_v3_relay().
+    pub fn mark_expired(ctx: Context<MarkExpired>, relay_hash: [u8; 32], relay_data: V3RelayData) -> Result<()> {
+        instructions::mark_expired(ctx, relay_hash, relay_data)
+    }
+
+    /// Closes a UsedSignature PDA of an expired signed payload, returning its rent to the original payer.
+    ///
+    /// Anyone can call this once the signed payload has expired, i.e. the fill deadline of its relay has passed, as
+    /// the rent is always sent to the payer that consumed the signature. Instructions consuming signatures reject
+    /// expired payloads, so closing the PDA does not allow replaying the signature.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the closure. No permission requirements.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - used_signature (Writable): The UsedSignature PDA to be closed.
+    ///   Seed: ["used_signature",state.seed,keccak(signature)[..16]].
+    /// - payer (Writable): The original payer recorded in the used_signature PDA, receiving the reclaimed rent. Must
+    ///   be owned by the system program.
+    pub fn close_used_signature(ctx: Context<CloseUsedSignature>) -> Result<()> {
+        instructions::close_used_signature(ctx)
+    }
+
+    /// Closes an AdminNonce PDA of a consumed idempotency key, returning its rent to the original payer.
+    ///
+    /// Anyone can call this once the retention window of the key has passed, as the rent is always sent to the payer
+    /// that consumed the key. Admin instructions resubmitted with the key after closing are applied again.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the closure. No permission requirements.
+    /// - admin_nonce (Writable): The AdminNonce PDA to be closed. Seed: ["admin_nonce",state.seed,idempotency_key].
+    /// - payer (Writable): The original payer recorded in the admin_nonce PDA, receiving the reclaimed rent. Must be
+    ///   owned by the system program.
+    pub fn close_admin_nonce(ctx: Context<CloseAdminNonce>) -> Result<()> {
+        instructions::close_admin_nonce(ctx)
+    }
+
+    /// Returns packed fill statuses for a batch of FillStatusAccount PDAs. This acts like a "view" function for
+    /// off-chain actors, e.g. indexers backfilling fill statuses, to read many fill PDAs in a single simulation.
+    ///
+    /// Each entry is packed as status (u8), relayer (Pubkey) and fill_deadline (u32, little-endian). The byte layout
+    /// is exported as FILL_STATUSES_PACKED_* constants. Entries follow the order of the passed accounts, so callers map
+    /// them back to the relay hashes used to derive the PDAs. Accounts that do not deserialize as a FillStatusAccount,
+    /// e.g. closed or foreign accounts, get the FILL_STATUSES_PACKED_INVALID_STATUS sentinel as their status.
+    ///
+    /// ### Required Accounts:
+    /// - remaining_accounts: Up to FILL_STATUSES_PACKED_MAX_ACCOUNTS FillStatusAccount PDAs to read.
+    ///   Seed: ["fills",relay_hash].
+    pub fn get_fill_statuses_packed(ctx: Context<Null>) -> Result<Vec<u8>> {
+        instructions::get_fill_statuses_packed(ctx)
+    }
+
+    /// Returns the FillStatusAccount of a relay. This acts like a "view" function for off-chain actors and for other
+    /// programs reading the fill status through CPI return data.
+    ///
+    /// PDAs that were never created or were already closed are returned as Unfilled with zeroed fields. Legacy PDAs
+    /// created before the fill deadline, requester and payer were recorded are decoded with these fields zeroed, same
+    /// as FillStatusAccount::try_deserialize_any_layout that off-chain code can use on the raw account data.
+    ///
+    /// ### Required Accounts:
+    /// - fill_status (Account): The fill status PDA to read. Seed: ["fills",relay_hash].
+    ///
+    /// ### Parameters:
+    /// - _relay_hash: The hash identifying the relay, used to derive the fill status PDA.
+    pub fn get_fill_status(ctx: Context<GetFillStatus>, _relay_hash: [u8; 32]) -> Result<FillStatusAccount> {
+        instructions::get_fill_status(ctx)
+    }
+
+    /// Migrates a legacy FillStatusAccount PDA to the current layout. Permissionless.
+    ///
+    /// PDAs created before the fill deadline, requester and payer were recorded have a smaller size that the
+    /// instructions writing to fill statuses fail to deserialize. This reallocates them to the current size, with the
+    /// signer paying the additional rent, and records the fill deadline of the relay data. The relayer is recorded as
+    /// the payer that receives the rent when the PDA is closed, and also as the requester of requested slow fills, as
+    /// it was the signer creating the legacy PDA. PDAs of the current layout are rejected.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The account paying the rent for the extended size.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_status (Writable): The legacy fill status PDA to migrate. Seed: ["fills",relay_hash].
+    /// - system_program (Program): The system program.
+    ///
+    /// ### Parameters:
+    /// - _relay_hash: The hash identifying the relay, used to derive the fill status PDA.
+    /// - relay_data: The relay data hashed into the relay hash, providing the fill deadline.
+    pub fn migrate_fill_status_account(
+        ctx: Context<MigrateFillStatusAccount>,
+        _relay_hash: [u8; 32],
+        relay_data: V3RelayData,
+    ) -> Result<()> {
+        instructions::migrate_fill_status_account(ctx, relay_data)
+    }
+
+    /// Re-emits the fill event of a filled relay from its FillStatusAccount PDA. Callable only by the owner.
+    ///
+    /// This lets indexers repair gaps when RPC providers dropped the event CPI data of the original fill. The emitted
+    /// FilledV3RelayReplay event carries the fields stored in the fill status PDA with the replay flag set, so that
+    /// consumers can dedupe it. No fill state is changed and replays are limited to EVENT_REPLAY_RATE_LIMIT within each
+    /// EVENT_REPLAY_RATE_WINDOW seconds.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The owner that pays for the replay activity creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_status (Account): The FillStatusAccount PDA of the filled relay. Seed: ["fills",relay_hash].
+    /// - event_replay_activity (Writable): The replay rate limit PDA. Seed: ["event_replay_activity",state.seed].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - relay_hash: The hash identifying the filled relay.
+    pub fn re_emit_fill_event(ctx: Context<ReEmitFillEvent>, relay_hash: [u8; 32]) -> Result<()> {
+        instructions::re_emit_fill_event(ctx, relay_hash)
+    }
+
+    /// Claims a relayer refund for the caller.
+    ///
+    /// In the event a relayer refund was sent to a claim account, then this function enables the relayer to claim it by
+    /// transferring the claim amount from the vault to their token account. The claim account is closed after refund.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the claim.
+    /// - initializer (UncheckedAccount): Must be the same account that initialized the claim account and owned by the
+    ///   system program.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - vault (InterfaceAccount): The ATA for the refunded mint. Authority must be the vault authority.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - mint (InterfaceAccount): The mint account for the token being refunded.
+    /// - token_account (InterfaceAccount): The ATA for the token being refunded to.
+    /// - claim_account (Account): The claim account PDA. Seed: ["claim_account",mint,refund_address].
+    /// - rate_limit (Writable): Optional rate limit of the mint, only required when vault outflow rate limits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - claim_liability (Writable): Optional refunds of the mint owed to claim accounts, released by the claimed
+    ///   amount. Pass this program ID to represent None. Seed: ["claim_liability",state.seed,mint].
+    pub fn claim_relayer_refund(ctx: Context<ClaimRelayerRefund>) -> Result<()> {
+        instructions::claim_relayer_refund(ctx)
+    }
+
+    /// Functionally identical to claim_relayer_refund() except the refund is sent to a specified refund address.
+    pub fn claim_relayer_refund_for(ctx: Context<ClaimRelayerRefundFor>, refund_address: Pubkey) -> Result<()> {
+        instructions::claim_relayer_refund_for(ctx, refund_address)
+    }
+
+    /// Pushes a deferred refund to the ATA of its refund address. Permissionless.
+    ///
+    /// Functionally identical to claim_relayer_refund_for() except the ATA of the refund address is created at the
+    /// expense of the signer when missing, so that anyone can deliver refunds deferred for refund addresses without an
+    /// ATA, such as depositors refunded for expired deposits. The claim account is closed after refund.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account paying for the ATA creation. No permission requirements.
+    /// - initializer (UncheckedAccount): Must be the same account that initialized the claim account and owned by the
+    ///   system program.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - vault (InterfaceAccount): The ATA for the refunded mint. Authority must be the vault authority.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - mint (InterfaceAccount): The mint account for the token being refunded.
+    /// - refund_address (UncheckedAccount): The refund address of the claim account.
+    /// - token_account (Writable): The ATA of the refund address for the mint. Created if missing.
+    /// - claim_account (Account): The claim account PDA. Seed: ["claim_account",mint,refund_address].
+    /// - rate_limit (Writable): Optional rate limit of the mint, only required when vault outflow rate limits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - associated_token_program (Interface): The associated token program.
+    /// - system_program (Program): The system program required for account creation.
+    /// - claim_liability (Writable): Optional refunds of the mint owed to claim accounts, released by the claimed
+    ///   amount. Pass this program ID to represent None. Seed: ["claim_liability",state.seed,mint].
+    pub fn claim_refund_for(ctx: Context<ClaimRefundFor>) -> Result<()> {
+        instructions::claim_refund_for(ctx)
+    }
+
+    /// Creates token accounts in batch for a set of addresses.
+    ///
+    /// This helper function allows the caller to pass in a set of remaining accounts to create a batch of Associated
+    /// Token Accounts (ATAs) for addresses. It is particularly useful for relayers to call before filling a deposit.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the creation of token accounts.
+    /// - mint (InterfaceAccount): The mint account for the token.
+    /// - token_program (Interface): The token program.
+    /// - associated_token_program (Program): The associated token program.
+    /// - system_program (Program): The system program required for account creation.
+    pub fn create_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CreateTokenAccounts<'info>>) -> Result<()> {
+        instructions::create_token_accounts(ctx)
+    }
+
+    /// Creates the vault of a mint and registers it with the token program owning the mint.
+    ///
+    /// Vaults are otherwise only created when enabling routes, so that deposits of a mint without a vault fail to load
+    /// it. Anyone can create the ATA of the vault authority PDA for the mint, created idempotently so that vaults
+    /// created by set_enable_route get registered as well. Deposits and fills of registered mints fail with
+    /// InvalidVault when passing another token program. Legacy and Token-2022 mints are separate mint accounts, so each
+    /// is registered on its own. Emits a VaultCreated event.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that pays the rent of the vault and vault PDA.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (InterfaceAccount): The mint account of the vault.
+    /// - authority (UncheckedAccount): The owner of the vault, the vault authority PDA.
+    ///   Seed: ["vault_authority",state].
+    /// - vault (Writable): The ATA of the authority for the mint. Created if missing.
+    /// - vault_registry (Writable): The vault PDA registering the mint, created on this function call.
+    ///   Seed: ["vault",state.seed,mint].
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - associated_token_program (Program): The associated token program.
+    /// - system_program (Program): The system program required for account creation.
+    pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
+        instructions::create_vault(ctx)
+    }
+
+    /// Registers or updates the message schema for a recipient.
+    ///
+    /// Recipient handler programs can register the expected size bounds and 4 byte schema tag of messages they are able
+    /// to decode. When passed, fill_v3_relay and request_v3_slow_fill reject non-empty messages not matching the
+    /// schema before doing any further work. Fills to recipients without a registered schema are unaffected.
+    ///
+    /// ### Required Accounts:
+    /// - payer (Signer): The account that pays for the creation of the message_schema PDA.
+    /// - recipient (Signer): The recipient registering its schema. Handler programs should sign via their PDA.
+    /// - message_schema (Writable): The message schema PDA. Seed: ["message_schema",recipient].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - min_message_size: Minimum size of non-empty messages. Must be at least 4 bytes to include the schema tag.
+    /// - max_message_size: Maximum size of non-empty messages.
+    /// - schema_tag: Expected first 4 bytes of non-empty messages.
+    pub fn register_message_schema(
+        ctx: Context<RegisterMessageSchema>,
+        min_message_size: u32,
+        max_message_size: u32,
+        schema_tag: [u8; 4],
+    ) -> Result<()> {
+        instructions::register_message_schema(ctx, min_message_size, max_message_size, schema_tag)
+    }
+
+    /// Deregisters the message schema for a recipient and closes its PDA.
+    ///
+    /// ### Required Accounts:
+    /// - closer (SystemAccount): The account that will receive the lamports from closing the message_schema account.
+    /// - recipient (Signer): The recipient that registered the schema.
+    /// - message_schema (Writable): The message schema PDA to be closed. Seed: ["message_schema",recipient].
+    pub fn deregister_message_schema(ctx: Context<DeregisterMessageSchema>) -> Result<()> {
+        instructions::deregister_message_schema(ctx)
+    }
+
+    /// Sets how fill payouts are delivered to the recipient.
+    ///
+    /// With the Escrow mode, fast fills and slow fill vault transfers accrue the output tokens in the recipient escrow
+    /// instead of the recipient's ATA, and the recipient withdraws them with withdraw_escrow. The recipient escrow must
+    /// be initialized for each output token. With the Native mode, wSOL payouts are unwrapped and sent to the recipient
+    /// as lamports. Fills only honor the preference when the filler passes its PDA.
+    ///
+    /// ### Required Accounts:
+    /// - recipient (Signer): The recipient that sets its preference and pays for the account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - delivery_preference (Writable): The delivery preference PDA, created on the first call.
+    ///   Seed: ["delivery_preference",state.seed,recipient].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - mode: Push to deliver payouts to the recipient's ATA, Escrow to accrue them in the recipient escrow or Native
+    ///   to receive wSOL payouts as lamports (other output tokens are pushed to the recipient's ATA).
+    pub fn set_delivery_preference(ctx: Context<SetDeliveryPreferenceState>, mode: DeliveryMode) -> Result<()> {
+        instructions::set_delivery_preference(ctx, mode)
+    }
+
+    /// Clears the delivery preference of the recipient, so that fill payouts are sent to its ATA, and closes its PDA.
+    ///
+    /// ### Required Accounts:
+    /// - recipient (Signer): The recipient that set the preference. Receives the rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - delivery_preference (Writable): The delivery preference PDA to close.
+    ///   Seed: ["delivery_preference",state.seed,recipient].
+    pub fn clear_delivery_preference(ctx: Context<ClearDeliveryPreference>) -> Result<()> {
+        instructions::clear_delivery_preference(ctx)
+    }
+
+    /// Initializes the recipient escrow token account for a mint. Anyone can pay for its creation.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that pays for the escrow account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the escrowed token.
+    /// - recipient_escrow (Writable): The escrow token account to initialize, owned by the state PDA.
+    ///   Seed: ["escrow",state.seed,recipient,mint].
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - _recipient: The recipient whose payouts can be escrowed.
+    pub fn initialize_recipient_escrow(
+        _ctx: Context<InitializeRecipientEscrow>,
+        _recipient: Pubkey, // Only used in account constraints.
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Withdraws escrowed tokens of the recipient. Only callable by the recipient.
+    ///
+    /// ### Required Accounts:
+    /// - recipient (Signer): The recipient whose payouts were escrowed.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the escrowed token.
+    /// - recipient_escrow (Writable): The escrow token account. Seed: ["escrow",state.seed,recipient,mint].
+    /// - destination (Writable): Any token account of the mint to receive the withdrawn tokens.
+    /// - token_program (Interface): The token program.
+    ///
+    /// ### Parameters:
+    /// - amount: The amount of tokens to withdraw.
+    pub fn withdraw_escrow(ctx: Context<WithdrawEscrow>, amount: u64) -> Result<()> {
+        instructions::withdraw_escrow(ctx, amount)
+    }
+
+    /// Sets the protocol fee skimmed from slow fill vault payouts and the recipient allowed to withdraw it. Only
+    /// callable by the owner.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - protocol_fee_bps: Share of each payout in basis points accrued into the fee vault of its mint. Together with
+    ///   the liquidity pool fee it cannot exceed MAX_TOTAL_FEE_BPS (200).
+    /// - protocol_fee_recipient: The only account that can withdraw the accrued fees, or None to disable withdrawals.
+    pub fn set_protocol_fee(
+        ctx: Context<SetProtocolFeeState>,
+        protocol_fee_bps: u16,
+        protocol_fee_recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_protocol_fee(ctx, protocol_fee_bps, protocol_fee_recipient)
+    }
+
+    /// Initializes the program owned fee vault of a mint where protocol fees accrue. Callable by anyone.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that pays for the fee vault creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the fee token.
+    /// - fee_vault (Writable): The fee vault token account to initialize, owned by the state PDA.
+    ///   Seed: ["fee_vault",state.seed,mint].
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    pub fn initialize_fee_vault(_ctx: Context<InitializeFeeVault>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Withdraws accrued protocol fees from the fee vault of a mint. Only callable by the protocol fee recipient, not
+    /// the owner. Partial withdrawals are allowed.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The protocol fee recipient set in the state.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the fee token.
+    /// - fee_vault (Writable): The fee vault token account. Seed: ["fee_vault",state.seed,mint].
+    /// - destination (Writable): Any token account of the mint to receive the withdrawn fees.
+    /// - token_program (Interface): The token program.
+    ///
+    /// ### Parameters:
+    /// - amount: The amount of fees to withdraw. Cannot exceed the fee vault balance.
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_protocol_fees(ctx, amount)
+    }
+
+    /// Enables or disables external liquidity provision and sets the liquidity pool fee skimmed from slow fill vault
+    /// payouts. Only callable by the owner. Disabling blocks new liquidity, but providers can still remove theirs.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: True to accept liquidity and accrue pool fees, false otherwise.
+    /// - fee_bps: Share of each payout in basis points accrued to the liquidity pool of its mint. Together with the
+    ///   protocol fee it cannot exceed MAX_TOTAL_FEE_BPS (200).
+    pub fn set_liquidity_pool_config(
+        ctx: Context<SetLiquidityPoolConfigState>,
+        enabled: bool,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_liquidity_pool_config(ctx, enabled, fee_bps)
+    }
+
+    /// Opts a mint into external liquidity by initializing its liquidity pool PDA. Only callable by the owner.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The owner that pays for the liquidity pool creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the pooled token.
+    /// - liquidity_pool (Writable): The liquidity pool PDA to initialize. Seed: ["liquidity_pool",state.seed,mint].
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    pub fn initialize_liquidity_pool(ctx: Context<InitializeLiquidityPool>) -> Result<()> {
+        instructions::initialize_liquidity_pool(ctx)
+    }
+
+    /// Provides liquidity to the vault of a mint in exchange for liquidity pool shares.
+    ///
+    /// Shares are minted proportionally to the liquidity owed to the pool, which excludes refund bundle inflows and
+    /// any other protocol owned funds held in the vault. The first provider receives one share per token.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The liquidity provider, or an account with a token delegation from it.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the pooled token.
+    /// - liquidity_pool (Writable): The liquidity pool of the mint. Seed: ["liquidity_pool",state.seed,mint].
+    /// - liquidity_position (Writable): The shares of the signer, created if missing.
+    ///   Seed: ["liquidity_position",state.seed,mint,signer].
+    /// - provider_token_account (Writable): The token account providing the liquidity. Must have delegated the amount
+    ///   to the state PDA.
+    /// - vault (Writable): The ATA of the vault authority PDA for the mint receiving the liquidity.
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - amount: The amount of tokens to provide. Must be worth at least one share.
+    pub fn provide_liquidity(ctx: Context<ProvideLiquidity>, amount: u64) -> Result<()> {
+        instructions::provide_liquidity(ctx, amount)
+    }
+
+    /// Removes liquidity from the vault of a mint by burning liquidity pool shares of the signer.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The liquidity provider owning the shares.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the pooled token.
+    /// - liquidity_pool (Writable): The liquidity pool of the mint. Seed: ["liquidity_pool",state.seed,mint].
+    /// - liquidity_position (Writable): The shares of the signer. Seed: ["liquidity_position",state.seed,mint,signer].
+    /// - vault (Writable): The ATA of the vault authority PDA for the mint, source of the removed liquidity.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - destination (Writable): Any token account of the mint to receive the removed liquidity.
+    /// - token_program (Interface): The token program.
+    ///
+    /// ### Parameters:
+    /// - shares: The number of shares to burn. The signer receives their share of the pool liquidity, rounded down.
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, shares: u64) -> Result<()> {
+        instructions::remove_liquidity(ctx, shares)
+    }
+
+    /// **************************************
+    ///           BUNDLE FUNCTIONS           *
+    /// *************************************
+
+    /// Executes relayer refund leaf. Only callable by owner.
+    ///
+    /// Processes a relayer refund leaf, verifying its inclusion in a previous Merkle root and that it was not
+    /// previously executed. Function has two modes of operation: a) transfers all relayer refunds directly to
+    /// relayers ATA or b) credits relayers with claimable claim_account PDA that they can use later to claim their
+    /// refund. In the happy path, (a) should be used. (b) should only be used if there is a relayer within the bundle
+    /// who can't receive the transfer for some reason, such as failed token transfers due to blacklisting. In mode (a)
+    /// refunds to ATAs that are not created, frozen or not matching the mint are still credited to the relayer's
+    /// claim_account PDA, so that a single relayer cannot block the leaf. Executing relayer refunds requires the caller
+    /// to create a LUT and load the execution params into it. This is needed to fit the data in a single instruction.
+    /// The exact structure and validation of the leaf is defined in the UMIP.
+    ///
+    /// instruction_params Parameters:
+    /// - root_bundle_id: The ID of the root bundle containing the relayer refund root.
+    /// - relayer_refund_leaf: The relayer refund leaf to be executed. Contents must include:
+    ///     - amount_to_return: The amount to be to be sent back to mainnet Ethereum from this Spoke pool.
+    ///     - chain_id: The targeted chainId for the refund. Validated against state.chain_id.
+    ///     - refund_amounts: The amounts to be returned to the relayer for each refund_address.
+    ///     - leaf_id: The leaf ID of the relayer refund leaf.
+    ///     - mint_public_key: The public key of the mint (refunded token) being refunded.
+    ///     - refund_addresses: The addresses to be refunded.
+    /// - proof: The Merkle proof for the relayer refund leaf, at most 32 nodes deep.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the execution. No permission requirements.
+    /// - instruction_params (Account): LUT containing the execution parameters. seed: ["instruction_params",signer]
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Writable): The root bundle PDA containing the relayer refund root, created when the root bundle
+    ///   was initially bridged. seed: ["root_bundle",state.seed,root_bundle_id].
+    /// - vault (Writable): The ATA for refunded mint. Authority must be the vault authority.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - mint (Account): The mint account for the token being refunded.
+    /// - transfer_liability (Writable): Account to track pending refunds to be sent to the Ethereum hub pool. Only used
+    ///   if the amount_to_return value is non-zero within the leaf. Seed: ["transfer_liability",mint]
+    /// - error_counters (Writable): Optional failure tracker, incremented only when the leaf defers any refunds, for
+    ///   the leaf and its deferred refunds. Pass this program ID to represent None.
+    ///   Seed: ["error_counters",state.seed].
+    /// - bundle_audit (Writable): Optional vault balance snapshots of the bundle, required only when bundle audits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["bundle_audit",state.seed,root_bundle_id].
+    /// - rate_limit (Writable): Optional rate limit of the mint, only required in mode (a) when vault outflow rate
+    ///   limits are enabled. Pass this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
+    /// - vault_liability (Writable): Optional liabilities of the vault, debited with the refunds sent in mode (a).
+    ///   Pass this program ID to represent None. Seed: ["vault_liability",state.seed,mint].
+    /// - slow_fill_liability (Account): Pending slow fill amount of the mint, which refunds sent in mode (a) can't take
+    ///   the vault balance below. Treated as nothing pending if not created yet.
+    ///   Seed: ["slow_fill_liability",state.seed,mint].
+    /// - fill_volume (Writable): Fill volume of the mint, bounding the refunds and amount_to_return of all leaves of
+    ///   the bundle by the fill volume of the mint since the previous bundle plus its tolerance. The first leaf of a
+    ///   newer root bundle closes the fill volume window. Leaves above it fail with RefundsExceedFillVolume. Ignored if
+    ///   the owner did not opt the mint into refund checks. Seed: ["fill_volume",state.seed,mint].
+    /// - execution_approval (Account): Approval of the relayer refund leaf by an execution approver other than the
+    ///   signer, only required to override RefundsExceedFillVolume. Emits ApprovedRefundsAboveFillVolume. Pass this
+    ///   program ID to represent None. Seed: ["execution_approval",state.seed,leaf_hash].
+    /// - token_program: The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - system_program: The system program required for account creation.
+    /// - associated_token_program: Optional associated token program, creating missing refund ATAs in mode (a). Pass
+    ///   this program ID to represent None.
+    /// - route: Optional route of the mint towards any destination chain. When passed, the mint decimals must match its
+    ///   expected decimals. Pass this program ID to represent None.
+    ///   Seed: ["route",mint,state.seed,destination_chain_id].
+    /// - claim_liability (Writable): Refunds of the mint owed to claim_account PDAs, increased by the refunds credited
+    ///   to them in either mode. Created if missing. Seed: ["claim_liability",state.seed,mint].
+    ///
+    /// Remaining accounts hold the refund ATAs (mode a) or claim_account PDAs (mode b) in refund_addresses order. In
+    /// mode (a) the claim_account PDAs of relayers whose ATA cannot receive the refund follow at index n + i, where n
+    /// is the number of refunds. Refund addresses passed at index 2n + i get their missing ATA created at the expense
+    /// of the signer, so that depositors refunded for expired deposits get paid directly. Refund addresses owned by
+    /// programs are left to be credited to their claim_account PDA, as are refunds to ATAs that still cannot receive
+    /// them. Refunds paid into created ATAs emit RefundPaid and refunds credited in mode (a) emit RefundDeferred. The
+    /// ExecutedRelayerRefundRoot event sets deferred_refunds when any refund was credited to a claim_account PDA.
+    /// Leaves with too many refunds for the event to fit in MAX_EVENT_CPI_DATA_SIZE emit ExecutedRelayerRefundRootChunk
+    /// events instead, each with a slice of the refunds, its chunk_index and the total_chunks of the leaf.
+    ///
+    /// execute_relayer_refund_leaf executes in mode (a) where refunds are sent to ATA directly.
+    /// execute_relayer_refund_leaf_deferred executes in mode (b) where refunds are allocated to the claim_account PDA.
+    pub fn execute_relayer_refund_leaf<'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExecuteRelayerRefundLeaf<'info>>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        instructions::execute_relayer_refund_leaf(ctx, false)
+    }
+
+    pub fn execute_relayer_refund_leaf_deferred<'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExecuteRelayerRefundLeaf<'info>>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        instructions::execute_relayer_refund_leaf(ctx, true)
+    }
+
+    /// Verifies whether a leaf is included in one of the roots of a relayed root bundle without side effects. This acts
+    /// like a "view" function for off-chain actors, e.g. observers during bundle disputes.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Account): The root bundle PDA to verify against. Seed: ["root_bundle",state.seed,root_bundle_id].
+    ///
+    /// ### Parameters:
+    /// - _root_bundle_id: Index of the root bundle. Only used in account constraints.
+    /// - root_type: Selects whether to verify against the relayer refund root or the slow relay root.
+    /// - leaf_hash: The keccak hash of the leaf.
+    /// - proof: The Merkle proof for the leaf.
+    ///
+    /// Returns whether the leaf is included in the selected root along with both roots of the bundle.
+    pub fn verify_leaf_inclusion(
+        ctx: Context<VerifyLeafInclusion>,
+        _root_bundle_id: u32,
+        root_type: RootType,
+        leaf_hash: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<LeafInclusion> {
+        instructions::verify_leaf_inclusion(ctx, root_type, leaf_hash, proof)
+    }
+
+    /// Returns the IDs of relayer refund leaves of a relayed root bundle that were not executed yet. This acts like a
+    /// "view" function for off-chain tooling tracking which refund leaves remain, as executed leaves are marked in the
+    /// claimed bitmap of the root bundle and rejected with ClaimedMerkleLeaf when executed again.
+    ///
+    /// Returns unclaimed IDs in ascending order from start_leaf_id up to leaf_count, at most UNCLAIMED_LEAVES_MAX_IDS
+    /// of them to fit in the return data. Callers page through larger bundles by passing the ID after the last one
+    /// returned. The claimed bitmap grows on execution by at most 10KB per instruction, i.e. a leaf can be executed
+    /// while its ID is less than 81920 leaves above the highest executed one.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Account): The root bundle PDA to read. Seed: ["root_bundle",state.seed,root_bundle_id].
+    ///
+    /// ### Parameters:
+    /// - _root_bundle_id: Index of the root bundle. Only used in account constraints.
+    /// - start_leaf_id: First leaf ID to return if unclaimed.
+    /// - leaf_count: Number of leaves in the relayer refund tree of the root bundle.
+    pub fn get_unclaimed_leaves(
+        ctx: Context<GetUnclaimedLeaves>,
+        _root_bundle_id: u32,
+        start_leaf_id: u32,
+        leaf_count: u32,
+    ) -> Result<Vec<u32>> {
+        instructions::get_unclaimed_leaves(ctx, start_leaf_id, leaf_count)
+    }
+
+    /// Migrates a legacy RootBundle PDA to the current layout. Permissionless.
+    ///
+    /// Root bundles relayed before the id, execution deadline and restricted executor were recorded end with their
+    /// claimed bitmap, so the instructions reading root bundles fail to deserialize them. This extends them by the size
+    /// of the appended fields, with the signer paying the additional rent, and records the id of the PDA. Legacy root
+    /// bundles keep no execution deadline and no restricted executor, as they were relayed before those existed. Root
+    /// bundles of the current layout are rejected.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The account paying the rent for the extended size.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Writable): The legacy root bundle PDA to migrate. Seed: ["root_bundle",state.seed,root_bundle_id].
+    /// - system_program (Program): The system program.
+    ///
+    /// ### Parameters:
+    /// - root_bundle_id: The id of the root bundle, used to derive the root bundle PDA and recorded in it.
+    pub fn migrate_root_bundle(ctx: Context<MigrateRootBundle>, root_bundle_id: u32) -> Result<()> {
+        instructions::migrate_root_bundle(ctx, root_bundle_id)
+    }
+
+    /// Simulates the execution of a sequence of leaves of a relayed root bundle without side effects. This acts like a
+    /// "view" function for operators proving that every leaf of a freshly relayed bundle would succeed.
+    ///
+    /// Leaves are evaluated in order: each executable leaf marks its refund leaf as claimed or its relay as filled and
+    /// debits its outflow from the simulated vault balance, so later leaves observe the effects of preceding ones.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that loaded the leaves into its instruction params.
+    /// - instruction_params (Account): Account holding the SimulateBundleSummaryParams with the relayer refund and slow
+    ///   relay leaves to simulate together with their proofs. Seed: ["instruction_params",signer].
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Account): The root bundle PDA of the leaves. Seed: ["root_bundle",state.seed,root_bundle_id].
+    /// - remaining_accounts: For each leaf in order, the vault ATA of the leaf mint, followed by the fill status
+    ///   PDA of the relay for slow relay leaves.
+    ///
+    /// ### Parameters:
+    /// - _root_bundle_id: Index of the root bundle. Only used in account constraints.
+    ///
+    /// Returns the counts of executable, blocked by vault balance, blocked by claimed or fill status and invalid leaves
+    /// along with the total outflow per mint of the executable leaves. Slow relay outflows are accounted at their
+    /// updated output amount, before any route decimal shift and fees. At most 20 distinct mints are supported.
+    pub fn simulate_bundle_summary(ctx: Context<SimulateBundleSummary>, _root_bundle_id: u32) -> Result<BundleSummary> {
+        instructions::simulate_bundle_summary(ctx)
+    }
+
+    /// Initializes the error counters PDA used to track failures that do not revert the instruction.
+    ///
+    /// Counters are incremented on a best-effort basis: only when the caller passes this account to an instruction
+    /// path that records failures. Currently only execute_relayer_refund_leaf_deferred increments them. This is
+    /// permissionless and can only be called once per state.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that pays for the error counters account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - error_counters (Writable): The error counters PDA to initialize. Seed: ["error_counters",state.seed].
+    /// - system_program: The system program required for account creation.
+    pub fn initialize_error_counters(_ctx: Context<InitializeErrorCounters>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the error counters. This acts like a "view" function for off-chain actors to monitor failures that
+    /// do not revert the instruction.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - error_counters (Account): The error counters PDA. Seed: ["error_counters",state.seed].
+    pub fn get_error_counters(ctx: Context<GetErrorCounters>) -> Result<ErrorCounters> {
+        instructions::get_error_counters(ctx)
+    }
+
+    /// Returns the stats of an exclusive relayer. This acts like a "view" function for quoting systems comparing how
+    /// often the relayer fills within their exclusivity window versus letting deposits lapse to slow fills.
+    ///
+    /// Stats are created and updated on a best-effort basis: only when fillers and slow fill requesters pass them to
+    /// fill_v3_relay and request_v3_slow_fill. Whenever the lapse ratio crosses the state relayer_lapse_threshold_bps,
+    /// these emit a RelayerStatsThresholdCrossed event.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - relayer_stats (Account): The relayer stats PDA. Seed: ["relayer_stats",state.seed,relayer].
+    ///
+    /// ### Parameters:
+    /// - _relayer: The exclusive relayer whose stats are returned.
+    pub fn get_relayer_stats(ctx: Context<GetRelayerStats>, _relayer: Pubkey) -> Result<RelayerStats> {
+        instructions::get_relayer_stats(ctx)
+    }
+
+    /// Returns the roles of the accounts of an instruction. This acts like a "view" function for wallet simulators
+    /// explaining the accounts of instructions signed by end users, such as why the state and vault are writable.
+    ///
+    /// Metadata covers the deposit instructions and withdraw_escrow, other instructions fail with
+    /// MissingInstructionMetadata. Returns each account in instruction order with its flags, role and writable reason,
+    /// encoded as documented next to the METADATA_* constants. Rust clients can read it from the metadata module.
+    ///
+    /// ### Parameters:
+    /// - discriminator: The 8 byte discriminator of the instruction.
+    pub fn get_instruction_metadata(ctx: Context<Null>, discriminator: [u8; 8]) -> Result<Vec<u8>> {
+        instructions::get_instruction_metadata(ctx, discriminator)
+    }
+
+    /// Emits a ConfigSnapshot event with every config field and counter of the state. Permissionless.
+    ///
+    /// Lets indexers bootstrapping from a given slot read the full config without replaying all historical admin
+    /// events. Apart from the first snapshot, snapshots can be emitted at most once per snapshot_min_interval, that
+    /// the owner sets through set_config.
+    ///
+    /// ### Required Accounts:
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - lookup_table_config: Lookup table config PDA. Seed: ["lookup_table_config",state.seed]. The snapshot reports
+    ///   no lookup table while it is not created.
+    pub fn emit_config_snapshot(ctx: Context<EmitConfigSnapshot>) -> Result<()> {
+        instructions::emit_config_snapshot(ctx)
+    }
+
+    /// Returns whether the state is the canonical instance of the spoke, i.e. the state of seed 0 initialized or
+    /// migrated by a single-state production build. This acts like a "view" function for clients asserting that they
+    /// don't deposit or fill against a state of a dev or test build.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    pub fn is_canonical(ctx: Context<IsCanonical>) -> Result<bool> {
+        instructions::is_canonical(ctx)
+    }
+
+    /// Creates the canonical address lookup table of the spoke through the Address Lookup Table program, with the state
+    /// PDA as its authority. Only callable by the owner, and only once per spoke.
+    ///
+    /// The table is extended with the static addresses of every relayer transaction: this program, the state, the
+    /// event authority, the vault authority and the token, associated token and system programs. Routes enabled with
+    /// the table accounts then add the addresses of their corridor, so that relayers can fit fills of any corridor in
+    /// a versioned transaction. Its address is recorded in the lookup table config PDA, read by get_lookup_table.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner, paying the rent for the lookup table and its config PDA.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - lookup_table_config (Writable): The lookup table config PDA to create.
+    ///   Seed: ["lookup_table_config",state.seed].
+    /// - lookup_table (Writable): The lookup table to create, derived from the state and recent_slot by the Address
+    ///   Lookup Table program.
+    /// - address_lookup_table_program: The Address Lookup Table program.
+    /// - system_program: The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - recent_slot: A slot still held by the SlotHashes sysvar, from which the lookup table address is derived.
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        instructions::create_lookup_table(ctx, recent_slot)
+    }
+
+    /// Extends the canonical lookup table of the spoke with additional addresses. Only callable by the owner.
+    ///
+    /// Addresses already held by the table are skipped, and the extension fails with LookupTableFull if the table would
+    /// hold more than 256 addresses. Meant for the corridors of routes enabled through set_enable_routes_batch or
+    /// before the table was created.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner, paying the additional rent of the lookup table.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - lookup_table_config (Account): The lookup table config PDA. Seed: ["lookup_table_config",state.seed].
+    /// - lookup_table (Writable): The canonical lookup table recorded in the lookup table config.
+    /// - address_lookup_table_program: The Address Lookup Table program.
+    /// - system_program: The system program required for the rent transfer.
+    ///
+    /// ### Parameters:
+    /// - addresses: The addresses to add to the lookup table.
+    pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+        instructions::extend_lookup_table(ctx, addresses)
+    }
+
+    /// Returns the address of the canonical lookup table of the spoke. This acts like a "view" function for relayers
+    /// to find the table to compile their versioned transactions against.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - lookup_table_config (Account): The lookup table config PDA. Seed: ["lookup_table_config",state.seed].
+    pub fn get_lookup_table(ctx: Context<GetLookupTable>) -> Result<Pubkey> {
+        instructions::get_lookup_table(ctx)
+    }
+
+    /// Runs cheap self-checks of the core invariants without moving funds, for synthetic monitoring. Permissionless.
+    ///
+    /// Returns a bitfield of the failed checks as HEALTH_CHECK_* bits, so 0 means healthy:
+    /// - HEALTH_CHECK_STATE_LAYOUT: The state is owned by the program and deserializes at the current layout. When
+    ///   this fails, all other bits are set as well since they depend on the state.
+    /// - HEALTH_CHECK_STATE_BUMP: The state address re-derives as the canonical PDA of its seed.
+    /// - HEALTH_CHECK_PAUSE_FLAGS: The fills pause expiry is only set while fills are paused.
+    /// - HEALTH_CHECK_WSOL_VAULT: The passed wSOL vault is the vault authority ATA and its amount is synced with its
+    ///   lamports.
+    /// - HEALTH_CHECK_COUNTERS: Wind down and config snapshot times are not in the future, the combined fees are
+    ///   within MAX_TOTAL_FEE_BPS and the execution approval thresholds within their max count.
+    /// Failed checks don't revert the instruction, but are also emitted in a HealthCheckFailed event.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - wsol_vault (Account): Optional wSOL vault ATA of the vault authority to check. Pass this program ID to
+    ///   represent None.
+    pub fn health_check(ctx: Context<HealthCheck>) -> Result<u32> {
+        instructions::health_check(ctx)
+    }
+
+    /// Reports whether the vault of a mint is missing, to monitor vaults closed or never created. Permissionless.
+    ///
+    /// Slow fills of a mint without a vault fail with VaultMissingForMint, so dashboards can probe the output tokens of
+    /// pending slow fills ahead of their execution. Returns true when the vault is missing, which is also emitted in a
+    /// VaultMissing event. Existing vaults don't emit any event.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the reported vault.
+    /// - vault (Account): Vault ATA of the vault authority PDA for the mint, derived with the token program
+    ///   owning the mint.
+    pub fn report_missing_vault(ctx: Context<ReportMissingVault>) -> Result<bool> {
+        instructions::report_missing_vault(ctx)
+    }
+
+    /// Reports whether the vault of a mint is frozen, to monitor mints whose freeze authority froze the vault.
+    /// Permissionless.
+    ///
+    /// Slow fills, refunds and other payouts of a mint with a frozen vault fail with VaultFrozen, until the owner moves
+    /// them to a new vault with migrate_frozen_vault. Returns true when the vault is frozen, which is also emitted in a
+    /// VaultFrozen event. Missing vaults and vaults that can pay out don't emit any event.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the reported vault.
+    /// - vault (Account): Vault ATA of the vault authority PDA for the mint, derived with the token program
+    ///   owning the mint.
+    pub fn report_frozen_vault(ctx: Context<ReportFrozenVault>) -> Result<bool> {
+        instructions::report_frozen_vault(ctx)
+    }
+
+    /// Returns the balance of the vault of a mint along with its obligations and free capacity. This acts like a "view"
+    /// function for dashboards and relayers estimating how much of the vault is backing requested slow fills and
+    /// refunds.
+    ///
+    /// The free capacity is the vault balance exceeding the vault liabilities and the output amount reserved for
+    /// requested slow fills, i.e. the amount sweep_tokens can sweep. Counters are best-effort, so the time of the last
+    /// reconcile_counters is returned as an indicator of how stale they can be.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the vault.
+    /// - vault (Account): Vault ATA of the vault authority PDA for the mint.
+    /// - vault_liability (Account): Optional vault liability PDA of the mint, treated as no liabilities when None.
+    ///   Seed: ["vault_liability",state.seed,mint].
+    /// - slow_fill_liability (UncheckedAccount): Slow fill liability PDA of the mint, treated as nothing pending when
+    ///   missing. Seed: ["slow_fill_liability",state.seed,mint].
+    pub fn get_vault_capacity(ctx: Context<GetVaultCapacity>) -> Result<VaultCapacity> {
+        instructions::get_vault_capacity(ctx)
+    }
+
+    /// Enables or disables chaining of core flow events into per epoch event commitment PDAs. Only callable by the
+    /// owner.
+    ///
+    /// When enabled, deposits, fills, slow fill requests and slow fill executions must pass the event commitment PDA
+    /// of the current epoch, so it should be initialized ahead of each epoch.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: True to require and update event commitments, false to skip them.
+    pub fn set_event_commitments(ctx: Context<SetEventCommitmentsState>, enabled: bool) -> Result<()> {
+        instructions::set_event_commitments(ctx, enabled)
+    }
+
+    /// Initializes the event commitment PDA of an epoch, where epochs are the current time divided by
+    /// EVENT_COMMITMENT_EPOCH_DURATION. This is permissionless and can only be called once per epoch.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that pays for the event commitment account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - event_commitment (Writable): The event commitment PDA to initialize.
+    ///   Seed: ["event_commitment",state.seed,epoch].
+    /// - system_program: The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - epoch: The epoch of the events to be committed.
+    pub fn initialize_event_commitment(ctx: Context<InitializeEventCommitment>, epoch: u32) -> Result<()> {
+        instructions::initialize_event_commitment(ctx, epoch)
+    }
+
+    /// Returns the event commitment of an epoch. This acts like a "view" function for indexers to verify their
+    /// reconstructed event stream: starting from a zeroed hash, each event updates it as keccak(hash ‖ event
+    /// discriminator ‖ keccak(event data)) in emission order, and event_count counts the committed events.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - event_commitment (Account): The event commitment PDA. Seed: ["event_commitment",state.seed,epoch].
+    ///
+    /// ### Parameters:
+    /// - _epoch: The epoch of the event commitment.
+    pub fn get_event_commitment(ctx: Context<GetEventCommitment>, _epoch: u32) -> Result<EventCommitment> {
+        instructions::get_event_commitment(ctx)
+    }
+
+    /// Enables or disables the fill index of deposit ids that have a fill status PDA. Only callable by the owner.
+    ///
+    /// When enabled, fills and slow fill requests must pass the fill index shard of the deposit id if it fits into
+    /// u64. Shards are created on first write and paid by the signer, so the fill index adds an extra write per fill.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: True to require and update fill index shards, false to skip them.
+    pub fn set_fill_index(ctx: Context<SetFillIndexState>, enabled: bool) -> Result<()> {
+        instructions::set_fill_index(ctx, enabled)
+    }
+
+    /// Returns the fill index shard covering deposit ids [bucket * 1024, (bucket + 1) * 1024) from the origin chain.
+    /// This acts like a "view" function for the dataworker: bit i of bitmap byte j is set when the deposit id at
+    /// offset 8 * j + i has a fill status PDA that was recorded while the fill index was enabled.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_index_shard (Account): The fill index shard PDA.
+    ///   Seed: ["fill_index",state.seed,origin_chain_id,bucket].
+    ///
+    /// ### Parameters:
+    /// - _origin_chain_id: The origin chain of the indexed deposit ids.
+    /// - _bucket: The deposit id divided by 1024.
+    pub fn get_fill_index_shard(
+        ctx: Context<GetFillIndexShard>,
+        _origin_chain_id: u64,
+        _bucket: u64,
+    ) -> Result<FillIndexShard> {
+        instructions::get_fill_index_shard(ctx)
+    }
+
+    /// Enables or disables vault outflow rate limits. Only callable by the owner.
+    ///
+    /// When enabled, slow fill payouts, relayer refunds and vault sweeps to the owner must pass the rate limit PDA of
+    /// their mint and are rejected once the amount leaving the vault within the current window would exceed its
+    /// limit. Every mint paying out of its vault needs a configured rate limit before enabling them.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: True to debit vault outflows against the rate limits of their mints, false to skip them.
+    pub fn set_vault_outflow_rate_limits(ctx: Context<SetVaultOutflowRateLimitsState>, enabled: bool) -> Result<()> {
+        instructions::set_vault_outflow_rate_limits(ctx, enabled)
+    }
+
+    /// Creates or updates the vault outflow rate limit of a mint. Only callable by the owner.
+    ///
+    /// Windows follow a fixed schedule starting when the rate limit is created and inflows into the vault don't
+    /// replenish the current window. Updates keep the outflow recorded in the current window, so outflows rejected
+    /// with VaultOutflowRateLimited can be lifted by raising the limit.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The owner that pays for the rate limit creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - mint (Account): The mint of the rate limited vault.
+    /// - rate_limit (Writable): The rate limit PDA of the mint. Seed: ["rate_limit",state.seed,mint].
+    /// - token_program (Interface): The token program.
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - limit: Maximum amount of the mint that can leave the vault within each window.
+    /// - window: Length of the rate limit window in seconds. Must be positive.
+    pub fn set_vault_outflow_rate_limit(
+        ctx: Context<SetVaultOutflowRateLimitState>,
+        limit: u64,
+        window: u32,
+    ) -> Result<()> {
+        instructions::set_vault_outflow_rate_limit(ctx, limit, window)
+    }
+
+    /// Opts a mint into refund checks against its fill volume or updates its tolerance. Only callable by the owner.
+    ///
+    /// Once the fill volume PDA of the mint exists, fast fills of the mint credit it and relayer refund leaves of the
+    /// mint are rejected with RefundsExceedFillVolume when the refunds and amount_to_return of their bundle would
+    /// exceed the fill volume since the previous bundle plus the tolerance. This is a safety net against inflated
+    /// relayer refund roots, as refunds on this chain can also repay fills on other chains. Leaves of legitimate
+    /// bundles above it need an execution approval, and u64::MAX disables the check.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The owner that pays for the fill volume creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_volume (Writable): The fill volume PDA of the mint. Seed: ["fill_volume",state.seed,mint].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - mint: The mint opted into refund checks.
+    /// - tolerance: Amount by which the refunds of a bundle can exceed the fill volume of its window.
+    pub fn set_fill_volume_tolerance(
+        ctx: Context<SetFillVolumeToleranceState>,
+        mint: Pubkey,
+        tolerance: u64,
     ) -> Result<()> {
-        instructions::fill_v3_relay(ctx, relay_data, repayment_chain_id, repayment_address)
+        instructions::set_fill_volume_tolerance(ctx, mint, tolerance)
     }
 
-    /// Closes the FillStatusAccount PDA to reclaim relayer rent.
+    /// Overwrites the fill volume of a mint in the open bundle window. Only callable by the owner.
     ///
-    /// This function is used to close the FillStatusAccount associated with a specific relay hash, effectively marking
-    /// the end of its lifecycle. This can only be done once the fill deadline has passed. Relayers should do this for
-    /// all fills once they expire to reclaim their rent.
+    /// Used to reconcile the tracked fill volume with the fills observed off-chain, e.g. after opting in a mint mid
+    /// window. Refunds of the bundle whose leaves are being executed stay bounded by the fill volume of its window.
     ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the closure. Must be the relayer in the fill_status PDA.
-    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
-    /// - fill_status (Writable): The FillStatusAccount PDA to be closed.
-    pub fn close_fill_pda(ctx: Context<CloseFillPda>) -> Result<()> {
-        instructions::close_fill_pda(ctx)
+    /// - signer (Signer): The owner.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_volume (Writable): The fill volume PDA of the mint. Seed: ["fill_volume",state.seed,mint].
+    ///
+    /// ### Parameters:
+    /// - mint: The mint opted into refund checks.
+    /// - fill_volume: The reconciled fill volume of the open window.
+    pub fn reconcile_fill_volume(ctx: Context<ReconcileFillVolumeState>, mint: Pubkey, fill_volume: u64) -> Result<()> {
+        instructions::reconcile_fill_volume(ctx, mint, fill_volume)
     }
 
-    /// Claims a relayer refund for the caller.
+    /// Enables or disables permissioned mode. Callable only by the owner.
     ///
-    /// In the event a relayer refund was sent to a claim account, then this function enables the relayer to claim it by
-    /// transferring the claim amount from the vault to their token account. The claim account is closed after refund.
+    /// In permissioned mode fills require the relayer role, slow fill requests the requester role and slow relay leaf
+    /// executions the executor role in the AllowlistEntry of the signer, see set_allowlist_roles.
     ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the claim.
-    /// - initializer (UncheckedAccount): Must be the same account that initialized the claim account.
-    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
-    /// - vault (InterfaceAccount): The ATA for the refunded mint. Authority must be the state.
-    /// - mint (InterfaceAccount): The mint account for the token being refunded.
-    /// - token_account (InterfaceAccount): The ATA for the token being refunded to.
-    /// - claim_account (Account): The claim account PDA. Seed: ["claim_account",mint,refund_address].
-    /// - token_program (Interface): The token program.
-    pub fn claim_relayer_refund(ctx: Context<ClaimRelayerRefund>) -> Result<()> {
-        instructions::claim_relayer_refund(ctx)
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - enabled: True to check the allowlist roles of signers, false to skip them.
+    /// - strict_slow_fill_requester: True to only accept slow fill requests from signers with the relayer role that are
+    ///   neither the recipient nor the depositor of the relay. Only applies in permissioned mode. Off by default.
+    pub fn set_permissioned_mode(
+        ctx: Context<SetPermissionedModeState>,
+        enabled: bool,
+        strict_slow_fill_requester: bool,
+    ) -> Result<()> {
+        instructions::set_permissioned_mode(ctx, enabled, strict_slow_fill_requester)
     }
 
-    /// Functionally identical to claim_relayer_refund() except the refund is sent to a specified refund address.
-    pub fn claim_relayer_refund_for(ctx: Context<ClaimRelayerRefundFor>, refund_address: Pubkey) -> Result<()> {
-        instructions::claim_relayer_refund_for(ctx, refund_address)
+    /// Grants or revokes the allowlist roles of an account. Only callable by the owner.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The owner that pays for the allowlist entry creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - allowlist_entry (Writable): The allowlist entry PDA of the account. Seed: ["allowlist",state.seed,account].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - account: The account whose roles are set.
+    /// - roles: Bitmap of the granted roles: 1 for relayer, 2 for slow fill requester and 4 for slow relay leaf
+    ///   executor. All other bits must be unset. Zero revokes all roles.
+    pub fn set_allowlist_roles(ctx: Context<SetAllowlistRolesState>, account: Pubkey, roles: u8) -> Result<()> {
+        instructions::set_allowlist_roles(ctx, account, roles)
     }
 
-    /// Creates token accounts in batch for a set of addresses.
-    ///
-    /// This helper function allows the caller to pass in a set of remaining accounts to create a batch of Associated
-    /// Token Accounts (ATAs) for addresses. It is particularly useful for relayers to call before filling a deposit.
+    /// Enables or disables a swap program that deposit_v3_with_swap can invoke. Only callable by the owner.
     ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the creation of token accounts.
-    /// - mint (InterfaceAccount): The mint account for the token.
-    /// - token_program (Interface): The token program.
-    /// - associated_token_program (Program): The associated token program.
+    /// - signer (Signer): The owner that pays for the swap adapter creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - swap_adapter (Writable): The swap adapter PDA of the swap program.
+    ///   Seed: ["swap_adapter",state.seed,swap_program].
     /// - system_program (Program): The system program required for account creation.
-    pub fn create_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CreateTokenAccounts<'info>>) -> Result<()> {
-        instructions::create_token_accounts(ctx)
+    ///
+    /// ### Parameters:
+    /// - swap_program: The program ID of the swap program.
+    /// - enabled: True to allow deposits through the swap program, false to reject them.
+    pub fn set_swap_adapter(ctx: Context<SetSwapAdapterState>, swap_program: Pubkey, enabled: bool) -> Result<()> {
+        instructions::set_swap_adapter(ctx, swap_program, enabled)
     }
 
-    /// **************************************
-    ///           BUNDLE FUNCTIONS           *
-    /// *************************************
-
-    /// Executes relayer refund leaf. Only callable by owner.
+    /// Maps a chain ID alias to its canonical chain ID. Deposits towards the alias use the route, deposit limit and
+    /// destination chain ID of the canonical chain ID, while fills and slow fill requests reject origin chain IDs that
+    /// alias another canonical chain ID. Canonical chain IDs can be registered as aliases of themselves, which strict
+    /// chain alias mode requires for all deposit destinations and relay origins. Only callable by the owner.
     ///
-    /// Processes a relayer refund leaf, verifying its inclusion in a previous Merkle root and that it was not
-    /// previously executed. Function has two modes of operation: a) transfers all relayer refunds directly to
-    /// relayers ATA or b) credits relayers with claimable claim_account PDA that they can use later to claim their
-    /// refund. In the happy path, (a) should be used. (b) should only be used if there is a relayer within the bundle
-    /// who can't receive the transfer for some reason, such as failed token transfers due to blacklisting. Executing
-    /// relayer refunds requires the caller to create a LUT and load the execution params into it. This is needed to
-    /// fit the data in a single instruction. The exact structure and validation of the leaf is defined in the UMIP.
+    /// ### Required Accounts:
+    /// - signer (Signer): The owner that pays for the chain alias creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - chain_alias (Writable): The chain alias PDA, created if needed. Seed: ["chain_alias",state.seed,alias].
+    /// - system_program (Program): The system program required for account creation.
     ///
-    /// instruction_params Parameters:
-    /// - root_bundle_id: The ID of the root bundle containing the relayer refund root.
-    /// - relayer_refund_leaf: The relayer refund leaf to be executed. Contents must include:
-    ///     - amount_to_return: The amount to be to be sent back to mainnet Ethereum from this Spoke pool.
-    ///     - chain_id: The targeted chainId for the refund. Validated against state.chain_id.
-    ///     - refund_amounts: The amounts to be returned to the relayer for each refund_address.
-    ///     - leaf_id: The leaf ID of the relayer refund leaf.
-    ///     - mint_public_key: The public key of the mint (refunded token) being refunded.
-    ///     - refund_addresses: The addresses to be refunded.
-    /// - proof: The Merkle proof for the relayer refund leaf.
+    /// ### Parameters:
+    /// - alias: The chain ID alias.
+    /// - canonical_chain_id: The canonical chain ID of the alias. Must not be 0.
+    pub fn set_chain_alias(ctx: Context<SetChainAliasState>, alias: u64, canonical_chain_id: u64) -> Result<()> {
+        instructions::set_chain_alias(ctx, alias, canonical_chain_id)
+    }
+
+    /// Removes a chain ID alias, returning its rent to the owner. Only callable by the owner.
     ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the execution. No permission requirements.
-    /// - instruction_params (Account): LUT containing the execution parameters. seed: ["instruction_params",signer]
-    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
-    /// - root_bundle (Writable): The root bundle PDA containing the relayer refund root, created when the root bundle
-    ///   was initially bridged. seed: ["root_bundle",state.seed,root_bundle_id].
-    /// - vault (Writable): The ATA for refunded mint. Authority must be the state.
-    /// - mint (Account): The mint account for the token being refunded.
-    /// - transfer_liability (Writable): Account to track pending refunds to be sent to the Ethereum hub pool. Only used
-    ///   if the amount_to_return value is non-zero within the leaf. Seed: ["transfer_liability",mint]
-    /// - token_program: The token program.
-    /// - system_program: The system program required for account creation.
+    /// - signer (Signer): The owner receiving the chain alias rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - chain_alias (Writable): The chain alias PDA being closed. Seed: ["chain_alias",state.seed,alias].
     ///
-    /// execute_relayer_refund_leaf executes in mode (a) where refunds are sent to ATA directly.
-    /// execute_relayer_refund_leaf_deferred executes in mode (b) where refunds are allocated to the claim_account PDA.
-    pub fn execute_relayer_refund_leaf<'c, 'info>(
-        ctx: Context<'_, '_, 'c, 'info, ExecuteRelayerRefundLeaf<'info>>,
-    ) -> Result<()>
-    where
-        'c: 'info,
-    {
-        instructions::execute_relayer_refund_leaf(ctx, false)
+    /// ### Parameters:
+    /// - alias: The chain ID alias.
+    pub fn remove_chain_alias(ctx: Context<RemoveChainAlias>, alias: u64) -> Result<()> {
+        instructions::remove_chain_alias(ctx, alias)
     }
 
-    pub fn execute_relayer_refund_leaf_deferred<'c, 'info>(
-        ctx: Context<'_, '_, 'c, 'info, ExecuteRelayerRefundLeaf<'info>>,
-    ) -> Result<()>
-    where
-        'c: 'info,
-    {
-        instructions::execute_relayer_refund_leaf(ctx, true)
+    /// Initializes the bundle audit PDA that stores vault balance snapshots for a relayed root bundle.
+    ///
+    /// Must be called before executing relayer refund leaves of the bundle when bundle audits are enabled. The account
+    /// is sized to track the number of mints configured in the state at the time of this call.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that pays for the bundle audit account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Account): Root bundle PDA to audit. Seed: ["root_bundle",state.seed,root_bundle_id].
+    /// - bundle_audit (Writable): The bundle audit PDA to initialize. Seed: ["bundle_audit",state.seed,root_bundle_id].
+    /// - system_program: The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - root_bundle_id: ID of the root bundle to audit.
+    pub fn initialize_bundle_audit(ctx: Context<InitializeBundleAudit>, root_bundle_id: u32) -> Result<()> {
+        instructions::initialize_bundle_audit(ctx, root_bundle_id)
     }
 
     /// Bridges tokens to the Hub Pool.
     ///
     /// This function initiates the process of sending tokens from the vault to the Hub Pool based on the outstanding
     /// token liability this Spoke Pool has accrued. Enables the caller to choose a custom amount to work around CCTP
-    /// bridging limits. enforces that amount is less than or equal to liability. On execution decrements liability.
+    /// bridging limits. enforces that amount is less than or equal to liability and the CCTP burn limit per message.
+    /// Relayer refund leaf execution only credits the liability, so this is permissionless and can be retried if the
+    /// CCTP burn fails. Liability is only decremented after a successful burn.
     ///
     /// ### Required Accounts:
     /// - signer (Signer): The account that authorizes the bridge operation.
@@ -553,13 +2893,15 @@ pub mod svm_spoke {
     /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
     /// - transfer_liability (Account): Account tracking the pending amount to be sent to the Hub Pool. Incremented on
     ///   relayRootBundle() and decremented on when this function is called. Seed: ["transfer_liability",mint].
-    /// - vault (InterfaceAccount): The ATA for the token being bridged. Authority must be the state.
+    /// - vault (InterfaceAccount): The ATA for the token being bridged. Authority must be the vault authority.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
     /// - token_messenger_minter_sender_authority (UncheckedAccount): Authority for the token messenger minter.
     /// - message_transmitter (UncheckedAccount): Account for the message transmitter.
     /// - token_messenger (UncheckedAccount): Account for the token messenger.
     /// - remote_token_messenger (UncheckedAccount): Account for the remote token messenger.
     /// - token_minter (UncheckedAccount): Account for the token minter.
-    /// - local_token (UncheckedAccount): Account for the local token.
+    /// - local_token (Account): CCTP local token account. Its burn_limit_per_message caps the bridged amount.
     /// - cctp_event_authority (UncheckedAccount): Authority for CCTP events.
     /// - message_sent_event_data (Signer): Account for message sent event data.
     /// - message_transmitter_program (Program): Program for the message transmitter.
@@ -582,12 +2924,16 @@ pub mod svm_spoke {
     /// - signer (Signer): The account that pays for the transaction and initializes the instruction parameters.
     /// - instruction_params (UncheckedAccount): The account where raw data will be stored. Initialized with specified
     ///   size. seed: ["instruction_params",signer].
+    /// - instruction_params_header (Writable): Header recording the signer as payer and the creation time, so that
+    ///   stale instruction params can be closed by others. seed: ["instruction_params_header",signer].
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet. Initialization is
+    ///   rejected with UpgradePending while an announced program upgrade is effective.
     /// - system_program: The system program required for account creation.
     ///
     /// ### Parameters:
     /// - _total_size: The total size of the instruction parameters account.
-    pub fn initialize_instruction_params(_ctx: Context<InitializeInstructionParams>, _total_size: u32) -> Result<()> {
-        Ok(())
+    pub fn initialize_instruction_params(ctx: Context<InitializeInstructionParams>, _total_size: u32) -> Result<()> {
+        instructions::initialize_instruction_params(ctx)
     }
 
     /// Writes a fragment of raw data into the instruction parameters account.
@@ -620,10 +2966,29 @@ pub mod svm_spoke {
     /// - signer (Signer): The account that authorizes the closure.
     /// - instruction_params (UncheckedAccount): The account to be closed. seed: ["instruction_params",signer]. Not
     ///   the signer being within the seed here implicitly protects this from only being called by the creator.
+    /// - instruction_params_header (Writable): The header of the instruction params, also closed.
+    ///   seed: ["instruction_params_header",signer].
     pub fn close_instruction_params(ctx: Context<CloseInstructionParams>) -> Result<()> {
         instructions::close_instruction_params(ctx)
     }
 
+    /// Closes the instruction parameters account of a relayer that did not close it within the params buffer TTL.
+    ///
+    /// This is permissionless so that buffers of crashed relayers do not leak rent. The rent of both the instruction
+    /// params and their header is always returned to the original payer recorded in the header, never to the caller.
+    /// Emits a ClosedStaleParamsBuffer event. Rejected when the state params_buffer_ttl is 0.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the closure. No permission requirements.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - payer (Writable): The original payer recorded in the header, receiving the reclaimed rent.
+    /// - instruction_params (UncheckedAccount): The account to be closed. seed: ["instruction_params",payer].
+    /// - instruction_params_header (Writable): The header of the instruction params, also closed.
+    ///   seed: ["instruction_params_header",payer].
+    pub fn close_stale_instruction_params(ctx: Context<CloseStaleInstructionParams>) -> Result<()> {
+        instructions::close_stale_instruction_params(ctx)
+    }
+
     /// Initializes a claim account for a relayer refund.
     ///
     /// This function sets up a claim account for a relayer to claim their refund at a later time and should only be
@@ -651,10 +3016,12 @@ pub mod svm_spoke {
     ///
     /// This function is used to close the claim account associated with a specific mint and refund address,
     /// effectively marking the end of its lifecycle. It can only be called once the claim account is empty. It
-    /// transfers any remaining lamports to the signer and resets the account.
+    /// transfers any remaining lamports to the signer and resets the account. Closing is rejected during the dispute
+    /// freeze window.
     ///
     /// ### Required Accounts:
     /// - signer (Signer): The account that authorizes the closure. Must be the initializer of the claim account.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
     /// - claim_account (Writable): The claim account PDA to be closed. Seed: ["claim_account",mint,refund_address].
     ///
     /// ### Parameters:
@@ -672,6 +3039,60 @@ pub mod svm_spoke {
     ///         SLOW FILL FUNCTIONS          *
     /// *************************************
 
+    /// Initializes the requester activity PDA that counts the signer's slow fill requests.
+    ///
+    /// Must be called before requesting slow fills when slow fill rate limits are enabled.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account whose slow fill requests are counted. Pays for the account creation.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - requester_activity (Writable): The requester activity PDA to initialize.
+    ///   Seed: ["requester_activity",state.seed,signer].
+    /// - system_program: The system program required for account creation.
+    pub fn initialize_requester_activity(_ctx: Context<InitializeRequesterActivity>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Closes the signer's requester activity PDA and returns its rent.
+    ///
+    /// Can only be called once the current rate limit window of the signer has passed, so that closing and recreating
+    /// the account cannot be used to reset the request count.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account whose requester activity is closed. Receives the rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - requester_activity (Writable): The requester activity PDA to close.
+    ///   Seed: ["requester_activity",state.seed,signer].
+    pub fn close_requester_activity(ctx: Context<CloseRequesterActivity>) -> Result<()> {
+        instructions::close_requester_activity(ctx)
+    }
+
+    /// Closes a stale account of one of the garbage collectable kinds, reclaiming its rent. Callable by anyone.
+    ///
+    /// Supported kinds and their eligibility:
+    /// - Route: disabled, or past its scheduled disable time, for at least GC_ROUTE_DISABLED_PERIOD. Routes that still
+    ///   configure fills of their token with a burn settlement mode, a decimal shift, a relayer fee floor or expected
+    ///   mint decimals are never closed.
+    /// - RequesterActivity: rate limit window ended at least GC_REQUESTER_ACTIVITY_RETENTION ago.
+    /// - EventCommitment: epoch ended at least GC_EVENT_COMMITMENT_RETENTION ago.
+    ///
+    /// When the kind records the original payer of the account, the caller receives GC_CALLER_INCENTIVE_BPS of the
+    /// rent and the rest is returned to the original payer. Otherwise the caller receives all of the rent. Emits a
+    /// GarbageCollectedAccount event naming the account kind and key.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The caller receiving its share of the rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - target (Writable): The account to close. Must be the PDA derived from the kind arguments.
+    /// - original_payer (Writable): Original payer of the account, only required for RequesterActivity where it must be
+    ///   the requester. Pass this program ID to represent None.
+    ///
+    /// ### Parameters:
+    /// - kind: The account kind of the target, with the arguments needed to derive its PDA.
+    pub fn gc_account(ctx: Context<GcAccount>, kind: GcAccountKind) -> Result<()> {
+        instructions::gc_account(ctx, kind)
+    }
+
     /// Requests Across to send LP funds to this program to fulfill a slow fill.
     ///
     /// Slow fills are not possible unless the input and output tokens are "equivalent", i.e., they route to the same L1
@@ -680,6 +3101,17 @@ pub mod svm_spoke {
     /// relay the slow root to this chain via relayRootBundle(). Once the slow root is relayed, the slow fill can be
     /// executed by anyone who calls executeV3SlowRelayLeaf(). Cant request a slow fill if the fill deadline has
     /// passed. Cant request a slow fill if the relay has already been filled or a slow fill has already been requested.
+    /// Cant request a slow fill until slow_fill_grace_seconds configured in the state have passed after the exclusivity
+    /// deadline, see get_relay_timing, unless the signer is the exclusive relayer of the relay and the state does not
+    /// set exclusive_slow_fills_disabled. Relays without an exclusive relayer have no exclusivity window. Cant
+    /// request a slow fill of relay data that fill_v3_relay rejects as degenerate.
+    ///
+    /// Emits RequestedV3SlowFill followed by RequestedV3SlowFill2, which additionally carries the destination chain
+    /// ID, the signer as requester and the relay hash. RequestedV3SlowFill also carries the current time of this
+    /// program and the seconds left until the fill deadline, so that slow fill batching can order requests by urgency.
+    /// The output amount is added to the pending slow fill liability of the output token, emitting
+    /// SlowFillLiabilityChanged. It is released once the slow fill is executed, replaced by a fast fill or closed
+    /// unexecuted, and until then neither sweeps nor relayer refunds can take the vault balance below it.
     ///
     /// ### Required Accounts:
     /// - signer (Signer): The account that authorizes the slow fill request.
@@ -689,22 +3121,176 @@ pub mod svm_spoke {
     /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
     /// - fill_status (Writable): The fill status PDA, created on this function call. Updated to track slow fill status.
     ///   Used to prevent double request and fill. Seed: ["fills",relay_hash].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the output token, credited with the requested
+    ///   output amount. Created if needed. Seed: ["slow_fill_liability",state.seed,output_token].
+    /// - message_schema (Account): Message schema PDA of the recipient, required even when no schema is registered.
+    ///   Non-empty messages must match the size bounds and schema tag of a registered schema.
+    ///   Seed: ["message_schema",recipient].
+    /// - depositor_compliance (Account): Blocklist entry of the depositor, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    /// - recipient_compliance (Account): Blocklist entry of the recipient, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    /// - allowlist_entry (Account): Allowlist entry of the signer, only required in permissioned mode for signers
+    ///   holding the requester role, or the relayer role with the strict
+    ///   requester check. Pass this program ID to represent None. Seed: ["allowlist",state.seed,signer].
+    /// - requester_activity (Writable): Request counter of the signer, only required when slow fill rate limits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["requester_activity",state.seed,signer].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - fill_index_shard (Writable): Fill index shard of the deposit id, only required when the fill index is enabled
+    ///   and the deposit id fits into u64. Created if missing. Pass this program ID to represent None.
+    ///   Seed: ["fill_index",state.seed,origin_chain_id,deposit_id/1024].
+    /// - origin_chain_alias (Account): Chain alias of the origin chain ID, only required in strict chain alias mode.
+    ///   Rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent None.
+    ///   Seed: ["chain_alias",state.seed,origin_chain_id].
+    /// - relayer_stats (Writable): Stats of the exclusive relayer, counting the request as a lapse of their exclusivity
+    ///   window. Only pass when the relay names an exclusive relayer. Created if missing. Pass this program ID to
+    ///   represent None. Seed: ["relayer_stats",state.seed,exclusive_relayer].
     /// - system_program (Interface): The system program.
+    /// - payer (Writable, Signer): The account paying the rent of the fill_status PDA and any other account created by
+    ///   the request, same as in fill_v3_relay.
     ///
     /// ### Parameters:
-    /// - _relay_hash: The hash identifying the deposit to be filled. Caller must pass this in. Computed as hash of
+    /// - relay_hash: The hash identifying the deposit to be filled. Caller must pass this in. Computed as hash of
     ///   the flattened relay_data & destination_chain_id.
     /// - relay_data: Struct containing all the data needed to identify the deposit that should be slow filled. If any
     ///   of the params are missing or different from the origin chain deposit, then Across will not include a slow
-    ///   fill for the intended deposit. See fill_v3_relay & V3RelayData struct for more details.
+    ///   fill for the intended deposit. See fill_v3_relay & V3RelayData struct for more details. Message-only relays
+    ///   can be requested as well, flagged as message_only in the RequestedV3SlowFill2 event.
     /// Note: relay_data is optional parameter. If None for it is passed, the caller must load it via the
     /// instruction_params account.
     pub fn request_v3_slow_fill(
         ctx: Context<RequestV3SlowFill>,
-        _relay_hash: [u8; 32],
+        relay_hash: [u8; 32],
         relay_data: Option<V3RelayData>,
     ) -> Result<()> {
-        instructions::request_v3_slow_fill(ctx, relay_data)
+        instructions::request_v3_slow_fill(ctx, relay_hash, relay_data)
+    }
+
+    /// Requests a slow fill on behalf of the deposit recipient who signed the request off-chain.
+    ///
+    /// This enables recipients without SOL to request their own slow fill: any payer can submit the request and pays
+    /// the rent for the fill_status PDA. The recipient signs the relay_hash followed by the little-endian encoded nonce
+    /// and the signature must be verified by an Ed25519 program instruction immediately preceding this instruction.
+    /// Each nonce can only be used once per recipient and nonces must be increasing, while each signature can only be
+    /// used once until the fill deadline. All other checks are the same as in request_v3_slow_fill. Rejected in
+    /// permissioned mode, as the recipient requester holds no allowlist role. The RequestedV3SlowFill2 event carries
+    /// the recipient as requester.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that submits the request and pays the rent. Can close the fill_status PDA.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - fill_status (Writable): The fill status PDA, created on this function call. Records the recipient as the
+    ///   requester and the signer as the relayer paying rent. Seed: ["fills",relay_hash].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the output token, credited with the requested
+    ///   output amount. Created if needed. Seed: ["slow_fill_liability",state.seed,output_token].
+    /// - requester_nonce (Writable): Tracks the next accepted nonce for the recipient, created on the first call.
+    ///   Seed: ["requester_nonce",recipient].
+    /// - used_signature (Writable): Records the consumed requester signature to reject its replays, created on this
+    ///   function call. Seed: ["used_signature",state.seed,keccak(requester_signature)[..16]].
+    /// - message_schema (Account): Message schema PDA of the recipient, required even when no schema is registered.
+    ///   Non-empty messages must match the size bounds and schema tag of a registered schema.
+    ///   Seed: ["message_schema",recipient].
+    /// - depositor_compliance (Account): Blocklist entry of the depositor, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    /// - recipient_compliance (Account): Blocklist entry of the recipient, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    /// - requester_activity (Writable): Request counter of the signer, only required when slow fill rate limits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["requester_activity",state.seed,signer].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - fill_index_shard (Writable): Fill index shard of the deposit id, only required when the fill index is enabled
+    ///   and the deposit id fits into u64. Created if missing. Pass this program ID to represent None.
+    ///   Seed: ["fill_index",state.seed,origin_chain_id,deposit_id/1024].
+    /// - origin_chain_alias (Account): Chain alias of the origin chain ID, only required in strict chain alias mode.
+    ///   Rejects origin chain IDs aliasing another canonical chain ID. Pass this program ID to represent None.
+    ///   Seed: ["chain_alias",state.seed,origin_chain_id].
+    /// - instructions_sysvar (Account): The instructions sysvar used to load the Ed25519 verification instruction.
+    /// - system_program (Interface): The system program.
+    ///
+    /// ### Parameters:
+    /// - relay_hash: The hash identifying the deposit to be filled. Computed as hash of the flattened relay_data &
+    ///   destination_chain_id.
+    /// - relay_data: Struct containing all the data needed to identify the deposit that should be slow filled.
+    /// - nonce: The recipient's request nonce included in the signed payload.
+    /// - requester_signature: The recipient's Ed25519 signature over the relay_hash and nonce.
+    pub fn request_v3_slow_fill_for(
+        ctx: Context<RequestV3SlowFillFor>,
+        relay_hash: [u8; 32],
+        relay_data: V3RelayData,
+        nonce: u64,
+        requester_signature: [u8; 64],
+    ) -> Result<()> {
+        instructions::request_v3_slow_fill_for(ctx, relay_hash, relay_data, nonce, requester_signature)
+    }
+
+    /// Requests slow fills of a batch of relays with the same output token, as request_v3_slow_fill would for each.
+    ///
+    /// Relays whose fill status is no longer Unfilled, because they were filled, slow fill requested or expired in the
+    /// meantime, are skipped instead of reverting the batch. Any other failing relay reverts the whole batch. The
+    /// signer is recorded as the requester of all slow fills and relayer stats are not updated. Batches are limited to
+    /// MAX_SLOW_FILL_REQUESTS_PER_BATCH relays, each consuming roughly 50k compute units.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that submits the requests and pays the rent of the created fill status PDAs.
+    /// - instruction_params (Account): Optional account holding the RequestV3SlowFillsBatchParams when the relays are
+    ///   not passed in the instruction data due to message size constraints. Pass this program ID to represent None.
+    ///   Seed: ["instruction_params",signer].
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the output token, credited with the output amounts
+    ///   of all requested relays. Created if needed. Seed: ["slow_fill_liability",state.seed,output_token].
+    /// - allowlist_entry (Account): Allowlist entry of the signer, only required in permissioned mode for signers
+    ///   holding the requester role. Pass this program ID to represent None. Seed: ["allowlist",state.seed,signer].
+    /// - requester_activity (Writable): Request counter of the signer, only required when slow fill rate limits are
+    ///   enabled. Pass this program ID to represent None. Seed: ["requester_activity",state.seed,signer].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - system_program (Interface): The system program.
+    /// - remaining_accounts: For each relay in order, the writable fill status PDA with seeds ["fills",relay_hash],
+    ///   created if missing.
+    ///
+    /// ### Parameters:
+    /// - output_token: The output token of all relays, used to derive the slow fill liability PDA.
+    /// - relays: Relays to request slow fills for. If None is passed, the caller must load them via the
+    ///   instruction_params account.
+    pub fn request_v3_slow_fills_batch<'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RequestV3SlowFillsBatch<'info>>,
+        output_token: Pubkey,
+        relays: Option<Vec<V3RelayData>>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        instructions::request_v3_slow_fills_batch(ctx, output_token, relays)
+    }
+
+    /// Returns the timing windows of a relay. This acts like a "view" function for relayers and requesters to know when
+    /// a relay can be fast filled exclusively, slow fill requested and filled at all.
+    ///
+    /// ### Required Accounts:
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - relay_data: Struct containing all the data needed to identify the deposit.
+    pub fn get_relay_timing(ctx: Context<GetRelayTiming>, relay_data: V3RelayData) -> Result<RelayTiming> {
+        instructions::get_relay_timing(ctx, relay_data)
+    }
+
+    /// Computes the relay hash of the relay data on the chain. This acts like a "view" function for relayers to derive
+    /// the fill status PDA with the exact hashing that fills and slow fills validate relay hashes against.
+    ///
+    /// ### Parameters:
+    /// - relay_data: Struct containing all the data needed to identify the deposit.
+    /// - chain_id: The chain ID of the spoke filling the relay, i.e. the chain_id of its state.
+    pub fn get_v3_relay_hash(_ctx: Context<Null>, relay_data: V3RelayData, chain_id: u64) -> Result<[u8; 32]> {
+        Ok(utils::get_v3_relay_hash(&relay_data, chain_id))
+    }
+
+    /// Returns the event authority PDA signing the self-CPI of emitted events. This acts like a "view" function for
+    /// indexers filtering the inner instructions of events by their only account, so that they don't hardcode the seed
+    /// of the Anchor version the program was built with.
+    pub fn get_event_authority(_ctx: Context<Null>) -> Result<Pubkey> {
+        Ok(utils::derive_event_authority())
     }
 
     /// Executes a slow relay leaf stored as part of a root bundle relayed by the HubPool.
@@ -714,8 +3300,20 @@ pub mod svm_spoke {
     /// like any other fill sent through fillV3Relay(). There is no relayer credited with filling this relay since funds
     /// are sent directly out of this program's vault.
     ///
+    /// Same as in the EVM SpokePool, leaves can only be executed until the fill deadline of the relay, even when the root
+    /// bundle was relayed before the deadline (see SLOW_FILL_EXECUTABLE_AFTER_FILL_DEADLINE).
+    ///
+    /// Vault transfers of Token-2022 mints with the TransferFee extension are grossed up by the transfer fee of the
+    /// current epoch, so that the recipient receives the full payout.
+    ///
+    /// While fills are paused, only the emergency executor of the state can execute leaves, each with an execution
+    /// approval of the leaf by the execution approver regardless of its threshold. Such executions set the
+    /// emergency_execution flag of the FilledV3Relay event.
+    ///
     /// ### Required Accounts:
-    /// - signer (Signer): The account that authorizes the execution. No permission requirements.
+    /// - signer (Signer): The account that authorizes the execution. No permission requirements unless fills are
+    ///   paused, when it must be the emergency executor. Writable to pay the native unwrap account rent, which is
+    ///   returned within the execution.
     /// - instruction_params (Account): Optional account to load instruction parameters when they are not passed in the
     ///   instruction data due to message size constraints. Pass this program ID to represent None. When Some, this must
     ///   be derived from the signer's public key with seed ["instruction_params",signer].
@@ -724,33 +3322,215 @@ pub mod svm_spoke {
     /// - fill_status (Writable): The fill status PDA, created when slow request was made. Updated to track slow fill.
     ///   Used to prevent double request and fill. Seed: ["fills",relay_hash].
     /// - mint (Account): The mint account for the output token.
-    /// - recipient_token_account (Writable): The recipient's ATA for the output token.
-    /// - vault (Writable): The ATA for refunded mint. Authority must be the state.
-    /// - token_program (Interface): The token program.
+    /// - route (Account): Optional route PDA selecting the settlement mode. Pass this program ID to represent None,
+    ///   which settles by vault transfer. Seed: ["route",mint,state.seed,relay_data.origin_chain_id].
+    /// - recipient_token_account (Writable): The recipient's token account for the output token, usually their ATA. Any
+    ///   token account whose owner is the recipient is accepted. Pass this program ID to represent None when the route
+    ///   settles by burning from the vault or the recipient opted into escrow delivery.
+    /// - delivery_preference (Account): Optional delivery preference set by the recipient. When its mode is Escrow,
+    ///   vault transfers are sent to the recipient escrow. Pass this program ID to represent None.
+    ///   Seed: ["delivery_preference",state.seed,recipient].
+    /// - recipient_escrow (Writable): The recipient escrow for the output token, only required for vault transfers when
+    ///   the recipient opted into escrow delivery. Pass this program ID to represent None.
+    ///   Seed: ["escrow",state.seed,recipient,mint].
+    /// - native_unwrap_account (Writable): Temporary wSOL account of the state, only required for vault transfers of
+    ///   wSOL outputs when the recipient opted into native delivery. Pass this program ID to represent None.
+    ///   Seed: ["native_unwrap",state.seed,signer].
+    /// - native_recipient (Writable): The relay recipient receiving the unwrapped lamports, only required together with
+    ///   the native unwrap account. Pass this program ID to represent None.
+    /// - depositor_compliance (Account): Blocklist entry of the depositor, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,depositor].
+    /// - recipient_compliance (Account): Blocklist entry of the recipient, only required when the state has a
+    ///   compliance authority. Pass this program ID to represent None. Seed: ["compliance",state.seed,recipient].
+    /// - allowlist_entry (Account): Allowlist entry of the signer, only required in permissioned mode for signers
+    ///   holding the executor role. Pass this program ID to represent None. Seed: ["allowlist",state.seed,signer].
+    /// - vault (Writable): The ATA for refunded mint. Authority must be the vault authority. Fails with
+    ///   VaultMissingForMint when the vault was closed or never created.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - fee_vault (Writable): The fee vault of the output token, only required for vault transfers when the state
+    ///   charges a protocol fee. The fee is skimmed from the payout. Pass this program ID to represent None.
+    ///   Seed: ["fee_vault",state.seed,mint].
+    /// - liquidity_pool (Writable): The liquidity pool of the output token, only required for vault transfers when the
+    ///   state enables liquidity pools with a fee. The fee stays in the vault and accrues to the pool share holders.
+    ///   Pass this program ID to represent None. Seed: ["liquidity_pool",state.seed,mint].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - memo_program (Account): The SPL Memo program, only required when a payout memo is passed in the extra args.
+    ///   Pass this program ID to represent None.
+    /// - rate_limit (Writable): Optional rate limit of the output token, only required for vault transfers when vault
+    ///   outflow rate limits are enabled. Pass this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
+    /// - vault_liability (Writable): Optional liabilities of the vault, debited with the slow fill payout. Pass this
+    ///   program ID to represent None. Seed: ["vault_liability",state.seed,mint].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the mint, released by the executed slow fill.
+    ///   Skipped if not created yet. Seed: ["slow_fill_liability",state.seed,mint].
+    /// - execution_approval (Account): Approval of the slow fill leaf by the execution approver, only required when the
+    ///   updated output amount exceeds the execution approval threshold of the mint or for emergency executions. Pass
+    ///   this program ID to represent None. Seed: ["execution_approval",state.seed,leaf_hash].
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
     /// - system_program (Program): The system program.
     ///
     /// ### Parameters:
-    /// - _relay_hash: The hash identifying the deposit to be filled. Used to identify the deposit to be filled.
+    /// - relay_hash: The hash identifying the deposit to be filled. Emitted in the FilledV3Relay event.
     /// - slow_fill_leaf: Contains all data necessary to uniquely verify the slow fill. This struct contains:
     ///     - relayData: Struct containing all the data needed to identify the original deposit to be slow filled. Same
     ///       as the relay_data struct in fill_v3_relay().
     ///     - chainId: Chain identifier where slow fill leaf should be executed. If this doesn't match this chain's
-    ///       chainId, then this function will revert.
+    ///       chainId, then this function will revert with WrongDestinationChain.
     ///     - updatedOutputAmount: Amount to be sent to recipient out of this contract's balance. Can be set differently
     ///       from relayData.outputAmount to charge a different fee because this deposit was "slow" filled. Usually,
-    ///       this will be set higher to reimburse the recipient for waiting for the slow fill.
+    ///       this will be set higher to reimburse the recipient for waiting for the slow fill. Zero skips the vault
+    ///       transfer and the recipient token account, only running the message handler, and is rejected with
+    ///       EmptyRelay for relays without a message.
     /// - _root_bundle_id: Unique ID of root bundle containing slow relay root that this leaf is contained in.
-    /// - proof: Inclusion proof for this leaf in slow relay root in root bundle.
+    /// - proof: Inclusion proof for this leaf in slow relay root in root bundle, at most 32 nodes deep.
+    /// - args_version: Version of the extra args blob. Versions 0 and 1 are supported, unknown versions are rejected.
+    /// - extra: Extra args encoded according to args_version. Must be empty for version 0. Version 1 is the borsh
+    ///   serialized optional payout memo of 1 to 64 bytes, logged via the SPL Memo program right before the payout
+    ///   transfer and hashed into the emitted memo_hash. The memo is mandatory for Token-2022 recipient accounts that
+    ///   require incoming transfer memos.
     /// Note: slow_fill_leaf, _root_bundle_id, and proof are optional parameters. If None for any of these is passed,
     /// the caller must load them via the instruction_params account.
     pub fn execute_v3_slow_relay_leaf<'info>(
         ctx: Context<'_, '_, '_, 'info, ExecuteV3SlowRelayLeaf<'info>>,
-        _relay_hash: [u8; 32],
+        relay_hash: [u8; 32],
         slow_fill_leaf: Option<V3SlowFill>,
         _root_bundle_id: Option<u32>,
         proof: Option<Vec<[u8; 32]>>,
+        args_version: u8,
+        extra: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_v3_slow_relay_leaf(ctx, relay_hash, slow_fill_leaf, proof, args_version, extra)
+    }
+
+    /// Executes up to 12 slow relay leaves of the same root bundle and output token in a single instruction.
+    ///
+    /// Each leaf is verified against the slow relay root of the root bundle and executed as in
+    /// execute_v3_slow_relay_leaf, settling by vault transfer to the recipient's associated token account. Leaves with
+    /// messages, under a compliance authority, above the execution approval threshold, on burn settlement routes or for
+    /// recipients opted into escrow or native delivery must be executed individually. Any failing leaf reverts the
+    /// whole batch. Each leaf consumes roughly 40k to 60k compute units depending on its proof length and fees. Batches
+    /// are rejected while fills are paused, when leaves can only be executed individually by the emergency executor.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that authorizes the execution. Must be the restricted executor of the root
+    ///   bundle during its grace period.
+    /// - instruction_params (Account): Optional account holding the ExecuteV3SlowRelayLeavesParams when the leaves are
+    ///   not passed in the instruction data due to message size constraints. Pass this program ID to represent None.
+    ///   Seed: ["instruction_params",signer].
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - root_bundle (Account): Root bundle PDA with slowRelayRoot. Seed: ["root_bundle",state.seed,root_bundle_id].
+    /// - allowlist_entry (Account): Allowlist entry of the signer, only required in permissioned mode for signers
+    ///   holding the executor role. Pass this program ID to represent None. Seed: ["allowlist",state.seed,signer].
+    /// - mint (Account): The mint account of the output token of all leaves.
+    /// - vault (Writable): The ATA of the vault authority for the mint.
+    /// - vault_authority (UncheckedAccount): Vault authority PDA owning and signing for the vault.
+    ///   Seed: ["vault_authority",state].
+    /// - fee_vault (Writable): The fee vault of the mint, only required when the state charges a protocol fee. Pass
+    ///   this program ID to represent None. Seed: ["fee_vault",state.seed,mint].
+    /// - liquidity_pool (Writable): The liquidity pool of the mint, only required when the state enables liquidity
+    ///   pools with a fee. Pass this program ID to represent None. Seed: ["liquidity_pool",state.seed,mint].
+    /// - event_commitment (Writable): Event commitment PDA of the current epoch, only required when event commitments
+    ///   are enabled. Pass this program ID to represent None. Seed: ["event_commitment",state.seed,epoch].
+    /// - rate_limit (Writable): Rate limit of the mint, only required when vault outflow rate limits are enabled. Pass
+    ///   this program ID to represent None. Seed: ["rate_limit",state.seed,mint].
+    /// - vault_liability (Writable): Optional liabilities of the vault, debited with the slow fill payouts. Pass this
+    ///   program ID to represent None. Seed: ["vault_liability",state.seed,mint].
+    /// - slow_fill_liability (Writable): Pending slow fill amount of the mint, released by the executed slow fills.
+    ///   Skipped if not created yet. Seed: ["slow_fill_liability",state.seed,mint].
+    /// - token_program (Interface): The token program owning the mint, others fail with TokenProgramMismatch.
+    /// - remaining_accounts: For each leaf in order, the writable fill status PDA created by the slow fill request,
+    ///   the route PDA with seeds ["route",mint,state.seed,origin_chain_id], the delivery preference PDA with seeds
+    ///   ["delivery_preference",state.seed,recipient] and the writable recipient ATA. Pass this program ID for the
+    ///   route or delivery preference PDA to represent None.
+    ///
+    /// ### Parameters:
+    /// - _root_bundle_id: Unique ID of root bundle containing slow relay root that all leaves are contained in.
+    /// - slow_relay_leaves: Slow fill leaves with their inclusion proofs, same as in execute_v3_slow_relay_leaf. If
+    ///   None is passed, the caller must load them via the instruction_params account.
+    pub fn execute_v3_slow_relay_leaves<'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExecuteV3SlowRelayLeaves<'info>>,
+        _root_bundle_id: u32,
+        slow_relay_leaves: Option<Vec<SlowRelayLeafExecution>>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        instructions::execute_v3_slow_relay_leaves(ctx, slow_relay_leaves)
+    }
+
+    /// Sets, rotates or clears the execution approver of high-value slow fill leaves. Callable only by the owner.
+    ///
+    /// Approvals created by a previous approver are no longer honored once the approver is rotated.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - execution_approver: The key approving slow fill leaves above their mint threshold, or None to disable the
+    ///   execution approvals.
+    /// - execution_approval_validity: Number of seconds after their creation during which approvals can be used.
+    pub fn set_execution_approver(
+        ctx: Context<SetExecutionApprovalConfigState>,
+        execution_approver: Option<Pubkey>,
+        execution_approval_validity: u32,
+    ) -> Result<()> {
+        instructions::set_execution_approver(ctx, execution_approver, execution_approval_validity)
+    }
+
+    /// Sets or removes the execution approval threshold of a mint. Callable only by the owner.
+    ///
+    /// Executing slow fill leaves with an updated output amount above the threshold of their output token requires an
+    /// execution approval, while leaves of mints without a threshold stay permissionless.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to authorize the change.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    ///
+    /// ### Parameters:
+    /// - mint: The output token of the slow fill leaves.
+    /// - threshold: The threshold in raw output token units, or None to remove it. At most
+    ///   MAX_EXECUTION_APPROVAL_THRESHOLDS mints can have a threshold.
+    pub fn set_execution_approval_threshold(
+        ctx: Context<SetExecutionApprovalConfigState>,
+        mint: Pubkey,
+        threshold: Option<u64>,
     ) -> Result<()> {
-        instructions::execute_v3_slow_relay_leaf(ctx, slow_fill_leaf, proof)
+        instructions::set_execution_approval_threshold(ctx, mint, threshold)
+    }
+
+    /// Approves the execution of a single slow fill leaf above the execution approval threshold of its mint.
+    ///
+    /// Callable only by the execution approver. The approval can be used until execution_approval_validity seconds
+    /// after its creation. Approvals of relayer refund leaves override RefundsExceedFillVolume for their execution.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The execution approver, paying the rent for the execution approval.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - execution_approval (Writable): The execution approval PDA, created on this function call.
+    ///   Seed: ["execution_approval",state.seed,leaf_hash].
+    /// - system_program (Program): The system program.
+    ///
+    /// ### Parameters:
+    /// - leaf_hash: The keccak hash of the slow fill leaf, same as verified against the slow relay root, or of the
+    ///   relayer refund leaf, same as verified against the relayer refund root.
+    pub fn create_execution_approval(ctx: Context<CreateExecutionApproval>, leaf_hash: [u8; 32]) -> Result<()> {
+        instructions::create_execution_approval(ctx, leaf_hash)
+    }
+
+    /// Revokes an execution approval, closing it back to the approver that created it.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The approver that created the execution approval, receiving the reclaimed rent.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet.
+    /// - execution_approval (Writable): The execution approval PDA to close.
+    ///   Seed: ["execution_approval",state.seed,leaf_hash].
+    ///
+    /// ### Parameters:
+    /// - leaf_hash: The keccak hash of the approved slow fill leaf.
+    pub fn revoke_execution_approval(ctx: Context<RevokeExecutionApproval>, leaf_hash: [u8; 32]) -> Result<()> {
+        instructions::revoke_execution_approval(ctx, leaf_hash)
     }
 
     /// **************************************
@@ -765,8 +3545,8 @@ pub mod svm_spoke {
     ///
     /// ### Required Accounts:
     /// - authority_pda: A signer account that ensures this instruction can only be called by the Message Transmitter.
-    ///   This acts to block that only the CCTP Message Transmitter can send messages to this program.
-    ///   seed:["message_transmitter_authority", program_id]
+    ///   This acts to block that only the CCTP Message Transmitter can send messages to this program, or the admin
+    ///   messenger instead when set through set_admin_messenger. seed:["message_transmitter_authority", program_id]
     /// - state (Account): Spoke state PDA. Seed: ["state",state.seed] where seed is 0 on mainnet. Enforces that the
     ///   remote domain and sender are valid.
     /// - self_authority: An unchecked account used for authenticating self-CPI invoked by the received message.
@@ -786,8 +3566,59 @@ pub mod svm_spoke {
         instructions::handle_receive_message(ctx, params)
     }
 
-    /// Sets the current time for the SVM Spoke Pool when running in test mode. Disabled on Mainnet.
+    /// Sets the current time override of the state. Only callable by the owner, and rejected outside test builds.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Signer): The account that must be the owner to set the time.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed].
+    ///
+    /// ### Parameters:
+    /// - new_time: The current time that time based checks use from now on.
     pub fn set_current_time(ctx: Context<SetCurrentTime>, new_time: u32) -> Result<()> {
-        utils::set_current_time(ctx, new_time)
+        instructions::set_current_time(ctx, new_time)
+    }
+
+    /// Sets the roots of a root bundle without relaying it from the HubPool. Only callable by the owner, and rejected
+    /// outside test builds.
+    ///
+    /// The root bundle PDA is created if missing and overwritten otherwise, without execution deadline, restricted
+    /// executor or claimed leaves. The root_bundle_id of the state is moved past the set id, so that root bundles
+    /// relayed later do not collide with it.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner, paying the rent for the root bundle PDA if it is created.
+    /// - state (Writable): Spoke state PDA. Seed: ["state",state.seed].
+    /// - root_bundle (Writable): The root bundle PDA. Seed: ["root_bundle",state.seed,root_bundle_id].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - root_bundle_id: The id of the root bundle, used to derive the root bundle PDA.
+    /// - relayer_refund_root: Merkle root of the relayer refund leaves.
+    /// - slow_relay_root: Merkle root of the slow relay leaves.
+    pub fn set_root_bundle(
+        ctx: Context<SetRootBundle>,
+        root_bundle_id: u32,
+        relayer_refund_root: [u8; 32],
+        slow_relay_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_root_bundle(ctx, root_bundle_id, relayer_refund_root, slow_relay_root)
+    }
+
+    /// Sets the status of a relay without filling it or requesting its slow fill. Only callable by the owner, and
+    /// rejected outside test builds.
+    ///
+    /// The fill status PDA is created if missing, recording the owner as its relayer, requester and payer.
+    ///
+    /// ### Required Accounts:
+    /// - signer (Writable, Signer): The owner, paying the rent for the fill status PDA if it is created.
+    /// - state (Account): Spoke state PDA. Seed: ["state",state.seed].
+    /// - fill_status (Writable): The fill status PDA of the relay. Seed: ["fills",relay_hash].
+    /// - system_program (Program): The system program required for account creation.
+    ///
+    /// ### Parameters:
+    /// - _relay_hash: The hash identifying the relay, used to derive the fill status PDA.
+    /// - status: The status to record.
+    pub fn set_fill_status(ctx: Context<SetFillStatus>, _relay_hash: [u8; 32], status: FillStatus) -> Result<()> {
+        instructions::set_fill_status(ctx, status)
     }
 }
@@ -0,0 +1,47 @@
+// Merkle proof verification of the OpenZeppelin MerkleProof library used by the EVM HubPool, kept free of Solana
+// dependencies so that the shared test vectors (test/svm/fixtures/merkle.json) check it in isolation. Hashing is
+// injected with the MerkleHasher trait, the program uses keccak256 from merkle_proof_utils.
+
+pub trait MerkleHasher {
+    fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32];
+}
+
+// See https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/MerkleProof.sol
+pub fn process_proof<H: MerkleHasher>(proof: &[[u8; 32]], leaf: &[u8; 32]) -> [u8; 32] {
+    let mut computed_hash = *leaf;
+    for proof_element in proof.iter() {
+        computed_hash = commutative_hash::<H>(&computed_hash, proof_element);
+    }
+    computed_hash
+}
+
+// Proofs longer than max_depth are rejected before hashing, as they can't match any root and only waste compute.
+pub fn verify_proof<H: MerkleHasher>(
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]],
+    max_depth: usize,
+) -> Result<(), MerkleProofError> {
+    if proof.len() > max_depth {
+        return Err(MerkleProofError::InvalidProofLength);
+    }
+    if process_proof::<H>(proof, leaf) != *root {
+        return Err(MerkleProofError::InvalidMerkleProof);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleProofError {
+    InvalidProofLength,
+    InvalidMerkleProof,
+}
+
+// See https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/Hashes.sol
+fn commutative_hash<H: MerkleHasher>(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a < b {
+        H::hash_pair(a, b)
+    } else {
+        H::hash_pair(b, a)
+    }
+}
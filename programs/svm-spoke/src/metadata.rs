@@ -0,0 +1,134 @@
+use anchor_lang::Discriminator;
+
+use crate::{
+    constants::{METADATA_LAYOUT_VERSION, METADATA_OPTIONAL, METADATA_SIGNER, METADATA_WRITABLE},
+    instruction,
+};
+
+// Human-readable roles of the accounts of the instructions signed by end users, so that wallet simulators can explain
+// the program accounts they don't recognize instead of warning about them. Accounts are listed in the order of their
+// accounts structs, including the event authority and program accounts appended by event_cpi.
+pub struct AccountMetadata {
+    pub name: &'static str,
+    pub signer: bool,
+    pub optional: bool,
+    pub role: &'static str,
+    pub writable: Option<&'static str>, // Why the account is writable, None for read-only accounts.
+}
+
+const fn account(name: &'static str, role: &'static str, writable: Option<&'static str>) -> AccountMetadata {
+    AccountMetadata { name, signer: false, optional: false, role, writable }
+}
+
+const fn signer(name: &'static str, role: &'static str, writable: Option<&'static str>) -> AccountMetadata {
+    AccountMetadata { signer: true, ..account(name, role, writable) }
+}
+
+const fn optional(name: &'static str, role: &'static str, writable: Option<&'static str>) -> AccountMetadata {
+    AccountMetadata { optional: true, ..account(name, role, writable) }
+}
+
+const EVENT_AUTHORITY: AccountMetadata = account("event_authority", "Signs the self-CPI emitting events", None);
+const PROGRAM: AccountMetadata = account("program", "This program, receiving the event self-CPI", None);
+
+pub const DEPOSIT_V3_ACCOUNTS: &[AccountMetadata] = &[
+    signer("signer", "Depositor or its delegate", Some("Pays the rent of created accounts")),
+    account("state", "Spoke configuration", Some("Increments the deposit count")),
+    optional("destination_chain_alias", "Alias of the destination chain ID", None),
+    account("route", "Enabled route of the token and chain", None),
+    account("deposit_limit", "Deposit cap of the route", None),
+    account("depositor_token_account", "Depositor token account", Some("Sends the deposited tokens")),
+    account("vault", "Spoke vault of the token", Some("Receives the deposited tokens")),
+    account("mint", "Deposited token", None),
+    account("vault_liability", "Deposits owed by the vault", Some("Records the deposited amount")),
+    optional("event_commitment", "Event log of the current epoch", Some("Chains the deposit event")),
+    account("token_program", "Token program of the mint", None),
+    account("system_program", "Creates accounts", None),
+    EVENT_AUTHORITY,
+    PROGRAM,
+];
+
+pub const DEPOSIT_V3_NATIVE_ACCOUNTS: &[AccountMetadata] = &[
+    signer("signer", "Depositor", Some("Sends the deposited SOL and pays rent")),
+    account("state", "Spoke configuration", Some("Increments the deposit count")),
+    optional("destination_chain_alias", "Alias of the destination chain ID", None),
+    account("route", "Enabled route of wSOL and the chain", None),
+    account("deposit_limit", "Deposit cap of the route", None),
+    account("vault", "Spoke wSOL vault", Some("Receives the deposited SOL")),
+    account("mint", "wSOL mint", None),
+    account("vault_liability", "Deposits owed by the vault", Some("Records the deposited amount")),
+    optional("event_commitment", "Event log of the current epoch", Some("Chains the deposit event")),
+    account("token_program", "SPL Token program", None),
+    account("system_program", "Transfers SOL and creates accounts", None),
+    account("native_wrap_account", "Temporary wSOL account", Some("Wraps the deposited SOL and is closed")),
+    EVENT_AUTHORITY,
+    PROGRAM,
+];
+
+pub const DEPOSIT_V3_WITH_SWAP_ACCOUNTS: &[AccountMetadata] = &[
+    signer("signer", "Depositor", Some("Sends the swapped tokens and pays rent")),
+    account("state", "Spoke configuration", Some("Increments the deposit count")),
+    optional("destination_chain_alias", "Alias of the destination chain ID", None),
+    account("route", "Enabled route of the token and chain", None),
+    account("deposit_limit", "Deposit cap of the route", None),
+    account("swap_adapter", "Owner approval of the swap program", None),
+    account("swap_program", "Swap program", None),
+    account("swap_in_mint", "Swapped token", None),
+    account("depositor_swap_in_token_account", "Depositor token account", Some("Sends the swapped tokens")),
+    account("vault", "Spoke vault of the token", Some("Receives the swap output")),
+    account("mint", "Deposited token", None),
+    account("vault_liability", "Deposits owed by the vault", Some("Records the deposited amount")),
+    optional("event_commitment", "Event log of the current epoch", Some("Chains the deposit event")),
+    account("token_program", "Token program of the mint", None),
+    account("swap_in_token_program", "Token program of the swapped token", None),
+    account("system_program", "Creates accounts", None),
+    EVENT_AUTHORITY,
+    PROGRAM,
+];
+
+pub const WITHDRAW_ESCROW_ACCOUNTS: &[AccountMetadata] = &[
+    signer("recipient", "Owner of the escrowed tokens", None),
+    account("state", "Spoke configuration, escrow authority", None),
+    account("mint", "Escrowed token", None),
+    account("recipient_escrow", "Escrow of the recipient", Some("Sends the withdrawn tokens")),
+    account("destination", "Token account of the recipient's choice", Some("Receives the withdrawn tokens")),
+    account("token_program", "Token program of the mint", None),
+    EVENT_AUTHORITY,
+    PROGRAM,
+];
+
+// Instructions without metadata return None.
+pub fn get_instruction_metadata(discriminator: &[u8; 8]) -> Option<&'static [AccountMetadata]> {
+    match *discriminator {
+        instruction::DepositV3::DISCRIMINATOR
+        | instruction::DepositV3Now::DISCRIMINATOR
+        | instruction::UnsafeDepositV3::DISCRIMINATOR => Some(DEPOSIT_V3_ACCOUNTS),
+        instruction::DepositV3Native::DISCRIMINATOR => Some(DEPOSIT_V3_NATIVE_ACCOUNTS),
+        instruction::DepositV3WithSwap::DISCRIMINATOR => Some(DEPOSIT_V3_WITH_SWAP_ACCOUNTS),
+        instruction::WithdrawEscrow::DISCRIMINATOR => Some(WITHDRAW_ESCROW_ACCOUNTS),
+        _ => None,
+    }
+}
+
+// Compact encoding documented next to the METADATA_* constants. Names are left out, as the IDL already has them.
+pub fn encode_instruction_metadata(accounts: &[AccountMetadata]) -> Vec<u8> {
+    let mut encoded = vec![METADATA_LAYOUT_VERSION, accounts.len() as u8];
+    for account in accounts {
+        let mut flags = 0;
+        if account.writable.is_some() {
+            flags |= METADATA_WRITABLE;
+        }
+        if account.signer {
+            flags |= METADATA_SIGNER;
+        }
+        if account.optional {
+            flags |= METADATA_OPTIONAL;
+        }
+        encoded.push(flags);
+        for text in [account.role, account.writable.unwrap_or_default()] {
+            encoded.push(text.len() as u8);
+            encoded.extend_from_slice(text.as_bytes());
+        }
+    }
+    encoded
+}
@@ -0,0 +1,110 @@
+// Single predicate deciding whether a relay can be filled or have its slow fill requested, shared by fill_v3_relay,
+// fill_v3_relay_with_updated_deposit, request_v3_slow_fill and request_v3_slow_fill_for. Rules on the pause,
+// exclusivity, fill deadline and fill status of a relay must only be changed here. Deadlines are inclusive as on the
+// EVM SpokePool: exclusive relayers can fill until and including the exclusivity deadline, and both fills and slow
+// fill requests are accepted until and including the fill deadline. Exclusive relayers can also request slow fills of
+// their relays during their own window, unless the state disables it with exclusive_slow_fills_disabled. Rejections
+// log the times or fill status they were decided on, as their error codes alone don't tell what failed.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    common::V3RelayData,
+    error::{CommonError, SvmError},
+    state::{FillStatus, State},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallerRole {
+    Relayer(Pubkey),           // Fills the relay, exclusive relayers are identified by their key.
+    SlowFillRequester(Pubkey), // Requests the slow fill, exclusive relayers are identified by their key.
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Admission {
+    FastFill,
+    ReplacedSlowFill, // Fast fill of a relay with a requested slow fill, that can't be executed afterwards.
+    SlowFillRequest,
+}
+
+pub fn check(
+    relay_data: &V3RelayData,
+    status: &FillStatus,
+    state: &State,
+    now: u32,
+    caller_role: CallerRole,
+) -> Result<Admission> {
+    if is_fill_pause_active(state, now) {
+        msg!("Fills paused until {} (0 is indefinite), current time {}", state.fills_pause_expires_at, now);
+        return err!(CommonError::FillsArePaused);
+    }
+
+    match caller_role {
+        CallerRole::Relayer(relayer) => {
+            if relay_data.exclusive_relayer != Pubkey::default()
+                && relay_data.exclusive_relayer != relayer
+                && now <= relay_data.exclusivity_deadline
+            {
+                msg!(
+                    "Exclusive relayer {} until {}, current time {}",
+                    relay_data.exclusive_relayer,
+                    relay_data.exclusivity_deadline,
+                    now
+                );
+                return err!(CommonError::NotExclusiveRelayer);
+            }
+            check_fill_deadline(relay_data, now)?;
+            match status {
+                FillStatus::Unfilled => Ok(Admission::FastFill),
+                FillStatus::RequestedSlowFill => Ok(Admission::ReplacedSlowFill),
+                FillStatus::Filled => reject_fill_status(status, error!(CommonError::RelayFilled)),
+                FillStatus::Expired => reject_fill_status(status, error!(SvmError::RelayExpired)),
+            }
+        }
+        CallerRole::SlowFillRequester(requester) => {
+            let exclusive_request = relay_data.exclusive_relayer != Pubkey::default()
+                && relay_data.exclusive_relayer == requester
+                && !state.exclusive_slow_fills_disabled;
+            let slow_fill_request_time = get_slow_fill_request_time(state, relay_data);
+            if !exclusive_request && now < slow_fill_request_time {
+                msg!("Slow fills can be requested from {}, current time {}", slow_fill_request_time, now);
+                return err!(CommonError::NoSlowFillsInExclusivityWindow);
+            }
+            check_fill_deadline(relay_data, now)?;
+            match status {
+                FillStatus::Unfilled => Ok(Admission::SlowFillRequest),
+                FillStatus::RequestedSlowFill => reject_fill_status(status, error!(SvmError::SlowFillAlreadyRequested)),
+                FillStatus::Filled => reject_fill_status(status, error!(SvmError::RelayAlreadyFilled)),
+                FillStatus::Expired => reject_fill_status(status, error!(SvmError::RelayExpired)),
+            }
+        }
+    }
+}
+
+fn check_fill_deadline(relay_data: &V3RelayData, now: u32) -> Result<()> {
+    if relay_data.fill_deadline < now {
+        msg!("Fill deadline {} passed, current time {}", relay_data.fill_deadline, now);
+        return err!(CommonError::ExpiredFillDeadline);
+    }
+    Ok(())
+}
+
+fn reject_fill_status(status: &FillStatus, error: Error) -> Result<Admission> {
+    msg!("Relay has fill status {:?}", status);
+    Err(error)
+}
+
+// Paused fills are treated as unpaused once their optional expiry has passed.
+pub fn is_fill_pause_active(state: &State, now: u32) -> bool {
+    state.paused_fills && (state.fills_pause_expires_at == 0 || now < state.fills_pause_expires_at)
+}
+
+// First time at which a slow fill can be requested. The grace period only delays slow fill requests, so that fast fills
+// racing the end of the exclusivity window don't create needless slow fill leaves. Fast fills are not affected. Relays
+// without an exclusive relayer have no exclusivity window, matching the fill exclusivity check.
+pub fn get_slow_fill_request_time(state: &State, relay_data: &V3RelayData) -> u32 {
+    if relay_data.exclusive_relayer == Pubkey::default() {
+        return 0;
+    }
+    relay_data.exclusivity_deadline.saturating_add(state.slow_fill_grace_seconds).saturating_add(1)
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct AdminNonce {
+    pub expires_at_slot: u64, // Idempotency key is retained until this slot, after which the PDA can be closed.
+    pub payer: Pubkey,        // Payer of the rent receiving it back on close. Set when the key is consumed.
+}
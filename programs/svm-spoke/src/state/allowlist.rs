@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+// Roles of an account checked in permissioned mode. The same entry serves fills, slow fill requests and slow relay leaf
+// executions, so a single registry covers all permissioned flows.
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub roles: u8, // Bitmap of the ALLOWLIST_ROLE_* bits granted to the account.
+}
+
+impl AllowlistEntry {
+    pub fn has_role(&self, role: u8) -> bool {
+        self.roles & role == role
+    }
+}
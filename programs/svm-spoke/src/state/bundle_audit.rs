@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct VaultSnapshot {
+    pub mint: Pubkey,        // Mint of the vault that was affected by the bundle.
+    pub balance_before: u64, // Vault balance before the first leaf for this mint was executed.
+    pub balance_after: u64,  // Vault balance after the latest leaf for this mint was executed.
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BundleAudit {
+    pub root_bundle_id: u32, // ID of the audited root bundle, same as used in its PDA derivation.
+    pub max_mints: u8,       // Number of mints this account was sized for.
+    #[max_len(0)]
+    pub snapshots: Vec<VaultSnapshot>, // Grows up to max_mints as leaves for new mints are executed.
+}
+
+impl BundleAudit {
+    // Records vault balances around a leaf execution. The before balance is only kept from the first execution for the
+    // mint so that snapshots straddle the whole bundle. Returns None when a new mint does not fit in the account.
+    pub fn record_snapshot(
+        &mut self,
+        mint: Pubkey,
+        vault_balance_before: u64,
+        vault_balance_after: u64,
+    ) -> Option<VaultSnapshot> {
+        if let Some(snapshot) = self.snapshots.iter_mut().find(|snapshot| snapshot.mint == mint) {
+            snapshot.balance_after = vault_balance_after;
+            return Some(snapshot.clone());
+        }
+
+        if self.snapshots.len() >= self.max_mints as usize {
+            return None;
+        }
+
+        let snapshot = VaultSnapshot { mint, balance_before: vault_balance_before, balance_after: vault_balance_after };
+        self.snapshots.push(snapshot.clone());
+
+        Some(snapshot)
+    }
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+// Canonical chain ID used in relay hashes for a chain ID as passed by clients, keyed by the alias. Canonical chain IDs
+// are registered as aliases of themselves, which is required for all chain IDs when the state has strict_chain_aliases.
+#[account]
+#[derive(InitSpace)]
+pub struct ChainAlias {
+    pub canonical_chain_id: u64,
+}
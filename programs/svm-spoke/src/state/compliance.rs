@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+// Existence of this account means its subject address is on the blocklist. Removing the entry closes the account.
+#[account]
+#[derive(InitSpace)]
+pub struct ComplianceAttestation {
+    pub subject: Pubkey, // Blocked depositor or recipient address, same as used in its PDA derivation.
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, PartialEq)]
+pub enum DeliveryMode {
+    Push,   // Fills transfer output tokens to the recipient's associated token account.
+    Escrow, // Fills accrue output tokens in the recipient escrow that only the recipient can withdraw from.
+    Native, // Fills of wSOL outputs are unwrapped to recipient lamports, other outputs are pushed as with Push.
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DeliveryPreference {
+    pub mode: DeliveryMode, // How fill payouts are delivered to the recipient.
+}
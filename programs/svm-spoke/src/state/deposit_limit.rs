@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+// Caps the vault balance of a mint that deposits through a route can build up to. The vault is shared by all routes of
+// the mint, so each cap bounds the whole vault balance for deposits to its destination chain, and setting the same cap
+// on all routes caps the mint globally. Only new deposits are checked, so fills and refund or slow fill payouts are
+// never blocked, and lowering a cap below the current vault balance keeps the existing funds while rejecting deposits.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositLimit {
+    pub max_outstanding: u64, // Max vault balance after a deposit through the route, u64::MAX when unlimited.
+}
+
+impl DepositLimit {
+    // Balances saturate, so a u64::MAX cap allows any deposit.
+    pub fn allows_deposit(&self, vault_balance: u64, amount: u64) -> bool {
+        vault_balance.saturating_add(amount) <= self.max_outstanding
+    }
+}
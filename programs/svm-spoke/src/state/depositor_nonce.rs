@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+// Opt-in ordering of the deposits of a depositor. While the PDA exists deposit_v3 only accepts the deposit carrying
+// next_nonce, so that integrations processing deposits in strict order on the destination can detect gaps.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositorNonce {
+    pub next_nonce: u64, // Nonce required in the next deposit of the depositor, emitted with the deposit.
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+// Tracks failure categories that do not revert the instruction, so they cannot be observed as failed transactions.
+// Counters saturate instead of overflowing. Only the following paths increment them:
+// - execute_relayer_refund_leaf_deferred: deferred_refund_leaves by 1 and deferred_refunds by the number of refunds
+//   accrued to claim accounts instead of being transferred to relayer ATAs.
+#[account]
+#[derive(InitSpace)]
+pub struct ErrorCounters {
+    pub deferred_refund_leaves: u32, // Number of relayer refund leaves executed with deferred refunds.
+    pub deferred_refunds: u32,       // Number of individual relayer refunds accrued to claim accounts.
+}
+
+impl ErrorCounters {
+    pub fn record_deferred_refunds(&mut self, refund_count: usize) {
+        self.deferred_refund_leaves = self.deferred_refund_leaves.saturating_add(1);
+        self.deferred_refunds = self
+            .deferred_refunds
+            .saturating_add(u32::try_from(refund_count).unwrap_or(u32::MAX));
+    }
+}
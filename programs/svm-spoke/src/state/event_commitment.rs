@@ -0,0 +1,21 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+// Rolling commitment to the events emitted within an epoch, so that indexers can verify the event stream they
+// reconstructed after RPC providers pruned the event CPI data. The hash starts zeroed for each epoch and each committed
+// event updates it as keccak(previous hash ‖ event discriminator ‖ keccak(borsh serialized event)).
+#[account]
+#[derive(InitSpace)]
+pub struct EventCommitment {
+    pub seed: u64,        // Seed of the state whose events are committed.
+    pub epoch: u32,       // Current time divided by EVENT_COMMITMENT_EPOCH_DURATION when the events were emitted.
+    pub hash: [u8; 32],   // Rolling hash over all events committed within the epoch.
+    pub event_count: u64, // Number of events committed within the epoch.
+}
+
+impl EventCommitment {
+    pub fn commit(&mut self, event_discriminator: &[u8], event_data: &[u8]) {
+        let event_hash = keccak::hash(event_data);
+        self.hash = keccak::hashv(&[&self.hash, event_discriminator, event_hash.as_ref()]).to_bytes();
+        self.event_count = self.event_count.saturating_add(1);
+    }
+}
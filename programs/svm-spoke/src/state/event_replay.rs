@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{EVENT_REPLAY_RATE_LIMIT, EVENT_REPLAY_RATE_WINDOW},
+    error::SvmError,
+    utils::OrArithmeticOverflow,
+};
+
+// Caps the number of fill events replayed by the owner within each window, so that replays can't be used to spam
+// indexers with events.
+#[account]
+#[derive(InitSpace)]
+pub struct EventReplayActivity {
+    pub window_start: u32, // Start time of the current replay rate limit window.
+    pub replay_count: u32, // Number of events replayed in the current window.
+}
+
+impl EventReplayActivity {
+    // Records an event replay, starting a new window once the previous one has passed.
+    pub fn record_replay(&mut self, current_time: u32) -> Result<()> {
+        if current_time >= self.window_start.saturating_add(EVENT_REPLAY_RATE_WINDOW) {
+            self.window_start = current_time;
+            self.replay_count = 0;
+        }
+
+        if self.replay_count >= EVENT_REPLAY_RATE_LIMIT {
+            return err!(SvmError::EventReplayRateLimited);
+        }
+        self.replay_count = self.replay_count.checked_add(1).or_overflow("event replay count")?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Approval of the execution approver for a single slow fill leaf above the execution approval threshold of its mint.
+#[account]
+#[derive(InitSpace)]
+pub struct ExecutionApproval {
+    pub leaf_hash: [u8; 32], // Hash of the approved slow fill leaf, same as verified against the slow relay root.
+    pub approver: Pubkey,    // Execution approver that created the approval.
+    pub approved_at: u32,    // Time when the approval was created.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ExecutionApprovalThreshold {
+    pub mint: Pubkey,   // Output token of the slow fill leaves.
+    pub threshold: u64, // Slow fill leaves with an updated output amount above this require an execution approval.
+}
@@ -1,16 +1,87 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, PartialEq)]
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, LEGACY_FILL_STATUS_ACCOUNT_SIZES},
+    error::SvmError,
+    utils::OrArithmeticOverflow,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace, PartialEq)]
 pub enum FillStatus {
     Unfilled,
     RequestedSlowFill,
     Filled,
+    Expired, // Appended after the variants of existing accounts, so that their serialized status is unchanged.
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct FillStatusAccount {
-    pub status: FillStatus, // Tracks the status of the fill between Unfilled, requestedSlowFill, and Filled.
+    pub status: FillStatus, // Tracks the status of the fill between Unfilled, requestedSlowFill, Filled and Expired.
     pub relayer: Pubkey,    // Address of the relayer that made the fill to control who can close this PDA.
     pub fill_deadline: u32, // Stores the fill deadline to control when this PDA can be safely closed.
+    pub requester: Pubkey,  // Address that requested the slow fill, if any. Can differ from the rent paying relayer.
+    pub payer: Pubkey,      // Address that paid the rent when this PDA was created, receives it back when closed.
+}
+
+impl FillStatusAccount {
+    // Only the first relayer or slow fill requester creating the PDA pays its rent, so later updates keep the payer.
+    pub fn record_payer(&mut self, payer: Pubkey) {
+        if self.payer == Pubkey::default() {
+            self.payer = payer;
+        }
+    }
+
+    // Decodes fill status PDAs of the current or a legacy layout, so that views and off-chain code read both the same
+    // way. Fields appended after the legacy layout are zeroed, as they were never recorded.
+    pub fn try_deserialize_any_layout(data: &[u8]) -> Result<Self> {
+        let size = DISCRIMINATOR_SIZE + Self::INIT_SPACE;
+        if !LEGACY_FILL_STATUS_ACCOUNT_SIZES.contains(&data.len()) {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+
+        let mut padded_data = data.to_vec();
+        padded_data.resize(size, 0);
+        Self::try_deserialize(&mut padded_data.as_slice())
+    }
+
+    // Records the fields that legacy layouts did not. Legacy PDAs were paid for by their relayer, that is also the
+    // signer of the slow fill request of requested relays.
+    pub fn migrate_legacy_layout(&mut self, fill_deadline: u32) {
+        self.fill_deadline = fill_deadline;
+        self.record_payer(self.relayer);
+        if self.status == FillStatus::RequestedSlowFill {
+            self.requester = self.relayer;
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RequesterNonce {
+    pub next_nonce: u64, // Minimum nonce accepted in the next signed slow fill request to prevent replays.
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RequesterActivity {
+    pub window_start: u32,  // Start time of the current slow fill request rate limit window.
+    pub request_count: u32, // Number of slow fill requests made by the signer in the current window.
+}
+
+impl RequesterActivity {
+    // Records a slow fill request, starting a new window once the previous one has passed.
+    pub fn record_request(&mut self, current_time: u32, window: u32, limit: u32) -> Result<()> {
+        if current_time >= self.window_start.saturating_add(window) {
+            self.window_start = current_time;
+            self.request_count = 0;
+        }
+
+        if self.request_count >= limit {
+            return err!(SvmError::RateLimited);
+        }
+        self.request_count = self.request_count.checked_add(1).or_overflow("slow fill request count")?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct FillConfirmation {
+    pub deposit_id: [u8; 32],                  // ID of the SVM-origin deposit.
+    pub destination_fill_event_hash: [u8; 32], // Hash of the fill event on the destination chain.
+    pub oracle: Pubkey,                        // Oracle key that attested the fill.
+    pub confirmed_at: u32,                     // Time when the fill confirmation was posted.
+}
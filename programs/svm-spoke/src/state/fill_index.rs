@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Index of deposit ids from an origin chain that have a fill status PDA on this chain, so that the dataworker can scan
+// shards instead of all fill status PDAs. Each shard covers FILL_INDEX_SHARD_SIZE consecutive deposit ids.
+#[account]
+#[derive(InitSpace)]
+pub struct FillIndexShard {
+    pub origin_chain_id: u64, // Origin chain of the indexed deposit ids.
+    pub bucket: u64,          // Shard covers deposit ids starting from bucket * FILL_INDEX_SHARD_SIZE.
+    pub bitmap: [u8; 128],    // Bit i of byte j is set when deposit id at offset 8 * j + i has a fill status PDA.
+}
+
+impl FillIndexShard {
+    pub fn set(&mut self, offset: u16) {
+        self.bitmap[(offset / 8) as usize] |= 1 << (offset % 8);
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+// Fast fill volume of a mint observed on this spoke within bundle windows, bounding the relayer refunds and amount to
+// return that the refund leaves of each root bundle can send for the mint. The first refund leaf of a newer root bundle
+// closes the window, and that bundle is then checked against the fill volume of the closed window plus the tolerance.
+// This is a safety net against inflated relayer refund roots rather than accounting truth, as refunds on this spoke
+// also repay fills on other chains. Legitimate bundles exceeding it are executed with an execution approval per leaf.
+#[account]
+#[derive(InitSpace)]
+pub struct FillVolume {
+    pub tolerance: u64,              // Refunds of a bundle can exceed its window fill volume by this.
+    pub fill_volume: u64,            // Output amount filled in the open window.
+    pub root_bundle_id: Option<u32>, // Root bundle that closed the last window, if any.
+    pub bundle_fill_volume: u64,     // Fill volume of the window closed by root_bundle_id.
+    pub bundle_refunds: u64,         // Refunds and amount to return since root_bundle_id.
+}
+
+impl FillVolume {
+    pub fn record_fill(&mut self, amount: u64) {
+        self.fill_volume = self.fill_volume.saturating_add(amount);
+    }
+
+    // Records the refunds of a leaf, first closing the window when the leaf belongs to a newer root bundle. Leaves of
+    // older root bundles executed late count against the current bundle. Returns whether the bundle refunds are still
+    // within the fill volume of its window plus the tolerance.
+    pub fn record_refunds(&mut self, root_bundle_id: u32, amount: u64) -> bool {
+        if self.root_bundle_id < Some(root_bundle_id) {
+            self.root_bundle_id = Some(root_bundle_id);
+            self.bundle_fill_volume = self.fill_volume;
+            self.bundle_refunds = 0;
+            self.fill_volume = 0;
+        }
+        self.bundle_refunds = self.bundle_refunds.saturating_add(amount);
+
+        self.bundle_refunds <= self.bundle_fill_volume.saturating_add(self.tolerance)
+    }
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+// Account kinds that can be closed by anyone through gc_account once stale, each carrying the arguments needed to
+// re-derive the PDA of the closed account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum GcAccountKind {
+    Route { origin_token: Pubkey, destination_chain_id: u64 }, // Disabled for GC_ROUTE_DISABLED_PERIOD.
+    RequesterActivity { requester: Pubkey }, // Rate limit window ended GC_REQUESTER_ACTIVITY_RETENTION ago.
+    EventCommitment { epoch: u32 },          // Epoch ended GC_EVENT_COMMITMENT_RETENTION ago.
+}
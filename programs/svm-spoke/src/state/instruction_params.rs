@@ -2,6 +2,14 @@ use anchor_lang::prelude::*;
 
 use crate::{RelayerRefundLeaf, V3RelayData, V3SlowFill};
 
+// Stored next to the raw instruction params buffer, as the buffer itself has no layout of its own.
+#[account]
+#[derive(InitSpace)]
+pub struct InstructionParamsHeader {
+    pub payer: Pubkey,   // Relayer that initialized the instruction params, receiving their rent back on close.
+    pub created_at: u32, // Time when the instruction params were initialized. Used to find stale buffers.
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct ExecuteRelayerRefundLeafParams {
@@ -33,3 +41,30 @@ pub struct ExecuteV3SlowRelayLeafParams {
     #[max_len(0)]
     pub proof: Vec<[u8; 32]>,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum BundleSummaryLeaf {
+    RelayerRefund { relayer_refund_leaf: RelayerRefundLeaf, proof: Vec<[u8; 32]> },
+    SlowRelay { slow_fill_leaf: V3SlowFill, proof: Vec<[u8; 32]> },
+}
+
+#[account]
+pub struct SimulateBundleSummaryParams {
+    pub leaves: Vec<BundleSummaryLeaf>, // Leaves of the root bundle with their proofs, simulated in this order.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SlowRelayLeafExecution {
+    pub slow_fill_leaf: V3SlowFill,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[account]
+pub struct ExecuteV3SlowRelayLeavesParams {
+    pub leaves: Vec<SlowRelayLeafExecution>, // Slow relay leaves of the root bundle with their proofs, in order.
+}
+
+#[account]
+pub struct RequestV3SlowFillsBatchParams {
+    pub relays: Vec<V3RelayData>, // Relays to request slow fills for, in the order of their fill status PDAs.
+}
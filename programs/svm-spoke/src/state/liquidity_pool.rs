@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SvmError;
+
+// Tracks liquidity supplied to the vault of a mint by external providers. Shares are priced against the liquidity owed
+// to the pool rather than the vault balance, so that refund bundle inflows and other protocol owned funds in the vault
+// can never be claimed by share holders. Only pool fees accrued on slow fill payouts grow the liquidity per share.
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityPool {
+    pub mint: Pubkey,         // Mint of the pooled token, held in the vault authority ATA.
+    pub total_shares: u64,    // Shares outstanding across all liquidity positions of the pool.
+    pub total_liquidity: u64, // Provided liquidity plus accrued pool fees owed to the share holders.
+}
+
+impl LiquidityPool {
+    // Rounds down in favor of the existing share holders.
+    pub fn shares_for_liquidity(&self, amount: u64) -> Result<u64> {
+        if self.total_shares == 0 {
+            return Ok(amount);
+        }
+
+        u64::try_from(amount as u128 * self.total_shares as u128 / self.total_liquidity as u128)
+            .map_err(|_| error!(SvmError::InvalidLiquidityAmount))
+    }
+
+    // Rounds down in favor of the remaining share holders.
+    pub fn liquidity_for_shares(&self, shares: u64) -> Result<u64> {
+        if shares > self.total_shares {
+            return err!(SvmError::InsufficientLiquidityShares);
+        }
+
+        Ok((shares as u128 * self.total_liquidity as u128 / self.total_shares as u128) as u64)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityPosition {
+    pub shares: u64, // Shares of the liquidity pool owned by the provider.
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+// Canonical Address Lookup Table of the spoke, created and extended with the state PDA as its authority so that
+// relayers can reference a single published table instead of assembling their own tables of the static accounts.
+#[account]
+#[derive(InitSpace)]
+pub struct LookupTableConfig {
+    pub lookup_table: Pubkey, // Address of the lookup table, derived from the state PDA and the recent slot.
+    pub recent_slot: u64,     // Recent slot the lookup table address was derived from at creation.
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MessageSchema {
+    pub min_message_size: u32, // Minimum size of the non-empty message the recipient handler can decode.
+    pub max_message_size: u32, // Maximum size of the non-empty message the recipient handler can decode.
+    pub schema_tag: [u8; 4],   // Expected first 4 bytes of the non-empty message.
+}
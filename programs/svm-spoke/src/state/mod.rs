@@ -1,15 +1,63 @@
+pub mod admin_nonce;
+pub mod allowlist;
+pub mod bundle_audit;
+pub mod chain_alias;
+pub mod compliance;
+pub mod delivery_preference;
+pub mod deposit_limit;
+pub mod depositor_nonce;
+pub mod error_counters;
+pub mod event_commitment;
+pub mod event_replay;
+pub mod execution_approval;
 pub mod fill;
+pub mod fill_confirmation;
+pub mod fill_index;
+pub mod fill_volume;
+pub mod gc_account;
 pub mod instruction_params;
+pub mod liquidity_pool;
+pub mod lookup_table;
+pub mod message_schema;
+pub mod rate_limit;
 pub mod refund_account;
+pub mod relayer_stats;
 pub mod root_bundle;
 pub mod route;
 pub mod state;
+pub mod swap_adapter;
 pub mod transfer_liability;
+pub mod used_signature;
+pub mod vault;
 
+pub use admin_nonce::*;
+pub use allowlist::*;
+pub use bundle_audit::*;
+pub use chain_alias::*;
+pub use compliance::*;
+pub use delivery_preference::*;
+pub use deposit_limit::*;
+pub use depositor_nonce::*;
+pub use error_counters::*;
+pub use event_commitment::*;
+pub use event_replay::*;
+pub use execution_approval::*;
 pub use fill::*;
+pub use fill_confirmation::*;
+pub use fill_index::*;
+pub use fill_volume::*;
+pub use gc_account::*;
 pub use instruction_params::*;
+pub use liquidity_pool::*;
+pub use lookup_table::*;
+pub use message_schema::*;
+pub use rate_limit::*;
 pub use refund_account::*;
+pub use relayer_stats::*;
 pub use root_bundle::*;
 pub use route::*;
 pub use state::*;
+pub use swap_adapter::*;
 pub use transfer_liability::*;
+pub use used_signature::*;
+pub use vault::*;
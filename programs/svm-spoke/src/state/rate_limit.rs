@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SvmError;
+
+// Caps the amount of a mint that can leave its vault within each window across slow fill payouts, relayer refunds and
+// vault sweeps combined. Windows follow a fixed schedule from the initial window start and inflows into the vault don't
+// replenish the remaining capacity of the current window.
+#[account]
+#[derive(InitSpace)]
+pub struct RateLimit {
+    pub limit: u64,          // Max amount of the mint that can leave the vault within each window.
+    pub window: u32,         // Length of the rate limit window in seconds.
+    pub window_start: u32,   // Start time of the current rate limit window.
+    pub window_outflow: u64, // Amount of the mint that left the vault within the current window.
+}
+
+impl RateLimit {
+    // Records a vault outflow, first advancing to the window containing the current time if the previous one has passed.
+    pub fn record_outflow(&mut self, current_time: u32, amount: u64) -> Result<()> {
+        let elapsed = current_time.saturating_sub(self.window_start);
+        if self.window > 0 && elapsed >= self.window {
+            self.window_start = current_time - elapsed % self.window;
+            self.window_outflow = 0;
+        }
+
+        let window_outflow = self.window_outflow.saturating_add(amount);
+        if window_outflow > self.limit {
+            return err!(SvmError::VaultOutflowRateLimited);
+        }
+        self.window_outflow = window_outflow;
+
+        Ok(())
+    }
+}
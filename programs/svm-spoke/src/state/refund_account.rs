@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
 
+use crate::utils::create_pda_with_bump;
+
 #[account]
 #[derive(InitSpace)]
 pub struct ClaimAccount {
     pub amount: u64,         // Amount of tokens to be refunded to the relayer.
     pub initializer: Pubkey, // Address that initialized this PDA
+    pub bump: u8,            // Canonical bump of this PDA, so that remaining account checks avoid the bump search.
+}
+
+// Seeds of a claim account PDA, that the claim account itself does not store, passed along to export it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimAccountKey {
+    pub mint: Pubkey,
+    pub refund_address: Pubkey,
 }
 
 // This implements the following Anchor account constraints when parsing remaining account as a claim account:
 // #[account(
 //     mut,
 //     seeds = [b"claim_account", mint.key().as_ref(), refund_address.key().as_ref()],
-//     bump
+//     bump = claim_account.bump
 // )]
 // pub claim_account: Account<'info, ClaimAccount>,
 // Note: Account name should be appended to any possible errors by the caller.
@@ -25,8 +35,8 @@ impl<'info> ClaimAccount {
         let claim_account: Account<'info, ClaimAccount> = Account::try_from(account_info)?;
 
         // Checks the PDA is derived from mint and refund address keys.
-        let (pda_address, _bump) =
-            Pubkey::find_program_address(&[b"claim_account", mint.as_ref(), refund_address.as_ref()], &crate::ID);
+        let pda_address =
+            create_pda_with_bump(&[b"claim_account", mint.as_ref(), refund_address.as_ref()], claim_account.bump)?;
         if account_info.key() != pda_address {
             return Err(Error::from(ErrorCode::ConstraintSeeds).with_pubkeys((claim_account.key(), pda_address)));
         }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BPS_DENOMINATOR;
+
+// Tracks how often an exclusive relayer fills deposits within their exclusivity window versus letting them lapse into
+// slow fill requests, as on-chain evidence for quoting. Counters saturate instead of overflowing. Only the following
+// paths increment them:
+// - fill_v3_relay: fills_in_window when the exclusive relayer fills until and including the exclusivity deadline.
+// - request_v3_slow_fill: lapses for the exclusive relayer named in the relay data.
+#[account]
+#[derive(InitSpace)]
+pub struct RelayerStats {
+    pub relayer: Pubkey,      // Exclusive relayer the outcomes are attributed to.
+    pub fills_in_window: u64, // Number of deposits filled by the relayer within their exclusivity window.
+    pub lapses: u64,          // Number of deposits of the relayer that lapsed into slow fill requests.
+}
+
+impl RelayerStats {
+    // Share of lapses in all recorded outcomes. 0 when no outcome was recorded yet.
+    pub fn lapse_ratio_bps(&self) -> u64 {
+        let outcomes = (self.fills_in_window as u128).saturating_add(self.lapses as u128);
+        if outcomes == 0 {
+            return 0;
+        }
+
+        (self.lapses as u128 * BPS_DENOMINATOR as u128 / outcomes) as u64
+    }
+
+    pub fn record_fill_in_window(&mut self, relayer: Pubkey) {
+        self.relayer = relayer;
+        self.fills_in_window = self.fills_in_window.saturating_add(1);
+    }
+
+    pub fn record_lapse(&mut self, relayer: Pubkey) {
+        self.relayer = relayer;
+        self.lapses = self.lapses.saturating_add(1);
+    }
+}
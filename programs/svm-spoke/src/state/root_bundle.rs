@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::DISCRIMINATOR_SIZE;
+
+// Fields following claimed_bitmap were appended after root bundles were first relayed, so that the legacy layout stays
+// a prefix of the current one.
 #[account]
 #[derive(InitSpace)]
 pub struct RootBundle {
@@ -7,4 +11,52 @@ pub struct RootBundle {
     pub slow_relay_root: [u8; 32],     // Root of the slow relay merkle tree.
     #[max_len(1)]
     pub claimed_bitmap: Vec<u8>, // Dynamic sized vec to store claimed status of each relayer refund root leaf.
+    pub id: u32,                 // ID of the root bundle, same as used in its PDA derivation.
+    pub execution_deadline: u32, // Time after which slow relay leaves can no longer be executed. 0 = no deadline.
+    // Only key allowed to execute slow relay leaves before restricted_until. None = permissionless execution.
+    pub restricted_executor: Option<Pubkey>,
+    pub restricted_until: u32, // Time from which slow relay leaf execution becomes permissionless.
+}
+
+// Bundles relayed with only one of the roots have the other one zeroed, so its leaves can be rejected before hashing.
+impl RootBundle {
+    pub fn has_slow_relay_root(&self) -> bool {
+        self.slow_relay_root != [0u8; 32]
+    }
+
+    pub fn has_relayer_refund_root(&self) -> bool {
+        self.relayer_refund_root != [0u8; 32]
+    }
+
+    // Space of the fields appended after claimed_bitmap, with restricted_executor set.
+    pub const APPENDED_FIELDS_SPACE: usize = 4 + 4 + 33 + 4;
+
+    // Serialized size of the appended fields without restricted_executor, so the least that current layouts hold.
+    const MIN_APPENDED_FIELDS_SIZE: usize = 4 + 4 + 1 + 4;
+
+    // Offset of the claimed bitmap length prefix, after the discriminator and both roots.
+    const CLAIMED_BITMAP_OFFSET: usize = DISCRIMINATOR_SIZE + 32 + 32;
+
+    // Legacy root bundles end with their claimed bitmap, followed by at most the single byte of padding of their initial
+    // allocation, so they are told apart by the room left for the appended fields.
+    pub fn is_legacy_layout(data: &[u8]) -> bool {
+        let Some(length_prefix) = data.get(Self::CLAIMED_BITMAP_OFFSET..Self::CLAIMED_BITMAP_OFFSET + 4) else {
+            return false;
+        };
+        let claimed_bitmap_len = u32::from_le_bytes(length_prefix.try_into().unwrap()) as usize;
+        let claimed_bitmap_end = Self::CLAIMED_BITMAP_OFFSET + 4 + claimed_bitmap_len;
+        data.len() >= claimed_bitmap_end && data.len() < claimed_bitmap_end + Self::MIN_APPENDED_FIELDS_SIZE
+    }
+
+    // Decodes root bundles of the current or the legacy layout, so that views and off-chain code read both the same
+    // way. Fields appended after the legacy layout are zeroed, as they were never recorded.
+    pub fn try_deserialize_any_layout(data: &[u8]) -> Result<Self> {
+        if !Self::is_legacy_layout(data) {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+
+        let mut padded_data = data.to_vec();
+        padded_data.resize(data.len() + Self::MIN_APPENDED_FIELDS_SIZE, 0);
+        Self::try_deserialize(&mut padded_data.as_slice())
+    }
 }
@@ -1,7 +1,45 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{DISCRIMINATOR_SIZE, LEGACY_ROUTE_SIZE};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, PartialEq)]
+pub enum SettlementMode {
+    VaultTransfer, // Slow fills transfer output tokens from the vault to the recipient.
+    BurnFromVault, // Slow fills burn vault held synthetic/receipt tokens and notify the handler instead.
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Route {
-    pub enabled: bool, // Tracks if the route is enabled.
+    pub enabled: bool,                   // Tracks if the route is enabled.
+    pub expected_decimals: u8,           // Origin token mint decimals snapshotted when the route was last enabled.
+    pub settlement_mode: SettlementMode, // How slow fills of this token from the destination chain are settled.
+    pub disable_at: u32,                 // Time from which deposits are rejected as on a disabled route. 0 if unset.
+    pub decimal_shift: i8,               // Power of 10 scaling output amounts of fills from the destination chain.
+    pub disabled_at: u32,                // Time when the route was disabled, 0 while enabled or when unknown.
+    pub bump: u8,                        // Canonical bump of the route PDA, so that it is not searched on every use.
+    pub min_relayer_fee_bps: u16,        // Informational floor of the implied fee of fast fills, 0 if unset.
+    pub destination_chain_id: u64,       // Destination chain of the route, same as used in its PDA derivation.
+}
+
+impl Route {
+    // Decodes route PDAs of the current or the legacy layout, so that views and off-chain code read both the same way.
+    // Fields appended after the legacy layout are zeroed, as they were never recorded.
+    pub fn try_deserialize_any_layout(data: &[u8]) -> Result<Self> {
+        if data.len() != LEGACY_ROUTE_SIZE {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+
+        let mut padded_data = data.to_vec();
+        padded_data.resize(DISCRIMINATOR_SIZE + Self::INIT_SPACE, 0);
+        Self::try_deserialize(&mut padded_data.as_slice())
+    }
+
+    // Records the fields that the legacy layout did not. Mint decimals are immutable, so snapshotting them now is the
+    // same as having snapshotted them when the legacy route was enabled.
+    pub fn migrate_legacy_layout(&mut self, bump: u8, destination_chain_id: u64, mint_decimals: u8) {
+        self.bump = bump;
+        self.destination_chain_id = destination_chain_id;
+        self.expected_decimals = mint_decimals;
+    }
 }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{CURRENT_STATE_VERSION, MAX_EXECUTION_APPROVAL_THRESHOLDS},
+    state::ExecutionApprovalThreshold,
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct State {
@@ -15,4 +20,96 @@ pub struct State {
     pub root_bundle_id: u32,            // Tracks the next current root bundle id.
     pub deposit_quote_time_buffer: u32, // Deposit quote times can't be set more than this amount into the past/future.
     pub fill_deadline_buffer: u32,      // Fill deadlines can't be set more than this amount into the future.
+    pub winding_down: bool,             // Irreversibly set when the spoke is being decommissioned.
+    pub wind_down_time: u32,            // Time when wind down was started. Used to gate vault sweeps.
+    pub wind_down_delay: u32,           // Delay after wind_down_time before vaults can be swept.
+    pub network: u8,                    // Network tag (mainnet/devnet/localnet) that chain_id is validated against.
+    // When set, fills are checked against the depositor/recipient blocklist maintained by this authority.
+    pub compliance_authority: Option<Pubkey>,
+    pub bundle_audits_enabled: bool,    // When set, relayer refund leaves snapshot vault balances in BundleAudit PDAs.
+    pub bundle_audit_max_mints: u8,     // Number of mints each new BundleAudit PDA is sized to track.
+    // When set, slow fill requests are rate limited per signer and require their RequesterActivity PDA.
+    pub slow_fill_rate_limit_enabled: bool,
+    pub slow_fill_rate_limit: u32,      // Max slow fill requests per signer within each rate limit window.
+    pub slow_fill_rate_window: u32,     // Length of the slow fill request rate limit window in seconds.
+    // Oracle key whose attestations can link SVM-origin deposits to their destination fills. None disables them.
+    pub fill_confirmation_oracle: Option<Pubkey>,
+    pub dispute_freeze_until: u32,      // Closing PDAs is rejected until this time during HubPool bundle disputes.
+    pub protocol_fee_bps: u16,          // Share of slow fill vault payouts skimmed into the mint's fee vault.
+    // Only account that can withdraw the accrued protocol fees from the fee vaults.
+    pub protocol_fee_recipient: Option<Pubkey>,
+    // When set, core flow events are chained into per epoch EventCommitment PDAs that must be passed along.
+    pub event_commitments_enabled: bool,
+    pub liquidity_pools_enabled: bool,  // When set, external providers can add liquidity to LiquidityPool PDAs.
+    pub liquidity_pool_fee_bps: u16,    // Share of slow fill vault payouts accrued to the mint's liquidity pool.
+    pub slow_fill_grace_seconds: u32,   // Slow fills can't be requested until this long after exclusivity ends.
+    pub fills_pause_expires_at: u32,    // Paused fills resume at this time. 0 keeps the pause indefinite.
+    // When set, fills and slow fill requests mark their deposit ids in FillIndexShard PDAs.
+    pub fill_index_enabled: bool,
+    // When set, vault outflows are debited against the RateLimit PDA of their mint that must be passed along.
+    pub vault_outflow_rate_limits_enabled: bool,
+    // Program that fill_v3_relay events are mirrored into along with emit_cpi!. None disables event forwarding.
+    pub event_forwarder: Option<Pubkey>,
+    pub event_forwarder_best_effort: bool, // Tolerates event forwarding failures detected before the forwarder CPI.
+    // When set, fills, slow fill requests and slow relay leaf executions require roles in the signer AllowlistEntry.
+    pub permissioned_mode: bool,
+    // When set in permissioned mode, slow fills can only be requested by allowlisted relayers, never by the recipient
+    // or depositor of the relay.
+    pub strict_slow_fill_requester: bool,
+    // Slot of an announced program upgrade from which starting new multi-transaction flows is rejected until cleared.
+    pub upgrade_effective_after_slot: Option<u64>,
+    // Key approving the execution of slow fill leaves above the threshold of their mint. None disables the approvals.
+    pub execution_approver: Option<Pubkey>,
+    pub execution_approval_validity: u32, // Execution approvals expire this many seconds after they were created.
+    // Per mint thresholds above which executing slow fill leaves requires an ExecutionApproval by the approver.
+    #[max_len(MAX_EXECUTION_APPROVAL_THRESHOLDS)]
+    pub execution_approval_thresholds: Vec<ExecutionApprovalThreshold>,
+    pub pending_owner: Option<Pubkey>,  // Proposed owner that becomes the owner once it accepts ownership.
+    pub snapshot_min_interval: u32,     // Config snapshots can't be emitted more often than this many seconds.
+    pub last_config_snapshot_time: u32, // Time of the last config snapshot. 0 when none was emitted yet.
+    // Messenger program relaying HubPool messages to handle_receive_message instead of the CCTP Message Transmitter.
+    // None keeps CCTP. The messenger is trusted to reject replayed messages, as CCTP does with its used nonces.
+    pub admin_messenger: Option<Pubkey>,
+    // Anyone can close instruction params this long after they were initialized. 0 disables closing by others.
+    pub params_buffer_ttl: u32,
+    // Key that can still execute slow fill leaves while fills are paused, each approved by the execution approver. None
+    // rejects all slow fill leaf executions while fills are paused.
+    pub emergency_executor: Option<Pubkey>,
+    // Set at initialization so that state exported from a previous deployment can be imported. Cleared by the first
+    // deposit or relayed root bundle.
+    pub genesis: bool,
+    // RelayerStatsThresholdCrossed is emitted when the lapse ratio of a relayer crosses this threshold in either
+    // direction. 0 disables the events.
+    pub relayer_lapse_threshold_bps: u16,
+    // Time of the last reconcile_counters, reported by get_vault_capacity to indicate how stale the counters can be. 0
+    // when counters were never reconciled.
+    pub last_counter_reconciliation_time: u32,
+    // Requires a registered ChainAlias for the destination chain ID of deposits and the origin chain ID of fills and
+    // slow fill requests, so that no chain ID can be hashed without being vetted by the owner.
+    pub strict_chain_aliases: bool,
+    // Layout version of the state, only CURRENT_STATE_VERSION is accepted by instructions other than migrate_state.
+    // Reads as 0 in states created before it was recorded. Fields added later must be appended after it.
+    pub version: u8,
+    // Rejects slow fill requests of exclusive relayers during their own exclusivity window, which are otherwise allowed
+    // as on the EVM SpokePool. Added in version 2.
+    pub exclusive_slow_fills_disabled: bool,
+    // Set for the state of seed 0 when initialized or migrated by a single-state build, so that clients can assert
+    // they are talking to the canonical instance of the spoke. Added in version 3.
+    pub canonical: bool,
+}
+
+impl State {
+    pub fn is_current_version(&self) -> bool {
+        self.version == CURRENT_STATE_VERSION
+    }
+
+    // Seed that the state PDA is derived from in seeds constraints. Pinned to 0 in production builds opting into the
+    // single-state feature, so that states of other seeds fail the constraints even if a dev build created them.
+    pub fn pda_seed(&self) -> u64 {
+        if cfg!(feature = "single-state") {
+            0
+        } else {
+            self.seed
+        }
+    }
 }
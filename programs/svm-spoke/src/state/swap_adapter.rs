@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+// Swap program that deposit_v3_with_swap can CPI into, keyed by its program ID. Disabling keeps the entry around so
+// that its rent is not churned when the adapter is enabled again.
+#[account]
+#[derive(InitSpace)]
+pub struct SwapAdapter {
+    pub enabled: bool,
+}
@@ -1,7 +1,64 @@
 use anchor_lang::prelude::*;
 
+use crate::utils::OrArithmeticOverflow;
+
 #[account]
 #[derive(InitSpace)]
 pub struct TransferLiability {
     pub pending_to_hub_pool: u64, // Amount of tokens pending to be transferred to the hub pool.
 }
+
+// Tokens held by a vault on behalf of deposits that are still owed as relayer refunds or slow fill payouts. Outflows
+// are debited saturating, so that the counter can only overstate the obligations and sweeps never reach into them.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultLiability {
+    pub liabilities: u64, // Amount of the vault balance backing outstanding obligations.
+}
+
+impl VaultLiability {
+    pub fn record_outflow(&mut self, amount: u64) {
+        self.liabilities = self.liabilities.saturating_sub(amount);
+    }
+}
+
+// Output tokens of requested slow fills that are still pending execution, so that refunds and sweeps can't drain the
+// vault before slow fills execute. Released saturating on execution, replacement by a fast fill or expiry.
+#[account]
+#[derive(InitSpace)]
+pub struct SlowFillLiability {
+    pub pending_slow_fill_amount: u64, // Amount of the vault balance reserved for requested slow fills.
+}
+
+impl SlowFillLiability {
+    pub fn record_request(&mut self, amount: u64) -> Result<()> {
+        self.pending_slow_fill_amount =
+            self.pending_slow_fill_amount.checked_add(amount).or_overflow("pending slow fill amount")?;
+        Ok(())
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.pending_slow_fill_amount = self.pending_slow_fill_amount.saturating_sub(amount);
+    }
+}
+
+// Relayer refunds accrued to claim accounts of a mint that are still unclaimed, so that wind down sweeps can't drain the
+// vault before relayers claim them. Released saturating on claims, as refunds accrued before the tracking are not
+// recorded.
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimLiability {
+    pub pending_claim_amount: u64, // Amount of the vault balance owed to claim accounts of the mint.
+}
+
+impl ClaimLiability {
+    pub fn record_accrual(&mut self, amount: u64) -> Result<()> {
+        self.pending_claim_amount =
+            self.pending_claim_amount.checked_add(amount).or_overflow("pending claim amount")?;
+        Ok(())
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.pending_claim_amount = self.pending_claim_amount.saturating_sub(amount);
+    }
+}
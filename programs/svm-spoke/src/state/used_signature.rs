@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct UsedSignature {
+    pub expires_at: u32, // Signed payload is rejected after this time, so the PDA can be closed once it has passed.
+    pub payer: Pubkey,   // Payer of the rent receiving it back on close. Set when the signature is consumed.
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+// Registry entry of a vault created by create_vault, keyed by its mint. Legacy and Token-2022 mints are distinct mint
+// accounts, so each gets its own entry even when they share a symbol, and deposits and fills of a registered mint must
+// pass the token program recorded here.
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub mint: Pubkey,
+    pub token_program: Pubkey, // Token program owning the mint when the vault was created.
+    pub authority: Pubkey,     // Owner of the vault ATA, the state or the vault authority PDA.
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::CallDataError,
+    utils::{
+        decode_solidity_address, decode_solidity_bool, decode_solidity_int8, decode_solidity_uint32,
+        decode_solidity_uint64, encode_solidity_selector, get_solidity_arg, get_solidity_selector,
+        EncodeInstructionData,
+    },
+};
+
+// Admin actions that the HubPool can relay to this program. Each action is tagged by the selector of the EVM calldata
+// produced by the Solana adapter. Older versions of a message map to the latest action with defaults for the missing
+// arguments, and any unrecognized tag is rejected instead of being ignored.
+pub enum AdminMessage {
+    PauseDeposits { pause: bool },
+    PauseFills { pause: bool },
+    SetXDomainAdmin { cross_domain_admin: Pubkey },
+    EnableRoute { origin_token: Pubkey, destination_chain_id: u64, enabled: bool, disable_at: u32, decimal_shift: i8 },
+    RelayRootBundle { relayer_refund_root: [u8; 32], slow_relay_root: [u8; 32], execution_deadline: u32 },
+    EmergencyDeleteRootBundle { root_bundle_id: u32 },
+    WindDown { wind_down_delay: u32 },
+    SetDisputeFreeze { dispute_freeze_until: u32 },
+    SetDepositLimit { mint: Pubkey, destination_chain_id: u64, max_outstanding: u64 },
+    // Generic escape hatch for minor config additions, see set_config for the supported keys.
+    SetConfig { key: [u8; 32], value: [u8; 32] },
+}
+
+impl AdminMessage {
+    pub fn decode(data: &Vec<u8>) -> Result<Self> {
+        let arg = |index: usize| get_solidity_arg(data, index);
+
+        match get_solidity_selector(data)? {
+            s if s == encode_solidity_selector("pauseDeposits(bool)") => {
+                Ok(Self::PauseDeposits { pause: decode_solidity_bool(&arg(0)?)? })
+            }
+            s if s == encode_solidity_selector("pauseFills(bool)") => {
+                Ok(Self::PauseFills { pause: decode_solidity_bool(&arg(0)?)? })
+            }
+            s if s == encode_solidity_selector("setCrossDomainAdmin(address)") => {
+                Ok(Self::SetXDomainAdmin { cross_domain_admin: decode_solidity_address(&arg(0)?)? })
+            }
+            // The EVM function signature is setEnableRoute(address,uint256,bool).
+            // The EVM Solana adapter translates this to the Solana format: setEnableRoute(bytes32,uint64,bool).
+            s if s == encode_solidity_selector("setEnableRoute(bytes32,uint64,bool)") => Ok(Self::EnableRoute {
+                origin_token: Pubkey::new_from_array(arg(0)?),
+                destination_chain_id: decode_solidity_uint64(&arg(1)?)?,
+                enabled: decode_solidity_bool(&arg(2)?)?,
+                disable_at: 0,
+                decimal_shift: 0,
+            }),
+            s if s == encode_solidity_selector("setEnableRoute(bytes32,uint64,bool,uint32)") => Ok(Self::EnableRoute {
+                origin_token: Pubkey::new_from_array(arg(0)?),
+                destination_chain_id: decode_solidity_uint64(&arg(1)?)?,
+                enabled: decode_solidity_bool(&arg(2)?)?,
+                disable_at: decode_solidity_uint32(&arg(3)?)?,
+                decimal_shift: 0,
+            }),
+            s if s == encode_solidity_selector("setEnableRoute(bytes32,uint64,bool,uint32,int8)") => {
+                Ok(Self::EnableRoute {
+                    origin_token: Pubkey::new_from_array(arg(0)?),
+                    destination_chain_id: decode_solidity_uint64(&arg(1)?)?,
+                    enabled: decode_solidity_bool(&arg(2)?)?,
+                    disable_at: decode_solidity_uint32(&arg(3)?)?,
+                    decimal_shift: decode_solidity_int8(&arg(4)?)?,
+                })
+            }
+            s if s == encode_solidity_selector("relayRootBundle(bytes32,bytes32)") => Ok(Self::RelayRootBundle {
+                relayer_refund_root: arg(0)?,
+                slow_relay_root: arg(1)?,
+                execution_deadline: 0,
+            }),
+            s if s == encode_solidity_selector("relayRootBundle(bytes32,bytes32,uint32)") => Ok(Self::RelayRootBundle {
+                relayer_refund_root: arg(0)?,
+                slow_relay_root: arg(1)?,
+                execution_deadline: decode_solidity_uint32(&arg(2)?)?,
+            }),
+            s if s == encode_solidity_selector("emergencyDeleteRootBundle(uint256)") => {
+                Ok(Self::EmergencyDeleteRootBundle { root_bundle_id: decode_solidity_uint32(&arg(0)?)? })
+            }
+            s if s == encode_solidity_selector("windDown(uint32)") => {
+                Ok(Self::WindDown { wind_down_delay: decode_solidity_uint32(&arg(0)?)? })
+            }
+            s if s == encode_solidity_selector("setDisputeFreeze(uint32)") => {
+                Ok(Self::SetDisputeFreeze { dispute_freeze_until: decode_solidity_uint32(&arg(0)?)? })
+            }
+            s if s == encode_solidity_selector("setDepositLimit(bytes32,uint64,uint64)") => Ok(Self::SetDepositLimit {
+                mint: Pubkey::new_from_array(arg(0)?),
+                destination_chain_id: decode_solidity_uint64(&arg(1)?)?,
+                max_outstanding: decode_solidity_uint64(&arg(2)?)?,
+            }),
+            s if s == encode_solidity_selector("setConfig(bytes32,bytes32)") => {
+                Ok(Self::SetConfig { key: arg(0)?, value: arg(1)? })
+            }
+            _ => err!(CallDataError::UnknownAdminAction),
+        }
+    }
+
+    // Encodes the instruction data of the self-invoked admin instruction implementing the action.
+    pub fn to_instruction_data(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::PauseDeposits { pause } => pause.encode_instruction_data("global:pause_deposits"),
+            Self::PauseFills { pause } => pause.encode_instruction_data("global:pause_fills"),
+            Self::SetXDomainAdmin { cross_domain_admin } => {
+                cross_domain_admin.encode_instruction_data("global:set_cross_domain_admin")
+            }
+            Self::EnableRoute { origin_token, destination_chain_id, enabled, disable_at, decimal_shift } => {
+                (origin_token, destination_chain_id, enabled, disable_at, decimal_shift)
+                    .encode_instruction_data("global:set_enable_route")
+            }
+            // Remotely relayed root bundles never restrict their slow fill executor. Messages are only received once
+            // thanks to the CCTP used nonces, so they do not pass idempotency keys.
+            Self::RelayRootBundle { relayer_refund_root, slow_relay_root, execution_deadline } => {
+                (relayer_refund_root, slow_relay_root, execution_deadline, None::<Pubkey>, 0u32, None::<[u8; 16]>)
+                    .encode_instruction_data("global:relay_root_bundle")
+            }
+            Self::EmergencyDeleteRootBundle { root_bundle_id } => {
+                root_bundle_id.encode_instruction_data("global:emergency_delete_root_bundle")
+            }
+            Self::WindDown { wind_down_delay } => wind_down_delay.encode_instruction_data("global:wind_down"),
+            Self::SetDisputeFreeze { dispute_freeze_until } => {
+                dispute_freeze_until.encode_instruction_data("global:set_dispute_freeze")
+            }
+            Self::SetDepositLimit { mint, destination_chain_id, max_outstanding } => {
+                (mint, destination_chain_id, max_outstanding).encode_instruction_data("global:set_deposit_limit")
+            }
+            Self::SetConfig { key, value } => {
+                (key, value, None::<[u8; 16]>).encode_instruction_data("global:set_config")
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::{error::ErrorCode, prelude::*};
+
+use crate::{
+    constants::{ADMIN_NONCE_RETENTION_SLOTS, DISCRIMINATOR_SIZE},
+    error::SvmError,
+    state::{AdminNonce, State},
+    utils::create_pda_account,
+};
+
+// Consumes the optional idempotency key of an admin instruction by creating its AdminNonce PDA, seeds ["admin_nonce",
+// seed, idempotency_key]. Returns false if the key was already consumed, in which case the caller must return without
+// re-applying the instruction or emitting its events. Instructions without a key are always applied.
+pub fn consume_idempotency_key<'info>(
+    idempotency_key: Option<[u8; 16]>,
+    state: &State,
+    admin_nonce: Option<&UncheckedAccount<'info>>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<bool> {
+    let Some(idempotency_key) = idempotency_key else {
+        return Ok(true);
+    };
+    let admin_nonce = admin_nonce.ok_or(SvmError::MissingAdminNonce)?;
+
+    let seed = state.seed.to_le_bytes();
+    let (admin_nonce_pda, bump) =
+        Pubkey::find_program_address(&[b"admin_nonce", seed.as_ref(), idempotency_key.as_ref()], &crate::ID);
+    if admin_nonce.key() != admin_nonce_pda {
+        return Err(Error::from(ErrorCode::ConstraintSeeds).with_pubkeys((admin_nonce.key(), admin_nonce_pda)));
+    }
+
+    // Only this instruction creates the PDA, so an existing one means the key was consumed and is still retained.
+    if admin_nonce.owner == &crate::ID && !admin_nonce.data_is_empty() {
+        return Ok(false);
+    }
+
+    let seeds = [b"admin_nonce".as_ref(), seed.as_ref(), idempotency_key.as_ref(), &[bump]];
+    let space = DISCRIMINATOR_SIZE + AdminNonce::INIT_SPACE;
+    create_pda_account(payer, admin_nonce, system_program, space, &seeds)?;
+
+    let consumed = AdminNonce {
+        expires_at_slot: Clock::get()?.slot.saturating_add(ADMIN_NONCE_RETENTION_SLOTS),
+        payer: payer.key(),
+    };
+    let mut data = admin_nonce.try_borrow_mut_data()?;
+    consumed.try_serialize(&mut &mut data[..])?;
+
+    Ok(true)
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    common::V3RelayData,
+    constants::{ALLOWLIST_ROLE_RELAYER, ALLOWLIST_ROLE_REQUESTER},
+    error::SvmError,
+    state::{AllowlistEntry, State},
+};
+
+// Only enforced in permissioned mode, else allowlist entries are not required. Entry PDA seeds are derived from the
+// signer by the caller, so signers without an entry pass this program ID and are rejected.
+pub fn check_allowlist_role(state: &State, allowlist_entry: &Option<Account<AllowlistEntry>>, role: u8) -> Result<()> {
+    if !state.permissioned_mode {
+        return Ok(());
+    }
+
+    match allowlist_entry {
+        Some(allowlist_entry) if allowlist_entry.has_role(role) => Ok(()),
+        _ => err!(SvmError::NotAllowlisted),
+    }
+}
+
+// With the strict requester check recipients and depositors can't request their own slow fills, as they could trigger
+// payouts to blocked counterparties before the compliance flow catches up. Only allowlisted relayers can request then.
+pub fn check_slow_fill_requester(
+    state: &State,
+    allowlist_entry: &Option<Account<AllowlistEntry>>,
+    requester: &Pubkey,
+    relay_data: &V3RelayData,
+) -> Result<()> {
+    if !state.permissioned_mode || !state.strict_slow_fill_requester {
+        return check_allowlist_role(state, allowlist_entry, ALLOWLIST_ROLE_REQUESTER);
+    }
+
+    let is_relayer = allowlist_entry.as_ref().is_some_and(|entry| entry.has_role(ALLOWLIST_ROLE_RELAYER));
+    if !is_relayer || *requester == relay_data.recipient || *requester == relay_data.depositor {
+        return err!(SvmError::SlowFillRequesterNotAllowed);
+    }
+
+    Ok(())
+}
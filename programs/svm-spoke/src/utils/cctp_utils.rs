@@ -79,6 +79,16 @@ pub fn decode_solidity_uint64(data: &[u8; 32]) -> Result<u64> {
     Ok(l_value as u64)
 }
 
+// Signed values are sign extended, so all bytes above the lowest one must match its sign bit.
+pub fn decode_solidity_int8(data: &[u8; 32]) -> Result<i8> {
+    let value = data[31] as i8;
+    let sign_extension = if value < 0 { u8::MAX } else { 0 };
+    if data[..31].iter().any(|byte| *byte != sign_extension) {
+        return err!(CallDataError::InvalidInt8);
+    }
+    Ok(value)
+}
+
 pub fn decode_solidity_address(data: &[u8; 32]) -> Result<Pubkey> {
     for i in 0..12 {
         if data[i] != 0 {
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::SvmError,
+    state::{ChainAlias, State},
+};
+
+// Chain IDs without a passed alias are taken as canonical. Also used in the seeds of deposit routes and limits, so these
+// are derived from the canonical destination chain ID.
+pub fn get_canonical_chain_id(chain_alias: &Option<Account<ChainAlias>>, chain_id: u64) -> u64 {
+    chain_alias.as_ref().map_or(chain_id, |chain_alias| chain_alias.canonical_chain_id)
+}
+
+// Resolves the destination chain ID of deposits, rejecting unregistered chain IDs when the state requires aliases.
+pub fn resolve_chain_id(state: &State, chain_alias: &Option<Account<ChainAlias>>, chain_id: u64) -> Result<u64> {
+    if chain_alias.is_none() && state.strict_chain_aliases {
+        return err!(SvmError::UnregisteredChainAlias);
+    }
+
+    Ok(get_canonical_chain_id(chain_alias, chain_id))
+}
+
+// Origin chain IDs of relay data are hashed as is, so relays using an alias of another chain ID could never match the
+// deposit and are rejected before any tokens are sent.
+pub fn check_origin_chain_id(state: &State, chain_alias: &Option<Account<ChainAlias>>, chain_id: u64) -> Result<()> {
+    if resolve_chain_id(state, chain_alias, chain_id)? != chain_id {
+        return err!(SvmError::NonCanonicalChainId);
+    }
+
+    Ok(())
+}
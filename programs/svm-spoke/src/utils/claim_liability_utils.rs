@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ClaimLiability;
+
+// Claim liability PDAs are only created by relayer refund leaf executions, so other instructions take them as unchecked
+// accounts at the derived address and treat missing ones as nothing owed to claim accounts of the mint.
+fn load_claim_liability(account: &AccountInfo) -> Result<Option<ClaimLiability>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ClaimLiability::try_deserialize(&mut &account.try_borrow_data()?[..])?))
+}
+
+pub fn get_pending_claim_amount(account: &AccountInfo) -> Result<u64> {
+    Ok(load_claim_liability(account)?.map_or(0, |liability| liability.pending_claim_amount))
+}
+
+// Skipped when the caller did not pass the liability PDA, which can only overstate the amount owed to claim accounts.
+pub fn release_claim_liability(claim_liability: &Option<UncheckedAccount>, amount: u64) -> Result<()> {
+    let Some(account) = claim_liability.as_ref() else {
+        return Ok(());
+    };
+    let Some(mut liability) = load_claim_liability(account)? else {
+        return Ok(());
+    };
+
+    liability.release(amount);
+    liability.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
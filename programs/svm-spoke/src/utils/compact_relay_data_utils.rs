@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{common::V3RelayData, constants::COMPACT_RELAY_DATA_VERSION, error::SvmError};
+
+// Compact encoding of relay data for batched instructions, where the zero padded bytes32 fields would otherwise
+// dominate the transaction size. It starts with the version byte and a little-endian u16 bitmap holding 2 bits per
+// bytes32 field (depositor, recipient, exclusive_relayer, input_token, output_token and deposit_id in this order),
+// followed by the encoded bytes32 fields and the borsh serialized remaining relay data fields. Each bytes32 field is
+// omitted when it is default, takes 20 bytes when its 12 high-order bytes are zero (e.g. EVM addresses or small
+// deposit ids) or is passed in full. The canonical V3RelayData is always reconstructed before hashing.
+const COMPACT_FIELD_DEFAULT: u16 = 0;
+const COMPACT_FIELD_SHORT: u16 = 1;
+const COMPACT_FIELD_FULL: u16 = 2;
+const COMPACT_FIELD_BITS: usize = 2;
+const COMPACT_SHORT_FIELD_SIZE: usize = 20;
+const COMPACT_BYTES32_FIELDS: usize = 6;
+const COMPACT_HEADER_SIZE: usize = 3;
+
+#[derive(AnchorDeserialize)]
+struct CompactRelayDataTail {
+    input_amount: u64,
+    output_amount: u64,
+    origin_chain_id: u64,
+    fill_deadline: u32,
+    exclusivity_deadline: u32,
+    message: Vec<u8>,
+}
+
+pub fn decode_compact_relay_data(data: &[u8]) -> Result<V3RelayData> {
+    if data.len() < COMPACT_HEADER_SIZE || data[0] != COMPACT_RELAY_DATA_VERSION {
+        return err!(SvmError::InvalidCompactRelayData);
+    }
+    let bitmap = u16::from_le_bytes([data[1], data[2]]);
+    if bitmap >> (COMPACT_FIELD_BITS * COMPACT_BYTES32_FIELDS) != 0 {
+        return err!(SvmError::InvalidCompactRelayData);
+    }
+
+    let mut cursor = &data[COMPACT_HEADER_SIZE..];
+    let mut fields = [[0u8; 32]; COMPACT_BYTES32_FIELDS];
+    for (index, field) in fields.iter_mut().enumerate() {
+        let size = match (bitmap >> (COMPACT_FIELD_BITS * index)) & 0b11 {
+            COMPACT_FIELD_DEFAULT => continue,
+            COMPACT_FIELD_SHORT => COMPACT_SHORT_FIELD_SIZE,
+            COMPACT_FIELD_FULL => 32,
+            _ => return err!(SvmError::InvalidCompactRelayData),
+        };
+        if cursor.len() < size {
+            return err!(SvmError::InvalidCompactRelayData);
+        }
+        let (value, remaining) = cursor.split_at(size);
+
+        // Deposit id is little-endian, so its high-order bytes are trailing unlike for the big-endian addresses.
+        let offset = if index == COMPACT_BYTES32_FIELDS - 1 { 0 } else { 32 - size };
+        field[offset..offset + size].copy_from_slice(value);
+        cursor = remaining;
+    }
+
+    // Strict deserialization also rejects any trailing bytes.
+    let tail = CompactRelayDataTail::try_from_slice(cursor).map_err(|_| SvmError::InvalidCompactRelayData)?;
+    let [depositor, recipient, exclusive_relayer, input_token, output_token, deposit_id] = fields;
+
+    Ok(V3RelayData {
+        depositor: Pubkey::new_from_array(depositor),
+        recipient: Pubkey::new_from_array(recipient),
+        exclusive_relayer: Pubkey::new_from_array(exclusive_relayer),
+        input_token: Pubkey::new_from_array(input_token),
+        output_token: Pubkey::new_from_array(output_token),
+        input_amount: tail.input_amount,
+        output_amount: tail.output_amount,
+        origin_chain_id: tail.origin_chain_id,
+        deposit_id,
+        fill_deadline: tail.fill_deadline,
+        exclusivity_deadline: tail.exclusivity_deadline,
+        message: tail.message,
+    })
+}
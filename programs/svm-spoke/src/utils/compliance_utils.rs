@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::SvmError, state::State};
+
+// Only enforced when the state has a compliance authority, else attestation accounts are not required. Attestation PDA
+// seeds are checked by the caller, so any initialized account owned by this program at that address is a block entry.
+pub fn check_compliance(state: &State, attestations: &[&Option<UncheckedAccount>]) -> Result<()> {
+    if state.compliance_authority.is_none() {
+        return Ok(());
+    }
+
+    for attestation in attestations {
+        let attestation = attestation.as_ref().ok_or(SvmError::MissingComplianceAttestation)?;
+        if attestation.owner == &crate::ID && !attestation.data_is_empty() {
+            return err!(SvmError::ComplianceBlocked);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::{close_account, CloseAccount, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::NATIVE_MINT,
+    error::SvmError,
+    state::{DeliveryMode, DeliveryPreference, State},
+};
+
+// Native delivery only applies to wSOL payouts, so that a single preference can be used across all output tokens.
+pub fn is_native_delivery(delivery_preference: &Option<Account<DeliveryPreference>>, mint: &Pubkey) -> bool {
+    *mint == NATIVE_MINT
+        && delivery_preference.as_ref().is_some_and(|preference| preference.mode == DeliveryMode::Native)
+}
+
+pub fn is_escrow_delivery(delivery_preference: &Option<Account<DeliveryPreference>>) -> bool {
+    delivery_preference.as_ref().is_some_and(|preference| preference.mode == DeliveryMode::Escrow)
+}
+
+// Selects where the relay payout is delivered: the recipient escrow when the recipient opted into escrow delivery, the
+// native unwrap account for wSOL payouts when the recipient opted into native delivery, else the recipient's associated
+// token account. Also returns the escrow address for events when it was selected.
+pub fn get_payout_destination<'a, 'info>(
+    delivery_preference: &Option<Account<'info, DeliveryPreference>>,
+    mint: &Pubkey,
+    recipient_token_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    recipient_escrow: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    native_unwrap_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+) -> Result<(&'a InterfaceAccount<'info, TokenAccount>, Option<Pubkey>)> {
+    if is_escrow_delivery(delivery_preference) {
+        let recipient_escrow = recipient_escrow.as_deref().ok_or(SvmError::MissingRecipientEscrow)?;
+        Ok((recipient_escrow, Some(recipient_escrow.key())))
+    } else if is_native_delivery(delivery_preference, mint) {
+        let native_unwrap_account = native_unwrap_account.as_deref().ok_or(SvmError::MissingNativeUnwrapAccount)?;
+        Ok((native_unwrap_account, None))
+    } else {
+        let recipient_token_account =
+            recipient_token_account.as_deref().ok_or(SvmError::MissingRecipientTokenAccount)?;
+        Ok((recipient_token_account, None))
+    }
+}
+
+// Unwraps the wSOL payout held by the native unwrap account to recipient lamports. Closing the account returns both the
+// payout and the rent to the payer that created it, which then forwards exactly the payout amount to the recipient.
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_native_payout<'info>(
+    native_unwrap_account: &InterfaceAccount<'info, TokenAccount>,
+    recipient: &Option<UncheckedAccount<'info>>,
+    expected_recipient: &Pubkey,
+    payer: &Signer<'info>,
+    amount: u64,
+    state: &Account<'info, State>,
+    state_bump: u8,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let recipient = recipient.as_ref().ok_or(SvmError::MissingNativeUnwrapAccount)?;
+    if recipient.key() != *expected_recipient {
+        return err!(SvmError::InvalidNativeRecipient);
+    }
+
+    let close_accounts = CloseAccount {
+        account: native_unwrap_account.to_account_info(),
+        destination: payer.to_account_info(),
+        authority: state.to_account_info(),
+    };
+
+    let state_seed_bytes = state.seed.to_le_bytes();
+    let seeds = &[b"state", state_seed_bytes.as_ref(), &[state_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    close_account(CpiContext::new_with_signer(token_program.to_account_info(), close_accounts, signer_seeds))?;
+
+    let transfer_accounts = Transfer { from: payer.to_account_info(), to: recipient.to_account_info() };
+    transfer(CpiContext::new(system_program.to_account_info(), transfer_accounts), amount)
+}
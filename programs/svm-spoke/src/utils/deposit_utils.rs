@@ -1,4 +1,21 @@
 use anchor_lang::{prelude::*, solana_program::keccak};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            permanent_delegate::PermanentDelegate, transfer_fee::TransferFeeConfig, transfer_hook::TransferHook,
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_interface::Mint,
+};
+
+use crate::{
+    constants::MAX_EXCLUSIVITY_PERIOD_SECONDS,
+    error::SvmError,
+    state::{DepositLimit, DepositorNonce},
+    utils::{hash_non_empty_message, OrArithmeticOverflow},
+};
 
 #[derive(Accounts)]
 pub struct Null {} // Define a dummy context struct so we can export this as a view function in lib.
@@ -9,3 +26,124 @@ pub fn get_unsafe_deposit_id(msg_sender: Pubkey, depositor: Pubkey, deposit_nonc
 
     keccak::hash(&data).to_bytes()
 }
+
+// Resolves the exclusivity parameter of a deposit to its absolute exclusivity deadline exactly as the EVM SpokePool
+// does: zero disables exclusivity, values up to and including MAX_EXCLUSIVITY_PERIOD_SECONDS are offsets from the
+// current time and larger values are absolute timestamps. Offsets overflowing the u32 deadline revert on EVM, so they
+// fail here as well instead of saturating.
+pub fn resolve_exclusivity_deadline(exclusivity_parameter: u32, current_time: u32) -> Result<u32> {
+    if exclusivity_parameter == 0 || exclusivity_parameter > MAX_EXCLUSIVITY_PERIOD_SECONDS {
+        return Ok(exclusivity_parameter);
+    }
+
+    exclusivity_parameter.checked_add(current_time).or_overflow("exclusivity deadline")
+}
+
+// Depositors sign the keccak hash of the borsh serialized destination chain id, relay hash and updated fill values,
+// with the updated message replaced by its hash. The chain id is bound explicitly so that an update signed for one
+// chain cannot be replayed on another one.
+pub fn get_updated_deposit_hash(
+    chain_id: u64,
+    relay_hash: &[u8; 32],
+    updated_output_amount: u64,
+    updated_recipient: &Pubkey,
+    updated_message: &Vec<u8>,
+) -> [u8; 32] {
+    let mut data = Vec::new();
+
+    AnchorSerialize::serialize(
+        &(chain_id, *relay_hash, updated_output_amount, *updated_recipient, hash_non_empty_message(updated_message)),
+        &mut data,
+    )
+    .unwrap();
+
+    keccak::hash(&data).to_bytes()
+}
+
+// Token-2022 mints with the TransferFee extension withhold part of the transferred amount in the destination account,
+// so the vault receives less than the deposited input_amount. This holds even when the current fee is zero, as the
+// fee can be changed by the mint authority at any time.
+pub fn has_transfer_fee(mint: &InterfaceAccount<Mint>) -> Result<bool> {
+    let account_info = mint.to_account_info();
+    if *account_info.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+
+    let data = account_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    Ok(mint.get_extension::<TransferFeeConfig>().is_ok())
+}
+
+// Amount to transfer so that the destination receives amount after the transfer fee of the current epoch is withheld,
+// else the recipients of vault payouts would be short changed by the fee.
+pub fn get_transfer_fee_inclusive_amount(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let account_info = mint.to_account_info();
+    if *account_info.owner != spl_token_2022::ID {
+        return Ok(amount);
+    }
+
+    let data = account_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(amount);
+    };
+
+    let fee = transfer_fee_config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(SvmError::InvalidTransferFeeAmount)?;
+
+    amount.checked_add(fee).or_overflow("transfer fee inclusive amount")
+}
+
+// Token-2022 mints with the PermanentDelegate extension let the delegate move vault funds, and mints with the
+// TransferHook extension run an arbitrary program on every vault transfer, so vault balances of such mints can not be
+// relied upon. As with transfer fees, only the presence of the extension matters as its authority can update it.
+pub fn has_unsupported_extension(mint: &InterfaceAccount<Mint>) -> Result<bool> {
+    let account_info = mint.to_account_info();
+    if *account_info.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+
+    let data = account_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    Ok(mint.get_extension::<PermanentDelegate>().is_ok() || mint.get_extension::<TransferHook>().is_ok())
+}
+
+// Deposit limit PDAs that were never created are owned by the system program and leave the route unlimited. The PDA
+// address is checked by the deposit instructions, so depositors can't skip an existing limit by omitting it.
+pub fn check_deposit_limit(deposit_limit: &AccountInfo, vault_balance: u64, amount: u64) -> Result<()> {
+    if deposit_limit.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let deposit_limit = DepositLimit::try_deserialize(&mut &deposit_limit.try_borrow_data()?[..])?;
+    if !deposit_limit.allows_deposit(vault_balance, amount) {
+        return err!(SvmError::DepositLimitExceeded);
+    }
+
+    Ok(())
+}
+
+// Depositor nonce PDAs that were never created are owned by the system program and leave the deposits of the depositor
+// unordered, so passing a nonce is rejected instead of being silently dropped. As with deposit limits the PDA address
+// is checked by the deposit instructions, so an opted in depositor can't skip its nonce by omitting it.
+pub fn apply_depositor_nonce(depositor_nonce: &AccountInfo, nonce: Option<u64>) -> Result<Option<u64>> {
+    if depositor_nonce.owner != &crate::ID {
+        return match nonce {
+            Some(_) => err!(SvmError::NonceMismatch),
+            None => Ok(None),
+        };
+    }
+
+    let mut data = depositor_nonce.try_borrow_mut_data()?;
+    let mut account = DepositorNonce::try_deserialize(&mut &data[..])?;
+    if nonce != Some(account.next_nonce) {
+        return err!(SvmError::NonceMismatch);
+    }
+    account.next_nonce = account.next_nonce.checked_add(1).or_overflow("depositor nonce")?;
+    account.try_serialize(&mut &mut data[..])?;
+
+    Ok(nonce)
+}
@@ -0,0 +1,80 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        instruction::Instruction,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::error::SvmError;
+
+// Size of the Ed25519SignatureOffsets struct in the Ed25519 program instruction data.
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+// Offset of the first Ed25519SignatureOffsets struct after the num_signatures and padding bytes.
+const SIGNATURE_OFFSETS_START: usize = 2;
+
+// Verifies that the instruction preceding the current one is an Ed25519 program instruction verifying exactly one
+// signature by the expected signer over the expected message. The Ed25519 program fails the transaction on an invalid
+// signature, so we only need to check that the verified data matches what this program expects. Mismatching verified
+// data is reported with the passed invalid_signature_error.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+    invalid_signature_error: SvmError,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return err!(SvmError::InvalidEd25519Instruction);
+    }
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+
+    validate_ed25519_instruction(&ed25519_ix, signer, message, signature, invalid_signature_error)
+}
+
+fn validate_ed25519_instruction(
+    ix: &Instruction,
+    signer: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+    invalid_signature_error: SvmError,
+) -> Result<()> {
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return err!(SvmError::InvalidEd25519Instruction);
+    }
+
+    let data = &ix.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE || data[0] != 1 {
+        return err!(SvmError::InvalidEd25519Instruction);
+    }
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix_index = read_u16(offsets + 2);
+    let public_key_offset = read_u16(offsets + 4) as usize;
+    let public_key_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // All verified data must be contained within the Ed25519 instruction itself.
+    if signature_ix_index != u16::MAX || public_key_ix_index != u16::MAX || message_ix_index != u16::MAX {
+        return err!(SvmError::InvalidEd25519Instruction);
+    }
+
+    let verified_signature = data.get(signature_offset..signature_offset + 64);
+    let verified_public_key = data.get(public_key_offset..public_key_offset + 32);
+    let verified_message = data.get(message_offset..message_offset + message_size);
+
+    if verified_signature != Some(&signature[..])
+        || verified_public_key != Some(signer.as_ref())
+        || verified_message != Some(message)
+    {
+        return Err(invalid_signature_error.into());
+    }
+
+    Ok(())
+}
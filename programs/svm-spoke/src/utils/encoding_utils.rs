@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+// Byte order policy:
+// - SeedEncode is little-endian, same as borsh. Use it for PDA seeds and for keccak pre-images that are only
+//   reconstructed from borsh encoded SVM data, i.e. relay hashes and SVM merkle leaves built by off-chain tooling.
+// - EvmAbiEncode is big-endian and left padded to 32 byte words, same as abi.encode in Solidity. Use it for any keccak
+//   pre-image that must match a hash computed on EVM.
+// New fields entering a hash or seed should go through one of these traits instead of calling to_le/be_bytes directly.
+
+pub trait SeedEncode {
+    type Bytes: AsRef<[u8]>;
+
+    fn to_seed_bytes(&self) -> Self::Bytes;
+}
+
+impl SeedEncode for u8 {
+    type Bytes = [u8; 1];
+
+    fn to_seed_bytes(&self) -> Self::Bytes {
+        self.to_le_bytes()
+    }
+}
+
+impl SeedEncode for u32 {
+    type Bytes = [u8; 4];
+
+    fn to_seed_bytes(&self) -> Self::Bytes {
+        self.to_le_bytes()
+    }
+}
+
+impl SeedEncode for u64 {
+    type Bytes = [u8; 8];
+
+    fn to_seed_bytes(&self) -> Self::Bytes {
+        self.to_le_bytes()
+    }
+}
+
+impl SeedEncode for Pubkey {
+    type Bytes = [u8; 32];
+
+    fn to_seed_bytes(&self) -> Self::Bytes {
+        self.to_bytes()
+    }
+}
+
+pub trait EvmAbiEncode {
+    fn to_abi_word(&self) -> [u8; 32];
+}
+
+impl EvmAbiEncode for u8 {
+    fn to_abi_word(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[31] = *self;
+        word
+    }
+}
+
+impl EvmAbiEncode for u32 {
+    fn to_abi_word(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[28..].copy_from_slice(&self.to_be_bytes());
+        word
+    }
+}
+
+impl EvmAbiEncode for u64 {
+    fn to_abi_word(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&self.to_be_bytes());
+        word
+    }
+}
+
+// Addresses are already encoded as bytes32 words on the EVM side, so they are passed through unchanged.
+impl EvmAbiEncode for Pubkey {
+    fn to_abi_word(&self) -> [u8; 32] {
+        self.to_bytes()
+    }
+}
+
+impl EvmAbiEncode for [u8; 32] {
+    fn to_abi_word(&self) -> [u8; 32] {
+        *self
+    }
+}
+
+// Structs entering a keccak pre-image under the SeedEncode policy are borsh serialized, which encodes all their numeric
+// fields as little-endian.
+pub fn seed_encode_struct<T: AnchorSerialize>(value: &T, bytes: &mut Vec<u8>) -> Result<()> {
+    AnchorSerialize::serialize(value, bytes)?;
+
+    Ok(())
+}
+
+// Lowercase 0x prefixed hex, as hashes and bytes are shown by EVM tooling, e.g. to log relay hashes that didn't match.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{:02x}", byte));
+    }
+    hex
+}
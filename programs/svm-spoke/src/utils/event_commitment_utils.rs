@@ -0,0 +1,35 @@
+use anchor_lang::{prelude::*, Event};
+
+use crate::{
+    constants::EVENT_COMMITMENT_EPOCH_DURATION,
+    error::SvmError,
+    state::{EventCommitment, State},
+    utils::get_current_time,
+};
+
+pub fn get_event_commitment_epoch(state: &State) -> Result<u32> {
+    Ok(get_current_time(state)? / EVENT_COMMITMENT_EPOCH_DURATION)
+}
+
+// Chains the event into the commitment of the current epoch when event commitments are enabled and returns it, so that
+// call sites wrap the event passed to emit_cpi! and no emitted event can be missed in the commitment.
+pub fn commit_event<'info, E: Event>(
+    state: &State,
+    event_commitment: &mut Option<Account<'info, EventCommitment>>,
+    event: E,
+) -> Result<E> {
+    if !state.event_commitments_enabled {
+        return Ok(event);
+    }
+
+    let event_commitment = event_commitment.as_mut().ok_or(SvmError::MissingEventCommitment)?;
+    if event_commitment.seed != state.seed || event_commitment.epoch != get_event_commitment_epoch(state)? {
+        return err!(SvmError::InvalidEventCommitment);
+    }
+
+    let mut event_data = Vec::new();
+    event.serialize(&mut event_data)?;
+    event_commitment.commit(E::DISCRIMINATOR.as_ref(), &event_data);
+
+    Ok(event)
+}
@@ -0,0 +1,59 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+    Event,
+};
+
+use crate::{constants::MAX_FORWARDED_EVENT_SIZE, error::SvmError, state::State};
+
+// Sha256(global:forward_event)[..8];
+const FORWARD_EVENT_DISCRIMINATOR: [u8; 8] = (0x465b8234dd6b0c37_u64).to_be_bytes();
+
+// Mirrors the event into the event forwarder program when the state has one and returns it, so that call sites wrap
+// the event passed to emit_cpi! the same way as with commit_event.
+pub fn forward_event<'info, E: Event>(
+    state: &State,
+    event_forwarder: &Option<UncheckedAccount<'info>>,
+    event: E,
+) -> Result<E> {
+    let forwarder = match state.event_forwarder {
+        Some(forwarder) => forwarder,
+        None => return Ok(event),
+    };
+
+    if let Err(error) = invoke_event_forwarder(&forwarder, event_forwarder, &event) {
+        if !state.event_forwarder_best_effort {
+            return Err(error);
+        }
+        msg!("Skipped event forwarding: {}", error);
+    }
+
+    Ok(event)
+}
+
+// The forwarder is invoked without accounts through its forward_event(event: Vec<u8>) instruction, where the event
+// holds the discriminator prefixed event data as it would be logged. Best effort forwarding can only tolerate failures
+// detected here, as an error returned by the forwarder program itself aborts the whole transaction.
+fn invoke_event_forwarder<E: Event>(
+    forwarder: &Pubkey,
+    event_forwarder: &Option<UncheckedAccount>,
+    event: &E,
+) -> Result<()> {
+    let event_forwarder = event_forwarder.as_ref().ok_or(SvmError::MissingEventForwarder)?;
+    if event_forwarder.key() != *forwarder || !event_forwarder.executable {
+        return err!(SvmError::InvalidEventForwarder);
+    }
+
+    let event_data = event.data();
+    if event_data.len() > MAX_FORWARDED_EVENT_SIZE {
+        return err!(SvmError::ForwardedEventTooLarge);
+    }
+
+    let mut data = FORWARD_EVENT_DISCRIMINATOR.to_vec();
+    event_data.serialize(&mut data)?;
+    let instruction = Instruction { program_id: *forwarder, accounts: vec![], data };
+
+    invoke(&instruction, &[event_forwarder.to_account_info()])?;
+
+    Ok(())
+}
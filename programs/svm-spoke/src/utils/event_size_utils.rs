@@ -0,0 +1,55 @@
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::*, Event};
+
+use crate::{
+    constants::MAX_EVENT_CPI_DATA_SIZE,
+    event::{ExecutedRelayerRefundRoot, ExecutedRelayerRefundRootChunk},
+};
+
+// Size of the self-CPI instruction data built by emit_cpi!: event tag, event discriminator and serialized event.
+pub fn get_event_cpi_data_size<E: Event>(event: &E) -> usize {
+    EVENT_IX_TAG_LE.len() + event.data().len()
+}
+
+// Number of refunds fitting in each ExecutedRelayerRefundRootChunk event within MAX_EVENT_CPI_DATA_SIZE.
+pub fn get_refunds_per_event_chunk() -> usize {
+    let empty_chunk = ExecutedRelayerRefundRootChunk {
+        amount_to_return: 0,
+        chain_id: 0,
+        refund_amounts: Vec::new(),
+        root_bundle_id: 0,
+        leaf_id: 0,
+        l2_token_address: Pubkey::default(),
+        refund_addresses: Vec::new(),
+        deferred_refunds: false,
+        caller: Pubkey::default(),
+        token_decimals: 0,
+        chunk_index: 0,
+        total_chunks: 0,
+    };
+    let refund_size = std::mem::size_of::<u64>() + std::mem::size_of::<Pubkey>();
+    (MAX_EVENT_CPI_DATA_SIZE - get_event_cpi_data_size(&empty_chunk)) / refund_size
+}
+
+// Splits the refunds of an event too large to emit at once into chunks in leaf order.
+pub fn get_refund_event_chunks(event: &ExecutedRelayerRefundRoot) -> Vec<ExecutedRelayerRefundRootChunk> {
+    let refunds_per_chunk = get_refunds_per_event_chunk();
+    let total_chunks = event.refund_amounts.len().div_ceil(refunds_per_chunk) as u32;
+    let chunks = event.refund_amounts.chunks(refunds_per_chunk).zip(event.refund_addresses.chunks(refunds_per_chunk));
+    chunks
+        .enumerate()
+        .map(|(chunk_index, (refund_amounts, refund_addresses))| ExecutedRelayerRefundRootChunk {
+            amount_to_return: event.amount_to_return,
+            chain_id: event.chain_id,
+            refund_amounts: refund_amounts.to_vec(),
+            root_bundle_id: event.root_bundle_id,
+            leaf_id: event.leaf_id,
+            l2_token_address: event.l2_token_address,
+            refund_addresses: refund_addresses.to_vec(),
+            deferred_refunds: event.deferred_refunds,
+            caller: event.caller,
+            token_decimals: event.token_decimals,
+            chunk_index: chunk_index as u32,
+            total_chunks,
+        })
+        .collect()
+}
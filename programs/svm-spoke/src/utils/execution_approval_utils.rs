@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::SvmError,
+    state::{ExecutionApproval, State},
+};
+
+// Checks that the approval was created for the leaf by the current execution approver and is still recent enough.
+pub fn verify_execution_approval(
+    state: &State,
+    execution_approval: &Option<Account<ExecutionApproval>>,
+    approver: Pubkey,
+    leaf_hash: [u8; 32],
+    current_time: u32,
+) -> Result<()> {
+    let execution_approval = execution_approval.as_ref().ok_or(SvmError::MissingExecutionApproval)?;
+    // Approvals by a replaced approver are no longer honored.
+    if execution_approval.leaf_hash != leaf_hash || execution_approval.approver != approver {
+        return err!(SvmError::InvalidExecutionApproval);
+    }
+    if current_time > execution_approval.approved_at.saturating_add(state.execution_approval_validity) {
+        return err!(SvmError::ExpiredExecutionApproval);
+    }
+
+    Ok(())
+}
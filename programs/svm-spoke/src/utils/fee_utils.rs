@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BPS_DENOMINATOR, MAX_TOTAL_FEE_BPS};
+
+// Rounds down in favor of the payout recipient. The product cannot overflow u128 and the result fits u64 as fee_bps is
+// bounded by BPS_DENOMINATOR when set.
+pub fn calculate_bps_fee(amount: u64, fee_bps: u16) -> u64 {
+    (amount as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+// Returns the protocol and liquidity pool fees capped at MAX_TOTAL_FEE_BPS combined, prioritizing the protocol fee.
+// Setters already enforce the cap, so this only degrades safely if the state holds out of range fees.
+pub fn get_capped_fee_bps(protocol_fee_bps: u16, liquidity_pool_fee_bps: u16) -> (u16, u16) {
+    let capped_protocol_fee = (protocol_fee_bps as u64).min(MAX_TOTAL_FEE_BPS);
+    let capped_liquidity_pool_fee = (liquidity_pool_fee_bps as u64).min(MAX_TOTAL_FEE_BPS - capped_protocol_fee);
+
+    if capped_protocol_fee + capped_liquidity_pool_fee != protocol_fee_bps as u64 + liquidity_pool_fee_bps as u64 {
+        msg!("Clamped protocol fee {} and pool fee {} bps to the fee cap", protocol_fee_bps, liquidity_pool_fee_bps);
+    }
+
+    (capped_protocol_fee as u16, capped_liquidity_pool_fee as u16)
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    common::V3RelayData,
+    constants::FILL_INDEX_SHARD_SIZE,
+    error::SvmError,
+    state::{FillIndexShard, State},
+};
+
+// Deposit ids are little-endian encoded, so only the ones fitting into u64 are representable in fill index shards.
+// Returns the shard bucket and the offset of the deposit id within it.
+pub fn get_fill_index_position(deposit_id: &[u8; 32]) -> Option<(u64, u16)> {
+    if deposit_id[8..].iter().any(|&byte| byte != 0) {
+        return None;
+    }
+    let deposit_id = u64::from_le_bytes(deposit_id[..8].try_into().unwrap());
+
+    Some((deposit_id / FILL_INDEX_SHARD_SIZE, (deposit_id % FILL_INDEX_SHARD_SIZE) as u16))
+}
+
+// Bucket used in the fill index shard seeds. Deposit ids out of the representable range map to a bucket that is never
+// written to.
+pub fn get_fill_index_bucket(deposit_id: &[u8; 32]) -> u64 {
+    get_fill_index_position(deposit_id).map_or(u64::MAX, |(bucket, _)| bucket)
+}
+
+// Marks the relay deposit id in its fill index shard when the fill index is enabled. Deposit ids out of the
+// representable range are skipped, so their fill status PDAs can only be found by scanning program accounts.
+pub fn record_fill_index<'info>(
+    state: &State,
+    fill_index_shard: &mut Option<Account<'info, FillIndexShard>>,
+    relay_data: &V3RelayData,
+) -> Result<()> {
+    if !state.fill_index_enabled {
+        return Ok(());
+    }
+    let (bucket, offset) = match get_fill_index_position(&relay_data.deposit_id) {
+        Some(position) => position,
+        None => return Ok(()),
+    };
+
+    // Seeds already bind the shard to the origin chain and bucket, these are stored for lazily created shards.
+    let fill_index_shard = fill_index_shard.as_mut().ok_or(SvmError::MissingFillIndexShard)?;
+    fill_index_shard.origin_chain_id = relay_data.origin_chain_id;
+    fill_index_shard.bucket = bucket;
+    fill_index_shard.set(offset);
+
+    Ok(())
+}
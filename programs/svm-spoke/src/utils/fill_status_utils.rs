@@ -0,0 +1,19 @@
+use anchor_lang::{error::ErrorCode, prelude::*, solana_program::system_program};
+
+use crate::state::{FillStatus, FillStatusAccount};
+
+// Reads the status of a relay from its fill status PDA, validating the owner and discriminator of the account so that
+// composing programs don't decode it by hand. PDAs that were never created are owned by the system program and read as
+// Unfilled, as do PDAs closed once their fill deadline passed, so Filled can only be relied upon before the deadline.
+// The account address is not checked here: callers must compare it with derive_fill_status_pda of the relay hash.
+pub fn deserialize_fill_status(account_info: &AccountInfo) -> Result<FillStatus> {
+    if account_info.owner == &system_program::ID && account_info.data_is_empty() {
+        return Ok(FillStatus::Unfilled);
+    }
+    if account_info.owner != &crate::ID {
+        return Err(Error::from(ErrorCode::AccountOwnedByWrongProgram).with_pubkeys((*account_info.owner, crate::ID)));
+    }
+
+    let data = account_info.try_borrow_data()?;
+    Ok(FillStatusAccount::try_deserialize_any_layout(&data)?.status)
+}
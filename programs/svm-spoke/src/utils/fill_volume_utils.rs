@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::SvmError,
+    event::ApprovedRefundsAboveFillVolume,
+    state::{ExecutionApproval, FillVolume, State},
+    utils::verify_execution_approval,
+};
+
+// Fill volume PDAs are only created by the owner for mints opted into refund checks, so fills and refund leaves take
+// them as unchecked accounts at the derived address and skip mints that never created one. As with deposit limits the
+// PDA address is checked by the instructions, so fills of an opted in mint can't skip the tracking by omitting it.
+pub fn record_fill_volume(fill_volume: &AccountInfo, amount: u64) -> Result<()> {
+    if fill_volume.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut data = fill_volume.try_borrow_mut_data()?;
+    let mut account = FillVolume::try_deserialize(&mut &data[..])?;
+    account.record_fill(amount);
+    account.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+// Rejects refund leaves whose bundle refunds would exceed the fill volume of the mint in the bundle window plus its
+// tolerance with RefundsExceedFillVolume, unless the leaf has a recent execution approval by an execution approver other
+// than the executor. Returns the event to emit for leaves executed with such an override.
+#[allow(clippy::too_many_arguments)]
+pub fn check_refund_fill_volume(
+    state: &State,
+    fill_volume: &AccountInfo,
+    execution_approval: &Option<Account<ExecutionApproval>>,
+    executor: &Pubkey,
+    root_bundle_id: u32,
+    leaf_id: u32,
+    mint: Pubkey,
+    leaf_hash: [u8; 32],
+    amount: u64,
+    current_time: u32,
+) -> Result<Option<ApprovedRefundsAboveFillVolume>> {
+    if fill_volume.owner != &crate::ID {
+        return Ok(None);
+    }
+
+    let mut data = fill_volume.try_borrow_mut_data()?;
+    let mut account = FillVolume::try_deserialize(&mut &data[..])?;
+    let within_fill_volume = account.record_refunds(root_bundle_id, amount);
+    account.try_serialize(&mut &mut data[..])?;
+    if within_fill_volume {
+        return Ok(None);
+    }
+
+    let approver = match state.execution_approver {
+        Some(approver) if approver != *executor && execution_approval.is_some() => approver,
+        _ => return err!(SvmError::RefundsExceedFillVolume),
+    };
+    verify_execution_approval(state, execution_approval, approver, leaf_hash, current_time)?;
+
+    Ok(Some(ApprovedRefundsAboveFillVolume {
+        root_bundle_id,
+        leaf_id,
+        mint,
+        bundle_refunds: account.bundle_refunds,
+        bundle_fill_volume: account.bundle_fill_volume,
+        tolerance: account.tolerance,
+        approver,
+    }))
+}
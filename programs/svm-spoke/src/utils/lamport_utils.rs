@@ -0,0 +1,23 @@
+use anchor_lang::{prelude::*, solana_program::system_program};
+
+use crate::{error::SvmError, state::State, utils::get_current_time};
+
+// Lamports credited to a program owned account could become unrecoverable or break the owning program's invariants
+// (e.g. token account rent exempt reserve), so only system owned accounts are accepted as lamport destinations.
+pub fn assert_lamport_destination(account: &AccountInfo) -> Result<()> {
+    if account.owner != &system_program::ID {
+        return err!(SvmError::InvalidLamportDestination);
+    }
+
+    Ok(())
+}
+
+// Closed PDAs carry the evidence needed to re-derive refunds if a disputed HubPool bundle gets rolled back, so closing
+// them is rejected during the dispute freeze window set by the admin.
+pub fn assert_closes_not_frozen(state: &State) -> Result<()> {
+    if get_current_time(state)? < state.dispute_freeze_until {
+        return err!(SvmError::ClosesFrozenDuringDispute);
+    }
+
+    Ok(())
+}
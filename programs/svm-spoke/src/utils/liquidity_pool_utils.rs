@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+use crate::state::LiquidityPool;
+
+// Liquidity pool PDAs are only created by initialize_liquidity_pool, so instructions outside of the pool take them as
+// unchecked accounts at the derived address and treat missing ones as nothing owed to the pool.
+pub fn get_pool_liquidity(account: &AccountInfo) -> Result<u64> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(0);
+    }
+    Ok(LiquidityPool::try_deserialize(&mut &account.try_borrow_data()?[..])?.total_liquidity)
+}
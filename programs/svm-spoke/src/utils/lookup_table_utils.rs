@@ -0,0 +1,123 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        address_lookup_table::{
+            instruction::{create_lookup_table, extend_lookup_table},
+            state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES},
+        },
+        program::invoke_signed,
+        system_program,
+    },
+};
+use anchor_spl::{associated_token, token, token_2022};
+
+use crate::{
+    error::SvmError,
+    state::State,
+    utils::{derive_event_authority, get_vault_authority},
+};
+
+// Accounts of the spoke referenced by every relayer transaction, added to the lookup table when it is created.
+pub fn get_static_lookup_table_addresses(state: &Pubkey) -> Vec<Pubkey> {
+    vec![
+        crate::ID,
+        *state,
+        derive_event_authority(),
+        get_vault_authority(state),
+        token::ID,
+        token_2022::ID,
+        associated_token::ID,
+        system_program::ID,
+    ]
+}
+
+// Accounts of the corridor of a mint referenced by its deposits and fills: the mint, its vault and vault PDAs, and the
+// route towards the destination chain.
+pub fn get_corridor_lookup_table_addresses(
+    state: &State,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    route: &Pubkey,
+) -> Vec<Pubkey> {
+    let state_seed_bytes = state.seed.to_le_bytes();
+    let vault_registry =
+        Pubkey::find_program_address(&[b"vault", state_seed_bytes.as_ref(), mint.as_ref()], &crate::ID);
+    let vault_liability_seeds = [b"vault_liability".as_ref(), state_seed_bytes.as_ref(), mint.as_ref()];
+    let vault_liability = Pubkey::find_program_address(&vault_liability_seeds, &crate::ID);
+
+    vec![*mint, *vault, vault_registry.0, vault_liability.0, *route]
+}
+
+// Creates the lookup table with the state PDA as its authority. Its address is derived by the Address Lookup Table
+// program from the authority and the recent slot, which must still be in the SlotHashes sysvar.
+pub fn create_state_lookup_table<'info>(
+    lookup_table: &AccountInfo<'info>,
+    state: &Account<'info, State>,
+    state_bump: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    address_lookup_table_program: &AccountInfo<'info>,
+    recent_slot: u64,
+) -> Result<()> {
+    let (instruction, _) = create_lookup_table(state.key(), payer.key(), recent_slot);
+    let account_infos = [
+        lookup_table.clone(),
+        state.to_account_info(),
+        payer.clone(),
+        system_program.clone(),
+        address_lookup_table_program.clone(),
+    ];
+
+    let state_seed_bytes = state.seed.to_le_bytes();
+    let seeds = &[b"state", state_seed_bytes.as_ref(), &[state_bump]];
+
+    invoke_signed(&instruction, &account_infos, &[&seeds[..]])?;
+
+    Ok(())
+}
+
+// Extends the lookup table of the state PDA with the addresses that it does not hold yet, so that enabling routes of
+// an already added corridor doesn't fill the table with duplicates. Returns the appended addresses.
+pub fn extend_state_lookup_table<'info>(
+    lookup_table: &AccountInfo<'info>,
+    state: &Account<'info, State>,
+    state_bump: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    address_lookup_table_program: &AccountInfo<'info>,
+    addresses: &[Pubkey],
+) -> Result<Vec<Pubkey>> {
+    let (new_addresses, table_size) = {
+        let data = lookup_table.try_borrow_data()?;
+        let table = AddressLookupTable::deserialize(&data).map_err(|_| SvmError::InvalidLookupTable)?;
+        let mut new_addresses: Vec<Pubkey> = Vec::new();
+        for address in addresses {
+            if !table.addresses.contains(address) && !new_addresses.contains(address) {
+                new_addresses.push(*address);
+            }
+        }
+        (new_addresses, table.addresses.len())
+    };
+    if new_addresses.is_empty() {
+        return Ok(new_addresses);
+    }
+    if table_size + new_addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        return err!(SvmError::LookupTableFull);
+    }
+
+    let instruction = extend_lookup_table(lookup_table.key(), state.key(), Some(payer.key()), new_addresses.clone());
+    let account_infos = [
+        lookup_table.clone(),
+        state.to_account_info(),
+        payer.clone(),
+        system_program.clone(),
+        address_lookup_table_program.clone(),
+    ];
+
+    let state_seed_bytes = state.seed.to_le_bytes();
+    let seeds = &[b"state", state_seed_bytes.as_ref(), &[state_bump]];
+
+    invoke_signed(&instruction, &account_infos, &[&seeds[..]])?;
+
+    Ok(new_addresses)
+}
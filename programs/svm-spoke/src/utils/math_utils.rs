@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{BPS_DENOMINATOR, MAX_DECIMAL_SHIFT},
+    error::SvmError,
+};
+
+// Maps a failed checked arithmetic result to a typed error, logging the overflowing computation so that failures can be
+// traced without relying on overflow-checks panics that carry no program context.
+pub trait OrArithmeticOverflow<T> {
+    fn or_overflow(self, context: &str) -> Result<T>;
+}
+
+impl<T> OrArithmeticOverflow<T> for Option<T> {
+    fn or_overflow(self, context: &str) -> Result<T> {
+        self.ok_or_else(|| {
+            msg!("Arithmetic overflow in {}", context);
+            error!(SvmError::ArithmeticOverflow)
+        })
+    }
+}
+
+// Scales an output amount by 10^decimal_shift for corridors where the output token has different decimals on the
+// origin chain. Negative shifts round down, so that the normalized amount never exceeds the exact scaled amount.
+pub fn normalize_output_amount(amount: u64, decimal_shift: i8) -> Result<u64> {
+    if decimal_shift.unsigned_abs() > MAX_DECIMAL_SHIFT {
+        return err!(SvmError::InvalidDecimalShift);
+    }
+    let factor = 10u64.pow(decimal_shift.unsigned_abs() as u32);
+
+    match decimal_shift >= 0 {
+        true => amount.checked_mul(factor).or_overflow("normalized output amount"),
+        false => Ok(amount / factor),
+    }
+}
+
+// Whether filling input_amount with normalized_output_amount implies a relayer fee below min_fee_bps. Amounts are
+// compared at the larger of both scales so that no rounding of the normalization hides a fee below the floor. Amounts
+// too large to compare are never reported below the floor, as the check is informational only.
+pub fn is_relayer_fee_below_floor(
+    input_amount: u64,
+    normalized_output_amount: u64,
+    decimal_shift: i8,
+    min_fee_bps: u16,
+) -> bool {
+    if decimal_shift.unsigned_abs() > MAX_DECIMAL_SHIFT {
+        return false;
+    }
+    let factor = 10u128.pow(decimal_shift.unsigned_abs() as u32);
+    let (input_amount, output_amount) = match decimal_shift >= 0 {
+        true => (input_amount as u128 * factor, normalized_output_amount as u128),
+        false => (input_amount as u128, normalized_output_amount as u128 * factor),
+    };
+
+    // Fee is below the floor when output > input * (1 - floor).
+    let max_output_bps = (BPS_DENOMINATOR as u128).saturating_sub(min_fee_bps as u128);
+    match (output_amount.checked_mul(BPS_DENOMINATOR as u128), input_amount.checked_mul(max_output_bps)) {
+        (Some(output_bps), Some(max_output_bps)) => output_bps > max_output_bps,
+        _ => false,
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, keccak, program::invoke},
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{memo_transfer::MemoTransfer, BaseStateWithExtensions, StateWithExtensions},
+};
+
+use crate::{
+    constants::{MAX_PAYOUT_MEMO_SIZE, MEMO_PROGRAM_ID},
+    error::SvmError,
+};
+
+pub fn validate_payout_memo(memo: Option<&Vec<u8>>) -> Result<()> {
+    match memo {
+        Some(memo) if memo.is_empty() || memo.len() > MAX_PAYOUT_MEMO_SIZE => err!(SvmError::InvalidPayoutMemo),
+        _ => Ok(()),
+    }
+}
+
+// Missing memo emits zeroed bytes32, same as for empty messages.
+pub fn hash_payout_memo(memo: Option<&Vec<u8>>) -> [u8; 32] {
+    memo.map_or([0u8; 32], |memo| keccak::hash(memo).to_bytes())
+}
+
+// Token-2022 accounts with required memo transfers check that the instruction preceding the transfer CPI is a memo, so
+// this must be invoked right before the payout transfer. A missing memo for such accounts is rejected here with a
+// typed error instead of failing within the token program.
+pub fn invoke_payout_memo<'info>(
+    memo: Option<&Vec<u8>>,
+    memo_program: &Option<UncheckedAccount<'info>>,
+    payout_destination: &impl ToAccountInfo<'info>,
+) -> Result<()> {
+    let memo = match memo {
+        Some(memo) => memo,
+        None if is_memo_required(&payout_destination.to_account_info())? => return err!(SvmError::MissingRequiredMemo),
+        None => return Ok(()),
+    };
+
+    let memo_program = memo_program.as_ref().ok_or(SvmError::MissingMemoProgram)?;
+    let instruction = Instruction { program_id: MEMO_PROGRAM_ID, accounts: vec![], data: memo.clone() };
+
+    invoke(&instruction, &[memo_program.to_account_info()])?;
+
+    Ok(())
+}
+
+fn is_memo_required(account_info: &AccountInfo) -> Result<bool> {
+    if *account_info.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+
+    let data = account_info.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+
+    let memo_transfer = account.get_extension::<MemoTransfer>();
+
+    Ok(memo_transfer.is_ok_and(|extension| extension.require_incoming_transfer_memos.into()))
+}
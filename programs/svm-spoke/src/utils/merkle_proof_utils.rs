@@ -1,9 +1,20 @@
 use anchor_lang::{prelude::*, solana_program::keccak};
 
-use crate::{common::V3RelayData, error::CommonError, utils::hash_non_empty_message};
-
+use crate::{
+    common::V3RelayData,
+    constants::MAX_MERKLE_PROOF_DEPTH,
+    error::{CommonError, SvmError},
+    merkle::{self, MerkleHasher, MerkleProofError},
+    utils::{hash_non_empty_message, seed_encode_struct, SeedEncode},
+};
+
+// Relay hashes use the same explicit SVM format as slow relay leaves rather than abi.encode, so EVM side tooling
+// computes them for this spoke with calculateRelayHashUint8Array in src/svm. The pre-image is the borsh encoded
+// V3RelayData with the message replaced by its hash (zeroed bytes32 for empty message), followed by the u64 LE
+// chain_id. Known relay hashes are pinned in test/svm/fixtures/hashes.json.
 pub fn get_v3_relay_hash(relay_data: &V3RelayData, chain_id: u64) -> [u8; 32] {
-    let mut input = relay_data.try_to_vec().unwrap();
+    let mut input = Vec::new();
+    seed_encode_struct(relay_data, &mut input).unwrap();
 
     // We have serialized the original V3RelayData struct above, but we need to replace the message field with its hash,
     // so that relay hash can be reconstructed only from FilledV3Relay event that does not contain the original message
@@ -12,41 +23,27 @@ pub fn get_v3_relay_hash(relay_data: &V3RelayData, chain_id: u64) -> [u8; 32] {
     input.truncate(input.len() - 4 - relay_data.message.len());
     input.extend_from_slice(&hash_non_empty_message(&relay_data.message));
 
-    input.extend_from_slice(&chain_id.to_le_bytes());
+    input.extend_from_slice(&chain_id.to_seed_bytes());
     keccak::hash(&input).to_bytes()
 }
 
 pub fn verify_merkle_proof(root: [u8; 32], leaf: [u8; 32], proof: Vec<[u8; 32]>) -> Result<()> {
-    let computed_root = process_proof(&proof, &leaf);
-    if computed_root != root {
-        return err!(CommonError::InvalidMerkleProof);
+    match merkle::verify_proof::<Keccak256>(&root, &leaf, &proof, MAX_MERKLE_PROOF_DEPTH) {
+        Ok(()) => Ok(()),
+        Err(MerkleProofError::InvalidProofLength) => err!(SvmError::InvalidProofLength),
+        Err(MerkleProofError::InvalidMerkleProof) => err!(CommonError::InvalidMerkleProof),
     }
-
-    Ok(())
 }
 
-// The following is the rust implementation of the merkle proof verification from OpenZeppelin that can be found here:
-// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/MerkleProof.sol
 pub fn process_proof(proof: &[[u8; 32]], leaf: &[u8; 32]) -> [u8; 32] {
-    let mut computed_hash = *leaf;
-    for proof_element in proof.iter() {
-        computed_hash = commutative_keccak256(&computed_hash, proof_element);
-    }
-    computed_hash
+    merkle::process_proof::<Keccak256>(proof, leaf)
 }
 
-// See https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/Hashes.sol
-fn commutative_keccak256(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-    if a < b {
-        efficient_keccak256(a, b)
-    } else {
-        efficient_keccak256(b, a)
-    }
-}
+pub struct Keccak256;
 
-fn efficient_keccak256(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-    let mut input = [0u8; 64];
-    input[..32].copy_from_slice(a);
-    input[32..].copy_from_slice(b);
-    keccak::hash(&input).to_bytes()
+impl MerkleHasher for Keccak256 {
+    // Hashes the concatenation of both nodes without copying them into an intermediate buffer.
+    fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        keccak::hashv(&[a, b]).to_bytes()
+    }
 }
@@ -1,13 +1,26 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::{instruction::Instruction, keccak, program::invoke, system_instruction},
+    solana_program::{instruction::Instruction, keccak, log::sol_log_compute_units, program::invoke, system_instruction},
 };
+use anchor_spl::{token, token_2022, token_interface::TokenAccount};
 
-use crate::{constants::DISCRIMINATOR_SIZE, error::AcrossPlusError};
+use crate::{
+    constants::{
+        ACTION_LIST_MESSAGE_HEADER_SIZE, ACTION_LIST_MESSAGE_TAG, ACTION_LIST_MESSAGE_VERSION, DISCRIMINATOR_SIZE,
+        MAX_INLINE_EVENT_MESSAGE_SIZE, MAX_MESSAGE_ACTIONS,
+    },
+    error::AcrossPlusError,
+    state::MessageSchema,
+};
 
 // Sha256(global:handle_v3_across_message)[..8];
 const HANDLE_V3_ACROSS_MESSAGE_DISCRIMINATOR: [u8; 8] = (0x838d3447103bc45c_u64).to_be_bytes();
 
+// Message handlers must implement the Anchor compatible instruction handle_v3_across_message(token: Pubkey,
+// amount: u64, relayer: Pubkey, message: Vec<u8>), mirroring handleV3AcrossMessage on EVM. The token and amount are the
+// output token and the amount sent to the recipient, the relayer is the signer of the fill or slow fill execution and
+// the message is the handler message bytes. A failing handler reverts the whole fill.
+
 #[derive(AnchorDeserialize)]
 pub struct AcrossPlusMessage {
     pub handler: Pubkey,
@@ -17,32 +30,171 @@ pub struct AcrossPlusMessage {
     pub handler_message: Vec<u8>,
 }
 
+// Compact record of a single action within the action list message. The action handler is referenced by its index in
+// the message accounts, while its own accounts and handler message are the next accounts_len accounts and data_len
+// bytes following the previous action.
+#[derive(AnchorDeserialize)]
+pub struct MessageAction {
+    pub program_id_index: u8,
+    pub accounts_len: u8,
+    pub read_only_len: u8,
+    pub data_len: u16,
+}
+
+// Versioned envelope invoking up to MAX_MESSAGE_ACTIONS handlers in order. Message accounts map 1:1 to the remaining
+// accounts and the data holds the concatenated handler messages of all actions.
+#[derive(AnchorDeserialize)]
+pub struct ActionListMessage {
+    pub value_amount: u64,
+    pub accounts: Vec<Pubkey>,
+    pub actions: Vec<MessageAction>,
+    pub data: Vec<u8>,
+}
+
 pub fn invoke_handler<'info>(
     relayer: &AccountInfo<'info>,
+    state: &Pubkey,
     remaining_accounts: &[AccountInfo<'info>],
     message: &Vec<u8>,
+    token: &Pubkey,
+    amount: u64,
 ) -> Result<()> {
+    // Raw messages without the action list tag keep the single handler behavior.
+    if message.starts_with(&ACTION_LIST_MESSAGE_TAG) {
+        return invoke_action_list(relayer, state, remaining_accounts, message, token, amount);
+    }
+
     let message =
         AcrossPlusMessage::deserialize(&mut &message[..]).map_err(|_| AcrossPlusError::MessageDidNotDeserialize)?;
 
     // First remaining account is the handler and the rest are accounts to be passed to the message handler.
-    let message_accounts_len = message.accounts.len();
-    if remaining_accounts.len() != message_accounts_len + 1 {
+    if remaining_accounts.len() != message.accounts.len() + 1 {
         return err!(AcrossPlusError::InvalidMessageKeyLength);
     }
-    if (message.read_only_len as usize) > message_accounts_len {
-        return err!(AcrossPlusError::InvalidReadOnlyKeyLength);
-    }
     let handler = &remaining_accounts[0];
     let account_infos = &remaining_accounts[1..];
 
     if handler.key() != message.handler {
         return err!(AcrossPlusError::InvalidMessageHandler);
     }
+    // Messages addressed to plain wallets would otherwise fail within the runtime without a descriptive error.
+    if !handler.executable {
+        return err!(AcrossPlusError::MessageHandlerNotExecutable);
+    }
 
     // Populate accounts for the invoked message handler CPI.
+    let accounts = get_message_account_metas(account_infos, &message.accounts, message.read_only_len, state)?;
+
+    transfer_message_value(relayer, account_infos.first(), message.value_amount)?;
+
+    let data = get_handler_data(token, amount, relayer.key, &message.handler_message)?;
+
+    let instruction = Instruction { program_id: message.handler, accounts, data };
+
+    // TODO: consider if the message handler requires signed invocation.
+    invoke(&instruction, account_infos)?;
+
+    Ok(())
+}
+
+fn invoke_action_list<'info>(
+    relayer: &AccountInfo<'info>,
+    state: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    message: &[u8],
+    token: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    // Version byte follows the action list tag.
+    let version = message.get(ACTION_LIST_MESSAGE_TAG.len()).copied();
+    if version != Some(ACTION_LIST_MESSAGE_VERSION) {
+        return err!(AcrossPlusError::UnsupportedMessageVersion);
+    }
+
+    // Strict deserialization rejects any trailing bytes after the action list.
+    let message = ActionListMessage::try_from_slice(&message[ACTION_LIST_MESSAGE_HEADER_SIZE..])
+        .map_err(|_| AcrossPlusError::MessageDidNotDeserialize)?;
+
+    if message.actions.is_empty() || message.actions.len() > MAX_MESSAGE_ACTIONS {
+        return err!(AcrossPlusError::InvalidMessageActionCount);
+    }
+    if remaining_accounts.len() != message.accounts.len() {
+        return err!(AcrossPlusError::InvalidMessageKeyLength);
+    }
+
+    // Resolve and validate all actions before invoking any of them.
+    let mut instructions = Vec::with_capacity(message.actions.len());
+    let (mut accounts_offset, mut data_offset) = (0usize, 0usize);
+    for action in message.actions.iter() {
+        let accounts_end = accounts_offset + action.accounts_len as usize;
+        let data_end = data_offset + action.data_len as usize;
+        let program_id_index = action.program_id_index as usize;
+        if accounts_end > message.accounts.len()
+            || data_end > message.data.len()
+            || program_id_index >= message.accounts.len()
+        {
+            return err!(AcrossPlusError::MessageActionOutOfBounds);
+        }
+
+        // Action handlers cannot reenter this program.
+        let program_id = message.accounts[program_id_index];
+        if program_id == crate::ID {
+            return err!(AcrossPlusError::InvalidMessageActionProgram);
+        }
+        if remaining_accounts[program_id_index].key() != program_id {
+            return Err(Error::from(AcrossPlusError::InvalidMessageAccountKey)
+                .with_pubkeys((remaining_accounts[program_id_index].key(), program_id)));
+        }
+        if !remaining_accounts[program_id_index].executable {
+            return err!(AcrossPlusError::MessageHandlerNotExecutable);
+        }
+
+        let accounts = get_message_account_metas(
+            &remaining_accounts[accounts_offset..accounts_end],
+            &message.accounts[accounts_offset..accounts_end],
+            action.read_only_len,
+            state,
+        )?;
+        let data = get_handler_data(token, amount, relayer.key, &message.data[data_offset..data_end])?;
+        instructions.push(Instruction { program_id, accounts, data });
+
+        accounts_offset = accounts_end;
+        data_offset = data_end;
+    }
+    if data_offset != message.data.len() {
+        return err!(AcrossPlusError::MessageActionOutOfBounds);
+    }
+
+    // Value amount is pre-transferred to the first account of the first action.
+    let value_recipient = match message.actions[0].accounts_len {
+        0 => None,
+        _ => remaining_accounts.first(),
+    };
+    transfer_message_value(relayer, value_recipient, message.value_amount)?;
+
+    // Log the remaining compute units before each action so that clients can budget the individual handlers.
+    for (index, instruction) in instructions.iter().enumerate() {
+        msg!("Invoking message action {}", index);
+        sol_log_compute_units();
+        invoke(instruction, remaining_accounts)?;
+    }
+
+    Ok(())
+}
+
+fn get_message_account_metas(
+    account_infos: &[AccountInfo],
+    message_accounts: &[Pubkey],
+    read_only_len: u8,
+    state: &Pubkey,
+) -> Result<Vec<AccountMeta>> {
+    let message_accounts_len = message_accounts.len();
+    if (read_only_len as usize) > message_accounts_len {
+        return err!(AcrossPlusError::InvalidReadOnlyKeyLength);
+    }
+
     let mut accounts = Vec::with_capacity(message_accounts_len);
-    for (i, message_account_key) in message.accounts.into_iter().enumerate() {
+    for (i, &message_account_key) in message_accounts.iter().enumerate() {
         if account_infos[i].key() != message_account_key {
             return Err(Error::from(AcrossPlusError::InvalidMessageAccountKey)
                 .with_pubkeys((account_infos[i].key(), message_account_key)));
@@ -54,12 +206,16 @@ pub fn invoke_handler<'info>(
         // privileges. Though it is unlikely that any practical application would require this.
         // We also explicitly disable all signer privileges for all the accounts to protect the relayer from being
         // drained of funds in the inner instructions.
-        match i < message_accounts_len - (message.read_only_len as usize) {
+        match i < message_accounts_len - (read_only_len as usize) {
             true => {
                 if !account_infos[i].is_writable {
                     return Err(Error::from(AcrossPlusError::NotWritableMessageAccountKey)
                         .with_account_name(format!("{}", message_account_key)));
                 }
+                if is_spoke_owned_account(&account_infos[i], state) {
+                    return Err(Error::from(AcrossPlusError::ForbiddenHandlerAccount)
+                        .with_account_name(format!("{}", message_account_key)));
+                }
                 accounts.push(AccountMeta::new(message_account_key, false));
             }
             false => {
@@ -72,31 +228,88 @@ pub fn invoke_handler<'info>(
         }
     }
 
-    // Transfer value amount from the relayer to the first account in the message accounts.
-    // Note that the depositor is responsible to make sure that after invoking the handler the recipient account will
-    // not hold any balance that is below its rent-exempt threshold, otherwise the fill would fail.
-    if message.value_amount > 0 {
-        let recipient_account = account_infos.first().ok_or(AcrossPlusError::MissingValueRecipientKey)?;
-        let transfer_ix = system_instruction::transfer(&relayer.key(), &recipient_account.key(), message.value_amount);
-        invoke(&transfer_ix, &[relayer.to_account_info(), recipient_account.to_account_info()])?;
-    }
+    Ok(accounts)
+}
 
-    // The data will hold the handler ix discriminator and raw handler message bytes (including 4 bytes for the length).
-    let mut data = Vec::with_capacity(DISCRIMINATOR_SIZE + 4 + message.handler_message.len());
-    data.extend_from_slice(&HANDLE_V3_ACROSS_MESSAGE_DISCRIMINATOR);
-    AnchorSerialize::serialize(&message.handler_message, &mut data)?;
+// Accounts owned by this program (State, root bundles, fill statuses and other PDAs) and token accounts held by the
+// state (vaults, escrows and fee vaults) cannot be passed as writable to message handlers. Read-only inclusion is
+// allowed.
+fn is_spoke_owned_account(account_info: &AccountInfo, state: &Pubkey) -> bool {
+    if account_info.owner == &crate::ID {
+        return true;
+    }
+    if account_info.owner != &token::ID && account_info.owner != &token_2022::ID {
+        return false;
+    }
 
-    let instruction = Instruction { program_id: message.handler, accounts, data };
+    // Mints and other token program accounts do not deserialize as token accounts.
+    let data = match account_info.try_borrow_data() {
+        Ok(data) => data,
+        Err(_) => return true,
+    };
+    TokenAccount::try_deserialize(&mut &data[..]).is_ok_and(|token_account| token_account.owner == *state)
+}
 
-    // TODO: consider if the message handler requires signed invocation.
-    invoke(&instruction, account_infos)?;
+// Transfer value amount from the relayer to the first account in the message accounts.
+// Note that the depositor is responsible to make sure that after invoking the handler the recipient account will
+// not hold any balance that is below its rent-exempt threshold, otherwise the fill would fail.
+fn transfer_message_value<'info>(
+    relayer: &AccountInfo<'info>,
+    recipient_account: Option<&AccountInfo<'info>>,
+    value_amount: u64,
+) -> Result<()> {
+    if value_amount > 0 {
+        let recipient_account = recipient_account.ok_or(AcrossPlusError::MissingValueRecipientKey)?;
+        let transfer_ix = system_instruction::transfer(&relayer.key(), &recipient_account.key(), value_amount);
+        invoke(&transfer_ix, &[relayer.to_account_info(), recipient_account.to_account_info()])?;
+    }
 
     Ok(())
 }
 
+// The data will hold the handler ix discriminator, token, amount, relayer and raw handler message bytes (including 4
+// bytes for the length).
+fn get_handler_data(token: &Pubkey, amount: u64, relayer: &Pubkey, handler_message: &[u8]) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(DISCRIMINATOR_SIZE + 32 + 8 + 32 + 4 + handler_message.len());
+    data.extend_from_slice(&HANDLE_V3_ACROSS_MESSAGE_DISCRIMINATOR);
+    AnchorSerialize::serialize(&(*token, amount, *relayer), &mut data)?;
+    AnchorSerialize::serialize(handler_message, &mut data)?;
+
+    Ok(data)
+}
+
 pub fn hash_non_empty_message(message: &Vec<u8>) -> [u8; 32] {
     match message.len() {
         0 => [0u8; 32],
         _ => keccak::hash(message).to_bytes(),
     }
 }
+
+// Splits the message emitted in deposit events into its inline bytes and the hash of messages too large to inline.
+pub fn get_event_message(message: Vec<u8>) -> (Vec<u8>, Option<[u8; 32]>) {
+    match message.len() {
+        0..=MAX_INLINE_EVENT_MESSAGE_SIZE => (message, None),
+        _ => (Vec::new(), Some(keccak::hash(&message).to_bytes())),
+    }
+}
+
+// Validates non-empty message against the schema registered by the recipient. The schema PDA is always passed at its
+// derived address, so that callers cannot skip validation, and recipients without a registered schema accept any
+// message. This only rejects obvious garbage early and does not guarantee that the recipient handler would
+// successfully decode the message.
+pub fn validate_message_schema(message: &[u8], message_schema: &AccountInfo) -> Result<()> {
+    if message.is_empty() || message_schema.owner != &crate::ID || message_schema.data_is_empty() {
+        return Ok(());
+    }
+    let message_schema = MessageSchema::try_deserialize(&mut &message_schema.try_borrow_data()?[..])?;
+
+    let message_size = message.len() as u64;
+    if message_size < message_schema.min_message_size as u64
+        || message_size > message_schema.max_message_size as u64
+        || message[..4] != message_schema.schema_tag
+    {
+        return err!(AcrossPlusError::MessageSchemaMismatch);
+    }
+
+    Ok(())
+}
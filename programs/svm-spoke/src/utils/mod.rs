@@ -1,15 +1,81 @@
+pub mod admin_message_utils;
+pub mod admin_nonce_utils;
+pub mod allowlist_utils;
 pub mod bitmap_utils;
 pub mod cctp_utils;
+pub mod chain_alias_utils;
+pub mod claim_liability_utils;
+pub mod compact_relay_data_utils;
+pub mod compliance_utils;
+pub mod delivery_utils;
 pub mod deposit_utils;
+pub mod ed25519_utils;
+pub mod encoding_utils;
+pub mod event_commitment_utils;
+pub mod event_forwarder_utils;
+pub mod event_size_utils;
+pub mod execution_approval_utils;
+pub mod fee_utils;
+pub mod fill_index_utils;
+pub mod fill_status_utils;
+pub mod fill_volume_utils;
+pub mod lamport_utils;
+pub mod liquidity_pool_utils;
+pub mod lookup_table_utils;
+pub mod math_utils;
+pub mod memo_utils;
 pub mod merkle_proof_utils;
 pub mod message_utils;
+pub mod payout_utils;
+pub mod pda_utils;
+pub mod relayer_stats_utils;
+pub mod repayment_utils;
+pub mod secp256k1_utils;
+#[cfg(feature = "serde")]
+pub mod serde_utils;
+pub mod signature_utils;
+pub mod slow_fill_liability_utils;
 pub mod testable_utils;
+pub mod token_program_utils;
 pub mod transfer_utils;
+pub mod versioned_args_utils;
 
+pub use admin_message_utils::*;
+pub use admin_nonce_utils::*;
+pub use allowlist_utils::*;
 pub use bitmap_utils::*;
 pub use cctp_utils::*;
+pub use chain_alias_utils::*;
+pub use claim_liability_utils::*;
+pub use compact_relay_data_utils::*;
+pub use compliance_utils::*;
+pub use delivery_utils::*;
 pub use deposit_utils::*;
+pub use ed25519_utils::*;
+pub use encoding_utils::*;
+pub use event_commitment_utils::*;
+pub use event_forwarder_utils::*;
+pub use event_size_utils::*;
+pub use execution_approval_utils::*;
+pub use fee_utils::*;
+pub use fill_index_utils::*;
+pub use fill_status_utils::*;
+pub use fill_volume_utils::*;
+pub use lamport_utils::*;
+pub use liquidity_pool_utils::*;
+pub use lookup_table_utils::*;
+pub use math_utils::*;
+pub use memo_utils::*;
 pub use merkle_proof_utils::*;
 pub use message_utils::*;
+pub use payout_utils::*;
+pub use pda_utils::*;
+pub use relayer_stats_utils::*;
+pub use repayment_utils::*;
+pub use secp256k1_utils::*;
+pub use signature_utils::*;
+pub use slow_fill_liability_utils::*;
 pub use testable_utils::*;
+pub use token_program_utils::*;
 pub use transfer_utils::*;
+pub use versioned_args_utils::*;
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    error::SvmError,
+    state::{RateLimit, State},
+    utils::{get_current_time, transfer_from_vault, VaultSigner},
+};
+
+// Debits an outflow of the mint from its vault against the rate limit of the mint. Once vault outflow rate limits are
+// enabled every mint paying out of its vault must have its rate limit PDA configured by the owner.
+pub fn record_vault_outflow(state: &State, rate_limit: &mut Option<Account<RateLimit>>, amount: u64) -> Result<()> {
+    if !state.vault_outflow_rate_limits_enabled {
+        return Ok(());
+    }
+
+    let rate_limit = rate_limit.as_mut().ok_or(SvmError::MissingVaultOutflowRateLimit)?;
+
+    rate_limit.record_outflow(get_current_time(state)?, amount)
+}
+
+// Shared transfer of pooled funds out of the vault, so that no payout can bypass the vault outflow rate limit.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_from_vault<'info>(
+    vault: &impl ToAccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+    state: &Account<'info, State>,
+    vault_signer: &VaultSigner<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    rate_limit: &mut Option<Account<'info, RateLimit>>,
+) -> Result<()> {
+    record_vault_outflow(state, rate_limit, amount)?;
+
+    transfer_from_vault(vault, to, amount, vault_signer, mint, token_program)
+}
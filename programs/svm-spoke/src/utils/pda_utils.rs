@@ -0,0 +1,61 @@
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer},
+};
+
+// Derives the PDA of this program from its seeds and a bump stored at account creation. Unlike find_program_address,
+// which retries create_program_address for every bump from 255 down to the canonical one, this costs a single call.
+// Callers must only pass bumps recorded from Anchor's canonical bump derivation.
+pub fn create_pda_with_bump(seeds: &[&[u8]], bump: u8) -> Result<Pubkey> {
+    let bump_seed = [bump];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump_seed);
+
+    Pubkey::create_program_address(&seeds_with_bump, &crate::ID).map_err(|_| Error::from(ErrorCode::ConstraintSeeds))
+}
+
+// Anchor's event_cpi signs the self-CPI of emitted events with this PDA, passed as the only account of the inner
+// instruction that indexers filter events by. Returned by the get_event_authority view, so that clients don't need to
+// hardcode the Anchor seed.
+pub fn derive_event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &crate::ID).0
+}
+
+// Fill status PDA of a relay, seeds ["fills", relay_hash]. Takes the program ID so that composing programs can derive
+// the PDAs of any deployment of the spoke, not only the one this crate was built with.
+pub fn derive_fill_status_pda(relay_hash: &[u8; 32], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fills", relay_hash.as_ref()], program_id)
+}
+
+// Creates a rent exempt PDA of this program the same way as Anchor's init constraint, for PDAs passed as remaining
+// accounts. Addresses that were sent lamports beforehand are topped up, so that they can't block the creation, while
+// accounts that already exist fail the allocation.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let signer = &[signer_seeds];
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+
+    if account.lamports() == 0 {
+        let create_accounts = CreateAccount { from: payer.clone(), to: account.clone() };
+        let cpi_context = CpiContext::new_with_signer(system_program.clone(), create_accounts, signer);
+        return create_account(cpi_context, rent_exempt_lamports, space as u64, &crate::ID);
+    }
+
+    let required_lamports = rent_exempt_lamports.saturating_sub(account.lamports());
+    if required_lamports > 0 {
+        let transfer_accounts = Transfer { from: payer.clone(), to: account.clone() };
+        transfer(CpiContext::new(system_program.clone(), transfer_accounts), required_lamports)?;
+    }
+
+    let allocate_accounts = Allocate { account_to_allocate: account.clone() };
+    allocate(CpiContext::new_with_signer(system_program.clone(), allocate_accounts, signer), space as u64)?;
+
+    let assign_accounts = Assign { account_to_assign: account.clone() };
+    assign(CpiContext::new_with_signer(system_program.clone(), assign_accounts, signer), &crate::ID)
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    event::RelayerStatsThresholdCrossed,
+    state::{RelayerStats, State},
+};
+
+// Records the outcome of a deposit in the stats of its exclusive relayer when their RelayerStats PDA is passed. Relays
+// without an exclusive relayer are never attributed. This never fails, so that the stats can't block fills or slow fill
+// requests. Returns the event to emit when the outcome made the lapse ratio cross the state threshold.
+pub fn record_relayer_outcome(
+    state: &State,
+    relayer_stats: &mut Option<Account<RelayerStats>>,
+    exclusive_relayer: &Pubkey,
+    lapse: bool,
+) -> Option<RelayerStatsThresholdCrossed> {
+    let relayer_stats = relayer_stats.as_mut()?;
+    if *exclusive_relayer == Pubkey::default() {
+        return None;
+    }
+
+    let threshold_bps = state.relayer_lapse_threshold_bps;
+    let was_above_threshold = relayer_stats.lapse_ratio_bps() > threshold_bps as u64;
+    match lapse {
+        true => relayer_stats.record_lapse(*exclusive_relayer),
+        false => relayer_stats.record_fill_in_window(*exclusive_relayer),
+    }
+    let lapse_ratio_bps = relayer_stats.lapse_ratio_bps();
+    let above_threshold = lapse_ratio_bps > threshold_bps as u64;
+
+    (threshold_bps != 0 && above_threshold != was_above_threshold).then(|| RelayerStatsThresholdCrossed {
+        relayer: *exclusive_relayer,
+        fills_in_window: relayer_stats.fills_in_window,
+        lapses: relayer_stats.lapses,
+        lapse_ratio_bps,
+        threshold_bps,
+        above_threshold,
+    })
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::{CommonError, SvmError},
+    state::State,
+    utils::is_evm_address,
+};
+
+// Repayment addresses are bytes32 like on the EVM spoke pools, holding zero padded 20 byte addresses for EVM repayment
+// chains. Refunds on this chain go to the ATA of the repayment address, or accrue to its claim account while the ATA
+// does not exist, so the address must be a pubkey that can hold tokens here. Repayment chains without a spoke pool are
+// not known to this program and are left to the dataworker, which refunds such fills on the origin chain.
+pub fn validate_repayment(state: &State, repayment_chain_id: u64, repayment_address: &Pubkey) -> Result<()> {
+    if repayment_chain_id == 0 {
+        return err!(CommonError::InvalidChainId);
+    }
+    if *repayment_address == Pubkey::default()
+        || (repayment_chain_id == state.chain_id && is_evm_address(repayment_address))
+    {
+        return err!(SvmError::InvalidRepaymentAddress);
+    }
+
+    Ok(())
+}
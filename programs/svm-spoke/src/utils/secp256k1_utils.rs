@@ -0,0 +1,50 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{keccak, secp256k1_recover::secp256k1_recover},
+};
+
+use crate::error::SvmError;
+
+// Upper bound of the s component of accepted signatures, as each signature with s above half the curve order has an
+// equally valid counterpart with s below it.
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x5d, 0x57, 0x6e,
+    0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+// EVM addresses are stored in 32 byte address fields left padded with 12 zero bytes.
+pub fn is_evm_address(address: &Pubkey) -> bool {
+    address.as_ref()[..12].iter().all(|byte| *byte == 0)
+}
+
+// Verifies a 65 byte r || s || v secp256k1 signature over the message hash by recovering the signer public key and
+// comparing its EVM address with the one encoded in the expected signer. Both 0/1 and 27/28 recovery ids are accepted.
+pub fn verify_secp256k1_signature(
+    signer: &Pubkey,
+    message_hash: &[u8; 32],
+    signature: &[u8],
+    invalid_signature_error: SvmError,
+) -> Result<()> {
+    match recover_evm_address(message_hash, signature) {
+        Some(address) if is_evm_address(signer) && address == signer.as_ref()[12..] => Ok(()),
+        _ => Err(invalid_signature_error.into()),
+    }
+}
+
+fn recover_evm_address(message_hash: &[u8; 32], signature: &[u8]) -> Option<[u8; 20]> {
+    if signature.len() != 65 || signature[32..64] > SECP256K1_HALF_ORDER[..] {
+        return None;
+    }
+
+    let recovery_id = match signature[64] {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        _ => return None,
+    };
+    let public_key = secp256k1_recover(message_hash, recovery_id, &signature[..64]).ok()?;
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&keccak::hash(&public_key.to_bytes()).to_bytes()[12..]);
+
+    Some(address)
+}
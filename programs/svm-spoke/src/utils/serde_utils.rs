@@ -0,0 +1,186 @@
+// JSON representations of program types matching the TypeScript SDK: camelCase field names, pubkeys as base58, u64
+// amounts as decimal strings and bytes as 0x prefixed hex. Only compiled with the off-chain serde feature.
+use std::{result::Result, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{event::FillType, utils::encode_hex};
+
+pub mod pubkey {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Pubkey::from_str(&value).map_err(D::Error::custom)
+    }
+}
+
+pub mod option_pubkey {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Pubkey>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|value| value.to_string()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+        value.map(|value| Pubkey::from_str(&value).map_err(D::Error::custom)).transpose()
+    }
+}
+
+pub mod pubkey_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter().map(|value| value.to_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Pubkey>, D::Error> {
+        let value = Vec::<String>::deserialize(deserializer)?;
+        value.iter().map(|value| Pubkey::from_str(value).map_err(D::Error::custom)).collect()
+    }
+}
+
+// u64 values are strings as they do not fit the JavaScript number type (the SDK represents them as BN).
+pub mod amount {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+pub mod option_amount {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|value| value.to_string()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+        value.map(|value| value.parse().map_err(D::Error::custom)).transpose()
+    }
+}
+
+pub mod amount_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[u64], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter().map(|value| value.to_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u64>, D::Error> {
+        let value = Vec::<String>::deserialize(deserializer)?;
+        value.iter().map(|value| value.parse().map_err(D::Error::custom)).collect()
+    }
+}
+
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_hex(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        decode_hex(&value).map_err(D::Error::custom)
+    }
+}
+
+pub mod bytes32 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_hex(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let bytes = decode_hex(&value).map_err(D::Error::custom)?;
+        bytes.try_into().map_err(|_| D::Error::custom("expected 32 bytes"))
+    }
+}
+
+pub mod option_bytes32 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<[u8; 32]>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|value| encode_hex(&value)).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<[u8; 32]>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+        value
+            .map(|value| {
+                let bytes = decode_hex(&value).map_err(D::Error::custom)?;
+                bytes.try_into().map_err(|_| D::Error::custom("expected 32 bytes"))
+            })
+            .transpose()
+    }
+}
+
+// Only accepts the lowercase 0x prefixed encoding produced by encode_hex, so that every value has a single
+// representation.
+fn decode_hex(hex: &str) -> Result<Vec<u8>, &'static str> {
+    let digits = hex.strip_prefix("0x").ok_or("expected 0x prefixed hex")?;
+    if digits.len() % 2 != 0 {
+        return Err("expected an even number of hex digits");
+    }
+    let nibble = |digit: u8| -> Result<u8, &'static str> {
+        match digit {
+            b'0'..=b'9' => Ok(digit - b'0'),
+            b'a'..=b'f' => Ok(digit - b'a' + 10),
+            _ => Err("expected lowercase hex digits"),
+        }
+    };
+    digits.as_bytes().chunks(2).map(|pair| Ok((nibble(pair[0])? << 4) | nibble(pair[1])?)).collect()
+}
+
+// Anchor TypeScript clients represent unit enum variants as an object keyed by the camelCase variant name.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct EmptyVariant {}
+
+// Keys by the camelCase FillType variant names, while the variants themselves drop their shared suffix.
+#[derive(Serialize, Deserialize)]
+enum FillTypeJson {
+    #[serde(rename = "fastFill")]
+    Fast(EmptyVariant),
+    #[serde(rename = "replacedSlowFill")]
+    ReplacedSlow(EmptyVariant),
+    #[serde(rename = "slowFill")]
+    Slow(EmptyVariant),
+}
+
+impl Serialize for FillType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = match self {
+            FillType::FastFill => FillTypeJson::Fast(EmptyVariant {}),
+            FillType::ReplacedSlowFill => FillTypeJson::ReplacedSlow(EmptyVariant {}),
+            FillType::SlowFill => FillTypeJson::Slow(EmptyVariant {}),
+        };
+        value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FillType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match FillTypeJson::deserialize(deserializer)? {
+            FillTypeJson::Fast(_) => FillType::FastFill,
+            FillTypeJson::ReplacedSlow(_) => FillType::ReplacedSlowFill,
+            FillTypeJson::Slow(_) => FillType::SlowFill,
+        })
+    }
+}
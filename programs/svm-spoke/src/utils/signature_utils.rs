@@ -0,0 +1,57 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+use crate::{
+    error::{CommonError, SvmError},
+    state::UsedSignature,
+    utils::{is_evm_address, verify_ed25519_signature, verify_secp256k1_signature},
+};
+
+// Used signature PDAs are keyed by the first 16 bytes of the signature hash, which keeps the seeds short while still
+// being collision resistant for the signatures accepted within a retention window.
+pub fn get_used_signature_key(signature: &[u8]) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    key.copy_from_slice(&keccak::hash(signature).to_bytes()[..16]);
+    key
+}
+
+// Verifies the signature over the message and consumes it in its used signature PDA, so that the same signed payload
+// can't be submitted twice even if the state consuming it is reset (e.g. closed fill status PDA). EVM signers sign the
+// 32 byte message hash with secp256k1, while Solana signers sign the message with Ed25519 that is verified by the
+// Ed25519 program instruction preceding this one.
+//
+// Signed payloads are only accepted until expires_at, which is the fill deadline of the signed relay for all current
+// flows. This is what allows closing the used signature PDA after it: a reuse of the signature after closure is
+// rejected as expired.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_and_consume_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    signature: &[u8],
+    invalid_signature_error: SvmError,
+    used_signature: &mut UsedSignature,
+    expires_at: u32,
+    current_time: u32,
+    payer: &Pubkey,
+) -> Result<()> {
+    if used_signature.payer != Pubkey::default() {
+        return err!(SvmError::SignatureAlreadyUsed);
+    }
+
+    if expires_at < current_time {
+        return err!(CommonError::ExpiredFillDeadline);
+    }
+
+    if is_evm_address(signer) {
+        let message_hash = <&[u8; 32]>::try_from(message).map_err(|_| invalid_signature_error)?;
+        verify_secp256k1_signature(signer, message_hash, signature, invalid_signature_error)?;
+    } else {
+        let signature = <&[u8; 64]>::try_from(signature).map_err(|_| invalid_signature_error)?;
+        verify_ed25519_signature(instructions_sysvar, signer, message, signature, invalid_signature_error)?;
+    }
+
+    used_signature.expires_at = expires_at;
+    used_signature.payer = *payer;
+
+    Ok(())
+}
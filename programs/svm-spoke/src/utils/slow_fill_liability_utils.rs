@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{common::V3RelayData, error::SvmError, event::SlowFillLiabilityChanged, state::SlowFillLiability};
+
+// Slow fill liability PDAs are only created by slow fill requests, so other instructions take them as unchecked accounts
+// at the derived address and treat missing ones as nothing pending, e.g. for mints without slow fill requests since
+// liabilities are tracked.
+fn load_slow_fill_liability(account: &AccountInfo) -> Result<Option<SlowFillLiability>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(SlowFillLiability::try_deserialize(&mut &account.try_borrow_data()?[..])?))
+}
+
+pub fn get_pending_slow_fill_amount(account: &AccountInfo) -> Result<u64> {
+    Ok(load_slow_fill_liability(account)?.map_or(0, |liability| liability.pending_slow_fill_amount))
+}
+
+pub fn record_slow_fill_liability(
+    liability: &mut SlowFillLiability,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<SlowFillLiabilityChanged> {
+    let previous_amount = liability.pending_slow_fill_amount;
+    liability.record_request(amount)?;
+
+    Ok(SlowFillLiabilityChanged { mint, previous_amount, pending_slow_fill_amount: liability.pending_slow_fill_amount })
+}
+
+// Returns the event to emit when the liability PDA exists, the caller must pass it as writable.
+pub fn release_slow_fill_liability(
+    account: &AccountInfo,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<Option<SlowFillLiabilityChanged>> {
+    let Some(mut liability) = load_slow_fill_liability(account)? else {
+        return Ok(None);
+    };
+
+    let previous_amount = liability.pending_slow_fill_amount;
+    liability.release(amount);
+    liability.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    Ok(Some(SlowFillLiabilityChanged {
+        mint,
+        previous_amount,
+        pending_slow_fill_amount: liability.pending_slow_fill_amount,
+    }))
+}
+
+// Releases the output amount reserved by the request of a slow fill that can no longer be executed, as it has been
+// replaced by a fast fill or expired.
+pub fn release_requested_slow_fill(
+    slow_fill_liability: &Option<UncheckedAccount>,
+    relay_data: &V3RelayData,
+) -> Result<Option<SlowFillLiabilityChanged>> {
+    let account = slow_fill_liability.as_ref().ok_or(SvmError::MissingSlowFillLiability)?;
+    release_slow_fill_liability(account, relay_data.output_token, relay_data.output_amount)
+}
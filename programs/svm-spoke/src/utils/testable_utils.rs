@@ -1,29 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::State;
-
-#[derive(Accounts)]
-pub struct SetCurrentTime<'info> {
-    #[account(mut, seeds = [b"state", state.seed.to_le_bytes().as_ref()], bump)]
-    pub state: Account<'info, State>,
-
-    pub signer: Signer<'info>,
-}
-
-pub fn set_current_time(ctx: Context<SetCurrentTime>, _new_time: u32) -> Result<()> {
-    let _state = &mut ctx.accounts.state;
-
-    #[cfg(not(feature = "test"))]
-    {
-        err!(crate::error::SvmError::CannotSetCurrentTime)
-    }
-
-    #[cfg(feature = "test")]
-    {
-        _state.current_time = _new_time;
-        Ok(())
-    }
-}
+use crate::{constants::CURRENT_TIME_MAX_SKEW, state::State};
 
 pub fn initialize_current_time(_state: &mut State) -> Result<()> {
     #[cfg(feature = "test")]
@@ -42,13 +19,27 @@ pub fn get_current_time(_state: &State) -> Result<u32> {
 
     #[cfg(feature = "test")]
     {
+        #[cfg(feature = "debug-logs")]
+        {
+            let clock_time = Clock::get()?.unix_timestamp as u32;
+            if is_current_time_skewed(_state.current_time, clock_time) {
+                msg!("Current time override {} diverges from Clock time {}", _state.current_time, clock_time);
+            }
+        }
+
         Ok(_state.current_time)
     }
 }
 
+// Tests setting the current time override far from the Clock sysvar see time based checks that don't match the
+// slots, epochs and blockhash expiries still following the Clock.
+pub fn is_current_time_skewed(current_time: u32, clock_time: u32) -> bool {
+    current_time.abs_diff(clock_time) > CURRENT_TIME_MAX_SKEW
+}
+
 pub fn set_seed(_state: &mut State, _seed: u64) -> Result<()> {
-    // Seed should only be used in tests to enable fresh state between deployments. In production always set to 0.
-    #[cfg(not(feature = "test"))]
+    // Seed should only be used in tests to enable fresh state between deployments. In non-test builds always 0.
+    #[cfg(any(feature = "single-state", not(feature = "test")))]
     if _seed != 0 {
         return err!(crate::error::SvmError::InvalidProductionSeed);
     }
@@ -0,0 +1,101 @@
+use anchor_lang::{error::ErrorCode, prelude::*, solana_program::program_option::COption};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::SvmError, state::Vault};
+
+// ATA addresses differ by the token program used in their derivation, so token accounts are only resolved against
+// the program actually owning the mint. A client passing the other canonical token program would otherwise direct
+// transfers at an ATA the authority never controls under the expected program.
+pub fn is_canonical_token_program(mint: &InterfaceAccount<Mint>, token_program: &Interface<TokenInterface>) -> bool {
+    mint.to_account_info().owner == &token_program.key()
+}
+
+// Vaults created by create_vault record the token program of their mint, so deposits and fills passing another token
+// program fail here instead of in the token program CPI. Mints with vaults created before the registry, i.e. without a
+// vault PDA, are not checked.
+pub fn is_registered_token_program(vault_registry: &AccountInfo, token_program: &Pubkey) -> bool {
+    if vault_registry.owner != &crate::ID {
+        return true;
+    }
+
+    let Ok(data) = vault_registry.try_borrow_data() else {
+        return false;
+    };
+    match Vault::try_deserialize(&mut &data[..]) {
+        Ok(vault) => vault.token_program == *token_program,
+        Err(_) => false,
+    }
+}
+
+// Derives the ATA of the authority with the token program owning the mint, regardless of the passed token program.
+pub fn get_canonical_ata(authority: &Pubkey, mint: &AccountInfo) -> Pubkey {
+    get_associated_token_address_with_program_id(authority, mint.key, mint.owner)
+}
+
+// Vaults are the canonical ATAs of the vault authority PDA, so that the state seeds that also control the configuration
+// never move their funds. Vaults of the state PDA created before the vault authority are only emptied into the vault
+// authority ATA by migrate_vault_authority.
+pub fn get_vault_authority(state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault_authority", state.as_ref()], &crate::ID).0
+}
+
+// Migrated state vaults are kept open with the vault authority PDA as their close authority, so that the canonical ATA
+// of the state can't be recreated and is told apart from the state vaults of mints that were never migrated.
+pub fn is_migrated_state_vault(token_account: &TokenAccount, state: &Pubkey) -> bool {
+    match token_account.close_authority {
+        COption::Some(close_authority) => token_account.owner == *state && close_authority == get_vault_authority(state),
+        COption::None => false,
+    }
+}
+
+pub fn is_vault(vault: &InterfaceAccount<TokenAccount>, state: &Pubkey, mint: &AccountInfo) -> bool {
+    let vault_authority = get_vault_authority(state);
+    vault.mint == mint.key()
+        && vault.owner == vault_authority
+        && vault.key() == get_canonical_ata(&vault_authority, mint)
+}
+
+pub fn is_vault_address(vault: &Pubkey, state: &Pubkey, mint: &AccountInfo) -> bool {
+    *vault == get_canonical_ata(&get_vault_authority(state), mint)
+}
+
+// Vaults are created when enabling routes, so a leaf paying out a mint whose vault was never created or got closed
+// is rejected with an error naming the mint instead of failing to load the vault. The vault must still be the
+// canonical ATA of the vault authority, so mint and authority checks only catch accounts not created by the ATA
+// program.
+pub fn check_vault(vault: &AccountInfo, state: &Pubkey, mint: &AccountInfo) -> Result<()> {
+    if !is_vault_address(&vault.key(), state, mint) {
+        return Err(Error::from(ErrorCode::ConstraintAssociated)
+            .with_account_name("vault")
+            .with_pubkeys((vault.key(), get_canonical_ata(&get_vault_authority(state), mint))));
+    }
+
+    if !is_vault_initialized(vault, state, mint) {
+        msg!("Vault missing for mint {}", mint.key);
+        return err!(SvmError::VaultMissingForMint);
+    }
+
+    Ok(())
+}
+
+pub fn is_vault_initialized(vault: &AccountInfo, state: &Pubkey, mint: &AccountInfo) -> bool {
+    if vault.owner != mint.owner {
+        return false;
+    }
+
+    let Ok(data) = vault.try_borrow_data() else {
+        return false;
+    };
+    match TokenAccount::try_deserialize(&mut &data[..]) {
+        Ok(token_account) => {
+            let vault_authority = get_vault_authority(state);
+            token_account.mint == mint.key()
+                && token_account.owner == vault_authority
+                && vault.key() == get_canonical_ata(&vault_authority, mint)
+        }
+        Err(_) => false,
+    }
+}
@@ -1,11 +1,82 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_lang::solana_program::{
+    instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    program_option::COption,
+};
+use anchor_spl::token_interface::{
+    revoke, transfer_checked, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked,
+};
 
-use crate::State;
+use crate::{error::SvmError, State};
+
+// Vault authority PDA signing the outflows of vaults, seeds ["vault_authority", state]. Only built for vaults already
+// checked to be owned by it, as the state seeds sign no vault outflows besides the migration of state vaults.
+pub struct VaultSigner<'info> {
+    pub authority: AccountInfo<'info>,
+    state: [u8; 32],
+    bump: [u8; 1],
+}
+
+impl<'info> VaultSigner<'info> {
+    pub fn new(
+        state: &Account<'info, State>,
+        vault_authority: &impl ToAccountInfo<'info>,
+        vault_authority_bump: u8,
+    ) -> Self {
+        Self {
+            authority: vault_authority.to_account_info(),
+            state: state.key().to_bytes(),
+            bump: [vault_authority_bump],
+        }
+    }
+
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [b"vault_authority", &self.state, &self.bump]
+    }
+}
+
+pub fn is_vault_frozen<'info>(vault: &impl ToAccountInfo<'info>) -> Result<bool> {
+    Ok(TokenAccount::try_deserialize(&mut &vault.to_account_info().try_borrow_data()?[..])?.is_frozen())
+}
+
+// Vaults of mints with a freeze authority can be frozen, failing their outflows with an opaque token program error.
+pub fn check_vault_not_frozen<'info>(vault: &impl ToAccountInfo<'info>, mint: &Pubkey) -> Result<()> {
+    if is_vault_frozen(vault)? {
+        msg!("Vault frozen for mint {}", mint);
+        return err!(SvmError::VaultFrozen);
+    }
+
+    Ok(())
+}
+
+pub fn transfer_from_vault<'info>(
+    vault: &impl ToAccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+    vault_signer: &VaultSigner<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    check_vault_not_frozen(vault, &mint.key())?;
+
+    let transfer_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.clone(),
+        authority: vault_signer.authority.clone(),
+    };
+
+    let seeds = vault_signer.seeds();
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(token_program.to_account_info(), transfer_accounts, signer_seeds);
+
+    transfer_checked(cpi_context, amount, mint.decimals)
+}
 
 pub fn transfer_from<'info>(
-    from: &InterfaceAccount<'info, TokenAccount>,
-    to: &InterfaceAccount<'info, TokenAccount>,
+    from: &impl ToAccountInfo<'info>,
+    to: &impl ToAccountInfo<'info>,
     amount: u64,
     state: &Account<'info, State>,
     state_bump: u8,
@@ -27,3 +98,27 @@ pub fn transfer_from<'info>(
 
     transfer_checked(cpi_context, amount, mint.decimals)
 }
+
+// Revokes any remaining delegation of the state PDA over the token account. This requires the token account owner to
+// have signed the top level instruction, so the revoke is silently skipped when called via CPI or for other signers.
+pub fn revoke_state_delegation<'info>(
+    from: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    state: &Account<'info, State>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT || from.owner != authority.key() {
+        return Ok(());
+    }
+
+    // Token program clears the delegate when the delegated amount is fully used, so check for the remaining one.
+    from.reload()?;
+    if from.delegate != COption::Some(state.key()) {
+        return Ok(());
+    }
+
+    let revoke_accounts = Revoke { source: from.to_account_info(), authority: authority.to_account_info() };
+    let cpi_context = CpiContext::new(token_program.to_account_info(), revoke_accounts);
+
+    revoke(cpi_context)
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SvmError;
+
+// High-churn instructions take a trailing args_version and extra blob. New optional parameters are added by defining
+// a new version and parsing them from the extra blob, instead of adding new instruction variants.
+pub const ARGS_VERSION_V0: u8 = 0; // No extra parameters, extra blob must be empty.
+pub const ARGS_VERSION_V1: u8 = 1; // Borsh serialized ExtraArgsV1, only supported by fill payouts.
+pub const ARGS_VERSION_V2: u8 = 2; // Borsh serialized ExtraArgsV2, only supported by fast fills.
+pub const ARGS_VERSION_V3: u8 = 3; // Borsh serialized ExtraArgsV3, only supported by fast fills.
+pub const ARGS_VERSION_V4: u8 = 4; // Borsh serialized ExtraArgsV4, only supported by fast fills.
+pub const ARGS_VERSION_V5: u8 = 5; // Borsh serialized ExtraArgsV5, only supported by deposits.
+pub const ARGS_VERSION_V6: u8 = 6; // Borsh serialized ExtraArgsV6, only supported by fast fills.
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExtraArgsV1 {
+    pub memo: Option<Vec<u8>>, // Logged via the SPL Memo program before the payout transfer.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExtraArgsV2 {
+    pub memo: Option<Vec<u8>>, // Logged via the SPL Memo program before the payout transfer.
+    pub create_repayment_token_account: bool, // Idempotently create the repayment address ATA for the output token.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExtraArgsV3 {
+    pub memo: Option<Vec<u8>>, // Logged via the SPL Memo program before the payout transfer.
+    pub create_repayment_token_account: bool, // Idempotently create the repayment address ATA for the output token.
+    pub origin_tx_ref: Option<[u8; 32]>, // Origin chain deposit transaction hash, emitted as is in the fill event.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExtraArgsV4 {
+    pub memo: Option<Vec<u8>>, // Logged via the SPL Memo program before the payout transfer.
+    pub create_repayment_token_account: bool, // Idempotently create the repayment address ATA for the output token.
+    pub origin_tx_ref: Option<[u8; 32]>, // Origin chain deposit transaction hash, emitted as is in the fill event.
+    pub accept_low_fee: bool,  // Proceed with a fill whose implied relayer fee is below the floor of the route.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExtraArgsV5 {
+    pub depositor_nonce: u64, // Must match the next nonce of the depositor nonce PDA, emitted with the deposit.
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExtraArgsV6 {
+    pub memo: Option<Vec<u8>>, // Logged via the SPL Memo program before the payout transfer.
+    pub create_repayment_token_account: bool, // Idempotently create the repayment address ATA for the output token.
+    pub origin_tx_ref: Option<[u8; 32]>, // Origin chain deposit transaction hash, emitted as is in the fill event.
+    pub accept_low_fee: bool,  // Proceed with a fill whose implied relayer fee is below the floor of the route.
+    pub create_recipient_token_account: bool, // Idempotently create the recipient ATA for the output token.
+}
+
+pub enum ExtraArgs {
+    V0,
+    V1(ExtraArgsV1),
+    V2(ExtraArgsV2),
+    V3(ExtraArgsV3),
+    V4(ExtraArgsV4),
+    V5(ExtraArgsV5),
+    V6(ExtraArgsV6),
+}
+
+impl ExtraArgs {
+    pub fn payout_memo(&self) -> Option<&Vec<u8>> {
+        match self {
+            Self::V0 => None,
+            Self::V1(extra_args) => extra_args.memo.as_ref(),
+            Self::V2(extra_args) => extra_args.memo.as_ref(),
+            Self::V3(extra_args) => extra_args.memo.as_ref(),
+            Self::V4(extra_args) => extra_args.memo.as_ref(),
+            Self::V5(_) => None,
+            Self::V6(extra_args) => extra_args.memo.as_ref(),
+        }
+    }
+
+    pub fn create_repayment_token_account(&self) -> bool {
+        match self {
+            Self::V2(extra_args) => extra_args.create_repayment_token_account,
+            Self::V3(extra_args) => extra_args.create_repayment_token_account,
+            Self::V4(extra_args) => extra_args.create_repayment_token_account,
+            Self::V6(extra_args) => extra_args.create_repayment_token_account,
+            _ => false,
+        }
+    }
+
+    // The origin transaction reference is neither hashed nor validated, so an all-zero reference is treated as absent
+    // to give indexers a single representation of relayers not providing it.
+    pub fn origin_tx_ref(&self) -> Option<[u8; 32]> {
+        match self {
+            Self::V3(extra_args) => extra_args.origin_tx_ref.filter(|origin_tx_ref| origin_tx_ref != &[0u8; 32]),
+            Self::V4(extra_args) => extra_args.origin_tx_ref.filter(|origin_tx_ref| origin_tx_ref != &[0u8; 32]),
+            Self::V6(extra_args) => extra_args.origin_tx_ref.filter(|origin_tx_ref| origin_tx_ref != &[0u8; 32]),
+            _ => None,
+        }
+    }
+
+    pub fn accept_low_fee(&self) -> bool {
+        match self {
+            Self::V4(extra_args) => extra_args.accept_low_fee,
+            Self::V6(extra_args) => extra_args.accept_low_fee,
+            _ => false,
+        }
+    }
+
+    pub fn create_recipient_token_account(&self) -> bool {
+        match self {
+            Self::V6(extra_args) => extra_args.create_recipient_token_account,
+            _ => false,
+        }
+    }
+
+    pub fn depositor_nonce(&self) -> Option<u64> {
+        match self {
+            Self::V5(extra_args) => Some(extra_args.depositor_nonce),
+            _ => None,
+        }
+    }
+}
+
+// Unknown versions are rejected rather than ignored, so that a client never has its extra parameters silently dropped
+// by a program that does not understand them yet.
+pub fn parse_extra_args(args_version: u8, extra: &[u8]) -> Result<ExtraArgs> {
+    match args_version {
+        ARGS_VERSION_V0 => {
+            if !extra.is_empty() {
+                return err!(SvmError::InvalidExtraArgs);
+            }
+
+            Ok(ExtraArgs::V0)
+        }
+        ARGS_VERSION_V1 => {
+            let extra_args = ExtraArgsV1::try_from_slice(extra).map_err(|_| SvmError::InvalidExtraArgs)?;
+
+            Ok(ExtraArgs::V1(extra_args))
+        }
+        ARGS_VERSION_V2 => {
+            let extra_args = ExtraArgsV2::try_from_slice(extra).map_err(|_| SvmError::InvalidExtraArgs)?;
+
+            Ok(ExtraArgs::V2(extra_args))
+        }
+        ARGS_VERSION_V3 => {
+            let extra_args = ExtraArgsV3::try_from_slice(extra).map_err(|_| SvmError::InvalidExtraArgs)?;
+
+            Ok(ExtraArgs::V3(extra_args))
+        }
+        ARGS_VERSION_V4 => {
+            let extra_args = ExtraArgsV4::try_from_slice(extra).map_err(|_| SvmError::InvalidExtraArgs)?;
+
+            Ok(ExtraArgs::V4(extra_args))
+        }
+        ARGS_VERSION_V5 => {
+            let extra_args = ExtraArgsV5::try_from_slice(extra).map_err(|_| SvmError::InvalidExtraArgs)?;
+
+            Ok(ExtraArgs::V5(extra_args))
+        }
+        ARGS_VERSION_V6 => {
+            let extra_args = ExtraArgsV6::try_from_slice(extra).map_err(|_| SvmError::InvalidExtraArgs)?;
+
+            Ok(ExtraArgs::V6(extra_args))
+        }
+        _ => err!(SvmError::UnsupportedArgsVersion),
+    }
+}
@@ -0,0 +1,244 @@
+// Checks the checked-in byte dumps of accounts at each of their layout versions (test/svm/fixtures/account_layouts.json)
+// against the current account types, so that layout changes bricking existing deployments fail here. The last dump of
+// each account type is its current layout, and a layout change must append a new dump along with its expected value.
+// Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, AccountSerialize, Space};
+use serde::Deserialize;
+use svm_spoke::{
+    client::{ExecutionApprovalThreshold, FillStatus, FillStatusAccount, RootBundle, Route, SettlementMode, State},
+    constants::{CURRENT_STATE_VERSION, DISCRIMINATOR_SIZE, LEGACY_FILL_STATUS_ACCOUNT_SIZES, LEGACY_ROUTE_SIZE},
+};
+
+const FIXTURE: &str = include_str!("../../../test/svm/fixtures/account_layouts.json");
+
+const FILL_DEADLINE: u32 = 1760400000;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fixture {
+    fill_status_account: Vec<Dump>,
+    route: Vec<Dump>,
+    root_bundle: Vec<Dump>,
+    state: Vec<Dump>,
+}
+
+#[derive(Deserialize)]
+struct Dump(#[serde(with = "svm_spoke::utils::serde_utils::bytes")] Vec<u8>);
+
+fn fixture() -> Fixture {
+    serde_json::from_str(FIXTURE).unwrap()
+}
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+// Current layouts must be sized as the account type and serialize to the dump, followed by the zeroed space that
+// accounts with variable length fields are allocated for.
+fn assert_current_layout<T: AccountSerialize + AccountDeserialize + Space>(dumps: &[Dump], expected: &T, name: &str) {
+    let data = &dumps.last().unwrap().0;
+    assert_eq!(data.len(), DISCRIMINATOR_SIZE + T::INIT_SPACE, "{} layout changed without a new dump", name);
+
+    let serialized = serialize(expected);
+    assert_eq!(serialized, data[..serialized.len()], "{} dump should match the expected value", name);
+    assert!(data[serialized.len()..].iter().all(|&byte| byte == 0), "{} dump should be zero padded", name);
+
+    let decoded = T::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(serialize(&decoded), serialized, "{} dump should round trip", name);
+}
+
+#[test]
+fn fill_status_account_layouts() {
+    let dumps = fixture().fill_status_account;
+    assert_eq!(dumps.len(), LEGACY_FILL_STATUS_ACCOUNT_SIZES.len() + 1, "Each legacy layout should have a dump");
+
+    // Legacy layouts decode with the unrecorded fields zeroed, and migrate_fill_status_account then records them.
+    let expected_migrations = [
+        FillStatusAccount {
+            status: FillStatus::RequestedSlowFill,
+            relayer: key(1),
+            fill_deadline: FILL_DEADLINE,
+            requester: key(1),
+            payer: key(1),
+        },
+        FillStatusAccount {
+            status: FillStatus::Filled,
+            relayer: key(1),
+            fill_deadline: FILL_DEADLINE,
+            requester: Pubkey::default(),
+            payer: key(1),
+        },
+    ];
+    for ((dump, size), expected) in dumps.iter().zip(LEGACY_FILL_STATUS_ACCOUNT_SIZES).zip(&expected_migrations) {
+        assert_eq!(dump.0.len(), size, "Legacy dump should match its layout size");
+        let mut fill_status = FillStatusAccount::try_deserialize_any_layout(&dump.0).unwrap();
+        assert_eq!(fill_status.payer, Pubkey::default(), "Payer was not recorded in legacy layouts");
+        fill_status.migrate_legacy_layout(FILL_DEADLINE);
+        assert_eq!(serialize(&fill_status), serialize(expected), "Migration should match");
+    }
+
+    let expected = FillStatusAccount {
+        status: FillStatus::RequestedSlowFill,
+        relayer: key(1),
+        fill_deadline: FILL_DEADLINE,
+        requester: key(2),
+        payer: key(3),
+    };
+    assert_current_layout(&dumps, &expected, "FillStatusAccount");
+    let decoded = FillStatusAccount::try_deserialize_any_layout(&dumps.last().unwrap().0).unwrap();
+    assert_eq!(serialize(&decoded), serialize(&expected), "Current layout should decode as is");
+}
+
+#[test]
+fn route_layouts() {
+    let expected = Route {
+        enabled: true,
+        expected_decimals: 6,
+        settlement_mode: SettlementMode::BurnFromVault,
+        disable_at: FILL_DEADLINE,
+        decimal_shift: -1,
+        disabled_at: 0,
+        bump: 254,
+        min_relayer_fee_bps: 10,
+        destination_chain_id: 137,
+    };
+    let dumps = fixture().route;
+    assert_current_layout(&dumps, &expected, "Route");
+
+    // The legacy route only tracked whether it was enabled, and migrate_route records the fields it did not.
+    let legacy_dump = &dumps[0].0;
+    assert_eq!(legacy_dump.len(), LEGACY_ROUTE_SIZE, "Dump should have the legacy layout");
+    assert!(Route::try_deserialize(&mut legacy_dump.as_slice()).is_err(), "Legacy layout should need migration");
+    let mut route = Route::try_deserialize_any_layout(legacy_dump).unwrap();
+    route.migrate_legacy_layout(253, 137, 6);
+    let migrated = Route {
+        enabled: true,
+        expected_decimals: 6,
+        settlement_mode: SettlementMode::VaultTransfer,
+        disable_at: 0,
+        decimal_shift: 0,
+        disabled_at: 0,
+        bump: 253,
+        min_relayer_fee_bps: 0,
+        destination_chain_id: 137,
+    };
+    assert_eq!(serialize(&route), serialize(&migrated), "Legacy layout should migrate with defaults");
+}
+
+#[test]
+fn root_bundle_layouts() {
+    let dumps = fixture().root_bundle;
+    let (legacy_dumps, current_dumps) = dumps.split_at(dumps.len() - 1);
+
+    // Legacy root bundles, as relayed and after executing leaves, decode with the appended fields zeroed, and
+    // migrate_root_bundle then records the id.
+    for (dump, claimed_bitmap) in legacy_dumps.iter().zip([vec![], vec![0b101]]) {
+        assert!(RootBundle::is_legacy_layout(&dump.0), "Dump should have the legacy layout");
+        assert!(RootBundle::try_deserialize(&mut dump.0.as_slice()).is_err(), "Legacy layout should need migration");
+        let mut root_bundle = RootBundle::try_deserialize_any_layout(&dump.0).unwrap();
+        root_bundle.id = 7;
+        let expected = RootBundle {
+            relayer_refund_root: [4; 32],
+            slow_relay_root: [5; 32],
+            claimed_bitmap,
+            id: 7,
+            execution_deadline: 0,
+            restricted_executor: None,
+            restricted_until: 0,
+        };
+        assert_eq!(serialize(&root_bundle), serialize(&expected), "Migration should match");
+        let mut migrated = dump.0.clone();
+        migrated.resize(dump.0.len() + RootBundle::APPENDED_FIELDS_SPACE, 0);
+        assert!(!RootBundle::is_legacy_layout(&migrated), "Migrated size should have the current layout");
+    }
+
+    let expected = RootBundle {
+        relayer_refund_root: [4; 32],
+        slow_relay_root: [5; 32],
+        claimed_bitmap: vec![0b101],
+        id: 7,
+        execution_deadline: FILL_DEADLINE,
+        restricted_executor: Some(key(6)),
+        restricted_until: FILL_DEADLINE + 3600,
+    };
+    assert_current_layout(current_dumps, &expected, "RootBundle");
+    assert!(!RootBundle::is_legacy_layout(&current_dumps[0].0), "Current dump should not have the legacy layout");
+}
+
+#[test]
+fn state_layouts() {
+    let expected = State {
+        paused_deposits: false,
+        paused_fills: true,
+        owner: key(10),
+        seed: 42,
+        number_of_deposits: 5,
+        chain_id: 34268394551451,
+        current_time: FILL_DEADLINE,
+        remote_domain: 0,
+        cross_domain_admin: key(11),
+        root_bundle_id: 3,
+        deposit_quote_time_buffer: 3600,
+        fill_deadline_buffer: 21600,
+        winding_down: false,
+        wind_down_time: 0,
+        wind_down_delay: 86400,
+        network: 2,
+        compliance_authority: Some(key(12)),
+        bundle_audits_enabled: true,
+        bundle_audit_max_mints: 4,
+        slow_fill_rate_limit_enabled: true,
+        slow_fill_rate_limit: 5,
+        slow_fill_rate_window: 60,
+        fill_confirmation_oracle: None,
+        dispute_freeze_until: FILL_DEADLINE + 600,
+        protocol_fee_bps: 5,
+        protocol_fee_recipient: Some(key(13)),
+        event_commitments_enabled: true,
+        liquidity_pools_enabled: false,
+        liquidity_pool_fee_bps: 3,
+        slow_fill_grace_seconds: 30,
+        fills_pause_expires_at: 0,
+        fill_index_enabled: true,
+        vault_outflow_rate_limits_enabled: false,
+        event_forwarder: None,
+        event_forwarder_best_effort: false,
+        permissioned_mode: true,
+        strict_slow_fill_requester: false,
+        upgrade_effective_after_slot: Some(123456789),
+        execution_approver: Some(key(14)),
+        execution_approval_validity: 300,
+        execution_approval_thresholds: vec![
+            ExecutionApprovalThreshold { mint: key(15), threshold: 1_000_000 },
+            ExecutionApprovalThreshold { mint: key(16), threshold: 2_000_000 },
+        ],
+        pending_owner: None,
+        snapshot_min_interval: 3600,
+        last_config_snapshot_time: FILL_DEADLINE - 60,
+        admin_messenger: Some(key(17)),
+        params_buffer_ttl: 86400,
+        emergency_executor: None,
+        genesis: true,
+        relayer_lapse_threshold_bps: 500,
+        last_counter_reconciliation_time: FILL_DEADLINE - 120,
+        strict_chain_aliases: true,
+        version: CURRENT_STATE_VERSION,
+        exclusive_slow_fills_disabled: true,
+        canonical: true,
+    };
+    let dumps = fixture().state;
+    assert_current_layout(&dumps, &expected, "State");
+
+    // The baseline layout ends at fill_deadline_buffer, so it must be reallocated by migrate_state.
+    let baseline_dump = &dumps[0].0;
+    assert_eq!(baseline_dump.len(), DISCRIMINATOR_SIZE + 106, "Dump should have the baseline layout");
+    assert!(State::try_deserialize(&mut baseline_dump.as_slice()).is_err(), "Baseline layout should need migration");
+}
@@ -0,0 +1,43 @@
+// Checks the claimed bitmap of root bundles at the byte boundaries of the leaf IDs, so that executed relayer refund
+// leaves are rejected when executed again without marking their neighbours. Run with
+// `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use svm_spoke::utils::{is_claimed, set_claimed};
+
+const LEAF_COUNT: u32 = 17;
+
+#[test]
+fn empty_bitmap_has_no_claimed_leaves() {
+    let claimed_bitmap = Vec::new();
+    assert!((0..LEAF_COUNT).all(|leaf_id| !is_claimed(&claimed_bitmap, leaf_id)), "No leaf should be claimed");
+    assert!(!is_claimed(&claimed_bitmap, u32::MAX), "Leaves past the bitmap should not be claimed");
+}
+
+#[test]
+fn claims_leaves_at_byte_boundaries() {
+    let last_leaf_id = LEAF_COUNT - 1;
+    for leaf_id in [0, 7, 8, last_leaf_id] {
+        let mut claimed_bitmap = Vec::new();
+        set_claimed(&mut claimed_bitmap, leaf_id);
+        let byte_count = leaf_id as usize / 8 + 1;
+        assert_eq!(claimed_bitmap.len(), byte_count, "Bitmap should grow to the byte of leaf {}", leaf_id);
+
+        let claimed: Vec<u32> = (0..LEAF_COUNT).filter(|id| is_claimed(&claimed_bitmap, *id)).collect();
+        assert_eq!(claimed, vec![leaf_id], "Only leaf {} should be claimed", leaf_id);
+    }
+}
+
+#[test]
+fn claims_accumulate_without_shrinking() {
+    let mut claimed_bitmap = Vec::new();
+    set_claimed(&mut claimed_bitmap, LEAF_COUNT - 1);
+    for leaf_id in [8, 7, 0] {
+        set_claimed(&mut claimed_bitmap, leaf_id);
+    }
+    assert_eq!(claimed_bitmap, vec![0b1000_0001, 0b0000_0001, 0b0000_0001], "Lower claims should keep the bitmap size");
+
+    // Claiming again is idempotent, execution rejects it through is_claimed beforehand.
+    set_claimed(&mut claimed_bitmap, 7);
+    assert_eq!(claimed_bitmap, vec![0b1000_0001, 0b0000_0001, 0b0000_0001], "Claiming twice should not change bits");
+}
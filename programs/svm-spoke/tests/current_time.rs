@@ -0,0 +1,54 @@
+// Checks that production builds ignore the current time override of the state, so that a non-zero current_time can
+// never control time based checks on mainnet, and the skew of the override logged in test builds. Run with
+// `cargo test -p svm-spoke --features serde`.
+#![cfg(all(feature = "serde", not(feature = "test")))]
+
+use anchor_lang::{prelude::*, solana_program::program_stubs, AccountDeserialize, Discriminator};
+use svm_spoke::{
+    client::State,
+    constants::CURRENT_TIME_MAX_SKEW,
+    utils::{get_current_time, is_current_time_skewed},
+};
+
+const CLOCK_TIME: i64 = 1_700_000_000;
+
+// Serves the Clock sysvar off-chain, as the default syscall stubs do not support sysvars.
+struct ClockStubs;
+
+impl program_stubs::SyscallStubs for ClockStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock { unix_timestamp: CLOCK_TIME, ..Clock::default() } };
+        anchor_lang::solana_program::entrypoint::SUCCESS
+    }
+}
+
+fn state_with_current_time(current_time: u32) -> State {
+    let mut data = State::DISCRIMINATOR.to_vec();
+    data.resize(8 + State::INIT_SPACE, 0);
+    let mut state = State::try_deserialize(&mut data.as_slice()).unwrap();
+    state.current_time = current_time;
+    state
+}
+
+#[test]
+fn ignores_current_time_override() {
+    program_stubs::set_syscall_stubs(Box::new(ClockStubs));
+
+    assert_eq!(get_current_time(&state_with_current_time(0)).unwrap(), CLOCK_TIME as u32);
+    assert_eq!(
+        get_current_time(&state_with_current_time(42)).unwrap(),
+        CLOCK_TIME as u32,
+        "Current time override must be ignored without the test feature"
+    );
+}
+
+#[test]
+fn detects_current_time_skew() {
+    let clock_time = CLOCK_TIME as u32;
+    assert!(!is_current_time_skewed(clock_time, clock_time));
+    assert!(!is_current_time_skewed(clock_time + CURRENT_TIME_MAX_SKEW, clock_time));
+    assert!(!is_current_time_skewed(clock_time - CURRENT_TIME_MAX_SKEW, clock_time));
+    assert!(is_current_time_skewed(clock_time + CURRENT_TIME_MAX_SKEW + 1, clock_time), "Override ahead of Clock");
+    assert!(is_current_time_skewed(clock_time - CURRENT_TIME_MAX_SKEW - 1, clock_time), "Override behind Clock");
+    assert!(is_current_time_skewed(0, clock_time), "Unset override");
+}
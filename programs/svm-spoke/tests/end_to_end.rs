@@ -0,0 +1,573 @@
+// Walks a single mint through the whole lifecycle of the spoke on a program-test bank: initialize, enable the route,
+// create the vault, deposit, fast fill an inbound relay, request and execute the slow fill of another relay from a
+// locally built root bundle and execute the relayer refund leaf repaying the fast fill. Balances, events, fill statuses
+// and counters are asserted after each step, so that it doubles as the reference flow for new contributors. Run with
+// `cargo test -p svm-spoke --features serde,test`.
+#![cfg(all(feature = "serde", feature = "test"))]
+
+use std::sync::Mutex;
+
+use anchor_lang::{
+    event::EVENT_IX_TAG_LE,
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack, program_stubs, system_instruction,
+        system_program,
+    },
+    AccountDeserialize, Discriminator, InstructionData,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address, spl_associated_token_account},
+    token::{self, spl_token},
+};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use svm_spoke::{
+    accounts,
+    client::{
+        build_deposit_ix, build_execute_slow_relay_leaf_ix, build_fill_ix, build_request_slow_fill_ix,
+        get_fill_status_address, get_state_address, ClaimLiability, DepositArgs, DepositOptions,
+        ExecuteRelayerRefundLeafParams, ExecuteSlowRelayLeafOptions, FillOptions, FillStatus, FillStatusAccount,
+        RelayerRefundLeaf, RequestSlowFillOptions, RootBundle, SlowFillLiability, SpokeConfig, State, V3RelayData,
+        V3SlowFill, VaultLiability,
+    },
+    constants::NETWORK_LOCALNET,
+    event::{
+        EnabledDepositRoute, ExecutedRelayerRefundRoot, FillType, FilledV3Relay, RelayedRootBundle,
+        RequestedV3SlowFill, V3FundsDeposited, VaultCreated,
+    },
+    instruction,
+    utils::{derive_event_authority, get_v3_relay_hash, is_claimed},
+};
+
+const SEED: u64 = 0;
+const CHAIN_ID: u64 = 1234;
+const ORIGIN_CHAIN_ID: u64 = 1;
+const DESTINATION_CHAIN_ID: u64 = 10;
+const DECIMALS: u8 = 6;
+const DEPOSIT_AMOUNT: u64 = 1_000_000;
+const FAST_FILL_AMOUNT: u64 = 500_000;
+const SLOW_FILL_AMOUNT: u64 = 300_000;
+
+// Event CPI data of the instructions processed by the bank, recorded by EventRecorder.
+static EVENTS: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+// Records the events emitted through self CPIs before forwarding all syscalls to the program-test stubs, as native
+// programs don't leave their inner instructions in the transaction metadata.
+struct EventRecorder {
+    stubs: Box<dyn program_stubs::SyscallStubs>,
+}
+
+impl program_stubs::SyscallStubs for EventRecorder {
+    fn sol_log(&self, message: &str) {
+        self.stubs.sol_log(message)
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id == svm_spoke::ID && instruction.data.starts_with(&EVENT_IX_TAG_LE[..]) {
+            EVENTS.lock().unwrap().push(instruction.data[EVENT_IX_TAG_LE.len()..].to_vec());
+        }
+        self.stubs.sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.stubs.sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.stubs.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.stubs.sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.stubs.sol_get_rent_sysvar(var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.stubs.sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.stubs.sol_set_return_data(data)
+    }
+
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.stubs.sol_get_processed_sibling_instruction(index)
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        self.stubs.sol_get_stack_height()
+    }
+}
+
+// Placeholder swapped in to take back the program-test stubs, which EventRecorder then wraps.
+struct NoStubs;
+
+impl program_stubs::SyscallStubs for NoStubs {}
+
+// Anchor entrypoints need the accounts to live as long as their data, which native processors don't guarantee.
+fn process_instruction<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    svm_spoke::entry(program_id, accounts, data)
+}
+
+fn spoke() -> SpokeConfig {
+    SpokeConfig { seed: SEED, chain_id: CHAIN_ID, ..SpokeConfig::default() }
+}
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &svm_spoke::ID).0
+}
+
+fn find_event<T: Discriminator + AnchorDeserialize>(events: &[Vec<u8>]) -> T {
+    let data = events.iter().find(|data| data.starts_with(&T::DISCRIMINATOR)).expect("Event should be emitted");
+    T::try_from_slice(&data[T::DISCRIMINATOR.len()..]).unwrap()
+}
+
+// Processes the instructions in a single transaction paid by the context payer, returning the emitted events.
+async fn process(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> Vec<Vec<u8>> {
+    EVENTS.lock().unwrap().clear();
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(transaction).await.unwrap();
+    std::mem::take(&mut *EVENTS.lock().unwrap())
+}
+
+async fn fetch<T: AccountDeserialize>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let account = context.banks_client.get_account(address).await.unwrap().expect("Account should exist");
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+async fn token_balance(context: &mut ProgramTestContext, token_account: Pubkey) -> u64 {
+    let account = context.banks_client.get_account(token_account).await.unwrap().expect("Token account should exist");
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let payer = context.payer.pubkey();
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&token::ID, &mint.pubkey(), &payer, None, DECIMALS).unwrap(),
+    ];
+    process(context, &instructions, &[&mint]).await;
+    mint.pubkey()
+}
+
+// Creates the ATA of the owner, minting it the amount if any.
+async fn create_token_account(context: &mut ProgramTestContext, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let mut instructions = vec![spl_associated_token_account::instruction::create_associated_token_account(
+        &payer,
+        owner,
+        mint,
+        &token::ID,
+    )];
+    let token_account = get_associated_token_address(owner, mint);
+    if amount > 0 {
+        instructions
+            .push(spl_token::instruction::mint_to(&token::ID, mint, &token_account, &payer, &[], amount).unwrap());
+    }
+    process(context, &instructions, &[]).await;
+    token_account
+}
+
+// Delegates the amount of the owner ATA to the state PDA, which pulls the tokens of deposits and fills.
+fn approve_state_ix(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    let token_account = get_associated_token_address(owner, mint);
+    let state = get_state_address(SEED);
+    spl_token::instruction::approve_checked(&token::ID, &token_account, mint, &state, owner, &[], amount, DECIMALS)
+        .unwrap()
+}
+
+fn relay_data(depositor: Pubkey, recipient: Pubkey, mint: Pubkey, exclusive_relayer: Pubkey, now: u32) -> V3RelayData {
+    V3RelayData {
+        depositor,
+        recipient,
+        exclusive_relayer,
+        input_token: Pubkey::new_from_array([1; 32]), // Origin chain token, only hashed into the relay.
+        output_token: mint,
+        input_amount: 0,
+        output_amount: 0,
+        origin_chain_id: ORIGIN_CHAIN_ID,
+        deposit_id: [0; 32],
+        fill_deadline: now + 3600,
+        exclusivity_deadline: 0,
+        message: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn deposit_fill_slow_fill_and_refund() {
+    let mut program_test = ProgramTest::new("svm_spoke", svm_spoke::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    let mut context = program_test.start_with_context().await;
+    let stubs = program_stubs::set_syscall_stubs(Box::new(NoStubs));
+    program_stubs::set_syscall_stubs(Box::new(EventRecorder { stubs }));
+
+    let owner = context.payer.pubkey();
+    let depositor = Keypair::new();
+    let relayer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let fund_ixs = [
+        system_instruction::transfer(&owner, &depositor.pubkey(), 1_000_000_000),
+        system_instruction::transfer(&owner, &relayer.pubkey(), 1_000_000_000),
+    ];
+    process(&mut context, &fund_ixs, &[]).await;
+
+    let mint = create_mint(&mut context).await;
+    let depositor_token_account = create_token_account(&mut context, &depositor.pubkey(), &mint, DEPOSIT_AMOUNT).await;
+    let relayer_token_account = create_token_account(&mut context, &relayer.pubkey(), &mint, FAST_FILL_AMOUNT).await;
+    let recipient_token_account = create_token_account(&mut context, &recipient, &mint, 0).await;
+
+    let state = get_state_address(SEED);
+    let vault_authority = find_address(&[b"vault_authority", state.as_ref()]);
+    let vault = get_associated_token_address(&vault_authority, &mint);
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp as u32;
+
+    // Initialize the state, which is owned by the signer and tracks the Clock time in test builds.
+    let initialize_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::Initialize { signer: owner, state, system_program: system_program::ID }
+            .to_account_metas(None),
+        data: instruction::Initialize {
+            seed: SEED,
+            initial_number_of_deposits: 0,
+            chain_id: CHAIN_ID,
+            remote_domain: 0,
+            cross_domain_admin: Pubkey::new_unique(),
+            deposit_quote_time_buffer: 3600,
+            fill_deadline_buffer: 4 * 3600,
+            network: NETWORK_LOCALNET,
+        }
+        .data(),
+    };
+    process(&mut context, &[initialize_ix], &[]).await;
+    let state_data: State = fetch(&mut context, state).await;
+    assert_eq!(state_data.owner, owner, "Signer should own the state");
+    assert_eq!(state_data.current_time, now, "Current time should be initialized from the Clock");
+    assert_eq!((state_data.number_of_deposits, state_data.root_bundle_id), (0, 0), "Counters should start at 0");
+
+    // Enable the route of the mint towards the destination chain, creating the vault ATA of the vault authority.
+    let route = find_address(&[b"route", mint.as_ref(), &SEED.to_le_bytes(), &DESTINATION_CHAIN_ID.to_le_bytes()]);
+    let set_enable_route_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::SetEnableRoute {
+            signer: owner,
+            payer: owner,
+            state,
+            route,
+            vault,
+            origin_token_mint: mint,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            lookup_table_config: None,
+            lookup_table: None,
+            address_lookup_table_program: None,
+            vault_authority,
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::SetEnableRoute {
+            origin_token: mint,
+            destination_chain_id: DESTINATION_CHAIN_ID,
+            enabled: true,
+            disable_at: 0,
+            decimal_shift: 0,
+        }
+        .data(),
+    };
+    let events = process(&mut context, &[set_enable_route_ix], &[]).await;
+    let enabled_route: EnabledDepositRoute = find_event(&events);
+    assert_eq!((enabled_route.origin_token, enabled_route.destination_chain_id), (mint, DESTINATION_CHAIN_ID));
+    assert!(enabled_route.enabled, "Route should be enabled");
+    assert_eq!(token_balance(&mut context, vault).await, 0, "Vault should be created empty");
+
+    // Register the vault of the mint with its token program, which deposits of the mint check.
+    let vault_registry = find_address(&[b"vault", &SEED.to_le_bytes(), mint.as_ref()]);
+    let create_vault_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::CreateVault {
+            signer: owner,
+            state,
+            mint,
+            authority: vault_authority,
+            vault,
+            vault_registry,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateVault {}.data(),
+    };
+    let events = process(&mut context, &[create_vault_ix], &[]).await;
+    let vault_created: VaultCreated = find_event(&events);
+    assert_eq!((vault_created.mint, vault_created.token_program), (mint, token::ID));
+    assert_eq!((vault_created.authority, vault_created.vault), (vault_authority, vault));
+
+    // Deposit towards the destination chain, pulling the input amount into the vault.
+    let deposit = DepositArgs {
+        depositor: depositor.pubkey(),
+        recipient: Pubkey::new_unique(),
+        input_token: mint,
+        output_token: Pubkey::new_unique(),
+        input_amount: DEPOSIT_AMOUNT,
+        output_amount: DEPOSIT_AMOUNT - 1_000,
+        destination_chain_id: DESTINATION_CHAIN_ID,
+        exclusive_relayer: Pubkey::default(),
+        quote_timestamp: now,
+        fill_deadline: now + 3600,
+        exclusivity_parameter: 0,
+        message: Vec::new(),
+    };
+    let deposit_options = DepositOptions { spoke: spoke(), mint_owner: token::ID, ..DepositOptions::default() };
+    let deposit_ixs = [
+        approve_state_ix(&depositor.pubkey(), &mint, DEPOSIT_AMOUNT),
+        build_deposit_ix(&deposit, &depositor.pubkey(), &deposit_options).unwrap(),
+    ];
+    let events = process(&mut context, &deposit_ixs, &[&depositor]).await;
+    let deposited: V3FundsDeposited = find_event(&events);
+    assert_eq!(deposited.deposit_id[..4], 1u32.to_le_bytes(), "Deposit ID should be the first deposit number");
+    assert_eq!((deposited.input_token, deposited.input_amount), (mint, DEPOSIT_AMOUNT));
+    assert_eq!((deposited.depositor, deposited.recipient), (deposit.depositor, deposit.recipient));
+    assert_eq!(deposited.destination_chain_id, DESTINATION_CHAIN_ID);
+    assert!(!deposited.unsafe_deposit_id, "Deposit ID should be assigned by the state");
+    assert_eq!(token_balance(&mut context, depositor_token_account).await, 0, "Depositor should pay the input");
+    assert_eq!(token_balance(&mut context, vault).await, DEPOSIT_AMOUNT, "Vault should hold the deposit");
+    let state_data: State = fetch(&mut context, state).await;
+    assert_eq!(state_data.number_of_deposits, 1, "Deposit count should be incremented");
+    let vault_liability = find_address(&[b"vault_liability", &SEED.to_le_bytes(), mint.as_ref()]);
+    let vault_liability_data: VaultLiability = fetch(&mut context, vault_liability).await;
+    assert_eq!(vault_liability_data.liabilities, DEPOSIT_AMOUNT, "Deposit should back vault liabilities");
+
+    // Fast fill an inbound relay as its exclusive relayer, asking to be repaid on this chain.
+    let fast_relay = V3RelayData {
+        input_amount: FAST_FILL_AMOUNT + 1_000,
+        output_amount: FAST_FILL_AMOUNT,
+        deposit_id: [1; 32],
+        exclusivity_deadline: now + 60,
+        ..relay_data(Pubkey::new_unique(), recipient, mint, relayer.pubkey(), now)
+    };
+    let fill_options = FillOptions {
+        spoke: spoke(),
+        mint_owner: token::ID,
+        repayment_chain_id: CHAIN_ID,
+        repayment_address: relayer.pubkey(),
+        ..FillOptions::default()
+    };
+    let fill_ixs = [
+        approve_state_ix(&relayer.pubkey(), &mint, FAST_FILL_AMOUNT),
+        build_fill_ix(&fast_relay, &relayer.pubkey(), &fill_options).unwrap(),
+    ];
+    let events = process(&mut context, &fill_ixs, &[&relayer]).await;
+    let filled: FilledV3Relay = find_event(&events);
+    assert_eq!((filled.deposit_id, filled.origin_chain_id), (fast_relay.deposit_id, ORIGIN_CHAIN_ID));
+    assert_eq!((filled.relayer, filled.repayment_chain_id), (relayer.pubkey(), CHAIN_ID));
+    assert!(filled.relay_execution_info.fill_type == FillType::FastFill, "Relay should be fast filled");
+    assert_eq!(filled.relay_hash, get_v3_relay_hash(&fast_relay, CHAIN_ID), "Relay hash should match");
+    assert_eq!(token_balance(&mut context, relayer_token_account).await, 0, "Relayer should pay the output");
+    assert_eq!(token_balance(&mut context, recipient_token_account).await, FAST_FILL_AMOUNT);
+    let fast_fill_status = get_fill_status_address(&get_v3_relay_hash(&fast_relay, CHAIN_ID));
+    let fill_status_data: FillStatusAccount = fetch(&mut context, fast_fill_status).await;
+    assert!(fill_status_data.status == FillStatus::Filled, "Fast filled relay should be filled");
+    assert_eq!(fill_status_data.relayer, relayer.pubkey(), "Fill status should record the relayer");
+
+    // Request the slow fill of a relay without an exclusive relayer, reserving its output amount in the vault.
+    let slow_relay = V3RelayData {
+        input_amount: SLOW_FILL_AMOUNT + 1_000,
+        output_amount: SLOW_FILL_AMOUNT,
+        deposit_id: [2; 32],
+        ..relay_data(Pubkey::new_unique(), recipient, mint, Pubkey::default(), now)
+    };
+    let slow_relay_hash = get_v3_relay_hash(&slow_relay, CHAIN_ID);
+    let request_options = RequestSlowFillOptions { spoke: spoke(), ..RequestSlowFillOptions::default() };
+    let request_ix = build_request_slow_fill_ix(&slow_relay, &relayer.pubkey(), &request_options);
+    let events = process(&mut context, &[request_ix], &[&relayer]).await;
+    let requested: RequestedV3SlowFill = find_event(&events);
+    assert_eq!((requested.deposit_id, requested.output_amount), (slow_relay.deposit_id, SLOW_FILL_AMOUNT));
+    let slow_fill_status = get_fill_status_address(&slow_relay_hash);
+    let fill_status_data: FillStatusAccount = fetch(&mut context, slow_fill_status).await;
+    assert!(fill_status_data.status == FillStatus::RequestedSlowFill, "Slow fill should be requested");
+    let slow_fill_liability = find_address(&[b"slow_fill_liability", &SEED.to_le_bytes(), mint.as_ref()]);
+    let liability_data: SlowFillLiability = fetch(&mut context, slow_fill_liability).await;
+    assert_eq!(liability_data.pending_slow_fill_amount, SLOW_FILL_AMOUNT, "Slow fill amount should be reserved");
+
+    // Relay a root bundle built locally, with single leaf trees whose roots are the hashes of their leaves.
+    let slow_fill_leaf =
+        V3SlowFill { relay_data: slow_relay.clone(), chain_id: CHAIN_ID, updated_output_amount: SLOW_FILL_AMOUNT };
+    let refund_leaf = RelayerRefundLeaf {
+        amount_to_return: 0,
+        chain_id: CHAIN_ID,
+        refund_amounts: vec![FAST_FILL_AMOUNT],
+        leaf_id: 0,
+        mint_public_key: mint,
+        refund_addresses: vec![relayer.pubkey()],
+    };
+    let relayer_refund_root = refund_leaf.to_keccak_hash().unwrap();
+    let slow_relay_root = slow_fill_leaf.to_keccak_hash().unwrap();
+    let root_bundle_id = 0u32;
+    let root_bundle = find_address(&[b"root_bundle", &SEED.to_le_bytes(), &root_bundle_id.to_le_bytes()]);
+    let relay_root_bundle_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::RelayRootBundle {
+            signer: owner,
+            payer: owner,
+            state,
+            root_bundle,
+            system_program: system_program::ID,
+            admin_nonce: None,
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RelayRootBundle {
+            relayer_refund_root,
+            slow_relay_root,
+            execution_deadline: 0,
+            restricted_executor: None,
+            executor_grace_period: 0,
+            idempotency_key: None,
+        }
+        .data(),
+    };
+    let events = process(&mut context, &[relay_root_bundle_ix], &[]).await;
+    let relayed: RelayedRootBundle = find_event(&events);
+    assert_eq!(relayed.root_bundle_id, root_bundle_id, "Root bundle ID should match");
+    assert_eq!((relayed.relayer_refund_root, relayed.slow_relay_root), (relayer_refund_root, slow_relay_root));
+    let state_data: State = fetch(&mut context, state).await;
+    assert_eq!(state_data.root_bundle_id, root_bundle_id + 1, "Root bundle ID should be incremented");
+
+    // Execute the slow fill leaf, paying the recipient from the vault and releasing the reserved amount.
+    let execute_options = ExecuteSlowRelayLeafOptions {
+        spoke: spoke(),
+        mint_owner: token::ID,
+        vault_liability: true,
+        ..ExecuteSlowRelayLeafOptions::default()
+    };
+    let execute_slow_fill_ix = build_execute_slow_relay_leaf_ix(
+        &slow_fill_leaf,
+        root_bundle_id,
+        Vec::new(),
+        &relayer.pubkey(),
+        &execute_options,
+    )
+    .unwrap();
+    let events = process(&mut context, &[execute_slow_fill_ix], &[&relayer]).await;
+    let slow_filled: FilledV3Relay = find_event(&events);
+    assert_eq!(slow_filled.deposit_id, slow_relay.deposit_id, "Slow filled deposit ID should match");
+    assert!(slow_filled.relay_execution_info.fill_type == FillType::SlowFill, "Relay should be slow filled");
+    assert_eq!(slow_filled.relay_execution_info.root_bundle_id, Some(root_bundle_id), "Root bundle should be set");
+    assert_eq!(token_balance(&mut context, recipient_token_account).await, FAST_FILL_AMOUNT + SLOW_FILL_AMOUNT);
+    assert_eq!(token_balance(&mut context, vault).await, DEPOSIT_AMOUNT - SLOW_FILL_AMOUNT);
+    let fill_status_data: FillStatusAccount = fetch(&mut context, slow_fill_status).await;
+    assert!(fill_status_data.status == FillStatus::Filled, "Slow filled relay should be filled");
+    let liability_data: SlowFillLiability = fetch(&mut context, slow_fill_liability).await;
+    assert_eq!(liability_data.pending_slow_fill_amount, 0, "Reserved slow fill amount should be released");
+
+    // Load the relayer refund leaf into the instruction params of the executor, which are too large for the data.
+    let instruction_params = find_address(&[b"instruction_params", relayer.pubkey().as_ref()]);
+    let mut params = Vec::new();
+    ExecuteRelayerRefundLeafParams { root_bundle_id, relayer_refund_leaf: refund_leaf.clone(), proof: Vec::new() }
+        .try_serialize(&mut params)
+        .unwrap();
+    let load_params_ixs = [
+        Instruction {
+            program_id: svm_spoke::ID,
+            accounts: accounts::InitializeInstructionParams {
+                signer: relayer.pubkey(),
+                instruction_params,
+                instruction_params_header: find_address(&[b"instruction_params_header", relayer.pubkey().as_ref()]),
+                state,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeInstructionParams { _total_size: params.len() as u32 }.data(),
+        },
+        Instruction {
+            program_id: svm_spoke::ID,
+            accounts: accounts::WriteInstructionParamsFragment {
+                signer: relayer.pubkey(),
+                instruction_params,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::WriteInstructionParamsFragment { offset: 0, fragment: params }.data(),
+        },
+    ];
+    process(&mut context, &load_params_ixs, &[&relayer]).await;
+
+    // Execute the relayer refund leaf, repaying the fast fill of the relayer from the vault.
+    let claim_liability = find_address(&[b"claim_liability", &SEED.to_le_bytes(), mint.as_ref()]);
+    let mut refund_account_metas = accounts::ExecuteRelayerRefundLeaf {
+        signer: relayer.pubkey(),
+        instruction_params,
+        state,
+        root_bundle,
+        vault,
+        vault_authority,
+        mint,
+        transfer_liability: find_address(&[b"transfer_liability", mint.as_ref()]),
+        error_counters: None,
+        bundle_audit: None,
+        rate_limit: None,
+        vault_liability: Some(vault_liability),
+        slow_fill_liability,
+        fill_volume: find_address(&[b"fill_volume", &SEED.to_le_bytes(), mint.as_ref()]),
+        execution_approval: None,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        associated_token_program: None,
+        route: Some(route), // Checks the mint decimals against the snapshot of the enabled route.
+        claim_liability,
+        event_authority: derive_event_authority(),
+        program: svm_spoke::ID,
+    }
+    .to_account_metas(None);
+    refund_account_metas.push(AccountMeta::new(relayer_token_account, false));
+    let execute_refund_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: refund_account_metas,
+        data: instruction::ExecuteRelayerRefundLeaf {}.data(),
+    };
+    let events = process(&mut context, &[execute_refund_ix], &[&relayer]).await;
+    let executed: ExecutedRelayerRefundRoot = find_event(&events);
+    assert_eq!((executed.root_bundle_id, executed.leaf_id), (root_bundle_id, 0), "Executed leaf should match");
+    assert_eq!((executed.refund_amounts, executed.refund_addresses), (vec![FAST_FILL_AMOUNT], vec![relayer.pubkey()]));
+    assert!(!executed.deferred_refunds, "Refunds should be sent directly");
+    assert_eq!(executed.caller, relayer.pubkey(), "Caller should be the executor");
+    assert_eq!(token_balance(&mut context, relayer_token_account).await, FAST_FILL_AMOUNT, "Relayer should be repaid");
+    let remaining = DEPOSIT_AMOUNT - SLOW_FILL_AMOUNT - FAST_FILL_AMOUNT;
+    assert_eq!(token_balance(&mut context, vault).await, remaining, "Vault should keep the rest of the deposit");
+    let vault_liability_data: VaultLiability = fetch(&mut context, vault_liability).await;
+    assert_eq!(vault_liability_data.liabilities, remaining, "Vault outflows should be debited from the liabilities");
+    let claim_liability_data: ClaimLiability = fetch(&mut context, claim_liability).await;
+    assert_eq!(claim_liability_data.pending_claim_amount, 0, "Refunds sent directly should not be owed to claims");
+    let root_bundle_data: RootBundle = fetch(&mut context, root_bundle).await;
+    assert!(is_claimed(&root_bundle_data.claimed_bitmap, 0), "Refund leaf should be claimed");
+}
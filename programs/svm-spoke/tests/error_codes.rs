@@ -0,0 +1,36 @@
+// Pins the numeric codes of the errors that relayers and off-chain tooling match on when fills, slow fill requests and
+// slow fill executions are rejected, so that variants are only ever appended to their enums and existing codes keep
+// their meaning across upgrades. Run with `cargo test -p svm-spoke`.
+
+use svm_spoke::error::{CommonError, SvmError};
+
+#[test]
+fn common_error_codes_are_stable() {
+    let cases = [
+        (CommonError::NotExclusiveRelayer, 6003),
+        (CommonError::NoSlowFillsInExclusivityWindow, 6004),
+        (CommonError::RelayFilled, 6005),
+        (CommonError::InvalidSlowFillRequest, 6006),
+        (CommonError::ExpiredFillDeadline, 6007),
+        (CommonError::FillsArePaused, 6013),
+    ];
+    for (error, code) in cases {
+        let name = error.name();
+        assert_eq!(u32::from(error), code, "Code of CommonError::{} should not change", name);
+    }
+}
+
+#[test]
+fn svm_error_codes_are_stable() {
+    let cases = [
+        (SvmError::InvalidRelayHash, 6002),
+        (SvmError::RelayExpired, 6127),
+        (SvmError::SlowFillNotRequested, 6155),
+        (SvmError::RelayAlreadyFilled, 6156),
+        (SvmError::SlowFillAlreadyRequested, 6157),
+    ];
+    for (error, code) in cases {
+        let name = error.name();
+        assert_eq!(u32::from(error), code, "Code of SvmError::{} should not change", name);
+    }
+}
@@ -0,0 +1,99 @@
+// Checks the sizing of refund and deposit events against MAX_EVENT_CPI_DATA_SIZE, which splits the
+// ExecutedRelayerRefundRoot events of large refund leaves into ExecutedRelayerRefundRootChunk events and hashes large
+// V3FundsDeposited messages. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use anchor_lang::{prelude::Pubkey, solana_program::keccak};
+use svm_spoke::{
+    constants::{MAX_EVENT_CPI_DATA_SIZE, MAX_INLINE_EVENT_MESSAGE_SIZE, MAX_MESSAGE_SIZE},
+    event::{ExecutedRelayerRefundRoot, V3FundsDeposited},
+    utils::{get_event_cpi_data_size, get_event_message, get_refund_event_chunks, get_refunds_per_event_chunk},
+};
+
+fn refund_event(refund_count: usize) -> ExecutedRelayerRefundRoot {
+    ExecutedRelayerRefundRoot {
+        amount_to_return: 0,
+        chain_id: 34268394551451,
+        refund_amounts: vec![u64::MAX; refund_count],
+        root_bundle_id: 7,
+        leaf_id: 3,
+        l2_token_address: Pubkey::new_from_array([1; 32]),
+        refund_addresses: (0..refund_count).map(|i| Pubkey::new_from_array([i as u8; 32])).collect(),
+        deferred_refunds: false,
+        caller: Pubkey::new_from_array([2; 32]),
+        token_decimals: 6,
+    }
+}
+
+#[test]
+fn single_event_limit() {
+    // Refund count also asserted by the maximum refunds tests of test/svm/SvmSpoke.Bundle.ts.
+    assert_eq!(get_refunds_per_event_chunk(), 27);
+    assert!(get_event_cpi_data_size(&refund_event(27)) <= MAX_EVENT_CPI_DATA_SIZE);
+    assert!(get_event_cpi_data_size(&refund_event(28)) > MAX_EVENT_CPI_DATA_SIZE, "Chunked from 28 refunds");
+}
+
+#[test]
+fn chunks_large_refund_leaves() {
+    let event = refund_event(80);
+    assert!(get_event_cpi_data_size(&event) > MAX_EVENT_CPI_DATA_SIZE);
+
+    let chunks = get_refund_event_chunks(&event);
+    assert_eq!(chunks.len(), 3);
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert!(get_event_cpi_data_size(chunk) <= MAX_EVENT_CPI_DATA_SIZE, "Chunk {} should fit", i);
+        assert_eq!(chunk.chunk_index, i as u32);
+        assert_eq!(chunk.total_chunks, 3);
+        assert_eq!((chunk.root_bundle_id, chunk.leaf_id), (7, 3));
+    }
+
+    // Reassembling the chunks in order gives back the refunds of the leaf.
+    let refund_amounts: Vec<u64> = chunks.iter().flat_map(|chunk| chunk.refund_amounts.clone()).collect();
+    let refund_addresses: Vec<Pubkey> = chunks.iter().flat_map(|chunk| chunk.refund_addresses.clone()).collect();
+    assert_eq!(refund_amounts, event.refund_amounts);
+    assert_eq!(refund_addresses, event.refund_addresses);
+}
+
+fn deposit_event(message: Vec<u8>) -> V3FundsDeposited {
+    let (message, message_hash) = get_event_message(message);
+    V3FundsDeposited {
+        input_token: Pubkey::new_from_array([1; 32]),
+        output_token: Pubkey::new_from_array([2; 32]),
+        input_amount: u64::MAX,
+        output_amount: u64::MAX,
+        destination_chain_id: 1,
+        deposit_id: [3; 32],
+        quote_timestamp: 1,
+        fill_deadline: 2,
+        exclusivity_deadline: 3,
+        depositor: Pubkey::new_from_array([4; 32]),
+        recipient: Pubkey::new_from_array([5; 32]),
+        exclusive_relayer: Pubkey::new_from_array([6; 32]),
+        message,
+        token_decimals: 6,
+        unsafe_deposit_id: false,
+        depositor_nonce: Some(u64::MAX),
+        message_hash,
+        exclusivity_parameter: u32::MAX,
+        dust_refunded: Some(u64::MAX),
+    }
+}
+
+#[test]
+fn inlines_small_deposit_messages() {
+    let message = vec![7; MAX_INLINE_EVENT_MESSAGE_SIZE];
+    let event = deposit_event(message.clone());
+    assert_eq!((event.message, event.message_hash), (message, None));
+    assert!(get_event_cpi_data_size(&deposit_event(vec![7; MAX_INLINE_EVENT_MESSAGE_SIZE])) <= MAX_EVENT_CPI_DATA_SIZE);
+}
+
+#[test]
+fn hashes_large_deposit_messages() {
+    for size in [MAX_INLINE_EVENT_MESSAGE_SIZE + 1, MAX_MESSAGE_SIZE] {
+        let message = vec![7; size];
+        let event = deposit_event(message.clone());
+        assert!(event.message.is_empty(), "{} byte message should not be inlined", size);
+        assert_eq!(event.message_hash, Some(keccak::hash(&message).to_bytes()));
+        assert!(get_event_cpi_data_size(&event) <= MAX_EVENT_CPI_DATA_SIZE);
+    }
+}
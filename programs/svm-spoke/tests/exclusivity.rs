@@ -0,0 +1,42 @@
+// Checks the resolution of deposit exclusivity parameters to deadlines at the MAX_EXCLUSIVITY_PERIOD_SECONDS threshold
+// against the expected outputs of the EVM SpokePool for the same inputs, so that a deposit can never be exclusive for a
+// different window depending on its origin chain. Run with `cargo test -p svm-spoke`.
+
+use svm_spoke::{constants::MAX_EXCLUSIVITY_PERIOD_SECONDS, utils::resolve_exclusivity_deadline};
+
+const CURRENT_TIME: u32 = 1_760_396_400;
+
+#[test]
+fn resolves_offsets_up_to_the_threshold() {
+    let cases = [
+        (0, 0),
+        (1, CURRENT_TIME + 1),
+        (MAX_EXCLUSIVITY_PERIOD_SECONDS - 1, CURRENT_TIME + MAX_EXCLUSIVITY_PERIOD_SECONDS - 1),
+        (MAX_EXCLUSIVITY_PERIOD_SECONDS, CURRENT_TIME + MAX_EXCLUSIVITY_PERIOD_SECONDS),
+    ];
+    for (exclusivity_parameter, expected) in cases {
+        let resolved = resolve_exclusivity_deadline(exclusivity_parameter, CURRENT_TIME).unwrap();
+        assert_eq!(resolved, expected, "{} should be resolved as an offset", exclusivity_parameter);
+    }
+}
+
+#[test]
+fn keeps_timestamps_above_the_threshold() {
+    let cases = [MAX_EXCLUSIVITY_PERIOD_SECONDS + 1, CURRENT_TIME - 1, CURRENT_TIME, CURRENT_TIME + 1, u32::MAX];
+    for exclusivity_parameter in cases {
+        let resolved = resolve_exclusivity_deadline(exclusivity_parameter, CURRENT_TIME).unwrap();
+        assert_eq!(resolved, exclusivity_parameter, "{} should be kept as a timestamp", exclusivity_parameter);
+    }
+}
+
+#[test]
+fn fails_on_overflowing_offsets() {
+    // The EVM SpokePool reverts on the checked addition instead of wrapping the deadline.
+    let current_time = u32::MAX - MAX_EXCLUSIVITY_PERIOD_SECONDS;
+    assert_eq!(resolve_exclusivity_deadline(MAX_EXCLUSIVITY_PERIOD_SECONDS, current_time).unwrap(), u32::MAX);
+    assert!(resolve_exclusivity_deadline(MAX_EXCLUSIVITY_PERIOD_SECONDS, current_time + 1).is_err());
+
+    // Timestamps are not added to the current time, so they never overflow.
+    let timestamp = MAX_EXCLUSIVITY_PERIOD_SECONDS + 1;
+    assert_eq!(resolve_exclusivity_deadline(timestamp, u32::MAX).unwrap(), timestamp);
+}
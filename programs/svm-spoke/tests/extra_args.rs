@@ -0,0 +1,86 @@
+// Decodes versioned extra args blobs as encoded by the TypeScript SDK (src/svm/conversionUtils.ts), including the
+// bounds on the origin transaction reference of fast fills. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use anchor_lang::AnchorSerialize;
+use svm_spoke::utils::{
+    parse_extra_args, ExtraArgsV3, ExtraArgsV5, ExtraArgsV6, ARGS_VERSION_V0, ARGS_VERSION_V3, ARGS_VERSION_V5,
+    ARGS_VERSION_V6,
+};
+
+const ORIGIN_TX_REF: [u8; 32] = [0xab; 32];
+
+fn encode_v3(origin_tx_ref: Option<[u8; 32]>) -> Vec<u8> {
+    let extra_args = ExtraArgsV3 { memo: None, create_repayment_token_account: false, origin_tx_ref };
+    extra_args.try_to_vec().unwrap()
+}
+
+#[test]
+fn origin_tx_ref() {
+    let extra_args = parse_extra_args(ARGS_VERSION_V3, &encode_v3(Some(ORIGIN_TX_REF))).unwrap();
+    assert_eq!(extra_args.origin_tx_ref(), Some(ORIGIN_TX_REF));
+
+    // Matches the TypeScript encoding: no memo, no repayment token account flag and then the optional reference.
+    let mut encoded = vec![0, 0, 1];
+    encoded.extend_from_slice(&ORIGIN_TX_REF);
+    assert_eq!(encode_v3(Some(ORIGIN_TX_REF)), encoded);
+}
+
+#[test]
+fn absent_origin_tx_ref() {
+    let extra_args = parse_extra_args(ARGS_VERSION_V3, &encode_v3(None)).unwrap();
+    assert_eq!(extra_args.origin_tx_ref(), None);
+
+    let extra_args = parse_extra_args(ARGS_VERSION_V3, &encode_v3(Some([0; 32]))).unwrap();
+    assert_eq!(extra_args.origin_tx_ref(), None, "All-zero origin tx ref must be treated as absent");
+
+    let extra_args = parse_extra_args(ARGS_VERSION_V0, &[]).unwrap();
+    assert_eq!(extra_args.origin_tx_ref(), None);
+}
+
+#[test]
+fn rejects_origin_tx_ref_of_other_length() {
+    let mut encoded = encode_v3(Some(ORIGIN_TX_REF));
+    encoded.pop();
+    assert!(parse_extra_args(ARGS_VERSION_V3, &encoded).is_err(), "Truncated origin tx ref must be rejected");
+
+    let mut encoded = encode_v3(Some(ORIGIN_TX_REF));
+    encoded.push(0xab);
+    assert!(parse_extra_args(ARGS_VERSION_V3, &encoded).is_err(), "Oversized origin tx ref must be rejected");
+}
+
+#[test]
+fn depositor_nonce() {
+    // Matches the TypeScript encoding of the nonce as little endian u64.
+    let encoded = ExtraArgsV5 { depositor_nonce: 7 }.try_to_vec().unwrap();
+    assert_eq!(encoded, 7u64.to_le_bytes());
+    assert_eq!(parse_extra_args(ARGS_VERSION_V5, &encoded).unwrap().depositor_nonce(), Some(7));
+
+    let extra_args = parse_extra_args(ARGS_VERSION_V3, &encode_v3(None)).unwrap();
+    assert_eq!(extra_args.depositor_nonce(), None, "Fill extra args must not carry a depositor nonce");
+
+    assert!(parse_extra_args(ARGS_VERSION_V5, &encoded[..4]).is_err(), "Truncated nonce must be rejected");
+}
+
+#[test]
+fn create_recipient_token_account() {
+    let extra_args = ExtraArgsV6 {
+        memo: None,
+        create_repayment_token_account: false,
+        origin_tx_ref: None,
+        accept_low_fee: true,
+        create_recipient_token_account: true,
+    };
+    // Matches the TypeScript encoding: the version 4 args followed by the recipient token account flag.
+    let encoded = extra_args.try_to_vec().unwrap();
+    assert_eq!(encoded, vec![0, 0, 0, 1, 1]);
+
+    let extra_args = parse_extra_args(ARGS_VERSION_V6, &encoded).unwrap();
+    assert!(extra_args.create_recipient_token_account());
+    assert!(extra_args.accept_low_fee());
+
+    let extra_args = parse_extra_args(ARGS_VERSION_V3, &encode_v3(None)).unwrap();
+    assert!(!extra_args.create_recipient_token_account(), "Older versions must not create the recipient ATA");
+
+    assert!(parse_extra_args(ARGS_VERSION_V6, &encoded[..4]).is_err(), "Missing flag must be rejected");
+}
@@ -0,0 +1,98 @@
+// Round trips fill status PDAs of the current and legacy layouts through the decoding shared by the get_fill_status
+// view and off-chain code. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use anchor_lang::{prelude::Pubkey, AccountSerialize, Discriminator, Space};
+use svm_spoke::{
+    client::{FillStatus, FillStatusAccount},
+    constants::{DISCRIMINATOR_SIZE, LEGACY_FILL_STATUS_ACCOUNT_SIZES},
+};
+
+const RELAYER: Pubkey = Pubkey::new_from_array([1; 32]);
+const REQUESTER: Pubkey = Pubkey::new_from_array([2; 32]);
+const PAYER: Pubkey = Pubkey::new_from_array([3; 32]);
+
+// Legacy layouts are prefixes of the current one: status, relayer and then the fill deadline.
+fn legacy_data(size: usize, status: FillStatus, fill_deadline: u32) -> Vec<u8> {
+    let mut data = FillStatusAccount::DISCRIMINATOR.to_vec();
+    data.push(status as u8);
+    data.extend_from_slice(RELAYER.as_ref());
+    data.extend_from_slice(&fill_deadline.to_le_bytes());
+    data.truncate(size);
+    data
+}
+
+#[test]
+fn current_layout() {
+    let fill_status = FillStatusAccount {
+        status: FillStatus::RequestedSlowFill,
+        relayer: RELAYER,
+        fill_deadline: 1760400000,
+        requester: REQUESTER,
+        payer: PAYER,
+    };
+    let mut data = Vec::new();
+    fill_status.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), DISCRIMINATOR_SIZE + FillStatusAccount::INIT_SPACE);
+
+    let decoded = FillStatusAccount::try_deserialize_any_layout(&data).unwrap();
+    assert!(decoded.status == FillStatus::RequestedSlowFill);
+    assert_eq!(decoded.relayer, RELAYER);
+    assert_eq!(decoded.fill_deadline, 1760400000);
+    assert_eq!(decoded.requester, REQUESTER);
+    assert_eq!(decoded.payer, PAYER);
+}
+
+#[test]
+fn legacy_layouts() {
+    let decoded = FillStatusAccount::try_deserialize_any_layout(&legacy_data(
+        LEGACY_FILL_STATUS_ACCOUNT_SIZES[0],
+        FillStatus::Filled,
+        1760400000,
+    ))
+    .unwrap();
+    assert!(decoded.status == FillStatus::Filled);
+    assert_eq!(decoded.relayer, RELAYER);
+    assert_eq!(decoded.fill_deadline, 0, "Fill deadline was not recorded in the first layout");
+    assert_eq!(decoded.requester, Pubkey::default());
+    assert_eq!(decoded.payer, Pubkey::default());
+
+    let decoded = FillStatusAccount::try_deserialize_any_layout(&legacy_data(
+        LEGACY_FILL_STATUS_ACCOUNT_SIZES[1],
+        FillStatus::RequestedSlowFill,
+        1760400000,
+    ))
+    .unwrap();
+    assert!(decoded.status == FillStatus::RequestedSlowFill);
+    assert_eq!(decoded.fill_deadline, 1760400000);
+    assert_eq!(decoded.requester, Pubkey::default());
+    assert_eq!(decoded.payer, Pubkey::default());
+}
+
+#[test]
+fn rejects_invalid_data() {
+    let mut data = legacy_data(LEGACY_FILL_STATUS_ACCOUNT_SIZES[1], FillStatus::Filled, 1760400000);
+    data[0] ^= 1;
+    assert!(FillStatusAccount::try_deserialize_any_layout(&data).is_err(), "Discriminator must match");
+
+    let data = legacy_data(LEGACY_FILL_STATUS_ACCOUNT_SIZES[0] - 1, FillStatus::Filled, 1760400000);
+    assert!(FillStatusAccount::try_deserialize_any_layout(&data).is_err(), "Truncated layouts must be rejected");
+}
+
+#[test]
+fn status_encoding() {
+    // Expired was appended, so accounts written before it keep decoding to the same status.
+    for (byte, status) in [(0, FillStatus::Unfilled), (1, FillStatus::RequestedSlowFill), (2, FillStatus::Filled)] {
+        let mut data = legacy_data(LEGACY_FILL_STATUS_ACCOUNT_SIZES[1], FillStatus::Unfilled, 1760400000);
+        data[DISCRIMINATOR_SIZE] = byte;
+        assert!(FillStatusAccount::try_deserialize_any_layout(&data).unwrap().status == status);
+    }
+
+    let data = legacy_data(LEGACY_FILL_STATUS_ACCOUNT_SIZES[1], FillStatus::Expired, 1760400000);
+    assert_eq!(data[DISCRIMINATOR_SIZE], 3);
+    assert!(FillStatusAccount::try_deserialize_any_layout(&data).unwrap().status == FillStatus::Expired);
+
+    let mut data = legacy_data(LEGACY_FILL_STATUS_ACCOUNT_SIZES[1], FillStatus::Expired, 1760400000);
+    data[DISCRIMINATOR_SIZE] = 4;
+    assert!(FillStatusAccount::try_deserialize_any_layout(&data).is_err(), "Unknown statuses must be rejected");
+}
@@ -0,0 +1,155 @@
+// Example consumer program depending on the spoke through its read interface only, as intent settlement programs do:
+// it hashes the relay passed in its instruction data, checks the fill status PDA of the relay and gates its own logic
+// on the relay being filled, without any CPI into the spoke. Run with `cargo test -p svm-spoke --features cpi`.
+#![cfg(feature = "cpi")]
+
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction},
+    AccountSerialize,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use svm_spoke::{
+    constants::LEGACY_FILL_STATUS_ACCOUNT_SIZES,
+    interface::{
+        derive_fill_status_pda, deserialize_fill_status, get_v3_relay_hash, FillStatus, FillStatusAccount, V3RelayData,
+    },
+};
+
+const CONSUMER_ID: Pubkey = Pubkey::new_from_array([9; 32]);
+const CHAIN_ID: u64 = 1234;
+const NOT_FILLED: u32 = 1;
+
+// Instruction data is the borsh encoded relay data and destination chain id, the only account is its fill status PDA.
+fn process_consumer(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (relay_data, chain_id) =
+        <(V3RelayData, u64)>::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let fill_status = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // The interface does not check the address, as only the consumer knows which relay the account should belong to.
+    let relay_hash = get_v3_relay_hash(&relay_data, chain_id);
+    if fill_status.key != &derive_fill_status_pda(&relay_hash, &svm_spoke::ID).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if deserialize_fill_status(fill_status)? != FillStatus::Filled {
+        return Err(ProgramError::Custom(NOT_FILLED));
+    }
+
+    msg!("Relay filled, settling the intent");
+    Ok(())
+}
+
+fn relay_data(deposit_id: u8) -> V3RelayData {
+    V3RelayData {
+        depositor: Pubkey::new_from_array([1; 32]),
+        recipient: Pubkey::new_from_array([2; 32]),
+        exclusive_relayer: Pubkey::default(),
+        input_token: Pubkey::new_from_array([3; 32]),
+        output_token: Pubkey::new_from_array([4; 32]),
+        input_amount: 1_000,
+        output_amount: 900,
+        origin_chain_id: 1,
+        deposit_id: [deposit_id; 32],
+        fill_deadline: 1_760_400_000,
+        exclusivity_deadline: 0,
+        message: Vec::new(),
+    }
+}
+
+fn fill_status_address(relay_data: &V3RelayData) -> Pubkey {
+    derive_fill_status_pda(&get_v3_relay_hash(relay_data, CHAIN_ID), &svm_spoke::ID).0
+}
+
+fn fill_status_data(status: FillStatus) -> Vec<u8> {
+    let fill_status = FillStatusAccount {
+        status,
+        relayer: Pubkey::new_from_array([5; 32]),
+        fill_deadline: 1_760_400_000,
+        requester: Pubkey::default(),
+        payer: Pubkey::new_from_array([5; 32]),
+    };
+    let mut data = Vec::new();
+    fill_status.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn add_fill_status(program_test: &mut ProgramTest, relay_data: &V3RelayData, data: Vec<u8>, owner: Pubkey) {
+    let account = SolanaAccount { lamports: 1_000_000_000, data, owner, executable: false, rent_epoch: 0 };
+    program_test.add_account(fill_status_address(relay_data), account);
+}
+
+async fn consume(
+    context: &mut ProgramTestContext,
+    relay_data: &V3RelayData,
+    fill_status: Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: CONSUMER_ID,
+        accounts: vec![AccountMeta::new_readonly(fill_status, false)],
+        data: (relay_data.clone(), CHAIN_ID).try_to_vec().unwrap(),
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+fn assert_instruction_error(result: std::result::Result<(), BanksClientError>, error: InstructionError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(0, instruction_error) => assert_eq!(instruction_error, error),
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn gates_on_filled_relays() {
+    let mut program_test = ProgramTest::new("fill_status_consumer", CONSUMER_ID, processor!(process_consumer));
+    program_test.prefer_bpf(false);
+
+    let (unfilled, requested, filled, legacy, foreign, other) =
+        (relay_data(1), relay_data(2), relay_data(3), relay_data(4), relay_data(5), relay_data(6));
+    add_fill_status(&mut program_test, &requested, fill_status_data(FillStatus::RequestedSlowFill), svm_spoke::ID);
+    add_fill_status(&mut program_test, &filled, fill_status_data(FillStatus::Filled), svm_spoke::ID);
+
+    // Legacy layouts are prefixes of the current one and are still read by the interface.
+    let mut legacy_data = fill_status_data(FillStatus::Filled);
+    legacy_data.truncate(LEGACY_FILL_STATUS_ACCOUNT_SIZES[1]);
+    add_fill_status(&mut program_test, &legacy, legacy_data, svm_spoke::ID);
+
+    // Spoke data copied into an account of another program is not trusted, nor are other spoke accounts.
+    add_fill_status(&mut program_test, &foreign, fill_status_data(FillStatus::Filled), CONSUMER_ID);
+    let mut other_data = fill_status_data(FillStatus::Filled);
+    other_data[0] ^= 1;
+    add_fill_status(&mut program_test, &other, other_data, svm_spoke::ID);
+    let mut context = program_test.start_with_context().await;
+
+    // Relays are only settled once filled, including by legacy fill status PDAs.
+    consume(&mut context, &filled, fill_status_address(&filled)).await.unwrap();
+    consume(&mut context, &legacy, fill_status_address(&legacy)).await.unwrap();
+
+    // Never created PDAs read as unfilled, as do slow fill requests.
+    let not_filled = InstructionError::Custom(NOT_FILLED);
+    let result = consume(&mut context, &unfilled, fill_status_address(&unfilled)).await;
+    assert_instruction_error(result, not_filled.clone());
+    let result = consume(&mut context, &requested, fill_status_address(&requested)).await;
+    assert_instruction_error(result, not_filled);
+
+    // Filled PDAs of other relays are rejected by the address check of the consumer.
+    let result = consume(&mut context, &unfilled, fill_status_address(&filled)).await;
+    assert_instruction_error(result, InstructionError::InvalidSeeds);
+
+    let owner_error = InstructionError::Custom(ErrorCode::AccountOwnedByWrongProgram.into());
+    assert_instruction_error(consume(&mut context, &foreign, fill_status_address(&foreign)).await, owner_error);
+    let discriminator_error = InstructionError::Custom(ErrorCode::AccountDiscriminatorMismatch.into());
+    assert_instruction_error(consume(&mut context, &other, fill_status_address(&other)).await, discriminator_error);
+}
@@ -0,0 +1,59 @@
+// Checks relay hashes and slow relay leaf hashes against the fixture shared with the TypeScript SDK
+// (test/svm/Utils.Hashing.ts) that the dataworker uses to build slow relay roots. The fixture hashes are generated
+// independently of both by test/svm/fixtures/generateHashes.js. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use svm_spoke::{
+    client::{V3RelayData, V3SlowFill},
+    utils::get_v3_relay_hash,
+};
+
+const FIXTURE: &str = include_str!("../../../test/svm/fixtures/hashes.json");
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fixture {
+    relay_hashes: Vec<RelayHashCase>,
+    slow_fill_leaves: Vec<SlowFillLeafCase>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RelayHashCase {
+    relay_data: V3RelayData,
+    #[serde(with = "svm_spoke::utils::serde_utils::amount")]
+    chain_id: u64,
+    #[serde(with = "svm_spoke::utils::serde_utils::bytes32")]
+    relay_hash: [u8; 32],
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SlowFillLeafCase {
+    leaf: V3SlowFill,
+    #[serde(with = "svm_spoke::utils::serde_utils::bytes32")]
+    leaf_hash: [u8; 32],
+}
+
+fn fixture() -> Fixture {
+    serde_json::from_str(FIXTURE).unwrap()
+}
+
+#[test]
+fn relay_hashes() {
+    let cases = fixture().relay_hashes;
+    assert!(cases.iter().any(|case| case.relay_data.message.is_empty()), "Empty message should be covered");
+    for case in cases {
+        assert_eq!(get_v3_relay_hash(&case.relay_data, case.chain_id), case.relay_hash, "Relay hash should match");
+    }
+}
+
+#[test]
+fn slow_fill_leaf_hashes() {
+    for case in fixture().slow_fill_leaves {
+        let bytes = case.leaf.to_bytes().unwrap();
+        assert_eq!(bytes[..64], [0u8; 64], "Leaf should start with 64 zero bytes");
+        assert_eq!(case.leaf.to_keccak_hash().unwrap(), case.leaf_hash, "Leaf hash should match");
+    }
+}
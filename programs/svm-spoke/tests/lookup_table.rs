@@ -0,0 +1,263 @@
+// Creates the canonical lookup table of the spoke on a program-test bank, in which the Address Lookup Table program is
+// a builtin, and checks that enabling routes extends it with the addresses of their corridor exactly once, so that
+// relayers can compile fills of any enabled corridor against it. Run with `cargo test -p svm-spoke --features cpi`.
+#![cfg(feature = "cpi")]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        address_lookup_table::{self, instruction::derive_lookup_table_address, state::AddressLookupTable},
+        entrypoint::ProgramResult,
+        instruction::Instruction,
+        program_pack::Pack,
+        system_instruction, system_program,
+        sysvar::slot_hashes::SlotHashes,
+    },
+    AccountDeserialize, InstructionData,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::{self, spl_token},
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use svm_spoke::{
+    accounts,
+    constants::NETWORK_LOCALNET,
+    error::SvmError,
+    instruction,
+    state::LookupTableConfig,
+    utils::{derive_event_authority, get_vault_authority},
+};
+
+const SEED: u64 = 0;
+const CHAIN_ID: u64 = 1234;
+const DESTINATION_CHAIN_ID: u64 = 10;
+const OTHER_DESTINATION_CHAIN_ID: u64 = 42;
+
+// Anchor entrypoints need the accounts to live as long as their data, which native processors don't guarantee.
+fn process_instruction<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    svm_spoke::entry(program_id, accounts, data)
+}
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &svm_spoke::ID).0
+}
+
+async fn try_process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+fn assert_svm_error(result: std::result::Result<(), BanksClientError>, error: SvmError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(0, instruction_error) => {
+            assert_eq!(instruction_error, InstructionError::Custom(error.into()))
+        }
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
+
+async fn lookup_table_addresses(context: &mut ProgramTestContext, lookup_table: Pubkey) -> Vec<Pubkey> {
+    let account = context.banks_client.get_account(lookup_table).await.unwrap().expect("Lookup table should exist");
+    AddressLookupTable::deserialize(&account.data).unwrap().addresses.to_vec()
+}
+
+async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let payer = context.payer.pubkey();
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&token::ID, &mint.pubkey(), &payer, None, 6).unwrap(),
+    ];
+    try_process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+fn create_lookup_table_ix(signer: Pubkey, lookup_table: Pubkey, recent_slot: u64) -> Instruction {
+    let state = find_address(&[b"state", &SEED.to_le_bytes()]);
+    Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::CreateLookupTable {
+            signer,
+            state,
+            lookup_table_config: find_address(&[b"lookup_table_config", &SEED.to_le_bytes()]),
+            lookup_table,
+            address_lookup_table_program: address_lookup_table::program::ID,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateLookupTable { recent_slot }.data(),
+    }
+}
+
+// Enables the route of the mint as the owner, extending the lookup table if one is passed.
+async fn enable_route(
+    context: &mut ProgramTestContext,
+    mint: Pubkey,
+    destination_chain_id: u64,
+    lookup_table: Option<Pubkey>,
+) -> std::result::Result<(), BanksClientError> {
+    let owner = context.payer.pubkey();
+    let state = find_address(&[b"state", &SEED.to_le_bytes()]);
+    let route = find_address(&[b"route", mint.as_ref(), &SEED.to_le_bytes(), &destination_chain_id.to_le_bytes()]);
+    let set_enable_route_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::SetEnableRoute {
+            signer: owner,
+            payer: owner,
+            state,
+            route,
+            vault: get_associated_token_address(&get_vault_authority(&state), &mint),
+            origin_token_mint: mint,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            lookup_table_config: lookup_table.map(|_| find_address(&[b"lookup_table_config", &SEED.to_le_bytes()])),
+            lookup_table,
+            address_lookup_table_program: lookup_table.map(|_| address_lookup_table::program::ID),
+            vault_authority: get_vault_authority(&state),
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::SetEnableRoute {
+            origin_token: mint,
+            destination_chain_id,
+            enabled: true,
+            disable_at: 0,
+            decimal_shift: 0,
+        }
+        .data(),
+    };
+    try_process(context, &[set_enable_route_ix], &[]).await
+}
+
+#[tokio::test]
+async fn lookup_table_tracks_enabled_corridors() {
+    let mut program_test = ProgramTest::new("svm_spoke", svm_spoke::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    let mut context = program_test.start_with_context().await;
+    let owner = context.payer.pubkey();
+    let state = find_address(&[b"state", &SEED.to_le_bytes()]);
+
+    let initialize_ix = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::Initialize { signer: owner, state, system_program: system_program::ID }
+            .to_account_metas(None),
+        data: instruction::Initialize {
+            seed: SEED,
+            initial_number_of_deposits: 0,
+            chain_id: CHAIN_ID,
+            remote_domain: 0,
+            cross_domain_admin: Pubkey::new_unique(),
+            deposit_quote_time_buffer: 3600,
+            fill_deadline_buffer: 4 * 3600,
+            network: NETWORK_LOCALNET,
+        }
+        .data(),
+    };
+    try_process(&mut context, &[initialize_ix], &[]).await.unwrap();
+    let mint = create_mint(&mut context).await;
+
+    // Lookup table addresses are derived from a slot that must still be held by the SlotHashes sysvar.
+    context.warp_to_slot(100).unwrap();
+    let recent_slot = context.banks_client.get_sysvar::<SlotHashes>().await.unwrap().first().unwrap().0;
+    let lookup_table = derive_lookup_table_address(&state, recent_slot).0;
+
+    // Only the owner can create the lookup table.
+    let non_owner = Keypair::new();
+    let fund_ix = system_instruction::transfer(&owner, &non_owner.pubkey(), 1_000_000_000);
+    try_process(&mut context, &[fund_ix], &[]).await.unwrap();
+    let result = try_process(
+        &mut context,
+        &[create_lookup_table_ix(non_owner.pubkey(), lookup_table, recent_slot)],
+        &[&non_owner],
+    )
+    .await;
+    assert_svm_error(result, SvmError::NotOwner);
+
+    // The created table is owned by the state PDA and holds the static addresses of every relayer transaction.
+    try_process(&mut context, &[create_lookup_table_ix(owner, lookup_table, recent_slot)], &[]).await.unwrap();
+    let config_address = find_address(&[b"lookup_table_config", &SEED.to_le_bytes()]);
+    let config_account = context.banks_client.get_account(config_address).await.unwrap().unwrap();
+    let config = LookupTableConfig::try_deserialize(&mut config_account.data.as_slice()).unwrap();
+    assert_eq!(
+        (config.lookup_table, config.recent_slot),
+        (lookup_table, recent_slot),
+        "Config should record the table"
+    );
+    let table_account = context.banks_client.get_account(lookup_table).await.unwrap().unwrap();
+    let table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(table.meta.authority, Some(state), "State should be the table authority");
+    let static_addresses = vec![
+        svm_spoke::ID,
+        state,
+        derive_event_authority(),
+        get_vault_authority(&state),
+        token::ID,
+        anchor_spl::token_2022::ID,
+        associated_token::ID,
+        system_program::ID,
+    ];
+    assert_eq!(table.addresses.to_vec(), static_addresses, "Table should hold the static addresses");
+
+    // Routes enabled without the table accounts leave the table untouched.
+    enable_route(&mut context, mint, OTHER_DESTINATION_CHAIN_ID, None).await.unwrap();
+    assert_eq!(lookup_table_addresses(&mut context, lookup_table).await, static_addresses, "Table should be unchanged");
+
+    // Enabling a route with the table accounts adds its corridor.
+    enable_route(&mut context, mint, DESTINATION_CHAIN_ID, Some(lookup_table)).await.unwrap();
+    let route = find_address(&[b"route", mint.as_ref(), &SEED.to_le_bytes(), &DESTINATION_CHAIN_ID.to_le_bytes()]);
+    let corridor_addresses = [
+        mint,
+        get_associated_token_address(&get_vault_authority(&state), &mint),
+        find_address(&[b"vault", &SEED.to_le_bytes(), mint.as_ref()]),
+        find_address(&[b"vault_liability", &SEED.to_le_bytes(), mint.as_ref()]),
+        route,
+    ];
+    let mut expected_addresses = static_addresses.clone();
+    expected_addresses.extend_from_slice(&corridor_addresses);
+    assert_eq!(
+        lookup_table_addresses(&mut context, lookup_table).await,
+        expected_addresses,
+        "Corridor should be added"
+    );
+
+    // Re-enabling the route doesn't add duplicates, and other routes of the mint only add their route PDA.
+    context.warp_to_slot(101).unwrap();
+    enable_route(&mut context, mint, DESTINATION_CHAIN_ID, Some(lookup_table)).await.unwrap();
+    assert_eq!(lookup_table_addresses(&mut context, lookup_table).await, expected_addresses, "No duplicates expected");
+    enable_route(&mut context, mint, OTHER_DESTINATION_CHAIN_ID, Some(lookup_table)).await.unwrap();
+    let other_route =
+        find_address(&[b"route", mint.as_ref(), &SEED.to_le_bytes(), &OTHER_DESTINATION_CHAIN_ID.to_le_bytes()]);
+    expected_addresses.push(other_route);
+    assert_eq!(lookup_table_addresses(&mut context, lookup_table).await, expected_addresses, "Route should be added");
+
+    // Tables other than the canonical one are rejected.
+    let other_table = Pubkey::new_unique();
+    let result = enable_route(&mut context, mint, DESTINATION_CHAIN_ID, Some(other_table)).await;
+    assert_svm_error(result, SvmError::InvalidLookupTable);
+}
@@ -0,0 +1,84 @@
+// Checks merkle proof verification against the fixture proofs shared with the TypeScript tests
+// (test/svm/Utils.Merkle.ts) that were built with the sorted pair hashing of the OpenZeppelin MerkleProof library used
+// by the EVM HubPool. The fixture is meant to be checked into the EVM repo as well, so that both implementations run
+// the same vectors. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use svm_spoke::{
+    constants::MAX_MERKLE_PROOF_DEPTH,
+    merkle::{self, MerkleProofError},
+    utils::{process_proof, verify_merkle_proof, Keccak256},
+};
+
+const FIXTURE: &str = include_str!("../../../test/svm/fixtures/merkle.json");
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fixture {
+    valid_proofs: Vec<ProofCase>,
+    too_long_proofs: Vec<ProofCase>,
+    invalid_proofs: Vec<ProofCase>,
+}
+
+#[derive(Deserialize)]
+struct Node(#[serde(with = "svm_spoke::utils::serde_utils::bytes32")] [u8; 32]);
+
+#[derive(Deserialize)]
+struct ProofCase {
+    #[serde(default)]
+    description: String,
+    leaf: Node,
+    proof: Vec<Node>,
+    root: Node,
+}
+
+impl ProofCase {
+    fn proof(&self) -> Vec<[u8; 32]> {
+        self.proof.iter().map(|node| node.0).collect()
+    }
+}
+
+fn fixture() -> Fixture {
+    serde_json::from_str(FIXTURE).unwrap()
+}
+
+fn verify(case: &ProofCase) -> Result<(), MerkleProofError> {
+    merkle::verify_proof::<Keccak256>(&case.root.0, &case.leaf.0, &case.proof(), MAX_MERKLE_PROOF_DEPTH)
+}
+
+#[test]
+fn valid_proofs() {
+    let cases = fixture().valid_proofs;
+    assert!(cases.iter().any(|case| case.proof.len() == MAX_MERKLE_PROOF_DEPTH), "Maximum depth should be covered");
+    for case in cases {
+        assert_eq!(process_proof(&case.proof(), &case.leaf.0), case.root.0, "Root should match");
+        assert!(verify_merkle_proof(case.root.0, case.leaf.0, case.proof()).is_ok(), "Proof should verify");
+        assert_eq!(verify(&case), Ok(()), "Proof should verify in the merkle module");
+
+        let mut other_leaf = case.leaf.0;
+        other_leaf[0] ^= 1;
+        assert!(verify_merkle_proof(case.root.0, other_leaf, case.proof()).is_err(), "Other leaf should not verify");
+    }
+}
+
+#[test]
+fn too_long_proofs() {
+    for case in fixture().too_long_proofs {
+        assert!(case.proof.len() > MAX_MERKLE_PROOF_DEPTH, "Proof should exceed the maximum depth");
+        // The root matches, so only the depth limit rejects the proof.
+        assert_eq!(process_proof(&case.proof(), &case.leaf.0), case.root.0, "Root should match");
+        assert!(verify_merkle_proof(case.root.0, case.leaf.0, case.proof()).is_err(), "Proof should be rejected");
+        assert_eq!(verify(&case), Err(MerkleProofError::InvalidProofLength), "Proof length should be rejected");
+    }
+}
+
+#[test]
+fn invalid_proofs() {
+    let cases = fixture().invalid_proofs;
+    assert!(!cases.is_empty(), "Invalid proofs should be covered");
+    for case in cases {
+        assert_eq!(verify(&case), Err(MerkleProofError::InvalidMerkleProof), "{} should not verify", case.description);
+        assert!(verify_merkle_proof(case.root.0, case.leaf.0, case.proof()).is_err(), "{}", case.description);
+    }
+}
@@ -0,0 +1,200 @@
+// Checks the predicate admitting fills and slow fill requests of relays over its matrix of pause, exclusivity window,
+// fill deadline, fill status and caller, so that all four instructions calling it share the same inclusive deadlines.
+// Exclusivity only applies to relays naming an exclusive relayer, which can request slow fills during its own window as
+// on the EVM SpokePool unless the state opts out with exclusive_slow_fills_disabled.
+// Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use anchor_lang::{prelude::*, AccountDeserialize, Discriminator};
+use svm_spoke::{
+    client::{FillStatus, State, V3RelayData},
+    error::{CommonError, SvmError},
+    relay_admission::{check, Admission, CallerRole},
+};
+
+const EXCLUSIVE_RELAYER: Pubkey = Pubkey::new_from_array([1; 32]);
+const OTHER_RELAYER: Pubkey = Pubkey::new_from_array([2; 32]);
+const EXCLUSIVITY_DEADLINE: u32 = 1_000;
+const FILL_DEADLINE: u32 = 2_000;
+const SLOW_FILL_GRACE_SECONDS: u32 = 10;
+
+const STATUSES: [FillStatus; 4] =
+    [FillStatus::Unfilled, FillStatus::RequestedSlowFill, FillStatus::Filled, FillStatus::Expired];
+
+fn state() -> State {
+    let mut data = State::DISCRIMINATOR.to_vec();
+    data.resize(8 + State::INIT_SPACE, 0);
+    let mut state = State::try_deserialize(&mut data.as_slice()).unwrap();
+    state.slow_fill_grace_seconds = SLOW_FILL_GRACE_SECONDS;
+    state
+}
+
+fn relay_data(exclusive_relayer: Pubkey) -> V3RelayData {
+    V3RelayData {
+        depositor: Pubkey::new_from_array([3; 32]),
+        recipient: Pubkey::new_from_array([4; 32]),
+        exclusive_relayer,
+        input_token: Pubkey::new_from_array([5; 32]),
+        output_token: Pubkey::new_from_array([6; 32]),
+        input_amount: 100,
+        output_amount: 99,
+        origin_chain_id: 1,
+        deposit_id: [7; 32],
+        fill_deadline: FILL_DEADLINE,
+        exclusivity_deadline: EXCLUSIVITY_DEADLINE,
+        message: Vec::new(),
+    }
+}
+
+fn fill_admission(status: &FillStatus) -> Result<Admission> {
+    match status {
+        FillStatus::Unfilled => Ok(Admission::FastFill),
+        FillStatus::RequestedSlowFill => Ok(Admission::ReplacedSlowFill),
+        FillStatus::Filled => Err(CommonError::RelayFilled.into()),
+        FillStatus::Expired => Err(SvmError::RelayExpired.into()),
+    }
+}
+
+fn slow_fill_request_admission(status: &FillStatus) -> Result<Admission> {
+    match status {
+        FillStatus::Unfilled => Ok(Admission::SlowFillRequest),
+        FillStatus::RequestedSlowFill => Err(SvmError::SlowFillAlreadyRequested.into()),
+        FillStatus::Filled => Err(SvmError::RelayAlreadyFilled.into()),
+        FillStatus::Expired => Err(SvmError::RelayExpired.into()),
+    }
+}
+
+#[test]
+fn exclusive_relay_matrix() {
+    let state = state();
+    let relay_data = relay_data(EXCLUSIVE_RELAYER);
+    let slow_fill_request_time = EXCLUSIVITY_DEADLINE + SLOW_FILL_GRACE_SECONDS + 1;
+    let times = [
+        0,
+        EXCLUSIVITY_DEADLINE,
+        EXCLUSIVITY_DEADLINE + 1,
+        slow_fill_request_time - 1,
+        slow_fill_request_time,
+        FILL_DEADLINE,
+        FILL_DEADLINE + 1,
+    ];
+
+    for now in times {
+        for status in STATUSES.iter() {
+            let expired = || Err(CommonError::ExpiredFillDeadline.into());
+
+            let expected = if now > FILL_DEADLINE { expired() } else { fill_admission(status) };
+            let admission = check(&relay_data, status, &state, now, CallerRole::Relayer(EXCLUSIVE_RELAYER));
+            assert_eq!(admission, expected, "Exclusive relayer at {}", now);
+
+            let expected = match now {
+                _ if now <= EXCLUSIVITY_DEADLINE => Err(CommonError::NotExclusiveRelayer.into()),
+                _ if now > FILL_DEADLINE => expired(),
+                _ => fill_admission(status),
+            };
+            let admission = check(&relay_data, status, &state, now, CallerRole::Relayer(OTHER_RELAYER));
+            assert_eq!(admission, expected, "Other relayer at {}", now);
+
+            let expected = match now {
+                _ if now < slow_fill_request_time => Err(CommonError::NoSlowFillsInExclusivityWindow.into()),
+                _ if now > FILL_DEADLINE => expired(),
+                _ => slow_fill_request_admission(status),
+            };
+            let admission = check(&relay_data, status, &state, now, CallerRole::SlowFillRequester(OTHER_RELAYER));
+            assert_eq!(admission, expected, "Slow fill requester at {}", now);
+        }
+    }
+}
+
+#[test]
+fn relay_without_exclusive_relayer_matrix() {
+    let state = state();
+    let relay_data = relay_data(Pubkey::default());
+
+    for now in [0, EXCLUSIVITY_DEADLINE, FILL_DEADLINE, FILL_DEADLINE + 1] {
+        for status in STATUSES.iter() {
+            let expired = || Err(CommonError::ExpiredFillDeadline.into());
+
+            let expected = if now > FILL_DEADLINE { expired() } else { fill_admission(status) };
+            let admission = check(&relay_data, status, &state, now, CallerRole::Relayer(OTHER_RELAYER));
+            assert_eq!(admission, expected, "Relayer at {}", now);
+
+            let expected = if now > FILL_DEADLINE { expired() } else { slow_fill_request_admission(status) };
+            let admission = check(&relay_data, status, &state, now, CallerRole::SlowFillRequester(OTHER_RELAYER));
+            assert_eq!(admission, expected, "Slow fill requester at {}", now);
+        }
+    }
+}
+
+#[test]
+fn pause_rejects_all_callers_until_expiry() {
+    let mut state = state();
+    state.paused_fills = true;
+    let relay_data = relay_data(Pubkey::default());
+    let callers = [CallerRole::Relayer(OTHER_RELAYER), CallerRole::SlowFillRequester(OTHER_RELAYER)];
+
+    for caller_role in callers {
+        let admission = check(&relay_data, &FillStatus::Unfilled, &state, 0, caller_role);
+        assert_eq!(admission, Err(CommonError::FillsArePaused.into()), "Pause without expiry for {:?}", caller_role);
+    }
+
+    state.fills_pause_expires_at = EXCLUSIVITY_DEADLINE;
+    for caller_role in callers {
+        let admission = check(&relay_data, &FillStatus::Unfilled, &state, EXCLUSIVITY_DEADLINE - 1, caller_role);
+        assert_eq!(admission, Err(CommonError::FillsArePaused.into()), "Pause before expiry for {:?}", caller_role);
+
+        let admission = check(&relay_data, &FillStatus::Unfilled, &state, EXCLUSIVITY_DEADLINE, caller_role);
+        assert!(admission.is_ok(), "Pause at expiry should admit {:?}", caller_role);
+    }
+}
+
+#[test]
+fn exclusive_relayer_requests_slow_fill_during_its_window() {
+    let mut state = state();
+    let relay_data = relay_data(EXCLUSIVE_RELAYER);
+    let exclusive_requester = CallerRole::SlowFillRequester(EXCLUSIVE_RELAYER);
+
+    for now in [0, EXCLUSIVITY_DEADLINE, FILL_DEADLINE] {
+        for status in STATUSES.iter() {
+            let admission = check(&relay_data, status, &state, now, exclusive_requester);
+            assert_eq!(admission, slow_fill_request_admission(status), "Exclusive requester at {}", now);
+        }
+    }
+    let admission = check(&relay_data, &FillStatus::Unfilled, &state, FILL_DEADLINE + 1, exclusive_requester);
+    assert_eq!(
+        admission,
+        Err(CommonError::ExpiredFillDeadline.into()),
+        "Fill deadline applies to exclusive requesters"
+    );
+
+    // Opting out subjects exclusive relayers to the same window and grace period as other requesters.
+    state.exclusive_slow_fills_disabled = true;
+    let slow_fill_request_time = EXCLUSIVITY_DEADLINE + SLOW_FILL_GRACE_SECONDS + 1;
+    for now in [0, EXCLUSIVITY_DEADLINE, slow_fill_request_time - 1] {
+        let admission = check(&relay_data, &FillStatus::Unfilled, &state, now, exclusive_requester);
+        assert_eq!(admission, Err(CommonError::NoSlowFillsInExclusivityWindow.into()), "Opted out at {}", now);
+    }
+    let admission = check(&relay_data, &FillStatus::Unfilled, &state, slow_fill_request_time, exclusive_requester);
+    assert_eq!(admission, Ok(Admission::SlowFillRequest), "Opted out exclusive requester after the window");
+}
+
+#[test]
+fn exclusivity_boundaries_of_slow_fill_requests() {
+    let state = state();
+    let unfilled = &FillStatus::Unfilled;
+    let requester = CallerRole::SlowFillRequester(OTHER_RELAYER);
+
+    // Relays without an exclusive relayer have no window, even with a non zero exclusivity deadline.
+    let mut without_exclusive_relayer = relay_data(Pubkey::default());
+    without_exclusive_relayer.exclusivity_deadline = FILL_DEADLINE;
+    let admission = check(&without_exclusive_relayer, unfilled, &state, 0, requester);
+    assert_eq!(admission, Ok(Admission::SlowFillRequest), "Zero relayer with a non zero deadline");
+
+    // Relays naming an exclusive relayer whose exclusivity deadline has already passed only wait for the grace period.
+    let mut expired_exclusivity = relay_data(EXCLUSIVE_RELAYER);
+    expired_exclusivity.exclusivity_deadline = 0;
+    let admission = check(&expired_exclusivity, unfilled, &state, SLOW_FILL_GRACE_SECONDS, requester);
+    assert_eq!(admission, Err(CommonError::NoSlowFillsInExclusivityWindow.into()), "Expired deadline in grace period");
+    let admission = check(&expired_exclusivity, unfilled, &state, SLOW_FILL_GRACE_SECONDS + 1, requester);
+    assert_eq!(admission, Ok(Admission::SlowFillRequest), "Expired deadline after the grace period");
+}
@@ -0,0 +1,92 @@
+// Pins the Borsh encoding of the relay, leaf and event types decoded off-chain (test/svm/fixtures/schema.json) for the
+// values of the canonical JSON fixture (test/svm/fixtures/serde.json), so that reordering or retyping any of their
+// fields fails here instead of silently breaking indexers. Events are pinned with their discriminator, as it prefixes
+// their CPI event data. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use anchor_lang::{AnchorDeserialize, AnchorSerialize, Discriminator, Event};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use svm_spoke::{
+    client::{RelayerRefundLeaf, V3RelayData, V3SlowFill},
+    event::{
+        ExecutedRelayerRefundRoot, ExecutedRelayerRefundRootChunk, FilledV3Relay, RequestedV3SlowFill,
+        RequestedV3SlowFill2, V3FundsDeposited, EXECUTED_RELAYER_REFUND_ROOT_CHUNK_DISCRIMINATOR,
+        EXECUTED_RELAYER_REFUND_ROOT_DISCRIMINATOR, FILLED_V3_RELAY_DISCRIMINATOR,
+        REQUESTED_V3_SLOW_FILL_2_DISCRIMINATOR, REQUESTED_V3_SLOW_FILL_DISCRIMINATOR, V3_FUNDS_DEPOSITED_DISCRIMINATOR,
+    },
+};
+
+const FIXTURE: &str = include_str!("../../../test/svm/fixtures/schema.json");
+const VALUES: &str = include_str!("../../../test/svm/fixtures/serde.json");
+
+#[derive(Deserialize)]
+struct Dump(#[serde(with = "svm_spoke::utils::serde_utils::bytes")] Vec<u8>);
+
+fn fixture(key: &str) -> Vec<u8> {
+    let mut fixture: BTreeMap<String, Dump> = serde_json::from_str(FIXTURE).unwrap();
+    fixture.remove(key).unwrap_or_else(|| panic!("{} should have a schema fixture", key)).0
+}
+
+fn value<T: DeserializeOwned>(key: &str) -> T {
+    let values: Value = serde_json::from_str(VALUES).unwrap();
+    serde_json::from_value(values[key].clone()).unwrap()
+}
+
+// Checks that the canonical value serializes to the fixture and that the fixture decodes back to it.
+fn assert_schema<T: AnchorSerialize + AnchorDeserialize + DeserializeOwned>(key: &str) {
+    let expected = fixture(key);
+    assert_eq!(value::<T>(key).try_to_vec().unwrap(), expected, "{} layout changed", key);
+
+    let decoded = T::try_from_slice(&expected).unwrap();
+    assert_eq!(decoded.try_to_vec().unwrap(), expected, "{} fixture should round trip", key);
+}
+
+fn assert_event_schema<T: Event + Discriminator + AnchorDeserialize + DeserializeOwned>(
+    key: &str,
+    discriminator: [u8; 8],
+) {
+    let expected = fixture(key);
+    assert_eq!(T::DISCRIMINATOR, discriminator, "{} discriminator constant should match", key);
+    assert_eq!(expected[..8], discriminator, "{} discriminator changed", key);
+    assert_eq!(value::<T>(key).data(), expected, "{} layout changed", key);
+
+    let decoded = T::try_from_slice(&expected[8..]).unwrap();
+    assert_eq!(decoded.data(), expected, "{} fixture should round trip", key);
+}
+
+#[test]
+fn covers_every_canonical_value() {
+    let fixture: BTreeMap<String, Dump> = serde_json::from_str(FIXTURE).unwrap();
+    let values: BTreeMap<String, Value> = serde_json::from_str(VALUES).unwrap();
+    assert!(fixture.keys().eq(values.keys()), "Every canonical value should have a schema fixture");
+}
+
+#[test]
+fn relay_and_leaf_schemas() {
+    assert_schema::<V3RelayData>("v3RelayData");
+    assert_schema::<V3SlowFill>("v3SlowFill");
+    assert_schema::<RelayerRefundLeaf>("relayerRefundLeaf");
+
+    // Slow fill leaves are borsh encoded V3SlowFill behind the 64 zero bytes of their hash pre-image.
+    let slow_fill: V3SlowFill = value("v3SlowFill");
+    assert_eq!(slow_fill.to_bytes().unwrap()[64..], fixture("v3SlowFill"));
+}
+
+#[test]
+fn event_schemas() {
+    assert_event_schema::<V3FundsDeposited>("v3FundsDeposited", V3_FUNDS_DEPOSITED_DISCRIMINATOR);
+    assert_event_schema::<FilledV3Relay>("filledV3Relay", FILLED_V3_RELAY_DISCRIMINATOR);
+    assert_event_schema::<RequestedV3SlowFill>("requestedV3SlowFill", REQUESTED_V3_SLOW_FILL_DISCRIMINATOR);
+    assert_event_schema::<RequestedV3SlowFill2>("requestedV3SlowFill2", REQUESTED_V3_SLOW_FILL_2_DISCRIMINATOR);
+    assert_event_schema::<ExecutedRelayerRefundRoot>(
+        "executedRelayerRefundRoot",
+        EXECUTED_RELAYER_REFUND_ROOT_DISCRIMINATOR,
+    );
+    assert_event_schema::<ExecutedRelayerRefundRootChunk>(
+        "executedRelayerRefundRootChunk",
+        EXECUTED_RELAYER_REFUND_ROOT_CHUNK_DISCRIMINATOR,
+    );
+}
@@ -0,0 +1,102 @@
+// Round trips the canonical JSON fixture shared with the TypeScript SDK (test/svm/Utils.CanonicalJson.ts). Run with
+// `cargo test -p svm-spoke --features serde`.
+#![cfg(feature = "serde")]
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use svm_spoke::{
+    client::{RelayerRefundLeaf, V3RelayData, V3SlowFill},
+    event::{
+        ExecutedRelayerRefundRoot, ExecutedRelayerRefundRootChunk, FillType, FilledV3Relay, RequestedV3SlowFill,
+        RequestedV3SlowFill2, V3FundsDeposited,
+    },
+};
+
+const FIXTURE: &str = include_str!("../../../test/svm/fixtures/serde.json");
+
+fn fixture(key: &str) -> Value {
+    let fixture: Value = serde_json::from_str(FIXTURE).unwrap();
+    fixture[key].clone()
+}
+
+// Deserializes the fixture and checks that serializing it back yields the same JSON.
+fn round_trip<T: Serialize + DeserializeOwned>(key: &str) -> T {
+    let expected = fixture(key);
+    let value: T = serde_json::from_value(expected.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), expected, "{} should round trip", key);
+    value
+}
+
+#[test]
+fn v3_relay_data() {
+    let relay_data: V3RelayData = round_trip("v3RelayData");
+    assert_eq!(relay_data.depositor, Pubkey::from_str("DnLjPzpMCW2CF99URhGF3jDYnVRcCJMjUWsbPb4xVoBn").unwrap());
+    assert_eq!(relay_data.exclusive_relayer, Pubkey::default());
+    assert_eq!(relay_data.output_amount, u64::MAX);
+    assert_eq!(relay_data.deposit_id[0], 42);
+    assert_eq!(relay_data.message, vec![0x12, 0x34]);
+}
+
+#[test]
+fn v3_slow_fill() {
+    let slow_fill: V3SlowFill = round_trip("v3SlowFill");
+    assert_eq!(slow_fill.chain_id, 34268394551451);
+    assert_eq!(slow_fill.updated_output_amount, 999_000);
+}
+
+#[test]
+fn relayer_refund_leaf() {
+    let leaf: RelayerRefundLeaf = round_trip("relayerRefundLeaf");
+    assert_eq!(leaf.refund_amounts, vec![500, u64::MAX]);
+    assert_eq!(leaf.refund_addresses.len(), 2);
+}
+
+#[test]
+fn events() {
+    let deposit: V3FundsDeposited = round_trip("v3FundsDeposited");
+    assert!(deposit.message.is_empty());
+    assert_eq!(deposit.token_decimals, 6);
+    assert!(!deposit.unsafe_deposit_id);
+    assert_eq!(deposit.depositor_nonce, Some(5));
+    assert_eq!(deposit.message_hash, None);
+    assert_eq!(deposit.dust_refunded, None);
+
+    let fill: FilledV3Relay = round_trip("filledV3Relay");
+    assert!(fill.relay_execution_info.fill_type == FillType::SlowFill);
+    assert_eq!(fill.relay_execution_info.escrow, None);
+    assert!(!fill.relay_execution_info.emergency_execution);
+    assert_eq!(fill.relay_execution_info.origin_tx_ref, None);
+    assert!(!fill.relay_execution_info.message_only);
+    assert_eq!(fill.message_hash, [0xab; 32]);
+    assert_eq!(fill.token_decimals, 9);
+    assert_eq!(fill.relay_hash, [0xcd; 32]);
+
+    let request: RequestedV3SlowFill = round_trip("requestedV3SlowFill");
+    assert_eq!(request.relay_hash, [0xcd; 32]);
+    assert_eq!(request.current_time + request.seconds_until_fill_deadline, request.fill_deadline);
+    let request: RequestedV3SlowFill2 = round_trip("requestedV3SlowFill2");
+    assert_eq!(request.destination_chain_id, 34268394551451);
+    assert_eq!(request.requester, request.recipient);
+    assert_eq!(request.relay_hash, [0xcd; 32]);
+    let _: ExecutedRelayerRefundRoot = round_trip("executedRelayerRefundRoot");
+    let chunk: ExecutedRelayerRefundRootChunk = round_trip("executedRelayerRefundRootChunk");
+    assert_eq!((chunk.chunk_index, chunk.total_chunks), (1, 2));
+}
+
+#[test]
+fn rejects_non_canonical_values() {
+    let mut relay_data = fixture("v3RelayData");
+    relay_data["inputAmount"] = Value::from(1_000_000u64);
+    assert!(serde_json::from_value::<V3RelayData>(relay_data).is_err(), "Amounts must be strings");
+
+    let mut relay_data = fixture("v3RelayData");
+    relay_data["message"] = Value::from("1234");
+    assert!(serde_json::from_value::<V3RelayData>(relay_data).is_err(), "Bytes must be 0x prefixed");
+
+    let mut relay_data = fixture("v3RelayData");
+    relay_data["depositId"] = Value::from("0x2a");
+    assert!(serde_json::from_value::<V3RelayData>(relay_data).is_err(), "Deposit id must have 32 bytes");
+}
@@ -0,0 +1,186 @@
+// Initializes states of zero and non-zero seeds on a program-test bank and checks that single-state production builds
+// reject the latter, both at initialization and in the seeds constraints of states written straight into the bank,
+// that other builds without the test feature still reject them at initialization, and that test builds keep arbitrary
+// seeds and never mark their states canonical. Run with `cargo test -p svm-spoke --features serde,single-state` for
+// the production configuration, with `--features serde` for plain builds and with `--features serde,test` for tests.
+#![cfg(feature = "serde")]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction, system_program},
+    AccountDeserialize, InstructionData,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use svm_spoke::{
+    accounts,
+    client::{get_state_address, State},
+    constants::NETWORK_LOCALNET,
+    instruction,
+    utils::derive_event_authority,
+};
+
+const CHAIN_ID: u64 = 1234;
+const OTHER_SEED: u64 = 5;
+#[cfg(any(feature = "single-state", feature = "test"))]
+const WRITTEN_SEED: u64 = 7;
+
+// Anchor entrypoints need the accounts to live as long as their data, which native processors don't guarantee.
+fn process_instruction<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    svm_spoke::entry(program_id, accounts, data)
+}
+
+async fn start() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new("svm_spoke", svm_spoke::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    program_test.start_with_context().await
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+#[cfg(any(feature = "single-state", not(feature = "test")))]
+fn assert_error_code(result: std::result::Result<(), BanksClientError>, code: u32) {
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(0, instruction_error) => {
+            assert_eq!(instruction_error, InstructionError::Custom(code))
+        }
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
+
+async fn initialize(context: &mut ProgramTestContext, seed: u64) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::Initialize {
+            signer: context.payer.pubkey(),
+            state: get_state_address(seed),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Initialize {
+            seed,
+            initial_number_of_deposits: 0,
+            chain_id: CHAIN_ID,
+            remote_domain: 0,
+            cross_domain_admin: Pubkey::new_unique(),
+            deposit_quote_time_buffer: 3600,
+            fill_deadline_buffer: 4 * 3600,
+            network: NETWORK_LOCALNET,
+        }
+        .data(),
+    };
+    process(context, instruction).await
+}
+
+async fn fetch_state(context: &mut ProgramTestContext, seed: u64) -> State {
+    let account = context.banks_client.get_account(get_state_address(seed)).await.unwrap().expect("State should exist");
+    State::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+// Writes a state of the current version owned by the owner straight into the bank, as a test build would have created.
+#[cfg(any(feature = "single-state", feature = "test"))]
+async fn write_state(context: &mut ProgramTestContext, owner: Pubkey, seed: u64) {
+    use anchor_lang::{AccountSerialize, Discriminator};
+    use solana_sdk::account::Account as SolanaAccount;
+    use svm_spoke::constants::{CURRENT_STATE_VERSION, DISCRIMINATOR_SIZE};
+
+    let mut data = State::DISCRIMINATOR.to_vec();
+    data.resize(DISCRIMINATOR_SIZE + State::INIT_SPACE, 0);
+    let mut state = State::try_deserialize(&mut data.as_slice()).unwrap();
+    state.owner = owner;
+    state.seed = seed;
+    state.chain_id = CHAIN_ID;
+    state.version = CURRENT_STATE_VERSION;
+
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data.resize(DISCRIMINATOR_SIZE + State::INIT_SPACE, 0);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = SolanaAccount {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: svm_spoke::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&get_state_address(seed), &account.into());
+}
+
+fn pause_deposits_ix(owner: Pubkey, seed: u64) -> Instruction {
+    Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::PauseDeposits {
+            signer: owner,
+            state: get_state_address(seed),
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::PauseDeposits { pause: true }.data(),
+    }
+}
+
+#[cfg(feature = "single-state")]
+#[tokio::test]
+async fn production_builds_pin_the_state_seed() {
+    let mut context = start().await;
+    let owner = context.payer.pubkey();
+
+    // Non-zero seeds are rejected at initialization.
+    let result = initialize(&mut context, OTHER_SEED).await;
+    assert_error_code(result, svm_spoke::error::SvmError::InvalidProductionSeed.into());
+    assert!(context.banks_client.get_account(get_state_address(OTHER_SEED)).await.unwrap().is_none());
+
+    // The state of seed 0 is the canonical one.
+    initialize(&mut context, 0).await.unwrap();
+    assert!(fetch_state(&mut context, 0).await.canonical, "State of seed 0 should be canonical");
+    process(&mut context, pause_deposits_ix(owner, 0)).await.unwrap();
+
+    // States of other seeds, e.g. created by a dev build of the same program, fail the seeds constraints.
+    write_state(&mut context, owner, WRITTEN_SEED).await;
+    let result = process(&mut context, pause_deposits_ix(owner, WRITTEN_SEED)).await;
+    assert_error_code(result, anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+}
+
+#[cfg(not(any(feature = "single-state", feature = "test")))]
+#[tokio::test]
+async fn non_test_builds_reject_non_zero_seeds() {
+    let mut context = start().await;
+    let owner = context.payer.pubkey();
+
+    let result = initialize(&mut context, OTHER_SEED).await;
+    assert_error_code(result, svm_spoke::error::SvmError::InvalidProductionSeed.into());
+    assert!(context.banks_client.get_account(get_state_address(OTHER_SEED)).await.unwrap().is_none());
+
+    // Only single-state builds mark the state of seed 0 canonical.
+    initialize(&mut context, 0).await.unwrap();
+    assert!(!fetch_state(&mut context, 0).await.canonical, "Only single-state builds should mark states canonical");
+    process(&mut context, pause_deposits_ix(owner, 0)).await.unwrap();
+}
+
+#[cfg(all(feature = "test", not(feature = "single-state")))]
+#[tokio::test]
+async fn test_builds_keep_arbitrary_seeds() {
+    let mut context = start().await;
+    let owner = context.payer.pubkey();
+
+    // States of any seed are initialized and used, but none of them is canonical.
+    for seed in [0, OTHER_SEED] {
+        initialize(&mut context, seed).await.unwrap();
+        assert!(!fetch_state(&mut context, seed).await.canonical, "Test builds should not initialize canonical states");
+        process(&mut context, pause_deposits_ix(owner, seed)).await.unwrap();
+    }
+    write_state(&mut context, owner, WRITTEN_SEED).await;
+    process(&mut context, pause_deposits_ix(owner, WRITTEN_SEED)).await.unwrap();
+}
@@ -0,0 +1,176 @@
+// Writes the checked-in state PDA of the baseline layout (test/svm/fixtures/account_layouts.json), created before the
+// version was recorded, straight into a program-test bank, checks that it is rejected until migrate_state reallocates
+// it to the current layout, and then requests a slow fill against the migrated state. Its seed is not 0, so it runs on
+// dev builds only: run with `cargo test -p svm-spoke --features serde,test`.
+#![cfg(all(feature = "serde", feature = "test", not(feature = "single-state")))]
+
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction, system_program},
+    AccountDeserialize, InstructionData,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use serde::Deserialize;
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    signer::keypair::keypair_from_seed,
+    transaction::{Transaction, TransactionError},
+};
+use svm_spoke::{
+    accounts,
+    client::{
+        build_request_slow_fill_ix, get_fill_status_address, get_state_address, FillStatus, FillStatusAccount,
+        RequestSlowFillOptions, SpokeConfig, State, V3RelayData,
+    },
+    constants::{CURRENT_STATE_VERSION, DISCRIMINATOR_SIZE, NETWORK_LOCALNET},
+    error::SvmError,
+    instruction,
+    utils::{derive_event_authority, get_v3_relay_hash},
+};
+
+const FIXTURE: &str = include_str!("../../../test/svm/fixtures/account_layouts.json");
+
+// Fields of the baseline state dump, whose owner is the keypair derived from OWNER_KEYPAIR_SEED.
+const SEED: u64 = 7;
+const CHAIN_ID: u64 = 1234;
+const CURRENT_TIME: u32 = 1760400000;
+const OWNER_KEYPAIR_SEED: [u8; 32] = [10; 32];
+const BASELINE_STATE_SIZE: usize = DISCRIMINATOR_SIZE + 106; // 12 fields, up to fill_deadline_buffer.
+
+#[derive(Deserialize)]
+struct Fixture {
+    state: Vec<Dump>,
+}
+
+#[derive(Deserialize)]
+struct Dump(#[serde(with = "svm_spoke::utils::serde_utils::bytes")] Vec<u8>);
+
+// The first state dump is the baseline layout.
+fn baseline_state_data() -> Vec<u8> {
+    let fixture: Fixture = serde_json::from_str(FIXTURE).unwrap();
+    fixture.state.into_iter().next().unwrap().0
+}
+
+// Anchor entrypoints need the accounts to live as long as their data, which native processors don't guarantee.
+fn process_instruction<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    svm_spoke::entry(program_id, accounts, data)
+}
+
+// Processes the instruction paid by the context payer with a new blockhash, so that retried instructions are not
+// deduplicated.
+async fn process(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+fn assert_error(result: std::result::Result<(), BanksClientError>, error: impl Into<u32>) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(0, InstructionError::Custom(code)) => assert_eq!(code, error.into()),
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
+
+fn migrate_state_ix(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::MigrateState {
+            signer,
+            state: get_state_address(SEED),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority(),
+            program: svm_spoke::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::MigrateState { _seed: SEED }.data(),
+    }
+}
+
+#[tokio::test]
+async fn migrates_unversioned_state() {
+    let mut program_test = ProgramTest::new("svm_spoke", svm_spoke::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    let mut context = program_test.start_with_context().await;
+
+    let owner_keypair = keypair_from_seed(&OWNER_KEYPAIR_SEED).unwrap();
+    let owner = owner_keypair.pubkey();
+    // The owner pays the rent of the migration and of the slow fill request.
+    context.set_account(&owner, &SolanaAccount::new(1_000_000_000, 0, &system_program::ID).into());
+    let state = get_state_address(SEED);
+    let data = baseline_state_data();
+    assert_eq!(data.len(), BASELINE_STATE_SIZE, "Fixture should hold the baseline state");
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &state,
+        &SolanaAccount {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: svm_spoke::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+
+    let relay_data = V3RelayData {
+        depositor: Pubkey::new_unique(),
+        recipient: Pubkey::new_unique(),
+        exclusive_relayer: Pubkey::default(),
+        input_token: Pubkey::new_unique(),
+        output_token: Pubkey::new_unique(),
+        input_amount: 1_000,
+        output_amount: 900,
+        origin_chain_id: 1,
+        deposit_id: [1; 32],
+        fill_deadline: CURRENT_TIME + 3600,
+        exclusivity_deadline: 0,
+        message: Vec::new(),
+    };
+    let spoke = SpokeConfig { seed: SEED, chain_id: CHAIN_ID, ..SpokeConfig::default() };
+    let request_options = RequestSlowFillOptions { spoke, ..RequestSlowFillOptions::default() };
+    let request_ix = build_request_slow_fill_ix(&relay_data, &owner, &request_options);
+
+    // Baseline states fail closed instead of being read with their layout, as they are too short for the current one.
+    let result = process(&mut context, request_ix.clone(), &[&owner_keypair]).await;
+    assert_error(result, ErrorCode::AccountDidNotDeserialize);
+
+    // Only the owner can migrate the state.
+    let other = Keypair::new();
+    assert_error(process(&mut context, migrate_state_ix(other.pubkey()), &[&other]).await, SvmError::NotOwner);
+    process(&mut context, migrate_state_ix(owner), &[&owner_keypair]).await.unwrap();
+
+    let account = context.banks_client.get_account(state).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), DISCRIMINATOR_SIZE + State::INIT_SPACE, "State should be reallocated");
+    assert!(rent.is_exempt(account.lamports, account.data.len()), "State should stay rent exempt");
+    let migrated = State::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(migrated.version, CURRENT_STATE_VERSION, "Version should be bumped");
+    assert_eq!((migrated.owner, migrated.seed, migrated.chain_id), (owner, SEED, CHAIN_ID), "Fields should be kept");
+    assert_eq!(migrated.current_time, CURRENT_TIME, "Fields should be kept");
+    assert_eq!(migrated.network, NETWORK_LOCALNET, "Network should be derived from the chain ID");
+    assert!(!migrated.canonical, "Dev builds should not migrate canonical states");
+
+    // Each version is migrated once.
+    assert_error(
+        process(&mut context, migrate_state_ix(owner), &[&owner_keypair]).await,
+        SvmError::StateAlreadyMigrated,
+    );
+
+    // Slow fills are requested as usual against the migrated state.
+    process(&mut context, request_ix, &[&owner_keypair]).await.unwrap();
+    let relay_hash = get_v3_relay_hash(&relay_data, CHAIN_ID);
+    let account = context.banks_client.get_account(get_fill_status_address(&relay_hash)).await.unwrap().unwrap();
+    let fill_status = FillStatusAccount::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert!(fill_status.status == FillStatus::RequestedSlowFill, "Slow fill should be requested");
+    assert_eq!(fill_status.requester, owner, "Requester should be recorded");
+}
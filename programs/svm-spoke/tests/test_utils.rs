@@ -0,0 +1,123 @@
+// Checks that production builds reject the test-only instructions, which the program macro dispatches in every build,
+// even when the owner calls them with valid accounts. Run with `cargo test -p svm-spoke --features serde`.
+#![cfg(all(feature = "serde", not(feature = "test")))]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction, system_program},
+    AccountDeserialize, AccountSerialize, Discriminator, InstructionData,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use svm_spoke::{
+    accounts,
+    client::{get_fill_status_address, get_state_address, FillStatus, State},
+    constants::{CURRENT_STATE_VERSION, DISCRIMINATOR_SIZE},
+    error::SvmError,
+    instruction,
+};
+
+const ROOT_BUNDLE_ID: u32 = 3;
+
+// Anchor entrypoints need the accounts to live as long as their data, which native processors don't guarantee.
+fn process_instruction<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    svm_spoke::entry(program_id, accounts, data)
+}
+
+async fn start() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new("svm_spoke", svm_spoke::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    program_test.start_with_context().await
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+fn assert_error_code(result: std::result::Result<(), BanksClientError>, error: SvmError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(0, instruction_error) => {
+            assert_eq!(instruction_error, InstructionError::Custom(error.into()))
+        }
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
+
+// Writes the state of seed 0 owned by the owner straight into the bank, so that all account constraints pass.
+async fn write_state(context: &mut ProgramTestContext, owner: Pubkey) {
+    let mut data = State::DISCRIMINATOR.to_vec();
+    data.resize(DISCRIMINATOR_SIZE + State::INIT_SPACE, 0);
+    let mut state = State::try_deserialize(&mut data.as_slice()).unwrap();
+    state.owner = owner;
+    state.version = CURRENT_STATE_VERSION;
+
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data.resize(DISCRIMINATOR_SIZE + State::INIT_SPACE, 0);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = SolanaAccount {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: svm_spoke::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&get_state_address(0), &account.into());
+}
+
+#[tokio::test]
+async fn test_only_instructions_are_rejected() {
+    let mut context = start().await;
+    let owner = context.payer.pubkey();
+    write_state(&mut context, owner).await;
+    let state = get_state_address(0);
+
+    let set_current_time = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::SetCurrentTime { signer: owner, state }.to_account_metas(None),
+        data: instruction::SetCurrentTime { new_time: 42 }.data(),
+    };
+    assert_error_code(process(&mut context, set_current_time).await, SvmError::CannotSetCurrentTime);
+
+    let root_bundle = Pubkey::find_program_address(
+        &[b"root_bundle", 0u64.to_le_bytes().as_ref(), ROOT_BUNDLE_ID.to_le_bytes().as_ref()],
+        &svm_spoke::ID,
+    )
+    .0;
+    let set_root_bundle = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::SetRootBundle { signer: owner, state, root_bundle, system_program: system_program::ID }
+            .to_account_metas(None),
+        data: instruction::SetRootBundle {
+            root_bundle_id: ROOT_BUNDLE_ID,
+            relayer_refund_root: [1; 32],
+            slow_relay_root: [2; 32],
+        }
+        .data(),
+    };
+    assert_error_code(process(&mut context, set_root_bundle).await, SvmError::TestOnlyInstruction);
+    assert!(context.banks_client.get_account(root_bundle).await.unwrap().is_none(), "Root bundle should not exist");
+
+    let relay_hash = [3; 32];
+    let fill_status = get_fill_status_address(&relay_hash);
+    let set_fill_status = Instruction {
+        program_id: svm_spoke::ID,
+        accounts: accounts::SetFillStatus { signer: owner, state, fill_status, system_program: system_program::ID }
+            .to_account_metas(None),
+        data: instruction::SetFillStatus { _relay_hash: relay_hash, status: FillStatus::Filled }.data(),
+    };
+    assert_error_code(process(&mut context, set_fill_status).await, SvmError::TestOnlyInstruction);
+    assert!(context.banks_client.get_account(fill_status).await.unwrap().is_none(), "Fill status should not exist");
+}
@@ -1,8 +1,16 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    InstructionData,
+};
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
 use svm_spoke::{
+    client::HandleReceiveMessageParams,
     constants::DISCRIMINATOR_SIZE,
-    error::CommonError,
-    utils::{is_claimed, process_proof, set_claimed},
+    utils::{
+        decode_compact_relay_data, get_v3_relay_hash, is_claimed, normalize_output_amount, set_claimed,
+        verify_merkle_proof,
+    },
 };
 
 declare_id!("8tsEfDSiE4WUMf97oyyyasLAvWwjeRZb2GByh4w7HckA");
@@ -71,12 +79,30 @@ pub mod test {
     #[derive(Accounts)]
     pub struct Verify {}
     pub fn verify(_ctx: Context<Verify>, root: [u8; 32], leaf: [u8; 32], proof: Vec<[u8; 32]>) -> Result<()> {
-        let computed_root = process_proof(&proof, &leaf);
-        if computed_root != root {
-            return err!(CommonError::InvalidMerkleProof);
-        }
+        verify_merkle_proof(root, leaf, proof)
+    }
 
-        Ok(())
+    // Test compact relay data.
+    #[derive(Accounts)]
+    pub struct DecodeCompactRelayData {}
+    pub fn test_compact_relay_hash(
+        _ctx: Context<DecodeCompactRelayData>,
+        compact_relay_data: Vec<u8>,
+        chain_id: u64,
+    ) -> Result<[u8; 32]> {
+        let relay_data = decode_compact_relay_data(&compact_relay_data)?;
+        Ok(get_v3_relay_hash(&relay_data, chain_id))
+    }
+
+    // Test output amount normalization.
+    #[derive(Accounts)]
+    pub struct NormalizeOutputAmount {}
+    pub fn test_normalize_output_amount(
+        _ctx: Context<NormalizeOutputAmount>,
+        amount: u64,
+        decimal_shift: i8,
+    ) -> Result<u64> {
+        normalize_output_amount(amount, decimal_shift)
     }
 
     #[derive(Accounts)]
@@ -90,6 +116,98 @@ pub mod test {
         emit!(TestEvent { message: large_message });
         Ok(())
     }
+
+    // Mock event forwarder recording the received events in its logs.
+    #[derive(Accounts)]
+    pub struct ForwardEvent {}
+    #[event]
+    pub struct ForwardedEvent {
+        event: Vec<u8>,
+    }
+    pub fn forward_event(_ctx: Context<ForwardEvent>, event: Vec<u8>) -> Result<()> {
+        emit!(ForwardedEvent { event });
+        Ok(())
+    }
+
+    // Mock admin messenger relaying HubPool messages to svm_spoke without attestations or nonce checks.
+    #[derive(Accounts)]
+    pub struct RelayAdminMessage<'info> {
+        /// CHECK: PDA signing the relayed message, same seeds as the CCTP Message Transmitter authority.
+        #[account(seeds = [b"message_transmitter_authority", svm_spoke::ID.as_ref()], bump)]
+        pub authority_pda: UncheckedAccount<'info>,
+        /// CHECK: svm_spoke program receiving the message.
+        #[account(address = svm_spoke::ID)]
+        pub receiver: UncheckedAccount<'info>,
+    }
+    pub fn relay_admin_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, RelayAdminMessage<'info>>,
+        remote_domain: u32,
+        sender: Pubkey,
+        message_body: Vec<u8>,
+    ) -> Result<()> {
+        let authority_bump = ctx.bumps.authority_pda;
+        let params = HandleReceiveMessageParams { remote_domain, sender, message_body, authority_bump };
+        let data = svm_spoke::instruction::HandleReceiveMessage { params }.data();
+
+        let mut accounts = vec![AccountMeta::new_readonly(ctx.accounts.authority_pda.key(), true)];
+        for account in ctx.remaining_accounts {
+            if account.is_writable {
+                accounts.push(AccountMeta::new(account.key(), account.is_signer));
+            } else {
+                accounts.push(AccountMeta::new_readonly(account.key(), account.is_signer));
+            }
+        }
+        let account_infos = [
+            &[ctx.accounts.authority_pda.to_account_info(), ctx.accounts.receiver.to_account_info()],
+            ctx.remaining_accounts,
+        ]
+        .concat();
+
+        let authority_seeds: &[&[&[u8]]] =
+            &[&[b"message_transmitter_authority", svm_spoke::ID.as_ref(), &[authority_bump]]];
+        invoke_signed(&Instruction { program_id: svm_spoke::ID, accounts, data }, &account_infos, authority_seeds)?;
+
+        Ok(())
+    }
+
+    // Mock swap program taking amount_in from the user into the pool and sending amount_out from the pool, so that
+    // tests control the swap output received by deposit_v3_with_swap.
+    #[derive(Accounts)]
+    pub struct MockSwap<'info> {
+        pub user: Signer<'info>,
+        #[account(mut)]
+        pub user_token_account: Account<'info, TokenAccount>,
+        #[account(mut)]
+        pub pool_in_token_account: Account<'info, TokenAccount>,
+        /// CHECK: PDA owning the pool token accounts.
+        #[account(seeds = [b"swap_pool"], bump)]
+        pub pool_authority: UncheckedAccount<'info>,
+        #[account(mut)]
+        pub pool_out_token_account: Account<'info, TokenAccount>,
+        #[account(mut)]
+        pub destination: Account<'info, TokenAccount>,
+        pub token_program: Program<'info, Token>,
+    }
+    pub fn mock_swap(ctx: Context<MockSwap>, amount_in: u64, amount_out: u64) -> Result<()> {
+        let transfer_in = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.pool_in_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_in), amount_in)?;
+
+        let pool_seeds: &[&[&[u8]]] = &[&[b"swap_pool", &[ctx.bumps.pool_authority]]];
+        let transfer_out = Transfer {
+            from: ctx.accounts.pool_out_token_account.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        };
+        let cpi_context =
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_out, pool_seeds);
+        transfer(cpi_context, amount_out)?;
+
+        Ok(())
+    }
 }
 
 // State.
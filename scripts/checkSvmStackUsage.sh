@@ -0,0 +1,22 @@
+#!/bin/bash
+
+# The SBF toolchain only warns about functions exceeding the 4KB stack frame, while such programs can fail at runtime.
+# Build the SVM programs and fail on any of these warnings, so that stack usage regressions are caught in CI.
+PROGRAMS=("svm-spoke" "multicall-handler")
+
+for PROGRAM in "${PROGRAMS[@]}"; do
+    echo "Building the $PROGRAM program to check its stack usage"
+    BUILD_OUTPUT=$(cargo build-sbf --manifest-path "./programs/$PROGRAM/Cargo.toml" 2>&1)
+    if [[ $? -ne 0 ]]; then
+        echo "$BUILD_OUTPUT"
+        >&2 echo "❌ Failed to build the $PROGRAM program."
+        exit 1
+    fi
+    if echo "$BUILD_OUTPUT" | grep -E "Stack offset of [0-9]+ exceeded max offset"; then
+        >&2 echo "❌ Stack frame exceeded in the $PROGRAM program. Please box large accounts or variables."
+        exit 1
+    fi
+    echo "✅ No stack frame exceeded in the $PROGRAM program."
+done
+
+exit 0
@@ -202,9 +202,14 @@ async function bridgeLiabilityToHubPool(): Promise<void> {
 async function bridgeTokensToHubPool(amount: BN, signer: anchor.Wallet, statePda: PublicKey, inputToken: PublicKey) {
   const messageTransmitterProgram = new Program<MessageTransmitter>(messageTransmitterIdl, provider);
 
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    svmSpokeProgram.programId
+  );
+
   const vault = getAssociatedTokenAddressSync(
     inputToken,
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
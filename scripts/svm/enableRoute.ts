@@ -62,16 +62,20 @@ async function enableRoute(): Promise<void> {
     { Property: "routePda", Value: routePda.toString() },
   ]);
 
-  // Create ATA for the origin token to be stored by state (vault).
+  // Create ATA for the origin token to be stored by the vault authority (vault).
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    programId
+  );
   const vault = getAssociatedTokenAddressSync(
     originToken,
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
 
-  const tx = await (program.methods.setEnableRoute(originToken, chainId, enabled) as any)
+  const tx = await (program.methods.setEnableRoute(originToken, chainId, enabled, 0, 0) as any)
     .accounts({
       signer: signer,
       payer: signer,
@@ -82,6 +86,9 @@ async function enableRoute(): Promise<void> {
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
+      lookupTableConfig: programId,
+      lookupTable: programId,
+      addressLookupTableProgram: programId,
     })
     .rpc();
 
@@ -352,9 +352,14 @@ async function executeRelayerRefundLeaf(
   const proof = merkleTree.getProof(relayerRefundLeaf).map((p) => Array.from(p));
   const leaf = relayerRefundLeaf as RelayerRefundLeafSolana;
 
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    svmSpokeProgram.programId
+  );
+
   const vault = getAssociatedTokenAddressSync(
     inputToken,
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
@@ -384,6 +389,11 @@ async function executeRelayerRefundLeaf(
     tokenProgram: TOKEN_PROGRAM_ID,
     mint: inputToken,
     transferLiability,
+    errorCounters: program.programId,
+    bundleAudit: program.programId,
+    rateLimit: program.programId,
+    vaultLiability: program.programId,
+    executionApproval: program.programId,
     systemProgram: anchor.web3.SystemProgram.programId,
     // Appended by Acnhor `event_cpi` macro:
     eventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], program.programId)[0],
@@ -434,7 +444,7 @@ async function executeRelayerRefundLeaf(
     throw new Error("AddressLookupTableAccount not fetched");
   }
 
-  await loadExecuteRelayerRefundLeafParams(program, signer.publicKey, rootBundleId, leaf, proofAsNumbers);
+  await loadExecuteRelayerRefundLeafParams(program, signer.publicKey, statePda, rootBundleId, leaf, proofAsNumbers);
 
   console.log(`loaded execute relayer refund leaf params ${instructionParams}. \nExecuting relayer refund leaf...`);
 
@@ -112,7 +112,11 @@ async function executeRebalanceToSpokePool(): Promise<void> {
     [Buffer.from("state"), seed.toArrayLike(Buffer, "le", 8)],
     svmSpokeProgram.programId
   );
-  const vault = getAssociatedTokenAddressSync(solanaTokenKey, statePda, true);
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    svmSpokeProgram.programId
+  );
+  const vault = getAssociatedTokenAddressSync(solanaTokenKey, vaultAuthority, true);
   const [messageTransmitterState] = PublicKey.findProgramAddressSync(
     [Buffer.from("message_transmitter")],
     messageTransmitterProgram.programId
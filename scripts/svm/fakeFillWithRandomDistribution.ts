@@ -186,7 +186,14 @@ async function fillV3RelayToRandom(): Promise<void> {
   // Prepare fill instruction as we will need to use Address Lookup Table (ALT).
   const fillV3RelayValues: FillDataValues = [relayHash, relayData, repaymentChain, repaymentAddress];
   if (bufferParams) {
-    await loadFillV3RelayParams(program, signer, fillV3RelayValues[1], fillV3RelayValues[2], fillV3RelayValues[3]);
+    await loadFillV3RelayParams(
+      program,
+      signer,
+      statePda,
+      fillV3RelayValues[1],
+      fillV3RelayValues[2],
+      fillV3RelayValues[3],
+    );
   }
   const fillV3RelayParams: FillDataParams = bufferParams ? [fillV3RelayValues[0], null, null, null] : fillV3RelayValues;
   const [instructionParams] = bufferParams
@@ -200,13 +207,44 @@ async function fillV3RelayToRandom(): Promise<void> {
     state: statePda,
     signer: signer.publicKey,
     instructionParams,
+    vaultRegistry: PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), seed.toArrayLike(Buffer, "le", 8), outputToken.toBuffer()],
+      program.programId
+    )[0],
+    fillVolume: PublicKey.findProgramAddressSync(
+      [Buffer.from("fill_volume"), seed.toArrayLike(Buffer, "le", 8), outputToken.toBuffer()],
+      program.programId
+    )[0],
     mint: outputToken,
     relayerTokenAccount,
     recipientTokenAccount: handlerATA,
+    deliveryPreference: program.programId,
+    recipientEscrow: program.programId,
+    nativeUnwrapAccount: program.programId,
+    nativeRecipient: program.programId,
     fillStatus: fillStatusPda,
+    messageSchema: PublicKey.findProgramAddressSync(
+      [Buffer.from("message_schema"), relayData.recipient.toBuffer()],
+      program.programId
+    )[0],
+    depositorCompliance: program.programId,
+    recipientCompliance: program.programId,
+    eventCommitment: program.programId,
+    fillIndexShard: program.programId,
+    originChainAlias: program.programId,
+    relayerStats: program.programId,
+    memoProgram: program.programId,
+    eventForwarder: program.programId,
+    route: program.programId,
+    allowlistEntry: program.programId,
+    repaymentAddressAccount: program.programId,
+    repaymentTokenAccount: program.programId,
     tokenProgram: TOKEN_PROGRAM_ID,
     associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
     systemProgram: SystemProgram.programId,
+    payer: signer.publicKey,
+    recipientAddressAccount: program.programId,
+    recipientAta: program.programId,
     program: programId,
   };
   const remainingAccounts: AccountMeta[] = [
@@ -214,7 +252,7 @@ async function fillV3RelayToRandom(): Promise<void> {
     ...multicallHandlerCoder.compiledKeyMetas,
   ];
   const fillInstruction = await program.methods
-    .fillV3Relay(...fillV3RelayParams)
+    .fillV3Relay(...fillV3RelayParams, 0, Buffer.alloc(0))
     .accounts(fillAccounts)
     .remainingAccounts(remainingAccounts)
     .instruction();
@@ -33,6 +33,11 @@ const argv = yargs(hideBin(process.argv))
     demandOption: false,
     default: 3600 * 4,
     describe: "Fill deadline buffer",
+  })
+  .option("network", {
+    type: "number",
+    demandOption: true,
+    describe: "Network tag: 0 for mainnet, 1 for devnet, 2 for localnet",
   }).argv;
 
 async function initialize(): Promise<void> {
@@ -44,6 +49,7 @@ async function initialize(): Promise<void> {
   const crossDomainAdmin = evmAddressToPublicKey(resolvedArgv.crossDomainAdmin); // Use the function to cast the value
   const depositQuoteTimeBuffer = resolvedArgv.depositQuoteTimeBuffer;
   const fillDeadlineBuffer = resolvedArgv.fillDeadlineBuffer;
+  const network = resolvedArgv.network;
 
   // Define the state account PDA
   console.log("Seed:", seed.toString());
@@ -68,6 +74,7 @@ async function initialize(): Promise<void> {
     { Property: "crossDomainAdmin", Value: crossDomainAdmin.toString() },
     { Property: "depositQuoteTimeBuffer", Value: depositQuoteTimeBuffer.toString() },
     { Property: "fillDeadlineBuffer", Value: fillDeadlineBuffer.toString() },
+    { Property: "network", Value: network.toString() },
   ]);
 
   const tx = await (
@@ -78,7 +85,8 @@ async function initialize(): Promise<void> {
       remoteDomain,
       crossDomainAdmin,
       depositQuoteTimeBuffer,
-      fillDeadlineBuffer
+      fillDeadlineBuffer,
+      network
     ) as any
   )
     .accounts({
@@ -65,6 +65,10 @@ async function queryDeposits(): Promise<void> {
         { Property: "recipient", Value: new PublicKey(event.data.recipient).toString() },
         { Property: "exclusiveRelayer", Value: new PublicKey(event.data.exclusiveRelayer).toString() },
         { Property: "message", Value: event.data.message.toString() },
+        { Property: "tokenDecimals", Value: event.data.tokenDecimals.toString() },
+        { Property: "unsafeDepositId", Value: event.data.unsafeDepositId.toString() },
+        { Property: "depositorNonce", Value: event.data.depositorNonce?.toString() ?? "None" },
+        { Property: "messageHash", Value: event.data.messageHash?.toString() ?? "None" },
       ]);
     });
   } catch (error) {
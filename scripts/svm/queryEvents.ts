@@ -31,6 +31,7 @@ const argvPromise = yargs(hideBin(process.argv)).option("eventName", {
     "setXDomainAdmin",
     "emergencyDeletedRootBundle",
     "requestedV3SlowFill",
+    "requestedV3SlowFill2",
     "claimedRelayerRefund",
     "tokensBridged",
   ],
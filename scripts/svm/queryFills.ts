@@ -69,6 +69,7 @@ async function queryFills(): Promise<void> {
         { Property: "updatedMessageHash", Value: event.data.relayExecutionInfo.updatedMessageHash.toString() },
         { Property: "updatedOutputAmount", Value: event.data.relayExecutionInfo.updatedOutputAmount.toString() },
         { Property: "fillType", Value: event.data.relayExecutionInfo.fillType },
+        { Property: "tokenDecimals", Value: event.data.tokenDecimals.toString() },
       ]);
     });
   } catch (error) {
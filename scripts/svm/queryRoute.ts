@@ -50,10 +50,15 @@ async function queryRoute(): Promise<void> {
     programId
   );
 
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    programId
+  );
+
   // Compute the vault address
   const vault = getAssociatedTokenAddressSync(
     new PublicKey(originToken),
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
@@ -81,9 +81,14 @@ async function remoteHubPoolSetDepositRoute(): Promise<void> {
     svmSpokeProgram.programId
   );
 
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    svmSpokeProgram.programId
+  );
+
   const vault = getAssociatedTokenAddressSync(
     originToken,
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
@@ -67,10 +67,14 @@ async function depositV3(): Promise<void> {
   // Define the signer (replace with your actual signer)
   const signer = (provider.wallet as anchor.Wallet).payer;
 
-  // Find ATA for the input token to be stored by state (vault). This was created when the route was enabled.
+  // Find ATA for the input token to be stored by the vault authority (vault), created when the route was enabled.
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    programId
+  );
   const vault = getAssociatedTokenAddressSync(
     inputToken,
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
@@ -124,17 +128,44 @@ async function depositV3(): Promise<void> {
       quoteTimestamp,
       fillDeadline,
       exclusivityDeadline,
-      message
+      message,
+      false, // revokeAfter
+      0, // argsVersion
+      Buffer.alloc(0) // extra
     ) as any
   )
     .accounts({
       state: statePda,
       route: routePda,
+      destinationChainAlias: program.programId,
+      depositLimit: PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("deposit_limit"),
+          seed.toArrayLike(Buffer, "le", 8),
+          inputToken.toBuffer(),
+          destinationChainId.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0],
+      depositorNonce: PublicKey.findProgramAddressSync(
+        [Buffer.from("depositor_nonce"), seed.toArrayLike(Buffer, "le", 8), signer.publicKey.toBuffer()],
+        program.programId
+      )[0],
+      vaultRegistry: PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), seed.toArrayLike(Buffer, "le", 8), inputToken.toBuffer()],
+        program.programId
+      )[0],
       signer: signer.publicKey,
       userTokenAccount,
       vault: vault,
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: inputToken,
+      vaultLiability: PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_liability"), seed.toArrayLike(Buffer, "le", 8), inputToken.toBuffer()],
+        program.programId
+      )[0],
+      eventCommitment: program.programId,
+      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .instruction();
   const depositTx = new Transaction().add(approveIx, depositIx);
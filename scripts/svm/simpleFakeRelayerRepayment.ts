@@ -64,9 +64,14 @@ async function testBundleLogic(): Promise<void> {
     programId
   );
 
+  const [vaultAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_authority"), statePda.toBuffer()],
+    programId
+  );
+
   const vault = getAssociatedTokenAddressSync(
     inputToken,
-    statePda,
+    vaultAuthority,
     true,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
@@ -114,17 +119,27 @@ async function testBundleLogic(): Promise<void> {
       Math.floor(Date.now() / 1000) - 1, // quoteTimestamp
       Math.floor(Date.now() / 1000) + 3600, // fillDeadline
       0, // exclusivityDeadline
-      Buffer.from([]) // message
+      Buffer.from([]), // message
+      false, // revokeAfter
+      0, // argsVersion
+      Buffer.alloc(0) // extra
     ) as any
   )
     .accounts({
       state: statePda,
       route: routePda,
+      destinationChainAlias: program.programId,
       signer: signer.publicKey,
       userTokenAccount: getAssociatedTokenAddressSync(inputToken, signer.publicKey),
       vault: vault,
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: inputToken,
+      vaultLiability: PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_liability"), seed.toArrayLike(Buffer, "le", 8), inputToken.toBuffer()],
+        program.programId
+      )[0],
+      eventCommitment: program.programId,
+      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .instruction();
   const depositTx = await sendAndConfirmTransaction(provider.connection, new Transaction().add(approveIx, depositIx), [
@@ -185,13 +200,16 @@ async function testBundleLogic(): Promise<void> {
     { property: "Signer", value: signer.publicKey.toString() },
   ]);
 
-  const relayRootBundleTx = await (program.methods.relayRootBundle(Array.from(root), Array.from(root)) as any)
+  const relayRootBundleTx = await (
+    program.methods.relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null) as any
+  )
     .accounts({
       state: statePda,
       rootBundle: rootBundle,
       signer: signer.publicKey,
       payer: signer.publicKey,
       systemProgram: SystemProgram.programId,
+      adminNonce: program.programId,
     })
     .rpc();
   console.log(`Relay root bundle transaction sent: ${relayRootBundleTx}`);
@@ -224,6 +242,11 @@ async function testBundleLogic(): Promise<void> {
     tokenProgram: TOKEN_PROGRAM_ID,
     mint: inputToken,
     transferLiability,
+    errorCounters: program.programId,
+    bundleAudit: program.programId,
+    rateLimit: program.programId,
+    vaultLiability: program.programId,
+    executionApproval: program.programId,
     systemProgram: anchor.web3.SystemProgram.programId,
     // Appended by Acnhor `event_cpi` macro:
     eventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], program.programId)[0],
@@ -274,7 +297,7 @@ async function testBundleLogic(): Promise<void> {
     throw new Error("AddressLookupTableAccount not fetched");
   }
 
-  await loadExecuteRelayerRefundLeafParams(program, signer.publicKey, rootBundleId, leaf, proofAsNumbers);
+  await loadExecuteRelayerRefundLeafParams(program, signer.publicKey, statePda, rootBundleId, leaf, proofAsNumbers);
 
   console.log(`loaded execute relayer refund leaf params ${instructionParams}. \nExecuting relayer refund leaf...`);
 
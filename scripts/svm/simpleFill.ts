@@ -148,19 +148,57 @@ async function fillV3Relay(): Promise<void> {
   );
 
   const fillIx = await (
-    program.methods.fillV3Relay(Array.from(relayHashUint8Array), relayData, chainId, signer.publicKey) as any
+    program.methods.fillV3Relay(
+      Array.from(relayHashUint8Array),
+      relayData,
+      chainId,
+      signer.publicKey,
+      0,
+      Buffer.alloc(0)
+    ) as any
   )
     .accounts({
       state: statePda,
       signer: signer.publicKey,
       instructionParams: program.programId,
+      vaultRegistry: PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), seed.toArrayLike(Buffer, "le", 8), outputToken.toBuffer()],
+        program.programId
+      )[0],
+      fillVolume: PublicKey.findProgramAddressSync(
+        [Buffer.from("fill_volume"), seed.toArrayLike(Buffer, "le", 8), outputToken.toBuffer()],
+        program.programId
+      )[0],
       mint: outputToken,
       relayerTokenAccount: relayerTokenAccount,
       recipientTokenAccount: recipientTokenAccount,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
       fillStatus: fillStatusPda,
+      messageSchema: PublicKey.findProgramAddressSync(
+        [Buffer.from("message_schema"), relayData.recipient.toBuffer()],
+        program.programId
+      )[0],
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      eventCommitment: program.programId,
+      fillIndexShard: program.programId,
+      originChainAlias: program.programId,
+      relayerStats: program.programId,
+      memoProgram: program.programId,
+      eventForwarder: program.programId,
+      route: program.programId,
+      allowlistEntry: program.programId,
+      repaymentAddressAccount: program.programId,
+      repaymentTokenAccount: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
+      payer: signer.publicKey,
+      recipientAddressAccount: program.programId,
+      recipientAta: program.programId,
       programId: programId,
     })
     .instruction();
@@ -1,8 +1,9 @@
-import { BorshAccountsCoder } from "@coral-xyz/anchor";
+import { BN, BorshAccountsCoder } from "@coral-xyz/anchor";
 import { IdlCoder } from "@coral-xyz/anchor/dist/cjs/coder/borsh/idl";
 import { IdlTypeDef } from "@coral-xyz/anchor/dist/cjs/idl";
 import * as borsh from "@coral-xyz/borsh";
 import {
+  AccountMeta,
   CompiledInstruction,
   Message,
   MessageAccountKeys,
@@ -13,7 +14,7 @@ import {
 } from "@solana/web3.js";
 import bs58 from "bs58";
 import { Layout } from "buffer-layout";
-import { AcrossPlusMessage } from "../types/svm";
+import { AcrossPlusActionListMessage, AcrossPlusMessage } from "../types/svm";
 
 /**
  * Extended Anchor accounts coder to handle large account data.
@@ -265,3 +266,98 @@ export class AcrossPlusMessageCoder {
     return buffer.slice(0, len);
   }
 }
+
+/**
+ * Helper to encode versioned Across+ action list messages invoking multiple handlers in order.
+ */
+export class AcrossPlusActionListCoder {
+  static readonly tag = Buffer.from("ACXA");
+
+  static readonly version = 1;
+
+  private actionListMessage: AcrossPlusActionListMessage;
+
+  constructor(actions: { handler: PublicKey; coder: MulticallHandlerCoder }[], valueAmount = new BN(0)) {
+    // Each action takes its compiled handler accounts in order, while the handler programs are appended at the end.
+    const handlers = [...new Set(actions.map(({ handler }) => handler.toBase58()))].map((key) => new PublicKey(key));
+    const actionAccounts = actions.flatMap(({ coder }) => coder.compiledMessage.accountKeys);
+    const handlerMessages = actions.map(({ coder }) => coder.encode());
+
+    this.actionListMessage = {
+      valueAmount,
+      accounts: [...actionAccounts, ...handlers],
+      actions: actions.map(({ handler, coder }, i) => ({
+        programIdIndex: actionAccounts.length + handlers.findIndex((key) => key.equals(handler)),
+        accountsLen: coder.compiledMessage.accountKeys.length,
+        readOnlyLen: coder.readOnlyLen,
+        dataLen: handlerMessages[i].length,
+      })),
+      data: Buffer.concat(handlerMessages),
+    };
+  }
+
+  private static coderArg = {
+    name: "message",
+    type: {
+      defined: {
+        name: "actionListMessage",
+      },
+    },
+  };
+
+  private static coderTypes: IdlTypeDef[] = [
+    {
+      name: "messageAction",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "programIdIndex", type: "u8" },
+          { name: "accountsLen", type: "u8" },
+          { name: "readOnlyLen", type: "u8" },
+          { name: "dataLen", type: "u16" },
+        ],
+      },
+    },
+    {
+      name: "actionListMessage",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "valueAmount", type: "u64" },
+          { name: "accounts", type: { vec: "pubkey" } },
+          { name: "actions", type: { vec: { defined: { name: "messageAction" } } } },
+          { name: "data", type: "bytes" },
+        ],
+      },
+    },
+  ];
+
+  get accounts() {
+    return this.actionListMessage.accounts;
+  }
+
+  // Remaining accounts of the fill map 1:1 to the message accounts, preserving their write permissions.
+  get remainingAccounts(): AccountMeta[] {
+    let offset = 0;
+    const isWritable = new Array(this.accounts.length).fill(false);
+    for (const action of this.actionListMessage.actions) {
+      for (let i = 0; i < action.accountsLen - action.readOnlyLen; i++) isWritable[offset + i] = true;
+      offset += action.accountsLen;
+    }
+    return this.accounts.map((pubkey, i) => ({ pubkey, isSigner: false, isWritable: isWritable[i] }));
+  }
+
+  encode() {
+    const fieldLayouts = [
+      IdlCoder.fieldLayout(AcrossPlusActionListCoder.coderArg, AcrossPlusActionListCoder.coderTypes),
+    ];
+    const layout = borsh.struct(fieldLayouts);
+    const buffer = Buffer.alloc(12800);
+    const len = layout.encode({ message: this.actionListMessage }, buffer);
+    return Buffer.concat([
+      AcrossPlusActionListCoder.tag,
+      Buffer.from([AcrossPlusActionListCoder.version]),
+      buffer.slice(0, len),
+    ]);
+  }
+}
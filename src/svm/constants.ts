@@ -1,4 +1,5 @@
 import BN from "bn.js";
+import { PublicKey } from "@solana/web3.js";
 
 export const CIRCLE_IRIS_API_URL_DEVNET = "https://iris-api-sandbox.circle.com";
 export const CIRCLE_IRIS_API_URL_MAINNET = "https://iris-api.circle.com";
@@ -7,3 +8,37 @@ export const SOLANA_USDC_DEVNET = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"
 export const SEPOLIA_CCTP_MESSAGE_TRANSMITTER_ADDRESS = "0x7865fAfC2db2093669d92c0F33AeEF291086BEFD";
 export const MAINNET_CCTP_MESSAGE_TRANSMITTER_ADDRESS = "0x0a992d191deec32afe36203ad87d7d289a738f81";
 export const SOLANA_SPOKE_STATE_SEED = new BN(0);
+export const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+// Bits of the health_check return data and HealthCheckFailed event, matching the HEALTH_CHECK_* program constants.
+export const HEALTH_CHECK_STATE_LAYOUT = 1 << 0;
+export const HEALTH_CHECK_STATE_BUMP = 1 << 1;
+export const HEALTH_CHECK_PAUSE_FLAGS = 1 << 2;
+export const HEALTH_CHECK_WSOL_VAULT = 1 << 3;
+export const HEALTH_CHECK_COUNTERS = 1 << 4;
+export const HEALTH_CHECK_ALL =
+  HEALTH_CHECK_STATE_LAYOUT |
+  HEALTH_CHECK_STATE_BUMP |
+  HEALTH_CHECK_PAUSE_FLAGS |
+  HEALTH_CHECK_WSOL_VAULT |
+  HEALTH_CHECK_COUNTERS;
+
+// Bits of the preview_vault_migration blockers, matching the VAULT_MIGRATION_* program constants.
+export const VAULT_MIGRATION_STATE = 1 << 0;
+export const VAULT_MIGRATION_FROZEN = 1 << 1;
+export const VAULT_MIGRATION_SLOW_FILLS = 1 << 2;
+export const VAULT_MIGRATION_CLAIMS = 1 << 3;
+export const VAULT_MIGRATION_POOL_LIQUIDITY = 1 << 4;
+
+// Layout of the export_* return data pages and import_* page parameters, matching the EXPORT_* program constants.
+export const EXPORT_LAYOUT_VERSION = 1;
+export const EXPORT_HEADER_SIZE = 6;
+export const EXPORT_FILL_STATUS_RECORD_SIZE = 133;
+export const EXPORT_CLAIM_ACCOUNT_RECORD_SIZE = 104;
+export const EXPORT_LIABILITY_RECORD_SIZE = 40;
+
+// Layout of the get_instruction_metadata return data, matching the METADATA_* program constants.
+export const METADATA_LAYOUT_VERSION = 1;
+export const METADATA_WRITABLE = 1 << 0;
+export const METADATA_SIGNER = 1 << 1;
+export const METADATA_OPTIONAL = 1 << 2;
@@ -96,3 +96,72 @@ export const fromBytes32ToAddress = (input: string): string => {
 
   return "0x" + address;
 };
+
+/**
+ * Encodes version 1 extra args of fill payouts carrying an optional payout memo.
+ */
+export const encodePayoutMemoExtraArgs = (memo: Buffer | null): Buffer => {
+  if (memo === null) return Buffer.from([0]);
+  const memoLength = Buffer.alloc(4);
+  memoLength.writeUInt32LE(memo.length);
+  return Buffer.concat([Buffer.from([1]), memoLength, memo]);
+};
+
+/**
+ * Encodes version 2 extra args of fast fills carrying an optional payout memo and the repayment token account flag.
+ */
+export const encodeFillExtraArgsV2 = (memo: Buffer | null, createRepaymentTokenAccount: boolean): Buffer => {
+  return Buffer.concat([encodePayoutMemoExtraArgs(memo), Buffer.from([createRepaymentTokenAccount ? 1 : 0])]);
+};
+
+/**
+ * Encodes version 3 extra args of fast fills, adding an optional origin transaction reference to the version 2 args.
+ * The reference is encoded without length validation, so that malformed references can be tested against the program.
+ */
+export const encodeFillExtraArgsV3 = (
+  memo: Buffer | null,
+  createRepaymentTokenAccount: boolean,
+  originTxRef: Buffer | null
+): Buffer => {
+  const encodedOriginTxRef = originTxRef === null ? Buffer.from([0]) : Buffer.concat([Buffer.from([1]), originTxRef]);
+  return Buffer.concat([encodeFillExtraArgsV2(memo, createRepaymentTokenAccount), encodedOriginTxRef]);
+};
+
+/**
+ * Encodes version 4 extra args of fast fills, adding the flag accepting a fee below the route floor to the version 3
+ * args.
+ */
+export const encodeFillExtraArgsV4 = (
+  memo: Buffer | null,
+  createRepaymentTokenAccount: boolean,
+  originTxRef: Buffer | null,
+  acceptLowFee: boolean
+): Buffer => {
+  return Buffer.concat([
+    encodeFillExtraArgsV3(memo, createRepaymentTokenAccount, originTxRef),
+    Buffer.from([acceptLowFee ? 1 : 0]),
+  ]);
+};
+
+/**
+ * Encodes version 6 extra args of fast fills, adding the flag creating the recipient ATA to the version 4 args.
+ */
+export const encodeFillExtraArgsV6 = (
+  memo: Buffer | null,
+  createRepaymentTokenAccount: boolean,
+  originTxRef: Buffer | null,
+  acceptLowFee: boolean,
+  createRecipientTokenAccount: boolean
+): Buffer => {
+  return Buffer.concat([
+    encodeFillExtraArgsV4(memo, createRepaymentTokenAccount, originTxRef, acceptLowFee),
+    Buffer.from([createRecipientTokenAccount ? 1 : 0]),
+  ]);
+};
+
+/**
+ * Encodes version 5 extra args of deposits carrying the next nonce of a depositor with a depositor nonce PDA.
+ */
+export const encodeDepositExtraArgsV5 = (depositorNonce: BN): Buffer => {
+  return depositorNonce.toArrayLike(Buffer, "le", 8);
+};
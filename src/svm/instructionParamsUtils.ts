@@ -1,6 +1,12 @@
 import { Keypair, TransactionInstruction, Transaction, sendAndConfirmTransaction, PublicKey } from "@solana/web3.js";
 import { Program, BN } from "@coral-xyz/anchor";
-import { RelayData, SlowFillLeaf, RelayerRefundLeafSolana } from "../types/svm";
+import {
+  BundleSummaryLeaf,
+  RelayData,
+  SlowFillLeaf,
+  RelayerRefundLeafSolana,
+  SlowRelayLeafExecution,
+} from "../types/svm";
 import { SvmSpoke } from "../../target/types/svm_spoke";
 import { LargeAccountsCoder } from "./coders";
 
@@ -10,6 +16,7 @@ import { LargeAccountsCoder } from "./coders";
 export async function loadExecuteRelayerRefundLeafParams(
   program: Program<SvmSpoke>,
   caller: PublicKey,
+  state: PublicKey,
   rootBundleId: number,
   relayerRefundLeaf: RelayerRefundLeafSolana,
   proof: number[][]
@@ -31,7 +38,101 @@ export async function loadExecuteRelayerRefundLeafParams(
     proof,
   });
 
-  await program.methods.initializeInstructionParams(instructionParamsBytes.length).rpc();
+  await program.methods.initializeInstructionParams(instructionParamsBytes.length).accounts({ state }).rpc();
+
+  for (let i = 0; i < instructionParamsBytes.length; i += maxInstructionParamsFragment) {
+    const fragment = instructionParamsBytes.slice(i, i + maxInstructionParamsFragment);
+    await program.methods.writeInstructionParamsFragment(i, fragment).rpc();
+  }
+  return instructionParams;
+}
+
+/**
+ * Loads simulate bundle summary parameters, where each leaf is either
+ * { relayerRefund: { relayerRefundLeaf, proof } } or { slowRelay: { slowFillLeaf, proof } }.
+ */
+export async function loadSimulateBundleSummaryParams(
+  program: Program<SvmSpoke>,
+  caller: PublicKey,
+  state: PublicKey,
+  leaves: BundleSummaryLeaf[]
+) {
+  const maxInstructionParamsFragment = 900; // Should not exceed message size limit when writing to the data account.
+
+  // Close the instruction params account if the caller has used it before.
+  const [instructionParams] = PublicKey.findProgramAddressSync(
+    [Buffer.from("instruction_params"), caller.toBuffer()],
+    program.programId
+  );
+  const accountInfo = await program.provider.connection.getAccountInfo(instructionParams);
+  if (accountInfo !== null) await program.methods.closeInstructionParams().rpc();
+
+  const accountCoder = new LargeAccountsCoder(program.idl);
+  const instructionParamsBytes = await accountCoder.encode("simulateBundleSummaryParams", { leaves });
+
+  await program.methods.initializeInstructionParams(instructionParamsBytes.length).accounts({ state }).rpc();
+
+  for (let i = 0; i < instructionParamsBytes.length; i += maxInstructionParamsFragment) {
+    const fragment = instructionParamsBytes.slice(i, i + maxInstructionParamsFragment);
+    await program.methods.writeInstructionParamsFragment(i, fragment).rpc();
+  }
+  return instructionParams;
+}
+
+/**
+ * Loads execute V3 slow relay leaves parameters, where each leaf is { slowFillLeaf, proof }.
+ */
+export async function loadExecuteV3SlowRelayLeavesParams(
+  program: Program<SvmSpoke>,
+  caller: PublicKey,
+  state: PublicKey,
+  leaves: SlowRelayLeafExecution[]
+) {
+  const maxInstructionParamsFragment = 900; // Should not exceed message size limit when writing to the data account.
+
+  // Close the instruction params account if the caller has used it before.
+  const [instructionParams] = PublicKey.findProgramAddressSync(
+    [Buffer.from("instruction_params"), caller.toBuffer()],
+    program.programId
+  );
+  const accountInfo = await program.provider.connection.getAccountInfo(instructionParams);
+  if (accountInfo !== null) await program.methods.closeInstructionParams().rpc();
+
+  const accountCoder = new LargeAccountsCoder(program.idl);
+  const instructionParamsBytes = await accountCoder.encode("executeV3SlowRelayLeavesParams", { leaves });
+
+  await program.methods.initializeInstructionParams(instructionParamsBytes.length).accounts({ state }).rpc();
+
+  for (let i = 0; i < instructionParamsBytes.length; i += maxInstructionParamsFragment) {
+    const fragment = instructionParamsBytes.slice(i, i + maxInstructionParamsFragment);
+    await program.methods.writeInstructionParamsFragment(i, fragment).rpc();
+  }
+  return instructionParams;
+}
+
+/**
+ * Loads request V3 slow fills batch parameters.
+ */
+export async function loadRequestV3SlowFillsBatchParams(
+  program: Program<SvmSpoke>,
+  caller: PublicKey,
+  state: PublicKey,
+  relays: RelayData[]
+) {
+  const maxInstructionParamsFragment = 900; // Should not exceed message size limit when writing to the data account.
+
+  // Close the instruction params account if the caller has used it before.
+  const [instructionParams] = PublicKey.findProgramAddressSync(
+    [Buffer.from("instruction_params"), caller.toBuffer()],
+    program.programId
+  );
+  const accountInfo = await program.provider.connection.getAccountInfo(instructionParams);
+  if (accountInfo !== null) await program.methods.closeInstructionParams().rpc();
+
+  const accountCoder = new LargeAccountsCoder(program.idl);
+  const instructionParamsBytes = await accountCoder.encode("requestV3SlowFillsBatchParams", { relays });
+
+  await program.methods.initializeInstructionParams(instructionParamsBytes.length).accounts({ state }).rpc();
 
   for (let i = 0; i < instructionParamsBytes.length; i += maxInstructionParamsFragment) {
     const fragment = instructionParamsBytes.slice(i, i + maxInstructionParamsFragment);
@@ -61,6 +162,7 @@ export async function closeInstructionParams(program: Program<SvmSpoke>, signer:
 export async function createFillV3RelayParamsInstructions(
   program: Program<SvmSpoke>,
   signer: PublicKey,
+  state: PublicKey,
   relayData: RelayData,
   repaymentChainId: BN,
   repaymentAddress: PublicKey
@@ -76,7 +178,10 @@ export async function createFillV3RelayParamsInstructions(
 
   const loadInstructions: TransactionInstruction[] = [];
   loadInstructions.push(
-    await program.methods.initializeInstructionParams(instructionParamsBytes.length).accounts({ signer }).instruction()
+    await program.methods
+      .initializeInstructionParams(instructionParamsBytes.length)
+      .accounts({ signer, state })
+      .instruction()
   );
 
   for (let i = 0; i < instructionParamsBytes.length; i += maxInstructionParamsFragment) {
@@ -97,6 +202,7 @@ export async function createFillV3RelayParamsInstructions(
 export async function loadFillV3RelayParams(
   program: Program<SvmSpoke>,
   signer: Keypair,
+  state: PublicKey,
   relayData: RelayData,
   repaymentChainId: BN,
   repaymentAddress: PublicKey
@@ -108,6 +214,7 @@ export async function loadFillV3RelayParams(
   const { loadInstructions } = await createFillV3RelayParamsInstructions(
     program,
     signer.publicKey,
+    state,
     relayData,
     repaymentChainId,
     repaymentAddress
@@ -120,7 +227,12 @@ export async function loadFillV3RelayParams(
 /**
  * Loads requestV3 slow fill parameters.
  */
-export async function loadRequestV3SlowFillParams(program: Program<SvmSpoke>, signer: Keypair, relayData: RelayData) {
+export async function loadRequestV3SlowFillParams(
+  program: Program<SvmSpoke>,
+  signer: Keypair,
+  state: PublicKey,
+  relayData: RelayData
+) {
   // Close the instruction params account if the caller has used it before.
   await closeInstructionParams(program, signer);
 
@@ -134,7 +246,7 @@ export async function loadRequestV3SlowFillParams(program: Program<SvmSpoke>, si
   loadInstructions.push(
     await program.methods
       .initializeInstructionParams(instructionParamsBytes.length)
-      .accounts({ signer: signer.publicKey })
+      .accounts({ signer: signer.publicKey, state })
       .instruction()
   );
 
@@ -157,6 +269,7 @@ export async function loadRequestV3SlowFillParams(program: Program<SvmSpoke>, si
 export async function loadExecuteV3SlowRelayLeafParams(
   program: Program<SvmSpoke>,
   signer: Keypair,
+  state: PublicKey,
   slowFillLeaf: SlowFillLeaf,
   rootBundleId: number,
   proof: number[][]
@@ -178,7 +291,7 @@ export async function loadExecuteV3SlowRelayLeafParams(
   loadInstructions.push(
     await program.methods
       .initializeInstructionParams(instructionParamsBytes.length)
-      .accounts({ signer: signer.publicKey })
+      .accounts({ signer: signer.publicKey, state })
       .instruction()
   );
 
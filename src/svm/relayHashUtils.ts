@@ -1,6 +1,7 @@
 import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
 import { ethers } from "ethers";
-import { RelayerRefundLeaf, RelayerRefundLeafSolana, SlowFillLeaf } from "../types/svm";
+import { RelayData, RelayerRefundLeaf, RelayerRefundLeafSolana, SlowFillLeaf } from "../types/svm";
 import { serialize } from "borsh";
 
 /**
@@ -53,6 +54,35 @@ export function calculateRelayEventHashUint8Array(relayEventData: any, chainId:
   return new Uint8Array(relayHashBuffer);
 }
 
+/**
+ * Calculates the hash of updated fill values that the depositor signs for fill_v3_relay_with_updated_deposit.
+ */
+export function calculateUpdatedDepositHashUint8Array(
+  chainId: BN,
+  relayHash: Uint8Array,
+  updatedOutputAmount: BN,
+  updatedRecipient: PublicKey,
+  updatedMessage: Buffer
+): Uint8Array {
+  const contentToHash = Buffer.concat([
+    chainId.toArrayLike(Buffer, "le", 8),
+    Buffer.from(relayHash),
+    updatedOutputAmount.toArrayLike(Buffer, "le", 8),
+    updatedRecipient.toBuffer(),
+    hashNonEmptyMessage(updatedMessage),
+  ]);
+
+  const updatedDepositHash = ethers.utils.keccak256(contentToHash);
+  return new Uint8Array(Buffer.from(updatedDepositHash.slice(2), "hex"));
+}
+
+/**
+ * Calculates the key of the UsedSignature PDA consuming a signature, i.e. the first 16 bytes of its keccak hash.
+ */
+export function calculateUsedSignatureKey(signature: Uint8Array): Buffer {
+  return Buffer.from(ethers.utils.keccak256(signature).slice(2, 34), "hex");
+}
+
 /**
  * Reads a 256-bit unsigned integer from a buffer.
  */
@@ -220,3 +250,50 @@ export function slowFillHashFn(slowFillLeaf: SlowFillLeaf): string {
 
   return ethers.utils.keccak256(contentToHash);
 }
+
+/**
+ * Encodes relay data in the compact format of batched instructions: a version byte and a u16 bitmap with 2 bits per
+ * bytes32 field selecting whether it is default, its 20 low-order bytes or full width, followed by the encoded bytes32
+ * fields and the remaining borsh serialized relay data fields.
+ */
+export function encodeCompactRelayData(relayData: RelayData): Buffer {
+  const fields = [
+    relayData.depositor.toBuffer(),
+    relayData.recipient.toBuffer(),
+    relayData.exclusiveRelayer.toBuffer(),
+    relayData.inputToken.toBuffer(),
+    relayData.outputToken.toBuffer(),
+    Buffer.from(relayData.depositId),
+  ];
+  let bitmap = 0;
+  const encodedFields = fields.map((field, index) => {
+    // Deposit id is little-endian, so its high-order bytes are trailing unlike for the big-endian addresses.
+    const isLittleEndian = index === fields.length - 1;
+    const highOrderBytes = isLittleEndian ? field.subarray(20) : field.subarray(0, 12);
+    if (field.every((byte) => byte === 0)) return Buffer.alloc(0);
+    if (highOrderBytes.every((byte) => byte === 0)) {
+      bitmap |= 1 << (2 * index);
+      return isLittleEndian ? field.subarray(0, 20) : field.subarray(12);
+    }
+    bitmap |= 2 << (2 * index);
+    return field;
+  });
+
+  const header = Buffer.alloc(3);
+  header.writeUInt8(1, 0); // Compact relay data version.
+  header.writeUInt16LE(bitmap, 1);
+  const messageLength = Buffer.alloc(4);
+  messageLength.writeUInt32LE(relayData.message.length);
+
+  return Buffer.concat([
+    header,
+    ...encodedFields,
+    relayData.inputAmount.toArrayLike(Buffer, "le", 8),
+    relayData.outputAmount.toArrayLike(Buffer, "le", 8),
+    relayData.originChainId.toArrayLike(Buffer, "le", 8),
+    new BN(relayData.fillDeadline).toArrayLike(Buffer, "le", 4),
+    new BN(relayData.exclusivityDeadline).toArrayLike(Buffer, "le", 4),
+    messageLength,
+    relayData.message,
+  ]);
+}
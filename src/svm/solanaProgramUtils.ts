@@ -8,6 +8,7 @@ import {
   SignaturesForAddressOptions,
 } from "@solana/web3.js";
 import { deserialize } from "borsh";
+import { ethers } from "ethers";
 import { EventType } from "../types/svm";
 import { publicKeyToEvmAddress } from "./conversionUtils";
 
@@ -80,12 +81,13 @@ function processEventFromTx(txResult: web3.VersionedTransactionResponse, program
           eventAuthorities.get(ixProgramId.toString())?.equals(singleIxAccount)
         ) {
           const ixData = utils.bytes.bs58.decode(ix.data);
-          const eventData = utils.bytes.base64.encode(Buffer.from(new Uint8Array(ixData).slice(8)));
-          const event = program.coder.events.decode(eventData);
+          const rawData = Buffer.from(new Uint8Array(ixData).slice(8));
+          const event = program.coder.events.decode(utils.bytes.base64.encode(rawData));
           events.push({
             program: program.programId,
             data: event?.data,
             name: event?.name,
+            rawData, // Event discriminator followed by the borsh serialized event.
           });
         }
       }
@@ -131,6 +133,18 @@ export function getEvent(events: any[], program: PublicKey, eventName: string) {
   throw new Error("Event " + eventName + " not found");
 }
 
+/**
+ * Chains events into an event commitment hash the same way as the on-chain EventCommitment PDA:
+ * hash = keccak(hash ‖ event discriminator ‖ keccak(event data)). Events must be passed in their emission order.
+ */
+export function chainEventCommitment(hash: Uint8Array, events: { rawData: Buffer }[]): Buffer {
+  return events.reduce((chainedHash, event) => {
+    const eventHash = Buffer.from(ethers.utils.arrayify(ethers.utils.keccak256(event.rawData.subarray(8))));
+    const content = Buffer.concat([chainedHash, event.rawData.subarray(0, 8), eventHash]);
+    return Buffer.from(ethers.utils.arrayify(ethers.utils.keccak256(content)));
+  }, Buffer.from(hash));
+}
+
 /**
  * Reads all events for a specific program.
  */
@@ -257,3 +271,23 @@ export function stringifyCpiEvent(obj: any): any {
   }
   return obj;
 }
+
+/**
+ * Converts decoded program types to the canonical JSON representation shared with the off-chain Rust serde feature:
+ * pubkeys as base58, BN amounts as decimal strings and byte arrays as 0x prefixed hex. Unlike stringifyCpiEvent, no
+ * field specific formatting is applied so that both sides agree on every value.
+ */
+export function toCanonicalJson(obj: any): any {
+  if (obj instanceof PublicKey) {
+    return obj.toBase58();
+  } else if (BN.isBN(obj)) {
+    return obj.toString();
+  } else if (obj instanceof Uint8Array || (Array.isArray(obj) && obj.length > 0 && obj.every(Number.isInteger))) {
+    return "0x" + Buffer.from(obj).toString("hex");
+  } else if (Array.isArray(obj)) {
+    return obj.map(toCanonicalJson);
+  } else if (obj !== null && typeof obj === "object") {
+    return Object.fromEntries(Object.entries(obj).map(([key, value]) => [key, toCanonicalJson(value)]));
+  }
+  return obj;
+}
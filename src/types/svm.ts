@@ -36,6 +36,18 @@ export interface SlowFillLeaf {
   updatedOutputAmount: BN;
 }
 
+/**
+ * Bundle Summary Leaf Type
+ */
+export type BundleSummaryLeaf =
+  | { relayerRefund: { relayerRefundLeaf: RelayerRefundLeafSolana; proof: number[][] } }
+  | { slowRelay: { slowFillLeaf: SlowFillLeaf; proof: number[][] } };
+
+/**
+ * Slow relay leaf with its proof, executed in batches by execute_v3_slow_relay_leaves.
+ */
+export type SlowRelayLeafExecution = { slowFillLeaf: SlowFillLeaf; proof: number[][] };
+
 /**
  * Relay Data Interface
  */
@@ -119,6 +131,23 @@ export type AcrossPlusMessage = {
   handlerMessage: Buffer;
 };
 
+/**
+ * Across+ Action List Message Interfaces
+ */
+export type MessageAction = {
+  programIdIndex: number;
+  accountsLen: number;
+  readOnlyLen: number;
+  dataLen: number;
+};
+
+export type AcrossPlusActionListMessage = {
+  valueAmount: BN;
+  accounts: PublicKey[];
+  actions: MessageAction[];
+  data: Buffer;
+};
+
 /**
  * Event Type Interface
  */
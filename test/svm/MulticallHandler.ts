@@ -52,7 +52,7 @@ describe("multicall_handler", () => {
     const handlerMessage = multicallHandlerCoder.encode();
 
     await program.methods
-      .handleV3AcrossMessage(handlerMessage)
+      .handleV3AcrossMessage(mint, new anchor.BN(tokenAmount), provider.publicKey, handlerMessage)
       .remainingAccounts(multicallHandlerCoder.compiledKeyMetas)
       .rpc();
 
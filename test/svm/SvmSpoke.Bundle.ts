@@ -1,11 +1,14 @@
 import * as anchor from "@coral-xyz/anchor";
 import { AnchorProvider, BN, Wallet, web3 } from "@coral-xyz/anchor";
 import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   createMint,
+  getAccount,
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
   mintTo,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
 import { ComputeBudgetProgram, Keypair, PublicKey } from "@solana/web3.js";
 import { assert } from "chai";
@@ -22,7 +25,8 @@ import { MerkleTree } from "../../utils";
 import { common } from "./SvmSpoke.common";
 import { buildRelayerRefundMerkleTree, randomBigInt, readEvents, readProgramEvents } from "./utils";
 
-const { provider, program, owner, initializeState, connection, chainId, assertSE } = common;
+const { provider, program, owner, initializeState, connection, chainId, assertSE, setCurrentTime } = common;
+const { createSlowFillLiabilityPda, createClaimLiabilityPda, createFillVolumePda, getVaultAuthority } = common;
 
 describe("svm_spoke.bundle", () => {
   anchor.setProvider(provider);
@@ -52,7 +56,7 @@ describe("svm_spoke.bundle", () => {
     relayerTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, relayerA.publicKey)).address;
     relayerTB = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, relayerB.publicKey)).address;
 
-    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true)).address;
+    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)).address;
 
     const sig = await connection.requestAirdrop(nonOwner.publicKey, 10_000_000_000);
     await provider.connection.confirmTransaction(sig);
@@ -94,11 +98,12 @@ describe("svm_spoke.bundle", () => {
       rootBundle,
       signer: nonOwner.publicKey,
       payer: nonOwner.publicKey,
+      adminNonce: program.programId,
       program: program.programId,
     };
     try {
       await program.methods
-        .relayRootBundle(relayerRefundRootArray, slowRelayRootArray)
+        .relayRootBundle(relayerRefundRootArray, slowRelayRootArray, 0, null, 0, null)
         .accounts(relayRootBundleAccounts)
         .signers([nonOwner])
         .rpc();
@@ -108,9 +113,16 @@ describe("svm_spoke.bundle", () => {
     }
 
     // Relay root bundle as owner
-    relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
+    relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
     await program.methods
-      .relayRootBundle(relayerRefundRootArray, slowRelayRootArray)
+      .relayRootBundle(relayerRefundRootArray, slowRelayRootArray, 0, null, 0, null)
       .accounts(relayRootBundleAccounts)
       .rpc();
 
@@ -124,6 +136,14 @@ describe("svm_spoke.bundle", () => {
     );
     assert.isTrue(slowRelayRootHex === slowRelayRootBuffer.toString("hex"), "Slow relay root should be set");
 
+    // Root bundles relayed by this program already have the current layout and cannot be migrated.
+    try {
+      await program.methods.migrateRootBundle(rootBundleId).accounts({ signer: owner, state, rootBundle }).rpc();
+      assert.fail("Migrating a root bundle of the current layout should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotLegacyRootBundle", "Expected NotLegacyRootBundle error");
+    }
+
     // Check that the root bundle index has been incremented
     stateAccountData = await program.account.state.fetch(state);
     assert.isTrue(stateAccountData.rootBundleId.toString() === "1", "Root bundle index should be 1");
@@ -145,10 +165,11 @@ describe("svm_spoke.bundle", () => {
       rootBundle: rootBundle2,
       signer: owner,
       payer: owner,
+      adminNonce: program.programId,
       program: program.programId,
     };
     await program.methods
-      .relayRootBundle(relayerRefundRootArray2, slowRelayRootArray2)
+      .relayRootBundle(relayerRefundRootArray2, slowRelayRootArray2, 0, null, 0, null)
       .accounts(relayRootBundleAccounts)
       .rpc();
 
@@ -170,9 +191,16 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle as owner
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
     const tx = await program.methods
-      .relayRootBundle(relayerRefundRootArray, slowRelayRootArray)
+      .relayRootBundle(relayerRefundRootArray, slowRelayRootArray, 0, null, 0, null)
       .accounts(relayRootBundleAccounts)
       .rpc();
 
@@ -187,6 +215,136 @@ describe("svm_spoke.bundle", () => {
     assert.isTrue(event.slowRelayRoot.toString() === slowRelayRootArray.toString(), "Slow relay root should match");
   });
 
+  it("Relays root bundles resubmitted by automation once per idempotency key", async () => {
+    const relayerRefundRoot = Array.from(crypto.randomBytes(32));
+    const slowRelayRoot = Array.from(crypto.randomBytes(32));
+    const idempotencyKey = Array.from(crypto.randomBytes(16));
+    const [adminNonce] = PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_nonce"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(idempotencyKey)],
+      program.programId
+    );
+
+    // Automation rebuilds the transaction on each retry, deriving the root bundle PDA from the current root bundle ID.
+    const submitRelayRootBundle = async (key: number[] | null) => {
+      const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+      const rootBundleIdBuffer = Buffer.alloc(4);
+      rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+      const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+      const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+      const relayRootBundleAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: key ? adminNonce : program.programId,
+        program: program.programId,
+      };
+      const tx = await program.methods
+        .relayRootBundle(relayerRefundRoot, slowRelayRoot, 0, null, 0, key)
+        .accounts(relayRootBundleAccounts)
+        .rpc({ commitment: "confirmed" });
+
+      // Instruction returns whether the root bundle was relayed.
+      const txResult = await connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const applied = Buffer.from(txResult!.meta!.returnData!.data[0], "base64")[0] === 1;
+      const events = await readEvents(connection, tx, [program]);
+      return { rootBundleId, rootBundle, applied, events };
+    };
+
+    // Without a key, every submission relays another root bundle.
+    const first = await submitRelayRootBundle(null);
+    const second = await submitRelayRootBundle(null);
+    assert.isTrue(first.applied && second.applied, "Submissions without a key should be applied");
+    assertSE(second.rootBundleId, first.rootBundleId + 1, "Resubmission should relay another root bundle");
+
+    // With a key, only the first submission is applied.
+    const keyed = await submitRelayRootBundle(idempotencyKey);
+    assert.isTrue(keyed.applied, "First submission of the key should be applied");
+    assert.isDefined(keyed.events.find((event) => event.name === "relayedRootBundle"), "Bundle should be relayed");
+    const consumed = await program.account.adminNonce.fetch(adminNonce);
+    assertSE(consumed.payer, owner, "Admin nonce payer should be recorded");
+
+    const resubmitted = await submitRelayRootBundle(idempotencyKey);
+    assert.isFalse(resubmitted.applied, "Resubmission of the key should not be applied");
+    assertSE(resubmitted.rootBundleId, keyed.rootBundleId + 1, "Resubmission should see the relayed root bundle");
+    assert.isUndefined(
+      resubmitted.events.find((event) => event.name === "relayedRootBundle"),
+      "Resubmission should not emit events"
+    );
+    const stateAccountData = await program.account.state.fetch(state);
+    assertSE(stateAccountData.rootBundleId, resubmitted.rootBundleId, "Root bundle ID should not be incremented");
+    assert.isNull(
+      await connection.getAccountInfo(resubmitted.rootBundle),
+      "Resubmission should not leave a root bundle behind"
+    );
+
+    // Consumed keys are retained until their expiry slot.
+    try {
+      await program.methods.closeAdminNonce().accounts({ signer: owner, adminNonce, payer: owner }).rpc();
+      assert.fail("Closing an admin nonce before its expiry should fail");
+    } catch (err: any) {
+      assert.include(
+        err.toString(),
+        "CanOnlyCloseAdminNonceAfterExpiry",
+        "Expected CanOnlyCloseAdminNonceAfterExpiry error"
+      );
+    }
+  });
+
+  it("Verifies leaf inclusion against the selected root type", async () => {
+    const relayerRefundLeaves: RelayerRefundLeafType[] = [0, 1].map((leafId) => ({
+      isSolana: true,
+      leafId: new BN(leafId),
+      chainId: chainId,
+      amountToReturn: new BN(0),
+      mintPublicKey: mint,
+      refundAddresses: [relayerA.publicKey],
+      refundAmounts: [new BN(1000)],
+    }));
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
+    const proof = merkleTree.getProof(relayerRefundLeaves[0]).map((p) => Array.from(p));
+    const leafHash = Array.from(Buffer.from((relayerRefundHashFn(relayerRefundLeaves[0]) as string).slice(2), "hex"));
+    const slowRelayRoot = crypto.randomBytes(32);
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+    const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(slowRelayRoot), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
+
+    const inclusion = await program.methods
+      .verifyLeafInclusion(rootBundleId, { relayerRefund: {} }, leafHash, proof)
+      .accounts({ state, rootBundle })
+      .view();
+    assert.isTrue(inclusion.included, "Leaf should be included in relayer refund root");
+    assert.strictEqual(inclusion.relayerRefundRoot.toString(), Array.from(root).toString(), "Refund root should match");
+    assert.strictEqual(inclusion.slowRelayRoot.toString(), Array.from(slowRelayRoot).toString(), "Slow root mismatch");
+
+    // The same leaf and proof must not verify against the wrong root type.
+    const wrongRootInclusion = await program.methods
+      .verifyLeafInclusion(rootBundleId, { slowRelay: {} }, leafHash, proof)
+      .accounts({ state, rootBundle })
+      .view();
+    assert.isFalse(wrongRootInclusion.included, "Leaf should not be included in slow relay root");
+  });
+
   it("Simple Leaf Refunds Relayers", async () => {
     const relayerRefundLeaves: RelayerRefundLeafType[] = [];
     const relayerARefund = new BN(400000);
@@ -217,8 +375,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
       { pubkey: relayerTB, isWritable: true, isSigner: false },
@@ -236,12 +404,27 @@ describe("svm_spoke.bundle", () => {
       vault: vault,
       mint: mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
     const proofAsNumbers = proof.map((p) => Array.from(p));
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
     const tx = await program.methods
       .executeRelayerRefundLeaf()
       .accounts(executeRelayerRefundLeafAccounts)
@@ -263,6 +446,7 @@ describe("svm_spoke.bundle", () => {
     assertSE(event.refundAddresses[1], relayerB.publicKey, "Relayer B address should match");
     assert.isFalse(event.deferredRefunds, "deferredRefunds should be false");
     assertSE(event.caller, owner, "caller should match");
+    assertSE(event.tokenDecimals, 6, "tokenDecimals should match the mint");
 
     event = events.find((event) => event.name === "tokensBridged")?.data;
     assertSE(event.amountToReturn, relayerRefundLeaves[0].amountToReturn, "amountToReturn should match");
@@ -291,10 +475,25 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
       await program.methods
         .executeRelayerRefundLeaf()
         .accounts(executeRelayerRefundLeafAccounts)
@@ -343,8 +542,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -364,6 +573,14 @@ describe("svm_spoke.bundle", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
@@ -374,7 +591,14 @@ describe("svm_spoke.bundle", () => {
       ];
 
       // Verify valid leaf
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
       await program.methods
         .executeRelayerRefundLeaf()
         .accounts(executeRelayerRefundLeafAccounts)
@@ -394,10 +618,25 @@ describe("svm_spoke.bundle", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
 
     await program.methods
       .executeRelayerRefundLeaf()
@@ -433,12 +672,21 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
       await loadExecuteRelayerRefundLeafParams(
         program,
         owner,
+        state,
         stateAccountData.rootBundleId,
         invalidRelayerRefundLeaf as RelayerRefundLeafSolana,
         proofAsNumbers
@@ -476,8 +724,17 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -505,10 +762,25 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
 
       await program.methods
         .executeRelayerRefundLeaf()
@@ -571,8 +843,17 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -600,10 +881,25 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
 
       await program.methods
         .executeRelayerRefundLeaf()
@@ -675,8 +971,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -693,10 +999,25 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
       await program.methods
         .executeRelayerRefundLeaf()
         .accounts(executeRelayerRefundLeafAccounts)
@@ -737,8 +1058,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -755,10 +1086,25 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
       await program.methods
         .executeRelayerRefundLeaf()
         .accounts(executeRelayerRefundLeafAccounts)
@@ -769,6 +1115,103 @@ describe("svm_spoke.bundle", () => {
     }
   });
 
+  it("Execute Leaf Refunds Relayers of a Token-2022 mint only with its token program", async () => {
+    const tokenProgram = TOKEN_2022_PROGRAM_ID;
+    const mint2022 = await createMint(connection, payer, owner, owner, 6, undefined, undefined, tokenProgram);
+    const getTokenAccount = async (accountOwner: PublicKey) => {
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint2022,
+        accountOwner,
+        true,
+        undefined,
+        undefined,
+        tokenProgram
+      );
+      return tokenAccount.address;
+    };
+    const vault2022 = await getTokenAccount(getVaultAuthority(state));
+    const relayerTA2022 = await getTokenAccount(relayerA.publicKey);
+    await mintTo(connection, payer, mint2022, vault2022, owner, initialMintAmount, undefined, undefined, tokenProgram);
+
+    const relayerARefund = new BN(400000);
+    const relayerRefundLeaves: RelayerRefundLeafType[] = [
+      {
+        isSolana: true,
+        leafId: new BN(0),
+        chainId: chainId,
+        amountToReturn: new BN(0),
+        mintPublicKey: mint2022,
+        refundAddresses: [relayerA.publicKey],
+        refundAmounts: [relayerARefund],
+      },
+    ];
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
+    const proofAsNumbers = merkleTree.getProof(relayerRefundLeaves[0]).map((p) => Array.from(p));
+    const leaf = relayerRefundLeaves[0] as RelayerRefundLeafSolana;
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+    const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+    await loadExecuteRelayerRefundLeafParams(program, owner, state, rootBundleId, leaf, proofAsNumbers);
+
+    const [transferLiability2022] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer_liability"), mint2022.toBuffer()],
+      program.programId
+    );
+    const executeRelayerRefundLeaf = (executeTokenProgram: PublicKey) =>
+      program.methods
+        .executeRelayerRefundLeaf()
+        .accounts({
+          state,
+          rootBundle,
+          signer: owner,
+          vault: vault2022,
+          tokenProgram: executeTokenProgram,
+          mint: mint2022,
+          transferLiability: transferLiability2022,
+          errorCounters: program.programId,
+          bundleAudit: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(mint2022, seed),
+          claimLiability: createClaimLiabilityPda(mint2022, seed),
+          fillVolume: createFillVolumePda(mint2022, seed),
+          executionApproval: program.programId,
+          systemProgram: web3.SystemProgram.programId,
+          program: program.programId,
+        })
+        .remainingAccounts([{ pubkey: relayerTA2022, isWritable: true, isSigner: false }])
+        .rpc();
+
+    // Vault and refund ATAs are derived with the program owning the mint, so the passed token program must match.
+    try {
+      await executeRelayerRefundLeaf(TOKEN_PROGRAM_ID);
+      assert.fail("Execution passing the legacy token program should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "TokenProgramMismatch", "Expected TokenProgramMismatch error");
+    }
+
+    await executeRelayerRefundLeaf(tokenProgram);
+    const relayerAccount = await getAccount(connection, relayerTA2022, undefined, tokenProgram);
+    assertSE(relayerAccount.amount, relayerARefund, "Relayer should receive the refund");
+  });
+
   it("Sequential Leaf Refunds Relayers", async () => {
     const relayerRefundLeaves: RelayerRefundLeafType[] = [];
     const relayerRefundAmount = new BN(100000);
@@ -799,8 +1242,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [{ pubkey: relayerTA, isWritable: true, isSigner: false }];
 
@@ -817,12 +1270,21 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
       await loadExecuteRelayerRefundLeafParams(
         program,
         owner,
+        state,
         stateAccountData.rootBundleId,
         relayerRefundLeaves[i] as RelayerRefundLeafSolana,
         proof[i]
@@ -853,12 +1315,21 @@ describe("svm_spoke.bundle", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
           mint: mint,
           transferLiability,
+          errorCounters: program.programId,
+          bundleAudit: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+          claimLiability: createClaimLiabilityPda(mint, seed),
+          fillVolume: createFillVolumePda(mint, seed),
+          executionApproval: program.programId,
           systemProgram: web3.SystemProgram.programId,
           program: program.programId,
         };
         await loadExecuteRelayerRefundLeafParams(
           program,
           owner,
+          state,
           stateAccountData.rootBundleId,
           relayerRefundLeaves[i] as RelayerRefundLeafSolana,
           proof[i]
@@ -875,44 +1346,134 @@ describe("svm_spoke.bundle", () => {
     }
   });
 
-  it("Should allow the owner to delete the root bundle", async () => {
-    const relayerRefundRootBuffer = crypto.randomBytes(32);
-    const slowRelayRootBuffer = crypto.randomBytes(32);
-    const relayerRefundRootArray = Array.from(relayerRefundRootBuffer);
-    const slowRelayRootArray = Array.from(slowRelayRootBuffer);
+  it("Returns unclaimed leaves around the claimed bitmap byte boundaries", async () => {
+    const leafCount = 10;
+    const relayerRefundLeaves: RelayerRefundLeafType[] = [...Array(leafCount).keys()].map((leafId) => ({
+      isSolana: true,
+      leafId: new BN(leafId),
+      chainId: chainId,
+      amountToReturn: new BN(0),
+      mintPublicKey: mint,
+      refundAddresses: [relayerA.publicKey],
+      refundAmounts: [new BN(1000)],
+    }));
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
 
-    let stateAccountData = await program.account.state.fetch(state);
-    const initialRootBundleId = stateAccountData.rootBundleId;
-    const rootBundleId = stateAccountData.rootBundleId;
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
     const rootBundleIdBuffer = Buffer.alloc(4);
     rootBundleIdBuffer.writeUInt32LE(rootBundleId);
     const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
-
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
     await program.methods
-      .relayRootBundle(relayerRefundRootArray, slowRelayRootArray)
-      .accounts(relayRootBundleAccounts)
-      .rpc();
-
-    // Ensure the root bundle exists before deletion
-    let rootBundleData = await program.account.rootBundle.fetch(rootBundle);
-    assert.isNotNull(rootBundleData, "Root bundle should exist before deletion");
-
-    // Attempt to delete the root bundle as a non-owner
-    try {
-      const emergencyDeleteRootBundleAccounts = {
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts({
         state,
         rootBundle,
-        signer: nonOwner.publicKey,
-        closer: nonOwner.publicKey,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
         program: program.programId,
-      };
+      })
+      .rpc();
+
+    const getUnclaimedLeaves = (startLeafId: number) =>
+      program.methods.getUnclaimedLeaves(rootBundleId, startLeafId, leafCount).accounts({ state, rootBundle }).view();
+    const executeLeaf = async (leafId: number) => {
+      const proof = merkleTree.getProof(relayerRefundLeaves[leafId]).map((p) => Array.from(p));
+      const leaf = relayerRefundLeaves[leafId] as RelayerRefundLeafSolana;
+      await loadExecuteRelayerRefundLeafParams(program, owner, state, rootBundleId, leaf, proof);
       await program.methods
-        .emergencyDeleteRootBundle(rootBundleId)
-        .accounts(emergencyDeleteRootBundleAccounts)
-        .signers([nonOwner])
-        .rpc();
+        .executeRelayerRefundLeaf()
+        .accounts({
+          state,
+          rootBundle,
+          signer: owner,
+          vault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          mint,
+          transferLiability,
+          errorCounters: program.programId,
+          bundleAudit: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+          claimLiability: createClaimLiabilityPda(mint, seed),
+          fillVolume: createFillVolumePda(mint, seed),
+          executionApproval: program.programId,
+          systemProgram: web3.SystemProgram.programId,
+          program: program.programId,
+        })
+        .remainingAccounts([{ pubkey: relayerTA, isWritable: true, isSigner: false }])
+        .rpc();
+    };
+
+    const allLeafIds = [...Array(leafCount).keys()];
+    assert.deepEqual(await getUnclaimedLeaves(0), allLeafIds, "All leaves should be unclaimed");
+
+    // First and last bit of the first byte, first bit of the second byte and the last leaf.
+    const claimedLeafIds = [0, 7, 8, leafCount - 1];
+    for (const leafId of claimedLeafIds) await executeLeaf(leafId);
+    const unclaimedLeafIds = allLeafIds.filter((leafId) => !claimedLeafIds.includes(leafId));
+    assert.deepEqual(await getUnclaimedLeaves(0), unclaimedLeafIds, "Executed leaves should be claimed");
+    assert.deepEqual(await getUnclaimedLeaves(7), [], "No leaf from the start ID should be unclaimed");
+
+    for (const leafId of claimedLeafIds) {
+      try {
+        await executeLeaf(leafId);
+        assert.fail("Leaf should not be executed multiple times");
+      } catch (err: any) {
+        assert.include(err.toString(), "Leaf already claimed!", "Expected claimed leaf error");
+      }
+    }
+    assert.deepEqual(await getUnclaimedLeaves(0), unclaimedLeafIds, "Rejected executions should not claim leaves");
+  });
+
+  it("Should allow the owner to delete the root bundle", async () => {
+    const relayerRefundRootBuffer = crypto.randomBytes(32);
+    const slowRelayRootBuffer = crypto.randomBytes(32);
+    const relayerRefundRootArray = Array.from(relayerRefundRootBuffer);
+    const slowRelayRootArray = Array.from(slowRelayRootBuffer);
+
+    let stateAccountData = await program.account.state.fetch(state);
+    const initialRootBundleId = stateAccountData.rootBundleId;
+    const rootBundleId = stateAccountData.rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+    const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(relayerRefundRootArray, slowRelayRootArray, 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
+
+    // Ensure the root bundle exists before deletion
+    let rootBundleData = await program.account.rootBundle.fetch(rootBundle);
+    assert.isNotNull(rootBundleData, "Root bundle should exist before deletion");
+
+    // Attempt to delete the root bundle as a non-owner
+    try {
+      const emergencyDeleteRootBundleAccounts = {
+        state,
+        rootBundle,
+        signer: nonOwner.publicKey,
+        closer: nonOwner.publicKey,
+        program: program.programId,
+      };
+      await program.methods
+        .emergencyDeleteRootBundle(rootBundleId)
+        .accounts(emergencyDeleteRootBundleAccounts)
+        .signers([nonOwner])
+        .rpc();
       assert.fail("Non-owner should not be able to delete the root bundle");
     } catch (err: any) {
       assert.include(err.toString(), "NotOwner", "Expected error for non-owner trying to delete root bundle");
@@ -958,10 +1519,11 @@ describe("svm_spoke.bundle", () => {
       rootBundle: newRootBundle,
       signer: owner,
       payer: owner,
+      adminNonce: program.programId,
       program: program.programId,
     };
     await program.methods
-      .relayRootBundle(newRelayerRefundRootArray, newSlowRelayRootArray)
+      .relayRootBundle(newRelayerRefundRootArray, newSlowRelayRootArray, 0, null, 0, null)
       .accounts(newRelayRootBundleAccounts)
       .rpc();
 
@@ -982,6 +1544,9 @@ describe("svm_spoke.bundle", () => {
   });
 
   describe("Execute Max Refunds", () => {
+    // Refunds fitting in a single ExecutedRelayerRefundRoot event, and in each of its chunks.
+    const maxRefundsPerEvent = 27;
+
     const executeMaxRefunds = async (testConfig: {
       solanaDistributions: number;
       deferredRefunds: boolean;
@@ -1045,8 +1610,18 @@ describe("svm_spoke.bundle", () => {
       const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
       // Relay root bundle
-      const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-      await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+      const relayRootBundleAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+        .accounts(relayRootBundleAccounts)
+        .rpc();
 
       // Verify valid leaf
       const proofAsNumbers = proof.map((p) => Array.from(p));
@@ -1067,6 +1642,14 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         // Appended by Acnhor `event_cpi` macro:
         eventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], program.programId)[0],
@@ -1087,7 +1670,14 @@ describe("svm_spoke.bundle", () => {
         : [];
 
       // Build the instruction to execute relayer refund leaf and write its instruction args to the data account.
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
 
       const executeInstruction = !testConfig.deferredRefunds
         ? await program.methods
@@ -1119,12 +1709,35 @@ describe("svm_spoke.bundle", () => {
       instructions.push(executeInstruction);
 
       // Execute using ALT.
-      await sendTransactionWithLookupTable(
+      const { txSignature } = await sendTransactionWithLookupTable(
         connection,
         instructions,
         (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer
       );
 
+      // Refund events exceeding MAX_EVENT_CPI_DATA_SIZE are emitted as chunks carrying the refunds in leaf order.
+      const events = await readEventsUntilFound(connection, txSignature, [program]);
+      const chunks = events.filter((event) => event.name === "executedRelayerRefundRootChunk").map((e) => e.data);
+      const refundEvents = chunks.length ? chunks : [events.find((e) => e.name === "executedRelayerRefundRoot")?.data];
+      const refundCount = testConfig.solanaDistributions;
+      const expectedChunks = refundCount > maxRefundsPerEvent ? Math.ceil(refundCount / maxRefundsPerEvent) : 0;
+      assert.strictEqual(chunks.length, expectedChunks, "Refund event chunk count should match");
+      chunks.forEach((chunk, i) => {
+        assert.strictEqual(chunk.chunkIndex, i, "Chunk index should match");
+        assert.strictEqual(chunk.totalChunks, chunks.length, "Total chunks should match");
+        assertSE(chunk.leafId, leaf.leafId, "Leaf ID should match");
+      });
+      assert.deepEqual(
+        refundEvents.flatMap((event) => event.refundAddresses.map((address: PublicKey) => address.toString())),
+        refundAddresses.map((address) => address.toString()),
+        "Refund addresses should match in leaf order"
+      );
+      assert.deepEqual(
+        refundEvents.flatMap((event) => event.refundAmounts.map((amount: BN) => amount.toString())),
+        refundAmounts.map((amount) => amount.toString()),
+        "Refund amounts should match in leaf order"
+      );
+
       // Verify all refund account balances (either token or claim accounts).
       await new Promise((resolve) => setTimeout(resolve, 1000)); // Make sure account balances have been synced.
       const refundBalances = await Promise.all(
@@ -1142,8 +1755,8 @@ describe("svm_spoke.bundle", () => {
     };
 
     it("Execute Max Refunds to Token Accounts", async () => {
-      // Higher refund count hits inner instruction size limit when doing `emit_cpi` on public devnet. On localnet this is
-      // not an issue, but we hit out of memory panic above 32 refunds. This should not be an issue as currently Across
+      // Refund events above 27 refunds are chunked to fit the inner instruction size limit of `emit_cpi` on public
+      // devnet, but we hit out of memory panic above 32 refunds. This should not be an issue as currently Across
       // protocol does not expect this to be above 25.
       const solanaDistributions = 28;
 
@@ -1188,8 +1801,18 @@ describe("svm_spoke.bundle", () => {
       rootBundleIdBuffer.writeUInt32LE(rootBundleId);
       const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
       const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
-      let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-      await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+      let relayRootBundleAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+        .accounts(relayRootBundleAccounts)
+        .rpc();
       const proofAsNumbers = proof.map((p) => Array.from(p));
       const executeRelayerRefundLeafAccounts = {
         state: state,
@@ -1199,10 +1822,25 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
 
       await program.methods.executeRelayerRefundLeaf().accounts(executeRelayerRefundLeafAccounts).rpc();
     };
@@ -1264,8 +1902,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [{ pubkey: relayerTA, isWritable: true, isSigner: false }];
 
@@ -1282,12 +1930,21 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint: mint,
         transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
         program: program.programId,
       };
       await loadExecuteRelayerRefundLeafParams(
         program,
         owner,
+        state,
         stateAccountData.rootBundleId,
         relayerRefundLeaves[i] as RelayerRefundLeafSolana,
         proof[i]
@@ -1338,8 +1995,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -1355,11 +2022,26 @@ describe("svm_spoke.bundle", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
     const proofAsNumbers = proof.map((p) => Array.from(p));
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
 
     // Mismatched refund amount and account length should fail.
     try {
@@ -1374,11 +2056,214 @@ describe("svm_spoke.bundle", () => {
     }
   });
 
+  it("Fails to execute relayer refund leaf against a slow relay only root bundle", async () => {
+    const relayerRefundLeaves: RelayerRefundLeafType[] = [];
+    relayerRefundLeaves.push({
+      isSolana: true,
+      leafId: new BN(0),
+      chainId: chainId,
+      amountToReturn: new BN(0),
+      mintPublicKey: mint,
+      refundAddresses: [relayerA.publicKey],
+      refundAmounts: [new BN(400000)],
+    });
+
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
+    const proof = merkleTree.getProof(relayerRefundLeaves[0]);
+    const leaf = relayerRefundLeaves[0] as RelayerRefundLeafSolana;
+
+    const stateAccountData = await program.account.state.fetch(state);
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(stateAccountData.rootBundleId);
+    const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+    const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+
+    // Relay root bundle with the relayer refund root zeroed.
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(new Array(32).fill(0), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
+
+    const executeRelayerRefundLeafAccounts = {
+      state: state,
+      rootBundle: rootBundle,
+      signer: owner,
+      vault: vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: mint,
+      transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
+      systemProgram: web3.SystemProgram.programId,
+      program: program.programId,
+    };
+    const proofAsNumbers = proof.map((p) => Array.from(p));
+    const rootBundleId = stateAccountData.rootBundleId;
+    await loadExecuteRelayerRefundLeafParams(program, owner, state, rootBundleId, leaf, proofAsNumbers);
+
+    try {
+      await program.methods
+        .executeRelayerRefundLeaf()
+        .accounts(executeRelayerRefundLeafAccounts)
+        .remainingAccounts([{ pubkey: relayerTA, isWritable: true, isSigner: false }])
+        .rpc();
+      assert.fail("Execution should have failed against a root bundle without relayer refund root");
+    } catch (err: any) {
+      assert.include(err.toString(), "BundleHasNoRelayerRefundRoot", "Expected BundleHasNoRelayerRefundRoot error");
+    }
+  });
+
+  it("Snapshots vault balances around bundle executions when audits are enabled", async () => {
+    await program.methods
+      .setBundleAuditConfig(true, 1)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+
+    // Two leaves with refunds of the same mint.
+    const relayerARefund = new BN(400000);
+    const relayerBRefund = new BN(100000);
+    const relayerRefundLeaves: RelayerRefundLeafType[] = [
+      {
+        isSolana: true,
+        leafId: new BN(0),
+        chainId: chainId,
+        amountToReturn: new BN(0),
+        mintPublicKey: mint,
+        refundAddresses: [relayerA.publicKey],
+        refundAmounts: [relayerARefund],
+      },
+      {
+        isSolana: true,
+        leafId: new BN(1),
+        chainId: chainId,
+        amountToReturn: new BN(0),
+        mintPublicKey: mint,
+        refundAddresses: [relayerB.publicKey],
+        refundAmounts: [relayerBRefund],
+      },
+    ];
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    const [bundleAudit] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bundle_audit"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
+
+    const executeLeaf = async (leafIndex: number, refundAccount: PublicKey, bundleAuditAccount = bundleAudit) => {
+      const leaf = relayerRefundLeaves[leafIndex] as RelayerRefundLeafSolana;
+      const proofAsNumbers = merkleTree.getProof(leaf).map((p) => Array.from(p));
+      await loadExecuteRelayerRefundLeafParams(program, owner, state, rootBundleId, leaf, proofAsNumbers);
+      const executeRelayerRefundLeafAccounts = {
+        signer: owner,
+        state,
+        rootBundle,
+        vault,
+        mint,
+        transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: bundleAuditAccount,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: web3.SystemProgram.programId,
+        program: program.programId,
+      };
+      return await program.methods
+        .executeRelayerRefundLeaf()
+        .accounts(executeRelayerRefundLeafAccounts)
+        .remainingAccounts([{ pubkey: refundAccount, isWritable: true, isSigner: false }])
+        .rpc();
+    };
+
+    // Bundle audit account is required once audits are enabled.
+    try {
+      await executeLeaf(0, relayerTA, program.programId);
+      assert.fail("Execution should have failed without bundle audit account");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingBundleAudit", "Expected MissingBundleAudit error");
+    }
+
+    await program.methods
+      .initializeBundleAudit(rootBundleId)
+      .accounts({ signer: owner, state, rootBundle, bundleAudit })
+      .rpc();
+
+    const iVaultBal = BigInt((await connection.getTokenAccountBalance(vault)).value.amount);
+
+    // First leaf records the vault balance before any refunds of the bundle.
+    const tx = await executeLeaf(0, relayerTA);
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "bundleAuditSnapshot")?.data;
+    assertSE(event.rootBundleId, rootBundleId, "rootBundleId should match");
+    assertSE(event.mint, mint, "mint should match");
+    assertSE(event.balanceBefore, iVaultBal, "balanceBefore should match initial vault balance");
+    assertSE(event.balanceAfter, iVaultBal - BigInt(relayerARefund.toString()), "balanceAfter should match");
+
+    // Last leaf keeps the initial balance and updates the balance after the bundle.
+    await executeLeaf(1, relayerTB);
+    const bundleAuditData = await program.account.bundleAudit.fetch(bundleAudit);
+    const fVaultBal = BigInt((await connection.getTokenAccountBalance(vault)).value.amount);
+    assert.strictEqual(bundleAuditData.snapshots.length, 1, "Only one mint should be tracked");
+    assertSE(bundleAuditData.snapshots[0].balanceBefore, iVaultBal, "Snapshot should start before the first leaf");
+    assertSE(bundleAuditData.snapshots[0].balanceAfter, fVaultBal, "Snapshot should end after the last leaf");
+    assert.strictEqual(
+      iVaultBal - fVaultBal,
+      BigInt(relayerARefund.add(relayerBRefund).toString()),
+      "Vault balance should be reduced by all refunds"
+    );
+  });
+
   describe("Deferred refunds in ExecutedRelayerRefundRoot events", () => {
-    const executeRelayerRefundLeaf = async (testConfig: { deferredRefunds: boolean }) => {
+    const executeRelayerRefundLeaf = async (testConfig: {
+      deferredRefunds: boolean;
+      errorCounters?: PublicKey;
+      relayerBWithoutAta?: { publicKey: PublicKey };
+      withoutClaimAccount?: boolean;
+      createAtaB?: boolean;
+    }) => {
       // Create new relayer accounts for each sub-test.
       const relayerA = Keypair.generate();
-      const relayerB = Keypair.generate();
+      const relayerB = testConfig.relayerBWithoutAta ?? Keypair.generate();
       const relayerARefund = new BN(400000);
       const relayerBRefund = new BN(100000);
 
@@ -1387,7 +2272,9 @@ describe("svm_spoke.bundle", () => {
       // Create refund accounts depending on the refund type.
       if (!testConfig.deferredRefunds) {
         refundA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, relayerA.publicKey)).address;
-        refundB = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, relayerB.publicKey)).address;
+        refundB = testConfig.relayerBWithoutAta
+          ? getAssociatedTokenAddressSync(mint, relayerB.publicKey, true)
+          : (await getOrCreateAssociatedTokenAccount(connection, payer, mint, relayerB.publicKey)).address;
       } else {
         [refundA] = PublicKey.findProgramAddressSync(
           [Buffer.from("claim_account"), mint.toBuffer(), relayerA.publicKey.toBuffer()],
@@ -1428,8 +2315,18 @@ describe("svm_spoke.bundle", () => {
       const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
       // Relay root bundle
-      const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-      await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+      const relayRootBundleAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+        .accounts(relayRootBundleAccounts)
+        .rpc();
 
       // Pass refund addresses in remaining accounts.
       const remainingAccounts = [
@@ -1437,6 +2334,27 @@ describe("svm_spoke.bundle", () => {
         { pubkey: refundB, isWritable: true, isSigner: false },
       ];
 
+      // Refunds to missing ATAs fall back to the claim account at index n + i, the unused slot of relayerA is padded.
+      if (testConfig.relayerBWithoutAta && !testConfig.withoutClaimAccount) {
+        const [claimB] = PublicKey.findProgramAddressSync(
+          [Buffer.from("claim_account"), mint.toBuffer(), relayerB.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods.initializeClaimAccount(mint, relayerB.publicKey).rpc();
+        remainingAccounts.push(
+          { pubkey: program.programId, isWritable: false, isSigner: false },
+          { pubkey: claimB, isWritable: true, isSigner: false }
+        );
+      }
+
+      // Missing ATAs of refund addresses passed at index 2n + i are created, unused slots before it are padded.
+      if (testConfig.createAtaB) {
+        while (remainingAccounts.length < 5) {
+          remainingAccounts.push({ pubkey: program.programId, isWritable: false, isSigner: false });
+        }
+        remainingAccounts.push({ pubkey: relayerB.publicKey, isWritable: false, isSigner: false });
+      }
+
       // Verify valid leaf
       const executeRelayerRefundLeafAccounts = {
         state,
@@ -1446,11 +2364,27 @@ describe("svm_spoke.bundle", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         mint,
         transferLiability,
+        errorCounters: testConfig.errorCounters ?? program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
         systemProgram: web3.SystemProgram.programId,
+        associatedTokenProgram: testConfig.createAtaB ? ASSOCIATED_TOKEN_PROGRAM_ID : program.programId,
         program: program.programId,
       };
       const proofAsNumbers = proof.map((p) => Array.from(p));
-      await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+      await loadExecuteRelayerRefundLeafParams(
+        program,
+        owner,
+        state,
+        stateAccountData.rootBundleId,
+        leaf,
+        proofAsNumbers,
+      );
 
       if (!testConfig.deferredRefunds) {
         return await program.methods
@@ -1481,6 +2415,121 @@ describe("svm_spoke.bundle", () => {
       const event = events.find((event) => event.name === "executedRelayerRefundRoot")?.data;
       assert.isTrue(event.deferredRefunds, "deferredRefunds should be true");
     });
+
+    it("Tracks deferred refunds in error counters", async () => {
+      const [errorCounters] = PublicKey.findProgramAddressSync(
+        [Buffer.from("error_counters"), seed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods.initializeErrorCounters().accounts({ state }).rpc();
+
+      // Direct refunds should not be recorded.
+      await executeRelayerRefundLeaf({ deferredRefunds: false, errorCounters });
+      let counters = await program.methods.getErrorCounters().accounts({ state }).view();
+      assert.strictEqual(counters.deferredRefundLeaves, 0, "Deferred refund leaves should not be incremented");
+      assert.strictEqual(counters.deferredRefunds, 0, "Deferred refunds should not be incremented");
+
+      // Deferred refunds should increment leaf counter by one and refund counter by the number of refunds in leaf.
+      await executeRelayerRefundLeaf({ deferredRefunds: true, errorCounters });
+      counters = await program.methods.getErrorCounters().accounts({ state }).view();
+      assert.strictEqual(counters.deferredRefundLeaves, 1, "Deferred refund leaves should be incremented");
+      assert.strictEqual(counters.deferredRefunds, 2, "Deferred refunds should be incremented");
+    });
+
+    it("Defers refunds to missing ATAs to claim accounts without blocking the leaf", async () => {
+      const relayerB = Keypair.generate();
+      const [claimB] = PublicKey.findProgramAddressSync(
+        [Buffer.from("claim_account"), mint.toBuffer(), relayerB.publicKey.toBuffer()],
+        program.programId
+      );
+      const iVaultBal = (await getAccount(connection, vault)).amount;
+
+      const tx = await executeRelayerRefundLeaf({ deferredRefunds: false, relayerBWithoutAta: relayerB });
+
+      // Only the refund of relayerA (400000) leaves the vault, the refund of relayerB (100000) accrues to its claim.
+      assertSE((await getAccount(connection, vault)).amount, iVaultBal - BigInt(400000), "Vault should send refund A");
+      const claimAccount = await program.account.claimAccount.fetch(claimB);
+      assertSE(claimAccount.amount, 100000, "Refund B should be credited to the claim account");
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "executedRelayerRefundRoot")?.data;
+      assert.isTrue(event.deferredRefunds, "deferredRefunds should be true");
+      const deferredEvent = events.find((event) => event.name === "refundDeferred")?.data;
+      assertSE(deferredEvent.refundAddress, relayerB.publicKey, "Deferred refund address should match");
+      assertSE(deferredEvent.claimAccount, claimB, "Deferred claim account should match");
+      assertSE(deferredEvent.amount, 100000, "Deferred amount should match");
+      assert.isUndefined(events.find((event) => event.name === "refundPaid"), "No ATA should have been created");
+    });
+
+    it("Creates missing ATAs of refund addresses and pays them directly", async () => {
+      // Refund address of a depositor refunded for an expired deposit, without an ATA for the mint.
+      const depositor = Keypair.generate();
+      const depositorAta = getAssociatedTokenAddressSync(mint, depositor.publicKey);
+      const iVaultBal = (await getAccount(connection, vault)).amount;
+
+      const tx = await executeRelayerRefundLeaf({
+        deferredRefunds: false,
+        relayerBWithoutAta: depositor,
+        withoutClaimAccount: true,
+        createAtaB: true,
+      });
+
+      const fVaultBal = (await getAccount(connection, vault)).amount;
+      assertSE(fVaultBal, iVaultBal - BigInt(500000), "Vault should send both refunds");
+      assertSE((await getAccount(connection, depositorAta)).amount, 100000, "Depositor ATA should receive refund B");
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "executedRelayerRefundRoot")?.data;
+      assert.isFalse(event.deferredRefunds, "deferredRefunds should be false");
+      const paidEvents = events.filter((event) => event.name === "refundPaid");
+      assert.strictEqual(paidEvents.length, 1, "Only the refund to the created ATA should emit RefundPaid");
+      assertSE(paidEvents[0].data.refundAddress, depositor.publicKey, "Paid refund address should match");
+      assertSE(paidEvents[0].data.tokenAccount, depositorAta, "Paid token account should match");
+      assertSE(paidEvents[0].data.amount, 100000, "Paid amount should match");
+      assert.isUndefined(events.find((event) => event.name === "refundDeferred"), "No refund should be deferred");
+    });
+
+    it("Defers refunds of program owned refund addresses to claim accounts", async () => {
+      // Program owned refund address, whose missing ATA is left to its program.
+      const [errorCounters] = PublicKey.findProgramAddressSync(
+        [Buffer.from("error_counters"), seed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods.initializeErrorCounters().accounts({ state }).rpc();
+      const programOwned = { publicKey: errorCounters };
+      const [claimB] = PublicKey.findProgramAddressSync(
+        [Buffer.from("claim_account"), mint.toBuffer(), programOwned.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await executeRelayerRefundLeaf({
+        deferredRefunds: false,
+        relayerBWithoutAta: programOwned,
+        createAtaB: true,
+      });
+
+      const ata = getAssociatedTokenAddressSync(mint, programOwned.publicKey, true);
+      assert.isNull(await connection.getAccountInfo(ata), "ATA of the program owned address should not be created");
+      assertSE((await program.account.claimAccount.fetch(claimB)).amount, 100000, "Refund B should be claimable");
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const deferredEvent = events.find((event) => event.name === "refundDeferred")?.data;
+      assertSE(deferredEvent.refundAddress, programOwned.publicKey, "Deferred refund address should match");
+      assert.isUndefined(events.find((event) => event.name === "refundPaid"), "No ATA should have been created");
+    });
+
+    it("Requires claim accounts for refunds to missing ATAs", async () => {
+      try {
+        await executeRelayerRefundLeaf({
+          deferredRefunds: false,
+          relayerBWithoutAta: Keypair.generate(),
+          withoutClaimAccount: true,
+        });
+        assert.fail("Leaf should not execute without the claim account of the missing ATA");
+      } catch (err: any) {
+        assert.include(err.toString(), "MissingRefundClaimAccount", "Expected MissingRefundClaimAccount error");
+      }
+    });
   });
 
   it("Cannot execute relayer refund leaf with insufficient pool balance", async () => {
@@ -1515,8 +2564,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [{ pubkey: relayerTA, isWritable: true, isSigner: false }];
 
@@ -1528,11 +2587,26 @@ describe("svm_spoke.bundle", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
     const proofAsNumbers = proof.map((p) => Array.from(p));
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
 
     // Leaf execution should fail due to insufficient balance.
     try {
@@ -1607,8 +2681,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -1624,11 +2708,26 @@ describe("svm_spoke.bundle", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
     const proofAsNumbers = proof.map((p) => Array.from(p));
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
 
     try {
       await program.methods
@@ -1698,8 +2797,18 @@ describe("svm_spoke.bundle", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    let relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    let relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     const remainingAccounts = [
       { pubkey: relayerTA, isWritable: true, isSigner: false },
@@ -1715,11 +2824,26 @@ describe("svm_spoke.bundle", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
     const proofAsNumbers = proof.map((p) => Array.from(p));
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
 
     try {
       await program.methods
@@ -1732,4 +2856,395 @@ describe("svm_spoke.bundle", () => {
       assert.include(err.toString(), "Invalid Merkle proof", "Expected merkle verification to fail");
     }
   });
+
+  it("Rate limits vault outflows across relayer refund leaves within each window", async () => {
+    const window = 3600;
+    const [rateLimit] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rate_limit"), seed.toArrayLike(Buffer, "le", 8), mint.toBuffer()],
+      program.programId
+    );
+    const setRateLimitAccounts = {
+      signer: owner,
+      state,
+      mint,
+      rateLimit,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+      program: program.programId,
+    };
+    await program.methods.setVaultOutflowRateLimit(new BN(500000), window).accounts(setRateLimitAccounts).rpc();
+    await program.methods
+      .setVaultOutflowRateLimits(true)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+
+    const refunds = [
+      { relayer: relayerA, refundAccount: relayerTA, amount: new BN(300000) },
+      { relayer: relayerB, refundAccount: relayerTB, amount: new BN(300000) },
+      { relayer: relayerA, refundAccount: relayerTA, amount: new BN(200000) },
+      { relayer: relayerB, refundAccount: relayerTB, amount: new BN(400000) },
+    ];
+    const relayerRefundLeaves: RelayerRefundLeafType[] = refunds.map((refund, i) => ({
+      isSolana: true,
+      leafId: new BN(i),
+      chainId: chainId,
+      amountToReturn: new BN(0),
+      mintPublicKey: mint,
+      refundAddresses: [refund.relayer.publicKey],
+      refundAmounts: [refund.amount],
+    }));
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
+
+    const executeLeaf = async (leafIndex: number, rateLimitAccount = rateLimit) => {
+      const leaf = relayerRefundLeaves[leafIndex] as RelayerRefundLeafSolana;
+      const proofAsNumbers = merkleTree.getProof(leaf).map((p) => Array.from(p));
+      await loadExecuteRelayerRefundLeafParams(program, owner, state, rootBundleId, leaf, proofAsNumbers);
+      const executeRelayerRefundLeafAccounts = {
+        signer: owner,
+        state,
+        rootBundle,
+        vault,
+        mint,
+        transferLiability,
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: rateLimitAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: web3.SystemProgram.programId,
+        program: program.programId,
+      };
+      return await program.methods
+        .executeRelayerRefundLeaf()
+        .accounts(executeRelayerRefundLeafAccounts)
+        .remainingAccounts([{ pubkey: refunds[leafIndex].refundAccount, isWritable: true, isSigner: false }])
+        .rpc();
+    };
+
+    const assertRateLimited = async (leafIndex: number) => {
+      try {
+        await executeLeaf(leafIndex);
+        assert.fail("Execution should have failed due to the vault outflow rate limit");
+      } catch (err: any) {
+        assert.include(err.toString(), "VaultOutflowRateLimited", "Expected VaultOutflowRateLimited error");
+      }
+    };
+
+    // Rate limit account is required once vault outflow rate limits are enabled.
+    try {
+      await executeLeaf(0, program.programId);
+      assert.fail("Execution should have failed without rate limit account");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingVaultOutflowRateLimit", "Expected MissingVaultOutflowRateLimit error");
+    }
+
+    // Outflows accumulate across leaves within the window up to the limit.
+    await executeLeaf(0);
+    await assertRateLimited(1);
+    await executeLeaf(2);
+    let rateLimitData = await program.account.rateLimit.fetch(rateLimit);
+    assertSE(rateLimitData.windowOutflow, 500000, "Window outflow should include all executed refunds");
+
+    // Window resets on its fixed schedule, regardless of the time of the first outflow.
+    const nextWindowStart = rateLimitData.windowStart + window;
+    await setCurrentTime(program, state, new BN(nextWindowStart + window / 2));
+    await executeLeaf(1);
+    rateLimitData = await program.account.rateLimit.fetch(rateLimit);
+    assertSE(rateLimitData.windowStart, nextWindowStart, "Window should start on the fixed schedule");
+    assertSE(rateLimitData.windowOutflow, 300000, "Window outflow should only include refunds of the new window");
+
+    // Raising the limit lifts rejected outflows within the same window.
+    await assertRateLimited(3);
+    await program.methods.setVaultOutflowRateLimit(new BN(700000), window).accounts(setRateLimitAccounts).rpc();
+    await executeLeaf(3);
+    rateLimitData = await program.account.rateLimit.fetch(rateLimit);
+    assertSE(rateLimitData.windowOutflow, 700000, "Window outflow should be kept when raising the limit");
+
+    // Disable rate limits for the remaining tests sharing the state.
+    await program.methods
+      .setVaultOutflowRateLimits(false)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+  });
+
+  describe("Refunds bounded by the fill volume of the mint", () => {
+    const approver = Keypair.generate();
+    const tolerance = new BN(1000);
+
+    let fillVolumeMint: PublicKey, fillVolumeVault: PublicKey, fillVolumeRelayerTA: PublicKey;
+    let fillVolume: PublicKey, fillVolumeTransferLiability: PublicKey;
+
+    const getExecutionApproval = (leafHash: number[]) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("execution_approval"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(leafHash)],
+        program.programId
+      )[0];
+
+    const reconcileFillVolume = async (amount: number) => {
+      await program.methods
+        .reconcileFillVolume(fillVolumeMint, new BN(amount))
+        .accounts({ signer: owner, state, fillVolume, program: program.programId })
+        .rpc();
+    };
+
+    const setExecutionApprover = async (executionApprover: PublicKey | null) => {
+      await program.methods
+        .setExecutionApprover(executionApprover, 3600)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+    };
+
+    // Relays a root bundle refunding relayer A once per amount, returning the leaves and their hashes.
+    const relayRefundBundle = async (refundAmounts: number[]) => {
+      const relayerRefundLeaves: RelayerRefundLeafType[] = refundAmounts.map((amount, i) => ({
+        isSolana: true,
+        leafId: new BN(i),
+        chainId: chainId,
+        amountToReturn: new BN(0),
+        mintPublicKey: fillVolumeMint,
+        refundAddresses: [relayerA.publicKey],
+        refundAmounts: [new BN(amount)],
+      }));
+      const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+      const root = merkleTree.getRoot();
+
+      const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+      const rootBundleIdBuffer = Buffer.alloc(4);
+      rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+      const [rootBundle] = PublicKey.findProgramAddressSync(
+        [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+        program.programId
+      );
+      await program.methods
+        .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+        .accounts({
+          state,
+          rootBundle,
+          signer: owner,
+          payer: owner,
+          adminNonce: program.programId,
+          program: program.programId,
+        })
+        .rpc();
+
+      return { rootBundleId, rootBundle, merkleTree, relayerRefundLeaves };
+    };
+
+    const executeLeaf = async (
+      bundle: Awaited<ReturnType<typeof relayRefundBundle>>,
+      leafIndex: number,
+      executionApproval = program.programId
+    ) => {
+      const leaf = bundle.relayerRefundLeaves[leafIndex] as RelayerRefundLeafSolana;
+      const proofAsNumbers = bundle.merkleTree.getProof(leaf).map((p) => Array.from(p));
+      await loadExecuteRelayerRefundLeafParams(program, owner, state, bundle.rootBundleId, leaf, proofAsNumbers);
+      return await program.methods
+        .executeRelayerRefundLeaf()
+        .accounts({
+          signer: owner,
+          state,
+          rootBundle: bundle.rootBundle,
+          vault: fillVolumeVault,
+          mint: fillVolumeMint,
+          transferLiability: fillVolumeTransferLiability,
+          errorCounters: program.programId,
+          bundleAudit: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(fillVolumeMint, seed),
+          claimLiability: createClaimLiabilityPda(fillVolumeMint, seed),
+          fillVolume,
+          executionApproval,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+          program: program.programId,
+        })
+        .remainingAccounts([{ pubkey: fillVolumeRelayerTA, isWritable: true, isSigner: false }])
+        .rpc();
+    };
+
+    const assertRefundsExceedFillVolume = async (
+      bundle: Awaited<ReturnType<typeof relayRefundBundle>>,
+      leafIndex: number,
+      executionApproval = program.programId
+    ) => {
+      try {
+        await executeLeaf(bundle, leafIndex, executionApproval);
+        assert.fail("Execution should have failed due to refunds exceeding the fill volume");
+      } catch (err: any) {
+        assert.include(err.toString(), "RefundsExceedFillVolume", "Expected RefundsExceedFillVolume error");
+      }
+    };
+
+    // Approves the leaf by the execution approver, returning its execution approval PDA.
+    const approveLeaf = async (bundle: Awaited<ReturnType<typeof relayRefundBundle>>, leafIndex: number) => {
+      const leafHash = Array.from(relayerRefundHashFn(bundle.relayerRefundLeaves[leafIndex]));
+      const executionApproval = getExecutionApproval(leafHash);
+      await program.methods
+        .createExecutionApproval(leafHash)
+        .accounts({ signer: approver.publicKey, state, executionApproval, program: program.programId })
+        .signers([approver])
+        .rpc();
+      return executionApproval;
+    };
+
+    before(async () => {
+      fillVolumeMint = await createMint(connection, payer, owner, owner, 6);
+      const getTokenAccount = async (accountOwner: PublicKey) => {
+        return (await getOrCreateAssociatedTokenAccount(connection, payer, fillVolumeMint, accountOwner, true)).address;
+      };
+      fillVolumeVault = await getTokenAccount(getVaultAuthority(state));
+      fillVolumeRelayerTA = await getTokenAccount(relayerA.publicKey);
+      await mintTo(connection, payer, fillVolumeMint, fillVolumeVault, owner, initialMintAmount);
+      [fillVolumeTransferLiability] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transfer_liability"), fillVolumeMint.toBuffer()],
+        program.programId
+      );
+      fillVolume = createFillVolumePda(fillVolumeMint, seed);
+
+      const sig = await connection.requestAirdrop(approver.publicKey, 10_000_000_000);
+      await provider.connection.confirmTransaction(sig);
+
+      const tx = await program.methods
+        .setFillVolumeTolerance(fillVolumeMint, tolerance)
+        .accounts({ signer: owner, state, fillVolume, program: program.programId })
+        .rpc();
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "setFillVolumeTolerance")?.data;
+      assertSE(event.mint, fillVolumeMint, "Mint should match");
+      assertSE(event.tolerance, tolerance, "Tolerance should match");
+    });
+
+    after(async () => {
+      // Reset the execution approver for the remaining tests sharing the state.
+      await setExecutionApprover(null);
+    });
+
+    it("Executes refunds within the fill volume plus the tolerance", async () => {
+      await reconcileFillVolume(1_000_000);
+      const bundle = await relayRefundBundle([600_000, 400_000 + tolerance.toNumber(), 1]);
+
+      // The first leaf of the bundle closes the window of the fills preceding it.
+      await executeLeaf(bundle, 0);
+      let fillVolumeData = await program.account.fillVolume.fetch(fillVolume);
+      assert.strictEqual(fillVolumeData.rootBundleId, bundle.rootBundleId, "Window should be closed by the bundle");
+      assertSE(fillVolumeData.bundleFillVolume, 1_000_000, "Bundle fill volume should be the closed window");
+      assertSE(fillVolumeData.fillVolume, 0, "Fill volume of the open window should be reset");
+
+      // Refunds can reach the fill volume plus the tolerance, but not above it.
+      await executeLeaf(bundle, 1);
+      fillVolumeData = await program.account.fillVolume.fetch(fillVolume);
+      assertSE(fillVolumeData.bundleRefunds, 1_000_000 + tolerance.toNumber(), "Bundle refunds should be tracked");
+      await assertRefundsExceedFillVolume(bundle, 2);
+    });
+
+    it("Executes refunds above the fill volume with an approval by another execution approver", async () => {
+      await reconcileFillVolume(500_000);
+      const bundle = await relayRefundBundle([400_000, 200_000]);
+      await executeLeaf(bundle, 0);
+      await assertRefundsExceedFillVolume(bundle, 1);
+
+      // The executor approving its own leaf doesn't override the check.
+      await setExecutionApprover(owner);
+      const leafHash = Array.from(relayerRefundHashFn(bundle.relayerRefundLeaves[1]));
+      const ownerApproval = getExecutionApproval(leafHash);
+      await program.methods
+        .createExecutionApproval(leafHash)
+        .accounts({ signer: owner, state, executionApproval: ownerApproval, program: program.programId })
+        .rpc();
+      await assertRefundsExceedFillVolume(bundle, 1, ownerApproval);
+
+      // Approvals by a replaced approver are not honored, so the leaf needs a new approval by the second key.
+      await setExecutionApprover(approver.publicKey);
+      try {
+        await executeLeaf(bundle, 1, ownerApproval);
+        assert.fail("Execution should have failed with an approval by a replaced approver");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidExecutionApproval", "Expected InvalidExecutionApproval error");
+      }
+      await program.methods
+        .revokeExecutionApproval(leafHash)
+        .accounts({ signer: owner, state, executionApproval: ownerApproval, program: program.programId })
+        .rpc();
+
+      const executionApproval = await approveLeaf(bundle, 1);
+      const tx = await executeLeaf(bundle, 1, executionApproval);
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "approvedRefundsAboveFillVolume")?.data;
+      assert.strictEqual(event.rootBundleId, bundle.rootBundleId, "Root bundle ID should match");
+      assert.strictEqual(event.leafId, 1, "Leaf ID should match");
+      assertSE(event.mint, fillVolumeMint, "Mint should match");
+      assertSE(event.bundleRefunds, 600_000, "Bundle refunds should include the approved leaf");
+      assertSE(event.bundleFillVolume, 500_000, "Bundle fill volume should match");
+      assertSE(event.tolerance, tolerance, "Tolerance should match");
+      assertSE(event.approver, approver.publicKey, "Approver should match");
+    });
+
+    it("Executes refunds rejected after a reconciliation lowering the fill volume with an approval", async () => {
+      // Reconciling the open window below its actual fills makes the legitimate refunds of the next bundle exceed it.
+      await reconcileFillVolume(800_000);
+      const tx = await program.methods
+        .reconcileFillVolume(fillVolumeMint, new BN(300_000))
+        .accounts({ signer: owner, state, fillVolume, program: program.programId })
+        .rpc();
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "reconciledFillVolume")?.data;
+      assertSE(event.previousFillVolume, 800_000, "Previous fill volume should match");
+      assertSE(event.fillVolume, 300_000, "Reconciled fill volume should match");
+
+      const bundle = await relayRefundBundle([700_000]);
+      await assertRefundsExceedFillVolume(bundle, 0);
+
+      const executionApproval = await approveLeaf(bundle, 0);
+      await executeLeaf(bundle, 0, executionApproval);
+      const relayerAccount = await getAccount(connection, fillVolumeRelayerTA);
+      const totalRefunds = 1_000_000 + tolerance.toNumber() + 600_000 + 700_000;
+      assertSE(relayerAccount.amount, totalRefunds, "Relayer should receive all executed refunds");
+    });
+
+    it("Only the owner can set the tolerance and reconcile the fill volume", async () => {
+      try {
+        await program.methods
+          .setFillVolumeTolerance(fillVolumeMint, new BN(0))
+          .accounts({ signer: nonOwner.publicKey, state, fillVolume, program: program.programId })
+          .signers([nonOwner])
+          .rpc();
+        assert.fail("Non-owner should not be able to set the tolerance");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+      }
+
+      try {
+        await program.methods
+          .reconcileFillVolume(fillVolumeMint, new BN(0))
+          .accounts({ signer: nonOwner.publicKey, state, fillVolume, program: program.programId })
+          .signers([nonOwner])
+          .rpc();
+        assert.fail("Non-owner should not be able to reconcile the fill volume");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+      }
+    });
+  });
 });
@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { AccountMeta, Keypair, PublicKey } from "@solana/web3.js";
+import {
+  calculateRelayHashUint8Array,
+  intToU8Array32,
+  loadSimulateBundleSummaryParams,
+  relayerRefundHashFn,
+  slowFillHashFn,
+} from "../../src/svm";
+import { BundleSummaryLeaf, RelayerRefundLeafSolana, RelayerRefundLeafType, SlowFillLeaf } from "../../src/types/svm";
+import { MerkleTree } from "../../utils";
+import { common } from "./SvmSpoke.common";
+
+const { provider, connection, program, owner, chainId, initializeState, assert, assertSE, getVaultAuthority } = common;
+
+describe("svm_spoke.bundle_summary", () => {
+  anchor.setProvider(provider);
+  const payer = (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer;
+  const vaultBalance = 1000;
+
+  let state: PublicKey, seed: BN, mint: PublicKey, vault: PublicKey;
+
+  beforeEach(async () => {
+    ({ state, seed } = await initializeState());
+    mint = await createMint(connection, payer, owner, owner, 6);
+    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)).address;
+    await mintTo(connection, payer, mint, vault, owner, vaultBalance);
+  });
+
+  const refundLeaf = (leafId: number, amount: number): RelayerRefundLeafSolana => ({
+    isSolana: true,
+    leafId: new BN(leafId),
+    chainId,
+    amountToReturn: new BN(0),
+    mintPublicKey: mint,
+    refundAddresses: [Keypair.generate().publicKey],
+    refundAmounts: [new BN(amount)],
+  });
+
+  it("Summarizes executable and blocked leaves of a bundle", async () => {
+    const refundLeaves = [refundLeaf(0, 600), refundLeaf(1, 600)];
+    const slowFillLeaf: SlowFillLeaf = {
+      relayData: {
+        depositor: Keypair.generate().publicKey,
+        recipient: Keypair.generate().publicKey,
+        exclusiveRelayer: PublicKey.default,
+        inputToken: mint,
+        outputToken: mint,
+        inputAmount: new BN(300),
+        outputAmount: new BN(300),
+        originChainId: new BN(1),
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+        fillDeadline: Math.floor(Date.now() / 1000) + 60,
+        exclusivityDeadline: 0,
+        message: Buffer.alloc(0),
+      },
+      chainId,
+      updatedOutputAmount: new BN(300),
+    };
+    const refundTree = new MerkleTree<RelayerRefundLeafType>(refundLeaves, relayerRefundHashFn);
+    const slowRelayTree = new MerkleTree<SlowFillLeaf>([slowFillLeaf], slowFillHashFn);
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    await program.methods
+      .relayRootBundle(Array.from(refundTree.getRoot()), Array.from(slowRelayTree.getRoot()), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    const refundProof = (leaf: RelayerRefundLeafSolana) => refundTree.getProof(leaf).map((p) => Array.from(p));
+    const slowRelayProof = slowRelayTree.getProof(slowFillLeaf).map((p) => Array.from(p));
+    const [fillStatus] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fills"), calculateRelayHashUint8Array(slowFillLeaf.relayData, chainId)],
+      program.programId
+    );
+
+    // The second refund leaf exceeds the vault balance left after the first one, while the repeated leaves are blocked
+    // by the claim and fill of their earlier occurrences. The last refund leaf is not part of the relayed bundle.
+    const leaves: BundleSummaryLeaf[] = [
+      { relayerRefund: { relayerRefundLeaf: refundLeaves[0], proof: refundProof(refundLeaves[0]) } },
+      { relayerRefund: { relayerRefundLeaf: refundLeaves[1], proof: refundProof(refundLeaves[1]) } },
+      { slowRelay: { slowFillLeaf, proof: slowRelayProof } },
+      { slowRelay: { slowFillLeaf, proof: slowRelayProof } },
+      { relayerRefund: { relayerRefundLeaf: refundLeaves[0], proof: refundProof(refundLeaves[0]) } },
+      { relayerRefund: { relayerRefundLeaf: refundLeaf(2, 1), proof: [] } },
+    ];
+    await loadSimulateBundleSummaryParams(program, owner, state, leaves);
+
+    const vaultMeta: AccountMeta = { pubkey: vault, isWritable: false, isSigner: false };
+    const fillStatusMeta: AccountMeta = { pubkey: fillStatus, isWritable: false, isSigner: false };
+    const remainingAccounts = leaves.flatMap((leaf) =>
+      "slowRelay" in leaf ? [vaultMeta, fillStatusMeta] : [vaultMeta]
+    );
+
+    const summary = await program.methods
+      .simulateBundleSummary(rootBundleId)
+      .accounts({ signer: owner, state, rootBundle })
+      .remainingAccounts(remainingAccounts)
+      .view();
+    assert.strictEqual(summary.executable, 2, "Executable leaf count should match");
+    assert.strictEqual(summary.blockedByBalance, 1, "Blocked by balance leaf count should match");
+    assert.strictEqual(summary.blockedByStatus, 2, "Blocked by status leaf count should match");
+    assert.strictEqual(summary.invalid, 1, "Invalid leaf count should match");
+    assert.strictEqual(summary.mintOutflows.length, 1, "Outflows should be reported for the single mint");
+    assertSE(summary.mintOutflows[0].mint, mint, "Outflow mint should match");
+    assertSE(summary.mintOutflows[0].vaultBalance, vaultBalance, "Vault balance should match");
+    assertSE(summary.mintOutflows[0].totalOutflow, 900, "Total outflow should only include executable leaves");
+
+    // Vaults of another mint are rejected.
+    const otherMint = await createMint(connection, payer, owner, owner, 6);
+    const otherVault = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, otherMint, getVaultAuthority(state), true)
+    ).address;
+    const otherVaultMeta: AccountMeta = { ...vaultMeta, pubkey: otherVault };
+    try {
+      await program.methods
+        .simulateBundleSummary(rootBundleId)
+        .accounts({ signer: owner, state, rootBundle })
+        .remainingAccounts(remainingAccounts.map((meta) => (meta === vaultMeta ? otherVaultMeta : meta)))
+        .rpc();
+      assert.fail("Simulation with a vault of another mint should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidBundleSummaryAccount", "Expected InvalidBundleSummaryAccount error");
+    }
+  });
+});
@@ -12,15 +12,37 @@ import {
   createApproveCheckedInstruction,
   createEnableCpiGuardInstruction,
   createReallocateInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getMintLen,
+  getAssociatedTokenAddressSync,
   ExtensionType,
+  NATIVE_MINT,
+  ACCOUNT_SIZE,
 } from "@solana/spl-token";
-import { PublicKey, Keypair, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
+import {
+  PublicKey,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+  Ed25519Program,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
 import { common } from "./SvmSpoke.common";
 import { DepositDataValues } from "../../src/types/svm";
-import { intToU8Array32, readEventsUntilFound } from "../../src/svm";
+import {
+  calculateRelayHashUint8Array,
+  encodeDepositExtraArgsV5,
+  intToU8Array32,
+  readEventsUntilFound,
+} from "../../src/svm";
 const { provider, connection, program, owner, seedBalance, initializeState, depositData } = common;
 const { createRoutePda, getVaultAta, assertSE, assert, getCurrentTime, depositQuoteTimeBuffer, fillDeadlineBuffer } =
   common;
+const { setCurrentTime, chainId, remoteDomain, crossDomainAdmin, createVaultLiabilityPda, getVaultAuthority } = common;
+const { createDepositLimitPda, createChainAliasPda, createDepositorNoncePda, createVaultRegistryPda } = common;
+const { createNativeWrapPda } = common;
 
 const maxExclusivityOffsetSeconds = new BN(365 * 24 * 60 * 60); // 1 year in seconds
 
@@ -37,12 +59,19 @@ describe("svm_spoke.deposit", () => {
   // Re-used between tests to simplify props.
   type DepositAccounts = {
     state: PublicKey;
+    destinationChainAlias: PublicKey;
     route: PublicKey;
+    depositLimit: PublicKey;
+    depositorNonce: PublicKey;
+    vaultRegistry: PublicKey;
     signer: PublicKey;
     depositorTokenAccount: PublicKey;
     vault: PublicKey;
     mint: PublicKey;
+    vaultLiability: PublicKey;
+    eventCommitment: PublicKey;
     tokenProgram: PublicKey;
+    systemProgram: PublicKey;
     program: PublicKey;
   };
   let depositAccounts: DepositAccounts;
@@ -82,9 +111,12 @@ describe("svm_spoke.deposit", () => {
       tokenProgram: tokenProgram ?? TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
     };
 
-    await program.methods.setEnableRoute(inputToken, routeChainId, true).accounts(setEnableRouteAccounts).rpc();
+    await program.methods.setEnableRoute(inputToken, routeChainId, true, 0, 0).accounts(setEnableRouteAccounts).rpc();
 
     // Set known fields in the depositData.
     depositData.depositor = depositor.publicKey;
@@ -93,18 +125,29 @@ describe("svm_spoke.deposit", () => {
     depositAccounts = {
       state,
       route,
+      destinationChainAlias: program.programId,
+      depositLimit: createDepositLimitPda(inputToken, seed, routeChainId),
+      depositorNonce: createDepositorNoncePda(depositor.publicKey, seed),
+      vaultRegistry: createVaultRegistryPda(inputToken, seed),
       signer: depositor.publicKey,
       depositorTokenAccount: depositorTA,
       vault,
       mint: inputToken,
+      vaultLiability: createVaultLiabilityPda(inputToken, seed),
+      eventCommitment: program.programId,
       tokenProgram: tokenProgram ?? TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
       program: program.programId,
     };
   };
 
   const approvedDepositV3 = async (
     depositDataValues: DepositDataValues,
-    calledDepositAccounts: DepositAccounts = depositAccounts
+    calledDepositAccounts: DepositAccounts = depositAccounts,
+    revokeAfter = false,
+    approvedAmount = BigInt(depositData.inputAmount.toString()),
+    argsVersion = 0,
+    extra = Buffer.alloc(0)
   ) => {
     // Delegate state PDA to pull depositor tokens.
     const approveIx = await createApproveCheckedInstruction(
@@ -112,13 +155,13 @@ describe("svm_spoke.deposit", () => {
       calledDepositAccounts.mint,
       calledDepositAccounts.state,
       depositor.publicKey,
-      BigInt(depositData.inputAmount.toString()),
+      approvedAmount,
       tokenDecimals,
       undefined,
       tokenProgram
     );
     const depositIx = await program.methods
-      .depositV3(...depositDataValues)
+      .depositV3(...depositDataValues, revokeAfter, argsVersion, extra)
       .accounts(calledDepositAccounts)
       .instruction();
     const depositTx = new Transaction().add(approveIx, depositIx);
@@ -126,6 +169,12 @@ describe("svm_spoke.deposit", () => {
     return tx;
   };
 
+  before(async () => {
+    // Depositor pays the rent of the vault liability PDA if it is created by its deposit.
+    const sig = await connection.requestAirdrop(depositor.publicKey, 10_000_000_000);
+    await provider.connection.confirmTransaction(sig);
+  });
+
   beforeEach(async () => {
     ({ state, seed } = await initializeState());
 
@@ -194,6 +243,8 @@ describe("svm_spoke.deposit", () => {
       if (key === "exclusivityParameter") key = "exclusivityDeadline"; // the prop and the event names differ on this key.
       assertSE(event[key], value, `${key} should match`);
     }
+    assert.strictEqual(event.tokenDecimals, tokenDecimals, "tokenDecimals should match the input token mint");
+    assert.isFalse(event.unsafeDepositId, "Sequential deposit ID should not be flagged as unsafe");
 
     // Execute the second deposit_v3 call
     const tx2 = await approvedDepositV3(depositDataValues);
@@ -207,6 +258,22 @@ describe("svm_spoke.deposit", () => {
     }
   });
 
+  it("Revokes leftover delegation after deposit only when requested", async () => {
+    // Approve more than the deposit amount so that delegation remains after the deposit pulls funds.
+    const approvedAmount = BigInt(depositData.inputAmount.toString()) * BigInt(2);
+    const depositDataValues = Object.values(depositData) as DepositDataValues;
+
+    await approvedDepositV3(depositDataValues, depositAccounts, false, approvedAmount);
+    let depositorAccount = await getAccount(connection, depositorTA, undefined, tokenProgram);
+    assert.isTrue(depositorAccount.delegate?.equals(state), "State should remain the delegate");
+    assertSE(depositorAccount.delegatedAmount, depositData.inputAmount, "Remaining delegated amount should match");
+
+    await approvedDepositV3(depositDataValues, depositAccounts, true, approvedAmount);
+    depositorAccount = await getAccount(connection, depositorTA, undefined, tokenProgram);
+    assert.isNull(depositorAccount.delegate, "Delegate should be revoked");
+    assertSE(depositorAccount.delegatedAmount, 0, "Delegated amount should be cleared");
+  });
+
   it("Fails to deposit tokens to a route that is uninitalized", async () => {
     const differentChainId = new BN(2); // Different chain ID
     if (!depositData.inputToken) {
@@ -230,7 +297,7 @@ describe("svm_spoke.deposit", () => {
   it("Fails to deposit tokens to a route that is explicitly disabled", async () => {
     // Disable the route
     await program.methods
-      .setEnableRoute(depositData.inputToken!, depositData.destinationChainId, false)
+      .setEnableRoute(depositData.inputToken!, depositData.destinationChainId, false, 0, 0)
       .accounts(setEnableRouteAccounts)
       .rpc();
 
@@ -243,6 +310,409 @@ describe("svm_spoke.deposit", () => {
     }
   });
 
+  it("Rejects deposits once the scheduled route disable time is reached", async () => {
+    const scheduleDisable = async (disableAt: number) => {
+      return await program.methods
+        .setEnableRoute(depositData.inputToken!, depositData.destinationChainId, true, disableAt, 0)
+        .accounts(setEnableRouteAccounts)
+        .rpc();
+    };
+    const depositAt = async (time: number) => {
+      const depositDataValues = Object.values({
+        ...depositData,
+        quoteTimestamp: new BN(time),
+        fillDeadline: new BN(time + 60),
+      }) as DepositDataValues;
+      return await approvedDepositV3(depositDataValues);
+    };
+
+    const disableAt = (await getCurrentTime(program, state)) + 100;
+    const tx = await scheduleDisable(disableAt);
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "enabledDepositRoute")?.data;
+    assert.isTrue(event.enabled, "Route should stay enabled until the scheduled time");
+    assertSE(event.disableAt, disableAt, "Event should announce the scheduled disable time");
+
+    // Deposits are still accepted just before the scheduled time.
+    await setCurrentTime(program, state, new BN(disableAt - 1));
+    await depositAt(disableAt - 1);
+
+    await setCurrentTime(program, state, new BN(disableAt));
+    try {
+      await depositAt(disableAt);
+      assert.fail("Deposit should have failed once the route disable time is reached");
+    } catch (err: any) {
+      assert.include(err.toString(), "DisabledRoute", "Expected DisabledRoute error");
+    }
+
+    // Moving the schedule requires another admin call and makes the route accept deposits again.
+    await scheduleDisable(disableAt + 100);
+    await depositAt(disableAt);
+
+    const vaultAccount = await getAccount(connection, vault);
+    assertSE(vaultAccount.amount, depositData.inputAmount.muln(2), "Only two deposits should be credited to the vault");
+  });
+
+  describe("Deposit limits", () => {
+    const setDepositLimit = async (maxOutstanding: BN) => {
+      return await program.methods
+        .setDepositLimit(inputToken, depositData.destinationChainId, maxOutstanding)
+        .accounts({
+          signer: owner,
+          payer: owner,
+          state,
+          depositLimit: depositAccounts.depositLimit,
+          systemProgram: SystemProgram.programId,
+          program: program.programId,
+        })
+        .rpc();
+    };
+    const deposit = async () => await approvedDepositV3(Object.values(depositData) as DepositDataValues);
+    const assertDepositLimitExceeded = async () => {
+      try {
+        await deposit();
+        assert.fail("Deposit should have failed above the deposit limit");
+      } catch (err: any) {
+        assert.include(err.toString(), "DepositLimitExceeded", "Expected DepositLimitExceeded error");
+      }
+    };
+
+    it("Accepts deposits up to exactly the deposit limit", async () => {
+      const maxOutstanding = depositData.inputAmount.muln(2);
+      const tx = await setDepositLimit(maxOutstanding);
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "setDepositLimit")?.data;
+      assertSE(event.mint, inputToken, "Mint should match");
+      assertSE(event.destinationChainId, depositData.destinationChainId, "Destination chain should match");
+      assertSE(event.maxOutstanding, maxOutstanding, "Max outstanding should match");
+
+      // Second deposit brings the vault balance exactly to the limit.
+      await deposit();
+      await deposit();
+      assertSE((await getAccount(connection, vault)).amount, maxOutstanding, "Vault should be filled up to the limit");
+      await assertDepositLimitExceeded();
+
+      // Limits of u64::MAX are unlimited.
+      await setDepositLimit(new BN("18446744073709551615"));
+      await deposit();
+      assertSE((await getAccount(connection, vault)).amount, depositData.inputAmount.muln(3), "Deposit should pass");
+    });
+
+    it("Keeps funds above a reduced deposit limit while blocking new deposits", async () => {
+      // Deposits without a deposit limit PDA are unlimited.
+      await deposit();
+      await deposit();
+
+      await setDepositLimit(depositData.inputAmount);
+      await assertDepositLimitExceeded();
+      const vaultAccount = await getAccount(connection, vault);
+      assertSE(vaultAccount.amount, depositData.inputAmount.muln(2), "Existing funds should stay in the vault");
+
+      // Raising the limit again accepts deposits up to it.
+      await setDepositLimit(depositData.inputAmount.muln(3));
+      await deposit();
+      await assertDepositLimitExceeded();
+    });
+
+    it("Fails to set the deposit limit by non-owner", async () => {
+      const nonOwner = Keypair.generate();
+      try {
+        await program.methods
+          .setDepositLimit(inputToken, depositData.destinationChainId, new BN(0))
+          .accounts({
+            signer: nonOwner.publicKey,
+            payer: owner,
+            state,
+            depositLimit: depositAccounts.depositLimit,
+            systemProgram: SystemProgram.programId,
+            program: program.programId,
+          })
+          .signers([nonOwner])
+          .rpc();
+        assert.fail("Non-owner should not be able to set the deposit limit");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+      }
+    });
+  });
+
+  describe("Chain aliases", () => {
+    const alias = new BN(1001);
+    const setChainAlias = async (aliasChainId: BN, canonicalChainId: BN, signer = payer) => {
+      return await program.methods
+        .setChainAlias(aliasChainId, canonicalChainId)
+        .accounts({
+          signer: signer.publicKey,
+          state,
+          chainAlias: createChainAliasPda(seed, aliasChainId),
+          systemProgram: SystemProgram.programId,
+          program: program.programId,
+        })
+        .signers([signer])
+        .rpc();
+    };
+    const aliasedDeposit = async (destinationChainId: BN, destinationChainAlias: PublicKey) => {
+      const depositDataValues = Object.values({ ...depositData, destinationChainId }) as DepositDataValues;
+      return await approvedDepositV3(depositDataValues, { ...depositAccounts, destinationChainAlias });
+    };
+
+    it("Deposits towards chain aliases through the route of the canonical chain ID", async () => {
+      const tx = await setChainAlias(alias, depositData.destinationChainId);
+      let events = await readEventsUntilFound(connection, tx, [program]);
+      const setEvent = events.find((event) => event.name === "setChainAlias")?.data;
+      assertSE(setEvent.alias, alias, "Alias should match");
+      assertSE(setEvent.canonicalChainId, depositData.destinationChainId, "Canonical chain ID should match");
+
+      // The alias account selects the route and deposit limit of the canonical chain ID, which is also emitted.
+      const depositTx = await aliasedDeposit(alias, createChainAliasPda(seed, alias));
+      events = await readEventsUntilFound(connection, depositTx, [program]);
+      const deposited = events.find((event) => event.name === "v3FundsDeposited")?.data;
+      assertSE(deposited.destinationChainId, depositData.destinationChainId, "Destination should be canonical");
+
+      // Without the alias account the route of the canonical chain ID does not match the destination chain ID.
+      try {
+        await aliasedDeposit(alias, program.programId);
+        assert.fail("Deposit towards an alias should have failed without its chain alias");
+      } catch (err: any) {
+        assert.include(err.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+      }
+
+      // Removed aliases are no longer resolved.
+      const removeTx = await program.methods
+        .removeChainAlias(alias)
+        .accounts({ signer: owner, state, chainAlias: createChainAliasPda(seed, alias), program: program.programId })
+        .rpc();
+      events = await readEventsUntilFound(connection, removeTx, [program]);
+      assertSE(events.find((event) => event.name === "removedChainAlias")?.data.alias, alias, "Alias should match");
+      try {
+        await aliasedDeposit(alias, createChainAliasPda(seed, alias));
+        assert.fail("Deposit should have failed with a removed chain alias");
+      } catch (err: any) {
+        assert.include(err.toString(), "AccountNotInitialized", "Expected AccountNotInitialized error");
+      }
+    });
+
+    it("Requires registered chain aliases of deposit destinations in strict mode", async () => {
+      const key = Buffer.alloc(32);
+      key.write("strict_chain_aliases");
+      const value = Buffer.alloc(32);
+      value.writeUInt8(1, 31);
+      await program.methods
+        .setConfig(Array.from(key), Array.from(value), null)
+        .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+        .rpc();
+
+      try {
+        await approvedDepositV3(Object.values(depositData) as DepositDataValues);
+        assert.fail("Deposit should have failed without a chain alias in strict mode");
+      } catch (err: any) {
+        assert.include(err.toString(), "UnregisteredChainAlias", "Expected UnregisteredChainAlias error");
+      }
+
+      // Canonical chain IDs are registered as aliases of themselves.
+      const canonicalChainId = depositData.destinationChainId;
+      await setChainAlias(canonicalChainId, canonicalChainId);
+      await aliasedDeposit(canonicalChainId, createChainAliasPda(seed, canonicalChainId));
+      assertSE((await getAccount(connection, vault)).amount, depositData.inputAmount, "Deposit should pass");
+    });
+
+    it("Fails to set chain aliases by non-owner or towards chain ID 0", async () => {
+      try {
+        await setChainAlias(alias, depositData.destinationChainId, depositor);
+        assert.fail("Non-owner should not be able to set chain aliases");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+      }
+
+      try {
+        await setChainAlias(alias, new BN(0));
+        assert.fail("Chain aliases should not resolve to chain ID 0");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidChainId", "Expected InvalidChainId error");
+      }
+    });
+  });
+
+  describe("Depositor nonces", () => {
+    const depositorNonceAccounts = () => ({
+      signer: depositor.publicKey,
+      state,
+      depositorNonce: depositAccounts.depositorNonce,
+      systemProgram: SystemProgram.programId,
+    });
+    const initializeDepositorNonce = async () => {
+      await program.methods.initializeDepositorNonce().accounts(depositorNonceAccounts()).signers([depositor]).rpc();
+    };
+    const noncedDeposit = async (nonce: number | null) => {
+      const depositDataValues = Object.values(depositData) as DepositDataValues;
+      const inputAmount = BigInt(depositData.inputAmount.toString());
+      if (nonce === null) return await approvedDepositV3(depositDataValues, depositAccounts, false, inputAmount);
+      const extra = encodeDepositExtraArgsV5(new BN(nonce));
+      return await approvedDepositV3(depositDataValues, depositAccounts, false, inputAmount, 5, extra);
+    };
+    const assertNonceMismatch = async (nonce: number | null, message: string) => {
+      try {
+        await noncedDeposit(nonce);
+        assert.fail(message);
+      } catch (err: any) {
+        assert.include(err.toString(), "NonceMismatch", "Expected NonceMismatch error");
+      }
+    };
+    const emittedNonce = async (tx: string) => {
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      return events.find((event) => event.name === "v3FundsDeposited")?.data.depositorNonce;
+    };
+
+    it("Orders deposits of depositors that initialized a depositor nonce", async () => {
+      // Depositors without the PDA are unaffected, but can not pass a nonce that would not be enforced.
+      assert.isNull(await emittedNonce(await noncedDeposit(null)), "Unordered deposit should not emit a nonce");
+      await assertNonceMismatch(0, "Deposit with a nonce should fail without a depositor nonce");
+
+      await initializeDepositorNonce();
+      await assertNonceMismatch(null, "Deposit without a nonce should fail with a depositor nonce");
+
+      assertSE(await emittedNonce(await noncedDeposit(0)), 0, "First deposit should emit nonce 0");
+      await assertNonceMismatch(2, "Deposit skipping a nonce should fail");
+      await assertNonceMismatch(0, "Deposit replaying a nonce should fail");
+      assertSE(await emittedNonce(await noncedDeposit(1)), 1, "Second deposit should emit nonce 1");
+      const depositorNonce = await program.account.depositorNonce.fetch(depositAccounts.depositorNonce);
+      assertSE(depositorNonce.nextNonce, 2, "Next nonce should be incremented by each deposit");
+      const vaultAccount = await getAccount(connection, vault);
+      assertSE(vaultAccount.amount, depositData.inputAmount.muln(3), "Only accepted deposits should be credited");
+
+      // Closing the PDA opts the depositor out of ordered deposits.
+      await program.methods
+        .closeDepositorNonce()
+        .accounts({ signer: depositor.publicKey, state, depositorNonce: depositAccounts.depositorNonce })
+        .signers([depositor])
+        .rpc();
+      assert.isNull(await emittedNonce(await noncedDeposit(null)), "Deposit after closing should not emit a nonce");
+    });
+
+    it("Resets depositor nonces only by the owner", async () => {
+      await initializeDepositorNonce();
+      const resetDepositorNonce = async (nextNonce: BN, signer = payer) => {
+        return await program.methods
+          .resetDepositorNonce(depositor.publicKey, nextNonce)
+          .accounts({
+            signer: signer.publicKey,
+            state,
+            depositorNonce: depositAccounts.depositorNonce,
+            program: program.programId,
+          })
+          .signers([signer])
+          .rpc();
+      };
+
+      try {
+        await resetDepositorNonce(new BN(7), depositor);
+        assert.fail("Non-owner should not be able to reset depositor nonces");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+      }
+
+      const tx = await resetDepositorNonce(new BN(7));
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const reset = events.find((event) => event.name === "resetDepositorNonce")?.data;
+      assertSE(reset.depositor, depositor.publicKey, "Depositor should match");
+      assertSE(reset.nextNonce, 7, "Next nonce should match");
+
+      await assertNonceMismatch(0, "Deposit with the nonce before the reset should fail");
+      assertSE(await emittedNonce(await noncedDeposit(7)), 7, "Deposit should continue from the reset nonce");
+    });
+  });
+
+  describe("Vault registry", () => {
+    const createVault = async (mint: PublicKey, mintTokenProgram: PublicKey, authority = getVaultAuthority(state)) => {
+      return await program.methods
+        .createVault()
+        .accounts({
+          signer: owner,
+          state,
+          mint,
+          authority,
+          vault: getAssociatedTokenAddressSync(mint, authority, true, mintTokenProgram),
+          vaultRegistry: createVaultRegistryPda(mint, seed),
+          tokenProgram: mintTokenProgram,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          program: program.programId,
+        })
+        .rpc();
+    };
+
+    it("Registers vaults of legacy and Token-2022 mints independently", async () => {
+      // Tokens sharing a symbol are still separate legacy and Token-2022 mint accounts, each with its own vault.
+      const legacyMint = inputToken;
+      const token2022Mint = await createMint(
+        connection,
+        payer,
+        owner,
+        owner,
+        tokenDecimals,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const vaultAuthority = getVaultAuthority(state);
+
+      // The legacy vault already created when enabling the route is registered as it is.
+      const legacyTx = await createVault(legacyMint, TOKEN_PROGRAM_ID);
+      const token2022Tx = await createVault(token2022Mint, TOKEN_2022_PROGRAM_ID);
+
+      const legacyEvents = await readEventsUntilFound(connection, legacyTx, [program]);
+      const legacyEvent = legacyEvents.find((event) => event.name === "vaultCreated")?.data;
+      assertSE(legacyEvent.mint, legacyMint, "Legacy vault event should name its mint");
+      assertSE(legacyEvent.tokenProgram, TOKEN_PROGRAM_ID, "Legacy vault event should name the legacy token program");
+      assertSE(legacyEvent.vault, vault, "Legacy vault should be the existing vault");
+      const token2022Events = await readEventsUntilFound(connection, token2022Tx, [program]);
+      const token2022Event = token2022Events.find((event) => event.name === "vaultCreated")?.data;
+      assertSE(token2022Event.authority, vaultAuthority, "Token-2022 vault should be owned by the vault authority");
+
+      const legacyRegistry = await program.account.vault.fetch(createVaultRegistryPda(legacyMint, seed));
+      const token2022Registry = await program.account.vault.fetch(createVaultRegistryPda(token2022Mint, seed));
+      assertSE(legacyRegistry.tokenProgram, TOKEN_PROGRAM_ID, "Legacy mint should record the legacy token program");
+      assertSE(token2022Registry.tokenProgram, TOKEN_2022_PROGRAM_ID, "Token-2022 mint should record Token-2022");
+      const token2022Vault = await getAccount(connection, token2022Event.vault, undefined, TOKEN_2022_PROGRAM_ID);
+      assertSE(token2022Vault.owner, vaultAuthority, "Token-2022 vault should be created for the vault authority");
+
+      try {
+        await createVault(legacyMint, TOKEN_PROGRAM_ID);
+        assert.fail("Vaults should only be registered once");
+      } catch (err: any) {
+        assert.include(err.toString(), "already in use", "Expected the vault PDA to already exist");
+      }
+
+      for (const authority of [state, depositor.publicKey]) {
+        try {
+          await createVault(token2022Mint, TOKEN_2022_PROGRAM_ID, authority);
+          assert.fail("Vaults should only be owned by the vault authority");
+        } catch (err: any) {
+          assert.include(err.toString(), "InvalidVault", "Expected InvalidVault error");
+        }
+      }
+    });
+
+    it("Rejects deposits into registered vaults passing another token program", async () => {
+      await createVault(inputToken, TOKEN_PROGRAM_ID);
+      const depositDataValues = Object.values(depositData) as DepositDataValues;
+      await approvedDepositV3(depositDataValues);
+
+      try {
+        await program.methods
+          .depositV3(...depositDataValues, false, 0, Buffer.alloc(0))
+          .accounts({ ...depositAccounts, tokenProgram: TOKEN_2022_PROGRAM_ID })
+          .signers([depositor])
+          .rpc();
+        assert.fail("Deposit should have failed with the token program of another mint");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidVault", "Expected InvalidVault error");
+      }
+      const vaultAccount = await getAccount(connection, vault);
+      assertSE(vaultAccount.amount, depositData.inputAmount, "Only the deposit passing the mint program is credited");
+    });
+  });
+
   it("Fails to process deposit when deposits are paused", async () => {
     // Pause deposits
     const pauseDepositsAccounts = { state, signer: owner, program: program.programId };
@@ -260,6 +730,20 @@ describe("svm_spoke.deposit", () => {
     }
   });
 
+  it("Fails to process deposit when spoke is winding down", async () => {
+    const windDownDelay = 30 * 86_400; // Minimum wind down delay.
+    await program.methods.windDown(windDownDelay).accounts({ state, signer: owner, program: program.programId }).rpc();
+
+    // Try to deposit. This should fail because the spoke is winding down.
+    try {
+      const depositDataValues = Object.values(depositData) as DepositDataValues;
+      await approvedDepositV3(depositDataValues);
+      assert.fail("Should not be able to process deposit when spoke is winding down");
+    } catch (err: any) {
+      assert.include(err.toString(), "Error Code: WindingDown", "Expected WindingDown error");
+    }
+  });
+
   it("Fails to deposit tokens with InvalidQuoteTimestamp when quote timestamp is in the future", async () => {
     const currentTime = await getCurrentTime(program, state);
     const futureQuoteTimestamp = new BN(currentTime + 10); // 10 seconds in the future
@@ -318,6 +802,82 @@ describe("svm_spoke.deposit", () => {
       assert.include(err.toString(), "InvalidFillDeadline", "Expected InvalidFillDeadline error for future deadline");
     }
   });
+  it("Enforces updated deposit buffers at their boundaries right after the change", async () => {
+    const currentTime = await getCurrentTime(program, state);
+    const newQuoteTimeBuffer = depositQuoteTimeBuffer.toNumber() * 2;
+    const newFillDeadlineBuffer = fillDeadlineBuffer.toNumber() / 2;
+    const deposit = (quoteTimestamp: number, fillDeadline: number) => {
+      const timestamps = { quoteTimestamp: new BN(quoteTimestamp), fillDeadline: new BN(fillDeadline) };
+      return approvedDepositV3(Object.values({ ...depositData, ...timestamps }) as DepositDataValues);
+    };
+    const assertRejected = async (quoteTimestamp: number, fillDeadline: number, error: string) => {
+      try {
+        await deposit(quoteTimestamp, fillDeadline);
+        assert.fail(`Deposit should have failed due to ${error}`);
+      } catch (err: any) {
+        assert.include(err.toString(), error, `Expected ${error} error`);
+      }
+    };
+
+    // Deposits exactly at the edge of the buffers are accepted, same as in the EVM SpokePool.
+    await deposit(currentTime - depositQuoteTimeBuffer.toNumber(), currentTime + fillDeadlineBuffer.toNumber());
+    await assertRejected(currentTime - newQuoteTimeBuffer, currentTime, "InvalidQuoteTimestamp");
+
+    let tx = await program.methods
+      .setDepositQuoteTimeBuffer(newQuoteTimeBuffer)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+    let events = await readEventsUntilFound(connection, tx, [program]);
+    let event = events.find((event) => event.name === "setDepositQuoteTimeBuffer")?.data;
+    assertSE(event.depositQuoteTimeBuffer, newQuoteTimeBuffer, "Deposit quote time buffer should match");
+    await deposit(currentTime - newQuoteTimeBuffer, currentTime);
+    await assertRejected(currentTime - newQuoteTimeBuffer - 1, currentTime, "InvalidQuoteTimestamp");
+
+    tx = await program.methods
+      .setFillDeadlineBuffer(newFillDeadlineBuffer)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+    events = await readEventsUntilFound(connection, tx, [program]);
+    event = events.find((event) => event.name === "setFillDeadlineBuffer")?.data;
+    assertSE(event.fillDeadlineBuffer, newFillDeadlineBuffer, "Fill deadline buffer should match");
+    await deposit(currentTime, currentTime + newFillDeadlineBuffer);
+    await assertRejected(currentTime, currentTime + newFillDeadlineBuffer + 1, "InvalidFillDeadline");
+    await assertRejected(currentTime, currentTime + fillDeadlineBuffer.toNumber(), "InvalidFillDeadline");
+
+    const stateData = await program.account.state.fetch(state);
+    assertSE(stateData.depositQuoteTimeBuffer, newQuoteTimeBuffer, "Deposit quote time buffer should be updated");
+    assertSE(stateData.fillDeadlineBuffer, newFillDeadlineBuffer, "Fill deadline buffer should be updated");
+  });
+
+  it("Rejects deposit buffers out of bounds or set by non-owner", async () => {
+    const maxDepositBuffer = 30 * 86_400;
+    for (const buffer of [0, maxDepositBuffer + 1]) {
+      for (const method of ["setDepositQuoteTimeBuffer", "setFillDeadlineBuffer"] as const) {
+        try {
+          await program.methods[method](buffer).accounts({ signer: owner, state, program: program.programId }).rpc();
+          assert.fail("Deposit buffer out of bounds should be rejected");
+        } catch (err: any) {
+          assert.include(err.toString(), "InvalidDepositBuffer", "Expected InvalidDepositBuffer error");
+        }
+      }
+    }
+    await program.methods
+      .setFillDeadlineBuffer(maxDepositBuffer)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+
+    try {
+      await program.methods
+        .setDepositQuoteTimeBuffer(maxDepositBuffer)
+        .accounts({ signer: depositor.publicKey, state, program: program.programId })
+        .signers([depositor])
+        .rpc();
+      assert.fail("Non-owner should not be able to set the deposit quote time buffer");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+    }
+  });
+
   it("Fails to process deposit for mint inconsistent input_token", async () => {
     // Save the correct data and accounts from global scope before changing it when creating a new input token.
     const firstInputToken = inputToken;
@@ -359,19 +919,32 @@ describe("svm_spoke.deposit", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
       program: program.programId,
     };
 
-    await program.methods.setEnableRoute(inputToken, fakeRouteChainId, true).accounts(fakeSetEnableRouteAccounts).rpc();
+    await program.methods
+      .setEnableRoute(inputToken, fakeRouteChainId, true, 0, 0)
+      .accounts(fakeSetEnableRouteAccounts)
+      .rpc();
 
     const fakeDepositAccounts = {
       state: fakeState.state,
       route: fakeRoutePda,
+      destinationChainAlias: program.programId,
+      depositLimit: createDepositLimitPda(inputToken, fakeState.seed, fakeRouteChainId),
+      depositorNonce: createDepositorNoncePda(depositor.publicKey, fakeState.seed),
+      vaultRegistry: createVaultRegistryPda(inputToken, fakeState.seed),
       signer: depositor.publicKey,
       depositorTokenAccount: depositorTA,
       vault: fakeVault,
       mint: inputToken,
+      vaultLiability: createVaultLiabilityPda(inputToken, fakeState.seed),
+      eventCommitment: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
       program: program.programId,
     };
 
@@ -429,7 +1002,7 @@ describe("svm_spoke.deposit", () => {
       depositAccounts.mint,
       depositAccounts.state,
       depositor.publicKey,
-      BigInt(depositData.inputAmount.toString()),
+      approvedAmount,
       tokenDecimals,
       undefined,
       tokenProgram
@@ -564,6 +1137,99 @@ describe("svm_spoke.deposit", () => {
 
     assertSE(event.exclusivityDeadline, zeroExclusivity, "Exclusivity deadline should always be 0");
   });
+
+  it("Resolves exclusivity params at the offset threshold as the EVM SpokePool", async () => {
+    const currentTime = new BN(await getCurrentTime(program, state));
+    depositData.quoteTimestamp = currentTime;
+    depositData.exclusiveRelayer = depositor.publicKey;
+
+    // Params up to and including the max offset are added to the current time, larger ones are absolute timestamps.
+    const expectedDeadlines: [BN, BN][] = [
+      [maxExclusivityOffsetSeconds.subn(1), currentTime.add(maxExclusivityOffsetSeconds).subn(1)],
+      [maxExclusivityOffsetSeconds, currentTime.add(maxExclusivityOffsetSeconds)],
+      [maxExclusivityOffsetSeconds.addn(1), maxExclusivityOffsetSeconds.addn(1)],
+    ];
+
+    for (const [exclusivityParameter, exclusivityDeadline] of expectedDeadlines) {
+      depositData.exclusivityParameter = exclusivityParameter;
+      const depositDataValues = Object.values(depositData) as DepositDataValues;
+      const tx = await approvedDepositV3(depositDataValues);
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events[0].data; // 0th event is the latest event;
+      assertSE(event.exclusivityParameter, exclusivityParameter, "exclusivityParameter should be the raw param");
+      assertSE(event.exclusivityDeadline, exclusivityDeadline, "exclusivityDeadline should be resolved from the param");
+    }
+  });
+  describe("Arithmetic overflow", () => {
+    const u32Max = new BN(2).pow(new BN(32)).subn(1);
+
+    // Re-initializes the state with overridden initial values, so that deposit computations start at their bounds.
+    const initializeBoundaryState = async (overrides: { initialNumberOfDeposits?: BN; fillDeadlineBuffer?: BN }) => {
+      ({ state, seed } = await initializeState(undefined, {
+        initialNumberOfDeposits: new BN(0),
+        chainId,
+        remoteDomain,
+        crossDomainAdmin,
+        depositQuoteTimeBuffer,
+        fillDeadlineBuffer,
+        ...overrides,
+      }));
+      await enableRoute();
+    };
+
+    const assertDepositOverflows = async () => {
+      try {
+        const depositDataValues = Object.values(depositData) as DepositDataValues;
+        await approvedDepositV3(depositDataValues);
+        assert.fail("Deposit should have failed due to ArithmeticOverflow");
+      } catch (err: any) {
+        assert.include(err.toString(), "ArithmeticOverflow", "Expected ArithmeticOverflow error");
+      }
+    };
+
+    it("Fails to assign deposit id once the number of deposits is at its maximum", async () => {
+      await initializeBoundaryState({ initialNumberOfDeposits: u32Max });
+      const currentTime = new BN(await getCurrentTime(program, state));
+      depositData.quoteTimestamp = currentTime;
+      depositData.fillDeadline = currentTime.addn(600);
+      depositData.exclusivityParameter = new BN(0);
+
+      await assertDepositOverflows();
+
+      const stateAccount = await program.account.state.fetch(state);
+      assertSE(stateAccount.numberOfDeposits, u32Max, "Number of deposits should not wrap around");
+    });
+
+    it("Fails to validate fill deadline when the fill deadline buffer overflows", async () => {
+      await initializeBoundaryState({ fillDeadlineBuffer: u32Max });
+      const currentTime = new BN(await getCurrentTime(program, state));
+      depositData.quoteTimestamp = currentTime;
+      depositData.fillDeadline = currentTime.addn(600);
+      depositData.exclusivityParameter = new BN(0);
+
+      await assertDepositOverflows();
+    });
+
+    it("Fails to resolve exclusivity offset that overflows the deadline", async () => {
+      await initializeBoundaryState({ fillDeadlineBuffer: new BN(5) });
+      const currentTime = u32Max.subn(5);
+      await setCurrentTime(program, state, currentTime);
+      depositData.quoteTimestamp = currentTime;
+      depositData.fillDeadline = u32Max;
+      depositData.exclusiveRelayer = depositor.publicKey;
+
+      // Offset at the boundary still resolves to the last representable timestamp.
+      depositData.exclusivityParameter = new BN(5);
+      const tx = await approvedDepositV3(Object.values(depositData) as DepositDataValues);
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      assertSE(events[0].data.exclusivityDeadline, u32Max, "exclusivityDeadline should be the maximum timestamp");
+
+      depositData.exclusivityParameter = new BN(6);
+      await assertDepositOverflows();
+    });
+  });
+
   it("unsafe deposit ID", async () => {
     const forcedDepositId = new BN(99);
 
@@ -593,7 +1259,7 @@ describe("svm_spoke.deposit", () => {
       depositAccounts.mint,
       depositAccounts.state,
       depositor.publicKey,
-      BigInt(depositData.inputAmount.toString()),
+      approvedAmount,
       tokenDecimals,
       undefined,
       tokenProgram
@@ -634,6 +1300,159 @@ describe("svm_spoke.deposit", () => {
       if (key === "exclusivityParameter") key = "exclusivityDeadline"; // Adjust for any key differences
       assertSE(event[key], value, `${key} should match`);
     }
+    assert.isTrue(event.unsafeDepositId, "Deposit ID derived from the nonce should be flagged as unsafe");
+  });
+
+  it("Accepts unsafe deposits reusing a deposit nonce", async () => {
+    // Nonces are not tracked, so reusing one repeats the deposit ID. Identical deposits then share their relay hash
+    // and only one of them can be filled, which is left to the depositor to avoid.
+    const depositNonce = new BN(11);
+    const depositIds: number[][] = [];
+    for (let i = 0; i < 2; i++) {
+      const approveIx = await createApproveCheckedInstruction(
+        depositAccounts.depositorTokenAccount,
+        depositAccounts.mint,
+        depositAccounts.state,
+        depositor.publicKey,
+        BigInt(depositData.inputAmount.toString()),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      const unsafeDepositIx = await program.methods
+        .unsafeDepositV3(
+          depositData.depositor!,
+          depositData.recipient!,
+          depositData.inputToken!,
+          depositData.outputToken!,
+          depositData.inputAmount!,
+          depositData.outputAmount!,
+          depositData.destinationChainId!,
+          depositData.exclusiveRelayer!,
+          depositNonce,
+          depositData.quoteTimestamp.toNumber(),
+          depositData.fillDeadline.toNumber(),
+          depositData.exclusivityParameter.toNumber(),
+          depositData.message!
+        )
+        .accounts(depositAccounts)
+        .instruction();
+      const tx = await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, unsafeDepositIx), [
+        payer,
+        depositor,
+      ]);
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "v3FundsDeposited")?.data;
+      assert.isTrue(event.unsafeDepositId, "Deposit ID derived from the nonce should be flagged as unsafe");
+      depositIds.push(Array.from(event.depositId));
+    }
+
+    assert.deepEqual(depositIds[0], depositIds[1], "Reused deposit nonce should repeat the deposit ID");
+    const stateAccount = await program.account.state.fetch(state);
+    assertSE(stateAccount.numberOfDeposits, 0, "Unsafe deposits should not consume sequential deposit IDs");
+  });
+
+  it("Emits the quote timestamp in deposit events without binding it into the relay hash", async () => {
+    // Same as on EVM, the quote timestamp is not part of the relay data, so fillers can only reconcile fees against
+    // the deposit event. Deposits with the same unsafe deposit nonce differ only by their quote timestamps.
+    const depositNonce = new BN(7);
+    const quoteTimestamps = [depositData.quoteTimestamp.toNumber(), depositData.quoteTimestamp.toNumber() - 1];
+    const relayHashes: number[][] = [];
+    for (const quoteTimestamp of quoteTimestamps) {
+      const approveIx = await createApproveCheckedInstruction(
+        depositAccounts.depositorTokenAccount,
+        depositAccounts.mint,
+        depositAccounts.state,
+        depositor.publicKey,
+        BigInt(depositData.inputAmount.toString()),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      const unsafeDepositIx = await program.methods
+        .unsafeDepositV3(
+          depositData.depositor!,
+          depositData.recipient!,
+          depositData.inputToken!,
+          depositData.outputToken!,
+          depositData.inputAmount!,
+          depositData.outputAmount!,
+          depositData.destinationChainId!,
+          depositData.exclusiveRelayer!,
+          depositNonce,
+          quoteTimestamp,
+          depositData.fillDeadline.toNumber(),
+          depositData.exclusivityParameter.toNumber(),
+          depositData.message!
+        )
+        .accounts(depositAccounts)
+        .instruction();
+      const tx = await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, unsafeDepositIx), [
+        payer,
+        depositor,
+      ]);
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "v3FundsDeposited")?.data;
+      assertSE(event.quoteTimestamp, quoteTimestamp, "Emitted quote timestamp should match the deposited one");
+      const relayData = {
+        depositor: event.depositor,
+        recipient: event.recipient,
+        exclusiveRelayer: event.exclusiveRelayer,
+        inputToken: event.inputToken,
+        outputToken: event.outputToken,
+        inputAmount: event.inputAmount,
+        outputAmount: event.outputAmount,
+        originChainId: chainId,
+        depositId: event.depositId,
+        fillDeadline: event.fillDeadline,
+        exclusivityDeadline: event.exclusivityDeadline,
+        message: event.message,
+      };
+      relayHashes.push(Array.from(calculateRelayHashUint8Array(relayData, depositData.destinationChainId)));
+    }
+
+    assert.deepEqual(relayHashes[0], relayHashes[1], "Relay hash should not depend on the quote timestamp");
+  });
+
+  it("Emits messages too large to inline as their hash", async () => {
+    for (const messageSize of [256, 257]) {
+      const message = Buffer.alloc(messageSize, 1);
+      const depositDataValues = Object.values({ ...depositData, message }) as DepositDataValues;
+
+      // Approve separately so that the largest inline message still fits in the deposit transaction.
+      const approveIx = await createApproveCheckedInstruction(
+        depositAccounts.depositorTokenAccount,
+        depositAccounts.mint,
+        depositAccounts.state,
+        depositor.publicKey,
+        BigInt(depositData.inputAmount.toString()),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      await sendAndConfirmTransaction(connection, new Transaction().add(approveIx), [payer, depositor]);
+      const tx = await program.methods
+        .depositV3(...depositDataValues, false, 0, Buffer.alloc(0))
+        .accounts(depositAccounts)
+        .signers([depositor])
+        .rpc();
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "v3FundsDeposited")?.data;
+      if (messageSize <= 256) {
+        assert.deepEqual(Buffer.from(event.message), message, "Message should be emitted inline");
+        assert.isNull(event.messageHash, "Message hash should not be emitted for inline messages");
+      } else {
+        assert.strictEqual(event.message.length, 0, "Message should not be emitted inline");
+        assert.strictEqual(
+          "0x" + Buffer.from(event.messageHash).toString("hex"),
+          ethers.utils.keccak256(message),
+          "Message hash should be emitted instead"
+        );
+      }
+    }
   });
 
   it("Deposit with enabled CPI-guard", async () => {
@@ -670,12 +1489,96 @@ describe("svm_spoke.deposit", () => {
     assertSE(vaultAccount.amount, depositData.inputAmount, "Vault balance should be increased by the deposited amount");
   });
 
+  it("Deposit of fee-on-transfer token records the received amount", async () => {
+    // Transfer fees are available only for the 2022 token program.
+    tokenProgram = TOKEN_2022_PROGRAM_ID;
+    const transferFeeBps = 100; // 1%
+
+    // Create the input token with the TransferFee extension.
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const createMintTx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: mintKeypair.publicKey,
+        space: mintLen,
+        lamports: await connection.getMinimumBalanceForRentExemption(mintLen),
+        programId: tokenProgram,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mintKeypair.publicKey,
+        owner,
+        owner,
+        transferFeeBps,
+        BigInt(seedBalance),
+        tokenProgram
+      ),
+      createInitializeMintInstruction(mintKeypair.publicKey, tokenDecimals, owner, owner, tokenProgram)
+    );
+    await sendAndConfirmTransaction(connection, createMintTx, [payer, mintKeypair]);
+    inputToken = mintKeypair.publicKey;
+    depositorTA = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        inputToken,
+        depositor.publicKey,
+        undefined,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    await mintTo(connection, payer, inputToken, depositorTA, owner, seedBalance, undefined, undefined, tokenProgram);
+    await enableRoute();
+
+    // Execute the deposit_v3 call
+    const depositDataValues = Object.values(depositData) as DepositDataValues;
+    const tx = await approvedDepositV3(depositDataValues);
+
+    // Vault receives the input amount less the transfer fee.
+    const receivedAmount = depositData.inputAmount.sub(depositData.inputAmount.muln(transferFeeBps).divn(10000));
+    const vaultAccount = await getAccount(connection, vault, undefined, tokenProgram);
+    assertSE(vaultAccount.amount, receivedAmount, "Vault balance should be increased by the received amount");
+
+    // Emitted input amount and the resulting relay hash are based on the received amount.
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "v3FundsDeposited")?.data;
+    assertSE(event.inputAmount, receivedAmount, "Emitted input amount should match the received amount");
+    const relayData = {
+      depositor: event.depositor,
+      recipient: event.recipient,
+      exclusiveRelayer: event.exclusiveRelayer,
+      inputToken: event.inputToken,
+      outputToken: event.outputToken,
+      inputAmount: event.inputAmount,
+      outputAmount: event.outputAmount,
+      originChainId: chainId,
+      depositId: event.depositId,
+      fillDeadline: event.fillDeadline,
+      exclusivityDeadline: event.exclusivityDeadline,
+      message: event.message,
+    };
+    const destinationChainId = depositData.destinationChainId;
+    assert.deepEqual(
+      Array.from(calculateRelayHashUint8Array(relayData, destinationChainId)),
+      Array.from(calculateRelayHashUint8Array({ ...relayData, inputAmount: receivedAmount }, destinationChainId)),
+      "Relay hash should be built from the received amount"
+    );
+    const depositedRelayData = { ...relayData, inputAmount: depositData.inputAmount };
+    assert.notDeepEqual(
+      Array.from(calculateRelayHashUint8Array(relayData, destinationChainId)),
+      Array.from(calculateRelayHashUint8Array(depositedRelayData, destinationChainId)),
+      "Relay hash should differ from the one of the deposited amount"
+    );
+  });
+
   it("Deposit without approval fails", async () => {
     const depositDataValues = Object.values(depositData) as DepositDataValues;
 
     try {
       await program.methods
-        .depositV3(...depositDataValues)
+        .depositV3(...depositDataValues, false, 0, Buffer.alloc(0))
         .accounts(depositAccounts)
         .signers([depositor])
         .rpc();
@@ -684,4 +1587,197 @@ describe("svm_spoke.deposit", () => {
       assert.include(err.toString(), "owner does not match");
     }
   });
+
+  it("Posts fill confirmations only with attestations of the current oracle", async () => {
+    const oracle = Keypair.generate();
+    const rotatedOracle = Keypair.generate();
+    const destinationFillEventHash = Array.from(Buffer.alloc(32, 7));
+
+    const postFillConfirmation = async (depositId: number[], signingKey: Keypair) => {
+      const payload = Buffer.concat([Buffer.from(depositId), Buffer.from(destinationFillEventHash)]);
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signingKey.secretKey,
+        message: payload,
+      });
+      // Ed25519Program places the signature after 16 bytes of offsets and the 32 bytes public key.
+      const attestation = Array.from(ed25519Ix.data.subarray(48, 112));
+      const [fillConfirmation] = PublicKey.findProgramAddressSync(
+        [Buffer.from("deposit_filled"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(depositId)],
+        program.programId
+      );
+      const tx = await program.methods
+        .postFillConfirmation(depositId, destinationFillEventHash, attestation)
+        .accounts({
+          signer: owner,
+          state,
+          fillConfirmation,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          program: program.programId,
+        })
+        .preInstructions([ed25519Ix]) // Signature verification must immediately precede the confirmation.
+        .rpc();
+      return { tx, fillConfirmation };
+    };
+    const setFillConfirmationOracle = async (oracleKey: PublicKey | null) => {
+      await program.methods
+        .setFillConfirmationOracle(oracleKey)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+    };
+
+    // Fill confirmations are disabled until the owner sets the oracle.
+    try {
+      await postFillConfirmation(intToU8Array32(1), oracle);
+      assert.fail("Fill confirmation should fail without oracle");
+    } catch (err: any) {
+      assert.include(err.toString(), "FillConfirmationOracleNotSet", "Expected FillConfirmationOracleNotSet error");
+    }
+
+    await setFillConfirmationOracle(oracle.publicKey);
+
+    try {
+      await postFillConfirmation(intToU8Array32(1), Keypair.generate());
+      assert.fail("Forged attestation should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidFillAttestation", "Expected InvalidFillAttestation error");
+    }
+
+    const { tx, fillConfirmation } = await postFillConfirmation(intToU8Array32(1), oracle);
+    const confirmation = await program.account.fillConfirmation.fetch(fillConfirmation);
+    assert.deepEqual(confirmation.depositId, intToU8Array32(1), "Deposit ID should match");
+    assert.deepEqual(confirmation.destinationFillEventHash, destinationFillEventHash, "Fill event hash should match");
+    assertSE(confirmation.oracle, oracle.publicKey, "Oracle should match");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "depositFilled")?.data;
+    assert.deepEqual(event.depositId, intToU8Array32(1), "Event deposit ID should match");
+    assert.deepEqual(event.destinationFillEventHash, destinationFillEventHash, "Event fill event hash should match");
+    assertSE(event.oracle, oracle.publicKey, "Event oracle should match");
+
+    // Each deposit can only be confirmed once.
+    try {
+      await postFillConfirmation(intToU8Array32(1), oracle);
+      assert.fail("Deposit should not be confirmed twice");
+    } catch (err: any) {
+      assert.include(err.toString(), "already in use", "Expected account already in use error");
+    }
+
+    // Rotated oracle key invalidates attestations of the previous oracle.
+    await setFillConfirmationOracle(rotatedOracle.publicKey);
+    try {
+      await postFillConfirmation(intToU8Array32(2), oracle);
+      assert.fail("Attestation of the previous oracle should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidFillAttestation", "Expected InvalidFillAttestation error");
+    }
+    await postFillConfirmation(intToU8Array32(2), rotatedOracle);
+  });
+  it("Deposits native SOL into the wSOL vault", async () => {
+    const routeChainId = new BN(1);
+    const nativeRoute = createRoutePda(NATIVE_MINT, seed, routeChainId);
+    const nativeVault = await getVaultAta(NATIVE_MINT, state);
+    await program.methods
+      .setEnableRoute(NATIVE_MINT, routeChainId, true, 0, 0)
+      .accounts({
+        ...setEnableRouteAccounts,
+        route: nativeRoute,
+        vault: nativeVault,
+        originTokenMint: NATIVE_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    // Odd lamport amount to check that exactly the deposited lamports are wrapped.
+    const inputAmount = new BN(1_000_003);
+    const fundTx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: payer.publicKey, toPubkey: depositor.publicKey, lamports: 10_000_000 })
+    );
+    await sendAndConfirmTransaction(connection, fundTx, [payer]);
+
+    const nativeWrapAccount = createNativeWrapPda(depositor.publicKey, seed);
+    const depositV3Native = async () => {
+      const depositIx = await program.methods
+        .depositV3Native(
+          depositData.recipient,
+          depositData.outputToken,
+          inputAmount,
+          depositData.outputAmount,
+          depositData.destinationChainId,
+          depositData.exclusiveRelayer,
+          depositData.quoteTimestamp.toNumber(),
+          depositData.fillDeadline.toNumber(),
+          depositData.exclusivityParameter.toNumber(),
+          depositData.message
+        )
+        .accounts({
+          signer: depositor.publicKey,
+          state,
+          route: nativeRoute,
+          destinationChainAlias: program.programId,
+          depositLimit: createDepositLimitPda(NATIVE_MINT, seed, routeChainId),
+          vault: nativeVault,
+          mint: NATIVE_MINT,
+          vaultLiability: createVaultLiabilityPda(NATIVE_MINT, seed),
+          eventCommitment: program.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          nativeWrapAccount,
+          program: program.programId,
+        })
+        .instruction();
+      // Fees are paid by the payer so that the depositor lamports only change by the deposited amount.
+      return await sendAndConfirmTransaction(connection, new Transaction().add(depositIx), [payer, depositor]);
+    };
+
+    // First deposit also creates the vault liability of the wSOL mint.
+    await depositV3Native();
+    const depositorLamportsBefore = await connection.getBalance(depositor.publicKey);
+    const tx = await depositV3Native();
+
+    const vaultAccount = await getAccount(connection, nativeVault);
+    assertSE(
+      vaultAccount.amount,
+      inputAmount.muln(2),
+      "Vault wSOL balance should be increased by the deposited lamports"
+    );
+    assertSE(
+      await connection.getBalance(depositor.publicKey),
+      depositorLamportsBefore - inputAmount.toNumber(),
+      "Depositor lamports should be reduced by exactly the deposited amount"
+    );
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "v3FundsDeposited")?.data;
+    assertSE(event.inputToken, NATIVE_MINT, "Event input token should be the native mint");
+    assertSE(event.inputAmount, inputAmount, "Event input amount should match");
+    assertSE(event.depositor, depositor.publicKey, "Event depositor should match");
+    assert.isNull(event.dustRefunded, "No dust should be refunded");
+    assert.isNull(await connection.getAccountInfo(nativeWrapAccount), "Wrap account should be closed");
+
+    // Lamports sent to the wrap account ahead of the deposit are wrapped along with the deposit and refunded as dust.
+    const dust = 7;
+    const wrapRent = await connection.getMinimumBalanceForRentExemption(ACCOUNT_SIZE);
+    const prefundTx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: payer.publicKey, toPubkey: nativeWrapAccount, lamports: wrapRent + dust })
+    );
+    await sendAndConfirmTransaction(connection, prefundTx, [payer]);
+    const depositorLamportsBeforeDust = await connection.getBalance(depositor.publicKey);
+    const dustTx = await depositV3Native();
+
+    assertSE(
+      (await getAccount(connection, nativeVault)).amount,
+      inputAmount.muln(3),
+      "Vault wSOL balance should only be increased by the deposited lamports"
+    );
+    assertSE(
+      await connection.getBalance(depositor.publicKey),
+      depositorLamportsBeforeDust - inputAmount.toNumber() + wrapRent + dust,
+      "Depositor should receive the prefunded rent and dust of the wrap account"
+    );
+
+    const dustEvents = await readEventsUntilFound(connection, dustTx, [program]);
+    const dustEvent = dustEvents.find((event) => event.name === "v3FundsDeposited")?.data;
+    assertSE(dustEvent.dustRefunded, new BN(dust), "Event should report the refunded dust");
+    assertSE(dustEvent.inputAmount, inputAmount, "Event input amount should exclude the dust");
+  });
 });
@@ -0,0 +1,223 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { AccountMeta, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { common } from "./SvmSpoke.common";
+import { Test } from "../../target/types/test";
+import { intToU8Array32, readEventsUntilFound } from "../../src/svm";
+const { provider, connection, program, owner, seedBalance, initializeState, depositData } = common;
+const { createRoutePda, getVaultAta, assertSE, assert, createVaultLiabilityPda, createDepositLimitPda } = common;
+const { createVaultRegistryPda } = common;
+
+describe("svm_spoke.deposit_with_swap", () => {
+  anchor.setProvider(provider);
+
+  // The test program mock_swap instruction takes amount_in from the depositor and sends amount_out from its pool.
+  const swapProgram = anchor.workspace.Test as Program<Test>;
+  const depositor = Keypair.generate();
+  const payer = (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer;
+  const tokenDecimals = 6;
+  const routeChainId = new BN(1);
+  const swapInAmount = new BN(500000);
+  const swapOutAmount = new BN(400000);
+
+  const [poolAuthority] = PublicKey.findProgramAddressSync([Buffer.from("swap_pool")], swapProgram.programId);
+
+  let state: PublicKey, seed: BN, swapAdapter: PublicKey;
+  let swapInToken: PublicKey, inputToken: PublicKey, depositorSwapInTA: PublicKey, vault: PublicKey;
+  let poolInTA: PublicKey, poolOutTA: PublicKey;
+
+  const createTokenAccount = async (mint: PublicKey, authority: PublicKey) => {
+    return (await getOrCreateAssociatedTokenAccount(connection, payer, mint, authority, true)).address;
+  };
+
+  const setSwapAdapter = async (enabled: boolean, signer: Keypair = payer) => {
+    await program.methods
+      .setSwapAdapter(swapProgram.programId, enabled)
+      .accounts({ signer: signer.publicKey, state, swapAdapter, program: program.programId })
+      .signers([signer])
+      .rpc();
+  };
+
+  const depositV3WithSwap = async (minInputAmount: BN, amountOut = swapOutAmount) => {
+    const swapRoute = (
+      await swapProgram.methods
+        .mockSwap(swapInAmount, amountOut)
+        .accounts({
+          user: depositor.publicKey,
+          userTokenAccount: depositorSwapInTA,
+          poolInTokenAccount: poolInTA,
+          poolOutTokenAccount: poolOutTA,
+          destination: vault,
+        })
+        .instruction()
+    ).data;
+    const swapAccounts: AccountMeta[] = [
+      { pubkey: depositor.publicKey, isSigner: true, isWritable: false },
+      { pubkey: depositorSwapInTA, isSigner: false, isWritable: true },
+      { pubkey: poolInTA, isSigner: false, isWritable: true },
+      { pubkey: poolAuthority, isSigner: false, isWritable: false },
+      { pubkey: poolOutTA, isSigner: false, isWritable: true },
+      { pubkey: vault, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ];
+
+    return await program.methods
+      .depositV3WithSwap(
+        depositData.recipient,
+        inputToken,
+        depositData.outputToken,
+        minInputAmount,
+        depositData.outputAmount,
+        routeChainId,
+        depositData.exclusiveRelayer,
+        depositData.quoteTimestamp.toNumber(),
+        depositData.fillDeadline.toNumber(),
+        depositData.exclusivityParameter.toNumber(),
+        depositData.message,
+        swapRoute
+      )
+      .accounts({
+        signer: depositor.publicKey,
+        state,
+        route: createRoutePda(inputToken, seed, routeChainId),
+        destinationChainAlias: program.programId,
+        depositLimit: createDepositLimitPda(inputToken, seed, routeChainId),
+        vaultRegistry: createVaultRegistryPda(inputToken, seed),
+        swapAdapter,
+        swapProgram: swapProgram.programId,
+        swapInMint: swapInToken,
+        depositorSwapInTokenAccount: depositorSwapInTA,
+        vault,
+        mint: inputToken,
+        vaultLiability: createVaultLiabilityPda(inputToken, seed),
+        eventCommitment: program.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        swapInTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        program: program.programId,
+      })
+      .remainingAccounts(swapAccounts)
+      .signers([depositor])
+      .rpc();
+  };
+
+  const assertBalances = async (depositorSwapIn: BN | number, vaultBalance: BN | number, message: string) => {
+    assertSE((await getAccount(connection, depositorSwapInTA)).amount, depositorSwapIn, `Depositor ${message}`);
+    assertSE((await getAccount(connection, vault)).amount, vaultBalance, `Vault ${message}`);
+  };
+
+  before(async () => {
+    const sig = await connection.requestAirdrop(depositor.publicKey, 10_000_000_000);
+    await provider.connection.confirmTransaction(sig);
+  });
+
+  beforeEach(async () => {
+    ({ state, seed } = await initializeState());
+    swapAdapter = PublicKey.findProgramAddressSync(
+      [Buffer.from("swap_adapter"), seed.toArrayLike(Buffer, "le", 8), swapProgram.programId.toBytes()],
+      program.programId
+    )[0];
+
+    swapInToken = await createMint(connection, payer, owner, owner, tokenDecimals);
+    inputToken = await createMint(connection, payer, owner, owner, tokenDecimals);
+    depositorSwapInTA = await createTokenAccount(swapInToken, depositor.publicKey);
+    await mintTo(connection, payer, swapInToken, depositorSwapInTA, owner, seedBalance);
+    poolInTA = await createTokenAccount(swapInToken, poolAuthority);
+    poolOutTA = await createTokenAccount(inputToken, poolAuthority);
+    await mintTo(connection, payer, inputToken, poolOutTA, owner, seedBalance);
+
+    vault = await getVaultAta(inputToken, state);
+    await program.methods
+      .setEnableRoute(inputToken, routeChainId, true, 0, 0)
+      .accounts({
+        signer: owner,
+        payer: owner,
+        state,
+        route: createRoutePda(inputToken, seed, routeChainId),
+        vault,
+        originTokenMint: inputToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        lookupTableConfig: program.programId,
+        lookupTable: program.programId,
+        addressLookupTableProgram: program.programId,
+      })
+      .rpc();
+  });
+
+  it("Deposits the measured swap output and reports the swapped token", async () => {
+    await setSwapAdapter(true);
+    const tx = await depositV3WithSwap(swapOutAmount);
+
+    await assertBalances(seedBalance - swapInAmount.toNumber(), swapOutAmount, "balance should reflect the swap");
+    const vaultLiability = await program.account.vaultLiability.fetch(createVaultLiabilityPda(inputToken, seed));
+    assertSE(vaultLiability.liabilities, swapOutAmount, "Vault liability should be credited with the swap output");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const deposited = events.find((event) => event.name === "v3FundsDeposited")?.data;
+    assertSE(deposited.inputToken, inputToken, "Input token should be the swap output token");
+    assertSE(deposited.inputAmount, swapOutAmount, "Input amount should be the measured swap output");
+    assertSE(deposited.depositor, depositor.publicKey, "Depositor should be the signer");
+    assertSE(deposited.depositId, intToU8Array32(1), "Deposit ID should use the deposit counter");
+
+    const swapped = events.find((event) => event.name === "swappedForDeposit")?.data;
+    assertSE(swapped.depositId, deposited.depositId, "Swap event should reference the deposit");
+    assertSE(swapped.swapProgram, swapProgram.programId, "Swap program should match");
+    assertSE(swapped.swapInToken, swapInToken, "Swap in token should match");
+    assertSE(swapped.swapInAmount, swapInAmount, "Swap in amount should match the depositor balance change");
+    assertSE(swapped.inputAmount, swapOutAmount, "Swap event input amount should match the deposit");
+
+    // Swap outputs above the minimum are deposited in full.
+    await depositV3WithSwap(swapOutAmount.subn(1));
+    await assertBalances(seedBalance - 2 * swapInAmount.toNumber(), swapOutAmount.muln(2), "should hold both swaps");
+  });
+
+  it("Reverts the whole deposit when the swap output is below min_input_amount", async () => {
+    await setSwapAdapter(true);
+
+    try {
+      await depositV3WithSwap(swapOutAmount, swapOutAmount.subn(1));
+      assert.fail("Deposit should have failed below the minimum input amount");
+    } catch (err: any) {
+      assert.include(err.toString(), "SwapSlippageExceeded", "Expected SwapSlippageExceeded error");
+    }
+    await assertBalances(seedBalance, 0, "balance should be unchanged after the reverted swap");
+    assertSE((await getAccount(connection, poolInTA)).amount, 0, "Pool should not keep the swapped tokens");
+  });
+
+  it("Only deposits through swap programs enabled by the owner", async () => {
+    try {
+      await depositV3WithSwap(swapOutAmount);
+      assert.fail("Deposit should have failed without a swap adapter");
+    } catch (err: any) {
+      assert.include(err.toString(), "AccountNotInitialized", "Expected AccountNotInitialized error");
+    }
+
+    try {
+      await setSwapAdapter(true, depositor);
+      assert.fail("Non owner should not be able to enable a swap adapter");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+    }
+
+    await setSwapAdapter(true);
+    await setSwapAdapter(false);
+    assert.isFalse((await program.account.swapAdapter.fetch(swapAdapter)).enabled, "Swap adapter should be disabled");
+    try {
+      await depositV3WithSwap(swapOutAmount);
+      assert.fail("Deposit should have failed through a disabled swap adapter");
+    } catch (err: any) {
+      assert.include(err.toString(), "DisabledSwapAdapter", "Expected DisabledSwapAdapter error");
+    }
+    await assertBalances(seedBalance, 0, "balance should be unchanged without an enabled swap adapter");
+  });
+});
@@ -12,6 +12,8 @@ import {
   createAssociatedTokenAccountInstruction,
   getMinimumBalanceForRentExemptAccount,
   createApproveCheckedInstruction,
+  createMintToCheckedInstruction,
+  createTransferInstruction,
 } from "@solana/spl-token";
 import {
   PublicKey,
@@ -26,15 +28,18 @@ import {
   calculateRelayHashUint8Array,
   MulticallHandlerCoder,
   AcrossPlusMessageCoder,
+  AcrossPlusActionListCoder,
   sendTransactionWithLookupTable,
   loadFillV3RelayParams,
   intToU8Array32,
+  readEventsUntilFound,
 } from "../../src/svm";
 import { MulticallHandler } from "../../target/types/multicall_handler";
 import { common } from "./SvmSpoke.common";
 import { FillDataParams, FillDataValues } from "../../src/types/svm";
 const { provider, connection, program, owner, chainId, seedBalance } = common;
-const { initializeState, assertSE } = common;
+const { initializeState, assert, assertSE, createVaultRegistryPda, createFillVolumePda } = common;
+const { createMessageSchemaPda } = common;
 
 describe("svm_spoke.fill.across_plus", () => {
   anchor.setProvider(provider);
@@ -48,6 +53,7 @@ describe("svm_spoke.fill.across_plus", () => {
     finalRecipient: PublicKey,
     finalRecipientATA: PublicKey,
     state: PublicKey,
+    seed: BN,
     mint: PublicKey,
     relayerATA: PublicKey;
 
@@ -68,17 +74,42 @@ describe("svm_spoke.fill.across_plus", () => {
       state,
       signer: relayer.publicKey,
       instructionParams: program.programId,
+      vaultRegistry: createVaultRegistryPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
       mint: mint,
       relayerTokenAccount: relayerATA,
       recipientTokenAccount: handlerATA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
       fillStatus: fillStatusPDA,
+      messageSchema: createMessageSchemaPda(relayData.recipient),
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      eventCommitment: program.programId,
+      fillIndexShard: program.programId,
+      originChainAlias: program.programId,
+      relayerStats: program.programId,
+      memoProgram: program.programId,
+      eventForwarder: program.programId,
+      route: program.programId,
+      allowlistEntry: program.programId,
+      repaymentAddressAccount: program.programId,
+      repaymentTokenAccount: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
+      payer: relayer.publicKey,
+      recipientAddressAccount: program.programId,
+      recipientAta: program.programId,
     };
   }
 
-  async function createApproveAndFillIx(multicallHandlerCoder: MulticallHandlerCoder, bufferParams = false) {
+  async function createApproveAndFillIx(
+    multicallHandlerCoder: MulticallHandlerCoder | AcrossPlusActionListCoder,
+    bufferParams = false
+  ) {
     // Delegate state PDA to pull relayer tokens.
     const approveIx = await createApproveCheckedInstruction(
       accounts.relayerTokenAccount,
@@ -89,17 +120,28 @@ describe("svm_spoke.fill.across_plus", () => {
       mintDecimals
     );
 
-    const remainingAccounts: AccountMeta[] = [
-      { pubkey: handlerProgram.programId, isSigner: false, isWritable: false },
-      ...multicallHandlerCoder.compiledKeyMetas,
-    ];
+    // Action list messages reference their handlers within the message accounts.
+    const remainingAccounts: AccountMeta[] =
+      multicallHandlerCoder instanceof AcrossPlusActionListCoder
+        ? multicallHandlerCoder.remainingAccounts
+        : [
+            { pubkey: handlerProgram.programId, isSigner: false, isWritable: false },
+            ...multicallHandlerCoder.compiledKeyMetas,
+          ];
 
     const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
 
     // Prepare fill instruction.
     const fillV3RelayValues: FillDataValues = [relayHash, relayData, new BN(1), relayer.publicKey];
     if (bufferParams) {
-      await loadFillV3RelayParams(program, relayer, fillV3RelayValues[1], fillV3RelayValues[2], fillV3RelayValues[3]);
+      await loadFillV3RelayParams(
+        program,
+        relayer,
+        state,
+        fillV3RelayValues[1],
+        fillV3RelayValues[2],
+        fillV3RelayValues[3],
+      );
       [accounts.instructionParams] = PublicKey.findProgramAddressSync(
         [Buffer.from("instruction_params"), relayer.publicKey.toBuffer()],
         program.programId
@@ -109,7 +151,7 @@ describe("svm_spoke.fill.across_plus", () => {
       ? [fillV3RelayValues[0], null, null, null]
       : fillV3RelayValues;
     const fillIx = await program.methods
-      .fillV3Relay(...fillV3RelayParams)
+      .fillV3Relay(...fillV3RelayParams, 0, Buffer.alloc(0))
       .accounts(accounts)
       .remainingAccounts(remainingAccounts)
       .instruction();
@@ -133,7 +175,7 @@ describe("svm_spoke.fill.across_plus", () => {
     finalRecipient = Keypair.generate().publicKey;
     finalRecipientATA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, finalRecipient)).address;
 
-    ({ state } = await initializeState());
+    ({ state, seed } = await initializeState());
 
     const initialRelayData = {
       depositor: finalRecipient,
@@ -201,6 +243,40 @@ describe("svm_spoke.fill.across_plus", () => {
     );
   });
 
+  it("Rejects messages addressed to a non-executable handler", async () => {
+    const transferIx = createTransferCheckedInstruction(
+      handlerATA,
+      mint,
+      finalRecipientATA,
+      handlerSigner,
+      relayData.outputAmount,
+      mintDecimals
+    );
+    const multicallHandlerCoder = new MulticallHandlerCoder([transferIx]);
+
+    // Address the message to a plain wallet instead of the handler program.
+    const walletHandler = Keypair.generate().publicKey;
+    const message = new AcrossPlusMessageCoder({
+      handler: walletHandler,
+      readOnlyLen: multicallHandlerCoder.readOnlyLen,
+      valueAmount: new BN(0),
+      accounts: multicallHandlerCoder.compiledMessage.accountKeys,
+      handlerMessage: multicallHandlerCoder.encode(),
+    });
+    updateRelayData({ ...relayData, message: message.encode() });
+
+    const { approveIx, fillIx } = await createApproveAndFillIx(multicallHandlerCoder);
+    fillIx.keys = fillIx.keys.map((meta) =>
+      meta.pubkey.equals(handlerProgram.programId) ? { ...meta, pubkey: walletHandler } : meta
+    );
+    try {
+      await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [relayer]);
+      assert.fail("Fill with a non-executable message handler should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "MessageHandlerNotExecutable", "Expected MessageHandlerNotExecutable error");
+    }
+  });
+
   describe("Max token distributions within invoked message call", async () => {
     const fillTokenDistributions = async (numberOfDistributions: number, bufferParams = false) => {
       const iRelayerBal = (await getAccount(connection, relayerATA)).amount;
@@ -325,6 +401,39 @@ describe("svm_spoke.fill.across_plus", () => {
     );
   });
 
+  it("Runs the message of message-only relays without transferring tokens", async () => {
+    const iRelayerBal = (await getAccount(connection, relayerATA)).amount;
+    const iHandlerBal = (await getAccount(connection, handlerATA)).amount;
+    const valueAmount = new BN(1_000_000_000);
+    const valueRecipient = Keypair.generate().publicKey;
+
+    const multicallHandlerCoder = new MulticallHandlerCoder([], valueRecipient);
+    const message = new AcrossPlusMessageCoder({
+      handler: handlerProgram.programId,
+      readOnlyLen: multicallHandlerCoder.readOnlyLen,
+      valueAmount,
+      accounts: multicallHandlerCoder.compiledMessage.accountKeys,
+      handlerMessage: multicallHandlerCoder.encode(),
+    });
+    updateRelayData({ ...relayData, outputAmount: new BN(0), message: message.encode() });
+
+    // Message-only relays need no recipient token account.
+    accounts.recipientTokenAccount = program.programId;
+    const { fillIx } = await createApproveAndFillIx(multicallHandlerCoder);
+    const tx = await sendAndConfirmTransaction(connection, new Transaction().add(fillIx), [relayer]);
+
+    assertSE((await getAccount(connection, relayerATA)).amount, iRelayerBal, "Relayer balance should be unchanged");
+    assertSE((await getAccount(connection, handlerATA)).amount, iHandlerBal, "Recipient balance should be unchanged");
+    assertSE(await connection.getBalance(valueRecipient), valueAmount, "Message should send the value amount");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assert.isTrue(event.relayExecutionInfo.messageOnly, "Fill should be flagged as message-only");
+    assertSE(event.relayExecutionInfo.normalizedOutputAmount, 0, "No output amount should be transferred");
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(accounts.fillStatus);
+    assert.isTrue("filled" in fillStatusAccount.status, "Message-only relay should be marked as filled");
+  });
+
   it("Creates new ATA when forwarding tokens within invoked message call", async () => {
     // We need precise estimate of required funding for ATA creation.
     const valueAmount = await getMinimumBalanceForRentExemptAccount(connection);
@@ -384,4 +493,56 @@ describe("svm_spoke.fill.across_plus", () => {
       "Recipient's balance should be increased by the relay amount"
     );
   });
+
+  it("Executes a swap then transfer handler chain from an action list message", async () => {
+    // Swap is mimicked by sending relayed tokens to the pool and minting output tokens by the handler PDA authority.
+    const outputMint = await createMint(connection, payer, handlerSigner, null, mintDecimals);
+    const handlerOutputATA = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, outputMint, handlerSigner, true)
+    ).address;
+    const pool = Keypair.generate().publicKey;
+    const poolATA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, pool)).address;
+    const finalRecipientOutputATA = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, outputMint, finalRecipient)
+    ).address;
+
+    const swapCoder = new MulticallHandlerCoder([
+      createTransferCheckedInstruction(handlerATA, mint, poolATA, handlerSigner, relayData.outputAmount, mintDecimals),
+      createMintToCheckedInstruction(outputMint, handlerOutputATA, handlerSigner, relayData.outputAmount, mintDecimals),
+    ]);
+    // Plain transfer avoids passing the output mint as read-only after it was writable in the swap action.
+    const transferCoder = new MulticallHandlerCoder([
+      createTransferInstruction(handlerOutputATA, finalRecipientOutputATA, handlerSigner, relayData.outputAmount),
+    ]);
+
+    const actionListCoder = new AcrossPlusActionListCoder([
+      { handler: handlerProgram.programId, coder: swapCoder },
+      { handler: handlerProgram.programId, coder: transferCoder },
+    ]);
+
+    // Update relay data with the encoded message.
+    const newRelayData = { ...relayData, message: actionListCoder.encode() };
+    updateRelayData(newRelayData);
+
+    // Prepare approval and fill instructions as we will need to use Address Lookup Table (ALT).
+    const { approveIx, fillIx } = await createApproveAndFillIx(actionListCoder);
+
+    // Fill using the ALT.
+    const computeBudgetIx = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 });
+    await sendTransactionWithLookupTable(connection, [computeBudgetIx, approveIx, fillIx], relayer);
+
+    // Verify both actions were executed in order.
+    await new Promise((resolve) => setTimeout(resolve, 500)); // Make sure token transfers get processed.
+    assertSE((await getAccount(connection, poolATA)).amount, relayAmount, "Pool should receive the relayed tokens");
+    assertSE(
+      (await getAccount(connection, handlerOutputATA)).amount,
+      0,
+      "Handler should forward all swapped output tokens"
+    );
+    assertSE(
+      (await getAccount(connection, finalRecipientOutputATA)).amount,
+      relayAmount,
+      "Final recipient should receive the swapped output tokens"
+    );
+  });
 });
@@ -1,10 +1,12 @@
 import * as anchor from "@coral-xyz/anchor";
-import { BN, web3 } from "@coral-xyz/anchor";
+import { BN, BorshCoder, EventParser, Program, web3 } from "@coral-xyz/anchor";
 import {
+  ACCOUNT_SIZE,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
   createAccount,
+  createInitializeAccount3Instruction,
   createMint,
   getOrCreateAssociatedTokenAccount,
   mintTo,
@@ -13,21 +15,54 @@ import {
   createApproveCheckedInstruction,
   createReallocateInstruction,
   createEnableCpiGuardInstruction,
+  createEnableRequiredMemoTransfersInstruction,
+  createWrappedNativeAccount,
   ExtensionType,
+  NATIVE_MINT,
 } from "@solana/spl-token";
-import { PublicKey, Keypair, TransactionInstruction, sendAndConfirmTransaction, Transaction } from "@solana/web3.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  Transaction,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { ethers } from "ethers";
 import {
   readEventsUntilFound,
   calculateRelayHashUint8Array,
+  calculateUpdatedDepositHashUint8Array,
+  calculateUsedSignatureKey,
+  evmAddressToPublicKey,
   sendTransactionWithLookupTable,
   hashNonEmptyMessage,
   intToU8Array32,
+  encodePayoutMemoExtraArgs,
+  encodeFillExtraArgsV2,
+  encodeFillExtraArgsV3,
+  encodeFillExtraArgsV4,
+  encodeFillExtraArgsV6,
+  loadExecuteRelayerRefundLeafParams,
+  relayerRefundHashFn,
+  MEMO_PROGRAM_ID,
 } from "../../src/svm";
 import { common } from "./SvmSpoke.common";
 import { testAcrossPlusMessage } from "./utils";
-import { FillDataValues, RelayData } from "../../src/types/svm";
+import {
+  FillDataValues,
+  RelayData,
+  RelayerRefundLeafSolana,
+  RelayerRefundLeafType,
+} from "../../src/types/svm";
+import { MerkleTree } from "../../utils";
+import { Test } from "../../target/types/test";
 const { provider, connection, program, owner, chainId, seedBalance } = common;
-const { recipient, initializeState, setCurrentTime, assertSE, assert } = common;
+const { recipient, initializeState, setCurrentTime, assertSE, assert, createSlowFillLiabilityPda } = common;
+const { createChainAliasPda, createVaultRegistryPda, createFillVolumePda, createClaimLiabilityPda } = common;
+const { createMessageSchemaPda, getVaultAuthority } = common;
 
 describe("svm_spoke.fill", () => {
   anchor.setProvider(provider);
@@ -38,6 +73,7 @@ describe("svm_spoke.fill", () => {
   const tokenDecimals = 6;
 
   let state: PublicKey,
+    seed: BN,
     mint: PublicKey,
     relayerTA: PublicKey,
     recipientTA: PublicKey,
@@ -51,13 +87,35 @@ describe("svm_spoke.fill", () => {
     state: PublicKey;
     signer: PublicKey;
     instructionParams: PublicKey;
+    vaultRegistry: PublicKey;
+    fillVolume: PublicKey;
     mint: PublicKey;
     relayerTokenAccount: PublicKey;
     recipientTokenAccount: PublicKey;
+    deliveryPreference?: PublicKey;
+    recipientEscrow?: PublicKey;
+    nativeUnwrapAccount?: PublicKey;
+    nativeRecipient?: PublicKey;
     fillStatus: PublicKey;
+    messageSchema: PublicKey;
+    depositorCompliance?: PublicKey;
+    recipientCompliance?: PublicKey;
+    eventCommitment?: PublicKey;
+    fillIndexShard?: PublicKey;
+    originChainAlias?: PublicKey;
+    relayerStats?: PublicKey;
+    memoProgram?: PublicKey;
+    eventForwarder?: PublicKey;
+    route?: PublicKey;
+    allowlistEntry?: PublicKey;
+    repaymentAddressAccount?: PublicKey;
+    repaymentTokenAccount?: PublicKey;
     tokenProgram: PublicKey;
     associatedTokenProgram: PublicKey;
     systemProgram: PublicKey;
+    payer: PublicKey;
+    recipientAddressAccount?: PublicKey;
+    recipientAta?: PublicKey;
     program: PublicKey;
   };
 
@@ -75,13 +133,35 @@ describe("svm_spoke.fill", () => {
       state,
       signer: relayer.publicKey,
       instructionParams: program.programId,
+      vaultRegistry: createVaultRegistryPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
       mint: mint,
       relayerTokenAccount: relayerTA,
       recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
       fillStatus: fillStatusPDA,
+      messageSchema: createMessageSchemaPda(relayData.recipient),
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      eventCommitment: program.programId,
+      fillIndexShard: program.programId,
+      originChainAlias: program.programId,
+      relayerStats: program.programId,
+      memoProgram: program.programId,
+      eventForwarder: program.programId,
+      route: program.programId,
+      allowlistEntry: program.programId,
+      repaymentAddressAccount: program.programId,
+      repaymentTokenAccount: program.programId,
       tokenProgram: tokenProgram ?? TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
+      payer: relayer.publicKey,
+      recipientAddressAccount: program.programId,
+      recipientAta: program.programId,
       program: program.programId,
     };
   }
@@ -89,7 +169,9 @@ describe("svm_spoke.fill", () => {
   const approvedFillV3Relay = async (
     fillDataValues: FillDataValues,
     calledFillAccounts: FillAccounts = accounts,
-    callingRelayer: Keypair = relayer
+    callingRelayer: Keypair = relayer,
+    argsVersion = 0,
+    extra: Buffer = Buffer.alloc(0)
   ): Promise<string> => {
     // Delegate state PDA to pull relayer tokens.
     const approveIx = await createApproveCheckedInstruction(
@@ -103,7 +185,7 @@ describe("svm_spoke.fill", () => {
       tokenProgram
     );
     const fillIx = await program.methods
-      .fillV3Relay(...fillDataValues)
+      .fillV3Relay(...fillDataValues, argsVersion, extra)
       .accounts(calledFillAccounts)
       .remainingAccounts(fillRemainingAccounts)
       .instruction();
@@ -131,7 +213,7 @@ describe("svm_spoke.fill", () => {
   });
 
   beforeEach(async () => {
-    ({ state } = await initializeState());
+    ({ state, seed } = await initializeState());
     tokenProgram = TOKEN_PROGRAM_ID; // Some tests might override this.
 
     const initialRelayData = {
@@ -177,6 +259,96 @@ describe("svm_spoke.fill", () => {
     assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
   });
 
+  it("Binds relay hashes to the chain ID of the filled state", async () => {
+    // Another deployment of the program that only differs by its chain ID.
+    const otherChainId = chainId.addn(1);
+    const { state: otherState } = await initializeState(undefined, {
+      initialNumberOfDeposits: new BN(0),
+      chainId: otherChainId,
+      remoteDomain: common.remoteDomain,
+      crossDomainAdmin: common.crossDomainAdmin,
+      depositQuoteTimeBuffer: common.depositQuoteTimeBuffer,
+      fillDeadlineBuffer: common.fillDeadlineBuffer,
+    });
+
+    const fillAgainst = async (fillState: PublicKey, destinationChainId: BN) => {
+      const relayHash = calculateRelayHashUint8Array(relayData, destinationChainId);
+      const [fillStatus] = PublicKey.findProgramAddressSync([Buffer.from("fills"), relayHash], program.programId);
+      await approvedFillV3Relay([Array.from(relayHash), relayData, new BN(1), relayer.publicKey], {
+        ...accounts,
+        state: fillState,
+        fillStatus,
+      });
+    };
+
+    // The same relay data can't be filled against a state of another chain than the one its relay hash commits to.
+    for (const [fillState, destinationChainId] of [
+      [otherState, chainId],
+      [state, otherChainId],
+    ] as [PublicKey, BN][]) {
+      try {
+        await fillAgainst(fillState, destinationChainId);
+        assert.fail("Fill against a state of another chain should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidRelayHash", "Expected InvalidRelayHash error");
+      }
+    }
+
+    await fillAgainst(state, chainId);
+    const recipientAccount = await getAccount(connection, recipientTA);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
+  });
+
+  it("Rejects fills of relays from origin chain IDs aliasing another chain ID", async () => {
+    const setChainAlias = async (alias: BN, canonicalChainId: BN) => {
+      await program.methods
+        .setChainAlias(alias, canonicalChainId)
+        .accounts({
+          signer: owner,
+          state,
+          chainAlias: createChainAliasPda(seed, alias),
+          systemProgram: SystemProgram.programId,
+          program: program.programId,
+        })
+        .rpc();
+    };
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const originChainAlias = createChainAliasPda(seed, relayData.originChainId);
+
+    // Deposits towards an alias are emitted with the canonical chain ID, so relays can't originate from an alias.
+    await setChainAlias(relayData.originChainId, relayData.originChainId.addn(1));
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], {
+        ...accounts,
+        originChainAlias,
+      });
+      assert.fail("Fill of a relay from an aliased origin chain ID should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "NonCanonicalChainId", "Expected NonCanonicalChainId error");
+    }
+
+    // Strict mode requires the chain alias of the origin chain ID.
+    const key = Buffer.alloc(32);
+    key.write("strict_chain_aliases");
+    const value = Buffer.alloc(32);
+    value.writeUInt8(1, 31);
+    await program.methods
+      .setConfig(Array.from(key), Array.from(value), null)
+      .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+      .rpc();
+    await setChainAlias(relayData.originChainId, relayData.originChainId);
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+      assert.fail("Fill without the origin chain alias should fail in strict mode");
+    } catch (err: any) {
+      assert.include(err.toString(), "UnregisteredChainAlias", "Expected UnregisteredChainAlias error");
+    }
+
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], { ...accounts, originChainAlias });
+    const recipientAccount = await getAccount(connection, recipientTA);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
+  });
+
   it("Verifies FilledV3Relay event after filling a relay", async () => {
     const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
     const tx = await approvedFillV3Relay([relayHash, relayData, new BN(420), otherRelayer.publicKey]);
@@ -192,6 +364,8 @@ describe("svm_spoke.fill", () => {
         assertSE(event.messageHash, hashNonEmptyMessage(value as Buffer), `MessageHash should match`);
       } else assertSE(event[key], value, `${key.charAt(0).toUpperCase() + key.slice(1)} should match`);
     });
+    assertSE(event.tokenDecimals, tokenDecimals, "TokenDecimals should match the output token mint");
+    assertSE(event.relayHash, relayHash, "RelayHash should match");
     // RelayExecutionInfo should match.
     assertSE(event.relayExecutionInfo.updatedRecipient, relayData.recipient, "UpdatedRecipient should match");
     assertSE(
@@ -206,6 +380,72 @@ describe("svm_spoke.fill", () => {
     assertSE(event.relayer, otherRelayer.publicKey, "Repayment address should match");
   });
 
+  it("Validates the repayment chain and address of the fill", async () => {
+    // EVM repayment addresses are left padded with zeros to bytes32.
+    const evmRepaymentAddress = evmAddressToPublicKey(ethers.Wallet.createRandom().address);
+    const fillWithRepayment = async (repaymentChainId: BN, repaymentAddress: PublicKey) => {
+      updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      return await approvedFillV3Relay([relayHash, relayData, repaymentChainId, repaymentAddress]);
+    };
+
+    const invalidRepayments: [BN, PublicKey, string][] = [
+      [new BN(0), relayer.publicKey, "InvalidChainId"],
+      [new BN(1), PublicKey.default, "InvalidRepaymentAddress"],
+      [chainId, evmRepaymentAddress, "InvalidRepaymentAddress"],
+    ];
+    for (const [repaymentChainId, repaymentAddress, error] of invalidRepayments) {
+      try {
+        await fillWithRepayment(repaymentChainId, repaymentAddress);
+        assert.fail("Fill with invalid repayment should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), error, `Expected ${error} error`);
+      }
+    }
+
+    // EVM repayment address is accepted for repayment on another chain.
+    const tx = await fillWithRepayment(new BN(1), evmRepaymentAddress);
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assertSE(event.repaymentChainId, new BN(1), "Repayment chain id should match");
+    assertSE(event.relayer, evmRepaymentAddress, "Repayment address should match");
+  });
+
+  it("Rejects fills of degenerate relay data", async () => {
+    // Token accounts can be owned by the default pubkey, so only the relay data validation keeps fills from them.
+    const defaultOwnerTA = await createAccount(
+      connection,
+      payer,
+      mint,
+      PublicKey.default,
+      Keypair.generate(),
+      undefined,
+      tokenProgram
+    );
+    const staleFillDeadline = Math.floor(Date.now() / 1000) - common.fillDeadlineBuffer.toNumber() - 69;
+
+    // Default output tokens are already rejected as mint of the fill, so they are covered by the slow fill tests.
+    const validRelayData = relayData;
+    const invalidFields: [Partial<RelayData>, string][] = [
+      [{ recipient: PublicKey.default }, "InvalidRelayRecipient"],
+      [{ outputAmount: new BN(0) }, "EmptyRelay"],
+      [{ fillDeadline: staleFillDeadline }, "StaleFillDeadline"],
+      [{ recipient: PublicKey.default, outputAmount: new BN(0) }, "InvalidRelayRecipient"],
+      [{ outputAmount: new BN(0), fillDeadline: staleFillDeadline }, "EmptyRelay"],
+    ];
+    for (const [fields, error] of invalidFields) {
+      updateRelayData({ ...validRelayData, ...fields });
+      if (relayData.recipient.equals(PublicKey.default)) accounts.recipientTokenAccount = defaultOwnerTA;
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      try {
+        await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+        assert.fail("Fill of degenerate relay data should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), error, `Expected ${error} error`);
+      }
+    }
+  });
+
   it("Fails to fill a V3 relay after the fill deadline", async () => {
     updateRelayData({ ...relayData, fillDeadline: Math.floor(Date.now() / 1000) - 69 }); // 69 seconds ago
 
@@ -220,6 +460,7 @@ describe("svm_spoke.fill", () => {
 
   it("Fails to fill a V3 relay by non-exclusive relayer before exclusivity deadline", async () => {
     accounts.signer = otherRelayer.publicKey;
+    accounts.payer = otherRelayer.publicKey;
     accounts.relayerTokenAccount = otherRelayerTA;
 
     const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
@@ -235,6 +476,7 @@ describe("svm_spoke.fill", () => {
     updateRelayData({ ...relayData, exclusivityDeadline: Math.floor(Date.now() / 1000) - 100 });
 
     accounts.signer = otherRelayer.publicKey;
+    accounts.payer = otherRelayer.publicKey;
     accounts.relayerTokenAccount = otherRelayerTA;
 
     const recipientAccountBefore = await getAccount(connection, recipientTA);
@@ -305,7 +547,7 @@ describe("svm_spoke.fill", () => {
     }
 
     // Set the current time to past the fill deadline
-    await setCurrentTime(program, state, relayer, new BN(relayData.fillDeadline + 1));
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
 
     // Close the fill PDA
     await program.methods.closeFillPda().accounts(closeFillPdaAccounts).signers([relayer]).rpc();
@@ -315,6 +557,222 @@ describe("svm_spoke.fill", () => {
     assert.isNull(fillStatusAccountAfter, "Fill PDA should be closed after closing");
   });
 
+  it("Cannot close the fill PDA during a bundle dispute", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+
+    const closeFillPdaAccounts = {
+      state,
+      signer: relayer.publicKey,
+      fillStatus: accounts.fillStatus,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+
+    // Freeze is applied after the fill PDA already became closable.
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+    const disputeFreezeUntil = relayData.fillDeadline + 3600;
+    await program.methods
+      .setDisputeFreeze(disputeFreezeUntil)
+      .accounts({ state, signer: owner, program: program.programId })
+      .rpc();
+
+    try {
+      await program.methods.closeFillPda().accounts(closeFillPdaAccounts).signers([relayer]).rpc();
+      assert.fail("Closing fill PDA should have failed during bundle dispute");
+    } catch (err: any) {
+      assert.include(err.toString(), "ClosesFrozenDuringDispute", "Expected ClosesFrozenDuringDispute error");
+    }
+
+    // Closing is possible again once the freeze expires.
+    await setCurrentTime(program, state, new BN(disputeFreezeUntil));
+    await program.methods.closeFillPda().accounts(closeFillPdaAccounts).signers([relayer]).rpc();
+
+    const fillStatusAccountAfter = await connection.getAccountInfo(accounts.fillStatus);
+    assert.isNull(fillStatusAccountAfter, "Fill PDA should be closed after freeze expires");
+  });
+
+  it("Cannot close the fill PDA to a token account", async () => {
+    // Fill with a fresh relayer that is later converted into a token account.
+    const tokenAccountRelayer = Keypair.generate();
+    await connection.confirmTransaction(await connection.requestAirdrop(tokenAccountRelayer.publicKey, 10_000_000_000));
+    const tokenAccountRelayerTA = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, mint, tokenAccountRelayer.publicKey)
+    ).address;
+    await mintTo(connection, payer, mint, tokenAccountRelayerTA, owner, seedBalance);
+
+    updateRelayData({ ...relayData, exclusiveRelayer: tokenAccountRelayer.publicKey });
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await approvedFillV3Relay(
+      [relayHash, relayData, new BN(1), tokenAccountRelayer.publicKey],
+      {
+        ...accounts,
+        signer: tokenAccountRelayer.publicKey,
+        payer: tokenAccountRelayer.publicKey,
+        relayerTokenAccount: tokenAccountRelayerTA,
+      },
+      tokenAccountRelayer
+    );
+
+    // Reassign the relayer account to the token program and initialize it as a token account.
+    const convertTx = new Transaction().add(
+      SystemProgram.allocate({ accountPubkey: tokenAccountRelayer.publicKey, space: ACCOUNT_SIZE }),
+      SystemProgram.assign({ accountPubkey: tokenAccountRelayer.publicKey, programId: TOKEN_PROGRAM_ID }),
+      createInitializeAccount3Instruction(tokenAccountRelayer.publicKey, mint, owner)
+    );
+    await sendAndConfirmTransaction(connection, convertTx, [payer, tokenAccountRelayer]);
+
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+
+    const closeFillPdaAccounts = {
+      state,
+      signer: tokenAccountRelayer.publicKey,
+      fillStatus: accounts.fillStatus,
+    };
+    try {
+      await program.methods.closeFillPda().accounts(closeFillPdaAccounts).signers([tokenAccountRelayer]).rpc();
+      assert.fail("Closing fill PDA to a token account should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidLamportDestination", "Expected InvalidLamportDestination error");
+    }
+  });
+
+  it("Fills a V3 relay with the rent paid by a separate payer", async () => {
+    const sponsor = Keypair.generate();
+    await connection.confirmTransaction(await connection.requestAirdrop(sponsor.publicKey, 10_000_000_000));
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const sponsoredAccounts = { ...accounts, payer: sponsor.publicKey };
+
+    const approveIx = await createApproveCheckedInstruction(
+      relayerTA,
+      mint,
+      state,
+      relayer.publicKey,
+      BigInt(relayData.outputAmount.toString()),
+      tokenDecimals,
+      undefined,
+      tokenProgram
+    );
+    const fillIx = await program.methods
+      .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
+      .accounts(sponsoredAccounts)
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+
+    // Payer must sign the fill, even though the relayer authorizes it.
+    const unsignedFillIx = new TransactionInstruction({
+      ...fillIx,
+      keys: fillIx.keys.map((key) => (key.pubkey.equals(sponsor.publicKey) ? { ...key, isSigner: false } : key)),
+    });
+    try {
+      await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, unsignedFillIx), [payer, relayer]);
+      assert.fail("Fill should have failed due to payer not signing");
+    } catch (err: any) {
+      assert.include(err.toString(), "AccountNotSigner", "Expected AccountNotSigner error");
+    }
+
+    // Relayer authorizes the fill without paying any lamports, as the transaction fee is paid by another account.
+    const iRelayerBal = await connection.getBalance(relayer.publicKey);
+    const iSponsorBal = await connection.getBalance(sponsor.publicKey);
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [payer, relayer, sponsor]);
+    const rent = (await connection.getAccountInfo(accounts.fillStatus))!.lamports;
+    assert.strictEqual(await connection.getBalance(relayer.publicKey), iRelayerBal, "Relayer should not pay rent");
+    assert.strictEqual(
+      await connection.getBalance(sponsor.publicKey),
+      iSponsorBal - rent,
+      "Payer should pay the fill status PDA rent"
+    );
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(accounts.fillStatus);
+    assertSE(fillStatusAccount.relayer, relayer.publicKey, "Relayer should be the signer");
+    assertSE(fillStatusAccount.payer, sponsor.publicKey, "Payer should be recorded");
+
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+
+    // Relayer can't reclaim the rent it did not pay.
+    try {
+      await program.methods
+        .closeFillPda()
+        .accounts({ state, signer: relayer.publicKey, fillStatus: accounts.fillStatus })
+        .signers([relayer])
+        .rpc();
+      assert.fail("Closing sponsored fill PDA by the relayer should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidOriginalPayer", "Expected InvalidOriginalPayer error");
+    }
+
+    // Rent is reclaimed to the payer instead.
+    await program.methods
+      .closeFillStatusAccount(relayHash, relayData)
+      .accounts({
+        signer: owner,
+        state,
+        fillStatus: accounts.fillStatus,
+        payer: sponsor.publicKey,
+        slowFillLiability: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+    assert.isNull(await connection.getAccountInfo(accounts.fillStatus), "Fill PDA should be closed");
+    assert.strictEqual(await connection.getBalance(sponsor.publicKey), iSponsorBal, "Payer should reclaim the rent");
+    assert.strictEqual(await connection.getBalance(relayer.publicKey), iRelayerBal, "Relayer should not get the rent");
+  });
+
+  it("Blocks fills to blocklisted recipient when compliance authority is set", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const getCompliancePda = (subject: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("compliance"), seed.toArrayLike(Buffer, "le", 8), subject.toBuffer()],
+        program.programId
+      )[0];
+    const depositorCompliance = getCompliancePda(relayData.depositor);
+    const recipientCompliance = getCompliancePda(relayData.recipient);
+    const complianceAccounts = { ...accounts, depositorCompliance, recipientCompliance };
+
+    // Owner acts as the compliance authority.
+    await program.methods
+      .setComplianceAuthority(owner)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+
+    // Attestation accounts are required once the compliance authority is set.
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+      assert.fail("Fill should have failed due to missing compliance attestations");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingComplianceAttestation", "Expected MissingComplianceAttestation error");
+    }
+
+    // Block the recipient.
+    const entryAccounts = {
+      signer: owner,
+      state,
+      complianceAttestation: recipientCompliance,
+      program: program.programId,
+    };
+    await program.methods.addComplianceEntry(relayData.recipient).accounts(entryAccounts).rpc();
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], complianceAccounts);
+      assert.fail("Fill should have failed due to blocked recipient");
+    } catch (err: any) {
+      assert.include(err.toString(), "ComplianceBlocked", "Expected ComplianceBlocked error");
+    }
+
+    // Unblocking the recipient allows the fill.
+    await program.methods.removeComplianceEntry(relayData.recipient).accounts(entryAccounts).rpc();
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], complianceAccounts);
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(accounts.fillStatus);
+    assert.equal(JSON.stringify(fillStatusAccount.status), `{"filled":{}}`, "Relay should be filled");
+
+    // Clearing the compliance authority no longer requires attestation accounts.
+    await program.methods
+      .setComplianceAuthority(null)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+    updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+    const newRelayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await approvedFillV3Relay([newRelayHash, relayData, new BN(1), relayer.publicKey]);
+  });
+
   it("Fetches FillStatusAccount before and after fillV3Relay", async () => {
     const relayHash = calculateRelayHashUint8Array(relayData, chainId);
     const [fillStatusPDA] = PublicKey.findProgramAddressSync([Buffer.from("fills"), relayHash], program.programId);
@@ -333,6 +791,193 @@ describe("svm_spoke.fill", () => {
     assert.equal(fillStatusAccount.relayer.toString(), relayer.publicKey.toString(), "Caller should be set as relayer");
   });
 
+  it("Reads packed fill statuses for live, closed and foreign accounts", async () => {
+    // Live fill PDA.
+    const liveRelayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const liveFillStatus = accounts.fillStatus;
+    await approvedFillV3Relay([liveRelayHash, relayData, new BN(1), relayer.publicKey]);
+
+    // Fill PDA that is closed after its fill deadline.
+    updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+    const closedRelayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const closedFillStatus = accounts.fillStatus;
+    await approvedFillV3Relay([closedRelayHash, relayData, new BN(1), relayer.publicKey]);
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+    const closeFillPdaAccounts = {
+      state,
+      signer: relayer.publicKey,
+      fillStatus: closedFillStatus,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await program.methods.closeFillPda().accounts(closeFillPdaAccounts).signers([relayer]).rpc();
+
+    // Foreign accounts owned by this program with another discriminator and owned by another program.
+    const fillStatusAccounts = [liveFillStatus, closedFillStatus, state, recipientTA];
+    const packed: Buffer = await program.methods
+      .getFillStatusesPacked()
+      .remainingAccounts(fillStatusAccounts.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+      .view();
+
+    const entrySize = 37; // status (u8), relayer (Pubkey), fill_deadline (u32).
+    const invalidStatus = 255;
+    assert.strictEqual(packed.length, fillStatusAccounts.length * entrySize, "Packed length should match accounts");
+    const entry = (index: number) => packed.subarray(index * entrySize, (index + 1) * entrySize);
+
+    assert.strictEqual(entry(0)[0], 2, "Live fill status should be Filled");
+    assert.isTrue(new PublicKey(entry(0).subarray(1, 33)).equals(relayer.publicKey), "Relayer should match");
+    assert.strictEqual(entry(0).readUInt32LE(33), relayData.fillDeadline, "Fill deadline should match");
+    for (let i = 1; i < fillStatusAccounts.length; i++) {
+      assert.strictEqual(entry(i)[0], invalidStatus, `Entry ${i} should be packed with the sentinel status`);
+    }
+
+    // Batches are capped to keep the packed entries within the return data limit.
+    try {
+      await program.methods
+        .getFillStatusesPacked()
+        .remainingAccounts(Array(26).fill({ pubkey: liveFillStatus, isWritable: false, isSigner: false }))
+        .rpc();
+      assert.fail("Reading more than 25 fill statuses should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "TooManyFillStatusAccounts", "Expected TooManyFillStatusAccounts error");
+    }
+  });
+
+  it("Reads the fill status of a relay", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const getFillStatus = () =>
+      program.methods.getFillStatus(relayHash).accounts({ fillStatus: accounts.fillStatus }).view();
+
+    // Relays without a fill status PDA are unfilled.
+    let fillStatus = await getFillStatus();
+    assert.isTrue("unfilled" in fillStatus.status, "Relay without a fill status PDA should be unfilled");
+    assertSE(fillStatus.relayer, PublicKey.default, "Relayer should be zeroed");
+    assertSE(fillStatus.fillDeadline, 0, "Fill deadline should be zeroed");
+
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    fillStatus = await getFillStatus();
+    assert.isTrue("filled" in fillStatus.status, "Relay should be filled");
+    assertSE(fillStatus.relayer, relayer.publicKey, "Relayer should match");
+    assertSE(fillStatus.fillDeadline, relayData.fillDeadline, "Fill deadline should match");
+    assertSE(fillStatus.payer, relayer.publicKey, "Payer should match");
+
+    // Fill status PDAs of the current layout don't need to be migrated.
+    try {
+      await program.methods
+        .migrateFillStatusAccount(relayHash, relayData)
+        .accounts({ signer: owner, state, fillStatus: accounts.fillStatus })
+        .rpc();
+      assert.fail("Migrating a fill status PDA of the current layout should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotLegacyFillStatusAccount", "Expected NotLegacyFillStatusAccount error");
+    }
+  });
+
+  it("Re-emits the fill event from the fill PDA", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const [eventReplayActivity] = PublicKey.findProgramAddressSync(
+      [Buffer.from("event_replay_activity"), seed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const reEmitAccounts = {
+      signer: owner,
+      state,
+      fillStatus: accounts.fillStatus,
+      eventReplayActivity,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      program: program.programId,
+    };
+
+    // Only filled relays can be replayed.
+    try {
+      await program.methods.reEmitFillEvent(relayHash).accounts(reEmitAccounts).rpc();
+      assert.fail("Replaying an unfilled relay should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "AccountNotInitialized", "Expected AccountNotInitialized error");
+    }
+
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(accounts.fillStatus);
+
+    const tx = await program.methods.reEmitFillEvent(relayHash).accounts(reEmitAccounts).rpc();
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3RelayReplay")?.data;
+    assert.isDefined(event, "FilledV3RelayReplay event should be emitted");
+    assertSE(event.relayHash, relayHash, "Relay hash should match");
+    assertSE(event.relayer, fillStatusAccount.relayer, "Relayer should match");
+    assertSE(event.fillDeadline, fillStatusAccount.fillDeadline, "Fill deadline should match");
+    assertSE(event.requester, fillStatusAccount.requester, "Requester should match");
+    assert.isTrue(event.replay, "Replay flag should be set");
+    assertSE(
+      await program.account.fillStatusAccount.fetch(accounts.fillStatus),
+      fillStatusAccount,
+      "Fill status should not change"
+    );
+
+    // Only the owner can replay fill events.
+    try {
+      await program.methods
+        .reEmitFillEvent(relayHash)
+        .accounts({ ...reEmitAccounts, signer: relayer.publicKey })
+        .signers([relayer])
+        .rpc();
+      assert.fail("Replaying by non-owner should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+    }
+
+    // Replays are rate limited within the window.
+    const eventReplayRateLimit = 10;
+    for (let i = 1; i < eventReplayRateLimit; i++) {
+      await program.methods.reEmitFillEvent(relayHash).accounts(reEmitAccounts).rpc();
+    }
+    try {
+      await program.methods.reEmitFillEvent(relayHash).accounts(reEmitAccounts).rpc();
+      assert.fail("Replaying above the rate limit should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "EventReplayRateLimited", "Expected EventReplayRateLimited error");
+    }
+  });
+
+  it("Validates versioned extra args of fill instruction data", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const fillWithExtraArgs = async (argsVersion: number, extra: Buffer) => {
+      return await program.methods
+        .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, argsVersion, extra)
+        .accounts(accounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .signers([relayer])
+        .rpc();
+    };
+
+    // Version 0 args are serialized as a trailing u8 version followed by an empty u32 length prefixed blob.
+    const fillIx = await program.methods
+      .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
+      .accounts(accounts)
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    assert.deepEqual(Array.from(fillIx.data.subarray(-5)), [0, 0, 0, 0, 0], "Version 0 suffix should be 5 zero bytes");
+
+    // Unknown versions are rejected so that newer clients get an error instead of silently dropped parameters.
+    try {
+      await fillWithExtraArgs(2, Buffer.alloc(0));
+      assert.fail("Fill should have failed for unknown args version");
+    } catch (err: any) {
+      assert.include(err.toString(), "UnsupportedArgsVersion", "Expected UnsupportedArgsVersion error");
+    }
+
+    // Version 0 does not carry any extra args.
+    try {
+      await fillWithExtraArgs(0, Buffer.from([1]));
+      assert.fail("Fill should have failed for non-empty version 0 extra args");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidExtraArgs", "Expected InvalidExtraArgs error");
+    }
+
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(accounts.fillStatus);
+    assert.equal(JSON.stringify(fillStatusAccount.status), `{"filled":{}}`, "Version 0 fill should succeed");
+  });
+
   it("Fails to fill a relay when fills are paused", async () => {
     // Pause fills
     const pauseFillsAccounts = { state: state, signer: owner, program: program.programId };
@@ -383,6 +1028,8 @@ describe("svm_spoke.fill", () => {
     try {
       await approvedFillV3Relay([Array.from(relayHash), relayData, new BN(1), relayer.publicKey], {
         ...accounts,
+        vaultRegistry: createVaultRegistryPda(wrongMint, seed),
+        fillVolume: createFillVolumePda(wrongMint, seed),
         mint: wrongMint,
         relayerTokenAccount: wrongRelayerTA,
         recipientTokenAccount: wrongRecipientTA,
@@ -405,7 +1052,7 @@ describe("svm_spoke.fill", () => {
 
     // No need for approval in self-relay.
     const txSignature = await program.methods
-      .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey)
+      .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
       .accounts(accounts)
       .remainingAccounts(fillRemainingAccounts)
       .signers([relayer])
@@ -454,6 +1101,44 @@ describe("svm_spoke.fill", () => {
       "Recipient's balance should be increased by the relay amount"
     );
   });
+  it("Fills to a non-ATA token account owned by a PDA recipient", async () => {
+    // Recipient PDA of another program holding a custom keypair-created token account.
+    const [pdaRecipient] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], Keypair.generate().publicKey);
+    const pdaRecipientTA = await createAccount(connection, payer, mint, pdaRecipient, Keypair.generate());
+    updateRelayData({
+      ...relayData,
+      recipient: pdaRecipient,
+      depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+    });
+
+    // Account owned by someone else is rejected even when the recipient is its delegate.
+    const delegatedTA = await createAccount(connection, payer, mint, relayer.publicKey, Keypair.generate());
+    const approveRecipientIx = createApproveCheckedInstruction(
+      delegatedTA,
+      mint,
+      pdaRecipient,
+      relayer.publicKey,
+      BigInt(relayAmount),
+      tokenDecimals
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveRecipientIx), [relayer]);
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], {
+        ...accounts,
+        recipientTokenAccount: delegatedTA,
+      });
+      assert.fail("Should not be able to fill relay to a token account not owned by the recipient");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConstraintTokenOwner", "Expected ConstraintTokenOwner error");
+    }
+
+    accounts.recipientTokenAccount = pdaRecipientTA;
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    const recipientAccount = await getAccount(connection, pdaRecipientTA);
+    assertSE(recipientAccount.amount, relayAmount, "PDA recipient's token account should receive the relay amount");
+  });
+
   it("Fills a deposit for a recipient without an existing ATA", async () => {
     // Generate a new recipient account
     const newRecipient = Keypair.generate().publicKey;
@@ -498,7 +1183,7 @@ describe("svm_spoke.fill", () => {
       tokenProgram
     );
     const fillInstruction = await program.methods
-      .fillV3Relay(relayHash, newRelayData, new BN(1), relayer.publicKey)
+      .fillV3Relay(relayHash, newRelayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
       .accounts(accounts)
       .remainingAccounts(fillRemainingAccounts)
       .instruction();
@@ -511,6 +1196,47 @@ describe("svm_spoke.fill", () => {
     const recipientAccount = await getAccount(connection, newRecipientATA);
     assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
   });
+  it("Creates the recipient ATA within the fill when requested by version 6 extra args", async () => {
+    const newRecipient = Keypair.generate().publicKey;
+    const newRecipientATA = getAssociatedTokenAddressSync(mint, newRecipient);
+    const newRelayData = {
+      ...relayData,
+      recipient: newRecipient,
+      depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+    };
+    updateRelayData(newRelayData);
+    const relayHash = Array.from(calculateRelayHashUint8Array(newRelayData, chainId));
+
+    // The created ATA receives the payout in place of the recipient token account.
+    const fillAccounts = {
+      ...accounts,
+      recipientTokenAccount: program.programId,
+      recipientAddressAccount: newRecipient,
+      recipientAta: newRecipientATA,
+    };
+    const extra = encodeFillExtraArgsV6(null, false, null, false, true);
+    const fillDataValues: FillDataValues = [relayHash, newRelayData, new BN(1), relayer.publicKey];
+    await approvedFillV3Relay(fillDataValues, fillAccounts, undefined, 6, extra);
+
+    const recipientAccount = await getAccount(connection, newRecipientATA);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
+
+    // The recipient address must match the relay recipient.
+    const otherRelayData = { ...newRelayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) };
+    const otherRelayHash = Array.from(calculateRelayHashUint8Array(otherRelayData, chainId));
+    try {
+      await approvedFillV3Relay(
+        [otherRelayHash, otherRelayData, new BN(1), relayer.publicKey],
+        { ...fillAccounts, recipientAddressAccount: relayer.publicKey },
+        undefined,
+        6,
+        extra
+      );
+      assert.fail("Fill should have failed due to a recipient address mismatch");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidRecipientAta", "Expected InvalidRecipientAta error");
+    }
+  });
   it("Max fills in one transaction with account creation", async () => {
     // Save relayer balance before the the fills
     const iRelayerBal = (await getAccount(connection, relayerTA)).amount;
@@ -547,7 +1273,7 @@ describe("svm_spoke.fill", () => {
       accounts.recipientTokenAccount = recipientAssociatedTokens[i];
       const relayHash = Array.from(calculateRelayHashUint8Array(newRelayData, chainId));
       const fillInstruction = await program.methods
-        .fillV3Relay(relayHash, newRelayData, new BN(1), relayer.publicKey)
+        .fillV3Relay(relayHash, newRelayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
         .accounts(accounts)
         .remainingAccounts(fillRemainingAccounts)
         .instruction();
@@ -653,18 +1379,1195 @@ describe("svm_spoke.fill", () => {
     assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
   });
 
-  it("Emits zeroed hash for empty message", async () => {
-    updateRelayData({ ...relayData, message: Buffer.alloc(0) });
-    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
-    const tx = await approvedFillV3Relay([relayHash, relayData, new BN(420), otherRelayer.publicKey]);
-
-    // Fetch and verify the FilledV3Relay event
-    const events = await readEventsUntilFound(connection, tx, [program]);
-    const event = events.find((event) => event.name === "filledV3Relay")?.data;
-    assert.isNotNull(event, "FilledV3Relay event should be emitted");
+  it("Rejects fills of a Token-2022 mint passing the legacy token program", async () => {
+    tokenProgram = TOKEN_2022_PROGRAM_ID;
+
+    // Remint the tokens on the token 2022 program.
+    mint = await createMint(connection, payer, owner, owner, tokenDecimals, undefined, undefined, tokenProgram);
+    recipientTA = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint,
+        recipient,
+        undefined,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    relayerTA = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint,
+        relayer.publicKey,
+        undefined,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    await mintTo(connection, payer, mint, relayerTA, owner, seedBalance, undefined, undefined, tokenProgram);
+    updateRelayData({ ...relayData, outputToken: mint });
+
+    // The recipient ATA is derived with the program owning the mint, so the passed token program must match it.
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], {
+        ...accounts,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+      assert.fail("Fill passing the legacy token program should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "TokenProgramMismatch", "Expected TokenProgramMismatch error");
+    }
+
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    const recipientAccount = await getAccount(connection, recipientTA, undefined, tokenProgram);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
+  });
+
+  it("Fills a V3 relay with a payout memo required by the recipient token account", async () => {
+    // Required memo transfers are available only for the 2022 token program.
+    tokenProgram = TOKEN_2022_PROGRAM_ID;
+
+    // Remint the tokens on the token 2022 program for a recipient that can enable required memos.
+    const memoRecipient = Keypair.generate();
+    mint = await createMint(connection, payer, owner, owner, tokenDecimals, undefined, undefined, tokenProgram);
+    recipientTA = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint,
+        memoRecipient.publicKey,
+        undefined,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    relayerTA = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint,
+        relayer.publicKey,
+        undefined,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    await mintTo(connection, payer, mint, relayerTA, owner, seedBalance, undefined, undefined, tokenProgram);
+
+    // Update relay data with new mint and recipient.
+    relayData.recipient = memoRecipient.publicKey;
+    relayData.outputToken = mint;
+    updateRelayData(relayData);
+
+    // Require incoming transfer memos for the recipient (requires TA reallocation).
+    const enableRequiredMemoTx = new Transaction().add(
+      createReallocateInstruction(recipientTA, payer.publicKey, [ExtensionType.MemoTransfer], memoRecipient.publicKey),
+      createEnableRequiredMemoTransfersInstruction(recipientTA, memoRecipient.publicKey)
+    );
+    await sendAndConfirmTransaction(connection, enableRequiredMemoTx, [payer, memoRecipient]);
+
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const fillWithMemo = async (extra: Buffer, memoProgram: PublicKey) => {
+      const approveIx = await createApproveCheckedInstruction(
+        relayerTA,
+        mint,
+        state,
+        relayer.publicKey,
+        BigInt(relayAmount),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      const fillIx = await program.methods
+        .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, 1, extra)
+        .accounts({ ...accounts, memoProgram })
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      return await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [payer, relayer]);
+    };
+
+    // Fill without a memo is rejected with a typed error instead of failing within the token program.
+    try {
+      await fillWithMemo(encodePayoutMemoExtraArgs(null), program.programId);
+      assert.fail("Fill should have failed without a memo required by the recipient");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingRequiredMemo", "Expected MissingRequiredMemo error");
+    }
+
+    // Payout memos are bounded to 64 bytes.
+    try {
+      await fillWithMemo(encodePayoutMemoExtraArgs(Buffer.alloc(65, 1)), MEMO_PROGRAM_ID);
+      assert.fail("Fill should have failed for an oversized memo");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidPayoutMemo", "Expected InvalidPayoutMemo error");
+    }
+
+    const memo = Buffer.from("exchange deposit tag 1234");
+    const tx = await fillWithMemo(encodePayoutMemoExtraArgs(memo), MEMO_PROGRAM_ID);
+
+    // Verify recipient's balance after the fill
+    const recipientAccount = await getAccount(connection, recipientTA, undefined, tokenProgram);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
+
+    // Verify the memo program was invoked in the inner instructions of the fill.
+    const txResult = await connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    if (txResult === null || txResult.meta === null) throw new Error("Transaction meta not confirmed");
+    const accountKeys = txResult.transaction.message.getAccountKeys();
+    const innerProgramIds = (txResult.meta.innerInstructions ?? []).flatMap((innerIxs) =>
+      innerIxs.instructions.map((ix) => accountKeys.get(ix.programIdIndex)?.toString())
+    );
+    assert.include(innerProgramIds, MEMO_PROGRAM_ID.toString(), "Memo program should be invoked");
+
+    // Verify the memo hash in the fill event.
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assertSE(event.relayExecutionInfo.memoHash, hashNonEmptyMessage(memo), "Memo hash should match");
+  });
+
+  it("Validates message against the recipient message schema", async () => {
+    // Register message schema for a new recipient that can sign.
+    const schemaRecipient = Keypair.generate();
+    const schemaTag = Array.from(encodedMessage.subarray(0, 4));
+    const [messageSchema] = PublicKey.findProgramAddressSync(
+      [Buffer.from("message_schema"), schemaRecipient.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .registerMessageSchema(encodedMessage.length, encodedMessage.length, schemaTag)
+      .accounts({ payer: owner, recipient: schemaRecipient.publicKey, messageSchema, program: program.programId })
+      .signers([schemaRecipient])
+      .rpc();
+
+    const schemaRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, schemaRecipient.publicKey))
+      .address;
+
+    // Fill with the message not matching the schema tag should fail.
+    const badMessage = Buffer.from(encodedMessage);
+    badMessage[0] ^= 0xff;
+    updateRelayData({ ...relayData, recipient: schemaRecipient.publicKey, message: badMessage });
+    accounts.recipientTokenAccount = schemaRecipientTA;
+    let relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+
+    // Validation cannot be skipped by passing the program ID in place of the schema PDA.
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], {
+        ...accounts,
+        messageSchema: program.programId,
+      });
+      assert.fail("Fill should have failed without the message schema PDA");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+    }
+
+    try {
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+      assert.fail("Fill should have failed due to message schema mismatch");
+    } catch (err: any) {
+      assert.include(err.toString(), "MessageSchemaMismatch", "Expected MessageSchemaMismatch error");
+    }
+
+    // Fill with the message matching the schema should succeed.
+    updateRelayData({ ...relayData, message: encodedMessage });
+    accounts.recipientTokenAccount = schemaRecipientTA;
+    relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+
+    const recipientAccount = await getAccount(connection, schemaRecipientTA);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient's balance should be increased by the relay amount");
+  });
+
+  it("Emits zeroed hash for empty message", async () => {
+    updateRelayData({ ...relayData, message: Buffer.alloc(0) });
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const tx = await approvedFillV3Relay([relayHash, relayData, new BN(420), otherRelayer.publicKey]);
+
+    // Fetch and verify the FilledV3Relay event
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assert.isNotNull(event, "FilledV3Relay event should be emitted");
 
     // Verify that the event data has zeroed message hash.
     assertSE(event.messageHash, new Uint8Array(32), `MessageHash should be zeroed`);
     assertSE(event.relayExecutionInfo.updatedMessageHash, new Uint8Array(32), `UpdatedMessageHash should be zeroed`);
   });
+
+  it("Delivers fill payouts to the recipient escrow only when opted in", async () => {
+    const escrowRecipient = Keypair.generate();
+    await connection.requestAirdrop(escrowRecipient.publicKey, 10_000_000_000); // 10 SOL
+    const escrowRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, escrowRecipient.publicKey))
+      .address;
+    const [deliveryPreference] = PublicKey.findProgramAddressSync(
+      [Buffer.from("delivery_preference"), seed.toArrayLike(Buffer, "le", 8), escrowRecipient.publicKey.toBuffer()],
+      program.programId
+    );
+    const [recipientEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), seed.toArrayLike(Buffer, "le", 8), escrowRecipient.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeRecipientEscrow(escrowRecipient.publicKey)
+      .accounts({ signer: owner, state, mint, recipientEscrow, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    const fillForEscrowRecipient = async (preference: PublicKey = deliveryPreference) => {
+      updateRelayData({
+        ...relayData,
+        recipient: escrowRecipient.publicKey,
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+      });
+      accounts.recipientTokenAccount = escrowRecipientTA;
+      accounts.deliveryPreference = preference;
+      accounts.recipientEscrow = recipientEscrow;
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      return await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    };
+    const preferenceAccounts = { recipient: escrowRecipient.publicKey, state, deliveryPreference };
+
+    // Recipient opts into escrow delivery, so the fill payout accrues in the escrow.
+    await program.methods
+      .setDeliveryPreference({ escrow: {} })
+      .accounts({ ...preferenceAccounts, program: program.programId })
+      .signers([escrowRecipient])
+      .rpc();
+    const tx = await fillForEscrowRecipient();
+    assertSE((await getAccount(connection, recipientEscrow)).amount, relayAmount, "Escrow should receive the payout");
+    assertSE((await getAccount(connection, escrowRecipientTA)).amount, 0, "Recipient ATA should not receive payout");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assertSE(event.relayExecutionInfo.escrow, recipientEscrow, "Event should record the escrowed destination");
+
+    // Escrow delivery requires the recipient escrow account.
+    try {
+      updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      accounts.recipientTokenAccount = escrowRecipientTA;
+      accounts.deliveryPreference = deliveryPreference;
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+      assert.fail("Fill should have failed without recipient escrow");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingRecipientEscrow", "Expected MissingRecipientEscrow error");
+    }
+
+    // Switching back to push delivery mid-flight sends the next payout to the recipient ATA.
+    await program.methods
+      .setDeliveryPreference({ push: {} })
+      .accounts({ ...preferenceAccounts, program: program.programId })
+      .signers([escrowRecipient])
+      .rpc();
+    await fillForEscrowRecipient();
+    assertSE((await getAccount(connection, escrowRecipientTA)).amount, relayAmount, "Recipient ATA should be paid");
+
+    // Clearing the preference after opting into escrow again also restores push delivery.
+    await program.methods
+      .setDeliveryPreference({ escrow: {} })
+      .accounts({ ...preferenceAccounts, program: program.programId })
+      .signers([escrowRecipient])
+      .rpc();
+    await program.methods
+      .clearDeliveryPreference()
+      .accounts({ ...preferenceAccounts, program: program.programId })
+      .signers([escrowRecipient])
+      .rpc();
+    await fillForEscrowRecipient(program.programId);
+    assertSE((await getAccount(connection, escrowRecipientTA)).amount, 2 * relayAmount, "Recipient ATA should be paid");
+    assertSE((await getAccount(connection, recipientEscrow)).amount, relayAmount, "Escrow should be unchanged");
+
+    // Only the recipient can withdraw from its escrow, to any token account.
+    const withdrawAccounts = {
+      recipient: escrowRecipient.publicKey,
+      state,
+      mint,
+      recipientEscrow,
+      destination: relayerTA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      program: program.programId,
+    };
+    try {
+      await program.methods
+        .withdrawEscrow(new BN(relayAmount))
+        .accounts({ ...withdrawAccounts, recipient: relayer.publicKey })
+        .signers([relayer])
+        .rpc();
+      assert.fail("Non-recipient should not be able to withdraw the escrow");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+    }
+
+    const iRelayerBal = (await getAccount(connection, relayerTA)).amount;
+    await program.methods
+      .withdrawEscrow(new BN(relayAmount))
+      .accounts(withdrawAccounts)
+      .signers([escrowRecipient])
+      .rpc();
+    assertSE((await getAccount(connection, recipientEscrow)).amount, 0, "Escrow should be empty");
+    assertSE(
+      (await getAccount(connection, relayerTA)).amount,
+      iRelayerBal + BigInt(relayAmount),
+      "Destination should receive the withdrawn amount"
+    );
+  });
+
+  it("Unwraps wSOL fill payouts to native SOL only when opted in", async () => {
+    const nativeRecipient = Keypair.generate();
+    await connection.requestAirdrop(nativeRecipient.publicKey, 10_000_000_000); // 10 SOL
+    const relayerWsolTA = await createWrappedNativeAccount(connection, payer, relayer.publicKey, 2 * relayAmount);
+    const [deliveryPreference] = PublicKey.findProgramAddressSync(
+      [Buffer.from("delivery_preference"), seed.toArrayLike(Buffer, "le", 8), nativeRecipient.publicKey.toBuffer()],
+      program.programId
+    );
+    const [nativeUnwrapAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("native_unwrap"), seed.toArrayLike(Buffer, "le", 8), relayer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const fillNativeRelay = async (nativeAccounts: { nativeUnwrapAccount: PublicKey; nativeRecipient: PublicKey }) => {
+      updateRelayData({
+        ...relayData,
+        recipient: nativeRecipient.publicKey,
+        outputToken: NATIVE_MINT,
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+      });
+      const fillAccounts = {
+        ...accounts,
+        vaultRegistry: createVaultRegistryPda(NATIVE_MINT, seed),
+        fillVolume: createFillVolumePda(NATIVE_MINT, seed),
+        mint: NATIVE_MINT,
+        relayerTokenAccount: relayerWsolTA,
+        recipientTokenAccount: program.programId,
+        deliveryPreference,
+        ...nativeAccounts,
+      };
+      // wSOL has 9 decimals, so the approval cannot reuse the approvedFillV3Relay helper.
+      const approveIx = await createApproveCheckedInstruction(
+        relayerWsolTA,
+        NATIVE_MINT,
+        state,
+        relayer.publicKey,
+        BigInt(relayAmount),
+        9
+      );
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const fillIx = await program.methods
+        .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
+        .accounts(fillAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      return await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [payer, relayer]);
+    };
+
+    await program.methods
+      .setDeliveryPreference({ native: {} })
+      .accounts({ recipient: nativeRecipient.publicKey, state, deliveryPreference, program: program.programId })
+      .signers([nativeRecipient])
+      .rpc();
+
+    // Native delivery requires the native unwrap account.
+    try {
+      await fillNativeRelay({ nativeUnwrapAccount: program.programId, nativeRecipient: nativeRecipient.publicKey });
+      assert.fail("Fill should have failed without native unwrap account");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingNativeUnwrapAccount", "Expected MissingNativeUnwrapAccount error");
+    }
+
+    // Unwrapped lamports can only be sent to the relay recipient.
+    try {
+      await fillNativeRelay({ nativeUnwrapAccount, nativeRecipient: otherRelayer.publicKey });
+      assert.fail("Fill should have failed with wrong native recipient");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidNativeRecipient", "Expected InvalidNativeRecipient error");
+    }
+
+    const iRecipientLamports = await connection.getBalance(nativeRecipient.publicKey);
+    await fillNativeRelay({ nativeUnwrapAccount, nativeRecipient: nativeRecipient.publicKey });
+    assertSE(
+      await connection.getBalance(nativeRecipient.publicKey),
+      iRecipientLamports + relayAmount,
+      "Recipient lamports should be increased by the relay amount"
+    );
+    assertSE((await getAccount(connection, relayerWsolTA)).amount, relayAmount, "Relayer wSOL should be reduced");
+    assert.isNull(await connection.getAccountInfo(nativeUnwrapAccount), "Native unwrap account should be closed");
+  });
+
+  it("Forwards fill events to the event forwarder", async () => {
+    // The test program records the events received through its forward_event instruction in its logs.
+    const forwarderProgram = anchor.workspace.Test as Program<Test>;
+
+    const setEventForwarder = async (eventForwarder: PublicKey | null, bestEffort: boolean) => {
+      await program.methods
+        .setEventForwarder(eventForwarder, bestEffort)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+    };
+    const fillNewDeposit = async (eventForwarder: PublicKey) => {
+      updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const fillAccounts = { ...accounts, eventForwarder };
+      return await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], fillAccounts);
+    };
+    const getForwardedEvents = async (tx: string) => {
+      const txResult = await connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const eventParser = new EventParser(forwarderProgram.programId, new BorshCoder(forwarderProgram.idl));
+      const events = Array.from(eventParser.parseLogs(txResult?.meta?.logMessages ?? []));
+      return events.filter((event) => event.name === "forwardedEvent").map((event) => Buffer.from(event.data.event));
+    };
+
+    await setEventForwarder(forwarderProgram.programId, false);
+    let stateAccount = await program.account.state.fetch(state);
+    assertSE(stateAccount.eventForwarder, forwarderProgram.programId, "Event forwarder should be set");
+
+    // The forwarder receives the same discriminator prefixed event data as emitted through emit_cpi!.
+    const tx = await fillNewDeposit(forwarderProgram.programId);
+    const forwardedEvents = await getForwardedEvents(tx);
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay");
+    assert.strictEqual(forwardedEvents.length, 1, "Fill event should be forwarded once");
+    assert.isTrue(forwardedEvents[0].equals(event.rawData), "Forwarded event should match the emitted event");
+    const forwardedEvent = program.coder.events.decode(forwardedEvents[0].toString("base64"));
+    assert.strictEqual(forwardedEvent?.name, "filledV3Relay", "Forwarded event should decode as the fill event");
+    assertSE(forwardedEvent?.data.depositId, relayData.depositId, "Forwarded deposit id should match");
+
+    // Without best effort the fill fails when the forwarder account is missing or invalid.
+    try {
+      await fillNewDeposit(program.programId);
+      assert.fail("Fill should have failed without the event forwarder");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingEventForwarder", "Expected MissingEventForwarder error");
+    }
+    const nonExecutableForwarder = Keypair.generate().publicKey;
+    await setEventForwarder(nonExecutableForwarder, false);
+    try {
+      await fillNewDeposit(nonExecutableForwarder);
+      assert.fail("Fill should have failed for a non-executable event forwarder");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidEventForwarder", "Expected InvalidEventForwarder error");
+    }
+
+    // Best effort forwarding skips the invalid forwarder without failing the fill.
+    await setEventForwarder(nonExecutableForwarder, true);
+    const bestEffortTx = await fillNewDeposit(nonExecutableForwarder);
+    assert.strictEqual((await getForwardedEvents(bestEffortTx)).length, 0, "Fill event should not be forwarded");
+    assertSE((await getAccount(connection, recipientTA)).amount, 2 * relayAmount, "Recipient should be paid");
+
+    // Cleared forwarder no longer requires the forwarder account.
+    await setEventForwarder(null, false);
+    stateAccount = await program.account.state.fetch(state);
+    assert.isNull(stateAccount.eventForwarder, "Event forwarder should be cleared");
+    const clearedTx = await fillNewDeposit(program.programId);
+    assert.strictEqual((await getForwardedEvents(clearedTx)).length, 0, "Fill event should not be forwarded");
+  });
+
+  it("Scales the fill output amount by the decimal shift of the route", async () => {
+    // Route of the output token towards the origin chain scales amounts down by one decimal.
+    const route = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("route"),
+        mint.toBytes(),
+        seed.toArrayLike(Buffer, "le", 8),
+        relayData.originChainId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+    const setEnableRouteAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      route,
+      vault: getAssociatedTokenAddressSync(mint, getVaultAuthority(state), true),
+      originTokenMint: mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
+      program: program.programId,
+    };
+    try {
+      await program.methods
+        .setEnableRoute(mint, relayData.originChainId, true, 0, 19)
+        .accounts(setEnableRouteAccounts)
+        .rpc();
+      assert.fail("Enabling a route with an out of range shift should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidDecimalShift", "Expected InvalidDecimalShift error");
+    }
+    await program.methods
+      .setEnableRoute(mint, relayData.originChainId, true, 0, -1)
+      .accounts(setEnableRouteAccounts)
+      .rpc();
+    assert.strictEqual((await program.account.route.fetch(route)).decimalShift, -1, "Decimal shift should be set");
+
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const tx = await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], { ...accounts, route });
+
+    const normalizedAmount = relayAmount / 10;
+    assertSE((await getAccount(connection, recipientTA)).amount, normalizedAmount, "Recipient should get scaled");
+    assertSE(
+      (await getAccount(connection, relayerTA)).amount,
+      seedBalance - normalizedAmount,
+      "Relayer should pay scaled amount"
+    );
+
+    // Events carry both the raw and the normalized output amounts.
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assertSE(event.outputAmount, relayAmount, "Raw output amount should match");
+    assertSE(event.relayExecutionInfo.updatedOutputAmount, relayAmount, "Raw updated output amount should match");
+    assertSE(event.relayExecutionInfo.normalizedOutputAmount, normalizedAmount, "Normalized amount should match");
+  });
+
+  it("Verifies the route of the fill by its stored bump", async () => {
+    // Pick a route whose canonical bump is far from 255, as searching for it would cost ~1.5k CU per skipped bump.
+    const findRoute = (originChainId: BN) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("route"),
+          mint.toBytes(),
+          seed.toArrayLike(Buffer, "le", 8),
+          originChainId.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+    let originChainId = new BN(1);
+    while (findRoute(originChainId)[1] > 250) originChainId = originChainId.addn(1);
+    const [route, bump] = findRoute(originChainId);
+    await program.methods
+      .setEnableRoute(mint, originChainId, true, 0, 0)
+      .accounts({
+        signer: owner,
+        payer: owner,
+        state,
+        route,
+        vault: getAssociatedTokenAddressSync(mint, getVaultAuthority(state), true),
+        originTokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        lookupTableConfig: program.programId,
+        lookupTable: program.programId,
+        addressLookupTableProgram: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+    assert.strictEqual((await program.account.route.fetch(route)).bump, bump, "Canonical route bump should be stored");
+
+    const fillComputeUnits = async (withRoute: boolean) => {
+      updateRelayData({ ...relayData, originChainId, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const fillAccounts = withRoute ? { ...accounts, route } : accounts;
+      const tx = await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey], fillAccounts);
+      const txResult = await connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      if (txResult === null || txResult.meta === null) throw new Error("Transaction meta not confirmed");
+      if (txResult.meta.computeUnitsConsumed === undefined) throw new Error("Compute units not found");
+      return txResult.meta.computeUnitsConsumed;
+    };
+
+    // Verifying the route costs a single PDA derivation instead of a search over all the skipped bumps.
+    const routeOverhead = (await fillComputeUnits(true)) - (await fillComputeUnits(false));
+    assert.isBelow(routeOverhead, (255 - bump) * 1500, "Route verification should not search for the bump");
+  });
+
+  it("Rejects fast fills below the relayer fee floor of the route unless the low fee is accepted", async () => {
+    const route = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("route"),
+        mint.toBytes(),
+        seed.toArrayLike(Buffer, "le", 8),
+        relayData.originChainId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+    await program.methods
+      .setEnableRoute(mint, relayData.originChainId, true, 0, 0)
+      .accounts({
+        signer: owner,
+        payer: owner,
+        state,
+        route,
+        vault: getAssociatedTokenAddressSync(mint, getVaultAuthority(state), true),
+        originTokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        lookupTableConfig: program.programId,
+        lookupTable: program.programId,
+        addressLookupTableProgram: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+    const setMinRelayerFee = (minRelayerFeeBps: number) =>
+      program.methods
+        .setRouteMinRelayerFee(mint, relayData.originChainId, minRelayerFeeBps)
+        .accounts({ signer: owner, state, route, program: program.programId })
+        .rpc();
+    try {
+      await setMinRelayerFee(10_001);
+      assert.fail("Setting a fee floor above 10000 bps should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidRelayerFeeFloor", "Expected InvalidRelayerFeeFloor error");
+    }
+    await setMinRelayerFee(10);
+    assert.strictEqual((await program.account.route.fetch(route)).minRelayerFeeBps, 10, "Fee floor should be set");
+
+    // Relay data has equal input and output amounts, so its implied fee is zero.
+    const fillWithRoute = async (
+      outputAmount: BN,
+      fillAccounts: FillAccounts,
+      argsVersion = 0,
+      extra = Buffer.alloc(0)
+    ) => {
+      updateRelayData({ ...relayData, outputAmount, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const approveIx = await createApproveCheckedInstruction(
+        relayerTA,
+        mint,
+        state,
+        relayer.publicKey,
+        BigInt(outputAmount.toString()),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      const fillIx = await program.methods
+        .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, argsVersion, extra)
+        .accounts(fillAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      return await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [payer, relayer]);
+    };
+    try {
+      await fillWithRoute(new BN(relayAmount), { ...accounts, route });
+      assert.fail("Fill below the fee floor should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "RelayerFeeBelowFloor", "Expected RelayerFeeBelowFloor error");
+    }
+
+    // Relayers can explicitly accept the low fee.
+    await fillWithRoute(new BN(relayAmount), { ...accounts, route }, 4, encodeFillExtraArgsV4(null, false, null, true));
+
+    // Fee at the floor passes, and fills without the route skip the check as they carry no floor.
+    await fillWithRoute(new BN(relayAmount - relayAmount / 1000), { ...accounts, route });
+    await fillWithRoute(new BN(relayAmount), accounts);
+  });
+
+  it("Creates the repayment token account so that the refund leaf pays the relayer directly", async () => {
+    // Repayment address on this chain without an ATA for the output token.
+    const repaymentAddress = Keypair.generate().publicKey;
+    const repaymentTA = getAssociatedTokenAddressSync(mint, repaymentAddress);
+
+    const fillWithExtraArgs = async (argsVersion: number, extra: Buffer, repaymentChainId: BN) => {
+      updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const approveIx = await createApproveCheckedInstruction(
+        relayerTA,
+        mint,
+        state,
+        relayer.publicKey,
+        BigInt(relayAmount),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      const fillIx = await program.methods
+        .fillV3Relay(relayHash, relayData, repaymentChainId, repaymentAddress, argsVersion, extra)
+        .accounts({ ...accounts, repaymentAddressAccount: repaymentAddress, repaymentTokenAccount: repaymentTA })
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      const tx = await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [
+        payer,
+        relayer,
+      ]);
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      return events.find((event) => event.name === "filledV3Relay")?.data;
+    };
+
+    // Fill without the flag does not create the repayment token account.
+    let event = await fillWithExtraArgs(0, Buffer.alloc(0), chainId);
+    assert.isFalse(event.relayExecutionInfo.repaymentTokenAccountCreated, "Repayment TA should not be created");
+    assert.isNull(await connection.getAccountInfo(repaymentTA), "Repayment TA should not exist");
+
+    // The flag is rejected when the relayer takes repayment on another chain.
+    try {
+      await fillWithExtraArgs(2, encodeFillExtraArgsV2(null, true), new BN(1));
+      assert.fail("Creating the repayment token account for a foreign repayment chain should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidChainId", "Expected InvalidChainId error");
+    }
+
+    // Fill with the flag creates the repayment token account, and only reports creation when it did not exist.
+    event = await fillWithExtraArgs(2, encodeFillExtraArgsV2(null, true), chainId);
+    assert.isTrue(event.relayExecutionInfo.repaymentTokenAccountCreated, "Repayment TA should be created");
+    assertSE((await getAccount(connection, repaymentTA)).owner, repaymentAddress, "Repayment TA owner should match");
+    event = await fillWithExtraArgs(2, encodeFillExtraArgsV2(null, true), chainId);
+    assert.isFalse(event.relayExecutionInfo.repaymentTokenAccountCreated, "Existing repayment TA is not created");
+
+    // Refund leaf for the repayment address can now be executed with direct payment.
+    const vault = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)
+    ).address;
+    await mintTo(connection, payer, mint, vault, owner, relayAmount);
+    const relayerRefundLeaves: RelayerRefundLeafType[] = [
+      {
+        isSolana: true,
+        leafId: new BN(0),
+        chainId,
+        amountToReturn: new BN(0),
+        mintPublicKey: mint,
+        refundAddresses: [repaymentAddress],
+        refundAmounts: [new BN(relayAmount)],
+      },
+    ];
+    const merkleTree = new MerkleTree<RelayerRefundLeafType>(relayerRefundLeaves, relayerRefundHashFn);
+    const root = merkleTree.getRoot();
+    const proof = merkleTree.getProof(relayerRefundLeaves[0]).map((p) => Array.from(p));
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      rootBundleId,
+      relayerRefundLeaves[0] as RelayerRefundLeafSolana,
+      proof
+    );
+    const tx = await program.methods
+      .executeRelayerRefundLeaf()
+      .accounts({
+        signer: owner,
+        state,
+        rootBundle,
+        vault,
+        mint,
+        transferLiability: PublicKey.findProgramAddressSync(
+          [Buffer.from("transfer_liability"), mint.toBuffer()],
+          program.programId
+        )[0],
+        errorCounters: program.programId,
+        bundleAudit: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        claimLiability: createClaimLiabilityPda(mint, seed),
+        fillVolume: createFillVolumePda(mint, seed),
+        executionApproval: program.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: web3.SystemProgram.programId,
+        program: program.programId,
+      })
+      .remainingAccounts([{ pubkey: repaymentTA, isWritable: true, isSigner: false }])
+      .rpc();
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const refundEvent = events.find((event) => event.name === "executedRelayerRefundRoot")?.data;
+    assert.isFalse(refundEvent.deferredRefunds, "Refunds should not be deferred");
+    assertSE((await getAccount(connection, repaymentTA)).amount, relayAmount, "Relayer should be refunded directly");
+  });
+
+  it("Emits the origin transaction reference provided by the relayer", async () => {
+    const fillWithExtraArgs = async (argsVersion: number, extra: Buffer) => {
+      updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const approveIx = await createApproveCheckedInstruction(
+        relayerTA,
+        mint,
+        state,
+        relayer.publicKey,
+        BigInt(relayAmount),
+        tokenDecimals,
+        undefined,
+        tokenProgram
+      );
+      const fillIx = await program.methods
+        .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, argsVersion, extra)
+        .accounts(accounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      const tx = await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [
+        payer,
+        relayer,
+      ]);
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      return events.find((event) => event.name === "filledV3Relay")?.data;
+    };
+
+    // The reference is emitted as is, without being hashed.
+    const originTxRef = Buffer.alloc(32, 0xab);
+    let event = await fillWithExtraArgs(3, encodeFillExtraArgsV3(null, false, originTxRef));
+    assertSE(event.relayExecutionInfo.originTxRef, Array.from(originTxRef), "Origin tx ref should match");
+
+    // Omitted and all-zero references are both emitted as absent.
+    event = await fillWithExtraArgs(0, Buffer.alloc(0));
+    assert.isNull(event.relayExecutionInfo.originTxRef, "Origin tx ref should be absent without extra args");
+    event = await fillWithExtraArgs(3, encodeFillExtraArgsV3(null, false, null));
+    assert.isNull(event.relayExecutionInfo.originTxRef, "Origin tx ref should be absent when not provided");
+    event = await fillWithExtraArgs(3, encodeFillExtraArgsV3(null, false, Buffer.alloc(32)));
+    assert.isNull(event.relayExecutionInfo.originTxRef, "All-zero origin tx ref should be absent");
+
+    // References of any other length don't decode.
+    try {
+      await fillWithExtraArgs(3, encodeFillExtraArgsV3(null, false, Buffer.alloc(31, 0xab)));
+      assert.fail("Fill should have failed for a truncated origin tx ref");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidExtraArgs", "Expected InvalidExtraArgs error");
+    }
+  });
+
+  it("Credits fills to the fill volume of mints opted into refund checks", async () => {
+    const fillVolume = createFillVolumePda(mint, seed);
+    const fillNewRelay = async () => {
+      updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+    };
+
+    // Fills of mints that are not opted in are not tracked.
+    await fillNewRelay();
+    assert.isNull(await connection.getAccountInfo(fillVolume), "Fill volume should not be created by fills");
+
+    await program.methods
+      .setFillVolumeTolerance(mint, new BN(0))
+      .accounts({ signer: owner, state, fillVolume, program: program.programId })
+      .rpc();
+    await fillNewRelay();
+    await fillNewRelay();
+    const fillVolumeData = await program.account.fillVolume.fetch(fillVolume);
+    assertSE(fillVolumeData.fillVolume, 2 * relayAmount, "Fill volume should include fills after opting in");
+    assert.isNull(fillVolumeData.rootBundleId, "No window should be closed before the first refund leaf");
+  });
+
+  describe("Fills with updated deposit", () => {
+    const solanaDepositor = Keypair.generate();
+    const evmDepositor = ethers.Wallet.createRandom();
+    const updatedRecipient = Keypair.generate().publicKey;
+    const updatedOutputAmount = new BN(relayAmount - 1000);
+    let updatedRecipientTA: PublicKey;
+
+    type Signer = { ed25519?: Keypair; evm?: ethers.Wallet };
+
+    const signUpdatedDeposit = (signer: Signer, signedChainId: BN = chainId, outputAmount = updatedOutputAmount) => {
+      const relayHash = calculateRelayHashUint8Array(relayData, chainId);
+      const updatedDepositHash = calculateUpdatedDepositHashUint8Array(
+        signedChainId,
+        relayHash,
+        outputAmount,
+        updatedRecipient,
+        Buffer.alloc(0)
+      );
+      if (signer.evm !== undefined) {
+        const signature = ethers.utils.joinSignature(signer.evm._signingKey().signDigest(updatedDepositHash));
+        return { signature: Buffer.from(ethers.utils.arrayify(signature)), preInstructions: [] };
+      }
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.ed25519!.secretKey,
+        message: updatedDepositHash,
+      });
+      // Ed25519Program places the signature after 16 bytes of offsets and the 32 bytes public key.
+      return { signature: ed25519Ix.data.subarray(48, 112), preInstructions: [ed25519Ix] };
+    };
+
+    const getUsedSignaturePda = (signature: Uint8Array) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("used_signature"), seed.toArrayLike(Buffer, "le", 8), calculateUsedSignatureKey(signature)],
+        program.programId
+      )[0];
+
+    const fillWithUpdatedDeposit = async (
+      signer: Signer,
+      signedChainId: BN = chainId,
+      outputAmount = updatedOutputAmount,
+      complianceAccounts = {}
+    ) => {
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const { signature, preInstructions } = signUpdatedDeposit(signer, signedChainId, outputAmount);
+      const approveIx = await createApproveCheckedInstruction(
+        relayerTA,
+        mint,
+        state,
+        relayer.publicKey,
+        BigInt(outputAmount.toString()),
+        tokenDecimals
+      );
+      const fillIx = await program.methods
+        .fillV3RelayWithUpdatedDeposit(
+          relayHash,
+          relayData,
+          new BN(1),
+          relayer.publicKey,
+          outputAmount,
+          updatedRecipient,
+          Buffer.alloc(0),
+          signature
+        )
+        .accounts({
+          signer: relayer.publicKey,
+          state,
+          vaultRegistry: createVaultRegistryPda(mint, seed),
+          fillVolume: createFillVolumePda(mint, seed),
+          mint,
+          relayerTokenAccount: relayerTA,
+          recipientTokenAccount: updatedRecipientTA,
+          fillStatus: accounts.fillStatus,
+          usedSignature: getUsedSignaturePda(signature),
+          messageSchema: createMessageSchemaPda(updatedRecipient),
+          depositorCompliance: program.programId,
+          recipientCompliance: program.programId,
+          allowlistEntry: program.programId,
+          route: program.programId,
+          eventCommitment: program.programId,
+          fillIndexShard: program.programId,
+          originChainAlias: program.programId,
+          relayerStats: program.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: relayer.publicKey,
+          originalRecipientCompliance: program.programId,
+          program: program.programId,
+          ...complianceAccounts,
+        })
+        .instruction();
+      const fillTx = new Transaction().add(...preInstructions, approveIx, fillIx);
+      return await sendAndConfirmTransaction(connection, fillTx, [payer, relayer]);
+    };
+
+    beforeEach(async () => {
+      updatedRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, updatedRecipient)).address;
+    });
+
+    it("Fills with values updated by a Solana depositor", async () => {
+      updateRelayData({ ...relayData, depositor: solanaDepositor.publicKey });
+
+      const tx = await fillWithUpdatedDeposit({ ed25519: solanaDepositor });
+
+      assertSE(
+        (await getAccount(connection, updatedRecipientTA)).amount,
+        updatedOutputAmount,
+        "Updated recipient should receive the updated output amount"
+      );
+      assertSE((await getAccount(connection, recipientTA)).amount, 0, "Original recipient should not receive funds");
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(accounts.fillStatus);
+      assert.equal(JSON.stringify(fillStatusAccount.status), `{"filled":{}}`, "Original relay should be filled");
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "filledV3Relay")?.data;
+      assertSE(event.outputAmount, relayAmount, "Original output amount should match");
+      assertSE(event.recipient, recipient, "Original recipient should match");
+      assertSE(event.relayExecutionInfo.updatedOutputAmount, updatedOutputAmount, "Updated output amount should match");
+      assertSE(event.relayExecutionInfo.updatedRecipient, updatedRecipient, "Updated recipient should match");
+      assertSE(event.relayExecutionInfo.updatedMessageHash, new Uint8Array(32), "Updated message hash should match");
+      assert.equal(JSON.stringify(event.relayExecutionInfo.fillType), `{"fastFill":{}}`, "FillType should be FastFill");
+
+      // Original relay data cannot be filled anymore.
+      try {
+        const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+        await approvedFillV3Relay([relayHash, relayData, new BN(1), relayer.publicKey]);
+        assert.fail("Fill of the original relay data should have failed");
+      } catch (err: any) {
+        assert.include(err.toString(), "RelayFilled", "Expected RelayFilled error");
+      }
+    });
+
+    it("Fills with values updated by an EVM depositor", async () => {
+      updateRelayData({ ...relayData, depositor: evmAddressToPublicKey(evmDepositor.address) });
+
+      await fillWithUpdatedDeposit({ evm: evmDepositor });
+
+      assertSE(
+        (await getAccount(connection, updatedRecipientTA)).amount,
+        updatedOutputAmount,
+        "Updated recipient should receive the updated output amount"
+      );
+    });
+
+    it("Rejects updates not signed by the depositor or signed for another chain", async () => {
+      updateRelayData({ ...relayData, depositor: evmAddressToPublicKey(evmDepositor.address) });
+
+      const invalidSigners: [Signer, BN][] = [
+        [{ evm: ethers.Wallet.createRandom() }, chainId],
+        [{ evm: evmDepositor }, chainId.addn(1)],
+        [{ ed25519: solanaDepositor }, chainId],
+      ];
+      for (const [signer, signedChainId] of invalidSigners) {
+        try {
+          await fillWithUpdatedDeposit(signer, signedChainId);
+          assert.fail("Fill with invalid depositor signature should have failed");
+        } catch (err: any) {
+          assert.include(err.toString(), "InvalidDepositorSignature", "Expected InvalidDepositorSignature error");
+        }
+      }
+    });
+
+    // Fills the updated deposit once the blocked recipient is removed from the blocklist, after checking that it
+    // blocks the fill. Ed25519 signatures are deterministic, so the same signed update is retried.
+    const fillWithBlockedRecipient = async (blockedRecipient: PublicKey) => {
+      updateRelayData({ ...relayData, depositor: solanaDepositor.publicKey });
+      const getCompliancePda = (subject: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("compliance"), seed.toArrayLike(Buffer, "le", 8), subject.toBuffer()],
+          program.programId
+        )[0];
+      const complianceAccounts = {
+        depositorCompliance: getCompliancePda(relayData.depositor),
+        recipientCompliance: getCompliancePda(updatedRecipient),
+        originalRecipientCompliance: getCompliancePda(relayData.recipient),
+      };
+      const entryAccounts = {
+        signer: owner,
+        state,
+        complianceAttestation: getCompliancePda(blockedRecipient),
+        program: program.programId,
+      };
+      await program.methods
+        .setComplianceAuthority(owner)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+
+      await program.methods.addComplianceEntry(blockedRecipient).accounts(entryAccounts).rpc();
+      try {
+        await fillWithUpdatedDeposit({ ed25519: solanaDepositor }, chainId, updatedOutputAmount, complianceAccounts);
+        assert.fail("Fill with updated deposit should have failed due to blocked recipient");
+      } catch (err: any) {
+        assert.include(err.toString(), "ComplianceBlocked", "Expected ComplianceBlocked error");
+      }
+
+      await program.methods.removeComplianceEntry(blockedRecipient).accounts(entryAccounts).rpc();
+      await fillWithUpdatedDeposit({ ed25519: solanaDepositor }, chainId, updatedOutputAmount, complianceAccounts);
+      assertSE(
+        (await getAccount(connection, updatedRecipientTA)).amount,
+        updatedOutputAmount,
+        "Updated recipient should receive the updated output amount"
+      );
+
+      await program.methods
+        .setComplianceAuthority(null)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+    };
+
+    it("Blocks updated fills of a blocklisted original recipient", async () => {
+      await fillWithBlockedRecipient(relayData.recipient);
+    });
+
+    it("Blocks updated fills to a blocklisted updated recipient", async () => {
+      await fillWithBlockedRecipient(updatedRecipient);
+    });
+
+    it("Rejects updates to a zero output amount without a message", async () => {
+      updateRelayData({ ...relayData, depositor: solanaDepositor.publicKey });
+
+      try {
+        await fillWithUpdatedDeposit({ ed25519: solanaDepositor }, chainId, new BN(0));
+        assert.fail("Fill with an empty updated relay should have failed");
+      } catch (err: any) {
+        assert.include(err.toString(), "EmptyRelay", "Expected EmptyRelay error");
+      }
+    });
+
+    it("Rejects updates after the fill deadline", async () => {
+      updateRelayData({
+        ...relayData,
+        depositor: solanaDepositor.publicKey,
+        fillDeadline: Math.floor(Date.now() / 1000) - 69,
+      });
+
+      try {
+        await fillWithUpdatedDeposit({ ed25519: solanaDepositor });
+        assert.fail("Fill with updated deposit should have failed due to fill deadline passed");
+      } catch (err: any) {
+        assert.include(err.toString(), "ExpiredFillDeadline", "Expected ExpiredFillDeadline error");
+      }
+    });
+
+    it("Rejects replays of consumed depositor signatures", async () => {
+      updateRelayData({ ...relayData, depositor: solanaDepositor.publicKey });
+      await fillWithUpdatedDeposit({ ed25519: solanaDepositor });
+
+      // Ed25519 signatures are deterministic, so the same signed update consumes the same used signature PDA.
+      try {
+        await fillWithUpdatedDeposit({ ed25519: solanaDepositor });
+        assert.fail("Replayed depositor signature should have been rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "SignatureAlreadyUsed", "Expected SignatureAlreadyUsed error");
+      }
+
+      const usedSignature = getUsedSignaturePda(signUpdatedDeposit({ ed25519: solanaDepositor }).signature);
+      const usedSignatureAccount = await program.account.usedSignature.fetch(usedSignature);
+      assertSE(usedSignatureAccount.expiresAt, relayData.fillDeadline, "Signature should expire at the fill deadline");
+      assertSE(usedSignatureAccount.payer, relayer.publicKey, "Relayer should be the rent payer");
+
+      const closeUsedSignature = async () => {
+        await program.methods
+          .closeUsedSignature()
+          .accounts({ signer: otherRelayer.publicKey, state, usedSignature, payer: relayer.publicKey })
+          .signers([otherRelayer])
+          .rpc();
+      };
+
+      // The used signature PDA is kept until the signed update expires.
+      try {
+        await closeUsedSignature();
+        assert.fail("Used signature should not be closed before expiry");
+      } catch (err: any) {
+        assert.include(
+          err.toString(),
+          "CanOnlyCloseUsedSignatureAfterExpiry",
+          "Expected CanOnlyCloseUsedSignatureAfterExpiry error"
+        );
+      }
+
+      await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+      const iRelayerLamports = await connection.getBalance(relayer.publicKey);
+      await closeUsedSignature();
+      assert.isNull(await connection.getAccountInfo(usedSignature), "Used signature PDA should be closed");
+      assert.isAbove(
+        await connection.getBalance(relayer.publicKey),
+        iRelayerLamports,
+        "Rent should be returned to the payer"
+      );
+
+      // Reusing the signature after closure is rejected as the signed update has expired.
+      try {
+        await fillWithUpdatedDeposit({ ed25519: solanaDepositor });
+        assert.fail("Expired depositor signature should have been rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "ExpiredFillDeadline", "Expected ExpiredFillDeadline error");
+      }
+    });
+  });
 });
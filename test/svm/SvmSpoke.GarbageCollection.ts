@@ -0,0 +1,202 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, createMint } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { readEventsUntilFound } from "../../src/svm";
+import { common } from "./SvmSpoke.common";
+
+const { provider, connection, program, owner, initializeState, createRoutePda, getVaultAta } = common;
+const { setCurrentTime, getCurrentTime, assert, assertSE } = common;
+
+describe("svm_spoke.garbage_collection", () => {
+  anchor.setProvider(provider);
+  const payer = (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer;
+  const caller = Keypair.generate();
+  const day = 86_400;
+
+  let state: PublicKey, seed: BN;
+
+  const gcAccount = async (kind: any, target: PublicKey, originalPayer: PublicKey = program.programId) => {
+    return await program.methods
+      .gcAccount(kind)
+      .accounts({ signer: caller.publicKey, state, target, originalPayer, program: program.programId })
+      .signers([caller])
+      .rpc();
+  };
+
+  const assertNotCollectable = async (kind: any, target: PublicKey, originalPayer?: PublicKey) => {
+    try {
+      await gcAccount(kind, target, originalPayer);
+      assert.fail("Closing a fresh account should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "AccountNotCollectable", "Expected AccountNotCollectable error");
+    }
+  };
+
+  const assertCollected = async (tx: string, kind: string, target: PublicKey) => {
+    assert.isNull(await connection.getAccountInfo(target), "Account should be closed");
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "garbageCollectedAccount")?.data;
+    assert.include(Object.keys(event.kind), kind, "Event should name the account kind");
+    assertSE(event.account, target, "Event should name the account key");
+    assertSE(event.caller, caller.publicKey, "Event should name the caller");
+    return event;
+  };
+
+  before(async () => {
+    await connection.requestAirdrop(caller.publicKey, 1_000_000_000); // 1 SOL
+  });
+
+  beforeEach(async () => {
+    ({ state, seed } = await initializeState());
+  });
+
+  const getSetEnableRouteAccounts = async (originToken: PublicKey, route: PublicKey) => {
+    return {
+      signer: owner,
+      payer: owner,
+      state,
+      route,
+      vault: await getVaultAta(originToken, state),
+      originTokenMint: originToken,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
+      program: program.programId,
+    };
+  };
+
+  it("Closes routes disabled for long enough", async () => {
+    // Zero decimals of the mint match the unset expected decimals of routes.
+    const originToken = await createMint(connection, payer, owner, owner, 0);
+    const destinationChainId = new BN(1);
+    const route = createRoutePda(originToken, seed, destinationChainId);
+    const setEnableRouteAccounts = await getSetEnableRouteAccounts(originToken, route);
+    const kind = { route: { originToken, destinationChainId } };
+
+    // Enabled routes are never collectable.
+    await program.methods
+      .setEnableRoute(originToken, destinationChainId, true, 0, 0)
+      .accounts(setEnableRouteAccounts)
+      .rpc();
+    await assertNotCollectable(kind, route);
+
+    // Disabled routes are only collectable after the disabled period.
+    await program.methods
+      .setEnableRoute(originToken, destinationChainId, false, 0, 0)
+      .accounts(setEnableRouteAccounts)
+      .rpc();
+    const disabledAt = (await program.account.route.fetch(route)).disabledAt;
+    assertSE(disabledAt, await getCurrentTime(program, state), "Disable time should be recorded");
+    await setCurrentTime(program, state, new BN(disabledAt + 30 * day - 1));
+    await assertNotCollectable(kind, route);
+
+    // Without a recorded payer the caller receives all of the rent.
+    await setCurrentTime(program, state, new BN(disabledAt + 30 * day));
+    const rent = (await connection.getAccountInfo(route))!.lamports;
+    const event = await assertCollected(await gcAccount(kind, route), "route", route);
+    assertSE(event.callerRent, rent, "Caller should receive all of the rent");
+    assert.isNull(event.originalPayer, "Original payer should not be recorded");
+  });
+
+  it("Keeps disabled routes that still configure fills", async () => {
+    const destinationChainId = new BN(1);
+    const disableRoute = async (originToken: PublicKey, route: PublicKey) => {
+      const setEnableRouteAccounts = await getSetEnableRouteAccounts(originToken, route);
+      for (const enabled of [true, false]) {
+        await program.methods
+          .setEnableRoute(originToken, destinationChainId, enabled, 0, 0)
+          .accounts(setEnableRouteAccounts)
+          .rpc();
+      }
+    };
+
+    // Fills passing the route check the mint decimals it recorded.
+    const decimalsToken = await createMint(connection, payer, owner, owner, 6);
+    const decimalsRoute = createRoutePda(decimalsToken, seed, destinationChainId);
+    await disableRoute(decimalsToken, decimalsRoute);
+
+    // Fills check their implied fee against the fee floor of the route.
+    const feeFloorToken = await createMint(connection, payer, owner, owner, 0);
+    const feeFloorRoute = createRoutePda(feeFloorToken, seed, destinationChainId);
+    await disableRoute(feeFloorToken, feeFloorRoute);
+    await program.methods
+      .setRouteMinRelayerFee(feeFloorToken, destinationChainId, 10)
+      .accounts({ signer: owner, state, route: feeFloorRoute, program: program.programId })
+      .rpc();
+
+    const disabledAt = (await program.account.route.fetch(decimalsRoute)).disabledAt;
+    await setCurrentTime(program, state, new BN(disabledAt + 30 * day));
+    await assertNotCollectable({ route: { originToken: decimalsToken, destinationChainId } }, decimalsRoute);
+    await assertNotCollectable({ route: { originToken: feeFloorToken, destinationChainId } }, feeFloorRoute);
+  });
+
+  it("Closes requester activities past their retention", async () => {
+    const requester = Keypair.generate();
+    await connection.requestAirdrop(requester.publicKey, 1_000_000_000); // 1 SOL
+    const [requesterActivity] = PublicKey.findProgramAddressSync(
+      [Buffer.from("requester_activity"), seed.toArrayLike(Buffer, "le", 8), requester.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeRequesterActivity()
+      .accounts({ signer: requester.publicKey, state, requesterActivity })
+      .signers([requester])
+      .rpc();
+    const kind = { requesterActivity: { requester: requester.publicKey } };
+
+    // The fresh activity window starts at zero, so retention is only over 7 days after it.
+    await setCurrentTime(program, state, new BN(7 * day - 1));
+    await assertNotCollectable(kind, requesterActivity, requester.publicKey);
+    await setCurrentTime(program, state, new BN(7 * day));
+
+    // The rent is split with the requester that paid for the account.
+    try {
+      await gcAccount(kind, requesterActivity, caller.publicKey);
+      assert.fail("Closing to another original payer should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidOriginalPayer", "Expected InvalidOriginalPayer error");
+    }
+    const rent = (await connection.getAccountInfo(requesterActivity))!.lamports;
+    const requesterBalance = await connection.getBalance(requester.publicKey);
+    const tx = await gcAccount(kind, requesterActivity, requester.publicKey);
+    const event = await assertCollected(tx, "requesterActivity", requesterActivity);
+    const callerRent = Math.floor((rent * 1000) / 10_000);
+    assertSE(event.callerRent, callerRent, "Caller should receive the incentive share");
+    assertSE(event.originalPayer, requester.publicKey, "Original payer should be the requester");
+    assertSE(event.originalPayerRent, rent - callerRent, "Requester should receive the rest of the rent");
+    const requesterRefund = (await connection.getBalance(requester.publicKey)) - requesterBalance;
+    assertSE(requesterRefund, rent - callerRent, "Requester should be refunded the rest of the rent");
+  });
+
+  it("Closes event commitments past their retention", async () => {
+    const epoch = Math.floor((await getCurrentTime(program, state)) / day);
+    const epochBuffer = Buffer.alloc(4);
+    epochBuffer.writeUInt32LE(epoch);
+    const [eventCommitment] = PublicKey.findProgramAddressSync(
+      [Buffer.from("event_commitment"), seed.toArrayLike(Buffer, "le", 8), epochBuffer],
+      program.programId
+    );
+    await program.methods.initializeEventCommitment(epoch).accounts({ signer: owner, state, eventCommitment }).rpc();
+    const kind = { eventCommitment: { epoch } };
+
+    // Retention is counted from the end of the epoch.
+    const epochEnd = (epoch + 1) * day;
+    await setCurrentTime(program, state, new BN(epochEnd + 90 * day - 1));
+    await assertNotCollectable(kind, eventCommitment);
+
+    // Accounts not matching the kind are rejected.
+    try {
+      await gcAccount({ eventCommitment: { epoch: epoch + 1 } }, eventCommitment);
+      assert.fail("Closing an account not derived from the kind should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidGcAccount", "Expected InvalidGcAccount error");
+    }
+
+    await setCurrentTime(program, state, new BN(epochEnd + 90 * day));
+    await assertCollected(await gcAccount(kind, eventCommitment), "eventCommitment", eventCommitment);
+  });
+});
@@ -8,15 +8,18 @@ import { ethers } from "ethers";
 import { encodeMessageHeader, evmAddressToPublicKey } from "../../src/svm";
 import { MessageTransmitter } from "../../target/types/message_transmitter";
 import { SvmSpoke } from "../../target/types/svm_spoke";
+import { Test } from "../../target/types/test";
 import { common } from "./SvmSpoke.common";
 
 const { createRoutePda, getVaultAta, initializeState, crossDomainAdmin, remoteDomain, localDomain } = common;
+const { createDepositLimitPda } = common;
 
 describe("svm_spoke.handle_receive_message", () => {
   anchor.setProvider(AnchorProvider.env());
 
   const program = workspace.SvmSpoke as Program<SvmSpoke>;
   const messageTransmitterProgram = workspace.MessageTransmitter as Program<MessageTransmitter>;
+  const mockMessengerProgram = workspace.Test as Program<Test>;
   const provider = AnchorProvider.env();
   const owner = provider.wallet.publicKey;
   let state: web3.PublicKey;
@@ -40,6 +43,8 @@ describe("svm_spoke.handle_receive_message", () => {
     "function setEnableRoute(bytes32 originToken, uint64 destinationChainId, bool enabled)",
     "function relayRootBundle(bytes32 relayerRefundRoot, bytes32 slowRelayRoot)",
     "function emergencyDeleteRootBundle(uint256 rootBundleId)",
+    "function setConfig(bytes32 key, bytes32 value)",
+    "function setDepositLimit(bytes32 mint, uint64 destinationChainId, uint64 maxOutstanding)",
   ]);
 
   beforeEach(async () => {
@@ -374,6 +379,24 @@ describe("svm_spoke.handle_receive_message", () => {
       isWritable: false,
       pubkey: web3.SystemProgram.programId,
     });
+    // lookup_table_config in self-invoked SetEnableRoute, None when passing this program ID.
+    enableRouteRemainingAccounts.push({
+      isSigner: false,
+      isWritable: false,
+      pubkey: program.programId,
+    });
+    // lookup_table in self-invoked SetEnableRoute, None when passing this program ID.
+    enableRouteRemainingAccounts.push({
+      isSigner: false,
+      isWritable: false,
+      pubkey: program.programId,
+    });
+    // address_lookup_table_program in self-invoked SetEnableRoute, None when passing this program ID.
+    enableRouteRemainingAccounts.push({
+      isSigner: false,
+      isWritable: false,
+      pubkey: program.programId,
+    });
     // event_authority in self-invoked SetEnableRoute (appended by Anchor with event_cpi macro).
     enableRouteRemainingAccounts.push({
       isSigner: false,
@@ -417,6 +440,102 @@ describe("svm_spoke.handle_receive_message", () => {
 
     routeAccount = await program.account.route.fetch(routePda);
     assert.isFalse(routeAccount.enabled, "Route should be disabled");
+
+    // Re-enable the route with a negative decimal shift, encoded as a sign extended int8.
+    nonce += 1;
+    const decimalShiftIface = new ethers.utils.Interface(["function setEnableRoute(bytes32,uint64,bool,uint32,int8)"]);
+    const decimalShift = -10;
+    calldata = decimalShiftIface.encodeFunctionData("setEnableRoute", [
+      originToken.toBuffer(),
+      routeChainId,
+      true,
+      0,
+      decimalShift,
+    ]);
+    messageBody = Buffer.from(calldata.slice(2), "hex");
+    message = encodeMessageHeader({
+      version: cctpMessageversion,
+      sourceDomain: remoteDomain.toNumber(),
+      destinationDomain: localDomain,
+      nonce: BigInt(nonce),
+      sender: crossDomainAdmin,
+      recipient: program.programId,
+      destinationCaller,
+      messageBody,
+    });
+    await messageTransmitterProgram.methods
+      .receiveMessage({ message, attestation })
+      .accounts(receiveMessageAccounts)
+      .remainingAccounts(enableRouteRemainingAccounts)
+      .rpc();
+
+    routeAccount = await program.account.route.fetch(routePda);
+    assert.isTrue(routeAccount.enabled, "Route should be enabled");
+    assert.strictEqual(routeAccount.decimalShift, decimalShift, "Decimal shift should be set");
+  });
+
+  it("Sets deposit limit remotely", async () => {
+    const mint = Keypair.generate().publicKey;
+    const routeChainId = 1;
+    const maxOutstanding = new BN(1_000_000);
+    const calldata = ethereumIface.encodeFunctionData("setDepositLimit", [
+      mint.toBuffer(),
+      routeChainId,
+      maxOutstanding.toString(),
+    ]);
+    const messageBody = Buffer.from(calldata.slice(2), "hex");
+    const message = encodeMessageHeader({
+      version: cctpMessageversion,
+      sourceDomain: remoteDomain.toNumber(),
+      destinationDomain: localDomain,
+      nonce: BigInt(nonce),
+      sender: crossDomainAdmin,
+      recipient: program.programId,
+      destinationCaller,
+      messageBody,
+    });
+
+    // Same 3 remaining accounts passed for HandleReceiveMessage context.
+    const depositLimitRemainingAccounts = remainingAccounts.slice(0, 3);
+    // payer in self-invoked SetDepositLimit.
+    depositLimitRemainingAccounts.push({
+      isSigner: true,
+      isWritable: true,
+      pubkey: provider.wallet.publicKey,
+    });
+    // state in self-invoked SetDepositLimit.
+    depositLimitRemainingAccounts.push({
+      isSigner: false,
+      isWritable: false,
+      pubkey: state,
+    });
+    // deposit_limit in self-invoked SetDepositLimit.
+    const depositLimit = createDepositLimitPda(mint, seed, new BN(routeChainId));
+    depositLimitRemainingAccounts.push({
+      isSigner: false,
+      isWritable: true,
+      pubkey: depositLimit,
+    });
+    // system_program in self-invoked SetDepositLimit.
+    depositLimitRemainingAccounts.push({
+      isSigner: false,
+      isWritable: false,
+      pubkey: web3.SystemProgram.programId,
+    });
+    // event_authority and program in self-invoked SetDepositLimit (appended by Anchor with event_cpi macro).
+    depositLimitRemainingAccounts.push(...remainingAccounts.slice(4));
+    await messageTransmitterProgram.methods
+      .receiveMessage({ message, attestation })
+      .accounts(receiveMessageAccounts)
+      .remainingAccounts(depositLimitRemainingAccounts)
+      .rpc();
+
+    const depositLimitAccount = await program.account.depositLimit.fetch(depositLimit);
+    assert.strictEqual(
+      depositLimitAccount.maxOutstanding.toString(),
+      maxOutstanding.toString(),
+      "Deposit limit should be set"
+    );
   });
 
   it("Relays root bundle remotely", async () => {
@@ -468,6 +587,12 @@ describe("svm_spoke.handle_receive_message", () => {
       isWritable: false,
       pubkey: web3.SystemProgram.programId,
     });
+    // admin_nonce in self-invoked RelayRootBundle, None as remote messages do not pass idempotency keys.
+    relayRootBundleRemainingAccounts.push({
+      isSigner: false,
+      isWritable: false,
+      pubkey: program.programId,
+    });
     // event_authority in self-invoked RelayRootBundle (appended by Anchor with event_cpi macro).
     relayRootBundleRemainingAccounts.push({
       isSigner: false,
@@ -505,9 +630,16 @@ describe("svm_spoke.handle_receive_message", () => {
     rootBundleIdBuffer.writeUInt32LE(rootBundleId);
     const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
     const [rootBundle] = web3.PublicKey.findProgramAddressSync(seeds, program.programId);
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
     await program.methods
-      .relayRootBundle(Array.from(relayerRefundRoot), Array.from(slowRelayRoot))
+      .relayRootBundle(Array.from(relayerRefundRoot), Array.from(slowRelayRoot), 0, null, 0, null)
       .accounts(relayRootBundleAccounts)
       .rpc();
 
@@ -583,6 +715,73 @@ describe("svm_spoke.handle_receive_message", () => {
     }
   });
 
+  const receiveCalldata = async (calldata: string, calldataRemainingAccounts = remainingAccounts) => {
+    const message = encodeMessageHeader({
+      version: cctpMessageversion,
+      sourceDomain: remoteDomain.toNumber(),
+      destinationDomain: localDomain,
+      nonce: BigInt(nonce),
+      sender: crossDomainAdmin,
+      recipient: program.programId,
+      destinationCaller,
+      messageBody: Buffer.from(calldata.slice(2), "hex"),
+    });
+    await messageTransmitterProgram.methods
+      .receiveMessage({ message, attestation })
+      .accounts(receiveMessageAccounts)
+      .remainingAccounts(calldataRemainingAccounts)
+      .rpc();
+  };
+
+  it("Sets config remotely", async () => {
+    // HubPool encodes the key as a bytes32 string literal and the value as an ABI encoded word.
+    const newFillDeadlineBuffer = 7200;
+    const key = ethers.utils.formatBytes32String("fill_deadline_buffer");
+    const value = ethers.utils.hexZeroPad(ethers.utils.hexlify(newFillDeadlineBuffer), 32);
+    const calldata = ethereumIface.encodeFunctionData("setConfig", [key, value]);
+    assert.strictEqual(
+      calldata,
+      ethers.utils.id("setConfig(bytes32,bytes32)").slice(0, 10) + key.slice(2) + value.slice(2),
+      "Calldata should be the selector followed by the key and value words"
+    );
+
+    // admin_nonce (None as remote messages do not pass idempotency keys) and system_program in self-invoked SetConfig.
+    const setConfigRemainingAccounts = [
+      ...remainingAccounts.slice(0, 4),
+      { isSigner: false, isWritable: false, pubkey: program.programId },
+      { isSigner: false, isWritable: false, pubkey: web3.SystemProgram.programId },
+      ...remainingAccounts.slice(4),
+    ];
+    await receiveCalldata(calldata, setConfigRemainingAccounts);
+    const stateData = await program.account.state.fetch(state);
+    assert.strictEqual(stateData.fillDeadlineBuffer, newFillDeadlineBuffer, "Fill deadline buffer should be updated");
+
+    // Keys not mapped to a State field are rejected.
+    nonce += 1;
+    const unknownKey = ethers.utils.formatBytes32String("unknown_config_key");
+    try {
+      await receiveCalldata(
+        ethereumIface.encodeFunctionData("setConfig", [unknownKey, value]),
+        setConfigRemainingAccounts
+      );
+      assert.fail("Should not be able to set unknown config key");
+    } catch (error: any) {
+      assert.instanceOf(error, AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "UnknownConfigKey", "Expected error code UnknownConfigKey");
+    }
+  });
+
+  it("Rejects unknown admin actions", async () => {
+    const unknownIface = new ethers.utils.Interface(["function pauseEverything(bool pause)"]);
+    try {
+      await receiveCalldata(unknownIface.encodeFunctionData("pauseEverything", [true]));
+      assert.fail("Should not be able to receive unknown admin action");
+    } catch (error: any) {
+      assert.instanceOf(error, AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "UnknownAdminAction", "Expected error code UnknownAdminAction");
+    }
+  });
+
   it("Replaying an old message is not possible", async () => {
     // Pause fills.
     const calldata = ethereumIface.encodeFunctionData("pauseFills", [true]);
@@ -653,4 +852,92 @@ describe("svm_spoke.handle_receive_message", () => {
       assert.strictEqual(error.error.errorCode.code, "NonceAlreadyUsed", "Expected error code NonceAlreadyUsed");
     }
   });
+
+  it("Receives admin messages only from the configured admin messenger", async () => {
+    const setAdminMessengerAccounts = { state, signer: owner, program: program.programId };
+    const calldata = ethereumIface.encodeFunctionData("pauseDeposits", [true]);
+    const messageBody = Buffer.from(calldata.slice(2), "hex");
+    const [mockAuthorityPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("message_transmitter_authority"), program.programId.toBuffer()],
+      mockMessengerProgram.programId
+    );
+    const relayAdminMessage = () =>
+      mockMessengerProgram.methods
+        .relayAdminMessage(remoteDomain.toNumber(), crossDomainAdmin, messageBody)
+        .accounts({ authorityPda: mockAuthorityPda, receiver: program.programId })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+
+    // Messenger is not trusted before the owner sets it.
+    try {
+      await relayAdminMessage();
+      assert.fail("Should not be able to receive message from an unset admin messenger");
+    } catch (error: any) {
+      assert.include(error.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+    }
+
+    // Only the local owner can set the admin messenger.
+    const nonOwner = Keypair.generate();
+    try {
+      await program.methods
+        .setAdminMessenger(mockMessengerProgram.programId)
+        .accounts({ state, signer: nonOwner.publicKey, program: program.programId })
+        .signers([nonOwner])
+        .rpc();
+      assert.fail("Non-owner should not be able to set the admin messenger");
+    } catch (error: any) {
+      assert.include(error.toString(), "Only the owner can call this function!", "Expected owner check error");
+    }
+    await program.methods.setAdminMessenger(mockMessengerProgram.programId).accounts(setAdminMessengerAccounts).rpc();
+    let stateData = await program.account.state.fetch(state);
+    assert.isTrue(stateData.adminMessenger?.equals(mockMessengerProgram.programId), "Admin messenger should be set");
+
+    // Messages relayed by the admin messenger are still authenticated against the HubPool.
+    try {
+      await mockMessengerProgram.methods
+        .relayAdminMessage(remoteDomain.toNumber(), Keypair.generate().publicKey, messageBody)
+        .accounts({ authorityPda: mockAuthorityPda, receiver: program.programId })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+      assert.fail("Should not be able to receive message from unauthorized sender");
+    } catch (error: any) {
+      assert.include(error.toString(), "InvalidRemoteSender", "Expected InvalidRemoteSender error");
+    }
+    await relayAdminMessage();
+    stateData = await program.account.state.fetch(state);
+    assert.isTrue(stateData.pausedDeposits, "Deposits should be paused by the admin messenger");
+
+    // The Message Transmitter is no longer trusted while the admin messenger is set.
+    const message = encodeMessageHeader({
+      version: cctpMessageversion,
+      sourceDomain: remoteDomain.toNumber(),
+      destinationDomain: localDomain,
+      nonce: BigInt(nonce),
+      sender: crossDomainAdmin,
+      recipient: program.programId,
+      destinationCaller,
+      messageBody: Buffer.from(ethereumIface.encodeFunctionData("pauseDeposits", [false]).slice(2), "hex"),
+    });
+    try {
+      await messageTransmitterProgram.methods
+        .receiveMessage({ message, attestation })
+        .accounts(receiveMessageAccounts)
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+      assert.fail("Should not be able to receive message from the Message Transmitter");
+    } catch (error: any) {
+      assert.include(error.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+    }
+
+    // Unsetting the admin messenger restores the Message Transmitter.
+    await program.methods.setAdminMessenger(null).accounts(setAdminMessengerAccounts).rpc();
+    await messageTransmitterProgram.methods
+      .receiveMessage({ message, attestation })
+      .accounts(receiveMessageAccounts)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+    stateData = await program.account.state.fetch(state);
+    assert.isNull(stateData.adminMessenger, "Admin messenger should be unset");
+    assert.isFalse(stateData.pausedDeposits, "Deposits should be unpaused by the Message Transmitter");
+  });
 });
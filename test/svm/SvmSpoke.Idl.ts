@@ -0,0 +1,104 @@
+import * as fs from "fs";
+import * as path from "path";
+import { METADATA_LAYOUT_VERSION, METADATA_OPTIONAL, METADATA_SIGNER, METADATA_WRITABLE } from "../../src/svm";
+import { common } from "./SvmSpoke.common";
+
+const { program, assert } = common;
+
+describe("svm_spoke.idl", () => {
+  const idl = JSON.parse(fs.readFileSync(path.resolve(__dirname, "../../target/idl/svm_spoke.json"), "utf8"));
+
+  const coreInstructions = [
+    "deposit_v3",
+    "fill_v3_relay",
+    "request_v3_slow_fill",
+    "execute_v3_slow_relay_leaf",
+    "execute_relayer_refund_leaf",
+  ];
+
+  // Flattens composite accounts so that each leaf account is checked.
+  const flattenAccounts = (accounts: any[], prefix = ""): any[] =>
+    accounts.flatMap((account) =>
+      account.accounts
+        ? flattenAccounts(account.accounts, `${prefix}${account.name}.`)
+        : [{ ...account, name: `${prefix}${account.name}` }]
+    );
+
+  const undocumentedAccounts = (instruction: any): string[] =>
+    flattenAccounts(instruction.accounts)
+      .filter((account) => !account.docs || account.docs.join("").trim().length === 0)
+      .map((account) => `${instruction.name}.${account.name}`);
+
+  it("Documents all accounts of core instructions", async () => {
+    for (const name of coreInstructions) {
+      const instruction = idl.instructions.find((instruction: any) => instruction.name === name);
+      assert.isDefined(instruction, `Instruction ${name} should be in the IDL`);
+      assert.isNotEmpty(instruction.accounts, `Instruction ${name} should have accounts`);
+      assert.isEmpty(undocumentedAccounts(instruction), `All accounts of ${name} should have docs`);
+    }
+  });
+
+  it("Documents all accounts of all instructions", async () => {
+    // New accounts must come with a doc comment describing their derivation, mutability and who provides them.
+    const undocumented = idl.instructions.flatMap(undocumentedAccounts);
+    assert.isEmpty(undocumented, `Accounts without docs: ${undocumented.join(", ")}`);
+  });
+
+  // Decodes the get_instruction_metadata return data laid out as documented next to the METADATA_* program constants.
+  const decodeInstructionMetadata = (encoded: Buffer) => {
+    assert.strictEqual(encoded.readUInt8(0), METADATA_LAYOUT_VERSION, "Metadata layout version should match");
+    let offset = 2;
+    const readText = () => {
+      const length = encoded.readUInt8(offset);
+      const text = encoded.subarray(offset + 1, offset + 1 + length).toString("utf8");
+      offset += 1 + length;
+      return text;
+    };
+    const accounts = Array.from({ length: encoded.readUInt8(1) }, () => {
+      const flags = encoded.readUInt8(offset++);
+      return { flags, role: readText(), writableReason: readText() };
+    });
+    assert.strictEqual(offset, encoded.length, "Metadata should not have trailing bytes");
+    return accounts;
+  };
+
+  const getInstructionMetadata = (name: string): Promise<Buffer> => {
+    const instruction = idl.instructions.find((instruction: any) => instruction.name === name);
+    return program.methods.getInstructionMetadata(instruction.discriminator).view();
+  };
+
+  it("Matches instruction metadata with the accounts of the IDL", async () => {
+    const instructions = [
+      "deposit_v3",
+      "deposit_v3_now",
+      "unsafe_deposit_v3",
+      "deposit_v3_native",
+      "deposit_v3_with_swap",
+      "withdraw_escrow",
+    ];
+    for (const name of instructions) {
+      const metadata = decodeInstructionMetadata(await getInstructionMetadata(name));
+      const accounts = idl.instructions.find((instruction: any) => instruction.name === name).accounts;
+      assert.strictEqual(metadata.length, accounts.length, `Metadata of ${name} should cover all accounts`);
+
+      accounts.forEach((account: any, i: number) => {
+        const { flags, role, writableReason } = metadata[i];
+        const accountName = `${name}.${account.name}`;
+        assert.strictEqual(!!(flags & METADATA_WRITABLE), !!account.writable, `${accountName} writable should match`);
+        assert.strictEqual(!!(flags & METADATA_SIGNER), !!account.signer, `${accountName} signer should match`);
+        assert.strictEqual(!!(flags & METADATA_OPTIONAL), !!account.optional, `${accountName} optional should match`);
+        assert.isNotEmpty(role, `${accountName} should have a role`);
+        assert.strictEqual(writableReason.length > 0, !!account.writable, `${accountName} reason should match`);
+      });
+    }
+  });
+
+  it("Fails to get metadata of instructions not signed by end users", async () => {
+    try {
+      await getInstructionMetadata("fill_v3_relay");
+      assert.fail("Getting metadata of fill_v3_relay should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingInstructionMetadata", "Expected MissingInstructionMetadata error");
+    }
+  });
+});
@@ -1,9 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import * as crypto from "crypto";
 import { Keypair, PublicKey } from "@solana/web3.js";
+import { readEventsUntilFound } from "../../src/svm";
 import { common } from "./SvmSpoke.common";
 
-const { provider, program, connection, assertSE, assert } = common;
+const { provider, program, owner, connection, initializeState, setCurrentTime, assertSE, assert } = common;
 
 describe("svm_spoke.instruction_params", () => {
   anchor.setProvider(provider);
@@ -11,9 +12,16 @@ describe("svm_spoke.instruction_params", () => {
   // We use different caller in each test as instructionData seed is derived from initializer's address.
   let caller: Keypair;
   let instructionParams: PublicKey;
+  let instructionParamsHeader: PublicKey;
+  let state: PublicKey;
 
   const initializeInstructionParams = async (totalSize: number) => {
-    const initializeInstructionParamsAccounts = { signer: caller.publicKey, instructionParams };
+    const initializeInstructionParamsAccounts = {
+      signer: caller.publicKey,
+      state,
+      instructionParams,
+      instructionParamsHeader,
+    };
     const ix = await program.methods
       .initializeInstructionParams(totalSize)
       .accounts(initializeInstructionParamsAccounts)
@@ -38,6 +46,7 @@ describe("svm_spoke.instruction_params", () => {
 
   beforeEach(async () => {
     caller = Keypair.generate();
+    ({ state } = await initializeState());
 
     await connection.requestAirdrop(caller.publicKey, 10_000_000_000); // 10 SOL
     await new Promise((resolve) => setTimeout(resolve, 1000)); // Wait so that subsequent transactions have funds.
@@ -46,6 +55,10 @@ describe("svm_spoke.instruction_params", () => {
       [Buffer.from("instruction_params"), caller.publicKey.toBuffer()],
       program.programId
     );
+    [instructionParamsHeader] = PublicKey.findProgramAddressSync(
+      [Buffer.from("instruction_params_header"), caller.publicKey.toBuffer()],
+      program.programId
+    );
   });
 
   it("Initializes instruction params", async () => {
@@ -130,5 +143,135 @@ describe("svm_spoke.instruction_params", () => {
     // Instruction params account should not exist.
     const instructionParamsAccount = await connection.getAccountInfo(instructionParams);
     assert.isNull(instructionParamsAccount, "Instruction params account not closed");
+    assert.isNull(await connection.getAccountInfo(instructionParamsHeader), "Instruction params header not closed");
+  });
+
+  it("Closes stale instruction params of another caller to their original payer", async () => {
+    const paramsBufferTtl = 86400;
+    const totalSize = 100;
+    const closeStaleInstructionParamsAccounts = {
+      state,
+      payer: caller.publicKey,
+      instructionParams,
+      instructionParamsHeader,
+      program: program.programId,
+    };
+    const closeStaleInstructionParams = () =>
+      program.methods.closeStaleInstructionParams().accounts(closeStaleInstructionParamsAccounts).rpc();
+
+    await initializeInstructionParams(totalSize);
+    const header = await program.account.instructionParamsHeader.fetch(instructionParamsHeader);
+    assertSE(header.payer, caller.publicKey, "Header should record the payer");
+    const createdAt = (await program.account.state.fetch(state)).currentTime;
+    assertSE(header.createdAt, createdAt, "Header should record the creation time");
+
+    // Third parties can't close instruction params while the params buffer TTL is not set.
+    try {
+      await closeStaleInstructionParams();
+      assert.fail("Closing instruction params of another caller should fail without params buffer TTL");
+    } catch (err: any) {
+      assert.include(err.toString(), "CanOnlyCloseParamsBufferAfterTtl", "Expected CanOnlyCloseParamsBufferAfterTtl");
+    }
+
+    // Keys are State field names right padded with zero bytes and values are ABI encoded words.
+    const key = Buffer.alloc(32);
+    key.write("params_buffer_ttl");
+    const value = Buffer.alloc(32);
+    value.writeUInt32BE(paramsBufferTtl, 28);
+    await program.methods
+      .setConfig(Array.from(key), Array.from(value), null)
+      .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+      .rpc();
+
+    // Third parties can't close instruction params before the TTL passes.
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    await setCurrentTime(program, state, new anchor.BN(createdAt + paramsBufferTtl - 1));
+    try {
+      await closeStaleInstructionParams();
+      assert.fail("Closing instruction params of another caller should fail before the TTL");
+    } catch (err: any) {
+      assert.include(err.toString(), "CanOnlyCloseParamsBufferAfterTtl", "Expected CanOnlyCloseParamsBufferAfterTtl");
+    }
+
+    // Once the TTL has passed, anyone can close the instruction params and their rent goes to the original payer.
+    await setCurrentTime(program, state, new anchor.BN(createdAt + paramsBufferTtl));
+    const rent =
+      (await connection.getBalance(instructionParams)) + (await connection.getBalance(instructionParamsHeader));
+    const iCallerBalance = await connection.getBalance(caller.publicKey);
+    const tx = await closeStaleInstructionParams();
+    const fCallerBalance = await connection.getBalance(caller.publicKey);
+    assertSE(fCallerBalance - iCallerBalance, rent, "Original payer should receive the rent");
+    assert.isNull(await connection.getAccountInfo(instructionParams), "Instruction params account not closed");
+    assert.isNull(await connection.getAccountInfo(instructionParamsHeader), "Instruction params header not closed");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "closedStaleParamsBuffer")?.data;
+    assertSE(event.payer, caller.publicKey, "Event should include the original payer");
+    assertSE(event.closer, owner, "Event should include the closer");
+    assertSE(event.createdAt, createdAt, "Event should include the creation time");
+  });
+
+  it("Rejects closing stale instruction params to another payer", async () => {
+    await initializeInstructionParams(100);
+
+    try {
+      await program.methods
+        .closeStaleInstructionParams()
+        .accounts({ state, payer: owner, instructionParams, instructionParamsHeader, program: program.programId })
+        .rpc();
+      assert.fail("Closing instruction params to another payer should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidOriginalPayer", "Expected InvalidOriginalPayer error");
+    }
+  });
+
+  it("Blocks new instruction params while an announced upgrade is effective", async () => {
+    const totalSize = 100;
+    const inputData = crypto.randomBytes(totalSize);
+
+    // Upgrade announced far in the future does not block initialization yet.
+    const futureSlot = (await connection.getSlot()) + 1_000_000;
+    let tx = await program.methods.announceUpgrade(new anchor.BN(futureSlot)).accounts({ signer: owner, state }).rpc();
+    let events = await readEventsUntilFound(connection, tx, [program]);
+    let event = events.find((event) => event.name === "announcedUpgrade")?.data;
+    assertSE(event.effectiveAfterSlot, futureSlot, "Announced effective slot should match");
+    await initializeInstructionParams(totalSize);
+
+    // Once the announced slot is reached, already started flows can complete but no new ones can be started.
+    await program.methods.announceUpgrade(new anchor.BN(0)).accounts({ signer: owner, state }).rpc();
+    await writeInstructionParams(inputData);
+    const ix = await program.methods.closeInstructionParams().accounts({ signer: caller.publicKey }).instruction();
+    await anchor.web3.sendAndConfirmTransaction(connection, new anchor.web3.Transaction().add(ix), [caller]);
+    try {
+      await program.methods
+        .initializeInstructionParams(totalSize)
+        .accounts({ signer: caller.publicKey, state, instructionParams })
+        .signers([caller])
+        .rpc();
+      assert.fail("Initialize instruction params should have failed during pending upgrade");
+    } catch (err: any) {
+      assert.include(err.toString(), "UpgradePending", "Expected UpgradePending error");
+    }
+
+    // Clearing the announcement unblocks initialization.
+    tx = await program.methods.clearUpgradeAnnouncement().accounts({ signer: owner, state }).rpc();
+    events = await readEventsUntilFound(connection, tx, [program]);
+    event = events.find((event) => event.name === "clearedUpgradeAnnouncement")?.data;
+    assertSE(event.effectiveAfterSlot, 0, "Cleared effective slot should match");
+    assert.isNull((await program.account.state.fetch(state)).upgradeEffectiveAfterSlot, "Announcement not cleared");
+    await initializeInstructionParams(totalSize);
+  });
+
+  it("Cannot announce upgrade by non-owner", async () => {
+    try {
+      await program.methods
+        .announceUpgrade(new anchor.BN(0))
+        .accounts({ signer: caller.publicKey, state })
+        .signers([caller])
+        .rpc();
+      assert.fail("Announce upgrade should have failed for non-owner");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+    }
   });
 });
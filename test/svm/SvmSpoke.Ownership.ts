@@ -1,11 +1,13 @@
 import * as anchor from "@coral-xyz/anchor";
 import { BN } from "@coral-xyz/anchor";
-import { Keypair, PublicKey } from "@solana/web3.js";
+import { createSyncNativeInstruction, getOrCreateAssociatedTokenAccount, NATIVE_MINT } from "@solana/spl-token";
+import { Keypair, PublicKey, sendAndConfirmTransaction, SystemProgram, Transaction } from "@solana/web3.js";
 import { assert } from "chai";
 import { common } from "./SvmSpoke.common";
-import { readEventsUntilFound } from "../../src/svm";
+import { HEALTH_CHECK_ALL, HEALTH_CHECK_COUNTERS, HEALTH_CHECK_WSOL_VAULT, readEventsUntilFound } from "../../src/svm";
 
-const { provider, program, owner, initializeState, crossDomainAdmin, assertSE } = common;
+const { provider, program, owner, initializeState, crossDomainAdmin, setCurrentTime, assertSE } = common;
+const { getVaultAuthority } = common;
 
 describe("svm_spoke.ownership", () => {
   anchor.setProvider(provider);
@@ -14,9 +16,15 @@ describe("svm_spoke.ownership", () => {
   const newOwner = Keypair.generate();
   const newCrossDomainAdmin = Keypair.generate();
   let state: PublicKey;
+  let lookupTableConfig: PublicKey;
 
   beforeEach(async () => {
-    ({ state } = await initializeState());
+    let seed: BN;
+    ({ state, seed } = await initializeState());
+    [lookupTableConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lookup_table_config"), seed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
   });
 
   it("Initializes state with provided initial state", async () => {
@@ -44,6 +52,53 @@ describe("svm_spoke.ownership", () => {
         `${key} should match`
       );
     });
+
+    // Test builds keep arbitrary seeds, so none of their states is the canonical instance.
+    assert.isFalse(stateData.canonical, "Test build state should not be canonical");
+    assert.isFalse(await program.methods.isCanonical().accounts({ state }).view(), "View should match the state");
+  });
+
+  it("Validates chain ID against the network at initialization", async () => {
+    const [mainnet, devnet, localnet] = [0, 1, 2];
+    const mainnetChainId = new BN("34268394551451");
+    const devnetChainId = new BN("133268194659241");
+    const localChainId = new BN(420);
+    const combinations = [
+      { network: mainnet, chainId: mainnetChainId, allowed: true },
+      { network: mainnet, chainId: devnetChainId, allowed: false },
+      { network: mainnet, chainId: localChainId, allowed: false },
+      { network: devnet, chainId: mainnetChainId, allowed: false },
+      { network: devnet, chainId: devnetChainId, allowed: true },
+      { network: devnet, chainId: localChainId, allowed: false },
+      { network: localnet, chainId: mainnetChainId, allowed: false },
+      { network: localnet, chainId: devnetChainId, allowed: false },
+      { network: localnet, chainId: localChainId, allowed: true },
+      { network: 3, chainId: localChainId, allowed: false }, // Unknown network.
+    ];
+
+    for (const { network, chainId, allowed } of combinations) {
+      const initialState = {
+        initialNumberOfDeposits: new BN(0),
+        chainId,
+        remoteDomain: new BN(0),
+        crossDomainAdmin,
+        depositQuoteTimeBuffer: new BN(3600),
+        fillDeadlineBuffer: new BN(14400),
+      };
+      if (allowed) {
+        ({ state } = await initializeState(undefined, initialState, network));
+        const stateData = await program.account.state.fetch(state);
+        assertSE(stateData.network, network, "Network should match");
+        assertSE(stateData.chainId, chainId, "Chain ID should match");
+      } else {
+        try {
+          await initializeState(undefined, initialState, network);
+          assert.fail(`Network ${network} should not allow chain ID ${chainId.toString()}`);
+        } catch (err: any) {
+          assert.include(err.toString(), "InvalidNetworkChainId", "Expected InvalidNetworkChainId error");
+        }
+      }
+    }
   });
 
   it("Pauses and unpauses deposits", async () => {
@@ -122,18 +177,241 @@ describe("svm_spoke.ownership", () => {
     }
   });
 
+  it("Pauses fills with expiry and unpauses them before the expiry", async () => {
+    const pauseFillsAccounts = { state, signer: owner, program: program.programId };
+    const expiresAt = Math.floor(Date.now() / 1000) + 3600;
+    const tx = await program.methods.pauseFillsWithExpiry(true, expiresAt).accounts(pauseFillsAccounts).rpc();
+
+    let stateAccountData = await program.account.state.fetch(state);
+    assert.isTrue(stateAccountData.pausedFills, "Fills should be paused");
+    assertSE(stateAccountData.fillsPauseExpiresAt, expiresAt, "Pause expiry should be stored");
+
+    // Verify the PausedFillsWithExpiry event
+    const events = await readEventsUntilFound(provider.connection, tx, [program]);
+    const pausedFillsEvent = events.find((event) => event.name === "pausedFillsWithExpiry")?.data;
+    assert.isTrue(pausedFillsEvent.isPaused, "PausedFillsWithExpiry event should indicate fills are paused");
+    assertSE(pausedFillsEvent.expiresAt, expiresAt, "PausedFillsWithExpiry event should include the expiry");
+
+    // Manual unpause before the expiry also clears the expiry.
+    await program.methods.pauseFillsWithExpiry(false, expiresAt).accounts(pauseFillsAccounts).rpc();
+    stateAccountData = await program.account.state.fetch(state);
+    assert.isFalse(stateAccountData.pausedFills, "Fills should not be paused");
+    assertSE(stateAccountData.fillsPauseExpiresAt, 0, "Pause expiry should be cleared");
+
+    // Try to pause fills with an expiry in the past
+    try {
+      await program.methods.pauseFillsWithExpiry(true, 1).accounts(pauseFillsAccounts).rpc();
+      assert.fail("Pause expiry in the past should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidPauseExpiry", "Expected InvalidPauseExpiry error");
+    }
+  });
+
+  it("Emits config snapshots matching the state at most once per snapshot interval", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const snapshotMinInterval = 86400;
+    const key = Buffer.alloc(32);
+    key.write("snapshot_min_interval");
+    const value = Buffer.alloc(32);
+    value.writeUInt32BE(snapshotMinInterval, 28);
+    await program.methods
+      .setConfig(Array.from(key), Array.from(value), null)
+      .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+      .rpc();
+    await program.methods.pauseDeposits(true).accounts({ state, signer: owner, program: program.programId }).rpc();
+
+    // Snapshots are permissionless, so no signer account is required.
+    const tx = await program.methods
+      .emitConfigSnapshot()
+      .accounts({ state, lookupTableConfig, program: program.programId })
+      .rpc();
+    const stateData = await program.account.state.fetch(state);
+    const events = await readEventsUntilFound(provider.connection, tx, [program]);
+    const snapshot = events.find((event) => event.name === "configSnapshot")?.data;
+    assert.isDefined(snapshot, "ConfigSnapshot event should be emitted");
+    assertSE(snapshot.snapshotTime, stateData.currentTime, "Snapshot time should be the current time");
+    assertSE(stateData.lastConfigSnapshotTime, stateData.currentTime, "Last snapshot time should be recorded");
+    assert.isTrue(snapshot.pausedDeposits, "Snapshot should include the paused deposits flag");
+    assertSE(snapshot.snapshotMinInterval, snapshotMinInterval, "Snapshot should include the snapshot interval");
+
+    assert.isNull(snapshot.lookupTable, "Snapshot should have no lookup table before it is created");
+
+    // Every snapshot field other than its time and lookup table is the state field of the same name.
+    Object.keys(snapshot)
+      .filter((field) => field !== "snapshotTime" && field !== "lookupTable")
+      .forEach((field) => {
+        assert.property(stateData, field, `State should have the ${field} field`);
+        assert.strictEqual(
+          JSON.stringify(snapshot[field]),
+          JSON.stringify(stateData[field as keyof typeof stateData]),
+          `Snapshot ${field} should match the state`
+        );
+      });
+
+    // Snapshots are rate limited until the snapshot interval has passed.
+    try {
+      await program.methods
+        .emitConfigSnapshot()
+        .accounts({ state, lookupTableConfig, program: program.programId })
+        .rpc();
+      assert.fail("Snapshot within the snapshot interval should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConfigSnapshotTooSoon", "Expected ConfigSnapshotTooSoon error");
+    }
+    await setCurrentTime(program, state, new BN(stateData.currentTime + snapshotMinInterval - 1));
+    try {
+      await program.methods
+        .emitConfigSnapshot()
+        .accounts({ state, lookupTableConfig, program: program.programId })
+        .rpc();
+      assert.fail("Snapshot right before the end of the snapshot interval should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConfigSnapshotTooSoon", "Expected ConfigSnapshotTooSoon error");
+    }
+    await setCurrentTime(program, state, new BN(stateData.currentTime + snapshotMinInterval));
+    await program.methods.emitConfigSnapshot().accounts({ state, lookupTableConfig, program: program.programId }).rpc();
+    assertSE(
+      (await program.account.state.fetch(state)).lastConfigSnapshotTime,
+      stateData.currentTime + snapshotMinInterval,
+      "Last snapshot time should be updated"
+    );
+  });
+
+  it("Reports failed health checks without reverting", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const wsolVault = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, NATIVE_MINT, getVaultAuthority(state), true)
+    ).address;
+    const healthCheck = (healthCheckState = state, healthCheckWsolVault = wsolVault) =>
+      program.methods
+        .healthCheck()
+        .accounts({ state: healthCheckState, wsolVault: healthCheckWsolVault, program: program.programId });
+
+    // A freshly initialized state with a synced wSOL vault passes all checks.
+    assert.strictEqual(await healthCheck().view(), 0, "All checks should pass");
+    assert.strictEqual(await healthCheck(state, program.programId).view(), 0, "wSOL vault check should be optional");
+
+    // Accounts other than the state fail all checks, as they all depend on the state.
+    assert.strictEqual(await healthCheck(owner).view(), HEALTH_CHECK_ALL, "All checks should fail for a non-state");
+
+    // Lamports sent straight to the wSOL vault are out of sync until synced into its balance.
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(SystemProgram.transfer({ fromPubkey: owner, toPubkey: wsolVault, lamports: 1_000 })),
+      [payer]
+    );
+    assert.strictEqual(await healthCheck().view(), HEALTH_CHECK_WSOL_VAULT, "Only the wSOL vault check should fail");
+    const tx = await healthCheck().rpc();
+    const events = await readEventsUntilFound(provider.connection, tx, [program]);
+    const failedEvent = events.find((event) => event.name === "healthCheckFailed")?.data;
+    assertSE(failedEvent.state, state, "HealthCheckFailed event should include the state");
+    assert.strictEqual(failedEvent.failedChecks, HEALTH_CHECK_WSOL_VAULT, "Event should include the failed checks");
+    const syncNativeTx = new Transaction().add(createSyncNativeInstruction(wsolVault));
+    await sendAndConfirmTransaction(provider.connection, syncNativeTx, [payer]);
+    assert.strictEqual(await healthCheck().view(), 0, "Synced wSOL vault should pass");
+
+    // wSOL accounts other than the vault ATA of the vault authority fail the check.
+    const otherWsolAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, payer, NATIVE_MINT, owner))
+      .address;
+    assert.strictEqual(
+      await healthCheck(state, otherWsolAccount).view(),
+      HEALTH_CHECK_WSOL_VAULT,
+      "Only the wSOL vault check should fail for other wSOL accounts"
+    );
+
+    // A config snapshot time in the future breaks the counters check.
+    await program.methods.emitConfigSnapshot().accounts({ state, lookupTableConfig, program: program.programId }).rpc();
+    const stateData = await program.account.state.fetch(state);
+    await setCurrentTime(program, state, new BN(stateData.currentTime - 1));
+    assert.strictEqual(await healthCheck().view(), HEALTH_CHECK_COUNTERS, "Only the counters check should fail");
+    await setCurrentTime(program, state, new BN(stateData.currentTime));
+    assert.strictEqual(await healthCheck().view(), 0, "All checks should pass again");
+  });
+
+  it("Returns the event authority signing the emitted events", async () => {
+    const eventAuthority = await program.methods.getEventAuthority().view();
+    const [hardcodedEventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      program.programId
+    );
+    assertSE(eventAuthority, hardcodedEventAuthority, "Event authority should match the Anchor seed");
+
+    // Events are emitted as self-CPI with the event authority as their only account.
+    const pauseDepositsAccounts = { state, signer: owner, program: program.programId };
+    const tx = await program.methods.pauseDeposits(true).accounts(pauseDepositsAccounts).rpc();
+    const txResult = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    if (txResult === null || txResult.meta === null) throw new Error("Transaction meta not confirmed");
+    const accountKeys = txResult.transaction.message.getAccountKeys({
+      accountKeysFromLookups: txResult.meta.loadedAddresses,
+    });
+    const eventInstructions = (txResult.meta.innerInstructions ?? [])
+      .flatMap((ixBlock) => ixBlock.instructions)
+      .filter((ix) => accountKeys.get(ix.programIdIndex)?.equals(program.programId));
+    assert.isNotEmpty(eventInstructions, "Event self-CPI should be found");
+    for (const ix of eventInstructions) {
+      assert.lengthOf(ix.accounts, 1, "Event self-CPI should have a single account");
+      assertSE(accountKeys.get(ix.accounts[0]), eventAuthority, "Emitted event authority should match");
+    }
+  });
+
   it("Transfers ownership", async () => {
-    // Transfer ownership to newOwner
-    const transferOwnershipAccounts = { state, signer: owner };
-    await program.methods.transferOwnership(newOwner.publicKey).accounts(transferOwnershipAccounts).rpc();
+    // Propose newOwner as the pending owner
+    const transferOwnershipAccounts = { state, signer: owner, program: program.programId };
+    const tx = await program.methods.transferOwnership(newOwner.publicKey).accounts(transferOwnershipAccounts).rpc();
 
-    // Verify the new owner
+    // Ownership is only transferred once the pending owner accepts it.
     let stateAccountData = await program.account.state.fetch(state);
+    assertSE(stateAccountData.owner, owner, "Owner should not change before acceptance");
+    assertSE(stateAccountData.pendingOwner, newOwner.publicKey, "Pending owner should be set");
+    let events = await readEventsUntilFound(provider.connection, tx, [program]);
+    const startedEvent = events.find((event) => event.name === "ownershipTransferStarted")?.data;
+    assertSE(startedEvent.owner, owner, "OwnershipTransferStarted event should include the owner");
+    assertSE(startedEvent.pendingOwner, newOwner.publicKey, "Event should include the pending owner");
+
+    // Try to accept ownership as an account other than the pending owner
+    try {
+      const acceptOwnershipAccounts = { state, signer: nonOwner.publicKey, program: program.programId };
+      await program.methods.acceptOwnership().accounts(acceptOwnershipAccounts).signers([nonOwner]).rpc();
+      assert.fail("Only the pending owner should be able to accept ownership");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotPendingOwner", "Expected NotPendingOwner error");
+    }
+
+    // Accept ownership as the pending owner
+    const acceptOwnershipAccounts = { state, signer: newOwner.publicKey, program: program.programId };
+    const tx2 = await program.methods.acceptOwnership().accounts(acceptOwnershipAccounts).signers([newOwner]).rpc();
+
+    // Verify the new owner
+    stateAccountData = await program.account.state.fetch(state);
     assert.equal(stateAccountData.owner.toString(), newOwner.publicKey.toString(), "Ownership should be transferred");
+    assert.isNull(stateAccountData.pendingOwner, "Pending owner should be cleared");
+    events = await readEventsUntilFound(provider.connection, tx2, [program]);
+    const transferredEvent = events.find((event) => event.name === "ownershipTransferred")?.data;
+    assertSE(transferredEvent.previousOwner, owner, "OwnershipTransferred event should include the previous owner");
+    assertSE(transferredEvent.newOwner, newOwner.publicKey, "OwnershipTransferred event should include the new owner");
+
+    // Pending ownership cannot be accepted twice.
+    try {
+      await program.methods.acceptOwnership().accounts(acceptOwnershipAccounts).signers([newOwner]).rpc();
+      assert.fail("Accepted ownership should not be accepted again");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotPendingOwner", "Expected NotPendingOwner error");
+    }
+
+    // Try to transfer ownership as the previous owner
+    try {
+      await program.methods.transferOwnership(owner).accounts(transferOwnershipAccounts).rpc();
+      assert.fail("Previous owner should not be able to transfer ownership");
+    } catch (err: any) {
+      assert.include(err.toString(), "Only the owner can call this function!", "Expected owner check error");
+    }
 
     // Try to transfer ownership as non-owner
     try {
-      const transferOwnershipAccounts = { state, signer: nonOwner.publicKey };
+      const transferOwnershipAccounts = { state, signer: nonOwner.publicKey, program: program.programId };
       await program.methods
         .transferOwnership(nonOwner.publicKey)
         .accounts(transferOwnershipAccounts)
@@ -145,6 +423,25 @@ describe("svm_spoke.ownership", () => {
     }
   });
 
+  it("Replaces the pending owner with a new proposal", async () => {
+    const transferOwnershipAccounts = { state, signer: owner, program: program.programId };
+    await program.methods.transferOwnership(nonOwner.publicKey).accounts(transferOwnershipAccounts).rpc();
+    await program.methods.transferOwnership(newOwner.publicKey).accounts(transferOwnershipAccounts).rpc();
+
+    // The replaced pending owner can no longer accept ownership.
+    try {
+      const acceptOwnershipAccounts = { state, signer: nonOwner.publicKey, program: program.programId };
+      await program.methods.acceptOwnership().accounts(acceptOwnershipAccounts).signers([nonOwner]).rpc();
+      assert.fail("Replaced pending owner should not be able to accept ownership");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotPendingOwner", "Expected NotPendingOwner error");
+    }
+
+    const acceptOwnershipAccounts = { state, signer: newOwner.publicKey, program: program.programId };
+    await program.methods.acceptOwnership().accounts(acceptOwnershipAccounts).signers([newOwner]).rpc();
+    assertSE((await program.account.state.fetch(state)).owner, newOwner.publicKey, "Ownership should be transferred");
+  });
+
   it("Sets cross-domain admin", async () => {
     // Set cross-domain admin as owner
     const setCrossDomainAdminAccounts = { state, signer: owner, program: program.programId };
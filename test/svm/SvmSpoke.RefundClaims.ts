@@ -4,11 +4,19 @@ import { Keypair, PublicKey } from "@solana/web3.js";
 import { assert } from "chai";
 import { common } from "./SvmSpoke.common";
 import { MerkleTree } from "@uma/common/dist/MerkleTree";
-import { createMint, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  closeAccount,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import { RelayerRefundLeafSolana, RelayerRefundLeafType } from "../../src/types/svm";
 import { loadExecuteRelayerRefundLeafParams, readEventsUntilFound, relayerRefundHashFn } from "../../src/svm";
 
-const { provider, program, owner, initializeState, connection, chainId, assertSE } = common;
+const { provider, program, owner, initializeState, connection, chainId, assertSE, createSlowFillLiabilityPda } = common;
+const { createFillVolumePda, createClaimLiabilityPda, getVaultAuthority } = common;
 
 describe("svm_spoke.refund_claims", () => {
   anchor.setProvider(provider);
@@ -33,7 +41,9 @@ describe("svm_spoke.refund_claims", () => {
     mint: PublicKey;
     tokenAccount: PublicKey;
     claimAccount: PublicKey;
+    rateLimit: PublicKey;
     tokenProgram: PublicKey;
+    claimLiability: PublicKey;
     program: PublicKey;
   };
 
@@ -86,8 +96,18 @@ describe("svm_spoke.refund_claims", () => {
     const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
 
     // Relay root bundle
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods.relayRootBundle(Array.from(root), Array.from(root)).accounts(relayRootBundleAccounts).rpc();
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .relayRootBundle(Array.from(root), Array.from(root), 0, null, 0, null)
+      .accounts(relayRootBundleAccounts)
+      .rpc();
 
     // Pass claim account as relayer refund address.
     const remainingAccounts = [{ pubkey: claimAccount, isWritable: true, isSigner: false }];
@@ -104,11 +124,26 @@ describe("svm_spoke.refund_claims", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
     const proofAsNumbers = proof.map((p) => Array.from(p));
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
     await program.methods
       .executeRelayerRefundLeafDeferred()
       .accounts(executeRelayerRefundLeafAccounts)
@@ -134,7 +169,7 @@ describe("svm_spoke.refund_claims", () => {
       program.programId
     );
 
-    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true)).address;
+    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)).address;
 
     claimRelayerRefundAccounts = {
       signer: owner,
@@ -144,7 +179,9 @@ describe("svm_spoke.refund_claims", () => {
       mint,
       tokenAccount,
       claimAccount,
+      rateLimit: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
+      claimLiability: createClaimLiabilityPda(mint, seed),
       program: program.programId,
     };
 
@@ -164,6 +201,9 @@ describe("svm_spoke.refund_claims", () => {
     // Execute relayer refund using claim account.
     const relayerRefund = new BN(500000);
     await executeRelayerRefundToClaim(relayerRefund);
+    const claimLiability = createClaimLiabilityPda(mint, seed);
+    const iClaimLiability = await program.account.claimLiability.fetch(claimLiability);
+    assertSE(iClaimLiability.pendingClaimAmount, relayerRefund, "Accrued refund should be owed to claims");
 
     const iVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
     const iRelayerBal = (await connection.getTokenAccountBalance(tokenAccount)).value.amount;
@@ -173,6 +213,8 @@ describe("svm_spoke.refund_claims", () => {
       .claimRelayerRefundFor(relayer.publicKey)
       .accounts(claimRelayerRefundAccounts)
       .rpc();
+    const fClaimLiability = await program.account.claimLiability.fetch(claimLiability);
+    assertSE(fClaimLiability.pendingClaimAmount, 0, "Claimed refund should be released");
 
     // The relayer should have received funds from the vault.
     const fVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
@@ -188,6 +230,32 @@ describe("svm_spoke.refund_claims", () => {
     assertSE(event.refundAddress, relayer.publicKey, "Relayer refund address should match");
   });
 
+  it("Anyone can push a deferred refund to a refund address without ATA", async () => {
+    // Execute relayer refund using claim account.
+    const relayerRefund = new BN(500000);
+    await executeRelayerRefundToClaim(relayerRefund);
+
+    // The refund address holds no ATA for the mint, e.g. a depositor refunded for an expired deposit.
+    await closeAccount(connection, payer, tokenAccount, relayer.publicKey, relayer);
+    const iVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+
+    // Push the refund, paying for the ATA creation.
+    const tx = await program.methods
+      .claimRefundFor()
+      .accounts({ ...claimRelayerRefundAccounts, refundAddress: relayer.publicKey })
+      .rpc();
+
+    const fVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    assertSE(BigInt(iVaultBal) - BigInt(fVaultBal), relayerRefund, "Vault balance");
+    assertSE((await getAccount(connection, tokenAccount)).amount, relayerRefund, "Created ATA balance");
+    assert.isNull(await connection.getAccountInfo(claimAccount), "Claim account should be closed");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "claimedRelayerRefund")?.data;
+    assertSE(event.claimAmount, relayerRefund, "Relayer refund amount should match");
+    assertSE(event.refundAddress, relayer.publicKey, "Relayer refund address should match");
+  });
+
   it("Cannot Double Claim Relayer Refund", async () => {
     // Execute relayer refund using claim account.
     const relayerRefund = new BN(500000);
@@ -283,7 +351,10 @@ describe("svm_spoke.refund_claims", () => {
 
     // Should not be able to close the claim account from default wallet as the initializer was different.
     try {
-      await program.methods.closeClaimAccount(mint, relayer.publicKey).accounts({ signer: payer.publicKey }).rpc();
+      await program.methods
+        .closeClaimAccount(mint, relayer.publicKey)
+        .accounts({ signer: payer.publicKey, state })
+        .rpc();
       assert.fail("Closing claim account from different initializer should fail");
     } catch (error: any) {
       assert.instanceOf(error, AnchorError);
@@ -297,7 +368,7 @@ describe("svm_spoke.refund_claims", () => {
     // Close the claim account from initializer before executing relayer refunds.
     await program.methods
       .closeClaimAccount(mint, relayer.publicKey)
-      .accounts({ signer: claimInitializer.publicKey })
+      .accounts({ signer: claimInitializer.publicKey, state })
       .signers([claimInitializer])
       .rpc();
 
@@ -319,7 +390,7 @@ describe("svm_spoke.refund_claims", () => {
     try {
       await program.methods
         .closeClaimAccount(mint, relayer.publicKey)
-        .accounts({ signer: claimInitializer.publicKey })
+        .accounts({ signer: claimInitializer.publicKey, state })
         .signers([claimInitializer])
         .rpc();
       assert.fail("Closing claim account with non-zero refund liability should fail");
@@ -1,12 +1,24 @@
 import * as anchor from "@coral-xyz/anchor";
 import { BN } from "@coral-xyz/anchor";
-import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, createMint, getAccount } from "@solana/spl-token";
-import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeTransferHookInstruction,
+  createMint,
+  getAccount,
+  getMint,
+  getMintLen,
+} from "@solana/spl-token";
+import { PublicKey, Keypair, SystemProgram, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
 import { assert } from "chai";
 import { common } from "./SvmSpoke.common";
 import { readEventsUntilFound } from "../../src/svm";
 
-const { provider, program, owner, initializeState, createRoutePda, getVaultAta } = common;
+const { provider, program, owner, initializeState, createRoutePda, getVaultAta, getVaultAuthority } = common;
 
 describe("svm_spoke.routes", () => {
   anchor.setProvider(provider);
@@ -38,19 +50,36 @@ describe("svm_spoke.routes", () => {
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
     };
   });
 
   it("Sets, retrieves, and controls access to route enablement", async () => {
     // Enable the route as owner
     const tx = await program.methods
-      .setEnableRoute(tokenMint, routeChainId, true)
+      .setEnableRoute(tokenMint, routeChainId, true, 0, 0)
       .accounts(setEnableRouteAccounts)
       .rpc();
 
     // Retrieve and verify the route is enabled
     let routeAccount = await program.account.route.fetch(routePda);
     assert.isTrue(routeAccount.enabled, "Route should be enabled");
+    const mintDecimals = (await getMint(provider.connection, tokenMint)).decimals;
+    assert.strictEqual(routeAccount.expectedDecimals, mintDecimals, "Route should snapshot mint decimals");
+    assert.strictEqual(routeAccount.destinationChainId.toString(), routeChainId.toString(), "Route should record chain");
+
+    // Routes enabled by this program already have the current layout and cannot be migrated.
+    try {
+      await program.methods
+        .migrateRoute(tokenMint, routeChainId)
+        .accounts({ signer: owner, state, route: routePda, originTokenMint: tokenMint })
+        .rpc();
+      assert.fail("Migrating a route of the current layout should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotLegacyRoute", "Expected NotLegacyRoute error");
+    }
 
     // Verify the enabledDepositRoute event
     let events = await readEventsUntilFound(provider.connection, tx, [program]);
@@ -61,7 +90,7 @@ describe("svm_spoke.routes", () => {
 
     // Disable the route as owner
     const tx2 = await program.methods
-      .setEnableRoute(tokenMint, routeChainId, false)
+      .setEnableRoute(tokenMint, routeChainId, false, 0, 0)
       .accounts(setEnableRouteAccounts)
       .rpc();
 
@@ -79,7 +108,7 @@ describe("svm_spoke.routes", () => {
     // Try to enable the route as non-owner
     try {
       await program.methods
-        .setEnableRoute(tokenMint, routeChainId, true)
+        .setEnableRoute(tokenMint, routeChainId, true, 0, 0)
         .accounts({ ...setEnableRouteAccounts, signer: nonOwner.publicKey })
         .signers([nonOwner])
         .rpc();
@@ -92,9 +121,10 @@ describe("svm_spoke.routes", () => {
     routeAccount = await program.account.route.fetch(routePda);
     assert.isFalse(routeAccount.enabled, "Route should still be disabled after non-owner attempt");
 
-    // Verify the owner of the vault is the state
+    // Verify the owner of the vault is the vault authority
     const vaultAccount = await getAccount(provider.connection, vault);
-    assert.strictEqual(vaultAccount.owner.toBase58(), state.toBase58(), "Vault owner should be the state");
+    const vaultAuthority = getVaultAuthority(state).toBase58();
+    assert.strictEqual(vaultAccount.owner.toBase58(), vaultAuthority, "Vault owner should be the vault authority");
 
     // Verify the owner of the state is the expected owner
     const stateAccount = await program.account.state.fetch(state);
@@ -107,7 +137,7 @@ describe("svm_spoke.routes", () => {
 
     try {
       await program.methods
-        .setEnableRoute(wrongOriginToken, routeChainId, true)
+        .setEnableRoute(wrongOriginToken, routeChainId, true, 0, 0)
         .accounts({ ...setEnableRouteAccounts, route: wrongRoutePda })
         .rpc();
       assert.fail("Setting route with wrong origin token should fail");
@@ -116,4 +146,148 @@ describe("svm_spoke.routes", () => {
       assert.strictEqual(err.error.errorCode.code, "InvalidMint", "Expected error code InvalidMint");
     }
   });
+
+  it("Cannot enable route for Token-2022 mints making vault balances unreliable", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const extensions = [
+      {
+        extensionType: ExtensionType.PermanentDelegate,
+        createInitializeInstruction: (mint: PublicKey) =>
+          createInitializePermanentDelegateInstruction(mint, owner, TOKEN_2022_PROGRAM_ID),
+      },
+      {
+        extensionType: ExtensionType.TransferHook,
+        createInitializeInstruction: (mint: PublicKey) =>
+          createInitializeTransferHookInstruction(mint, owner, program.programId, TOKEN_2022_PROGRAM_ID),
+      },
+    ];
+
+    for (const { extensionType, createInitializeInstruction } of extensions) {
+      // Create the origin token on the 2022 token program with the extension.
+      const mintKeypair = Keypair.generate();
+      const mintLen = getMintLen([extensionType]);
+      const createMintTx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: payer.publicKey,
+          newAccountPubkey: mintKeypair.publicKey,
+          space: mintLen,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeInstruction(mintKeypair.publicKey),
+        createInitializeMintInstruction(mintKeypair.publicKey, 6, owner, owner, TOKEN_2022_PROGRAM_ID)
+      );
+      await sendAndConfirmTransaction(provider.connection, createMintTx, [payer, mintKeypair]);
+      const extensionMint = mintKeypair.publicKey;
+
+      const extensionRouteAccounts = {
+        ...setEnableRouteAccounts,
+        route: createRoutePda(extensionMint, seed, routeChainId),
+        vault: await getVaultAta(extensionMint, state),
+        originTokenMint: extensionMint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      };
+      try {
+        await program.methods
+          .setEnableRoute(extensionMint, routeChainId, true, 0, 0)
+          .accounts(extensionRouteAccounts)
+          .rpc();
+        assert.fail(`Enabling route for mint with ${ExtensionType[extensionType]} extension should fail`);
+      } catch (err: any) {
+        assert.include(err.toString(), "UnsupportedMintExtension", "Expected UnsupportedMintExtension error");
+      }
+
+      // Disabling the route is still possible.
+      await program.methods
+        .setEnableRoute(extensionMint, routeChainId, false, 0, 0)
+        .accounts(extensionRouteAccounts)
+        .rpc();
+      const routeAccount = await program.account.route.fetch(extensionRouteAccounts.route);
+      assert.isFalse(routeAccount.enabled, "Route should be disabled");
+    }
+  });
+
+  // Remaining accounts of setEnableRoutesBatch hold the route PDA, vault and mint of each route update in order.
+  const getRouteBatchAccounts = async (routeUpdates: { originToken: PublicKey; destinationChainId: BN }[]) => {
+    const accounts = await Promise.all(
+      routeUpdates.map(async ({ originToken, destinationChainId }) => [
+        { pubkey: createRoutePda(originToken, seed, destinationChainId), isWritable: true, isSigner: false },
+        { pubkey: await getVaultAta(originToken, state), isWritable: true, isSigner: false },
+        { pubkey: originToken, isWritable: false, isSigner: false },
+      ])
+    );
+    return accounts.flat();
+  };
+
+  const getRouteBatchStaticAccounts = () => {
+    const { signer, payer, tokenProgram, associatedTokenProgram, systemProgram } = setEnableRouteAccounts;
+    return { signer, payer, state, tokenProgram, associatedTokenProgram, systemProgram, program: program.programId };
+  };
+
+  it("Sets route enablement in a batch", async () => {
+    // Enable the first route on its own, so that the batch both updates an existing route and creates new ones.
+    await program.methods.setEnableRoute(tokenMint, routeChainId, true, 0, 0).accounts(setEnableRouteAccounts).rpc();
+
+    const otherMint = await createMint(provider.connection, (provider.wallet as anchor.Wallet).payer, owner, owner, 9);
+    const routeUpdates = [
+      { originToken: tokenMint, destinationChainId: routeChainId, enabled: false, disableAt: 0, decimalShift: 0 },
+      { originToken: tokenMint, destinationChainId: new BN(2), enabled: true, disableAt: 0, decimalShift: -3 },
+      { originToken: otherMint, destinationChainId: routeChainId, enabled: true, disableAt: 100, decimalShift: 0 },
+    ];
+    const tx = await program.methods
+      .setEnableRoutesBatch(routeUpdates)
+      .accounts(getRouteBatchStaticAccounts())
+      .remainingAccounts(await getRouteBatchAccounts(routeUpdates))
+      .rpc();
+
+    const routes = await Promise.all(
+      routeUpdates.map(({ originToken, destinationChainId }) =>
+        program.account.route.fetch(createRoutePda(originToken, seed, destinationChainId))
+      )
+    );
+    assert.isFalse(routes[0].enabled, "Existing route should be disabled");
+    assert.isTrue(routes[1].enabled, "New route should be enabled");
+    assert.strictEqual(routes[1].decimalShift, -3, "New route should store its decimal shift");
+    assert.strictEqual(routes[2].expectedDecimals, 9, "New route should snapshot its mint decimals");
+    assert.strictEqual(routes[2].disableAt, 100, "New route should store its disable time");
+
+    const vaultAccount = await getAccount(provider.connection, await getVaultAta(otherMint, state));
+    assert.strictEqual(vaultAccount.owner.toBase58(), state.toBase58(), "Created vault owner should be the state");
+
+    // Verify one enabledDepositRoute event per route update, in order.
+    const events = (await readEventsUntilFound(provider.connection, tx, [program])).filter(
+      (event) => event.name === "enabledDepositRoute"
+    );
+    assert.strictEqual(events.length, routeUpdates.length, "Should emit an event per route update");
+    events.forEach((event, i) => {
+      assert.strictEqual(event.data.originToken.toString(), routeUpdates[i].originToken.toString(), "originToken");
+      assert.strictEqual(
+        event.data.destinationChainId.toString(),
+        routeUpdates[i].destinationChainId.toString(),
+        "destinationChainId should match"
+      );
+      assert.strictEqual(event.data.enabled, routeUpdates[i].enabled, "enabled should match");
+    });
+  });
+
+  it("Reverts the whole route batch on an invalid route update", async () => {
+    const routeUpdates = [
+      { originToken: tokenMint, destinationChainId: routeChainId, enabled: true, disableAt: 0, decimalShift: 0 },
+      { originToken: tokenMint, destinationChainId: new BN(2), enabled: true, disableAt: 0, decimalShift: 19 },
+    ];
+    const remainingAccounts = await getRouteBatchAccounts(routeUpdates);
+    try {
+      await program.methods
+        .setEnableRoutesBatch(routeUpdates)
+        .accounts(getRouteBatchStaticAccounts())
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+      assert.fail("Batch with an invalid decimal shift should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidDecimalShift", "Expected InvalidDecimalShift error");
+    }
+
+    const routeAccount = await provider.connection.getAccountInfo(routePda);
+    assert.isNull(routeAccount, "Valid route update of a reverted batch should not be applied");
+  });
 });
@@ -41,7 +41,8 @@ import {
   SlowFillLeaf,
 } from "../../src/types/svm";
 const { provider, connection, program, owner, chainId, setCurrentTime } = common;
-const { initializeState, assertSE, assert } = common;
+const { initializeState, assertSE, assert, createSlowFillLiabilityPda, createMessageSchemaPda } = common;
+const { getVaultAuthority } = common;
 
 describe("svm_spoke.slow_fill.across_plus", () => {
   anchor.setProvider(provider);
@@ -80,7 +81,18 @@ describe("svm_spoke.slow_fill.across_plus", () => {
       instructionParams: program.programId,
       state,
       fillStatusPDA,
+      slowFillLiability: createSlowFillLiabilityPda(relayData.outputToken, seed),
+      messageSchema: createMessageSchemaPda(relayData.recipient),
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      requesterActivity: program.programId,
+      allowlistEntry: program.programId,
+      eventCommitment: program.programId,
+      fillIndexShard: program.programId,
+      originChainAlias: program.programId,
+      relayerStats: program.programId,
       systemProgram: anchor.web3.SystemProgram.programId,
+      payer: relayer.publicKey,
     };
   }
 
@@ -120,9 +132,16 @@ describe("svm_spoke.slow_fill.across_plus", () => {
     const relayerRefundRoot = crypto.randomBytes(32);
 
     // Relay root bundle
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
+    const relayRootBundleAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      payer: owner,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
     await program.methods
-      .relayRootBundle(Array.from(relayerRefundRoot), Array.from(slowRelayRoot))
+      .relayRootBundle(Array.from(relayerRefundRoot), Array.from(slowRelayRoot), 0, null, 0, null)
       .accounts(relayRootBundleAccounts)
       .rpc();
 
@@ -132,14 +151,23 @@ describe("svm_spoke.slow_fill.across_plus", () => {
     return { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle };
   };
 
-  const createSlowFillIx = async (multicallHandlerCoder: MulticallHandlerCoder, bufferParams = false) => {
+  const createSlowFillIx = async (
+    multicallHandlerCoder: MulticallHandlerCoder,
+    bufferParams = false,
+    recipientTokenAccount = handlerATA
+  ) => {
     // Relay root bundle with slow fill leaf.
     const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
 
     const requestV3SlowFillValues: RequestV3SlowFillDataValues = [Array.from(relayHash), leaf.relayData];
     let loadRequestParamsInstructions: TransactionInstruction[] = [];
     if (bufferParams) {
-      loadRequestParamsInstructions = await loadRequestV3SlowFillParams(program, relayer, requestV3SlowFillValues[1]);
+      loadRequestParamsInstructions = await loadRequestV3SlowFillParams(
+        program,
+        relayer,
+        state,
+        requestV3SlowFillValues[1],
+      );
       [requestAccounts.instructionParams] = PublicKey.findProgramAddressSync(
         [Buffer.from("instruction_params"), relayer.publicKey.toBuffer()],
         program.programId
@@ -162,7 +190,23 @@ describe("svm_spoke.slow_fill.across_plus", () => {
       vault: vault,
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
-      recipientTokenAccount: handlerATA,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
       program: program.programId,
     };
     const executeRemainingAccounts: AccountMeta[] = [
@@ -180,6 +224,7 @@ describe("svm_spoke.slow_fill.across_plus", () => {
       loadExecuteParamsInstructions = await loadExecuteV3SlowRelayLeafParams(
         program,
         relayer,
+        state,
         executeV3SlowRelayLeafValues[1],
         executeV3SlowRelayLeafValues[2],
         executeV3SlowRelayLeafValues[3]
@@ -193,7 +238,7 @@ describe("svm_spoke.slow_fill.across_plus", () => {
       ? [executeV3SlowRelayLeafValues[0], null, null, null]
       : executeV3SlowRelayLeafValues;
     const executeIx = await program.methods
-      .executeV3SlowRelayLeaf(...executeV3SlowRelayLeafParams)
+      .executeV3SlowRelayLeaf(...executeV3SlowRelayLeafParams, 0, Buffer.alloc(0))
       .accounts(executeAccounts)
       .remainingAccounts(executeRemainingAccounts)
       .instruction();
@@ -215,7 +260,7 @@ describe("svm_spoke.slow_fill.across_plus", () => {
     finalRecipientATA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, finalRecipient)).address;
 
     ({ state, seed } = await initializeState());
-    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true)).address; // Initialize vault
+    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)).address;
 
     // mint mint to vault
     await mintTo(connection, payer, mint, vault, owner, seedBalance);
@@ -287,6 +332,106 @@ describe("svm_spoke.slow_fill.across_plus", () => {
     );
   });
 
+  it("Requests and executes message-only slow fills without transferring tokens", async () => {
+    const iVaultBal = (await getAccount(connection, vault)).amount;
+    const valueAmount = new BN(1_000_000_000);
+    const valueRecipient = Keypair.generate().publicKey;
+
+    const multicallHandlerCoder = new MulticallHandlerCoder([], valueRecipient);
+    const message = new AcrossPlusMessageCoder({
+      handler: handlerProgram.programId,
+      readOnlyLen: multicallHandlerCoder.readOnlyLen,
+      valueAmount,
+      accounts: multicallHandlerCoder.compiledMessage.accountKeys,
+      handlerMessage: multicallHandlerCoder.encode(),
+    });
+    updateRelayData({ ...relayData, outputAmount: new BN(0), message: message.encode() });
+
+    // Message-only relays need no recipient token account.
+    const { requestIx, executeIx } = await createSlowFillIx(multicallHandlerCoder, false, program.programId);
+    const requestTx = await sendAndConfirmTransaction(connection, new Transaction().add(requestIx), [relayer]);
+    const requestEvents = await readEventsUntilFound(connection, requestTx, [program]);
+    const request = requestEvents.find((event) => event.name === "requestedV3SlowFill2")?.data;
+    assert.isTrue(request.messageOnly, "Slow fill request should be flagged as message-only");
+
+    const executeTx = await sendAndConfirmTransaction(connection, new Transaction().add(executeIx), [relayer]);
+    const executeEvents = await readEventsUntilFound(connection, executeTx, [program]);
+    const fill = executeEvents.find((event) => event.name === "filledV3Relay")?.data;
+    assert.isTrue(fill.relayExecutionInfo.messageOnly, "Slow fill should be flagged as message-only");
+    assertSE(fill.relayExecutionInfo.normalizedOutputAmount, 0, "No output amount should be paid out");
+
+    assertSE((await getAccount(connection, vault)).amount, iVaultBal, "Vault balance should be unchanged");
+    assertSE(await connection.getBalance(valueRecipient), valueAmount, "Message should send the value amount");
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatusPDA);
+    assert.isTrue("filled" in fillStatusAccount.status, "Message-only slow fill should be marked as filled");
+  });
+
+  it("Rejects slow fill requests with messages above the max message size", async () => {
+    for (const messageSize of [8192, 8193]) {
+      updateRelayData({ ...relayData, message: Buffer.alloc(messageSize, 1) });
+      const relayHash = calculateRelayHashUint8Array(relayData, chainId);
+
+      // Messages this large can only be passed through the instruction params account.
+      const loadRequestParamsInstructions = await loadRequestV3SlowFillParams(program, relayer, state, relayData);
+      for (const loadIx of loadRequestParamsInstructions) {
+        await sendAndConfirmTransaction(connection, new Transaction().add(loadIx), [relayer]);
+      }
+      [requestAccounts.instructionParams] = PublicKey.findProgramAddressSync(
+        [Buffer.from("instruction_params"), relayer.publicKey.toBuffer()],
+        program.programId
+      );
+      const requestSlowFill = () =>
+        program.methods
+          .requestV3SlowFill(Array.from(relayHash), null)
+          .accounts(requestAccounts)
+          .signers([relayer])
+          .rpc();
+
+      if (messageSize <= 8192) {
+        await requestSlowFill();
+        const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatusPDA);
+        assert.isTrue("requestedSlowFill" in fillStatusAccount.status, "Slow fill should be requested");
+      } else {
+        try {
+          await requestSlowFill();
+          assert.fail("Slow fill request should fail for a message above the max message size");
+        } catch (err: any) {
+          assert.include(err.toString(), "MessageTooLarge", "Expected MessageTooLarge error");
+        }
+      }
+    }
+  });
+
+  it("Rejects message handler accounts smuggling the vault as writable", async () => {
+    // The handler cannot sign for the vault, but the message still must not forward it as writable.
+    const transferIx = createTransferCheckedInstruction(
+      vault,
+      mint,
+      finalRecipientATA,
+      handlerSigner,
+      relayData.outputAmount.toNumber(),
+      tokenDecimals
+    );
+    const multicallHandlerCoder = new MulticallHandlerCoder([transferIx]);
+    const message = new AcrossPlusMessageCoder({
+      handler: handlerProgram.programId,
+      readOnlyLen: multicallHandlerCoder.readOnlyLen,
+      valueAmount: new BN(0),
+      accounts: multicallHandlerCoder.compiledMessage.accountKeys,
+      handlerMessage: multicallHandlerCoder.encode(),
+    });
+    updateRelayData({ ...relayData, message: message.encode() });
+
+    const { requestIx, executeIx } = await createSlowFillIx(multicallHandlerCoder);
+    await sendAndConfirmTransaction(connection, new Transaction().add(requestIx), [relayer]);
+    try {
+      await sendAndConfirmTransaction(connection, new Transaction().add(executeIx), [relayer]);
+      assert.fail("Execution should have failed with the vault as writable handler account");
+    } catch (err: any) {
+      assert.include(err.toString(), "ForbiddenHandlerAccount", "Expected ForbiddenHandlerAccount error");
+    }
+  });
+
   describe("Max token distributions within invoked message call", async () => {
     const fillTokenDistributions = async (numberOfDistributions: number, bufferParams = false) => {
       const iVaultBal = (await getAccount(connection, vault)).amount;
@@ -472,7 +617,7 @@ describe("svm_spoke.slow_fill.across_plus", () => {
     assertSE(fillStatusAccount.relayer, relayer.publicKey, "Relayer should match in the fill status");
 
     // Set the current time to past the fill deadline
-    await setCurrentTime(program, state, relayer, new BN(fillStatusAccount.fillDeadline + 1));
+    await setCurrentTime(program, state, new BN(fillStatusAccount.fillDeadline + 1));
 
     const closeFillPdaAccounts = {
       signer: relayer.publicKey,
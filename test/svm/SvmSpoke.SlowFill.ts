@@ -3,27 +3,61 @@ import * as crypto from "crypto";
 import { BN } from "@coral-xyz/anchor";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  createAccount,
   createMint,
   getOrCreateAssociatedTokenAccount,
   mintTo,
   createApproveCheckedInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getMintLen,
+  getTransferFeeAmount,
 } from "@solana/spl-token";
-import { PublicKey, Keypair, Transaction, sendAndConfirmTransaction, ComputeBudgetProgram } from "@solana/web3.js";
+import {
+  PublicKey,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+  ComputeBudgetProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
 import { common } from "./SvmSpoke.common";
 import { MerkleTree } from "@uma/common/dist/MerkleTree";
-import { SlowFillLeaf } from "../../src/types/svm";
+import { SlowFillLeaf, SlowRelayLeafExecution } from "../../src/types/svm";
 import {
+  VAULT_MIGRATION_SLOW_FILLS,
   calculateRelayHashUint8Array,
+  calculateUsedSignatureKey,
+  chainEventCommitment,
   hashNonEmptyMessage,
   intToU8Array32,
+  loadExecuteV3SlowRelayLeavesParams,
+  loadRequestV3SlowFillsBatchParams,
   readEventsUntilFound,
   slowFillHashFn,
 } from "../../src/svm";
 import { testAcrossPlusMessage } from "./utils";
 
 const { provider, connection, program, owner, chainId, seedBalance, initializeState } = common;
-const { recipient, setCurrentTime, assertSE, assert } = common;
+const {
+  recipient,
+  setCurrentTime,
+  setRootBundle,
+  setFillStatus,
+  assertSE,
+  assert,
+  createRoutePda,
+  getVaultAuthority,
+} = common;
+const { createSlowFillLiabilityPda, createVaultRegistryPda, createFillVolumePda, createMessageSchemaPda } = common;
+const { createClaimLiabilityPda, createLiquidityPoolPda } = common;
 
 describe("svm_spoke.slow_fill", () => {
   anchor.setProvider(provider);
@@ -40,7 +74,8 @@ describe("svm_spoke.slow_fill", () => {
     recipientTA: PublicKey,
     otherRelayerTA: PublicKey,
     vault: PublicKey,
-    fillStatus: PublicKey;
+    fillStatus: PublicKey,
+    slowFillLiability: PublicKey;
 
   const relayAmount = 500_000;
   let relayData: SlowFillLeaf["relayData"]; // reused relay data for all tests.
@@ -53,6 +88,7 @@ describe("svm_spoke.slow_fill", () => {
     relayData = newRelayData;
     const relayHashUint8Array = calculateRelayHashUint8Array(relayData, chainId);
     [fillStatus] = PublicKey.findProgramAddressSync([Buffer.from("fills"), relayHashUint8Array], program.programId);
+    slowFillLiability = createSlowFillLiabilityPda(relayData.outputToken, seed);
 
     // recipientTA could be different for each relayData if custom recipient was passed.
     recipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, relayData.recipient)).address;
@@ -64,26 +100,63 @@ describe("svm_spoke.slow_fill", () => {
       instructionParams: program.programId,
       recipient: relayData.recipient, // This could be different from global recipient.
       fillStatus,
+      slowFillLiability,
+      messageSchema: createMessageSchemaPda(relayData.recipient),
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      requesterActivity: program.programId,
+      allowlistEntry: program.programId,
+      eventCommitment: program.programId,
+      fillIndexShard: program.programId,
+      originChainAlias: program.programId,
+      relayerStats: program.programId,
       systemProgram: anchor.web3.SystemProgram.programId,
+      payer: relayer.publicKey,
     };
     fillAccounts = {
       state,
       signer: relayer.publicKey,
       instructionParams: program.programId,
+      vaultRegistry: createVaultRegistryPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
       mint: mint,
       relayerTokenAccount: relayerTA,
       recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
       fillStatus,
+      slowFillLiability,
+      messageSchema: createMessageSchemaPda(relayData.recipient),
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      eventCommitment: program.programId,
+      fillIndexShard: program.programId,
+      originChainAlias: program.programId,
+      relayerStats: program.programId,
+      memoProgram: program.programId,
+      eventForwarder: program.programId,
+      route: program.programId,
+      allowlistEntry: program.programId,
+      repaymentAddressAccount: program.programId,
+      repaymentTokenAccount: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
+      payer: relayer.publicKey,
+      recipientAddressAccount: program.programId,
+      recipientAta: program.programId,
     };
   }
 
   const relaySlowFillRootBundle = async (
     slowRelayLeafRecipient = recipient,
     slowRelayLeafChainId = chainId,
-    message = encodedMessage
+    message = encodedMessage,
+    executionDeadline = 0,
+    restrictedExecutor: PublicKey | null = null,
+    executorGracePeriod = 0
   ) => {
     //TODO: verify that the leaf structure created here is equivalent to the one created by the EVM logic. I think
     // I've gotten the concatenation, endianness, etc correct but want to be sure.
@@ -119,19 +192,37 @@ describe("svm_spoke.slow_fill", () => {
     let stateAccountData = await program.account.state.fetch(state);
     const rootBundleId = stateAccountData.rootBundleId;
 
-    const rootBundleIdBuffer = Buffer.alloc(4);
-    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
-    const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
-    const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
-
-    const relayerRefundRoot = crypto.randomBytes(32);
+    const relayerRefundRoot = Array.from(crypto.randomBytes(32));
 
-    // Relay root bundle
-    const relayRootBundleAccounts = { state, rootBundle, signer: owner, payer: owner, program: program.programId };
-    await program.methods
-      .relayRootBundle(Array.from(relayerRefundRoot), Array.from(slowRelayRoot))
-      .accounts(relayRootBundleAccounts)
-      .rpc();
+    // Root bundles without execution restrictions are set directly, as their slow fill tests don't need the admin path.
+    let rootBundle: PublicKey;
+    if (executionDeadline === 0 && restrictedExecutor === null) {
+      rootBundle = await setRootBundle(state, seed, rootBundleId, relayerRefundRoot, Array.from(slowRelayRoot));
+    } else {
+      const rootBundleIdBuffer = Buffer.alloc(4);
+      rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+      const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+      [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+      const relayRootBundleAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .relayRootBundle(
+          relayerRefundRoot,
+          Array.from(slowRelayRoot),
+          executionDeadline,
+          restrictedExecutor,
+          executorGracePeriod,
+          null
+        )
+        .accounts(relayRootBundleAccounts)
+        .rpc();
+    }
 
     const proofAsNumbers = proof.map((p) => Array.from(p));
     const relayHash = calculateRelayHashUint8Array(slowRelayLeaf.relayData, chainId);
@@ -153,7 +244,7 @@ describe("svm_spoke.slow_fill", () => {
 
   beforeEach(async () => {
     ({ state, seed } = await initializeState());
-    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true)).address; // Initialize vault
+    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)).address;
 
     // mint mint to vault
     await mintTo(connection, payer, mint, vault, provider.publicKey, initialMintAmount);
@@ -184,18 +275,22 @@ describe("svm_spoke.slow_fill", () => {
   });
 
   it("Requests a V3 slow fill, verify the event & state change", async () => {
-    // Attempt to request a slow fill before the exclusivityDeadline
+    // Attempt to request a slow fill before the exclusivityDeadline by another relayer than the exclusive relayer.
     const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
 
     try {
-      await program.methods.requestV3SlowFill(relayHash, relayData).accounts(requestAccounts).signers([relayer]).rpc();
+      await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, signer: otherRelayer.publicKey, payer: otherRelayer.publicKey })
+        .signers([otherRelayer])
+        .rpc();
       assert.fail("Request should have failed due to exclusivity deadline not passed");
     } catch (err: any) {
       assert.include(err.toString(), "NoSlowFillsInExclusivityWindow", "Expected NoSlowFillsInExclusivityWindow error");
     }
 
     // Set the contract time to be after the exclusivityDeadline
-    await setCurrentTime(program, state, relayer, new BN(relayData.exclusivityDeadline + 1));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
 
     const tx = await program.methods
       .requestV3SlowFill(relayHash, relayData)
@@ -214,45 +309,269 @@ describe("svm_spoke.slow_fill", () => {
         assertSE(event.messageHash, hashNonEmptyMessage(value as Buffer), `MessageHash should match`);
       } else assertSE(event[key], value, `${key.charAt(0).toUpperCase() + key.slice(1)} should match`);
     });
+    assertSE(event.relayHash, relayHash, "RelayHash should match");
+    const currentTime = relayData.exclusivityDeadline + 1;
+    assert.strictEqual(event.currentTime, currentTime, "CurrentTime should be the program time");
+    assert.strictEqual(
+      event.secondsUntilFillDeadline,
+      relayData.fillDeadline - currentTime,
+      "SecondsUntilFillDeadline should match"
+    );
+
+    // Versioned event also identifies the destination chain, the requester and the relay hash.
+    const eventV2 = events.find((event) => event.name === "requestedV3SlowFill2")?.data;
+    assert.isNotNull(eventV2, "RequestedV3SlowFill2 event should be emitted");
+    assertSE(eventV2.destinationChainId, chainId, "DestinationChainId should match");
+    assertSE(eventV2.requester, relayer.publicKey, "Requester should be the signer");
+    assertSE(eventV2.relayHash, relayHash, "RelayHash should match");
   });
 
-  it("Fails to request a V3 slow fill if the relay has already been filled", async () => {
+  it("Delays slow fill requests by the grace period after the exclusivity deadline", async () => {
+    // Keys are State field names right padded with zero bytes and values are ABI encoded words.
+    const graceSeconds = 10;
+    const key = Buffer.alloc(32);
+    key.write("slow_fill_grace_seconds");
+    const value = Buffer.alloc(32);
+    value.writeUInt32BE(graceSeconds, 28);
+    await program.methods
+      .setConfig(Array.from(key), Array.from(value), null)
+      .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+      .rpc();
+
+    const timing = await program.methods.getRelayTiming(relayData).accounts({ state }).view();
+    assertSE(timing.exclusivityDeadline, relayData.exclusivityDeadline, "Exclusivity deadline should match");
+    assertSE(
+      timing.slowFillRequestTime,
+      relayData.exclusivityDeadline + graceSeconds + 1,
+      "Slow fill request time should include the grace period"
+    );
+    assertSE(timing.fillDeadline, relayData.fillDeadline, "Fill deadline should match");
+
+    // Slow fill cannot be requested by other relayers than the exclusive relayer at the end of the grace period.
     const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + graceSeconds));
+    try {
+      await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, signer: otherRelayer.publicKey, payer: otherRelayer.publicKey })
+        .signers([otherRelayer])
+        .rpc();
+      assert.fail("Request should have failed within the grace period");
+    } catch (err: any) {
+      assert.include(err.toString(), "NoSlowFillsInExclusivityWindow", "Expected NoSlowFillsInExclusivityWindow error");
+    }
 
-    // Fill the relay first
+    // Grace period does not apply to fast fills of non-exclusive relayers.
+    const slowRelayData = relayData;
+    const slowRequestAccounts = requestAccounts;
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(2) });
+    const fastRelayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const otherFillAccounts = {
+      ...fillAccounts,
+      signer: otherRelayer.publicKey,
+      payer: otherRelayer.publicKey,
+      relayerTokenAccount: otherRelayerTA,
+    };
     const approveIx = await createApproveCheckedInstruction(
-      fillAccounts.relayerTokenAccount,
-      fillAccounts.mint,
-      fillAccounts.state,
-      fillAccounts.signer,
+      otherRelayerTA,
+      mint,
+      state,
+      otherRelayer.publicKey,
       BigInt(relayData.outputAmount.toString()),
       tokenDecimals
     );
     const fillIx = await program.methods
-      .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey)
-      .accounts(fillAccounts)
+      .fillV3Relay(fastRelayHash, relayData, new BN(1), otherRelayer.publicKey, 0, Buffer.alloc(0))
+      .accounts(otherFillAccounts)
       .remainingAccounts(fillRemainingAccounts)
       .instruction();
-    const fillTx = new Transaction().add(approveIx, fillIx);
-    await sendAndConfirmTransaction(connection, fillTx, [relayer]);
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [otherRelayer]);
+    const fastFillStatus = await program.account.fillStatusAccount.fetch(fillStatus);
+    assert.isTrue("filled" in fastFillStatus.status, "Fast fill should succeed within the grace period");
+
+    // Slow fill can be requested once the grace period has passed.
+    await setCurrentTime(program, state, new BN(slowRelayData.exclusivityDeadline + graceSeconds + 1));
+    await program.methods
+      .requestV3SlowFill(relayHash, slowRelayData)
+      .accounts(slowRequestAccounts)
+      .signers([relayer])
+      .rpc();
+    const slowFillStatus = await program.account.fillStatusAccount.fetch(slowRequestAccounts.fillStatus);
+    assert.isTrue("requestedSlowFill" in slowFillStatus.status, "Slow fill should be requested after the grace period");
+  });
+
+  it("Requests V3 slow fills at the inclusive exclusivity and fill deadline boundaries", async () => {
+    const { exclusivityDeadline, fillDeadline } = relayData;
+    let depositId = 1;
+    const requestSlowFill = async (exclusiveRelayer: PublicKey, currentTime: number, expectedError?: string) => {
+      await updateRelayData({ ...relayData, exclusiveRelayer, depositId: intToU8Array32(++depositId) });
+      await setCurrentTime(program, state, new BN(currentTime));
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const request = program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts(requestAccounts)
+        .signers([relayer]);
+      if (!expectedError) {
+        const events = await readEventsUntilFound(connection, await request.rpc(), [program]);
+        return events.find((event) => event.name === "requestedV3SlowFill")?.data;
+      }
+      try {
+        await request.rpc();
+        assert.fail(`Request at ${currentTime} should have failed`);
+      } catch (err: any) {
+        assert.include(err.toString(), expectedError, `Expected ${expectedError} error`);
+      }
+    };
+
+    // Exclusive relayer is allowed to fill until and including the exclusivity deadline, so others can't request the
+    // slow fill until then.
+    await requestSlowFill(otherRelayer.publicKey, exclusivityDeadline, "NoSlowFillsInExclusivityWindow");
+    await requestSlowFill(otherRelayer.publicKey, exclusivityDeadline + 1);
+
+    // Exclusive relayer can request the slow fill of its relay during its own window, as on the EVM SpokePool.
+    await requestSlowFill(relayer.publicKey, exclusivityDeadline);
+    await requestSlowFill(relayer.publicKey, exclusivityDeadline - 1);
+
+    // Once the state opts out, exclusive relayers can't request slow fills during their window either.
+    const key = Buffer.alloc(32);
+    key.write("exclusive_slow_fills_disabled");
+    const value = Buffer.alloc(32);
+    value.writeUInt8(1, 31);
+    await program.methods
+      .setConfig(Array.from(key), Array.from(value), null)
+      .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+      .rpc();
+    await requestSlowFill(relayer.publicKey, exclusivityDeadline, "NoSlowFillsInExclusivityWindow");
+    await requestSlowFill(relayer.publicKey, exclusivityDeadline + 1);
+
+    // Relays without an exclusive relayer have no exclusivity window.
+    await requestSlowFill(PublicKey.default, exclusivityDeadline);
+    await requestSlowFill(PublicKey.default, exclusivityDeadline - 1);
+
+    // Slow fills can be requested until and including the fill deadline, when no time is left to fill.
+    for (const exclusiveRelayer of [relayer.publicKey, PublicKey.default]) {
+      const event = await requestSlowFill(exclusiveRelayer, fillDeadline);
+      assert.strictEqual(event.secondsUntilFillDeadline, 0, "No time should be left at the fill deadline");
+      await requestSlowFill(exclusiveRelayer, fillDeadline + 1, "ExpiredFillDeadline");
+    }
+  });
+
+  it("Fails to request a V3 slow fill if the relay has already been filled", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+
+    // Mark the relay as filled first
+    await setFillStatus(state, relayHash, { filled: {} });
 
     try {
-      await program.methods.requestV3SlowFill(relayHash, relayData).accounts(requestAccounts).signers([relayer]).rpc();
+      await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, signer: otherRelayer.publicKey, payer: otherRelayer.publicKey })
+        .signers([otherRelayer])
+        .rpc();
       assert.fail("Request should have failed due to being within exclusivity window");
     } catch (err: any) {
       assert.include(err.toString(), "NoSlowFillsInExclusivityWindow", "Expected NoSlowFillsInExclusivityWindow error");
     }
 
     // Set the contract time to be after the exclusivityDeadline.
-    await setCurrentTime(program, state, relayer, new BN(relayData.exclusivityDeadline + 1));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
 
     // Attempt to request a slow fill after the relay has been filled.
     try {
       await program.methods.requestV3SlowFill(relayHash, relayData).accounts(requestAccounts).signers([relayer]).rpc();
       assert.fail("Request should have failed due to relay already being filled");
     } catch (err: any) {
-      assert.include(err.toString(), "InvalidSlowFillRequest", "Expected InvalidSlowFillRequest error");
+      assert.include(err.toString(), "RelayAlreadyFilled", "Expected RelayAlreadyFilled error");
+    }
+  });
+
+  it("Rate limits slow fill requests per signer only when enabled", async () => {
+    const rateWindow = 10;
+    const startTime = relayData.exclusivityDeadline + 1;
+    await setCurrentTime(program, state, new BN(startTime));
+
+    const [requesterActivity] = PublicKey.findProgramAddressSync(
+      [Buffer.from("requester_activity"), seed.toArrayLike(Buffer, "le", 8), relayer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Requests a slow fill for a new deposit so that each request targets a fresh fill status PDA.
+    let depositId = 10;
+    const requestNewSlowFill = async (activity: PublicKey = program.programId) => {
+      await updateRelayData({ ...relayData, depositId: intToU8Array32(depositId++) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      return await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, requesterActivity: activity })
+        .signers([relayer])
+        .rpc();
+    };
+
+    // Rate limits are disabled by default, so requests do not need the requester activity account.
+    await requestNewSlowFill();
+    await requestNewSlowFill();
+
+    await program.methods
+      .setSlowFillRateLimit(true, 1, rateWindow)
+      .accounts({ signer: owner, state, program: program.programId })
+      .rpc();
+
+    try {
+      await requestNewSlowFill();
+      assert.fail("Request should have failed without requester activity account");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingRequesterActivity", "Expected MissingRequesterActivity error");
+    }
+
+    await program.methods
+      .initializeRequesterActivity()
+      .accounts({ signer: relayer.publicKey, state, requesterActivity })
+      .signers([relayer])
+      .rpc();
+
+    // Only the configured number of requests fits within the window.
+    await requestNewSlowFill(requesterActivity);
+    try {
+      await requestNewSlowFill(requesterActivity);
+      assert.fail("Request should have failed due to rate limit");
+    } catch (err: any) {
+      assert.include(err.toString(), "RateLimited", "Expected RateLimited error");
+    }
+
+    // Requester activity cannot be closed to reset the count while the window is active.
+    try {
+      await program.methods
+        .closeRequesterActivity()
+        .accounts({ signer: relayer.publicKey, state, requesterActivity })
+        .signers([relayer])
+        .rpc();
+      assert.fail("Closing should have failed while the window is active");
+    } catch (err: any) {
+      assert.include(err.toString(), "RequesterActivityStillActive", "Expected RequesterActivityStillActive error");
+    }
+
+    // The last second of the window is still limited, while the next window accepts requests again.
+    await setCurrentTime(program, state, new BN(startTime + rateWindow - 1));
+    try {
+      await requestNewSlowFill(requesterActivity);
+      assert.fail("Request should have failed due to rate limit");
+    } catch (err: any) {
+      assert.include(err.toString(), "RateLimited", "Expected RateLimited error");
     }
+    await setCurrentTime(program, state, new BN(startTime + rateWindow));
+    await requestNewSlowFill(requesterActivity);
+    const activityData = await program.account.requesterActivity.fetch(requesterActivity);
+    assertSE(activityData.windowStart, startTime + rateWindow, "Window start should be reset");
+    assertSE(activityData.requestCount, 1, "Request count should be reset");
+
+    // Closing succeeds once the signer has been inactive for the whole window.
+    await setCurrentTime(program, state, new BN(startTime + 2 * rateWindow));
+    await program.methods
+      .closeRequesterActivity()
+      .accounts({ signer: relayer.publicKey, state, requesterActivity })
+      .signers([relayer])
+      .rpc();
+    assert.isNull(await connection.getAccountInfo(requesterActivity), "Requester activity should be closed");
   });
 
   it("Fetches FillStatusAccount before and after requestV3SlowFill", async () => {
@@ -264,7 +583,7 @@ describe("svm_spoke.slow_fill", () => {
     assert.isNull(fillStatusAccount, "FillStatusAccount should be uninitialized before requestV3SlowFill");
 
     // Set the contract time to be after the exclusivityDeadline
-    await setCurrentTime(program, state, relayer, new BN(relayData.exclusivityDeadline + 1));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
 
     // Request a slow fill
     await program.methods
@@ -288,7 +607,7 @@ describe("svm_spoke.slow_fill", () => {
     const relayHash = calculateRelayHashUint8Array(relayData, chainId);
 
     // Set the contract time to be after the exclusivityDeadline
-    await setCurrentTime(program, state, relayer, new BN(relayData.exclusivityDeadline + 1));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
 
     // Request a slow fill
     await program.methods
@@ -306,8 +625,406 @@ describe("svm_spoke.slow_fill", () => {
         .rpc();
       assert.fail("Request should have failed due to relay already being requested for slow fill");
     } catch (err: any) {
-      assert.include(err.toString(), "InvalidSlowFillRequest", "Expected InvalidSlowFillRequest error");
+      assert.include(err.toString(), "SlowFillAlreadyRequested", "Expected SlowFillAlreadyRequested error");
+    }
+  });
+
+  it("Records the fast filler as relayer of relays with a requested slow fill", async () => {
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+    await program.methods.requestV3SlowFill(relayHash, relayData).accounts(requestAccounts).signers([relayer]).rpc();
+
+    // Slow fills can not be requested again by another relayer.
+    try {
+      await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, signer: otherRelayer.publicKey, payer: otherRelayer.publicKey })
+        .signers([otherRelayer])
+        .rpc();
+      assert.fail("Request should have failed on the requested slow fill");
+    } catch (err: any) {
+      assert.include(err.toString(), "SlowFillAlreadyRequested", "Expected SlowFillAlreadyRequested error");
+    }
+
+    // Another relayer fast fills the relay after the slow fill request.
+    const approveIx = await createApproveCheckedInstruction(
+      otherRelayerTA,
+      mint,
+      state,
+      otherRelayer.publicKey,
+      BigInt(relayData.outputAmount.toString()),
+      tokenDecimals
+    );
+    const fillIx = await program.methods
+      .fillV3Relay(relayHash, relayData, new BN(1), otherRelayer.publicKey, 0, Buffer.alloc(0))
+      .accounts({
+        ...fillAccounts,
+        signer: otherRelayer.publicKey,
+        payer: otherRelayer.publicKey,
+        relayerTokenAccount: otherRelayerTA,
+      })
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    const tx = await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [otherRelayer]);
+
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+    assert.equal(JSON.stringify(fillStatusAccount.status), `{"filled":{}}`, "FillStatus should be Filled");
+    assertSE(fillStatusAccount.relayer, otherRelayer.publicKey, "Fast filler should replace the requester as relayer");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assertSE(event.relayer, otherRelayer.publicKey, "Fill event relayer should be the fast filler");
+    assert.equal(
+      JSON.stringify(event.relayExecutionInfo.fillType),
+      `{"replacedSlowFill":{}}`,
+      "FillType should be ReplacedSlowFill"
+    );
+  });
+
+  it("Rejects slow fill requests of degenerate relay data", async () => {
+    const currentTime = relayData.exclusivityDeadline + 1;
+    await setCurrentTime(program, state, new BN(currentTime));
+    const staleFillDeadline = currentTime - common.fillDeadlineBuffer.toNumber() - 1;
+
+    // Combinations of invalid fields fail on the first of them in the order checked by the program.
+    const invalidFields: [Partial<SlowFillLeaf["relayData"]>, string][] = [
+      [{ recipient: PublicKey.default }, "InvalidRelayRecipient"],
+      [{ outputToken: PublicKey.default }, "InvalidRelayOutputToken"],
+      [{ outputAmount: new BN(0) }, "EmptyRelay"],
+      [{ fillDeadline: staleFillDeadline }, "StaleFillDeadline"],
+      [{ recipient: PublicKey.default, outputAmount: new BN(0) }, "InvalidRelayRecipient"],
+      [{ outputToken: PublicKey.default, fillDeadline: staleFillDeadline }, "InvalidRelayOutputToken"],
+      [{ outputAmount: new BN(0), fillDeadline: staleFillDeadline }, "EmptyRelay"],
+    ];
+    for (const [fields, error] of invalidFields) {
+      const invalidRelayData = { ...relayData, ...fields };
+      const relayHash = calculateRelayHashUint8Array(invalidRelayData, chainId);
+      const fillStatusSeeds = [Buffer.from("fills"), relayHash];
+      const [invalidFillStatus] = PublicKey.findProgramAddressSync(fillStatusSeeds, program.programId);
+      const invalidAccounts = {
+        recipient: invalidRelayData.recipient,
+        fillStatus: invalidFillStatus,
+        slowFillLiability: createSlowFillLiabilityPda(invalidRelayData.outputToken, seed),
+        messageSchema: createMessageSchemaPda(invalidRelayData.recipient),
+      };
+      try {
+        await program.methods
+          .requestV3SlowFill(Array.from(relayHash), invalidRelayData)
+          .accounts({ ...requestAccounts, ...invalidAccounts })
+          .signers([relayer])
+          .rpc();
+        assert.fail("Request of degenerate relay data should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), error, `Expected ${error} error`);
+      }
+      assert.isNull(await connection.getAccountInfo(invalidFillStatus), "Fill status PDA should not be created");
+    }
+  });
+
+  describe("Signed slow fill requests", () => {
+    const requester = Keypair.generate();
+    const forger = Keypair.generate();
+
+    const requestV3SlowFillFor = async (nonce: BN, signingKey: Keypair = requester) => {
+      const relayHash = calculateRelayHashUint8Array(relayData, chainId);
+      const payload = Buffer.concat([Buffer.from(relayHash), nonce.toArrayLike(Buffer, "le", 8)]);
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signingKey.secretKey,
+        message: payload,
+      });
+      // Ed25519Program places the signature after 16 bytes of offsets and the 32 bytes public key.
+      const requesterSignature = Array.from(ed25519Ix.data.subarray(48, 112));
+
+      const [requesterNonce] = PublicKey.findProgramAddressSync(
+        [Buffer.from("requester_nonce"), requester.publicKey.toBuffer()],
+        program.programId
+      );
+      const [usedSignature] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("used_signature"),
+          seed.toArrayLike(Buffer, "le", 8),
+          calculateUsedSignatureKey(Buffer.from(requesterSignature)),
+        ],
+        program.programId
+      );
+      const requestForAccounts = {
+        signer: relayer.publicKey,
+        state,
+        fillStatus,
+        slowFillLiability,
+        requesterNonce,
+        usedSignature,
+        messageSchema: createMessageSchemaPda(relayData.recipient),
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        requesterActivity: program.programId,
+        eventCommitment: program.programId,
+        fillIndexShard: program.programId,
+        originChainAlias: program.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        program: program.programId,
+      };
+      return await program.methods
+        .requestV3SlowFillFor(Array.from(relayHash), relayData, nonce, requesterSignature)
+        .accounts(requestForAccounts)
+        .preInstructions([ed25519Ix]) // Signature verification must immediately precede the request.
+        .signers([relayer])
+        .rpc();
+    };
+
+    beforeEach(async () => {
+      // Recipient without SOL signs the request and relayer submits it.
+      await updateRelayData({ ...relayData, recipient: requester.publicKey, depositId: intToU8Array32(2) });
+      await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+    });
+
+    it("Requests a V3 slow fill signed by the recipient", async () => {
+      const tx = await requestV3SlowFillFor(new BN(0));
+
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+      assert.isTrue("requestedSlowFill" in fillStatusAccount.status, "FillStatus should be RequestedSlowFill");
+      assertSE(fillStatusAccount.requester, requester.publicKey, "Requester should be the recipient");
+      assertSE(fillStatusAccount.relayer, relayer.publicKey, "Relayer should be the rent payer");
+
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "requestedV3SlowFill")?.data;
+      assertSE(event.recipient, requester.publicKey, "Recipient should match");
+      const eventV2 = events.find((event) => event.name === "requestedV3SlowFill2")?.data;
+      assertSE(eventV2.requester, requester.publicKey, "Requester should be the recipient");
+    });
+
+    it("Fails to request a V3 slow fill with a forged signature", async () => {
+      try {
+        await requestV3SlowFillFor(new BN(0), forger);
+        assert.fail("Request should have failed due to forged signature");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidRequesterSignature", "Expected InvalidRequesterSignature error");
+      }
+    });
+
+    it("Fails to replay a signed V3 slow fill request", async () => {
+      await requestV3SlowFillFor(new BN(5));
+
+      // Replaying the same payload for another deposit to the same recipient must fail on the used nonce.
+      await updateRelayData({ ...relayData, depositId: intToU8Array32(3) });
+      for (const nonce of [new BN(5), new BN(4)]) {
+        try {
+          await requestV3SlowFillFor(nonce);
+          assert.fail("Request should have failed due to used nonce");
+        } catch (err: any) {
+          assert.include(err.toString(), "InvalidRequesterNonce", "Expected InvalidRequesterNonce error");
+        }
+      }
+
+      // Next nonce is accepted.
+      await requestV3SlowFillFor(new BN(6));
+    });
+
+    it("Fails to reuse a consumed requester signature", async () => {
+      await requestV3SlowFillFor(new BN(0));
+
+      // The very same signed request is rejected on its used signature before the nonce is checked.
+      try {
+        await requestV3SlowFillFor(new BN(0));
+        assert.fail("Request should have failed due to used signature");
+      } catch (err: any) {
+        assert.include(err.toString(), "SignatureAlreadyUsed", "Expected SignatureAlreadyUsed error");
+      }
+    });
+  });
+
+  it("Closes fill status PDAs of expired relays back to their original payer", async () => {
+    const requestedSlowFillCloseDelay = 86_400;
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+
+    // Other relayer pays the rent of both fill status PDAs by requesting their slow fills.
+    const requestSlowFill = async () => {
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, signer: otherRelayer.publicKey, payer: otherRelayer.publicKey })
+        .signers([otherRelayer])
+        .rpc();
+      return { relayHash, relayData, fillStatus };
+    };
+    const requested = await requestSlowFill();
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(2) });
+    const filled = await requestSlowFill();
+
+    // Relayer replaces the second slow fill request, which does not make it the rent payer.
+    const approveIx = await createApproveCheckedInstruction(
+      relayerTA,
+      mint,
+      state,
+      relayer.publicKey,
+      BigInt(relayData.outputAmount.toString()),
+      tokenDecimals
+    );
+    const fillIx = await program.methods
+      .fillV3Relay(filled.relayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
+      .accounts(fillAccounts)
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [relayer]);
+    const filledStatus = await program.account.fillStatusAccount.fetch(filled.fillStatus);
+    assertSE(filledStatus.relayer, relayer.publicKey, "Relayer should be recorded");
+    assertSE(filledStatus.payer, otherRelayer.publicKey, "Payer should be the slow fill requester");
+
+    const closeFillStatusAccount = async (closed: typeof filled, payer = otherRelayer.publicKey) => {
+      await program.methods
+        .closeFillStatusAccount(closed.relayHash, closed.relayData)
+        .accounts({
+          signer: owner,
+          state,
+          fillStatus: closed.fillStatus,
+          payer,
+          slowFillLiability: createSlowFillLiabilityPda(closed.relayData.outputToken, seed),
+          program: program.programId,
+        })
+        .rpc();
+    };
+
+    try {
+      await closeFillStatusAccount(filled);
+      assert.fail("Closing fill status PDA should have failed before fill deadline");
+    } catch (err: any) {
+      assert.include(
+        err.toString(),
+        "CanOnlyCloseFillStatusPdaIfFillDeadlinePassed",
+        "Expected CanOnlyCloseFillStatusPdaIfFillDeadlinePassed error"
+      );
+    }
+
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+
+    // Rent can't be redirected to the relayer that filled the relay.
+    try {
+      await closeFillStatusAccount(filled, relayer.publicKey);
+      assert.fail("Closing fill status PDA to another account than its payer should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidOriginalPayer", "Expected InvalidOriginalPayer error");
+    }
+
+    const rent = (await connection.getAccountInfo(filled.fillStatus))!.lamports;
+    const iPayerBal = await connection.getBalance(otherRelayer.publicKey);
+    await closeFillStatusAccount(filled);
+    assert.isNull(await connection.getAccountInfo(filled.fillStatus), "Filled fill status PDA should be closed");
+    assert.strictEqual(
+      await connection.getBalance(otherRelayer.publicKey),
+      iPayerBal + rent,
+      "Payer should receive the reclaimed rent"
+    );
+
+    // Pending slow fill request is kept until its root bundle window has passed.
+    try {
+      await closeFillStatusAccount(requested);
+      assert.fail("Closing requested slow fill should have failed before its root bundle window passed");
+    } catch (err: any) {
+      assert.include(
+        err.toString(),
+        "CanOnlyCloseRequestedSlowFillAfterBundleWindow",
+        "Expected CanOnlyCloseRequestedSlowFillAfterBundleWindow error"
+      );
+    }
+
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + requestedSlowFillCloseDelay + 1));
+    await closeFillStatusAccount(requested);
+    assert.isNull(await connection.getAccountInfo(requested.fillStatus), "Requested fill status PDA should be closed");
+  });
+
+  it("Marks expired relays without fill, after the bundle window for requested slow fills", async () => {
+    const requestedSlowFillCloseDelay = 86_400;
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+
+    const currentRelay = () => ({
+      relayHash: Array.from(calculateRelayHashUint8Array(relayData, chainId)),
+      relayData,
+      fillStatus,
+    });
+    const requested = currentRelay();
+    await program.methods
+      .requestV3SlowFill(requested.relayHash, relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+    const pending = await program.account.slowFillLiability.fetch(slowFillLiability);
+    assertSE(pending.pendingSlowFillAmount, relayData.outputAmount, "Requested output amount should be pending");
+
+    // Relay that was never filled nor requested has no fill status PDA yet.
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(2) });
+    const unseen = currentRelay();
+    assert.isNull(await connection.getAccountInfo(unseen.fillStatus), "Fill status PDA should not exist");
+
+    const markExpired = (marked: typeof unseen) =>
+      program.methods
+        .markExpired(marked.relayHash, marked.relayData)
+        .accounts({
+          signer: owner,
+          state,
+          fillStatus: marked.fillStatus,
+          slowFillLiability: createSlowFillLiabilityPda(marked.relayData.outputToken, seed),
+          systemProgram: anchor.web3.SystemProgram.programId,
+          program: program.programId,
+        })
+        .rpc();
+
+    try {
+      await markExpired(unseen);
+      assert.fail("Marking expired should have failed before fill deadline");
+    } catch (err: any) {
+      assert.include(
+        err.toString(),
+        "CanOnlyMarkExpiredAfterFillDeadline",
+        "Expected CanOnlyMarkExpiredAfterFillDeadline error"
+      );
+    }
+
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + 1));
+    const tx = await markExpired(unseen);
+    const unseenStatus = await program.account.fillStatusAccount.fetch(unseen.fillStatus);
+    assert.isDefined(unseenStatus.status.expired, "Fill status should be Expired");
+    assertSE(unseenStatus.payer, owner, "Payer should be the caller creating the PDA");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "fillStatusExpired")?.data;
+    assertSE(event.relayHash, unseen.relayHash, "Relay hash should match");
+    assertSE(event.depositId, relayData.depositId, "Deposit ID should match");
+    assertSE(event.originChainId, relayData.originChainId, "Origin chain ID should match");
+    assertSE(event.fillDeadline, relayData.fillDeadline, "Fill deadline should match");
+
+    try {
+      await markExpired(unseen);
+      assert.fail("Marking expired twice should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "RelayExpired", "Expected RelayExpired error");
+    }
+
+    // Slow fill leaves of relayed root bundles can still be executed within the bundle window.
+    try {
+      await markExpired(requested);
+      assert.fail("Marking requested slow fill expired should have failed before its root bundle window passed");
+    } catch (err: any) {
+      assert.include(
+        err.toString(),
+        "CanOnlyMarkRequestedSlowFillExpiredAfterBundleWindow",
+        "Expected CanOnlyMarkRequestedSlowFillExpiredAfterBundleWindow error"
+      );
     }
+
+    await setCurrentTime(program, state, new BN(relayData.fillDeadline + requestedSlowFillCloseDelay + 1));
+    const expireTx = await markExpired(requested);
+    const requestedStatus = await program.account.fillStatusAccount.fetch(requested.fillStatus);
+    assert.isDefined(requestedStatus.status.expired, "Requested fill status should be Expired");
+    assertSE(requestedStatus.payer, relayer.publicKey, "Payer should remain the slow fill requester");
+
+    // Slow fill requests expiring unexecuted release their pending output amount.
+    const released = await program.account.slowFillLiability.fetch(slowFillLiability);
+    assertSE(released.pendingSlowFillAmount, new BN(0), "Pending slow fill amount should be released");
+    const expireEvents = await readEventsUntilFound(connection, expireTx, [program]);
+    const liabilityEvent = expireEvents.find((event) => event.name === "slowFillLiabilityChanged")?.data;
+    assertSE(liabilityEvent.mint, relayData.outputToken, "Mint should match");
+    assertSE(liabilityEvent.previousAmount, relayData.outputAmount, "Previous amount should match");
+    assertSE(liabilityEvent.pendingSlowFillAmount, new BN(0), "Pending slow fill amount should match");
   });
 
   it("Executes V3 slow relay leaf, verify the event & state change", async () => {
@@ -328,12 +1045,28 @@ describe("svm_spoke.slow_fill", () => {
       vault: vault,
       tokenProgram: TOKEN_PROGRAM_ID,
       mint: mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
       recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
       program: program.programId,
     };
     try {
       await program.methods
-        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers)
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
         .accounts(executeSlowRelayLeafAccounts)
         .remainingAccounts(fillRemainingAccounts)
         .rpc();
@@ -351,7 +1084,7 @@ describe("svm_spoke.slow_fill", () => {
 
     // Execute V3 slow relay leaf after requesting slow fill
     const ix = await program.methods
-      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers)
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
       .accounts(executeSlowRelayLeafAccounts)
       .remainingAccounts(fillRemainingAccounts)
       .instruction();
@@ -395,254 +1128,418 @@ describe("svm_spoke.slow_fill", () => {
     );
     assertSE(event.relayExecutionInfo.updatedOutputAmount, relayData.outputAmount, "UpdatedOutputAmount should match");
     assert.equal(JSON.stringify(event.relayExecutionInfo.fillType), `{"slowFill":{}}`, "FillType should be SlowFill");
+    assertSE(event.relayExecutionInfo.rootBundleId, rootBundleId, "RootBundleId should match");
+    assertSE(event.relayHash, Array.from(relayHash), "RelayHash should match");
     // These props below are not part of relayData.
     assertSE(event.repaymentChainId, new BN(0), "Repayment chain id should be 0");
     assertSE(event.relayer, PublicKey.default, "Repayment address should be 0");
+
+    // Executed slow fill releases the output amount pending since its request.
+    const liability = await program.account.slowFillLiability.fetch(slowFillLiability);
+    assertSE(liability.pendingSlowFillAmount, new BN(0), "Pending slow fill amount should be released");
   });
 
-  it("Fails to request a V3 slow fill when fills are paused", async () => {
-    // Pause fills
-    const pauseFillsAccounts = {
+  it("Fails to execute V3 slow relay leaf of an unfilled relay without a slow fill request", async () => {
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
+
+    // Fill status PDA exists, but the relay was never requested to be slow filled.
+    await setFillStatus(state, Array.from(relayHash), { unfilled: {} });
+
+    const executeSlowRelayLeafAccounts = {
       state: state,
+      rootBundle: rootBundle,
       signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault: vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
       program: program.programId,
     };
-    await program.methods.pauseFills(true).accounts(pauseFillsAccounts).rpc();
-    const stateAccountData = await program.account.state.fetch(state);
-    assert.isTrue(stateAccountData.pausedFills, "Fills should be paused");
-
-    // Attempt to request a slow fill. This should fail because fills are paused.
     try {
-      const relayHash = calculateRelayHashUint8Array(relayData, chainId);
       await program.methods
-        .requestV3SlowFill(Array.from(relayHash), relayData)
-        .accounts(requestAccounts)
-        .signers([relayer])
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
         .rpc();
-      assert.fail("Request should have failed due to fills being paused");
+      assert.fail("Execution should have failed due to slow fill not being requested");
     } catch (err: any) {
-      assert.instanceOf(err, anchor.AnchorError);
-      assert.strictEqual(err.error.errorCode.code, "FillsArePaused", "Expected error code FillsArePaused");
+      assert.include(err.toString(), "SlowFillNotRequested", "Expected SlowFillNotRequested error");
     }
   });
 
-  it("Fails to execute V3 slow relay leaf to wrong recipient", async () => {
-    // Request V3 slow fill.
+  it("Returns the vault capacity after slow fill requests, executions and expiries", async () => {
+    const assertVaultCapacity = async (vaultBalance: number, pendingSlowFillAmount: number, description: string) => {
+      const capacity = await program.methods
+        .getVaultCapacity()
+        .accounts({ state, mint, vault, vaultLiability: program.programId, slowFillLiability })
+        .view();
+      assertSE(capacity.vaultBalance, new BN(vaultBalance), `Vault balance should match ${description}`);
+      assertSE(
+        capacity.pendingSlowFillAmount,
+        new BN(pendingSlowFillAmount),
+        `Pending slow fill amount should match ${description}`
+      );
+      assertSE(capacity.pendingRefundAmount, new BN(0), `Mint without deposits should have no refunds ${description}`);
+      assertSE(
+        capacity.freeCapacity,
+        new BN(vaultBalance - pendingSlowFillAmount),
+        `Free capacity should match ${description}`
+      );
+      assert.strictEqual(capacity.lastCounterReconciliationTime, 0, "Counters should never have been reconciled");
+    };
+    await assertVaultCapacity(initialMintAmount, 0, "before any request");
+
+    // Request slow fills of the relay in the root bundle and of another relay that expires unexecuted.
     const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
+    const executedFillStatus = requestAccounts.fillStatus;
     await program.methods
       .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
       .accounts(requestAccounts)
       .signers([relayer])
       .rpc();
+    await assertVaultCapacity(initialMintAmount, relayAmount, "after the first request");
 
-    // Try to execute V3 slow relay leaf with wrong recipient token account should fail.
-    const wrongRecipient = Keypair.generate().publicKey;
-    const wrongRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, wrongRecipient)).address;
-    try {
-      const executeSlowRelayLeafAccounts = {
-        state: state,
+    const expiringRelayData = { ...leaf.relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) };
+    await updateRelayData(expiringRelayData);
+    const expiringRelayHash = Array.from(calculateRelayHashUint8Array(expiringRelayData, chainId));
+    await program.methods
+      .requestV3SlowFill(expiringRelayHash, expiringRelayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+    await assertVaultCapacity(initialMintAmount, 2 * relayAmount, "after the second request");
+
+    // Executing the slow fill pays out of the vault balance and releases its pending output amount.
+    const ix = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts({
+        state,
         rootBundle,
         signer: owner,
         instructionParams: program.programId,
-        fillStatus: requestAccounts.fillStatus,
-        vault: vault,
+        fillStatus: executedFillStatus,
+        vault,
         tokenProgram: TOKEN_PROGRAM_ID,
-        mint: mint,
-        recipientTokenAccount: wrongRecipientTA,
+        mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability,
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
         program: program.programId,
-      };
-      await program.methods
-        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers)
-        .accounts(executeSlowRelayLeafAccounts)
-        .remainingAccounts(fillRemainingAccounts)
-        .rpc();
-      assert.fail("Execution should have failed due to wrong recipient token account");
-    } catch (err: any) {
-      assert.instanceOf(err, anchor.AnchorError);
-      assert.strictEqual(err.error.errorCode.code, "ConstraintTokenOwner", "Expected error code ConstraintTokenOwner");
-    }
-  });
+      })
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    const computeBudgetInstruction = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 });
+    await sendAndConfirmTransaction(connection, new Transaction().add(computeBudgetInstruction, ix), [payer]);
+    await assertVaultCapacity(initialMintAmount - relayAmount, relayAmount, "after the execution");
 
-  it("Cannot replay execute V3 slow relay leaf against wrong fill status account", async () => {
-    // Request V3 slow fill for the first recipient.
-    const firstRecipient = Keypair.generate().publicKey;
-    const {
-      relayHash: firstRelayHash,
-      leaf: firstLeaf,
-      rootBundleId: firstRootBundleId,
-      proofAsNumbers: firstProofAsNumbers,
-      rootBundle: firstRootBundle,
-    } = await relaySlowFillRootBundle(firstRecipient);
+    // Expiring the unexecuted slow fill request releases its pending output amount without moving tokens.
+    const requestedSlowFillCloseDelay = 86_400;
+    const expiryTime = expiringRelayData.fillDeadline + requestedSlowFillCloseDelay + 1;
+    await setCurrentTime(program, state, new BN(expiryTime));
     await program.methods
-      .requestV3SlowFill(Array.from(firstRelayHash), firstLeaf.relayData)
-      .accounts(requestAccounts)
-      .signers([relayer])
+      .markExpired(expiringRelayHash, expiringRelayData)
+      .accounts({
+        signer: owner,
+        state,
+        fillStatus: requestAccounts.fillStatus,
+        slowFillLiability,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        program: program.programId,
+      })
       .rpc();
-    const firstRecipientTA = recipientTA; // Global recipientTA will get updated when passing the second relayData.
-    const firstFillStatus = fillStatus; // Global fillStatus will get updated when passing the second relayData.
+    await assertVaultCapacity(initialMintAmount - relayAmount, 0, "after the expiry");
+  });
 
-    // Request V3 slow fill for the second recipient.
-    // Note: we could also had generated single slow relay root for both recipients, but having them relayed in separate
-    // root bundles makes it easier to reuse existing test code.
-    const secondRecipient = Keypair.generate().publicKey;
-    const { relayHash: secondRelayHash, leaf: secondLeaf } = await relaySlowFillRootBundle(secondRecipient);
+  it("Blocks the migration of a state vault while a requested slow fill is pending and previews it", async () => {
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
     await program.methods
-      .requestV3SlowFill(Array.from(secondRelayHash), secondLeaf.relayData)
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
       .accounts(requestAccounts)
       .signers([relayer])
       .rpc();
-    const secondFillStatus = fillStatus; // Global fillStatus got updated with the second relayData.
 
-    const iFirstRecipientBal = (await connection.getTokenAccountBalance(firstRecipientTA)).value.amount;
-    // Execute V3 slow relay leaf for the first recipient.
+    // State vaults created before the vault authority keep their funds until migrated.
+    const vaultAuthority = getVaultAuthority(state);
+    const stateVault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true)).address;
+    await mintTo(connection, payer, mint, stateVault, provider.publicKey, initialMintAmount);
+    const migrateAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      mint,
+      stateVault,
+      vaultAuthority,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      liquidityPool: createLiquidityPoolPda(mint, seed),
+      program: program.programId,
+    };
+    const previewVaultMigration = () =>
+      program.methods.previewVaultMigration(mint, state).accounts(migrateAccounts).view();
+    const migrateVaultAuthority = () => program.methods.migrateVaultAuthority().accounts(migrateAccounts).rpc();
+
+    // The pending slow fill blocks the migration, which its preview reports along with the balance to move.
+    const blockedPreview = await previewVaultMigration();
+    assert.strictEqual(blockedPreview.blockers, VAULT_MIGRATION_SLOW_FILLS, "Only the slow fill should block it");
+    assertSE(blockedPreview.pendingSlowFillAmount, relayAmount, "Preview should include the pending slow fill");
+    assertSE(blockedPreview.stateVaultBalance, initialMintAmount, "Preview should include the state vault balance");
+    assert.isTrue(blockedPreview.vaultExists, "Preview should find the vault authority ATA");
+    try {
+      await migrateVaultAuthority();
+      assert.fail("Migration should be blocked by the pending slow fill");
+    } catch (err: any) {
+      assert.include(err.toString(), "VaultMigrationBlocked", "Expected VaultMigrationBlocked error");
+    }
+
     const executeSlowRelayLeafAccounts = {
       state,
-      rootBundle: firstRootBundle,
+      rootBundle,
       signer: owner,
       instructionParams: program.programId,
-      fillStatus: firstFillStatus,
+      fillStatus: requestAccounts.fillStatus,
       vault,
+      vaultAuthority,
       tokenProgram: TOKEN_PROGRAM_ID,
       mint,
-      recipientTokenAccount: firstRecipientTA,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
       program: program.programId,
     };
-    await program.methods
-      .executeV3SlowRelayLeaf(Array.from(firstRelayHash), firstLeaf, firstRootBundleId, firstProofAsNumbers)
-      .accounts(executeSlowRelayLeafAccounts)
-      .remainingAccounts(fillRemainingAccounts)
-      .rpc();
-    const fFirstRecipientBal = (await connection.getTokenAccountBalance(firstRecipientTA)).value.amount;
-    assert.strictEqual(
-      BigInt(fFirstRecipientBal) - BigInt(iFirstRecipientBal),
-      BigInt(firstLeaf.updatedOutputAmount.toString()),
-      "First recipient balance should be increased by its relay amount"
-    );
-
-    // Try to replay execute V3 slow relay leaf for the first recipient using the fill status account that is derived
-    // from the second relay hash. This should fail due to mismatching relay hash.
-    try {
-      const executeSlowRelayLeafAccounts = {
-        state,
-        rootBundle: firstRootBundle,
-        signer: owner,
-        instructionParams: program.programId,
-        fillStatus: secondFillStatus,
-        vault,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        mint,
-        recipientTokenAccount: firstRecipientTA,
-        program: program.programId,
-      };
-      await program.methods
-        .executeV3SlowRelayLeaf(Array.from(secondRelayHash), firstLeaf, firstRootBundleId, firstProofAsNumbers)
+    const executeSlowRelayLeaf = async () => {
+      const ix = await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
         .accounts(executeSlowRelayLeafAccounts)
         .remainingAccounts(fillRemainingAccounts)
-        .rpc();
-      assert.fail("Execution should have failed due to wrong fill status account");
+        .instruction();
+      const computeBudgetInstruction = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 });
+      await sendAndConfirmTransaction(connection, new Transaction().add(computeBudgetInstruction, ix), [payer]);
+    };
+
+    // State vaults are no vaults of their mint, so the slow fill is paid out of the vault authority ATA.
+    executeSlowRelayLeafAccounts.vault = stateVault;
+    try {
+      await executeSlowRelayLeaf();
+      assert.fail("Execution from the state vault should fail");
     } catch (err: any) {
-      assert.instanceOf(err, anchor.AnchorError);
-      assert.strictEqual(err.error.errorCode.code, "InvalidRelayHash", "Expected error code InvalidRelayHash");
+      assert.include(err.toString(), "ConstraintAssociated", "Expected ConstraintAssociated error");
     }
+
+    executeSlowRelayLeafAccounts.vault = vault;
+    const iVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    const iRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    await executeSlowRelayLeaf();
+
+    const fVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    const fRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    assertSE(BigInt(iVaultBal) - BigInt(fVaultBal), leaf.updatedOutputAmount, "Vault should pay the relay amount");
+    assertSE(BigInt(fRecipientBal) - BigInt(iRecipientBal), leaf.updatedOutputAmount, "Recipient should receive it");
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.isDefined(fillStatusAccount.status.filled, "Fill status should be Filled");
+
+    // Once the slow fill executed nothing blocks the migration, which moves the whole state vault balance.
+    assert.strictEqual((await previewVaultMigration()).blockers, 0, "Nothing should block the migration");
+    await migrateVaultAuthority();
+    const stateVaultBal = (await connection.getTokenAccountBalance(stateVault)).value.amount;
+    assertSE(stateVaultBal, 0, "State vault should be emptied by the migration");
+    const migratedVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    assertSE(BigInt(migratedVaultBal) - BigInt(fVaultBal), initialMintAmount, "Vault should receive the balance");
   });
 
-  it("Fails to execute V3 slow relay leaf for mint inconsistent output_token", async () => {
-    // Request V3 slow fill.
-    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
+  it("Fails to execute V3 slow relay leaf with root bundle account for a different id", async () => {
+    // Relay two root bundles, the second one containing the slow fill leaf.
+    const { rootBundle: otherRootBundle } = await relaySlowFillRootBundle();
+    const { relayHash, leaf, rootBundleId, proofAsNumbers } = await relaySlowFillRootBundle();
     await program.methods
       .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
       .accounts(requestAccounts)
       .signers([relayer])
       .rpc();
 
-    // Create and fund new accounts as derived from wrong mint account.
-    const wrongMint = await createMint(connection, payer, owner, owner, tokenDecimals);
-    const wrongRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, wrongMint, recipient)).address;
-    const wrongVault = (await getOrCreateAssociatedTokenAccount(connection, payer, wrongMint, state, true)).address;
-    await mintTo(connection, payer, wrongMint, wrongVault, provider.publicKey, initialMintAmount);
-
-    // Try to execute V3 slow relay leaf with inconsistent mint should fail.
+    const executeSlowRelayLeafAccounts = {
+      state: state,
+      rootBundle: otherRootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault: vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
     try {
-      const executeSlowRelayLeafAccounts = {
-        state,
-        rootBundle,
-        signer: owner,
-        instructionParams: program.programId,
-        fillStatus: requestAccounts.fillStatus,
-        vault: wrongVault,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        mint: wrongMint,
-        recipientTokenAccount: wrongRecipientTA,
-        program: program.programId,
-      };
       await program.methods
-        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers)
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
         .accounts(executeSlowRelayLeafAccounts)
         .remainingAccounts(fillRemainingAccounts)
         .rpc();
-      assert.fail("Execution should have failed for inconsistent mint");
+      assert.fail("Execution should have failed due to root bundle account not matching the root bundle id");
     } catch (err: any) {
       assert.instanceOf(err, anchor.AnchorError);
-      assert.strictEqual(err.error.errorCode.code, "InvalidMint", "Expected error code InvalidMint");
+      assert.include(
+        ["ConstraintSeeds", "RootBundleIdMismatch"],
+        err.error.errorCode.code,
+        "Expected error code ConstraintSeeds or RootBundleIdMismatch"
+      );
     }
   });
 
-  it("Cannot execute V3 slow relay leaf targeted at another chain", async () => {
-    // Request V3 slow fill for another chain.
-    const anotherChainId = new BN(Math.floor(Math.random() * 1000000));
-    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
-      undefined,
-      anotherChainId
-    );
+  it("Fails to execute V3 slow relay leaf against an emergency deleted root bundle", async () => {
+    const { relayHash, leaf, rootBundleId, rootBundle, proofAsNumbers } = await relaySlowFillRootBundle();
     await program.methods
       .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
       .accounts(requestAccounts)
       .signers([relayer])
       .rpc();
 
-    // Trying to execute V3 slow relay leaf for another chain should fail as the program overrides chain_id that should
-    // invalidate the proofs.
+    await program.methods
+      .emergencyDeleteRootBundle(rootBundleId)
+      .accounts({ state, rootBundle, signer: owner, closer: owner, program: program.programId })
+      .rpc();
+
+    // The deleted root bundle id must not be reused by the next relayed root bundle.
+    const stateAccountData = await program.account.state.fetch(state);
+    assert.strictEqual(stateAccountData.rootBundleId, rootBundleId + 1, "Root bundle id should not be decremented");
+
+    const executeSlowRelayLeafAccounts = {
+      state: state,
+      rootBundle: rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault: vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
     try {
-      const executeSlowRelayLeafAccounts = {
-        state,
-        rootBundle,
-        signer: owner,
-        instructionParams: program.programId,
-        fillStatus: requestAccounts.fillStatus,
-        vault,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        mint,
-        recipientTokenAccount: recipientTA,
-        program: program.programId,
-      };
       await program.methods
-        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers)
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
         .accounts(executeSlowRelayLeafAccounts)
         .remainingAccounts(fillRemainingAccounts)
         .rpc();
-      assert.fail("Execution should have failed for another chain");
+      assert.fail("Execution should have failed against a deleted root bundle");
     } catch (err: any) {
       assert.instanceOf(err, anchor.AnchorError);
-      assert.strictEqual(err.error.errorCode.code, "InvalidMerkleProof", "Expected error code InvalidMerkleProof");
+      assert.strictEqual(
+        err.error.errorCode.code,
+        "AccountNotInitialized",
+        "Expected error code AccountNotInitialized"
+      );
+      assert.strictEqual(err.error.origin, "root_bundle", "Expected the error to name the root bundle account");
     }
   });
 
-  it("Emits zeroed hash for empty message", async () => {
-    // Relay root bundle of slow fill leaf with empty message.
-    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
-      undefined,
-      undefined,
-      Buffer.alloc(0)
-    );
-
-    // Request V3 slow fill
-    const tx1 = await program.methods
+  it("Fails to execute V3 slow relay leaf against a refund only root bundle", async () => {
+    const { relayHash, leaf, proofAsNumbers } = await relaySlowFillRootBundle();
+    await program.methods
       .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
       .accounts(requestAccounts)
       .signers([relayer])
       .rpc();
 
-    // Execute V3 slow relay leaf after requesting slow fill
+    // Relay another root bundle that only carries a relayer refund root.
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+    const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+    await program.methods
+      .relayRootBundle(Array.from(crypto.randomBytes(32)), new Array(32).fill(0), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
     const executeSlowRelayLeafAccounts = {
       state,
       rootBundle,
@@ -652,29 +1549,2647 @@ describe("svm_spoke.slow_fill", () => {
       vault,
       tokenProgram: TOKEN_PROGRAM_ID,
       mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
       recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
       program: program.programId,
     };
-    const tx2 = await program.methods
-      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers)
-      .accounts(executeSlowRelayLeafAccounts)
-      .remainingAccounts(fillRemainingAccounts)
-      .rpc();
+    try {
+      await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+      assert.fail("Execution should have failed against a root bundle without slow relay root");
+    } catch (err: any) {
+      assert.include(err.toString(), "BundleHasNoSlowRelayRoot", "Expected BundleHasNoSlowRelayRoot error");
+    }
+  });
 
-    // Fetch and verify message hash in the RequestedV3SlowFill and FilledV3Relay events
-    const requestEvents = await readEventsUntilFound(connection, tx1, [program]);
-    const requestEvent = requestEvents.find((event) => event.name === "requestedV3SlowFill")?.data;
-    assert.isNotNull(requestEvent, "RequestedV3SlowFill event should be emitted");
-    assertSE(requestEvent.messageHash, new Uint8Array(32), `MessageHash should be zeroed`);
+  it("Executes V3 slow relay leaf only before root bundle execution deadline", async () => {
+    const executionDeadline = (await program.account.state.fetch(state)).currentTime + 10;
 
-    const fillEvents = await readEventsUntilFound(connection, tx2, [program]);
-    const fillEvent = fillEvents.find((event) => event.name === "filledV3Relay")?.data;
-    assert.isNotNull(fillEvent, "FilledV3Relay event should be emitted");
-    assertSE(fillEvent.messageHash, new Uint8Array(32), `MessageHash should be zeroed`);
-    assertSE(
-      fillEvent.relayExecutionInfo.updatedMessageHash,
-      new Uint8Array(32),
-      `UpdatedMessageHash should be zeroed`
-    );
+    const executeSlowRelayLeaf = async (
+      relayHash: number[],
+      leaf: SlowFillLeaf,
+      rootBundleId: number,
+      proof: number[][],
+      rootBundle: PublicKey
+    ) => {
+      await program.methods
+        .requestV3SlowFill(relayHash, leaf.relayData)
+        .accounts(requestAccounts)
+        .signers([relayer])
+        .rpc();
+      const executeSlowRelayLeafAccounts = {
+        state: state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault: vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      return await program.methods
+        .executeV3SlowRelayLeaf(relayHash, leaf, rootBundleId, proof, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+    };
+
+    // Leaf executed before the deadline should succeed. Empty message avoids invoking the handler.
+    const before = await relaySlowFillRootBundle(undefined, undefined, Buffer.alloc(0), executionDeadline);
+    const rootBundleData = await program.account.rootBundle.fetch(before.rootBundle);
+    assertSE(rootBundleData.executionDeadline, executionDeadline, "Execution deadline should be stored");
+    await executeSlowRelayLeaf(
+      Array.from(before.relayHash),
+      before.leaf,
+      before.rootBundleId,
+      before.proofAsNumbers,
+      before.rootBundle
+    );
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.equal(JSON.stringify(fillStatusAccount.status), `{"filled":{}}`, "Leaf should be filled before deadline");
+
+    // Leaf executed after the deadline should fail.
+    const after = await relaySlowFillRootBundle(undefined, undefined, Buffer.alloc(0), executionDeadline);
+    await setCurrentTime(program, state, new BN(executionDeadline + 1));
+    try {
+      await executeSlowRelayLeaf(
+        Array.from(after.relayHash),
+        after.leaf,
+        after.rootBundleId,
+        after.proofAsNumbers,
+        after.rootBundle
+      );
+      assert.fail("Execution should have failed after the root bundle execution deadline");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(
+        err.error.errorCode.code,
+        "BundleExecutionWindowClosed",
+        "Expected error code BundleExecutionWindowClosed"
+      );
+    }
+  });
+
+  it("Executes V3 slow relay leaf only until the relay fill deadline", async () => {
+    const requestAndExecuteSlowRelayLeaf = async (currentTime: (leaf: SlowFillLeaf) => number) => {
+      // Empty message avoids invoking the handler.
+      const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+        undefined,
+        undefined,
+        Buffer.alloc(0)
+      );
+      await program.methods
+        .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+        .accounts(requestAccounts)
+        .signers([relayer])
+        .rpc();
+      await setCurrentTime(program, state, new BN(currentTime(leaf)));
+      const executeSlowRelayLeafAccounts = {
+        state: state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault: vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      return await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+    };
+
+    // Leaf executed exactly at the fill deadline should succeed.
+    await requestAndExecuteSlowRelayLeaf((leaf) => leaf.relayData.fillDeadline);
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.equal(JSON.stringify(fillStatusAccount.status), `{"filled":{}}`, "Leaf should be filled at the deadline");
+
+    // Leaf executed after the fill deadline should fail, as the deposit is then refunded on the origin chain.
+    try {
+      await requestAndExecuteSlowRelayLeaf((leaf) => leaf.relayData.fillDeadline + 1);
+      assert.fail("Execution should have failed after the relay fill deadline");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "ExpiredFillDeadline", "Expected error code ExpiredFillDeadline");
+    }
+    const expiredFillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.equal(
+      JSON.stringify(expiredFillStatusAccount.status),
+      `{"requestedSlowFill":{}}`,
+      "Expired leaf should stay requested"
+    );
+  });
+
+  it("Restricts V3 slow relay leaf execution to the bundle executor until its grace period ends", async () => {
+    const executorGracePeriod = 30; // Shorter than the leaf fill deadline.
+
+    // Relays a bundle restricted to the owner and requests the slow fill for its leaf.
+    const relayRestrictedBundle = async () => {
+      const bundle = await relaySlowFillRootBundle(
+        undefined,
+        undefined,
+        Buffer.alloc(0),
+        0,
+        owner,
+        executorGracePeriod
+      );
+      await program.methods
+        .requestV3SlowFill(Array.from(bundle.relayHash), bundle.leaf.relayData)
+        .accounts(requestAccounts)
+        .signers([relayer])
+        .rpc();
+      return { ...bundle, fillStatus: requestAccounts.fillStatus };
+    };
+    type RestrictedBundle = Awaited<ReturnType<typeof relayRestrictedBundle>>;
+    const executeSlowRelayLeaf = async (bundle: RestrictedBundle, executor: Keypair) => {
+      const executeSlowRelayLeafAccounts = {
+        state: state,
+        rootBundle: bundle.rootBundle,
+        signer: executor.publicKey,
+        instructionParams: program.programId,
+        fillStatus: bundle.fillStatus,
+        vault: vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      return await program.methods
+        .executeV3SlowRelayLeaf(
+          Array.from(bundle.relayHash),
+          bundle.leaf,
+          bundle.rootBundleId,
+          bundle.proofAsNumbers,
+          0,
+          Buffer.alloc(0)
+        )
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .signers([executor])
+        .rpc();
+    };
+
+    const restricted = await relayRestrictedBundle();
+    const expired = await relayRestrictedBundle();
+    const rootBundleData = await program.account.rootBundle.fetch(restricted.rootBundle);
+    const restrictedUntil = (await program.account.state.fetch(state)).currentTime + executorGracePeriod;
+    assert.isTrue(rootBundleData.restrictedExecutor?.equals(owner), "Restricted executor should be stored");
+    assertSE(rootBundleData.restrictedUntil, restrictedUntil, "Restriction end time should be stored");
+
+    // Other executors cannot execute the leaf during the grace period, but the restricted executor can.
+    try {
+      await executeSlowRelayLeaf(restricted, otherRelayer);
+      assert.fail("Execution by other executor should have failed during the grace period");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotRestrictedExecutor", "Expected NotRestrictedExecutor error");
+    }
+    await executeSlowRelayLeaf(restricted, payer);
+    const restrictedFillStatus = await program.account.fillStatusAccount.fetch(restricted.fillStatus);
+    assert.equal(JSON.stringify(restrictedFillStatus.status), `{"filled":{}}`, "Restricted executor should fill");
+
+    // Execution becomes permissionless once the grace period ends.
+    await setCurrentTime(program, state, new BN(restrictedUntil));
+    await executeSlowRelayLeaf(expired, otherRelayer);
+    const expiredFillStatus = await program.account.fillStatusAccount.fetch(expired.fillStatus);
+    assert.equal(JSON.stringify(expiredFillStatus.status), `{"filled":{}}`, "Any executor should fill after expiry");
+  });
+
+  it("Fails to request a V3 slow fill when fills are paused", async () => {
+    // Pause fills
+    const pauseFillsAccounts = {
+      state: state,
+      signer: owner,
+      program: program.programId,
+    };
+    await program.methods.pauseFills(true).accounts(pauseFillsAccounts).rpc();
+    const stateAccountData = await program.account.state.fetch(state);
+    assert.isTrue(stateAccountData.pausedFills, "Fills should be paused");
+
+    // Attempt to request a slow fill. This should fail because fills are paused.
+    try {
+      const relayHash = calculateRelayHashUint8Array(relayData, chainId);
+      await program.methods
+        .requestV3SlowFill(Array.from(relayHash), relayData)
+        .accounts(requestAccounts)
+        .signers([relayer])
+        .rpc();
+      assert.fail("Request should have failed due to fills being paused");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "FillsArePaused", "Expected error code FillsArePaused");
+    }
+  });
+
+  it("Requests a V3 slow fill once the fills pause expires", async () => {
+    const pauseFillsAccounts = { state, signer: owner, program: program.programId };
+    const expiresAt = relayData.exclusivityDeadline + 10;
+    await program.methods.pauseFillsWithExpiry(true, expiresAt).accounts(pauseFillsAccounts).rpc();
+
+    // Fills are still paused right before the expiry.
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await setCurrentTime(program, state, new BN(expiresAt - 1));
+    try {
+      await program.methods.requestV3SlowFill(relayHash, relayData).accounts(requestAccounts).signers([relayer]).rpc();
+      assert.fail("Request should have failed due to fills being paused");
+    } catch (err: any) {
+      assert.include(err.toString(), "FillsArePaused", "Expected FillsArePaused error");
+    }
+
+    // Slow fill request observes the expired pause.
+    await setCurrentTime(program, state, new BN(expiresAt));
+    const tx = await program.methods
+      .requestV3SlowFill(relayHash, relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const pauseExpiredEvent = events.find((event) => event.name === "pauseExpired")?.data;
+    assert.isDefined(pauseExpiredEvent, "PauseExpired event should be emitted");
+    assertSE(pauseExpiredEvent.expiresAt, expiresAt, "PauseExpired event should include the expiry");
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.isTrue("requestedSlowFill" in fillStatusAccount.status, "Slow fill should be requested after the expiry");
+  });
+
+  it("Executes a requested V3 slow fill while only deposits are paused", async () => {
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    // Pause deposits while the slow fill is in flight.
+    const pauseDepositsAccounts = { state, signer: owner, program: program.programId };
+    await program.methods.pauseDeposits(true).accounts(pauseDepositsAccounts).rpc();
+    assert.isTrue((await program.account.state.fetch(state)).pausedDeposits, "Deposits should be paused");
+
+    const iRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    const executeSlowRelayLeafAccounts = {
+      state: state,
+      rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault: vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+    const ix = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts(executeSlowRelayLeafAccounts)
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    const computeBudgetInstruction = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 });
+    await sendAndConfirmTransaction(connection, new Transaction().add(computeBudgetInstruction, ix), [payer]);
+
+    const fRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    assert.strictEqual(
+      BigInt(fRecipientBal) - BigInt(iRecipientBal),
+      BigInt(leaf.updatedOutputAmount.toString()),
+      "Recipient balance should be increased by relay amount"
+    );
+    const fillStatusAccount = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.isTrue("filled" in fillStatusAccount.status, "Slow fill should be executed while deposits are paused");
+  });
+
+  it("Fails to execute V3 slow relay leaf to wrong recipient", async () => {
+    // Request V3 slow fill.
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    // Try to execute V3 slow relay leaf with wrong recipient token account should fail.
+    const wrongRecipient = Keypair.generate().publicKey;
+    const wrongRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, wrongRecipient)).address;
+    try {
+      const executeSlowRelayLeafAccounts = {
+        state: state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault: vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: wrongRecipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+      assert.fail("Execution should have failed due to wrong recipient token account");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "ConstraintTokenOwner", "Expected error code ConstraintTokenOwner");
+    }
+  });
+
+  it("Executes V3 slow relay leaf to a non-ATA token account owned by a PDA recipient", async () => {
+    // Recipient PDA of another program holding a custom keypair-created token account.
+    const [pdaRecipient] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], Keypair.generate().publicKey);
+    const pdaRecipientTA = await createAccount(connection, payer, mint, pdaRecipient, Keypair.generate());
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(pdaRecipient);
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    const executeSlowRelayLeaf = (recipientTokenAccount: PublicKey) =>
+      program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts({
+          state: state,
+          rootBundle,
+          signer: owner,
+          instructionParams: program.programId,
+          fillStatus: requestAccounts.fillStatus,
+          vault: vault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          mint: mint,
+          route: program.programId,
+          depositorCompliance: program.programId,
+          recipientCompliance: program.programId,
+          recipientTokenAccount,
+          deliveryPreference: program.programId,
+          recipientEscrow: program.programId,
+          nativeUnwrapAccount: program.programId,
+          nativeRecipient: program.programId,
+          feeVault: program.programId,
+          liquidityPool: program.programId,
+          eventCommitment: program.programId,
+          memoProgram: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+          executionApproval: program.programId,
+          allowlistEntry: program.programId,
+          program: program.programId,
+        })
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+
+    // Non-ATA token accounts must still be owned by the recipient.
+    const otherTA = await createAccount(connection, payer, mint, Keypair.generate().publicKey, Keypair.generate());
+    try {
+      await executeSlowRelayLeaf(otherTA);
+      assert.fail("Execution should have failed due to token account owner mismatch");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "ConstraintTokenOwner", "Expected error code ConstraintTokenOwner");
+    }
+
+    await executeSlowRelayLeaf(pdaRecipientTA);
+    const recipientAccount = await getAccount(connection, pdaRecipientTA);
+    assertSE(recipientAccount.amount, relayAmount, "PDA recipient's token account should receive the relay amount");
+  });
+
+  it("Cannot replay execute V3 slow relay leaf against wrong fill status account", async () => {
+    // Request V3 slow fill for the first recipient.
+    const firstRecipient = Keypair.generate().publicKey;
+    const {
+      relayHash: firstRelayHash,
+      leaf: firstLeaf,
+      rootBundleId: firstRootBundleId,
+      proofAsNumbers: firstProofAsNumbers,
+      rootBundle: firstRootBundle,
+    } = await relaySlowFillRootBundle(firstRecipient);
+    await program.methods
+      .requestV3SlowFill(Array.from(firstRelayHash), firstLeaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+    const firstRecipientTA = recipientTA; // Global recipientTA will get updated when passing the second relayData.
+    const firstFillStatus = fillStatus; // Global fillStatus will get updated when passing the second relayData.
+
+    // Request V3 slow fill for the second recipient.
+    // Note: we could also had generated single slow relay root for both recipients, but having them relayed in separate
+    // root bundles makes it easier to reuse existing test code.
+    const secondRecipient = Keypair.generate().publicKey;
+    const { relayHash: secondRelayHash, leaf: secondLeaf } = await relaySlowFillRootBundle(secondRecipient);
+    await program.methods
+      .requestV3SlowFill(Array.from(secondRelayHash), secondLeaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+    const secondFillStatus = fillStatus; // Global fillStatus got updated with the second relayData.
+
+    const iFirstRecipientBal = (await connection.getTokenAccountBalance(firstRecipientTA)).value.amount;
+    // Execute V3 slow relay leaf for the first recipient.
+    const executeSlowRelayLeafAccounts = {
+      state,
+      rootBundle: firstRootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: firstFillStatus,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: firstRecipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+    await program.methods
+      .executeV3SlowRelayLeaf(
+        Array.from(firstRelayHash),
+        firstLeaf,
+        firstRootBundleId,
+        firstProofAsNumbers,
+        0,
+        Buffer.alloc(0)
+      )
+      .accounts(executeSlowRelayLeafAccounts)
+      .remainingAccounts(fillRemainingAccounts)
+      .rpc();
+    const fFirstRecipientBal = (await connection.getTokenAccountBalance(firstRecipientTA)).value.amount;
+    assert.strictEqual(
+      BigInt(fFirstRecipientBal) - BigInt(iFirstRecipientBal),
+      BigInt(firstLeaf.updatedOutputAmount.toString()),
+      "First recipient balance should be increased by its relay amount"
+    );
+
+    // Try to replay execute V3 slow relay leaf for the first recipient using the fill status account that is derived
+    // from the second relay hash. This should fail due to mismatching relay hash.
+    try {
+      const executeSlowRelayLeafAccounts = {
+        state,
+        rootBundle: firstRootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: secondFillStatus,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: firstRecipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .executeV3SlowRelayLeaf(
+          Array.from(secondRelayHash),
+          firstLeaf,
+          firstRootBundleId,
+          firstProofAsNumbers,
+          0,
+          Buffer.alloc(0)
+        )
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+      assert.fail("Execution should have failed due to wrong fill status account");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "InvalidRelayHash", "Expected error code InvalidRelayHash");
+    }
+  });
+
+  it("Fails to execute V3 slow relay leaf for mint inconsistent output_token", async () => {
+    // Request V3 slow fill.
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle();
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    // Create and fund new accounts as derived from wrong mint account.
+    const wrongMint = await createMint(connection, payer, owner, owner, tokenDecimals);
+    const wrongRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, wrongMint, recipient)).address;
+    const wrongVault = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, wrongMint, getVaultAuthority(state), true)
+    ).address;
+    await mintTo(connection, payer, wrongMint, wrongVault, provider.publicKey, initialMintAmount);
+
+    // Try to execute V3 slow relay leaf with inconsistent mint should fail.
+    try {
+      const executeSlowRelayLeafAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault: wrongVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: wrongMint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: wrongRecipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(wrongMint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+      assert.fail("Execution should have failed for inconsistent mint");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "InvalidMint", "Expected error code InvalidMint");
+    }
+  });
+
+  it("Cannot execute V3 slow relay leaf targeted at another chain", async () => {
+    // Request V3 slow fill for another chain.
+    const anotherChainId = new BN(Math.floor(Math.random() * 1000000));
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+      undefined,
+      anotherChainId
+    );
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    // Trying to execute V3 slow relay leaf for another chain should fail before its proof is checked.
+    try {
+      const executeSlowRelayLeafAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      };
+      await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .rpc();
+      assert.fail("Execution should have failed for another chain");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(
+        err.error.errorCode.code,
+        "WrongDestinationChain",
+        "Expected error code WrongDestinationChain"
+      );
+    }
+  });
+
+  it("Executes V3 slow relay leaf by burning from vault on burn mode route", async () => {
+    // Configure the route of the output token towards the origin chain in burn mode.
+    const originChainId = new BN(1);
+    const route = createRoutePda(mint, seed, originChainId);
+    const setEnableRouteAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      route,
+      vault,
+      originTokenMint: mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
+    };
+    await program.methods.setEnableRoute(mint, originChainId, true, 0, 0).accounts(setEnableRouteAccounts).rpc();
+    await program.methods
+      .setRouteSettlementMode(mint, originChainId, { burnFromVault: {} })
+      .accounts({ signer: owner, state, route, program: program.programId })
+      .rpc();
+    const routeData = await program.account.route.fetch(route);
+    assert.equal(JSON.stringify(routeData.settlementMode), `{"burnFromVault":{}}`, "Route should be in burn mode");
+
+    // Relay root bundle of slow fill leaf with empty message and request the slow fill.
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+      undefined,
+      undefined,
+      Buffer.alloc(0)
+    );
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    const iVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    const iRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    const iSupply = (await getMint(connection, mint)).supply;
+
+    // Execute V3 slow relay leaf without the recipient token account.
+    const executeSlowRelayLeafAccounts = {
+      state: state,
+      rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault: vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: mint,
+      route,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: program.programId,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+    const tx = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts(executeSlowRelayLeafAccounts)
+      .rpc();
+
+    // Verify tokens were burned from the vault and the recipient was not paid.
+    const fVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    const fRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    const fSupply = (await getMint(connection, mint)).supply;
+    const amount = BigInt(leaf.updatedOutputAmount.toString());
+    assert.strictEqual(BigInt(iVaultBal) - BigInt(fVaultBal), amount, "Vault balance should be reduced by amount");
+    assert.strictEqual(iSupply - fSupply, amount, "Mint supply should be reduced by burned amount");
+    assert.strictEqual(BigInt(fRecipientBal), BigInt(iRecipientBal), "Recipient balance should not change");
+
+    // Fill event is identical to the vault transfer mode.
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "filledV3Relay")?.data;
+    assertSE(event.outputAmount, leaf.relayData.outputAmount, "OutputAmount should match");
+    assertSE(
+      event.relayExecutionInfo.updatedOutputAmount,
+      leaf.updatedOutputAmount,
+      "UpdatedOutputAmount should match"
+    );
+    assert.equal(JSON.stringify(event.relayExecutionInfo.fillType), `{"slowFill":{}}`, "FillType should be SlowFill");
+  });
+
+  it("Grosses up V3 slow relay leaf payouts of fee-on-transfer tokens", async () => {
+    // Create the output token with the TransferFee extension on the 2022 token program.
+    const tokenProgram = TOKEN_2022_PROGRAM_ID;
+    const transferFeeBps = 100; // 1%
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const createMintTx = new Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: mintKeypair.publicKey,
+        space: mintLen,
+        lamports: await connection.getMinimumBalanceForRentExemption(mintLen),
+        programId: tokenProgram,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mintKeypair.publicKey,
+        owner,
+        owner,
+        transferFeeBps,
+        BigInt(initialMintAmount),
+        tokenProgram
+      ),
+      createInitializeMintInstruction(mintKeypair.publicKey, tokenDecimals, owner, owner, tokenProgram)
+    );
+    await sendAndConfirmTransaction(connection, createMintTx, [payer, mintKeypair]);
+    const feeMint = mintKeypair.publicKey;
+    const getTokenAccount = async (accountOwner: PublicKey) => {
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        feeMint,
+        accountOwner,
+        true,
+        undefined,
+        undefined,
+        tokenProgram
+      );
+      return tokenAccount.address;
+    };
+    const feeVault = await getTokenAccount(getVaultAuthority(state));
+    const feeRecipientTA = await getTokenAccount(recipient);
+    await mintTo(connection, payer, feeMint, feeVault, owner, initialMintAmount, undefined, undefined, tokenProgram);
+
+    // Relay root bundle of a slow fill leaf of the fee-on-transfer token without exclusivity and request it.
+    const slowRelayLeaf: SlowFillLeaf = {
+      relayData: {
+        ...relayData,
+        exclusiveRelayer: PublicKey.default,
+        inputToken: feeMint,
+        outputToken: feeMint,
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+        message: Buffer.alloc(0),
+      },
+      chainId,
+      updatedOutputAmount: new BN(relayAmount),
+    };
+    const merkleTree = new MerkleTree<SlowFillLeaf>([slowRelayLeaf], slowFillHashFn);
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    await program.methods
+      .relayRootBundle(Array.from(crypto.randomBytes(32)), Array.from(merkleTree.getRoot()), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    const relayHash = calculateRelayHashUint8Array(slowRelayLeaf.relayData, chainId);
+    const [feeFillStatus] = PublicKey.findProgramAddressSync([Buffer.from("fills"), relayHash], program.programId);
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), slowRelayLeaf.relayData)
+      .accounts({ ...requestAccounts, fillStatus: feeFillStatus })
+      .signers([relayer])
+      .rpc();
+
+    const iVaultBal = (await getAccount(connection, feeVault, undefined, tokenProgram)).amount;
+    const iRecipientBal = (await getAccount(connection, feeRecipientTA, undefined, tokenProgram)).amount;
+
+    const proofAsNumbers = merkleTree.getProof(slowRelayLeaf).map((p) => Array.from(p));
+    await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), slowRelayLeaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: feeFillStatus,
+        vault: feeVault,
+        tokenProgram,
+        mint: feeMint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        recipientTokenAccount: feeRecipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(feeMint, seed),
+        executionApproval: program.programId,
+        allowlistEntry: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    // Recipient receives the full payout while the vault also pays the transfer fee withheld in the recipient account.
+    const fVaultAccount = await getAccount(connection, feeVault, undefined, tokenProgram);
+    const fRecipientAccount = await getAccount(connection, feeRecipientTA, undefined, tokenProgram);
+    const amount = BigInt(relayAmount);
+    const withheldAmount = getTransferFeeAmount(fRecipientAccount)?.withheldAmount ?? BigInt(0);
+    assert.strictEqual(fRecipientAccount.amount - iRecipientBal, amount, "Recipient should receive the full payout");
+    assert.isTrue(withheldAmount > BigInt(0), "Transfer fee should be withheld");
+    assert.strictEqual(
+      iVaultBal - fVaultAccount.amount,
+      amount + withheldAmount,
+      "Vault balance should be reduced by the payout and the transfer fee"
+    );
+  });
+
+  it("Rejects V3 slow relay leaf executions of a Token-2022 mint passing the legacy token program", async () => {
+    const tokenProgram = TOKEN_2022_PROGRAM_ID;
+    const mint2022 = await createMint(
+      connection,
+      payer,
+      owner,
+      owner,
+      tokenDecimals,
+      undefined,
+      undefined,
+      tokenProgram
+    );
+    const getTokenAccount = async (accountOwner: PublicKey) => {
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint2022,
+        accountOwner,
+        true,
+        undefined,
+        undefined,
+        tokenProgram
+      );
+      return tokenAccount.address;
+    };
+    const vault2022 = await getTokenAccount(getVaultAuthority(state));
+    const recipientTA2022 = await getTokenAccount(recipient);
+    await mintTo(connection, payer, mint2022, vault2022, owner, initialMintAmount, undefined, undefined, tokenProgram);
+
+    const slowRelayLeaf: SlowFillLeaf = {
+      relayData: {
+        ...relayData,
+        exclusiveRelayer: PublicKey.default,
+        inputToken: mint2022,
+        outputToken: mint2022,
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+        message: Buffer.alloc(0),
+      },
+      chainId,
+      updatedOutputAmount: new BN(relayAmount),
+    };
+    const merkleTree = new MerkleTree<SlowFillLeaf>([slowRelayLeaf], slowFillHashFn);
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    await program.methods
+      .relayRootBundle(Array.from(crypto.randomBytes(32)), Array.from(merkleTree.getRoot()), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    const relayHash = calculateRelayHashUint8Array(slowRelayLeaf.relayData, chainId);
+    const [fillStatus2022] = PublicKey.findProgramAddressSync([Buffer.from("fills"), relayHash], program.programId);
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), slowRelayLeaf.relayData)
+      .accounts({
+        ...requestAccounts,
+        fillStatus: fillStatus2022,
+        slowFillLiability: createSlowFillLiabilityPda(mint2022, seed),
+      })
+      .signers([relayer])
+      .rpc();
+
+    const proofAsNumbers = merkleTree.getProof(slowRelayLeaf).map((p) => Array.from(p));
+    const executeSlowRelayLeaf = (executeTokenProgram: PublicKey) =>
+      program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), slowRelayLeaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts({
+          state,
+          rootBundle,
+          signer: owner,
+          instructionParams: program.programId,
+          fillStatus: fillStatus2022,
+          vault: vault2022,
+          tokenProgram: executeTokenProgram,
+          mint: mint2022,
+          route: program.programId,
+          depositorCompliance: program.programId,
+          recipientCompliance: program.programId,
+          recipientTokenAccount: recipientTA2022,
+          deliveryPreference: program.programId,
+          recipientEscrow: program.programId,
+          nativeUnwrapAccount: program.programId,
+          nativeRecipient: program.programId,
+          feeVault: program.programId,
+          liquidityPool: program.programId,
+          eventCommitment: program.programId,
+          memoProgram: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(mint2022, seed),
+          executionApproval: program.programId,
+          allowlistEntry: program.programId,
+          program: program.programId,
+        })
+        .rpc();
+
+    // Vault and recipient ATAs are derived with the program owning the mint, so the passed token program must match.
+    try {
+      await executeSlowRelayLeaf(TOKEN_PROGRAM_ID);
+      assert.fail("Execution passing the legacy token program should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "TokenProgramMismatch", "Expected TokenProgramMismatch error");
+    }
+
+    await executeSlowRelayLeaf(tokenProgram);
+    const fRecipientAccount = await getAccount(connection, recipientTA2022, undefined, tokenProgram);
+    assertSE(fRecipientAccount.amount, relayAmount, "Recipient should receive the slow fill");
+  });
+
+  it("Fails to execute V3 slow relay leaf of a mint without vault and reports the missing vault", async () => {
+    // Vaults of the vault authority can't be closed outside of the program, so the vault of this mint is never created.
+    const missingVaultMint = await createMint(connection, payer, owner, owner, tokenDecimals);
+    const missingVault = getAssociatedTokenAddressSync(missingVaultMint, getVaultAuthority(state), true);
+    const recipientTAOfMint = (await getOrCreateAssociatedTokenAccount(connection, payer, missingVaultMint, recipient))
+      .address;
+
+    const slowRelayLeaf: SlowFillLeaf = {
+      relayData: {
+        ...relayData,
+        exclusiveRelayer: PublicKey.default,
+        inputToken: missingVaultMint,
+        outputToken: missingVaultMint,
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)),
+        message: Buffer.alloc(0),
+      },
+      chainId,
+      updatedOutputAmount: new BN(relayAmount),
+    };
+    const merkleTree = new MerkleTree<SlowFillLeaf>([slowRelayLeaf], slowFillHashFn);
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    await program.methods
+      .relayRootBundle(Array.from(crypto.randomBytes(32)), Array.from(merkleTree.getRoot()), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    const relayHash = calculateRelayHashUint8Array(slowRelayLeaf.relayData, chainId);
+    const [missingVaultFillStatus] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fills"), relayHash],
+      program.programId
+    );
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), slowRelayLeaf.relayData)
+      .accounts({ ...requestAccounts, fillStatus: missingVaultFillStatus })
+      .signers([relayer])
+      .rpc();
+
+    const proofAsNumbers = merkleTree.getProof(slowRelayLeaf).map((p) => Array.from(p));
+    const executeSlowRelayLeaf = () =>
+      program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), slowRelayLeaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts({
+          state,
+          rootBundle,
+          signer: owner,
+          instructionParams: program.programId,
+          fillStatus: missingVaultFillStatus,
+          vault: missingVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          mint: missingVaultMint,
+          route: program.programId,
+          depositorCompliance: program.programId,
+          recipientCompliance: program.programId,
+          recipientTokenAccount: recipientTAOfMint,
+          deliveryPreference: program.programId,
+          recipientEscrow: program.programId,
+          nativeUnwrapAccount: program.programId,
+          nativeRecipient: program.programId,
+          feeVault: program.programId,
+          liquidityPool: program.programId,
+          eventCommitment: program.programId,
+          memoProgram: program.programId,
+          rateLimit: program.programId,
+          vaultLiability: program.programId,
+          slowFillLiability: createSlowFillLiabilityPda(missingVaultMint, seed),
+          executionApproval: program.programId,
+          allowlistEntry: program.programId,
+          program: program.programId,
+        })
+        .rpc();
+
+    try {
+      await executeSlowRelayLeaf();
+      assert.fail("Execution should have failed for the missing vault");
+    } catch (err: any) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.strictEqual(err.error.errorCode.code, "VaultMissingForMint", "Expected error code VaultMissingForMint");
+    }
+
+    // Anyone can report the missing vault, which is emitted for monitoring.
+    const reportMissingVault = (reportedMint: PublicKey, reportedVault: PublicKey) =>
+      program.methods
+        .reportMissingVault()
+        .accounts({ state, mint: reportedMint, vault: reportedVault, program: program.programId });
+    assert.isTrue(await reportMissingVault(missingVaultMint, missingVault).view(), "Vault should be reported missing");
+    const tx = await reportMissingVault(missingVaultMint, missingVault).rpc();
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "vaultMissing")?.data;
+    assertSE(event.mint, missingVaultMint, "VaultMissing event should include the mint");
+    assertSE(event.vault, missingVault, "VaultMissing event should include the vault");
+    assert.isFalse(await reportMissingVault(mint, vault).view(), "Existing vault should not be reported missing");
+
+    // Once the vault is created and funded, the slow fill executes.
+    await getOrCreateAssociatedTokenAccount(connection, payer, missingVaultMint, getVaultAuthority(state), true);
+    await mintTo(connection, payer, missingVaultMint, missingVault, owner, initialMintAmount);
+    assert.isFalse(await reportMissingVault(missingVaultMint, missingVault).view(), "Vault should exist");
+    await executeSlowRelayLeaf();
+    const recipientAccount = await getAccount(connection, recipientTAOfMint);
+    assertSE(recipientAccount.amount, relayAmount, "Recipient should receive the slow fill");
+  });
+
+  // Relays a root bundle of count slow fill leaves without messages and requests their slow fills.
+  const relaySlowFillLeaves = async (count: number) => {
+    const slowRelayLeafs: SlowFillLeaf[] = [...Array(count).keys()].map((i) => ({
+      relayData: {
+        ...relayData,
+        exclusiveRelayer: PublicKey.default,
+        depositId: intToU8Array32(Math.floor(Math.random() * 1000000)), // Unique ID for each test.
+        message: Buffer.alloc(0),
+      },
+      chainId,
+      updatedOutputAmount: new BN(relayAmount + i),
+    }));
+    const merkleTree = new MerkleTree<SlowFillLeaf>(slowRelayLeafs, slowFillHashFn);
+
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const [rootBundle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer],
+      program.programId
+    );
+    await program.methods
+      .relayRootBundle(Array.from(crypto.randomBytes(32)), Array.from(merkleTree.getRoot()), 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    const fillStatuses: PublicKey[] = [];
+    for (const slowRelayLeaf of slowRelayLeafs) {
+      const relayHash = calculateRelayHashUint8Array(slowRelayLeaf.relayData, chainId);
+      const [fillStatus] = PublicKey.findProgramAddressSync([Buffer.from("fills"), relayHash], program.programId);
+      await program.methods
+        .requestV3SlowFill(Array.from(relayHash), slowRelayLeaf.relayData)
+        .accounts({ ...requestAccounts, fillStatus })
+        .signers([relayer])
+        .rpc();
+      fillStatuses.push(fillStatus);
+    }
+
+    const leaves: SlowRelayLeafExecution[] = slowRelayLeafs.map((slowFillLeaf) => ({
+      slowFillLeaf,
+      proof: merkleTree.getProof(slowFillLeaf).map((p) => Array.from(p)),
+    }));
+    const instructionParams = await loadExecuteV3SlowRelayLeavesParams(program, owner, state, leaves);
+    const executeAccounts = {
+      signer: owner,
+      instructionParams,
+      state,
+      rootBundle,
+      allowlistEntry: program.programId,
+      mint,
+      vault,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      program: program.programId,
+    };
+    // Remaining accounts of each leaf: fill status, route, delivery preference and recipient token account.
+    const leafAccounts = fillStatuses.map((fillStatus) => [
+      { pubkey: fillStatus, isWritable: true, isSigner: false },
+      { pubkey: program.programId, isWritable: false, isSigner: false },
+      { pubkey: program.programId, isWritable: false, isSigner: false },
+      { pubkey: recipientTA, isWritable: true, isSigner: false },
+    ]);
+
+    return { leaves, rootBundleId, fillStatuses, executeAccounts, leafAccounts };
+  };
+
+  it("Executes multiple V3 slow relay leaves in one instruction", async () => {
+    const { leaves, rootBundleId, fillStatuses, executeAccounts, leafAccounts } = await relaySlowFillLeaves(5);
+
+    const iVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    const iRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+
+    const tx = await program.methods
+      .executeV3SlowRelayLeaves(rootBundleId, null)
+      .accounts(executeAccounts)
+      .remainingAccounts(leafAccounts.flat())
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .rpc();
+
+    // Verify all leaves were paid out of the vault and marked as filled.
+    const totalAmount = leaves.reduce(
+      (total, leaf) => total + BigInt(leaf.slowFillLeaf.updatedOutputAmount.toString()),
+      BigInt(0)
+    );
+    const fVaultBal = (await connection.getTokenAccountBalance(vault)).value.amount;
+    const fRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    assert.strictEqual(BigInt(iVaultBal) - BigInt(fVaultBal), totalAmount, "Vault balance should be reduced by total");
+    assert.strictEqual(BigInt(fRecipientBal) - BigInt(iRecipientBal), totalAmount, "Recipient should receive total");
+    for (const fillStatus of fillStatuses) {
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+      assert.isTrue("filled" in fillStatusAccount.status, "Each relay should be filled");
+    }
+
+    // Verify one FilledV3Relay event per leaf, in order.
+    const events = (await readEventsUntilFound(connection, tx, [program])).filter(
+      (event) => event.name === "filledV3Relay"
+    );
+    assert.strictEqual(events.length, leaves.length, "Should emit a FilledV3Relay event per leaf");
+    events.forEach((event, i) => {
+      assertSE(event.data.depositId, leaves[i].slowFillLeaf.relayData.depositId, "DepositId should match");
+      assertSE(
+        event.data.relayExecutionInfo.updatedOutputAmount,
+        leaves[i].slowFillLeaf.updatedOutputAmount,
+        "UpdatedOutputAmount should match"
+      );
+      assert.equal(JSON.stringify(event.data.relayExecutionInfo.fillType), `{"slowFill":{}}`, "FillType should match");
+    });
+  });
+
+  it("Fails to execute V3 slow relay leaves with remaining accounts in the wrong order", async () => {
+    const { rootBundleId, fillStatuses, executeAccounts, leafAccounts } = await relaySlowFillLeaves(2);
+
+    try {
+      await program.methods
+        .executeV3SlowRelayLeaves(rootBundleId, null)
+        .accounts(executeAccounts)
+        .remainingAccounts([...leafAccounts[1], ...leafAccounts[0]])
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+        .rpc();
+      assert.fail("Execution should fail with swapped leaf accounts");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+    }
+
+    // No leaf of the reverted batch is filled.
+    for (const fillStatus of fillStatuses) {
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+      assert.isTrue("requestedSlowFill" in fillStatusAccount.status, "Relay should still be requested");
+    }
+  });
+
+  const slowFillsBatch = (count: number) => {
+    const relays = [...Array(count).keys()].map(() => ({
+      ...relayData,
+      depositId: intToU8Array32(Math.floor(Math.random() * 1000000)), // Unique ID for each test.
+    }));
+    const fillStatuses = relays.map(
+      (relay) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("fills"), calculateRelayHashUint8Array(relay, chainId)],
+          program.programId
+        )[0]
+    );
+    const batchAccounts = {
+      signer: relayer.publicKey,
+      instructionParams: program.programId,
+      state,
+      slowFillLiability,
+      allowlistEntry: program.programId,
+      requesterActivity: program.programId,
+      eventCommitment: program.programId,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      program: program.programId,
+    };
+    const batchRemainingAccounts = fillStatuses.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+    return { relays, fillStatuses, batchAccounts, batchRemainingAccounts };
+  };
+
+  it("Requests slow fills of a batch of relays, skipping relays that are no longer unfilled", async () => {
+    const { relays, fillStatuses, batchAccounts, batchRemainingAccounts } = slowFillsBatch(3);
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+
+    // The second relay is requested before the batch, e.g. by another keeper.
+    await program.methods
+      .requestV3SlowFill(Array.from(calculateRelayHashUint8Array(relays[1], chainId)), relays[1])
+      .accounts({ ...requestAccounts, fillStatus: fillStatuses[1] })
+      .signers([relayer])
+      .rpc();
+    const iPending = (await program.account.slowFillLiability.fetch(slowFillLiability)).pendingSlowFillAmount;
+
+    const tx = await program.methods
+      .requestV3SlowFillsBatch(relayData.outputToken, relays)
+      .accounts(batchAccounts)
+      .remainingAccounts(batchRemainingAccounts)
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .signers([relayer])
+      .rpc();
+
+    for (const fillStatus of fillStatuses) {
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+      assert.isTrue("requestedSlowFill" in fillStatusAccount.status, "Each relay should be requested");
+      assertSE(fillStatusAccount.requester, relayer.publicKey, "Requester should be the signer");
+    }
+    const fPending = (await program.account.slowFillLiability.fetch(slowFillLiability)).pendingSlowFillAmount;
+    assertSE(fPending.sub(iPending), relayData.outputAmount.muln(2), "Liability should only add requested relays");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const requestedEvents = events.filter((event) => event.name === "requestedV3SlowFill");
+    assert.strictEqual(requestedEvents.length, 2, "Should emit a RequestedV3SlowFill event per requested relay");
+    assertSE(requestedEvents[0].data.depositId, relays[0].depositId, "First requested relay should match");
+    assertSE(requestedEvents[1].data.depositId, relays[2].depositId, "Second requested relay should match");
+    const batch = events.find((event) => event.name === "requestedV3SlowFillsBatch")?.data;
+    assertSE(batch.requester, relayer.publicKey, "Batch requester should be the signer");
+    assert.strictEqual(batch.requested, 2, "Batch should request the unfilled relays");
+    assert.strictEqual(batch.skipped, 1, "Batch should skip the requested relay");
+
+    // Replaying the batch skips every relay without reverting.
+    const replayTx = await program.methods
+      .requestV3SlowFillsBatch(relayData.outputToken, relays)
+      .accounts(batchAccounts)
+      .remainingAccounts(batchRemainingAccounts)
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .signers([relayer])
+      .rpc();
+    const replayEvents = await readEventsUntilFound(connection, replayTx, [program]);
+    const replayBatch = replayEvents.find((event) => event.name === "requestedV3SlowFillsBatch")?.data;
+    assert.strictEqual(replayBatch.requested, 0, "Replayed batch should not request any relay");
+    assert.strictEqual(replayBatch.skipped, 3, "Replayed batch should skip all relays");
+    assertSE(
+      (await program.account.slowFillLiability.fetch(slowFillLiability)).pendingSlowFillAmount,
+      fPending,
+      "Replayed batch should not change the liability"
+    );
+  });
+
+  it("Rejects slow fill batches of invalid sizes or with mismatched fill status accounts", async () => {
+    const { relays, fillStatuses, batchAccounts, batchRemainingAccounts } = slowFillsBatch(2);
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+
+    try {
+      await program.methods
+        .requestV3SlowFillsBatch(relayData.outputToken, [])
+        .accounts(batchAccounts)
+        .signers([relayer])
+        .rpc();
+      assert.fail("Empty batch should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidSlowFillRequestCount", "Expected InvalidSlowFillRequestCount error");
+    }
+
+    const { relays: oversizedRelays, batchRemainingAccounts: oversizedAccounts } = slowFillsBatch(21);
+    try {
+      const instructionParams = await loadRequestV3SlowFillsBatchParams(program, owner, state, oversizedRelays);
+      await program.methods
+        .requestV3SlowFillsBatch(relayData.outputToken, null)
+        .accounts({ ...batchAccounts, signer: owner, instructionParams })
+        .remainingAccounts(oversizedAccounts)
+        .rpc();
+      assert.fail("Oversized batch should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidSlowFillRequestCount", "Expected InvalidSlowFillRequestCount error");
+    }
+
+    try {
+      await program.methods
+        .requestV3SlowFillsBatch(relayData.outputToken, relays)
+        .accounts(batchAccounts)
+        .remainingAccounts(batchRemainingAccounts.slice(1))
+        .signers([relayer])
+        .rpc();
+      assert.fail("Batch with missing fill status accounts should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidSlowFillRequestAccounts", "Expected InvalidSlowFillRequestAccounts error");
+    }
+
+    try {
+      await program.methods
+        .requestV3SlowFillsBatch(relayData.outputToken, relays)
+        .accounts(batchAccounts)
+        .remainingAccounts([...batchRemainingAccounts].reverse())
+        .signers([relayer])
+        .rpc();
+      assert.fail("Batch with fill status accounts in the wrong order should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConstraintSeeds", "Expected ConstraintSeeds error");
+    }
+
+    for (const fillStatus of fillStatuses) {
+      assert.isNull(await connection.getAccountInfo(fillStatus), "No fill status should be created");
+    }
+  });
+
+  it("Emits zeroed hash for empty message", async () => {
+    // Relay root bundle of slow fill leaf with empty message.
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+      undefined,
+      undefined,
+      Buffer.alloc(0)
+    );
+
+    // Request V3 slow fill
+    const tx1 = await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    // Execute V3 slow relay leaf after requesting slow fill
+    const executeSlowRelayLeafAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+    const tx2 = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts(executeSlowRelayLeafAccounts)
+      .remainingAccounts(fillRemainingAccounts)
+      .rpc();
+
+    // Fetch and verify message hash in the RequestedV3SlowFill and FilledV3Relay events
+    const requestEvents = await readEventsUntilFound(connection, tx1, [program]);
+    const requestEvent = requestEvents.find((event) => event.name === "requestedV3SlowFill")?.data;
+    assert.isNotNull(requestEvent, "RequestedV3SlowFill event should be emitted");
+    assertSE(requestEvent.messageHash, new Uint8Array(32), `MessageHash should be zeroed`);
+
+    const fillEvents = await readEventsUntilFound(connection, tx2, [program]);
+    const fillEvent = fillEvents.find((event) => event.name === "filledV3Relay")?.data;
+    assert.isNotNull(fillEvent, "FilledV3Relay event should be emitted");
+    assertSE(fillEvent.messageHash, new Uint8Array(32), `MessageHash should be zeroed`);
+    assertSE(
+      fillEvent.relayExecutionInfo.updatedMessageHash,
+      new Uint8Array(32),
+      `UpdatedMessageHash should be zeroed`
+    );
+  });
+
+  it("Skims protocol fees into the fee vault and lets the fee recipient withdraw them", async () => {
+    const feeRecipient = Keypair.generate();
+    const feeRecipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, feeRecipient.publicKey))
+      .address;
+    const protocolFeeBps = 100;
+    await program.methods
+      .setProtocolFee(protocolFeeBps, feeRecipient.publicKey)
+      .accounts({ state, signer: owner, program: program.programId })
+      .rpc();
+
+    const [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), seed.toArrayLike(Buffer, "le", 8), mint.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeFeeVault()
+      .accounts({ signer: owner, state, mint, feeVault, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    // Relay root bundle of slow fill leaf with empty message and request the slow fill.
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+      undefined,
+      undefined,
+      Buffer.alloc(0)
+    );
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    const executeSlowRelayLeafAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+
+    // Fee vault is required when a protocol fee is charged.
+    try {
+      await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .rpc();
+      assert.fail("Execution should have failed without the fee vault");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingFeeVault", "Expected MissingFeeVault error");
+    }
+
+    const iRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    const tx = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts({ ...executeSlowRelayLeafAccounts, feeVault })
+      .rpc();
+
+    const protocolFee = BigInt(relayAmount * protocolFeeBps) / BigInt(10_000);
+    const fRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    const feeVaultBal = (await connection.getTokenAccountBalance(feeVault)).value.amount;
+    assertSE(BigInt(fRecipientBal) - BigInt(iRecipientBal), BigInt(relayAmount) - protocolFee, "Recipient balance");
+    assertSE(BigInt(feeVaultBal), protocolFee, "Fee vault balance");
+
+    const executeEvents = await readEventsUntilFound(connection, tx, [program]);
+    const accruedEvent = executeEvents.find((event) => event.name === "accruedProtocolFees")?.data;
+    assertSE(accruedEvent.mint, mint, "Accrued fee mint should match");
+    assertSE(accruedEvent.amount, protocolFee, "Accrued fee amount should match");
+
+    const withdrawProtocolFees = async (amount: bigint, signer = feeRecipient) => {
+      return await program.methods
+        .withdrawProtocolFees(new BN(amount.toString()))
+        .accounts({
+          signer: signer.publicKey,
+          state,
+          mint,
+          feeVault,
+          destination: feeRecipientTA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .signers([signer])
+        .rpc();
+    };
+
+    // Owner cannot withdraw fees on behalf of the fee recipient.
+    try {
+      await withdrawProtocolFees(protocolFee, payer);
+      assert.fail("Owner should not be able to withdraw protocol fees");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotProtocolFeeRecipient", "Expected NotProtocolFeeRecipient error");
+    }
+
+    // Cannot withdraw more than accrued.
+    try {
+      await withdrawProtocolFees(protocolFee + BigInt(1));
+      assert.fail("Should not be able to withdraw more than accrued protocol fees");
+    } catch (err: any) {
+      assert.include(err.toString(), "InsufficientProtocolFees", "Expected InsufficientProtocolFees error");
+    }
+
+    // Partially withdraw the accrued fees.
+    const withdrawAmount = protocolFee / BigInt(2);
+    const withdrawTx = await withdrawProtocolFees(withdrawAmount);
+    const fFeeVaultBal = (await connection.getTokenAccountBalance(feeVault)).value.amount;
+    const feeRecipientBal = (await connection.getTokenAccountBalance(feeRecipientTA)).value.amount;
+    assertSE(BigInt(fFeeVaultBal), protocolFee - withdrawAmount, "Fee vault should keep the remaining fees");
+    assertSE(BigInt(feeRecipientBal), withdrawAmount, "Fee recipient should receive the withdrawn fees");
+
+    const withdrawEvents = await readEventsUntilFound(connection, withdrawTx, [program]);
+    const withdrawEvent = withdrawEvents.find((event) => event.name === "withdrewProtocolFees")?.data;
+    assertSE(withdrawEvent.recipient, feeRecipient.publicKey, "Withdrawal recipient should match");
+    assertSE(withdrawEvent.mint, mint, "Withdrawal mint should match");
+    assertSE(withdrawEvent.destination, feeRecipientTA, "Withdrawal destination should match");
+    assertSE(withdrawEvent.amount, withdrawAmount, "Withdrawal amount should match");
+  });
+
+  it("Caps the combined protocol and liquidity pool fees when set", async () => {
+    const maxTotalFeeBps = 200;
+    const setProtocolFee = (feeBps: number) =>
+      program.methods
+        .setProtocolFee(feeBps, owner)
+        .accounts({ state, signer: owner, program: program.programId })
+        .rpc();
+    const setLiquidityPoolFee = (feeBps: number) =>
+      program.methods
+        .setLiquidityPoolConfig(true, feeBps)
+        .accounts({ state, signer: owner, program: program.programId })
+        .rpc();
+
+    // Protocol fee alone can reach the cap, but not exceed it.
+    await setProtocolFee(maxTotalFeeBps);
+    try {
+      await setProtocolFee(maxTotalFeeBps + 1);
+      assert.fail("Protocol fee above the cap should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidProtocolFee", "Expected InvalidProtocolFee error");
+    }
+    try {
+      await setLiquidityPoolFee(1);
+      assert.fail("Liquidity pool fee above the remaining cap should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidLiquidityPoolFee", "Expected InvalidLiquidityPoolFee error");
+    }
+
+    // Both fees below the cap are accepted as long as their sum does not exceed it.
+    await setProtocolFee(maxTotalFeeBps / 2 - 1);
+    await setLiquidityPoolFee(maxTotalFeeBps / 2 + 1);
+    try {
+      await setProtocolFee(maxTotalFeeBps / 2);
+      assert.fail("Combined fees above the cap should be rejected");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidProtocolFee", "Expected InvalidProtocolFee error");
+    }
+
+    const stateAccountData = await program.account.state.fetch(state);
+    assert.strictEqual(stateAccountData.protocolFeeBps, maxTotalFeeBps / 2 - 1, "Protocol fee should not change");
+    assert.strictEqual(stateAccountData.liquidityPoolFeeBps, maxTotalFeeBps / 2 + 1, "Pool fee should not change");
+  });
+
+  it("Chains events of mixed instructions into the event commitment of the epoch", async () => {
+    await program.methods
+      .setEventCommitments(true)
+      .accounts({ state, signer: owner, program: program.programId })
+      .rpc();
+
+    // Epochs are derived from the state current time in testable mode.
+    const epoch = Math.floor((await program.account.state.fetch(state)).currentTime / 86_400);
+    const epochBuffer = Buffer.alloc(4);
+    epochBuffer.writeUInt32LE(epoch);
+    const [eventCommitment] = PublicKey.findProgramAddressSync(
+      [Buffer.from("event_commitment"), seed.toArrayLike(Buffer, "le", 8), epochBuffer],
+      program.programId
+    );
+    await program.methods.initializeEventCommitment(epoch).accounts({ signer: owner, state, eventCommitment }).rpc();
+
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+      undefined,
+      undefined,
+      Buffer.alloc(0)
+    );
+
+    // Event commitment is required once enabled.
+    try {
+      await program.methods
+        .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+        .accounts(requestAccounts)
+        .signers([relayer])
+        .rpc();
+      assert.fail("Request should have failed without the event commitment");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingEventCommitment", "Expected MissingEventCommitment error");
+    }
+
+    const requestTx = await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts({ ...requestAccounts, eventCommitment })
+      .signers([relayer])
+      .rpc();
+
+    const executeSlowRelayLeafAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+    const executeTx = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts(executeSlowRelayLeafAccounts)
+      .rpc();
+
+    // Recompute the chain from the emitted events as an indexer would and compare it against the view.
+    const requestEvents = await readEventsUntilFound(connection, requestTx, [program]);
+    const executeEvents = await readEventsUntilFound(connection, executeTx, [program]);
+    const events = [...requestEvents, ...executeEvents];
+    assert.deepEqual(
+      events.map((event) => event.name),
+      ["requestedV3SlowFill", "requestedV3SlowFill2", "filledV3Relay"],
+      "Committed events should match"
+    );
+
+    const commitment = await program.methods.getEventCommitment(epoch).accounts({ state, eventCommitment }).view();
+    assertSE(commitment.epoch, epoch, "Epoch should match");
+    assertSE(commitment.eventCount, events.length, "Event count should match");
+    assertSE(
+      Buffer.from(commitment.hash),
+      chainEventCommitment(new Uint8Array(32), events),
+      "Commitment should match the chain of emitted events"
+    );
+  });
+
+  it("Accrues liquidity pool fees to external providers without exposing protocol owned vault funds", async () => {
+    const liquidityProvider = otherRelayer;
+    const [liquidityPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("liquidity_pool"), seed.toArrayLike(Buffer, "le", 8), mint.toBuffer()],
+      program.programId
+    );
+    const [liquidityPosition] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("liquidity_position"),
+        seed.toArrayLike(Buffer, "le", 8),
+        mint.toBuffer(),
+        liquidityProvider.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    await program.methods
+      .initializeLiquidityPool()
+      .accounts({ signer: owner, state, mint, liquidityPool, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    const provideLiquidity = async (amount: number) => {
+      const approveIx = await createApproveCheckedInstruction(
+        otherRelayerTA,
+        mint,
+        state,
+        liquidityProvider.publicKey,
+        BigInt(amount),
+        tokenDecimals
+      );
+      const provideIx = await program.methods
+        .provideLiquidity(new BN(amount))
+        .accounts({
+          signer: liquidityProvider.publicKey,
+          state,
+          mint,
+          liquidityPool,
+          liquidityPosition,
+          providerTokenAccount: otherRelayerTA,
+          vault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .instruction();
+      const provideTx = new Transaction().add(approveIx, provideIx);
+      return await sendAndConfirmTransaction(connection, provideTx, [liquidityProvider]);
+    };
+    const removeLiquidity = async (shares: BN) => {
+      return await program.methods
+        .removeLiquidity(shares)
+        .accounts({
+          signer: liquidityProvider.publicKey,
+          state,
+          mint,
+          liquidityPool,
+          liquidityPosition,
+          vault,
+          destination: otherRelayerTA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .signers([liquidityProvider])
+        .rpc();
+    };
+
+    // Liquidity pools are disabled by default.
+    const providedAmount = 2_000_000;
+    try {
+      await provideLiquidity(providedAmount);
+      assert.fail("Providing liquidity should have failed while liquidity pools are disabled");
+    } catch (err: any) {
+      assert.include(err.toString(), "LiquidityPoolsDisabled", "Expected LiquidityPoolsDisabled error");
+    }
+
+    const liquidityPoolFeeBps = 100;
+    await program.methods
+      .setLiquidityPoolConfig(true, liquidityPoolFeeBps)
+      .accounts({ state, signer: owner, program: program.programId })
+      .rpc();
+
+    const iProviderBal = (await connection.getTokenAccountBalance(otherRelayerTA)).value.amount;
+    const provideTx = await provideLiquidity(providedAmount);
+    const provideEvents = await readEventsUntilFound(connection, provideTx, [program]);
+    const provideEvent = provideEvents.find((event) => event.name === "providedLiquidity")?.data;
+    assertSE(provideEvent.shares, providedAmount, "First provider should receive one share per token");
+
+    // Refund bundle inflows to the vault are protocol owned and must not change the liquidity owed to the pool.
+    await mintTo(connection, payer, mint, vault, owner, initialMintAmount);
+    let poolData = await program.account.liquidityPool.fetch(liquidityPool);
+    assertSE(poolData.totalLiquidity, providedAmount, "Vault inflows should not accrue to the pool");
+
+    const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = await relaySlowFillRootBundle(
+      undefined,
+      undefined,
+      Buffer.alloc(0)
+    );
+    await program.methods
+      .requestV3SlowFill(Array.from(relayHash), leaf.relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+
+    const executeSlowRelayLeafAccounts = {
+      state,
+      rootBundle,
+      signer: owner,
+      instructionParams: program.programId,
+      fillStatus: requestAccounts.fillStatus,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint,
+      route: program.programId,
+      depositorCompliance: program.programId,
+      recipientCompliance: program.programId,
+      recipientTokenAccount: recipientTA,
+      deliveryPreference: program.programId,
+      recipientEscrow: program.programId,
+      nativeUnwrapAccount: program.programId,
+      nativeRecipient: program.programId,
+      feeVault: program.programId,
+      liquidityPool: program.programId,
+      eventCommitment: program.programId,
+      memoProgram: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      executionApproval: program.programId,
+      allowlistEntry: program.programId,
+      program: program.programId,
+    };
+
+    // Liquidity pool is required when a liquidity pool fee is charged.
+    try {
+      await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .rpc();
+      assert.fail("Execution should have failed without the liquidity pool");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingLiquidityPool", "Expected MissingLiquidityPool error");
+    }
+
+    const iRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    const executeTx = await program.methods
+      .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+      .accounts({ ...executeSlowRelayLeafAccounts, liquidityPool })
+      .rpc();
+
+    const liquidityPoolFee = BigInt(relayAmount * liquidityPoolFeeBps) / BigInt(10_000);
+    const fRecipientBal = (await connection.getTokenAccountBalance(recipientTA)).value.amount;
+    assertSE(
+      BigInt(fRecipientBal) - BigInt(iRecipientBal),
+      BigInt(relayAmount) - liquidityPoolFee,
+      "Recipient balance"
+    );
+
+    const executeEvents = await readEventsUntilFound(connection, executeTx, [program]);
+    const accruedEvent = executeEvents.find((event) => event.name === "accruedLiquidityPoolFees")?.data;
+    assertSE(accruedEvent.mint, mint, "Accrued fee mint should match");
+    assertSE(accruedEvent.amount, liquidityPoolFee, "Accrued fee amount should match");
+
+    poolData = await program.account.liquidityPool.fetch(liquidityPool);
+    assertSE(poolData.totalLiquidity, BigInt(providedAmount) + liquidityPoolFee, "Pool fee should accrue to the pool");
+
+    // Provider cannot burn more shares than owned and only receives the provided liquidity plus accrued fees.
+    const positionData = await program.account.liquidityPosition.fetch(liquidityPosition);
+    try {
+      await removeLiquidity(positionData.shares.add(new BN(1)));
+      assert.fail("Should not be able to remove more shares than owned");
+    } catch (err: any) {
+      assert.include(err.toString(), "InsufficientLiquidityShares", "Expected InsufficientLiquidityShares error");
+    }
+
+    await removeLiquidity(positionData.shares);
+    const fProviderBal = (await connection.getTokenAccountBalance(otherRelayerTA)).value.amount;
+    assertSE(BigInt(fProviderBal) - BigInt(iProviderBal), liquidityPoolFee, "Provider should only earn the pool fee");
+
+    poolData = await program.account.liquidityPool.fetch(liquidityPool);
+    assertSE(poolData.totalShares, 0, "All shares should be burned");
+    assertSE(poolData.totalLiquidity, 0, "No liquidity should remain owed to the pool");
+  });
+
+  it("Indexes deposit ids of fills and slow fill requests in fill index shards", async () => {
+    await program.methods.setFillIndex(true).accounts({ state, signer: owner, program: program.programId }).rpc();
+
+    const bucket = new BN(3);
+    const [fillIndexShard] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("fill_index"),
+        seed.toArrayLike(Buffer, "le", 8),
+        relayData.originChainId.toArrayLike(Buffer, "le", 8),
+        bucket.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    // Slow fill request for the deposit id at offset 9 of the shard must create it.
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(3 * 1024 + 9) });
+    const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+    try {
+      await program.methods.requestV3SlowFill(relayHash, relayData).accounts(requestAccounts).signers([relayer]).rpc();
+      assert.fail("Request should have failed without the fill index shard");
+    } catch (err: any) {
+      assert.include(err.toString(), "MissingFillIndexShard", "Expected MissingFillIndexShard error");
+    }
+    await program.methods
+      .requestV3SlowFill(relayHash, relayData)
+      .accounts({ ...requestAccounts, fillIndexShard })
+      .signers([relayer])
+      .rpc();
+
+    // Fast fill for the deposit id at offset 10 updates the same shard.
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(3 * 1024 + 10) });
+    const fastRelayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    const approveIx = await createApproveCheckedInstruction(
+      relayerTA,
+      mint,
+      state,
+      relayer.publicKey,
+      BigInt(relayData.outputAmount.toString()),
+      tokenDecimals
+    );
+    const fillIx = await program.methods
+      .fillV3Relay(fastRelayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
+      .accounts({ ...fillAccounts, fillIndexShard })
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [relayer]);
+
+    const shard = await program.methods
+      .getFillIndexShard(relayData.originChainId, bucket)
+      .accounts({ state, fillIndexShard })
+      .view();
+    assertSE(shard.originChainId, relayData.originChainId, "Shard should track the origin chain");
+    assertSE(shard.bucket, bucket, "Shard should track the bucket");
+    const expectedBitmap = new Array(128).fill(0);
+    expectedBitmap[1] = (1 << 1) | (1 << 2); // Offsets 9 and 10.
+    assertSE(shard.bitmap, expectedBitmap, "Bitmap should mark both deposit ids");
+
+    // Deposit ids not fitting into u64 are not indexed, so the request does not require the shard.
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(new BN(2).pow(new BN(64)).addn(9)) });
+    const largeRelayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+    await program.methods
+      .requestV3SlowFill(largeRelayHash, relayData)
+      .accounts(requestAccounts)
+      .signers([relayer])
+      .rpc();
+    const largeFillStatus = await program.account.fillStatusAccount.fetch(requestAccounts.fillStatus);
+    assert.isTrue("requestedSlowFill" in largeFillStatus.status, "Out of range deposit ids should not be indexed");
+  });
+
+  it("Tracks fills in window and lapses of the exclusive relayer", async () => {
+    const thresholdBps = 5000;
+    const key = Buffer.alloc(32);
+    key.write("relayer_lapse_threshold_bps");
+    const value = Buffer.alloc(32);
+    value.writeUInt32BE(thresholdBps, 28);
+    await program.methods
+      .setConfig(Array.from(key), Array.from(value), null)
+      .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+      .rpc();
+
+    const getRelayerStats = (exclusiveRelayer: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("relayer_stats"), seed.toArrayLike(Buffer, "le", 8), exclusiveRelayer.toBuffer()],
+        program.programId
+      )[0];
+    const relayerStats = getRelayerStats(relayer.publicKey);
+    const getThresholdEvent = async (tx: string) =>
+      (await readEventsUntilFound(connection, tx, [program])).find(
+        (event) => event.name === "relayerStatsThresholdCrossed"
+      )?.data;
+
+    // Exclusive relayer fills deposit 1 within their window.
+    await updateRelayData({ ...relayData, depositId: intToU8Array32(1) });
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline));
+    const approveIx = await createApproveCheckedInstruction(
+      relayerTA,
+      mint,
+      state,
+      relayer.publicKey,
+      BigInt(relayData.outputAmount.toString()),
+      tokenDecimals
+    );
+    const fillIx = await program.methods
+      .fillV3Relay(
+        Array.from(calculateRelayHashUint8Array(relayData, chainId)),
+        relayData,
+        new BN(1),
+        relayer.publicKey,
+        0,
+        Buffer.alloc(0)
+      )
+      .accounts({ ...fillAccounts, relayerStats })
+      .remainingAccounts(fillRemainingAccounts)
+      .instruction();
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [relayer]);
+
+    let stats = await program.methods.getRelayerStats(relayer.publicKey).accounts({ state, relayerStats }).view();
+    assertSE(stats.relayer, relayer.publicKey, "Stats should track the exclusive relayer");
+    assertSE(stats.fillsInWindow, 1, "Fill within the window should be recorded");
+    assertSE(stats.lapses, 0, "No lapse should be recorded");
+
+    // Deposits 2 and 3 lapse into slow fill requests, the second one lifting the lapse ratio above the threshold.
+    await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+    const requestSlowFill = async (depositId: number, exclusiveRelayer: PublicKey) => {
+      await updateRelayData({ ...relayData, exclusiveRelayer, depositId: intToU8Array32(depositId) });
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      return await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, relayerStats: getRelayerStats(exclusiveRelayer) })
+        .signers([relayer])
+        .rpc();
+    };
+    assert.isUndefined(await getThresholdEvent(await requestSlowFill(2, relayer.publicKey)), "No event at the threshold");
+    const event = await getThresholdEvent(await requestSlowFill(3, relayer.publicKey));
+    assertSE(event.relayer, relayer.publicKey, "Relayer should match");
+    assertSE(event.fillsInWindow, 1, "Fills in window should match");
+    assertSE(event.lapses, 2, "Lapses should match");
+    assertSE(event.lapseRatioBps, 6666, "Lapse ratio should match");
+    assertSE(event.thresholdBps, thresholdBps, "Threshold should match");
+    assert.isTrue(event.aboveThreshold, "Lapse ratio should be above the threshold");
+
+    // Deposits without an exclusive relayer are not attributed to anyone.
+    await requestSlowFill(4, PublicKey.default);
+    const unattributedStats = await program.account.relayerStats.fetch(getRelayerStats(PublicKey.default));
+    assertSE(unattributedStats.lapses, 0, "Lapse without an exclusive relayer should not be recorded");
+    stats = await program.methods.getRelayerStats(relayer.publicKey).accounts({ state, relayerStats }).view();
+    assertSE(stats.fillsInWindow, 1, "Fills in window should be unchanged");
+    assertSE(stats.lapses, 2, "Lapses should be unchanged");
+  });
+
+  describe("Permissioned mode", () => {
+    const relayerRole = 1;
+    const requesterRole = 2;
+    const executorRole = 4;
+
+    const getAllowlistEntry = (account: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("allowlist"), seed.toArrayLike(Buffer, "le", 8), account.toBuffer()],
+        program.programId
+      )[0];
+
+    const setPermissionedMode = async (enabled: boolean, strictSlowFillRequester: boolean) => {
+      await program.methods
+        .setPermissionedMode(enabled, strictSlowFillRequester)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+    };
+
+    const setAllowlistRoles = async (account: PublicKey, roles: number) => {
+      await program.methods
+        .setAllowlistRoles(account, roles)
+        .accounts({ signer: owner, state, allowlistEntry: getAllowlistEntry(account), program: program.programId })
+        .rpc();
+    };
+
+    const requestSlowFill = async (requester: Keypair, allowlistEntry = getAllowlistEntry(requester.publicKey)) => {
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      await program.methods
+        .requestV3SlowFill(relayHash, relayData)
+        .accounts({ ...requestAccounts, signer: requester.publicKey, payer: requester.publicKey, allowlistEntry })
+        .signers([requester])
+        .rpc();
+    };
+
+    const executeSlowRelayLeaf = async ({
+      relayHash,
+      leaf,
+      rootBundleId,
+      proofAsNumbers,
+      rootBundle,
+    }: Awaited<ReturnType<typeof relaySlowFillRootBundle>>) => {
+      const executeSlowRelayLeafAccounts = {
+        state,
+        rootBundle,
+        signer: owner,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        allowlistEntry: getAllowlistEntry(owner),
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval: program.programId,
+        program: program.programId,
+      };
+      const ix = await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      const computeBudgetInstruction = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 });
+      await sendAndConfirmTransaction(connection, new Transaction().add(computeBudgetInstruction, ix), [payer]);
+    };
+
+    const fillRelay = async (allowlistEntry = getAllowlistEntry(relayer.publicKey)) => {
+      const relayHash = Array.from(calculateRelayHashUint8Array(relayData, chainId));
+      const approveIx = await createApproveCheckedInstruction(
+        relayerTA,
+        mint,
+        state,
+        relayer.publicKey,
+        BigInt(relayData.outputAmount.toString()),
+        tokenDecimals
+      );
+      const fillIx = await program.methods
+        .fillV3Relay(relayHash, relayData, new BN(1), relayer.publicKey, 0, Buffer.alloc(0))
+        .accounts({ ...fillAccounts, allowlistEntry })
+        .remainingAccounts(fillRemainingAccounts)
+        .instruction();
+      await sendAndConfirmTransaction(connection, new Transaction().add(approveIx, fillIx), [relayer]);
+    };
+
+    const assertAllowed = async (action: () => Promise<void>, allowed: boolean, error = "NotAllowlisted") => {
+      if (allowed) return await action();
+      try {
+        await action();
+        assert.fail("Action should have been rejected in permissioned mode");
+      } catch (err: any) {
+        assert.include(err.toString(), error, `Expected ${error} error`);
+      }
+    };
+
+    it("Checks allowlist roles of fills, slow fill requests and slow relay leaf executions", async () => {
+      try {
+        await setAllowlistRoles(relayer.publicKey, 8);
+        assert.fail("Unknown allowlist roles should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidAllowlistRoles", "Expected InvalidAllowlistRoles error");
+      }
+
+      await setPermissionedMode(true, false);
+
+      // Signers without an allowlist entry are rejected.
+      await updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      await assertAllowed(() => fillRelay(program.programId), false);
+
+      // Other relayer always holds the requester role, so that executions can be checked without it for the relayer.
+      await setAllowlistRoles(otherRelayer.publicKey, requesterRole);
+      for (const roles of [0, relayerRole, requesterRole, executorRole, relayerRole | requesterRole | executorRole]) {
+        await setAllowlistRoles(relayer.publicKey, roles);
+        await setAllowlistRoles(owner, roles);
+        const allowlistEntry = await program.account.allowlistEntry.fetch(getAllowlistEntry(relayer.publicKey));
+        assert.strictEqual(allowlistEntry.roles, roles, "Allowlist roles should be set");
+
+        const rootBundleLeaf = await relaySlowFillRootBundle();
+        const canRequest = (roles & requesterRole) !== 0;
+        await assertAllowed(() => requestSlowFill(relayer), canRequest);
+        if (!canRequest) await requestSlowFill(otherRelayer);
+
+        await assertAllowed(() => executeSlowRelayLeaf(rootBundleLeaf), (roles & executorRole) !== 0);
+
+        await updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+        await assertAllowed(() => fillRelay(), (roles & relayerRole) !== 0);
+      }
+
+      // Allowlist entries are not required once permissioned mode is disabled.
+      await setPermissionedMode(false, false);
+      await updateRelayData({ ...relayData, depositId: intToU8Array32(Math.floor(Math.random() * 1000000)) });
+      await fillRelay(program.programId);
+    });
+
+    it("Only accepts slow fill requests of third party relayers with the strict requester check", async () => {
+      await setPermissionedMode(true, true);
+      await setAllowlistRoles(relayer.publicKey, requesterRole);
+      await setAllowlistRoles(otherRelayer.publicKey, relayerRole);
+      await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+
+      // Requester role alone is not enough with the strict requester check.
+      await assertAllowed(() => requestSlowFill(relayer), false, "SlowFillRequesterNotAllowed");
+
+      // Allowlisted relayers can't request slow fills for relays they are the recipient or depositor of.
+      const thirdPartyRelayData = relayData;
+      await updateRelayData({ ...thirdPartyRelayData, recipient: otherRelayer.publicKey });
+      await assertAllowed(() => requestSlowFill(otherRelayer), false, "SlowFillRequesterNotAllowed");
+      await updateRelayData({ ...thirdPartyRelayData, depositor: otherRelayer.publicKey });
+      await assertAllowed(() => requestSlowFill(otherRelayer), false, "SlowFillRequesterNotAllowed");
+
+      await updateRelayData(thirdPartyRelayData);
+      await requestSlowFill(otherRelayer);
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+      assert.isTrue("requestedSlowFill" in fillStatusAccount.status, "Third party relayer should request slow fill");
+
+      // Strict requester check only applies in permissioned mode.
+      await setPermissionedMode(false, true);
+      const depositId = intToU8Array32(2);
+      await updateRelayData({ ...thirdPartyRelayData, depositId, recipient: otherRelayer.publicKey });
+      await requestSlowFill(otherRelayer, program.programId);
+    });
+  });
+
+  describe("Execution approvals", () => {
+    const approver = otherRelayer;
+    const approvalValidity = 10;
+
+    const getExecutionApproval = (leafHash: number[]) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("execution_approval"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(leafHash)],
+        program.programId
+      )[0];
+
+    const setExecutionApprovalThreshold = async (threshold: BN | null) => {
+      await program.methods
+        .setExecutionApprover(approver.publicKey, approvalValidity)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+      await program.methods
+        .setExecutionApprovalThreshold(mint, threshold)
+        .accounts({ signer: owner, state, program: program.programId })
+        .rpc();
+    };
+
+    // Relays a slow fill root bundle without a message and requests the slow fill of its leaf.
+    const relayAndRequestSlowFill = async () => {
+      const rootBundleLeaf = await relaySlowFillRootBundle(recipient, chainId, Buffer.alloc(0));
+      await setCurrentTime(program, state, new BN(relayData.exclusivityDeadline + 1));
+      await program.methods
+        .requestV3SlowFill(Array.from(rootBundleLeaf.relayHash), relayData)
+        .accounts(requestAccounts)
+        .signers([relayer])
+        .rpc();
+      const leafHash = Array.from(Buffer.from(slowFillHashFn(rootBundleLeaf.leaf).slice(2), "hex"));
+      return { ...rootBundleLeaf, leafHash };
+    };
+
+    const createExecutionApproval = async (leafHash: number[], signer = approver) => {
+      return await program.methods
+        .createExecutionApproval(leafHash)
+        .accounts({
+          signer: signer.publicKey,
+          state,
+          executionApproval: getExecutionApproval(leafHash),
+          program: program.programId,
+        })
+        .signers([signer])
+        .rpc();
+    };
+
+    const executeSlowRelayLeaf = async (
+      rootBundleLeaf: Awaited<ReturnType<typeof relaySlowFillRootBundle>>,
+      executionApproval = program.programId,
+      executor = payer
+    ) => {
+      const { relayHash, leaf, rootBundleId, proofAsNumbers, rootBundle } = rootBundleLeaf;
+      const executeSlowRelayLeafAccounts = {
+        state,
+        rootBundle,
+        signer: executor.publicKey,
+        instructionParams: program.programId,
+        fillStatus: requestAccounts.fillStatus,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint,
+        route: program.programId,
+        depositorCompliance: program.programId,
+        recipientCompliance: program.programId,
+        allowlistEntry: program.programId,
+        recipientTokenAccount: recipientTA,
+        deliveryPreference: program.programId,
+        recipientEscrow: program.programId,
+        nativeUnwrapAccount: program.programId,
+        nativeRecipient: program.programId,
+        feeVault: program.programId,
+        liquidityPool: program.programId,
+        eventCommitment: program.programId,
+        memoProgram: program.programId,
+        rateLimit: program.programId,
+        vaultLiability: program.programId,
+        slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+        executionApproval,
+        program: program.programId,
+      };
+      return await program.methods
+        .executeV3SlowRelayLeaf(Array.from(relayHash), leaf, rootBundleId, proofAsNumbers, 0, Buffer.alloc(0))
+        .accounts(executeSlowRelayLeafAccounts)
+        .signers([executor])
+        .rpc();
+    };
+
+    const assertFilled = async () => {
+      const fillStatusAccount = await program.account.fillStatusAccount.fetch(fillStatus);
+      assert.isTrue("filled" in fillStatusAccount.status, "Slow fill leaf should be executed");
+    };
+
+    it("Executes slow fill leaves under the threshold without an execution approval", async () => {
+      await setExecutionApprovalThreshold(new BN(relayAmount));
+      await executeSlowRelayLeaf(await relayAndRequestSlowFill());
+      await assertFilled();
+
+      // Leaves of mints without a threshold are also permissionless.
+      await setExecutionApprovalThreshold(null);
+      assert.isEmpty((await program.account.state.fetch(state)).executionApprovalThresholds, "Threshold not removed");
+      await executeSlowRelayLeaf(await relayAndRequestSlowFill());
+      await assertFilled();
+    });
+
+    it("Executes slow fill leaves above the threshold with an execution approval", async () => {
+      await setExecutionApprovalThreshold(new BN(relayAmount - 1));
+      const rootBundleLeaf = await relayAndRequestSlowFill();
+      const executionApproval = getExecutionApproval(rootBundleLeaf.leafHash);
+
+      try {
+        await executeSlowRelayLeaf(rootBundleLeaf);
+        assert.fail("Execution above the threshold should have failed without an approval");
+      } catch (err: any) {
+        assert.include(err.toString(), "MissingExecutionApproval", "Expected MissingExecutionApproval error");
+      }
+
+      try {
+        await createExecutionApproval(rootBundleLeaf.leafHash, relayer);
+        assert.fail("Execution approval should have failed for a non-approver");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotExecutionApprover", "Expected NotExecutionApprover error");
+      }
+
+      const tx = await createExecutionApproval(rootBundleLeaf.leafHash);
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "createdExecutionApproval")?.data;
+      assert.deepEqual(Array.from(event.leafHash), rootBundleLeaf.leafHash, "Leaf hash should match");
+      assertSE(event.approver, approver.publicKey, "Approver should match");
+
+      await executeSlowRelayLeaf(rootBundleLeaf, executionApproval);
+      await assertFilled();
+    });
+
+    it("Rejects expired execution approvals", async () => {
+      await setExecutionApprovalThreshold(new BN(relayAmount - 1));
+      const rootBundleLeaf = await relayAndRequestSlowFill();
+      const executionApproval = getExecutionApproval(rootBundleLeaf.leafHash);
+      await createExecutionApproval(rootBundleLeaf.leafHash);
+
+      const approvedAt = (await program.account.executionApproval.fetch(executionApproval)).approvedAt;
+      await setCurrentTime(program, state, new BN(approvedAt + approvalValidity + 1));
+      try {
+        await executeSlowRelayLeaf(rootBundleLeaf, executionApproval);
+        assert.fail("Execution should have failed with an expired approval");
+      } catch (err: any) {
+        assert.include(err.toString(), "ExpiredExecutionApproval", "Expected ExpiredExecutionApproval error");
+      }
+
+      // Approval is still usable on the last second of its validity.
+      await setCurrentTime(program, state, new BN(approvedAt + approvalValidity));
+      await executeSlowRelayLeaf(rootBundleLeaf, executionApproval);
+      await assertFilled();
+    });
+
+    it("Rejects revoked execution approvals", async () => {
+      await setExecutionApprovalThreshold(new BN(relayAmount - 1));
+      const rootBundleLeaf = await relayAndRequestSlowFill();
+      const executionApproval = getExecutionApproval(rootBundleLeaf.leafHash);
+      await createExecutionApproval(rootBundleLeaf.leafHash);
+
+      const revokeAccounts = { signer: relayer.publicKey, state, executionApproval, program: program.programId };
+      try {
+        await program.methods
+          .revokeExecutionApproval(rootBundleLeaf.leafHash)
+          .accounts(revokeAccounts)
+          .signers([relayer])
+          .rpc();
+        assert.fail("Revoking should have failed for a non-approver");
+      } catch (err: any) {
+        assert.include(err.toString(), "NotExecutionApprover", "Expected NotExecutionApprover error");
+      }
+
+      const tx = await program.methods
+        .revokeExecutionApproval(rootBundleLeaf.leafHash)
+        .accounts({ ...revokeAccounts, signer: approver.publicKey })
+        .signers([approver])
+        .rpc();
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "revokedExecutionApproval")?.data;
+      assertSE(event.approver, approver.publicKey, "Approver should match");
+      assert.isNull(await connection.getAccountInfo(executionApproval), "Execution approval should be closed");
+
+      try {
+        await executeSlowRelayLeaf(rootBundleLeaf, executionApproval);
+        assert.fail("Execution should have failed with a revoked approval");
+      } catch (err: any) {
+        assert.include(err.toString(), "AccountNotInitialized", "Expected AccountNotInitialized error");
+      }
+    });
+
+    describe("Emergency executions", () => {
+      const emergencyExecutor = relayer;
+
+      const setEmergencyExecutor = async (executor: PublicKey) => {
+        // Keys are State field names right padded with zero bytes, the zero address clears the emergency executor.
+        const key = Buffer.alloc(32);
+        key.write("emergency_executor");
+        await program.methods
+          .setConfig(Array.from(key), Array.from(executor.toBuffer()), null)
+          .accounts({ state, signer: owner, adminNonce: program.programId, program: program.programId })
+          .rpc();
+      };
+
+      const pauseFills = async () => {
+        await program.methods.pauseFills(true).accounts({ state, signer: owner, program: program.programId }).rpc();
+      };
+
+      it("Rejects slow fill leaf executions while fills are paused without an emergency executor", async () => {
+        await setExecutionApprovalThreshold(null);
+        const rootBundleLeaf = await relayAndRequestSlowFill();
+        await createExecutionApproval(rootBundleLeaf.leafHash);
+        await pauseFills();
+
+        try {
+          await executeSlowRelayLeaf(rootBundleLeaf, getExecutionApproval(rootBundleLeaf.leafHash));
+          assert.fail("Execution should have failed while fills are paused");
+        } catch (err: any) {
+          assert.include(err.toString(), "FillsArePaused", "Expected FillsArePaused error");
+        }
+      });
+
+      it("Rejects unapproved emergency executions while fills are paused", async () => {
+        await setEmergencyExecutor(emergencyExecutor.publicKey);
+        assertSE(
+          (await program.account.state.fetch(state)).emergencyExecutor,
+          emergencyExecutor.publicKey,
+          "Emergency executor should be set"
+        );
+        const rootBundleLeaf = await relayAndRequestSlowFill();
+        await pauseFills();
+
+        // Emergency executions require an execution approver.
+        try {
+          await executeSlowRelayLeaf(rootBundleLeaf, program.programId, emergencyExecutor);
+          assert.fail("Emergency execution should have failed without an execution approver");
+        } catch (err: any) {
+          assert.include(err.toString(), "MissingEmergencyApprover", "Expected MissingEmergencyApprover error");
+        }
+
+        // Leaves under the threshold still require an approval during emergency executions.
+        await setExecutionApprovalThreshold(null);
+        try {
+          await executeSlowRelayLeaf(rootBundleLeaf, program.programId, emergencyExecutor);
+          assert.fail("Emergency execution should have failed without an approval");
+        } catch (err: any) {
+          assert.include(err.toString(), "MissingExecutionApproval", "Expected MissingExecutionApproval error");
+        }
+
+        // Other executors are rejected even with an approval.
+        await createExecutionApproval(rootBundleLeaf.leafHash);
+        try {
+          await executeSlowRelayLeaf(rootBundleLeaf, getExecutionApproval(rootBundleLeaf.leafHash));
+          assert.fail("Execution should have failed for executors other than the emergency executor");
+        } catch (err: any) {
+          assert.include(err.toString(), "FillsArePaused", "Expected FillsArePaused error");
+        }
+
+        // Clearing the emergency executor rejects its executions.
+        await setEmergencyExecutor(PublicKey.default);
+        assert.isNull((await program.account.state.fetch(state)).emergencyExecutor, "Emergency executor not cleared");
+        try {
+          await executeSlowRelayLeaf(rootBundleLeaf, getExecutionApproval(rootBundleLeaf.leafHash), emergencyExecutor);
+          assert.fail("Execution should have failed after clearing the emergency executor");
+        } catch (err: any) {
+          assert.include(err.toString(), "FillsArePaused", "Expected FillsArePaused error");
+        }
+      });
+
+      it("Executes approved slow fill leaves by the emergency executor while fills are paused", async () => {
+        await setEmergencyExecutor(emergencyExecutor.publicKey);
+        await setExecutionApprovalThreshold(null);
+        const rootBundleLeaf = await relayAndRequestSlowFill();
+        await createExecutionApproval(rootBundleLeaf.leafHash);
+        await pauseFills();
+
+        const tx = await executeSlowRelayLeaf(
+          rootBundleLeaf,
+          getExecutionApproval(rootBundleLeaf.leafHash),
+          emergencyExecutor
+        );
+        await assertFilled();
+        const events = await readEventsUntilFound(connection, tx, [program]);
+        const event = events.find((event) => event.name === "filledV3Relay")?.data;
+        assert.isTrue(event.relayExecutionInfo.emergencyExecution, "Emergency execution should be flagged");
+
+        // Executions while fills are not paused are not flagged.
+        await program.methods.pauseFills(false).accounts({ state, signer: owner, program: program.programId }).rpc();
+        const unpausedTx = await executeSlowRelayLeaf(await relayAndRequestSlowFill());
+        const unpausedEvents = await readEventsUntilFound(connection, unpausedTx, [program]);
+        const unpausedEvent = unpausedEvents.find((event) => event.name === "filledV3Relay")?.data;
+        assert.isFalse(unpausedEvent.relayExecutionInfo.emergencyExecution, "Regular execution should not be flagged");
+      });
+    });
   });
 });
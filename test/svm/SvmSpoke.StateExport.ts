@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import {
+  EXPORT_CLAIM_ACCOUNT_RECORD_SIZE,
+  EXPORT_FILL_STATUS_RECORD_SIZE,
+  EXPORT_HEADER_SIZE,
+  EXPORT_LAYOUT_VERSION,
+  EXPORT_LIABILITY_RECORD_SIZE,
+} from "../../src/svm";
+import { common } from "./SvmSpoke.common";
+
+const { provider, program, owner, initializeState, assert } = common;
+
+describe("svm_spoke.state_export", () => {
+  anchor.setProvider(provider);
+
+  let state: PublicKey, seed: BN;
+
+  beforeEach(async () => {
+    ({ state, seed } = await initializeState());
+  });
+
+  const importAccounts = () => ({ state, signer: owner, systemProgram: anchor.web3.SystemProgram.programId });
+  const accountMetas = (pubkeys: PublicKey[], isWritable: boolean) =>
+    pubkeys.map((pubkey) => ({ pubkey, isWritable, isSigner: false }));
+  const findAddress = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const u64 = (value: number) => {
+    const encoded = Buffer.alloc(8);
+    encoded.writeBigUInt64LE(BigInt(value));
+    return encoded;
+  };
+
+  // Pages start with the layout version, record count and continuation token (u32, little-endian).
+  const exportPage = (records: Buffer[], nextPage = 0) => {
+    const header = Buffer.alloc(EXPORT_HEADER_SIZE);
+    header.writeUInt8(EXPORT_LAYOUT_VERSION, 0);
+    header.writeUInt8(records.length, 1);
+    header.writeUInt32LE(nextPage, 2);
+    return Buffer.concat([header, ...records]);
+  };
+
+  // Synthetic fill status of a relay hash: status (u8), relayer, fill_deadline (u32), requester and payer.
+  const fillStatusRecord = (relayHash: Buffer, status: number) => {
+    const fillDeadline = Buffer.alloc(4);
+    fillDeadline.writeUInt32LE(Math.floor(Date.now() / 1000));
+    const record = Buffer.concat([
+      relayHash,
+      Buffer.from([status]),
+      Keypair.generate().publicKey.toBuffer(),
+      fillDeadline,
+      Keypair.generate().publicKey.toBuffer(),
+      Keypair.generate().publicKey.toBuffer(),
+    ]);
+    assert.strictEqual(record.length, EXPORT_FILL_STATUS_RECORD_SIZE, "Fill status record size should match");
+    return record;
+  };
+
+  it("Round trips fill statuses across export pages", async () => {
+    const relayHashes = Array.from({ length: 8 }, () => randomBytes(32));
+    const records = relayHashes.map((relayHash, i) => fillStatusRecord(relayHash, i % 2 === 0 ? 2 : 1));
+    const fillStatuses = relayHashes.map((relayHash) => findAddress([Buffer.from("fills"), relayHash]));
+
+    // Imports are split to fit the transaction size limit.
+    for (const [start, end] of [
+      [0, 4],
+      [4, 8],
+    ]) {
+      await program.methods
+        .importFillStatuses(exportPage(records.slice(start, end)))
+        .accounts(importAccounts())
+        .remainingAccounts(accountMetas(fillStatuses.slice(start, end), true))
+        .rpc();
+    }
+
+    const fillStatus = await program.account.fillStatusAccount.fetch(fillStatuses[1]);
+    assert.isDefined(fillStatus.status.requestedSlowFill, "Imported fill status should be RequestedSlowFill");
+
+    // Only 7 records fit the return data, so the continuation token points to the last record.
+    const exportFillStatuses = (page: number): Promise<Buffer> =>
+      program.methods
+        .exportFillStatuses(page, relayHashes.map((relayHash) => Array.from(relayHash)))
+        .remainingAccounts(accountMetas(fillStatuses, false))
+        .view();
+    const firstPage = await exportFillStatuses(0);
+    assert.isTrue(firstPage.equals(exportPage(records.slice(0, 7), 7)), "First export page should match");
+    const lastPage = await exportFillStatuses(firstPage.readUInt32LE(2));
+    assert.isTrue(lastPage.equals(exportPage(records.slice(7))), "Last export page should match");
+  });
+
+  it("Round trips claim accounts and liabilities", async () => {
+    const claimKeys = [0, 1].map(() => ({
+      mint: Keypair.generate().publicKey,
+      refundAddress: Keypair.generate().publicKey,
+    }));
+    const claimRecords = claimKeys.map(({ mint, refundAddress }, i) => {
+      const initializer = Keypair.generate().publicKey.toBuffer();
+      return Buffer.concat([mint.toBuffer(), refundAddress.toBuffer(), u64(1_000_000 * (i + 1)), initializer]);
+    });
+    const claimAccounts = claimKeys.map(({ mint, refundAddress }) =>
+      findAddress([Buffer.from("claim_account"), mint.toBuffer(), refundAddress.toBuffer()])
+    );
+    claimRecords.forEach((record) => assert.strictEqual(record.length, EXPORT_CLAIM_ACCOUNT_RECORD_SIZE));
+
+    await program.methods
+      .importClaimAccounts(exportPage(claimRecords))
+      .accounts(importAccounts())
+      .remainingAccounts(accountMetas(claimAccounts, true))
+      .rpc();
+    const exportedClaims: Buffer = await program.methods
+      .exportClaimAccounts(0, claimKeys)
+      .remainingAccounts(accountMetas(claimAccounts, false))
+      .view();
+    assert.isTrue(exportedClaims.equals(exportPage(claimRecords)), "Exported claim accounts should match");
+
+    const mints = [0, 1].map(() => Keypair.generate().publicKey);
+    const liabilityRecords = mints.map((mint, i) => Buffer.concat([mint.toBuffer(), u64(500 * (i + 1))]));
+    const transferLiabilities = mints.map((mint) => findAddress([Buffer.from("transfer_liability"), mint.toBuffer()]));
+    liabilityRecords.forEach((record) => assert.strictEqual(record.length, EXPORT_LIABILITY_RECORD_SIZE));
+
+    await program.methods
+      .importLiabilities(exportPage(liabilityRecords))
+      .accounts(importAccounts())
+      .remainingAccounts(accountMetas(transferLiabilities, true))
+      .rpc();
+    const exportedLiabilities: Buffer = await program.methods
+      .exportLiabilities(0, mints)
+      .remainingAccounts(accountMetas(transferLiabilities, false))
+      .view();
+    assert.isTrue(exportedLiabilities.equals(exportPage(liabilityRecords)), "Exported liabilities should match");
+  });
+
+  it("Rejects malformed pages, existing accounts and imports after the genesis window", async () => {
+    const relayHash = randomBytes(32);
+    const page = exportPage([fillStatusRecord(relayHash, 2)]);
+    const fillStatus = findAddress([Buffer.from("fills"), relayHash]);
+    const importFillStatus = (importedPage: Buffer, importedFillStatus = fillStatus) =>
+      program.methods
+        .importFillStatuses(importedPage)
+        .accounts(importAccounts())
+        .remainingAccounts(accountMetas([importedFillStatus], true))
+        .rpc();
+
+    // Record count must match the records and the passed accounts.
+    const miscountedPage = Buffer.from(page);
+    miscountedPage.writeUInt8(2, 1);
+    try {
+      await importFillStatus(miscountedPage);
+      assert.fail("Importing a page with a wrong record count should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidExportPage", "Expected InvalidExportPage error");
+    }
+
+    // Imports only create accounts, so importing the same page twice fails.
+    await importFillStatus(page);
+    try {
+      await importFillStatus(page);
+      assert.fail("Importing an existing fill status should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "already in use", "Expected account already in use error");
+    }
+
+    // Relaying the first root bundle ends the genesis window.
+    const rootBundleId = (await program.account.state.fetch(state)).rootBundleId;
+    const rootBundleIdBuffer = Buffer.alloc(4);
+    rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+    const rootBundle = findAddress([Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer]);
+    const root = Array.from(randomBytes(32));
+    await program.methods
+      .relayRootBundle(root, root, 0, null, 0, null)
+      .accounts({
+        state,
+        rootBundle,
+        signer: owner,
+        payer: owner,
+        adminNonce: program.programId,
+        program: program.programId,
+      })
+      .rpc();
+
+    const otherRelayHash = randomBytes(32);
+    try {
+      await importFillStatus(
+        exportPage([fillStatusRecord(otherRelayHash, 2)]),
+        findAddress([Buffer.from("fills"), otherRelayHash])
+      );
+      assert.fail("Importing after the first root bundle should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "ImportsDisabled", "Expected ImportsDisabled error");
+    }
+  });
+});
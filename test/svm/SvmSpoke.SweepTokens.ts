@@ -0,0 +1,423 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createApproveCheckedInstruction,
+  createMint,
+  freezeAccount,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  thawAccount,
+} from "@solana/spl-token";
+import { ComputeBudgetProgram, Keypair, PublicKey } from "@solana/web3.js";
+import { common } from "./SvmSpoke.common";
+import { VAULT_MIGRATION_FROZEN, readEventsUntilFound } from "../../src/svm";
+
+const { provider, connection, program, owner, initializeState, createRoutePda, getVaultAta, assert, assertSE } = common;
+const { createDepositLimitPda, createDepositorNoncePda, createVaultLiabilityPda, createVaultRegistryPda } = common;
+const { seedBalance, depositData, createSlowFillLiabilityPda, createLiquidityPoolPda, getVaultAuthority } = common;
+const { createClaimLiabilityPda } = common;
+
+describe("svm_spoke.sweep_tokens", () => {
+  anchor.setProvider(provider);
+
+  const payer = (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer;
+  const recipient = Keypair.generate();
+  const tokenDecimals = 6;
+  const routeChainId = new BN(1);
+  const depositAmount = new BN(1_000_000);
+
+  let state: PublicKey, seed: BN, mint: PublicKey, vault: PublicKey, ownerTA: PublicKey, recipientTA: PublicKey;
+
+  const deposit = async () => {
+    const approveIx = createApproveCheckedInstruction(
+      ownerTA,
+      mint,
+      state,
+      owner,
+      BigInt(depositAmount.toString()),
+      tokenDecimals
+    );
+    await program.methods
+      .depositV3(
+        owner,
+        depositData.recipient,
+        mint,
+        depositData.outputToken,
+        depositAmount,
+        depositAmount,
+        routeChainId,
+        depositData.exclusiveRelayer,
+        depositData.quoteTimestamp.toNumber(),
+        depositData.fillDeadline.toNumber(),
+        depositData.exclusivityParameter.toNumber(),
+        Buffer.alloc(0),
+        false,
+        0,
+        Buffer.alloc(0)
+      )
+      .accounts({
+        state,
+        route: createRoutePda(mint, seed, routeChainId),
+        destinationChainAlias: program.programId,
+        depositLimit: createDepositLimitPda(mint, seed, routeChainId),
+        depositorNonce: createDepositorNoncePda(owner, seed),
+        vaultRegistry: createVaultRegistryPda(mint, seed),
+        signer: owner,
+        depositorTokenAccount: ownerTA,
+        vault,
+        mint,
+        vaultLiability: createVaultLiabilityPda(mint, seed),
+        eventCommitment: program.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        program: program.programId,
+      })
+      .preInstructions([approveIx])
+      .rpc();
+  };
+
+  const sweepTokens = async (amount: BN) => {
+    const sweepAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      mint,
+      vault,
+      vaultLiability: createVaultLiabilityPda(mint, seed),
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      liquidityPool: createLiquidityPoolPda(mint, seed),
+      recipientTokenAccount: recipientTA,
+      rateLimit: program.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      adminNonce: program.programId,
+      program: program.programId,
+    };
+    return await program.methods.sweepTokens(amount, recipient.publicKey, null).accounts(sweepAccounts).rpc();
+  };
+
+  const migrateAccounts = (signer: PublicKey) => {
+    const vaultAuthority = getVaultAuthority(state);
+    return {
+      signer,
+      payer: owner,
+      state,
+      mint,
+      stateVault: getAssociatedTokenAddressSync(mint, state, true),
+      vaultAuthority,
+      vault: getAssociatedTokenAddressSync(mint, vaultAuthority, true),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      liquidityPool: createLiquidityPoolPda(mint, seed),
+      program: program.programId,
+    };
+  };
+
+  beforeEach(async () => {
+    ({ state, seed } = await initializeState());
+
+    mint = await createMint(connection, payer, owner, owner, tokenDecimals);
+    ownerTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, owner)).address;
+    recipientTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, recipient.publicKey)).address;
+    await mintTo(connection, payer, mint, ownerTA, owner, seedBalance);
+
+    vault = await getVaultAta(mint, state);
+    const setEnableRouteAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      route: createRoutePda(mint, seed, routeChainId),
+      vault,
+      originTokenMint: mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
+    };
+    await program.methods.setEnableRoute(mint, routeChainId, true, 0, 0).accounts(setEnableRouteAccounts).rpc();
+
+    await deposit();
+  });
+
+  it("Fails to sweep when liabilities equal the vault balance", async () => {
+    const vaultLiability = await program.account.vaultLiability.fetch(createVaultLiabilityPda(mint, seed));
+    assertSE(vaultLiability.liabilities, depositAmount, "Deposit should be recorded as liability");
+    assertSE((await getAccount(connection, vault)).amount, depositAmount, "Vault balance should match liabilities");
+
+    for (const amount of [new BN(1), depositAmount]) {
+      try {
+        await sweepTokens(amount);
+        assert.fail("Sweeping a fully backed vault should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), "SweepExceedsSurplus", "Expected SweepExceedsSurplus error");
+      }
+    }
+  });
+
+  it("Sweeps only the vault balance exceeding its liabilities", async () => {
+    // Tokens sent directly to the vault are not backing any deposit.
+    const surplus = new BN(250_000);
+    await mintTo(connection, payer, mint, vault, owner, surplus.toNumber());
+
+    try {
+      await sweepTokens(surplus.addn(1));
+      assert.fail("Sweeping more than the surplus should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "SweepExceedsSurplus", "Expected SweepExceedsSurplus error");
+    }
+
+    const tx = await sweepTokens(surplus);
+    assertSE((await getAccount(connection, recipientTA)).amount, surplus, "Recipient should receive the surplus");
+    assertSE((await getAccount(connection, vault)).amount, depositAmount, "Vault should keep the deposited tokens");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "tokensSwept")?.data;
+    assertSE(event.mint, mint, "Mint should match");
+    assertSE(event.amount, surplus, "Amount should match");
+    assertSE(event.recipient, recipient.publicKey, "Recipient should match");
+
+    // Remaining vault balance is fully backed again.
+    try {
+      await sweepTokens(new BN(1));
+      assert.fail("Sweeping a fully backed vault should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "SweepExceedsSurplus", "Expected SweepExceedsSurplus error");
+    }
+  });
+
+  it("Sweeps only the vault balance exceeding its liabilities and the liquidity owed to the pool", async () => {
+    const liquidityPool = createLiquidityPoolPda(mint, seed);
+    const [liquidityPosition] = PublicKey.findProgramAddressSync(
+      [Buffer.from("liquidity_position"), seed.toArrayLike(Buffer, "le", 8), mint.toBuffer(), owner.toBuffer()],
+      program.programId
+    );
+    const setLiquidityPoolConfig = (enabled: boolean) =>
+      program.methods.setLiquidityPoolConfig(enabled, 0).accounts({ state, signer: owner, program: program.programId });
+    await program.methods
+      .initializeLiquidityPool()
+      .accounts({ signer: owner, state, mint, liquidityPool, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+    await setLiquidityPoolConfig(true).rpc();
+
+    const providedAmount = new BN(500_000);
+    const approveIx = createApproveCheckedInstruction(
+      ownerTA,
+      mint,
+      state,
+      owner,
+      BigInt(providedAmount.toString()),
+      tokenDecimals
+    );
+    await program.methods
+      .provideLiquidity(providedAmount)
+      .accounts({
+        signer: owner,
+        state,
+        mint,
+        liquidityPool,
+        liquidityPosition,
+        providerTokenAccount: ownerTA,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        program: program.programId,
+      })
+      .preInstructions([approveIx])
+      .rpc();
+    assertSE(
+      (await getAccount(connection, vault)).amount,
+      depositAmount.add(providedAmount),
+      "Vault should hold the deposit and the provided liquidity"
+    );
+
+    // Only tokens sent directly to the vault are swept, whether liquidity pools are enabled or not.
+    const surplus = new BN(250_000);
+    await mintTo(connection, payer, mint, vault, owner, surplus.toNumber());
+    for (const enabled of [true, false]) {
+      if (!enabled) await setLiquidityPoolConfig(enabled).rpc();
+      try {
+        await sweepTokens(surplus.addn(1));
+        assert.fail("Sweeping the liquidity owed to the pool should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), "SweepExceedsSurplus", "Expected SweepExceedsSurplus error");
+      }
+    }
+
+    await sweepTokens(surplus);
+    assertSE((await getAccount(connection, recipientTA)).amount, surplus, "Recipient should receive the surplus");
+    assertSE(
+      (await getAccount(connection, vault)).amount,
+      depositAmount.add(providedAmount),
+      "Vault should keep the deposit and the provided liquidity"
+    );
+  });
+
+  it("Migrates the state vault to the vault authority once and rejects it as a vault", async () => {
+    // State vaults created before the vault authority still hold tokens that only the migration moves.
+    const { stateVault, vaultAuthority, vault: migratedVault } = migrateAccounts(owner);
+    assertSE(migratedVault, vault, "Route should have created the vault of the vault authority");
+    const stateAmount = new BN(500_000);
+    await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true);
+    await mintTo(connection, payer, mint, stateVault, owner, stateAmount.toNumber());
+    vault = stateVault;
+    try {
+      await sweepTokens(stateAmount);
+      assert.fail("Sweeping from the state vault should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "ConstraintAssociated", "Expected ConstraintAssociated error");
+    }
+    vault = migratedVault;
+
+    const tx = await program.methods.migrateVaultAuthority().accounts(migrateAccounts(owner)).rpc();
+    const migratedAmount = depositAmount.add(stateAmount);
+    assertSE((await getAccount(connection, vault)).amount, migratedAmount, "Vault should hold both balances");
+    const migratedStateVault = await getAccount(connection, stateVault);
+    assertSE(migratedStateVault.amount, 0, "State vault should be emptied");
+    assertSE(migratedStateVault.closeAuthority, vaultAuthority, "State vault should be marked as migrated");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "migratedVaultAuthority")?.data;
+    assertSE(event.mint, mint, "Mint should match");
+    assertSE(event.vault, vault, "Vault should match");
+    assertSE(event.amount, stateAmount, "Amount should match");
+
+    // Repeated migrations have nothing left to move. The compute budget instruction only keeps the transaction unique.
+    await program.methods
+      .migrateVaultAuthority()
+      .accounts(migrateAccounts(owner))
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 200_000 })])
+      .rpc();
+    assertSE((await getAccount(connection, vault)).amount, migratedAmount, "Vault balance should not change");
+
+    // Tokens sent to the migrated state vault are moved by repeated migrations.
+    const surplus = new BN(250_000);
+    await mintTo(connection, payer, mint, stateVault, owner, surplus.toNumber());
+    await program.methods.migrateVaultAuthority().accounts(migrateAccounts(owner)).rpc();
+    assertSE((await getAccount(connection, stateVault)).amount, 0, "State vault should be emptied");
+
+    // Only the deposit backs liabilities, so the migrated balances are surplus swept with the vault authority.
+    await sweepTokens(stateAmount.add(surplus));
+    assertSE((await getAccount(connection, recipientTA)).amount, stateAmount.add(surplus), "Recipient should get both");
+    assertSE((await getAccount(connection, vault)).amount, depositAmount, "Vault should keep the deposited tokens");
+  });
+
+  it("Rejects payouts from a frozen vault and reports it", async () => {
+    const reportFrozenVault = () =>
+      program.methods.reportFrozenVault().accounts({ state, mint, vault, program: program.programId });
+
+    const surplus = new BN(250_000);
+    await mintTo(connection, payer, mint, vault, owner, surplus.toNumber());
+    assert.isFalse(await reportFrozenVault().view(), "Vault should not be reported frozen");
+
+    // Freeze authority of the mint freezes the vault, so payouts fail with a typed error instead of a token error.
+    await freezeAccount(connection, payer, vault, mint, owner);
+    try {
+      await sweepTokens(surplus);
+      assert.fail("Sweeping from a frozen vault should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "VaultFrozen", "Expected VaultFrozen error");
+    }
+
+    assert.isTrue(await reportFrozenVault().view(), "Vault should be reported frozen");
+    const reportTx = await reportFrozenVault().rpc();
+    const reportEvents = await readEventsUntilFound(connection, reportTx, [program]);
+    const reportEvent = reportEvents.find((event) => event.name === "vaultFrozen")?.data;
+    assertSE(reportEvent.mint, mint, "Mint should match");
+    assertSE(reportEvent.vault, vault, "Vault should match");
+
+    await thawAccount(connection, payer, vault, mint, owner);
+    await sweepTokens(surplus);
+    assertSE((await getAccount(connection, recipientTA)).amount, surplus, "Recipient should receive the surplus");
+  });
+
+  it("Moves the flows of a frozen state vault to the vault authority and migrates it once thawed", async () => {
+    const { stateVault, vault: migratedVault, ...accounts } = migrateAccounts(owner);
+    const migrateFrozenVaultAccounts = { ...accounts, frozenVault: stateVault, vault: migratedVault };
+
+    const stateAmount = new BN(500_000);
+    await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true);
+    await mintTo(connection, payer, mint, stateVault, owner, stateAmount.toNumber());
+    try {
+      await program.methods.migrateFrozenVault().accounts(migrateFrozenVaultAccounts).rpc();
+      assert.fail("Migrating a vault that is not frozen should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "VaultNotFrozen", "Expected VaultNotFrozen error");
+    }
+
+    // Frozen state vaults can't be migrated by the vault authority migration, which its preview reports.
+    await freezeAccount(connection, payer, stateVault, mint, owner);
+    const preview = await program.methods.previewVaultMigration(mint, state).accounts(migrateAccounts(owner)).view();
+    assert.strictEqual(preview.blockers, VAULT_MIGRATION_FROZEN, "Only the frozen check should block the migration");
+    try {
+      await program.methods.migrateVaultAuthority().accounts(migrateAccounts(owner)).rpc();
+      assert.fail("Migrating a frozen state vault should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "VaultMigrationBlocked", "Expected VaultMigrationBlocked error");
+    }
+
+    const migrateTx = await program.methods.migrateFrozenVault().accounts(migrateFrozenVaultAccounts).rpc();
+    const migrateEvents = await readEventsUntilFound(connection, migrateTx, [program]);
+    const migrateEvent = migrateEvents.find((event) => event.name === "migratedFrozenVault")?.data;
+    assertSE(migrateEvent.mint, mint, "Mint should match");
+    assertSE(migrateEvent.frozenVault, stateVault, "Frozen vault should match");
+    assertSE(migrateEvent.vault, migratedVault, "Vault should match");
+
+    // Once thawed, the balance left in the state vault is moved by the vault authority migration.
+    await thawAccount(connection, payer, stateVault, mint, owner);
+    await program.methods.migrateVaultAuthority().accounts(migrateAccounts(owner)).rpc();
+    const migratedAmount = depositAmount.add(stateAmount);
+    assertSE((await getAccount(connection, migratedVault)).amount, migratedAmount, "Vault should hold both balances");
+    assertSE((await getAccount(connection, stateVault)).amount, 0, "State vault should be emptied");
+  });
+
+  it("Fails to migrate the vault authority by non-owner", async () => {
+    const nonOwner = Keypair.generate();
+    try {
+      await program.methods
+        .migrateVaultAuthority()
+        .accounts(migrateAccounts(nonOwner.publicKey))
+        .signers([nonOwner])
+        .rpc();
+      assert.fail("Non-owner should not be able to migrate the vault authority");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+    }
+  });
+
+  it("Fails to sweep tokens by non-owner", async () => {
+    const nonOwner = Keypair.generate();
+    try {
+      await program.methods
+        .sweepTokens(new BN(1), recipient.publicKey, null)
+        .accounts({
+          signer: nonOwner.publicKey,
+          payer: owner,
+          state,
+          mint,
+          vault,
+          vaultLiability: createVaultLiabilityPda(mint, seed),
+          slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+          liquidityPool: createLiquidityPoolPda(mint, seed),
+          recipientTokenAccount: recipientTA,
+          rateLimit: program.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          adminNonce: program.programId,
+          program: program.programId,
+        })
+        .signers([nonOwner])
+        .rpc();
+      assert.fail("Non-owner should not be able to sweep tokens");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotOwner", "Expected NotOwner error");
+    }
+  });
+});
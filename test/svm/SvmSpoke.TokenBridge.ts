@@ -17,6 +17,7 @@ import { TokenMessengerMinter } from "../../target/types/token_messenger_minter"
 import { common } from "./SvmSpoke.common";
 
 const { provider, program, owner, initializeState, connection, remoteDomain, chainId, crossDomainAdmin } = common;
+const { createSlowFillLiabilityPda, createClaimLiabilityPda, createFillVolumePda, getVaultAuthority } = common;
 
 describe("svm_spoke.token_bridge", () => {
   anchor.setProvider(provider);
@@ -75,7 +76,7 @@ describe("svm_spoke.token_bridge", () => {
     // Each test will have different state and mint token.
     ({ state, seed } = await initializeState());
     mint = await createMint(connection, payer, owner, owner, 6);
-    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, state, true)).address;
+    vault = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, getVaultAuthority(state), true)).address;
 
     await mintTo(connection, payer, mint, vault, provider.publicKey, initialMintAmount);
 
@@ -163,10 +164,11 @@ describe("svm_spoke.token_bridge", () => {
       rootBundle,
       signer: owner,
       payer: owner,
+      adminNonce: program.programId,
       program: program.programId,
     };
     await program.methods
-      .relayRootBundle(Array.from(root), Array.from(Buffer.alloc(32)))
+      .relayRootBundle(Array.from(root), Array.from(Buffer.alloc(32)), 0, null, 0, null)
       .accounts(relayRootBundleAccounts)
       .rpc();
 
@@ -179,11 +181,26 @@ describe("svm_spoke.token_bridge", () => {
       vault,
       mint,
       transferLiability,
+      errorCounters: program.programId,
+      bundleAudit: program.programId,
+      rateLimit: program.programId,
+      vaultLiability: program.programId,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      fillVolume: createFillVolumePda(mint, seed),
+      executionApproval: program.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: web3.SystemProgram.programId,
       program: program.programId,
     };
-    await loadExecuteRelayerRefundLeafParams(program, owner, stateAccountData.rootBundleId, leaf, proofAsNumbers);
+    await loadExecuteRelayerRefundLeafParams(
+      program,
+      owner,
+      state,
+      stateAccountData.rootBundleId,
+      leaf,
+      proofAsNumbers,
+    );
     await program.methods.executeRelayerRefundLeaf().accounts(executeRelayerRefundLeafAccounts).rpc();
   };
 
@@ -327,5 +344,117 @@ describe("svm_spoke.token_bridge", () => {
     assert.isNotNull(event, "BridgedToHubPool event should be emitted");
     assert.strictEqual(event.amount.toString(), simpleBridgeAmount.toString(), "Invalid amount");
     assert.strictEqual(event.mint.toString(), mint.toString(), "Invalid mint");
+
+    const message = decodeMessageSentData(
+      (await messageTransmitterProgram.account.messageSent.fetch(simpleBridgeMessageSentEventData.publicKey)).message
+    );
+    assert.strictEqual(event.nonce.toString(), message.nonce.toString(), "Invalid nonce");
+  });
+
+  it("Bridge above CCTP burn limit should fail and leave pending liability intact", async () => {
+    const pendingToHubPool = 1_000_000;
+    const burnLimit = pendingToHubPool / 2;
+
+    await initializeBridgeToHubPool(pendingToHubPool);
+
+    // Lower the CCTP burn limit per message below the pending amount.
+    await tokenMessengerMinterProgram.methods
+      .setMaxBurnAmountPerMessage({ burnLimitPerMessage: new BN(burnLimit) })
+      .accounts({ tokenMinter, localToken, program: tokenMessengerMinterProgram.programId, eventAuthority })
+      .rpc();
+
+    try {
+      await program.methods
+        .bridgeTokensToHubPool(new BN(pendingToHubPool))
+        .accounts(bridgeTokensToHubPoolAccounts)
+        .signers([messageSentEventData])
+        .rpc();
+      assert.fail("Should not be able to bridge above CCTP burn limit");
+    } catch (error: any) {
+      assert.instanceOf(error, AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "ExceededCctpBurnLimit", "Expected error ExceededCctpBurnLimit");
+    }
+
+    let pending = (await program.account.transferLiability.fetch(transferLiability)).pendingToHubPool;
+    assert.strictEqual(pending.toString(), pendingToHubPool.toString(), "Pending liability should be intact");
+
+    // Bridging within the burn limit should still work and only decrement the bridged amount.
+    await program.methods
+      .bridgeTokensToHubPool(new BN(burnLimit))
+      .accounts(bridgeTokensToHubPoolAccounts)
+      .signers([messageSentEventData])
+      .rpc();
+    pending = (await program.account.transferLiability.fetch(transferLiability)).pendingToHubPool;
+    assert.strictEqual(pending.toString(), (pendingToHubPool - burnLimit).toString(), "Invalid pending liability");
+  });
+
+  describe("Reconcile counters", () => {
+    const pendingToHubPool = 1_000_000;
+    const maxDeviation = pendingToHubPool / 10; // 1000 bps sanity band.
+
+    const reconcileCounters = async (reconciledPendingToHubPool: number, force: boolean) => {
+      return await program.methods
+        .reconcileCounters(mint, new BN(reconciledPendingToHubPool), force)
+        .accounts({ signer: owner, state, transferLiability, program: program.programId })
+        .rpc();
+    };
+
+    const assertReconciledCounters = async (tx: string, reconciledPendingToHubPool: number, forced: boolean) => {
+      const events = await readEventsUntilFound(connection, tx, [program]);
+      const event = events.find((event) => event.name === "reconciledCounters")?.data;
+      assert.strictEqual(event.mint.toString(), mint.toString(), "Invalid mint");
+      assert.strictEqual(event.oldPendingToHubPool.toString(), pendingToHubPool.toString(), "Invalid old value");
+      assert.strictEqual(
+        event.newPendingToHubPool.toString(),
+        reconciledPendingToHubPool.toString(),
+        "Invalid new value"
+      );
+      assert.strictEqual(event.forced, forced, "Invalid forced flag");
+
+      const pending = (await program.account.transferLiability.fetch(transferLiability)).pendingToHubPool;
+      assert.strictEqual(pending.toString(), reconciledPendingToHubPool.toString(), "Invalid pending liability");
+
+      const stateData = await program.account.state.fetch(state);
+      assert.strictEqual(
+        stateData.lastCounterReconciliationTime,
+        stateData.currentTime,
+        "Reconciliation time should be recorded"
+      );
+    };
+
+    beforeEach(async () => {
+      await initializeBridgeToHubPool(pendingToHubPool);
+    });
+
+    it("Reconciles pending liability within the sanity band", async () => {
+      const reconciledPendingToHubPool = pendingToHubPool + maxDeviation; // Deviation at the sanity band boundary.
+      const tx = await reconcileCounters(reconciledPendingToHubPool, false);
+      await assertReconciledCounters(tx, reconciledPendingToHubPool, false);
+    });
+
+    it("Reconciles pending liability outside of the sanity band with force", async () => {
+      const tx = await reconcileCounters(0, true);
+      await assertReconciledCounters(tx, 0, true);
+    });
+
+    it("Rejects pending liability outside of the sanity band without force", async () => {
+      const outOfBandValues = [pendingToHubPool + maxDeviation + 1, pendingToHubPool - maxDeviation - 1];
+      for (const reconciledPendingToHubPool of outOfBandValues) {
+        try {
+          await reconcileCounters(reconciledPendingToHubPool, false);
+          assert.fail("Should not be able to reconcile outside of the sanity band without force");
+        } catch (error: any) {
+          assert.instanceOf(error, AnchorError);
+          assert.strictEqual(
+            error.error.errorCode.code,
+            "ReconciliationOutOfBand",
+            "Expected error ReconciliationOutOfBand"
+          );
+        }
+      }
+
+      const pending = (await program.account.transferLiability.fetch(transferLiability)).pendingToHubPool;
+      assert.strictEqual(pending.toString(), pendingToHubPool.toString(), "Pending liability should be intact");
+    });
   });
 });
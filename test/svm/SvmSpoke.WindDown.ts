@@ -0,0 +1,161 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { common } from "./SvmSpoke.common";
+import { readEventsUntilFound } from "../../src/svm";
+
+const { provider, connection, program, owner, initializeState, createRoutePda, getVaultAta, setCurrentTime } = common;
+const { assert, assertSE, seedBalance, createSlowFillLiabilityPda, createClaimLiabilityPda } = common;
+const { createLiquidityPoolPda } = common;
+
+describe("svm_spoke.wind_down", () => {
+  anchor.setProvider(provider);
+
+  const payer = (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer;
+  const nonOwner = Keypair.generate();
+  const windDownDelay = 30 * 86_400; // Minimum wind down delay.
+
+  let state: PublicKey, seed: BN, mint: PublicKey, vault: PublicKey, ownerTA: PublicKey, transferLiability: PublicKey;
+
+  const windDown = async (delay = windDownDelay) => {
+    return await program.methods.windDown(delay).accounts({ state, signer: owner, program: program.programId }).rpc();
+  };
+
+  const sweepVaultToHub = async () => {
+    const sweepAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      mint,
+      vault,
+      transferLiability,
+      localToken: program.programId,
+      ownerTokenAccount: ownerTA,
+      rateLimit: program.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      slowFillLiability: createSlowFillLiabilityPda(mint, seed),
+      claimLiability: createClaimLiabilityPda(mint, seed),
+      liquidityPool: createLiquidityPoolPda(mint, seed),
+      program: program.programId,
+    };
+    return await program.methods.sweepVaultToHub().accounts(sweepAccounts).rpc();
+  };
+
+  beforeEach(async () => {
+    ({ state, seed } = await initializeState());
+
+    mint = await createMint(connection, payer, owner, owner, 6);
+    ownerTA = (await getOrCreateAssociatedTokenAccount(connection, payer, mint, owner)).address;
+    [transferLiability] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer_liability"), mint.toBuffer()],
+      program.programId
+    );
+
+    // Enable route so the vault is created and funded as if it had accrued deposits.
+    const routeChainId = new BN(1);
+    vault = await getVaultAta(mint, state);
+    const setEnableRouteAccounts = {
+      signer: owner,
+      payer: owner,
+      state,
+      route: createRoutePda(mint, seed, routeChainId),
+      vault,
+      originTokenMint: mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      lookupTableConfig: program.programId,
+      lookupTable: program.programId,
+      addressLookupTableProgram: program.programId,
+    };
+    await program.methods.setEnableRoute(mint, routeChainId, true, 0, 0).accounts(setEnableRouteAccounts).rpc();
+    await mintTo(connection, payer, mint, vault, owner, seedBalance);
+  });
+
+  it("Starts wind down irreversibly", async () => {
+    const tx = await windDown();
+
+    const stateData = await program.account.state.fetch(state);
+    assert.isTrue(stateData.windingDown, "Spoke should be winding down");
+    assertSE(stateData.windDownDelay, windDownDelay, "Wind down delay should match");
+    assertSE(stateData.windDownTime, stateData.currentTime, "Wind down time should match current time");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "startedWindDown")?.data;
+    assertSE(event.windDownDelay, windDownDelay, "Event wind down delay should match");
+
+    // Wind down cannot be restarted, e.g. to reset its delay.
+    try {
+      await windDown(0);
+      assert.fail("Should not be able to restart wind down");
+    } catch (err: any) {
+      assert.include(err.toString(), "The spoke pool is winding down!", "Expected winding down error");
+    }
+  });
+
+  it("Cannot start wind down with a delay below the minimum", async () => {
+    try {
+      await windDown(windDownDelay - 1);
+      assert.fail("Should not be able to start wind down with a delay below the minimum");
+    } catch (err: any) {
+      assert.include(err.toString(), "WindDownDelayTooShort", "Expected WindDownDelayTooShort error");
+    }
+    assert.isFalse((await program.account.state.fetch(state)).windingDown, "Spoke should not be winding down");
+  });
+
+  it("Only owner can start wind down", async () => {
+    try {
+      await program.methods
+        .windDown(windDownDelay)
+        .accounts({ state, signer: nonOwner.publicKey, program: program.programId })
+        .signers([nonOwner])
+        .rpc();
+      assert.fail("Non-owner should not be able to start wind down");
+    } catch (err: any) {
+      assert.include(err.toString(), "Only the owner can call this function!", "Expected owner check error");
+    }
+  });
+
+  it("Cannot sweep vault before wind down", async () => {
+    try {
+      await sweepVaultToHub();
+      assert.fail("Should not be able to sweep vault before wind down");
+    } catch (err: any) {
+      assert.include(err.toString(), "The spoke pool is not winding down!", "Expected not winding down error");
+    }
+  });
+
+  it("Sweeps non-CCTP vault to owner only after wind down delay", async () => {
+    await windDown();
+
+    try {
+      await sweepVaultToHub();
+      assert.fail("Should not be able to sweep vault before wind down delay");
+    } catch (err: any) {
+      assert.include(err.toString(), "The wind down delay has not passed!", "Expected wind down delay error");
+    }
+
+    const windDownTime = (await program.account.state.fetch(state)).windDownTime;
+    await setCurrentTime(program, state, new BN(windDownTime + windDownDelay));
+
+    const tx = await sweepVaultToHub();
+
+    const vaultAccount = await getAccount(connection, vault);
+    assertSE(vaultAccount.amount, 0, "Vault should be empty");
+    const ownerAccount = await getAccount(connection, ownerTA);
+    assertSE(ownerAccount.amount, seedBalance, "Owner should receive the vault balance");
+
+    const events = await readEventsUntilFound(connection, tx, [program]);
+    const event = events.find((event) => event.name === "sweptVault")?.data;
+    assertSE(event.amount, seedBalance, "Event amount should match");
+    assert.isFalse(event.toHubPool, "Non-CCTP token should not be swept to hub pool");
+  });
+});
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { BN, Program } from "@coral-xyz/anchor";
+import { BN, IdlTypes, Program } from "@coral-xyz/anchor";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import { assert } from "chai";
@@ -30,6 +30,7 @@ const exclusivityParameter = new BN(0); // 0 means no exclusivity and disables t
 const message = Buffer.from("Test message");
 const depositQuoteTimeBuffer = new BN(3600); // 1 hour.
 const fillDeadlineBuffer = new BN(3600 * 4); // 4 hours.
+const localnetNetwork = 2; // Localnet network tag, allowing any chain ID except Solana mainnet and devnet.
 
 const initializeState = async (
   seed?: BN,
@@ -40,7 +41,8 @@ const initializeState = async (
     crossDomainAdmin: PublicKey;
     depositQuoteTimeBuffer: BN;
     fillDeadlineBuffer: BN;
-  }
+  },
+  network = localnetNetwork
 ) => {
   const actualSeed = seed || new BN(randomBytes(8).toString("hex"), 16); // Generate a random u64
   const seeds = [Buffer.from("state"), actualSeed.toArrayLike(Buffer, "le", 8)];
@@ -64,7 +66,8 @@ const initializeState = async (
       initialState.remoteDomain.toNumber(),
       initialState.crossDomainAdmin,
       initialState.depositQuoteTimeBuffer.toNumber(),
-      initialState.fillDeadlineBuffer.toNumber()
+      initialState.fillDeadlineBuffer.toNumber(),
+      network
     )
     .accounts(initializeAccounts)
     .rpc();
@@ -83,15 +86,129 @@ const createRoutePda = (originToken: PublicKey, seed: BN, routeChainId: BN) => {
   )[0];
 };
 
+const createDepositLimitPda = (mint: PublicKey, seed: BN, routeChainId: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("deposit_limit"),
+      seed.toArrayLike(Buffer, "le", 8),
+      mint.toBytes(),
+      routeChainId.toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  )[0];
+};
+
+const createChainAliasPda = (seed: BN, alias: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("chain_alias"), seed.toArrayLike(Buffer, "le", 8), alias.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+};
+
+const createDepositorNoncePda = (depositor: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("depositor_nonce"), seed.toArrayLike(Buffer, "le", 8), depositor.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createNativeWrapPda = (depositor: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("native_wrap"), seed.toArrayLike(Buffer, "le", 8), depositor.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createVaultLiabilityPda = (mint: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_liability"), seed.toArrayLike(Buffer, "le", 8), mint.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createVaultRegistryPda = (mint: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), seed.toArrayLike(Buffer, "le", 8), mint.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createFillVolumePda = (mint: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fill_volume"), seed.toArrayLike(Buffer, "le", 8), mint.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createSlowFillLiabilityPda = (mint: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("slow_fill_liability"), seed.toArrayLike(Buffer, "le", 8), mint.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createClaimLiabilityPda = (mint: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("claim_liability"), seed.toArrayLike(Buffer, "le", 8), mint.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createLiquidityPoolPda = (mint: PublicKey, seed: BN) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("liquidity_pool"), seed.toArrayLike(Buffer, "le", 8), mint.toBytes()],
+    program.programId
+  )[0];
+};
+
+const createMessageSchemaPda = (recipient: PublicKey) => {
+  return PublicKey.findProgramAddressSync([Buffer.from("message_schema"), recipient.toBytes()], program.programId)[0];
+};
+
+// Vaults are the ATAs of this PDA, while state vaults created before it are only emptied by migrateVaultAuthority.
+const getVaultAuthority = (state: PublicKey) => {
+  return PublicKey.findProgramAddressSync([Buffer.from("vault_authority"), state.toBuffer()], program.programId)[0];
+};
+
 const getVaultAta = async (tokenMint: PublicKey, state: PublicKey) => {
   const tokenMintAccount = await provider.connection.getAccountInfo(tokenMint);
   if (tokenMintAccount === null) throw new Error("Token Mint account not found");
-  return getAssociatedTokenAddressSync(tokenMint, state, true, tokenMintAccount.owner, ASSOCIATED_TOKEN_PROGRAM_ID);
+  return getAssociatedTokenAddressSync(
+    tokenMint,
+    getVaultAuthority(state),
+    true,
+    tokenMintAccount.owner,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
 };
 
-async function setCurrentTime(program: Program<SvmSpoke>, state: any, signer: anchor.web3.Keypair, newTime: BN) {
-  let setCurrentTimeAccounts = { state, signer: signer.publicKey };
-  await program.methods.setCurrentTime(newTime.toNumber()).accounts(setCurrentTimeAccounts).signers([signer]).rpc();
+// The test-only instructions below are owner gated, so they are signed by the provider wallet owning the test states.
+async function setCurrentTime(program: Program<SvmSpoke>, state: any, newTime: BN) {
+  await program.methods.setCurrentTime(newTime.toNumber()).accounts({ state, signer: owner }).rpc();
+}
+
+// Sets the roots of a root bundle directly instead of relaying it, returning the root bundle PDA.
+async function setRootBundle(
+  state: PublicKey,
+  seed: BN,
+  rootBundleId: number,
+  relayerRefundRoot: number[],
+  slowRelayRoot: number[]
+) {
+  const rootBundleIdBuffer = Buffer.alloc(4);
+  rootBundleIdBuffer.writeUInt32LE(rootBundleId);
+  const seeds = [Buffer.from("root_bundle"), seed.toArrayLike(Buffer, "le", 8), rootBundleIdBuffer];
+  const [rootBundle] = PublicKey.findProgramAddressSync(seeds, program.programId);
+  await program.methods
+    .setRootBundle(rootBundleId, relayerRefundRoot, slowRelayRoot)
+    .accounts({ state, rootBundle, signer: owner })
+    .rpc();
+  return rootBundle;
+}
+
+// Sets the status of a relay directly instead of filling it or requesting its slow fill.
+async function setFillStatus(state: PublicKey, relayHash: number[], status: IdlTypes<SvmSpoke>["fillStatus"]) {
+  await program.methods.setFillStatus(relayHash, status).accounts({ state, signer: owner }).rpc();
 }
 
 async function getCurrentTime(program: Program<SvmSpoke>, state: any) {
@@ -130,8 +247,22 @@ export const common = {
   fillDeadlineBuffer,
   initializeState,
   createRoutePda,
+  createDepositLimitPda,
+  createChainAliasPda,
+  createDepositorNoncePda,
+  createNativeWrapPda,
+  createVaultLiabilityPda,
+  createVaultRegistryPda,
+  createSlowFillLiabilityPda,
+  createClaimLiabilityPda,
+  createLiquidityPoolPda,
+  createMessageSchemaPda,
+  createFillVolumePda,
   getVaultAta,
+  getVaultAuthority,
   setCurrentTime,
+  setRootBundle,
+  setFillStatus,
   getCurrentTime,
   assert,
   assertSE,
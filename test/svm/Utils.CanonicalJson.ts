@@ -0,0 +1,217 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+import { SvmSpoke } from "../../target/types/svm_spoke";
+import { intToU8Array32, toCanonicalJson } from "../../src/svm";
+
+// The same fixture is deserialized and serialized back by the serde feature of the svm-spoke crate
+// (programs/svm-spoke/tests/serde.rs), so both representations must be kept in sync.
+const fixture = JSON.parse(fs.readFileSync(path.resolve(__dirname, "fixtures/serde.json"), "utf8"));
+// Borsh encodings of the same values, pinned by programs/svm-spoke/tests/schema.rs with events discriminator prefixed.
+const schemaFixture = JSON.parse(fs.readFileSync(path.resolve(__dirname, "fixtures/schema.json"), "utf8"));
+
+describe("utils.canonical_json", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SvmSpoke as Program<SvmSpoke>;
+
+  const spoke = new PublicKey("DnLjPzpMCW2CF99URhGF3jDYnVRcCJMjUWsbPb4xVoBn");
+  const handler = new PublicKey("6kqWTz3A3ZYMV2FMU24ke8rHzT82SaBz7GkBKTd7Z9BH");
+  const test = new PublicKey("8tsEfDSiE4WUMf97oyyyasLAvWwjeRZb2GByh4w7HckA");
+  const usdc = new PublicKey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+  const hash = new Array(32).fill(0xab);
+  const solanaChainId = new BN("34268394551451");
+  const maxU64 = new BN("18446744073709551615");
+
+  const relayData = {
+    depositor: spoke,
+    recipient: handler,
+    exclusiveRelayer: PublicKey.default,
+    inputToken: usdc,
+    outputToken: test,
+    inputAmount: new BN(1_000_000),
+    outputAmount: maxU64,
+    originChainId: new BN(42161),
+    depositId: intToU8Array32(42),
+    fillDeadline: 1760400000,
+    exclusivityDeadline: 0,
+    message: Buffer.from([0x12, 0x34]),
+  };
+
+  const values: { [key: string]: [string, any] } = {
+    v3RelayData: ["V3RelayData", relayData],
+    v3SlowFill: ["V3SlowFill", { relayData, chainId: solanaChainId, updatedOutputAmount: new BN(999_000) }],
+    relayerRefundLeaf: [
+      "RelayerRefundLeaf",
+      {
+        amountToReturn: new BN(0),
+        chainId: solanaChainId,
+        refundAmounts: [new BN(500), maxU64],
+        leafId: 3,
+        mintPublicKey: usdc,
+        refundAddresses: [spoke, handler],
+      },
+    ],
+    v3FundsDeposited: [
+      "V3FundsDeposited",
+      {
+        inputToken: usdc,
+        outputToken: test,
+        inputAmount: new BN(1_000_000),
+        outputAmount: new BN(999_000),
+        destinationChainId: new BN(1),
+        depositId: intToU8Array32(42),
+        quoteTimestamp: 1760396400,
+        fillDeadline: 1760400000,
+        exclusivityDeadline: 1760396460,
+        depositor: spoke,
+        recipient: handler,
+        exclusiveRelayer: PublicKey.default,
+        message: Buffer.alloc(0),
+        tokenDecimals: 6,
+        unsafeDepositId: false,
+        depositorNonce: new BN(5),
+        messageHash: null,
+        exclusivityParameter: 60,
+        dustRefunded: null,
+      },
+    ],
+    filledV3Relay: [
+      "FilledV3Relay",
+      {
+        inputToken: usdc,
+        outputToken: test,
+        inputAmount: new BN(1_000_000),
+        outputAmount: new BN(999_000),
+        repaymentChainId: solanaChainId,
+        originChainId: new BN(42161),
+        depositId: intToU8Array32(42),
+        fillDeadline: 1760400000,
+        exclusivityDeadline: 0,
+        exclusiveRelayer: PublicKey.default,
+        relayer: test,
+        depositor: spoke,
+        recipient: handler,
+        messageHash: hash,
+        relayExecutionInfo: {
+          updatedRecipient: handler,
+          updatedMessageHash: hash,
+          updatedOutputAmount: new BN(999_000),
+          fillType: { slowFill: {} },
+          rootBundleId: 7,
+          escrow: null,
+          memoHash: new Array(32).fill(0),
+          normalizedOutputAmount: new BN(999_000),
+          repaymentTokenAccountCreated: false,
+          emergencyExecution: false,
+          originTxRef: null,
+          messageOnly: false,
+        },
+        tokenDecimals: 9,
+        relayHash: new Array(32).fill(0xcd),
+      },
+    ],
+    requestedV3SlowFill: [
+      "RequestedV3SlowFill",
+      {
+        inputToken: usdc,
+        outputToken: test,
+        inputAmount: new BN(1_000_000),
+        outputAmount: new BN(999_000),
+        originChainId: new BN(42161),
+        depositId: intToU8Array32(42),
+        fillDeadline: 1760400000,
+        exclusivityDeadline: 0,
+        exclusiveRelayer: PublicKey.default,
+        depositor: spoke,
+        recipient: handler,
+        messageHash: hash,
+        relayHash: new Array(32).fill(0xcd),
+        currentTime: 1760396400,
+        secondsUntilFillDeadline: 3600,
+      },
+    ],
+    requestedV3SlowFill2: [
+      "RequestedV3SlowFill2",
+      {
+        inputToken: usdc,
+        outputToken: test,
+        inputAmount: new BN(1_000_000),
+        outputAmount: new BN(999_000),
+        originChainId: new BN(42161),
+        depositId: intToU8Array32(42),
+        fillDeadline: 1760400000,
+        exclusivityDeadline: 0,
+        exclusiveRelayer: PublicKey.default,
+        depositor: spoke,
+        recipient: handler,
+        messageHash: hash,
+        destinationChainId: solanaChainId,
+        requester: handler,
+        relayHash: new Array(32).fill(0xcd),
+        messageOnly: false,
+      },
+    ],
+    executedRelayerRefundRoot: [
+      "ExecutedRelayerRefundRoot",
+      {
+        amountToReturn: new BN(0),
+        chainId: solanaChainId,
+        refundAmounts: [new BN(500), maxU64],
+        rootBundleId: 7,
+        leafId: 3,
+        l2TokenAddress: usdc,
+        refundAddresses: [spoke, handler],
+        deferredRefunds: true,
+        caller: test,
+        tokenDecimals: 6,
+      },
+    ],
+    executedRelayerRefundRootChunk: [
+      "ExecutedRelayerRefundRootChunk",
+      {
+        amountToReturn: new BN(0),
+        chainId: solanaChainId,
+        refundAmounts: [maxU64],
+        rootBundleId: 7,
+        leafId: 3,
+        l2TokenAddress: usdc,
+        refundAddresses: [handler],
+        deferredRefunds: true,
+        caller: test,
+        tokenDecimals: 6,
+        chunkIndex: 1,
+        totalChunks: 2,
+      },
+    ],
+  };
+
+  it("Covers every fixture", () => {
+    assert.sameMembers(Object.keys(values), Object.keys(fixture), "Every fixture should have a value");
+  });
+
+  Object.entries(values).forEach(([key, [typeName, value]]) => {
+    it(`Matches the canonical JSON fixture of ${typeName}`, () => {
+      // Round trip through the program coder so that the value has the exact shape of decoded program data.
+      const decoded = program.coder.types.decode(typeName, program.coder.types.encode(typeName, value));
+      assert.deepEqual(toCanonicalJson(decoded), fixture[key], `${typeName} JSON should match the fixture`);
+    });
+
+    it(`Matches the Borsh schema fixture of ${typeName} with the IDL`, () => {
+      // Only event fixtures start with a discriminator known to the IDL.
+      const expected = Buffer.from(schemaFixture[key].slice(2), "hex");
+      const event = program.coder.events.decode(expected.toString("base64"));
+      const data = event ? expected.subarray(8) : expected;
+      assert.equal(program.coder.types.encode(typeName, value).toString("hex"), data.toString("hex"), "Layout changed");
+
+      if (event) {
+        assert.equal(event.name, key, `${typeName} discriminator should match the IDL`);
+        assert.deepEqual(toCanonicalJson(event.data), fixture[key], `${typeName} should decode to its value`);
+      }
+    });
+  });
+});
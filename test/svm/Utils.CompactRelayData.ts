@@ -0,0 +1,113 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import { ethers } from "ethers";
+import { Test } from "../../target/types/test";
+import {
+  calculateRelayHashUint8Array,
+  encodeCompactRelayData,
+  evmAddressToPublicKey,
+  intToU8Array32,
+} from "../../src/svm";
+import { RelayData } from "../../src/types/svm";
+
+describe("utils.compact_relay_data", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Test as Program<Test>;
+  const chainId = new BN(1234567);
+
+  // Size of the borsh serialized V3RelayData with an empty message.
+  const fullRelayDataSize = 6 * 32 + 3 * 8 + 2 * 4 + 4;
+
+  const randomEvmAddress = (): PublicKey => evmAddressToPublicKey(ethers.Wallet.createRandom().address);
+
+  const randomAddress = (): PublicKey => {
+    const choice = Math.floor(Math.random() * 3);
+    if (choice === 0) return PublicKey.default;
+    if (choice === 1) return randomEvmAddress();
+    return Keypair.generate().publicKey;
+  };
+
+  const randomDepositId = (): number[] => {
+    const choice = Math.floor(Math.random() * 3);
+    if (choice === 0) return intToU8Array32(0);
+    if (choice === 1) return intToU8Array32(Math.floor(Math.random() * 1_000_000));
+    return Array.from(ethers.utils.randomBytes(32));
+  };
+
+  const randomRelayData = (): RelayData => ({
+    depositor: randomAddress(),
+    recipient: randomAddress(),
+    exclusiveRelayer: randomAddress(),
+    inputToken: randomAddress(),
+    outputToken: randomAddress(),
+    inputAmount: new BN(ethers.utils.randomBytes(8)),
+    outputAmount: new BN(ethers.utils.randomBytes(8)),
+    originChainId: new BN(Math.floor(Math.random() * 1_000_000)),
+    depositId: randomDepositId(),
+    fillDeadline: Math.floor(Math.random() * 2 ** 32),
+    exclusivityDeadline: Math.floor(Math.random() * 2 ** 32),
+    message: Buffer.from(ethers.utils.randomBytes(Math.floor(Math.random() * 64))),
+  });
+
+  const assertCompactRelayHash = async (relayData: RelayData) => {
+    const relayHash = await program.methods.testCompactRelayHash(encodeCompactRelayData(relayData), chainId).view();
+    assert.deepEqual(
+      Array.from(relayHash),
+      Array.from(calculateRelayHashUint8Array(relayData, chainId)),
+      "Relay hash of the reconstructed relay data should match"
+    );
+  };
+
+  it("Reconstructs the canonical relay hash from randomized compact relay data", async () => {
+    for (let i = 0; i < 20; i++) {
+      await assertCompactRelayHash(randomRelayData());
+    }
+  });
+
+  it("Saves bytes on EVM originated relay data", async () => {
+    const relayData: RelayData = {
+      depositor: randomEvmAddress(),
+      recipient: Keypair.generate().publicKey,
+      exclusiveRelayer: PublicKey.default,
+      inputToken: randomEvmAddress(),
+      outputToken: Keypair.generate().publicKey,
+      inputAmount: new BN(1_000_000),
+      outputAmount: new BN(999_000),
+      originChainId: new BN(1),
+      depositId: intToU8Array32(42),
+      fillDeadline: Math.floor(Date.now() / 1000) + 60,
+      exclusivityDeadline: 0,
+      message: Buffer.alloc(0),
+    };
+    await assertCompactRelayHash(relayData);
+
+    // Header adds 3 bytes, EVM addresses and the deposit id take 20 bytes and the default exclusive relayer is omitted.
+    const compactRelayData = encodeCompactRelayData(relayData);
+    assert.strictEqual(compactRelayData.length, fullRelayDataSize + 3 - 3 * 12 - 32, "Compact size should match");
+    assert.isBelow(compactRelayData.length, fullRelayDataSize, "Compact relay data should save bytes");
+  });
+
+  it("Rejects malformed compact relay data", async () => {
+    const compactRelayData = encodeCompactRelayData(randomRelayData());
+
+    const unknownVersion = Buffer.from(compactRelayData);
+    unknownVersion[0] = 2;
+    const unusedBitmapBits = Buffer.from(compactRelayData);
+    unusedBitmapBits[2] |= 0x80;
+    const trailingBytes = Buffer.concat([compactRelayData, Buffer.from([0])]);
+    const truncated = compactRelayData.subarray(0, compactRelayData.length - 1);
+
+    for (const malformed of [unknownVersion, unusedBitmapBits, trailingBytes, truncated]) {
+      try {
+        await program.methods.testCompactRelayHash(malformed, chainId).rpc();
+        assert.fail("Decoding should have failed for malformed compact relay data");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidCompactRelayData", "Expected InvalidCompactRelayData error");
+      }
+    }
+  });
+});
@@ -0,0 +1,60 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+import { SvmSpoke } from "../../target/types/svm_spoke";
+import { calculateRelayHashUint8Array, slowFillHashFn } from "../../src/svm";
+import { RelayData } from "../../src/types/svm";
+
+// The same fixture is checked against the program hashing by the svm-spoke crate (programs/svm-spoke/tests/hashing.rs)
+// and the get_v3_relay_hash view, so that off-chain slow relay roots and relay hashes stay in sync with the program.
+// Its hashes are generated independently of both by fixtures/generateHashes.js.
+const fixture = JSON.parse(fs.readFileSync(path.resolve(__dirname, "fixtures/hashes.json"), "utf8"));
+
+const parseRelayData = (json: any): RelayData => ({
+  depositor: new PublicKey(json.depositor),
+  recipient: new PublicKey(json.recipient),
+  exclusiveRelayer: new PublicKey(json.exclusiveRelayer),
+  inputToken: new PublicKey(json.inputToken),
+  outputToken: new PublicKey(json.outputToken),
+  inputAmount: new BN(json.inputAmount),
+  outputAmount: new BN(json.outputAmount),
+  originChainId: new BN(json.originChainId),
+  depositId: Array.from(Buffer.from(json.depositId.slice(2), "hex")),
+  fillDeadline: json.fillDeadline,
+  exclusivityDeadline: json.exclusivityDeadline,
+  message: Buffer.from(json.message.slice(2), "hex"),
+});
+
+describe("utils.hashing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SvmSpoke as Program<SvmSpoke>;
+
+  fixture.relayHashes.forEach((relayHash: any, index: number) => {
+    it(`Matches the known relay hash ${index}`, () => {
+      const hash = calculateRelayHashUint8Array(parseRelayData(relayHash.relayData), new BN(relayHash.chainId));
+      assert.equal("0x" + Buffer.from(hash).toString("hex"), relayHash.relayHash, "Relay hash should match");
+    });
+
+    it(`Matches the known relay hash ${index} with the program`, async () => {
+      const relayData = parseRelayData(relayHash.relayData);
+      const hash = await program.methods.getV3RelayHash(relayData, new BN(relayHash.chainId)).view();
+      assert.equal("0x" + Buffer.from(hash).toString("hex"), relayHash.relayHash, "Program relay hash should match");
+    });
+  });
+
+  fixture.slowFillLeaves.forEach((slowFillLeaf: any, index: number) => {
+    it(`Matches the known slow fill leaf hash ${index}`, () => {
+      const leaf = {
+        relayData: parseRelayData(slowFillLeaf.leaf.relayData),
+        chainId: new BN(slowFillLeaf.leaf.chainId),
+        updatedOutputAmount: new BN(slowFillLeaf.leaf.updatedOutputAmount),
+      };
+      assert.equal(slowFillHashFn(leaf), slowFillLeaf.leafHash, "Slow fill leaf hash should match");
+    });
+  });
+});
@@ -5,9 +5,15 @@ import { assert } from "chai";
 import { MerkleTree } from "@uma/common/dist/MerkleTree";
 import { ethers } from "ethers";
 import { BigNumberish } from "ethers";
+import * as fs from "fs";
+import * as path from "path";
 import { common } from "./SvmSpoke.common";
 const { assertSE } = common;
 
+// The same fixture is checked against the program verification by the svm-spoke crate
+// (programs/svm-spoke/tests/merkle.rs).
+const fixture = JSON.parse(fs.readFileSync(path.resolve(__dirname, "fixtures/merkle.json"), "utf8"));
+
 function randomAddress(): string {
   const wallet = ethers.Wallet.createRandom();
   return wallet.address;
@@ -135,4 +141,42 @@ describe("utils.merkle", () => {
       )
       .rpc();
   });
+
+  it("Matches the sorted pair hashing of the EVM fixture proofs", async () => {
+    // Mirrors processProof of the OpenZeppelin MerkleProof library used by the HubPool, hex strings of equal length
+    // compare in the same order as the bytes they encode.
+    const hashPair = (a: string, b: string) => ethers.utils.keccak256(ethers.utils.concat(a < b ? [a, b] : [b, a]));
+    const processProof = (leaf: string, proof: string[]) => proof.reduce(hashPair, leaf);
+    for (const { leaf, proof, root } of [...fixture.validProofs, ...fixture.tooLongProofs]) {
+      assertSE(processProof(leaf, proof), root, "Fixture root should match EVM hashing");
+    }
+    for (const { description, leaf, proof, root } of fixture.invalidProofs) {
+      assert.notEqual(processProof(leaf, proof), root, `${description} should not match the root`);
+    }
+  });
+
+  it("Measures compute units of fixture proofs by depth", async () => {
+    const toArray = (hex: string) => Array.from(Buffer.from(hex.slice(2), "hex"));
+    const verifyComputeUnits = async (depth: number) => {
+      const { leaf, proof, root } = fixture.validProofs.find((proofCase: any) => proofCase.proof.length === depth);
+      const tx = await program.methods.verify(toArray(root), toArray(leaf), proof.map(toArray)).rpc();
+      const txResult = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      if (txResult === null || txResult.meta === null) throw new Error("Transaction meta not confirmed");
+      if (txResult.meta.computeUnitsConsumed === undefined) throw new Error("Compute units not found");
+      return txResult.meta.computeUnitsConsumed;
+    };
+
+    // Proofs of the maximum depth of 32 exceed the transaction size as instruction data, so their cost is extrapolated
+    // from the cost per level. Depth 32 and the rejection of deeper proofs are covered by the svm-spoke crate tests.
+    const depth1 = await verifyComputeUnits(1);
+    const depth16 = await verifyComputeUnits(16);
+    const perLevel = (depth16 - depth1) / 15;
+    const depth32 = depth16 + perLevel * 16;
+    console.log(`Merkle proof compute units: depth 1 ${depth1}, depth 16 ${depth16}, depth 32 ~${Math.ceil(depth32)}`);
+    assert.isAbove(perLevel, 0, "Each proof level should cost compute units");
+    assert.isBelow(depth32, 200_000, "Maximum depth proof should fit the default compute budget");
+  });
 });
@@ -0,0 +1,52 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { Test } from "../../target/types/test";
+
+describe("utils.normalize_output_amount", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Test as Program<Test>;
+
+  const normalize = async (amount: BN, decimalShift: number): Promise<BN> =>
+    await program.methods.testNormalizeOutputAmount(amount, decimalShift).view();
+
+  it("Keeps the amount unchanged for zero shift", async () => {
+    const amount = new BN("18446744073709551615"); // u64::MAX
+    assert.strictEqual((await normalize(amount, 0)).toString(), amount.toString(), "Amount should not change");
+  });
+
+  it("Scales the amount up for positive shift", async () => {
+    const normalized = await normalize(new BN(1_000_000), 3);
+    assert.strictEqual(normalized.toString(), "1000000000", "Amount should be scaled up");
+    const maxShift = await normalize(new BN(1), 18);
+    assert.strictEqual(maxShift.toString(), "1000000000000000000", "Amount should be scaled up by max shift");
+  });
+
+  it("Scales the amount down rounding down for negative shift", async () => {
+    // 1.234567890123456789 WETH with 18 decimals to 8 decimals.
+    const normalized = await normalize(new BN("1234567890123456789"), -10);
+    assert.strictEqual(normalized.toString(), "123456789", "Amount should be scaled down and rounded down");
+    const dust = await normalize(new BN(999), -3);
+    assert.strictEqual(dust.toString(), "0", "Dust should be rounded down to zero");
+  });
+
+  it("Rejects overflowing and out of range shifts", async () => {
+    try {
+      await program.methods.testNormalizeOutputAmount(new BN("18446744073709551615"), 1).rpc();
+      assert.fail("Scaling up above u64 should fail");
+    } catch (err: any) {
+      assert.include(err.toString(), "ArithmeticOverflow", "Expected ArithmeticOverflow error");
+    }
+
+    for (const decimalShift of [19, -19]) {
+      try {
+        await program.methods.testNormalizeOutputAmount(new BN(1), decimalShift).rpc();
+        assert.fail("Shift above the max should fail");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidDecimalShift", "Expected InvalidDecimalShift error");
+      }
+    }
+  });
+});
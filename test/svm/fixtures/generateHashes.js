@@ -0,0 +1,144 @@
+// Regenerates the relayHash and leafHash values of hashes.json from their relay data, independently of both the
+// svm-spoke program and the TypeScript SDK under test: keccak256, base58 and the encoding are implemented here from
+// their specifications with no dependencies. Run with `node test/svm/fixtures/generateHashes.js [--check]`.
+const fs = require("fs");
+const path = require("path");
+
+const FIXTURE_PATH = path.resolve(__dirname, "hashes.json");
+
+const MASK_64 = (1n << 64n) - 1n;
+
+// Keccak-f[1600] round constants and rotation offsets, indexed by x + 5 * y.
+const ROUND_CONSTANTS = [
+  0x0000000000000001n, 0x0000000000008082n, 0x800000000000808an, 0x8000000080008000n, 0x000000000000808bn,
+  0x0000000080000001n, 0x8000000080008081n, 0x8000000000008009n, 0x000000000000008an, 0x0000000000000088n,
+  0x0000000080008009n, 0x000000008000000an, 0x000000008000808bn, 0x800000000000008bn, 0x8000000000008089n,
+  0x8000000000008003n, 0x8000000000008002n, 0x8000000000000080n, 0x000000000000800an, 0x800000008000000an,
+  0x8000000080008081n, 0x8000000000008080n, 0x0000000080000001n, 0x8000000080008008n,
+];
+const ROTATIONS = [0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14];
+
+const rotl = (value, shift) =>
+  shift === 0 ? value : ((value << BigInt(shift)) | (value >> BigInt(64 - shift))) & MASK_64;
+
+function keccakF(state) {
+  for (const roundConstant of ROUND_CONSTANTS) {
+    const c = [0, 1, 2, 3, 4].map((x) => state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20]);
+    for (let x = 0; x < 5; x++) {
+      const d = c[(x + 4) % 5] ^ rotl(c[(x + 1) % 5], 1);
+      for (let y = 0; y < 25; y += 5) state[x + y] ^= d;
+    }
+    const b = new Array(25);
+    for (let x = 0; x < 5; x++) {
+      for (let y = 0; y < 5; y++) b[y + 5 * ((2 * x + 3 * y) % 5)] = rotl(state[x + 5 * y], ROTATIONS[x + 5 * y]);
+    }
+    for (let i = 0; i < 25; i++) {
+      const x = i % 5;
+      state[i] = b[i] ^ (~b[i - x + ((x + 1) % 5)] & MASK_64 & b[i - x + ((x + 2) % 5)]);
+    }
+    state[0] ^= roundConstant;
+  }
+}
+
+// Original Keccak padding (0x01), as used by Ethereum and solana_program::keccak, rather than SHA3 (0x06).
+function keccak256(data) {
+  const rate = 136;
+  const padded = Buffer.alloc((Math.floor(data.length / rate) + 1) * rate);
+  data.copy(padded);
+  padded[data.length] ^= 0x01;
+  padded[padded.length - 1] ^= 0x80;
+  const state = new Array(25).fill(0n);
+  for (let offset = 0; offset < padded.length; offset += rate) {
+    for (let i = 0; i < rate / 8; i++) state[i] ^= padded.readBigUInt64LE(offset + 8 * i);
+    keccakF(state);
+  }
+  const hash = Buffer.alloc(32);
+  for (let i = 0; i < 4; i++) hash.writeBigUInt64LE(state[i], 8 * i);
+  return hash;
+}
+
+const BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+function decodePubkey(base58) {
+  let value = 0n;
+  for (const char of base58) value = value * 58n + BigInt(BASE58_ALPHABET.indexOf(char));
+  const bytes = Buffer.from(value.toString(16).padStart(64, "0"), "hex");
+  if (bytes.length !== 32) throw new Error(`Invalid public key ${base58}`);
+  return bytes;
+}
+
+const u32 = (value) => {
+  const bytes = Buffer.alloc(4);
+  bytes.writeUInt32LE(Number(value));
+  return bytes;
+};
+const u64 = (value) => {
+  const bytes = Buffer.alloc(8);
+  bytes.writeBigUInt64LE(BigInt(value));
+  return bytes;
+};
+const hex = (value) => Buffer.from(value.slice(2), "hex");
+
+// Borsh layout of V3RelayData up to and including exclusivityDeadline.
+const encodeRelayDataFields = (relayData) =>
+  Buffer.concat([
+    decodePubkey(relayData.depositor),
+    decodePubkey(relayData.recipient),
+    decodePubkey(relayData.exclusiveRelayer),
+    decodePubkey(relayData.inputToken),
+    decodePubkey(relayData.outputToken),
+    u64(relayData.inputAmount),
+    u64(relayData.outputAmount),
+    u64(relayData.originChainId),
+    hex(relayData.depositId),
+    u32(relayData.fillDeadline),
+    u32(relayData.exclusivityDeadline),
+  ]);
+
+// Relay data fields with the message replaced by its hash (zeroed when empty), followed by the destination chain id.
+function relayHash({ relayData, chainId }) {
+  const message = hex(relayData.message);
+  const messageHash = message.length > 0 ? keccak256(message) : Buffer.alloc(32);
+  return keccak256(Buffer.concat([encodeRelayDataFields(relayData), messageHash, u64(chainId)]));
+}
+
+// 64 zero bytes, so that leaves cannot be replayed across EVM and SVM, followed by the borsh serialized V3SlowFill.
+function slowFillLeafHash({ relayData, chainId, updatedOutputAmount }) {
+  const message = hex(relayData.message);
+  return keccak256(
+    Buffer.concat([
+      Buffer.alloc(64),
+      encodeRelayDataFields(relayData),
+      u32(message.length),
+      message,
+      u64(chainId),
+      u64(updatedOutputAmount),
+    ])
+  );
+}
+
+const toHex = (bytes) => "0x" + bytes.toString("hex");
+
+if (toHex(keccak256(Buffer.from("abc"))) !== "0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45") {
+  throw new Error("keccak256 does not match the known test vector");
+}
+
+const fixture = JSON.parse(fs.readFileSync(FIXTURE_PATH, "utf8"));
+const generated = {
+  relayHashes: fixture.relayHashes.map((relayHashCase) => ({
+    ...relayHashCase,
+    relayHash: toHex(relayHash(relayHashCase)),
+  })),
+  slowFillLeaves: fixture.slowFillLeaves.map((slowFillLeaf) => ({
+    ...slowFillLeaf,
+    leafHash: toHex(slowFillLeafHash(slowFillLeaf.leaf)),
+  })),
+};
+const output = JSON.stringify(generated, null, 2) + "\n";
+
+if (process.argv.includes("--check")) {
+  if (output !== fs.readFileSync(FIXTURE_PATH, "utf8")) throw new Error("hashes.json is out of date");
+  console.log("hashes.json is up to date");
+} else {
+  fs.writeFileSync(FIXTURE_PATH, output);
+}